@@ -0,0 +1,33 @@
+//! Script de build: expõe o commit e o target para `runner version`.
+//!
+//! - `RUNNER_GIT_SHA`: commit curto (`git rev-parse --short HEAD`), ou o
+//!   valor da variável de ambiente de mesmo nome (builds fora do git).
+//! - `RUNNER_TARGET`: target triple do binário (ex: `x86_64-unknown-linux-gnu`).
+
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=RUNNER_GIT_SHA");
+    println!("cargo:rerun-if-changed=../.git/HEAD");
+    println!("cargo:rerun-if-changed=../.git/refs");
+
+    let git_sha = std::env::var("RUNNER_GIT_SHA")
+        .ok()
+        .filter(|sha| !sha.is_empty())
+        .or_else(|| {
+            Command::new("git")
+                .args(["rev-parse", "--short", "HEAD"])
+                .output()
+                .ok()
+                .filter(|out| out.status.success())
+                .and_then(|out| String::from_utf8(out.stdout).ok())
+                .map(|sha| sha.trim().to_string())
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+
+    println!("cargo:rustc-env=RUNNER_GIT_SHA={}", git_sha);
+    println!(
+        "cargo:rustc-env=RUNNER_TARGET={}",
+        std::env::var("TARGET").unwrap_or_default()
+    );
+}
@@ -0,0 +1,145 @@
+//! # Módulo de Atores - Cenários Multiusuário
+//!
+//! Dá a cada ator de `config.actors` (ex: `admin`, `customer`) as próprias
+//! variáveis, para que os steps de um ator não enxerguem nem sobrescrevam
+//! o estado de autenticação dos outros.
+//!
+//! ## Para todos entenderem:
+//!
+//! Os dois atores declaram `token`. Sem escopo, o login do cliente
+//! sobrescreveria o token do admin. Com atores:
+//!
+//! | Momento                          | `${token}`           | Guardado em          |
+//! |----------------------------------|----------------------|----------------------|
+//! | Step com `"actor": "admin"`      | token do admin       | `admin.token`        |
+//! | Step com `"actor": "customer"`   | token do cliente     | `customer.token`     |
+//! | Step sem ator                    | variável global      | -                    |
+//!
+//! ## Como funciona:
+//!
+//! 1. `register` copia as variáveis de cada ator para `<ator>.<nome>`.
+//! 2. `enter` (antes do step) põe os valores do ator nos nomes curtos,
+//!    guardando os globais que eles escondem.
+//! 3. `leave` (depois do step) devolve os nomes curtos para `<ator>.<nome>`
+//!    (incluindo extrações) e restaura os globais.
+//!
+//! Cookies, conexões e token renovado ficam na sessão HTTP do ator
+//! (`executors::http_session`).
+
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::context::Context;
+use crate::protocol::ActorConfig;
+
+/// Estado salvo por `enter` para ser desfeito em `leave`.
+pub struct ActorScope {
+    actor: String,
+    /// Valores globais escondidos pelas variáveis do ator.
+    shadowed: Vec<(String, Option<Value>)>,
+}
+
+/// Carrega as variáveis iniciais de cada ator em `<ator>.<nome>`.
+pub fn register(context: &mut Context, actors: &HashMap<String, ActorConfig>) {
+    for (actor, config) in actors {
+        let mut names: Vec<String> = config.variables.keys().cloned().collect();
+        names.sort();
+        for (name, value) in &config.variables {
+            context
+                .variables
+                .insert(format!("{}.{}", actor, name), value.clone());
+        }
+        context.actor_variables.insert(actor.clone(), names);
+    }
+}
+
+/// Aplica as variáveis do ator antes de um step.
+pub fn enter(context: &mut Context, actor: &str) -> ActorScope {
+    let names = context
+        .actor_variables
+        .get(actor)
+        .cloned()
+        .unwrap_or_default();
+
+    let mut shadowed = Vec::with_capacity(names.len());
+    for name in names {
+        let value = context
+            .variables
+            .get(&format!("{}.{}", actor, name))
+            .cloned()
+            .unwrap_or(Value::Null);
+        let previous = context.variables.insert(name.clone(), value);
+        shadowed.push((name, previous));
+    }
+
+    ActorScope {
+        actor: actor.to_string(),
+        shadowed,
+    }
+}
+
+/// Guarda o estado do ator e restaura as variáveis globais após o step.
+pub fn leave(context: &mut Context, scope: ActorScope) {
+    for (name, previous) in scope.shadowed {
+        let current = context.variables.remove(&name).unwrap_or(Value::Null);
+        context
+            .variables
+            .insert(format!("{}.{}", scope.actor, name), current);
+        if let Some(previous) = previous {
+            context.variables.insert(name, previous);
+        }
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn actors() -> HashMap<String, ActorConfig> {
+        serde_json::from_value(json!({
+            "admin": { "variables": { "email": "admin@shop.com", "token": null } },
+            "customer": { "variables": { "email": "ana@mail.com", "token": null } }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_actor_variables_shadow_globals() {
+        let mut context = Context::new();
+        context.set("email", json!("global@shop.com"));
+        register(&mut context, &actors());
+
+        let scope = enter(&mut context, "customer");
+        assert_eq!(context.get("email"), Some(&json!("ana@mail.com")));
+        leave(&mut context, scope);
+
+        assert_eq!(context.get("email"), Some(&json!("global@shop.com")));
+        assert!(context.get("token").is_none());
+    }
+
+    #[test]
+    fn test_extraction_updates_only_own_actor() {
+        let mut context = Context::new();
+        register(&mut context, &actors());
+
+        // Login do cliente extrai `token`.
+        let scope = enter(&mut context, "customer");
+        context.set("token", json!("customer-jwt"));
+        context.set("order_id", json!(42));
+        leave(&mut context, scope);
+
+        assert_eq!(context.get("customer.token"), Some(&json!("customer-jwt")));
+        assert_eq!(context.get("admin.token"), Some(&Value::Null));
+        // Variáveis que o ator não declara continuam globais.
+        assert_eq!(context.get("order_id"), Some(&json!(42)));
+
+        let scope = enter(&mut context, "admin");
+        assert_eq!(context.get("token"), Some(&Value::Null));
+        leave(&mut context, scope);
+    }
+}
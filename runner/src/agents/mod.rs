@@ -0,0 +1,368 @@
+//! # Módulo de Agentes Remotos - Steps em Outras Regiões
+//!
+//! Despacha steps marcados com `agent` para um `runner agent` remoto e
+//! traz o `StepResult` de volta para o relatório local.
+//!
+//! ## Para todos entenderem:
+//!
+//! "A API responde em 80ms" depende de onde se mede. Para checar a
+//! latência vista da Europa e da Ásia no mesmo plano, cada região roda um
+//! agente, e o Runner principal (o coordenador) envia a ele só os steps
+//! daquela região:
+//!
+//! ```json
+//! "config": {
+//!   "agents": {
+//!     "eu-west": { "url": "https://runner-eu.internal:7700", "token": "${env:AGENT_TOKEN}" }
+//!   }
+//! },
+//! "steps": [
+//!   { "id": "latency_eu", "action": "http_request", "agent": "eu-west", ... }
+//! ]
+//! ```
+//!
+//! ```bash
+//! # Na máquina da região:
+//! RUNNER_AGENT_TOKEN=segredo runner agent --listen 0.0.0.0:7700
+//! ```
+//!
+//! ## Protocolo (HTTP + JSON):
+//!
+//! | Lado        | Conteúdo                                                   |
+//! |-------------|------------------------------------------------------------|
+//! | Requisição  | `POST /v1/steps` com `{ "step": ..., "variables": {...} }` |
+//! | Resposta    | `{ "result": StepResult, "variables": {...} }`             |
+//!
+//! O agente executa o step com as variáveis do coordenador e devolve o
+//! contexto resultante: extrações feitas lá valem para os steps seguintes.
+//! Retries, `assertions_retry` e atores continuam sendo aplicados pelo
+//! coordenador; o agente executa uma tentativa por despacho.
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use async_trait::async_trait;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, StatusCode};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use subtle::ConstantTimeEq;
+use tracing::{error, info};
+
+use crate::context::Context;
+use crate::executors::assert::AssertExecutor;
+use crate::executors::graphql::GraphqlExecutor;
+use crate::executors::http::HttpExecutor;
+use crate::executors::log::LogExecutor;
+use crate::executors::set_variable::SetVariableExecutor;
+use crate::executors::transform::TransformExecutor;
+use crate::executors::wait::WaitExecutor;
+use crate::executors::StepExecutor;
+use crate::protocol::{AgentConfig, Step, StepResult, StepStatus};
+
+/// Rota que recebe os steps no agente.
+const STEPS_PATH: &str = "/v1/steps";
+
+/// Variável de ambiente com o token exigido pelo `runner agent`.
+pub const AGENT_TOKEN_ENV: &str = "RUNNER_AGENT_TOKEN";
+
+// ============================================================================
+// MENSAGENS
+// ============================================================================
+
+/// Step enviado do coordenador ao agente.
+#[derive(Debug, Serialize, Deserialize)]
+struct AgentRequest {
+    step: Step,
+    variables: HashMap<String, Value>,
+}
+
+/// Resultado devolvido pelo agente.
+#[derive(Debug, Serialize, Deserialize)]
+struct AgentResponse {
+    result: StepResult,
+    variables: HashMap<String, Value>,
+}
+
+// ============================================================================
+// COORDENADOR: DESPACHO
+// ============================================================================
+
+/// Executor dos steps com `agent`: envia cada um ao agente configurado.
+pub struct RemoteAgentExecutor {
+    agents: HashMap<String, AgentConfig>,
+    client: Client,
+}
+
+impl RemoteAgentExecutor {
+    pub fn new(agents: HashMap<String, AgentConfig>) -> Self {
+        Self {
+            agents,
+            client: Client::new(),
+        }
+    }
+
+    async fn dispatch(
+        &self,
+        agent: &AgentConfig,
+        step: &Step,
+        context: &Context,
+    ) -> Result<AgentResponse> {
+        let url = format!("{}{}", agent.url.trim_end_matches('/'), STEPS_PATH);
+        let mut local_step = step.clone();
+        local_step.agent = None;
+
+        let mut request = self
+            .client
+            .post(&url)
+            .timeout(Duration::from_millis(agent.timeout_ms))
+            .json(&AgentRequest {
+                step: local_step,
+                variables: context.variables.clone(),
+            });
+        if let Some(token) = &agent.token {
+            request = request.bearer_auth(context.interpolate_str(token)?);
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("HTTP {}: {}", status.as_u16(), body.trim()));
+        }
+        response.json().await.context("Resposta do agente inválida")
+    }
+}
+
+#[async_trait]
+impl StepExecutor for RemoteAgentExecutor {
+    fn can_handle(&self, _action: &str) -> bool {
+        false
+    }
+
+    fn can_handle_step(&self, step: &Step) -> bool {
+        step.agent.is_some()
+    }
+
+    async fn execute(&self, step: &Step, context: &mut Context) -> Result<StepResult> {
+        let name = step.agent.as_deref().unwrap_or_default();
+        let agent = self
+            .agents
+            .get(name)
+            .ok_or_else(|| anyhow!("Agente '{}' não existe em config.agents", name))?;
+        let start = Instant::now();
+
+        let mut result = match self.dispatch(agent, step, context).await {
+            Ok(response) => {
+                for (key, value) in response.variables {
+                    if context.get(&key) != Some(&value) {
+                        context.set(key, value);
+                    }
+                }
+                response.result
+            }
+            Err(e) => {
+                error!(step_id = %step.id, agent = %name, error = %format!("{:#}", e), "Agent dispatch failed");
+                StepResult {
+                    step_id: step.id.clone(),
+                    status: StepStatus::Failed,
+                    duration_ms: start.elapsed().as_millis() as u64,
+                    error: Some(format!("Agente '{}' ({}): {:#}", name, agent.url, e)),
+                    ..Default::default()
+                }
+            }
+        };
+        result.agent = Some(name.to_string());
+        Ok(result)
+    }
+}
+
+// ============================================================================
+// AGENTE: `runner agent`
+// ============================================================================
+
+/// Atende `POST /v1/steps` até o processo ser encerrado.
+///
+/// Com `token`, exige `Authorization: Bearer <token>` em cada requisição.
+/// Sem token, só aceita escutar em loopback: um agente aberto na rede
+/// executaria requisições e comandos de qualquer um.
+pub async fn serve(listener: std::net::TcpListener, token: Option<String>) -> Result<()> {
+    listener.set_nonblocking(true)?;
+    let addr = listener.local_addr()?;
+    if token.is_none() && !addr.ip().is_loopback() {
+        bail!(
+            "Agente em {} sem token: defina {} ou escute em 127.0.0.1",
+            addr,
+            AGENT_TOKEN_ENV
+        );
+    }
+    let executors: Arc<Vec<Box<dyn StepExecutor + Send + Sync>>> = Arc::new(vec![
+        Box::new(HttpExecutor::new()),
+        Box::new(WaitExecutor::new()),
+        Box::new(GraphqlExecutor::default()),
+        Box::new(SetVariableExecutor::new()),
+        Box::new(LogExecutor::new()),
+        Box::new(AssertExecutor::new()),
+        Box::new(TransformExecutor::new()),
+    ]);
+    let token = Arc::new(token);
+
+    let make_service = make_service_fn(move |_| {
+        let executors = Arc::clone(&executors);
+        let token = Arc::clone(&token);
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                let executors = Arc::clone(&executors);
+                let token = Arc::clone(&token);
+                async move { Ok::<_, Infallible>(handle(request, &executors, &token).await) }
+            }))
+        }
+    });
+
+    info!(%addr, "Runner agent listening");
+    hyper::Server::from_tcp(listener)?
+        .serve(make_service)
+        .await
+        .context("Agent server failed")
+}
+
+async fn handle(
+    request: Request<Body>,
+    executors: &[Box<dyn StepExecutor + Send + Sync>],
+    token: &Option<String>,
+) -> Response<Body> {
+    if request.method() != Method::POST || request.uri().path() != STEPS_PATH {
+        return reply(StatusCode::NOT_FOUND, "not found");
+    }
+    if let Some(token) = token {
+        let authorized = request
+            .headers()
+            .get(hyper::header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .is_some_and(|v| bool::from(v.as_bytes().ct_eq(token.as_bytes())));
+        if !authorized {
+            return reply(StatusCode::UNAUTHORIZED, "invalid agent token");
+        }
+    }
+
+    let request: AgentRequest = match hyper::body::to_bytes(request.into_body())
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|bytes| serde_json::from_slice(&bytes).map_err(anyhow::Error::from))
+    {
+        Ok(request) => request,
+        Err(e) => return reply(StatusCode::BAD_REQUEST, &e.to_string()),
+    };
+
+    let step = request.step;
+    let mut context = Context::new();
+    context.variables = request.variables;
+    info!(step_id = %step.id, action = %step.action, "Running step for coordinator");
+
+    let result = match executors.iter().find(|e| e.can_handle_step(&step)) {
+        Some(executor) => executor.execute(&step, &mut context).await,
+        None => Err(anyhow!("No executor for action: {}", step.action)),
+    };
+    let result = result.unwrap_or_else(|e| StepResult {
+        step_id: step.id.clone(),
+        status: StepStatus::Failed,
+        error: Some(format!("{:#}", e)),
+        ..Default::default()
+    });
+
+    let body = AgentResponse {
+        result,
+        variables: context.variables,
+    };
+    match serde_json::to_vec(&body) {
+        Ok(json) => Response::builder()
+            .header(hyper::header::CONTENT_TYPE, "application/json")
+            .body(Body::from(json))
+            .expect("valid response"),
+        Err(e) => reply(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+    }
+}
+
+fn reply(status: StatusCode, message: &str) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(Body::from(message.to_string()))
+        .expect("valid response")
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn start_agent(token: Option<&str>) -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(serve(listener, token.map(str::to_string)));
+        url
+    }
+
+    fn coordinator(url: String, token: &str) -> RemoteAgentExecutor {
+        RemoteAgentExecutor::new(HashMap::from([(
+            "eu-west".to_string(),
+            AgentConfig {
+                url,
+                token: Some(token.to_string()),
+                timeout_ms: 5000,
+            },
+        )]))
+    }
+
+    fn remote_step() -> Step {
+        serde_json::from_value(json!({
+            "id": "tag_region", "action": "set_variable", "agent": "eu-west",
+            "params": { "name": "region", "value": "eu-${zone}" }
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_step_runs_on_agent_and_context_comes_back() {
+        let executor = coordinator(start_agent(Some("s3cret")), "s3cret");
+        let mut context = Context::new();
+        context.set("zone", json!("west-1"));
+
+        let step = remote_step();
+        assert!(executor.can_handle_step(&step));
+        let result = executor.execute(&step, &mut context).await.unwrap();
+
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+        assert_eq!(result.agent.as_deref(), Some("eu-west"));
+        assert_eq!(context.get("region"), Some(&json!("eu-west-1")));
+    }
+
+    #[tokio::test]
+    async fn test_wrong_token_fails_step() {
+        let executor = coordinator(start_agent(Some("s3cret")), "wrong");
+        let mut context = Context::new();
+        context.set("zone", json!("west-1"));
+
+        let result = executor
+            .execute(&remote_step(), &mut context)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Failed);
+        assert!(result.error.unwrap().contains("HTTP 401"));
+        assert_eq!(result.agent.as_deref(), Some("eu-west"));
+    }
+
+    #[tokio::test]
+    async fn test_refuses_open_bind_without_token() {
+        let listener = std::net::TcpListener::bind("0.0.0.0:0").unwrap();
+        let error = serve(listener, None).await.unwrap_err().to_string();
+        assert!(error.contains(AGENT_TOKEN_ENV), "{}", error);
+    }
+}
@@ -0,0 +1,499 @@
+//! # Módulo de Autenticação - Login OIDC Gerenciado pelo Runner
+//!
+//! Faz o login OpenID Connect de `config.auth.oidc` e mantém o token válido
+//! durante a execução, expondo-o aos steps como `${auth.access_token}`.
+//!
+//! ## Para todos entenderem:
+//!
+//! Sem este módulo, o plano precisa de um step de login com o endpoint de
+//! token "chumbado" e não tem como renovar o token em execuções longas:
+//!
+//! | Etapa      | O que o Runner faz                                         |
+//! |------------|------------------------------------------------------------|
+//! | Discovery  | Lê `token_endpoint` em `/.well-known/openid-configuration` |
+//! | Login      | Fluxo `password` ou `device_code` (com PKCE opcional)      |
+//! | Renovação  | `refresh_token` antes de expirar (ou novo login)           |
+//!
+//! ## Variáveis no contexto:
+//!
+//! | Variável             | Conteúdo                            |
+//! |----------------------|-------------------------------------|
+//! | `auth.access_token`  | Token de acesso                     |
+//! | `auth.id_token`      | ID token (se o IdP devolver)        |
+//! | `auth.token_type`    | Normalmente `Bearer`                |
+//! | `auth.expires_at`    | Expiração em RFC 3339 (se houver)   |
+//!
+//! ## Device flow:
+//!
+//! O Runner registra no log a URL de verificação e o código; alguém conclui
+//! o login no navegador e o Runner consulta o IdP até receber o token.
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, OnceCell};
+
+use crate::context::Context;
+use crate::protocol::{OidcConfig, OidcFlow};
+
+/// Grant type do device flow (RFC 8628).
+const DEVICE_CODE_GRANT: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+// ============================================================================
+// ESTRUTURAS
+// ============================================================================
+
+/// Endpoints lidos no discovery.
+#[derive(Debug, Deserialize)]
+struct Discovery {
+    token_endpoint: String,
+    #[serde(default)]
+    device_authorization_endpoint: Option<String>,
+}
+
+/// Resposta do endpoint de token.
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    id_token: Option<String>,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default = "default_token_type")]
+    token_type: String,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+fn default_token_type() -> String {
+    "Bearer".to_string()
+}
+
+/// Resposta do endpoint de device authorization.
+#[derive(Debug, Deserialize)]
+struct DeviceAuthorization {
+    device_code: String,
+    user_code: String,
+    verification_uri: String,
+    #[serde(default)]
+    verification_uri_complete: Option<String>,
+    #[serde(default = "default_device_expires_in")]
+    expires_in: u64,
+    #[serde(default = "default_device_interval")]
+    interval: u64,
+}
+
+fn default_device_expires_in() -> u64 {
+    600
+}
+
+fn default_device_interval() -> u64 {
+    5
+}
+
+/// Tokens atuais e quando expiram.
+struct TokenSet {
+    response: TokenResponse,
+    expires_at: Option<Instant>,
+}
+
+/// Sessão OIDC de uma execução (compartilhada entre os steps).
+pub struct OidcSession {
+    config: OidcConfig,
+    client: Client,
+    discovery: OnceCell<Discovery>,
+    tokens: Mutex<Option<TokenSet>>,
+}
+
+// ============================================================================
+// LOGIN E RENOVAÇÃO
+// ============================================================================
+
+impl OidcSession {
+    pub fn new(config: OidcConfig, client: Client) -> Self {
+        Self {
+            config,
+            client,
+            discovery: OnceCell::new(),
+            tokens: Mutex::new(None),
+        }
+    }
+
+    /// Garante um token válido e publica `auth.*` no contexto.
+    ///
+    /// Faz o login na primeira chamada; depois, renova quando faltar menos
+    /// que `refresh_margin_ms` para expirar (novo login se a renovação falhar).
+    pub async fn ensure_token(&self, context: &mut Context) -> Result<()> {
+        let mut tokens = self.tokens.lock().await;
+        let margin = Duration::from_millis(self.config.refresh_margin_ms);
+        let fresh = tokens.as_ref().is_some_and(|t| {
+            t.expires_at
+                .is_none_or(|at| at.saturating_duration_since(Instant::now()) > margin)
+        });
+
+        if !fresh {
+            let refresh_token = tokens
+                .as_ref()
+                .and_then(|t| t.response.refresh_token.clone());
+            let response = match refresh_token {
+                Some(refresh_token) => match self.refresh(&refresh_token, context).await {
+                    Ok(response) => response,
+                    Err(e) => {
+                        tracing::warn!(error = %format!("{:#}", e), "Renovação OIDC falhou, refazendo o login");
+                        self.login(context).await?
+                    }
+                },
+                None => self.login(context).await?,
+            };
+            *tokens = Some(TokenSet {
+                expires_at: response
+                    .expires_in
+                    .map(|s| Instant::now() + Duration::from_secs(s)),
+                response,
+            });
+        }
+
+        if let Some(tokens) = tokens.as_ref() {
+            publish(context, &tokens.response);
+        }
+        Ok(())
+    }
+
+    async fn discovery(&self) -> Result<&Discovery> {
+        self.discovery
+            .get_or_try_init(|| async {
+                let url = format!(
+                    "{}/.well-known/openid-configuration",
+                    self.config.issuer.trim_end_matches('/')
+                );
+                self.client
+                    .get(&url)
+                    .send()
+                    .await
+                    .and_then(|r| r.error_for_status())
+                    .with_context(|| format!("Falha no discovery OIDC em {}", url))?
+                    .json::<Discovery>()
+                    .await
+                    .with_context(|| format!("Discovery OIDC inválido em {}", url))
+            })
+            .await
+    }
+
+    async fn login(&self, context: &Context) -> Result<TokenResponse> {
+        match self.config.flow {
+            OidcFlow::Password => {
+                let field = |value: &Option<String>, name: &str| -> Result<String> {
+                    let value = value.as_deref().ok_or_else(|| {
+                        anyhow!("auth.oidc: '{}' é obrigatório no fluxo password", name)
+                    })?;
+                    context.interpolate_str(value)
+                };
+                let form = vec![
+                    ("grant_type", "password".to_string()),
+                    ("username", field(&self.config.username, "username")?),
+                    ("password", field(&self.config.password, "password")?),
+                    ("scope", self.config.scope.clone()),
+                ];
+                let token = self.token_request(form, context).await?;
+                tracing::info!(issuer = %self.config.issuer, "Login OIDC concluído (password)");
+                Ok(token)
+            }
+            OidcFlow::DeviceCode => self.device_login(context).await,
+        }
+    }
+
+    async fn device_login(&self, context: &Context) -> Result<TokenResponse> {
+        let endpoint = self
+            .discovery()
+            .await?
+            .device_authorization_endpoint
+            .clone()
+            .ok_or_else(|| anyhow!("O IdP não anuncia device_authorization_endpoint"))?;
+
+        let verifier = self.config.pkce.then(pkce_verifier);
+        let mut form = self.client_form(context)?;
+        form.push(("scope", self.config.scope.clone()));
+        if let Some(verifier) = &verifier {
+            form.push(("code_challenge", pkce_challenge(verifier)));
+            form.push(("code_challenge_method", "S256".to_string()));
+        }
+        let device: DeviceAuthorization = self
+            .client
+            .post(&endpoint)
+            .form(&form)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .context("Falha ao iniciar o device flow OIDC")?
+            .json()
+            .await
+            .context("Resposta de device authorization inválida")?;
+
+        tracing::warn!(
+            url = %device.verification_uri_complete.as_deref().unwrap_or(&device.verification_uri),
+            code = %device.user_code,
+            "Login OIDC pendente: abra a URL e informe o código"
+        );
+
+        let deadline = Instant::now() + Duration::from_secs(device.expires_in);
+        let mut interval = Duration::from_secs(device.interval);
+        loop {
+            if Instant::now() >= deadline {
+                bail!("Device flow OIDC expirou sem login");
+            }
+            tokio::time::sleep(interval).await;
+
+            let mut form = vec![
+                ("grant_type", DEVICE_CODE_GRANT.to_string()),
+                ("device_code", device.device_code.clone()),
+            ];
+            if let Some(verifier) = &verifier {
+                form.push(("code_verifier", verifier.clone()));
+            }
+            match self.token_request(form, context).await {
+                Ok(token) => {
+                    tracing::info!(issuer = %self.config.issuer, "Login OIDC concluído (device_code)");
+                    return Ok(token);
+                }
+                Err(e) => match e.downcast_ref::<OAuthError>().map(|e| e.0.as_str()) {
+                    Some("authorization_pending") => {}
+                    Some("slow_down") => interval += Duration::from_secs(5),
+                    _ => return Err(e),
+                },
+            }
+        }
+    }
+
+    async fn refresh(&self, refresh_token: &str, context: &Context) -> Result<TokenResponse> {
+        let form = vec![
+            ("grant_type", "refresh_token".to_string()),
+            ("refresh_token", refresh_token.to_string()),
+        ];
+        let mut token = self.token_request(form, context).await?;
+        // IdPs que não rotacionam o refresh token não o devolvem de novo.
+        token
+            .refresh_token
+            .get_or_insert_with(|| refresh_token.to_string());
+        tracing::info!(issuer = %self.config.issuer, "Token OIDC renovado");
+        Ok(token)
+    }
+
+    /// `client_id` e, se houver, `client_secret` (client_secret_post).
+    fn client_form(&self, context: &Context) -> Result<Vec<(&'static str, String)>> {
+        let mut form = vec![("client_id", self.config.client_id.clone())];
+        if let Some(secret) = &self.config.client_secret {
+            form.push(("client_secret", context.interpolate_str(secret)?));
+        }
+        Ok(form)
+    }
+
+    async fn token_request(
+        &self,
+        mut form: Vec<(&'static str, String)>,
+        context: &Context,
+    ) -> Result<TokenResponse> {
+        let endpoint = self.discovery().await?.token_endpoint.clone();
+        form.extend(self.client_form(context)?);
+
+        let response = self
+            .client
+            .post(&endpoint)
+            .form(&form)
+            .send()
+            .await
+            .with_context(|| format!("Falha ao chamar o token endpoint {}", endpoint))?;
+        let status = response.status();
+        let body: Value = response.json().await.unwrap_or(Value::Null);
+        if !status.is_success() {
+            let error = body
+                .get("error")
+                .and_then(|e| e.as_str())
+                .unwrap_or("unknown_error");
+            return Err(
+                anyhow::Error::new(OAuthError(error.to_string())).context(format!(
+                    "Token endpoint respondeu {} ({})",
+                    status,
+                    body.get("error_description")
+                        .and_then(|d| d.as_str())
+                        .unwrap_or(error)
+                )),
+            );
+        }
+        serde_json::from_value(body).context("Resposta do token endpoint sem access_token")
+    }
+}
+
+/// Código de erro OAuth (`error` na resposta do token endpoint).
+#[derive(Debug)]
+struct OAuthError(String);
+
+impl std::fmt::Display for OAuthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "OAuth error '{}'", self.0)
+    }
+}
+
+impl std::error::Error for OAuthError {}
+
+/// Publica os tokens em `auth.*`.
+fn publish(context: &mut Context, token: &TokenResponse) {
+    context.set(
+        "auth.access_token",
+        Value::String(token.access_token.clone()),
+    );
+    context.set("auth.token_type", Value::String(token.token_type.clone()));
+    if let Some(id_token) = &token.id_token {
+        context.set("auth.id_token", Value::String(id_token.clone()));
+    }
+    if let Some(expires_in) = token.expires_in {
+        let at = chrono::Utc::now() + chrono::Duration::seconds(expires_in as i64);
+        context.set("auth.expires_at", Value::String(at.to_rfc3339()));
+    }
+}
+
+// ============================================================================
+// PKCE (RFC 7636)
+// ============================================================================
+
+fn pkce_verifier() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(64)
+        .map(char::from)
+        .collect()
+}
+
+fn pkce_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// IdP falso: discovery + token endpoint. Conta as chamadas por grant.
+    async fn fake_idp(expires_in: u64) -> (String, Arc<AtomicUsize>, Arc<AtomicUsize>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let issuer = format!("http://127.0.0.1:{}", listener.local_addr().unwrap().port());
+        let (logins, refreshes) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+        let (l, r, base) = (Arc::clone(&logins), Arc::clone(&refreshes), issuer.clone());
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let request = read_request(&mut socket).await;
+                let body = if request.starts_with("GET /.well-known/openid-configuration") {
+                    json!({ "token_endpoint": format!("{}/token", base) })
+                } else if request.contains("grant_type=password") {
+                    assert!(request.contains("username=ana"), "{}", request);
+                    l.fetch_add(1, Ordering::SeqCst);
+                    json!({ "access_token": "login-token", "refresh_token": "r1", "expires_in": expires_in })
+                } else {
+                    assert!(request.contains("refresh_token=r1"), "{}", request);
+                    r.fetch_add(1, Ordering::SeqCst);
+                    json!({ "access_token": "refreshed-token", "expires_in": 3600 })
+                }
+                .to_string();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        (issuer, logins, refreshes)
+    }
+
+    /// Lê cabeçalhos e body (o body pode chegar em outro pacote).
+    async fn read_request(socket: &mut tokio::net::TcpStream) -> String {
+        let mut received = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let n = socket.read(&mut buf).await.unwrap();
+            received.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&received).to_string();
+            if let Some(end) = text.find("\r\n\r\n") {
+                let length: usize = text
+                    .to_lowercase()
+                    .lines()
+                    .find_map(|l| l.strip_prefix("content-length: ").map(str::to_string))
+                    .map_or(0, |v| v.trim().parse().unwrap());
+                if n == 0 || received.len() - end - 4 >= length {
+                    return text;
+                }
+            }
+        }
+    }
+
+    fn config(issuer: &str) -> OidcConfig {
+        serde_json::from_value(json!({
+            "issuer": issuer,
+            "client_id": "aqa",
+            "username": "${user}",
+            "password": "secret"
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_password_login_then_reuse() {
+        let (issuer, logins, refreshes) = fake_idp(3600).await;
+        let session = OidcSession::new(config(&issuer), Client::new());
+        let mut context = Context::new();
+        context.set("user", json!("ana"));
+
+        session.ensure_token(&mut context).await.unwrap();
+        session.ensure_token(&mut context).await.unwrap();
+
+        assert_eq!(
+            context.get("auth.access_token"),
+            Some(&json!("login-token"))
+        );
+        assert_eq!(context.get("auth.token_type"), Some(&json!("Bearer")));
+        assert_eq!(logins.load(Ordering::SeqCst), 1);
+        assert_eq!(refreshes.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_expiring_token_is_refreshed() {
+        // expires_in abaixo da margem: a segunda chamada renova.
+        let (issuer, logins, refreshes) = fake_idp(10).await;
+        let session = OidcSession::new(config(&issuer), Client::new());
+        let mut context = Context::new();
+        context.set("user", json!("ana"));
+
+        session.ensure_token(&mut context).await.unwrap();
+        session.ensure_token(&mut context).await.unwrap();
+
+        assert_eq!(
+            context.get("auth.access_token"),
+            Some(&json!("refreshed-token"))
+        );
+        assert_eq!(logins.load(Ordering::SeqCst), 1);
+        assert_eq!(refreshes.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_pkce_challenge_rfc7636_vector() {
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+        assert_eq!(pkce_verifier().len(), 64);
+    }
+}
@@ -0,0 +1,294 @@
+//! # Módulo de Cancelamento - Interrupção Ordenada da Execução
+//!
+//! Um sinal compartilhado entre os steps que, uma vez disparado, faz os
+//! steps que ainda não começaram virarem `cancelled` em vez de rodar.
+//!
+//! ## Para todos entenderem:
+//!
+//! Vários motivos podem interromper um plano no meio: o limite de memória
+//! estourou, o Brain pediu para abortar via `--progress-url`, o prazo
+//! acabou... Todos usam o mesmo sinal, e o primeiro motivo é o que vai
+//! para o relatório:
+//!
+//! | Quem dispara                   | Código | Onde aparece                     |
+//! |--------------------------------|--------|----------------------------------|
+//! | `RUNNER_MAX_MEMORY_MB`         | E5004  | `errors` + steps `cancelled`     |
+//! | `--progress-url` (`abort`)     | E4006  | `errors` + steps `cancelled`     |
+//! | `ExecutionHandle::cancel()`    | E4006  | `errors` + steps `cancelled`     |
+//! | `RUNNER_MAX_EXECUTION_SECS`    | E1012  | `errors` + steps `skipped`       |
+//!
+//! Nos motivos de memória e abort, steps em andamento terminam normalmente e
+//! só os seguintes são cancelados. No prazo esgotado, o step em andamento é
+//! interrompido (`failed` com E1012) e os seguintes ficam `skipped` com
+//! `skip_reason: timed_out`. Se o prazo vence depois do último step, nada foi
+//! cortado e a execução não é reportada como estourada.
+//!
+//! `PauseGate` é o irmão mais brando: enquanto fechado, nenhum step novo
+//! começa; ao reabrir (ou ao cancelar), a execução segue de onde parou.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::errors::ErrorCode;
+use crate::protocol::{ReportError, SkipReason, StepResult, StepStatus};
+
+/// Sinal de parada, compartilhado entre os steps da execução.
+#[derive(Debug)]
+pub struct StopSignal {
+    reason: watch::Sender<Option<(ErrorCode, String)>>,
+    /// Steps cortados ou pulados pelo prazo.
+    timed_out_steps: AtomicUsize,
+}
+
+impl Default for StopSignal {
+    fn default() -> Self {
+        let (reason, _) = watch::channel(None);
+        Self {
+            reason,
+            timed_out_steps: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl StopSignal {
+    /// Sinal ainda não disparado.
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Dispara o sinal. Só o primeiro motivo é mantido (retorna `true` se foi este).
+    pub fn stop(&self, code: ErrorCode, message: impl Into<String>) -> bool {
+        let mut message = Some(message.into());
+        self.reason.send_if_modified(|reason| {
+            if reason.is_some() {
+                return false;
+            }
+            *reason = Some((code, message.take().unwrap_or_default()));
+            true
+        })
+    }
+
+    /// Código e mensagem do motivo da parada, se o sinal foi disparado.
+    pub fn reason(&self) -> Option<(ErrorCode, String)> {
+        self.reason.borrow().clone()
+    }
+
+    /// Dispara o sinal com E1012 quando `limit` passar (prazo da execução).
+    ///
+    /// Aborte a task retornada quando a execução terminar antes do prazo.
+    pub fn deadline(self: &Arc<Self>, limit: Duration) -> JoinHandle<()> {
+        let stop = Arc::clone(self);
+        tokio::spawn(async move {
+            tokio::time::sleep(limit).await;
+            stop.stop(
+                ErrorCode::EXECUTION_TIMEOUT,
+                format!(
+                    "Prazo da execução esgotado ({}s, RUNNER_MAX_EXECUTION_SECS)",
+                    limit.as_secs()
+                ),
+            );
+        })
+    }
+
+    /// `true` se o prazo acabou e cortou (ou pulou) pelo menos um step.
+    pub fn timed_out(&self) -> bool {
+        self.deadline_passed() && self.timed_out_steps.load(Ordering::SeqCst) > 0
+    }
+
+    fn deadline_passed(&self) -> bool {
+        self.reason()
+            .is_some_and(|(code, _)| code == ErrorCode::EXECUTION_TIMEOUT)
+    }
+
+    /// Roda `step` até terminar ou o prazo da execução vencer.
+    ///
+    /// Vencido o prazo, o step é interrompido (o future é descartado, o que
+    /// cancela esperas e requisições em andamento) e o retorno é `None`.
+    /// Os demais motivos de parada não interrompem o step.
+    pub async fn within_deadline<F: Future>(&self, step: F) -> Option<F::Output> {
+        let mut reason = self.reason.subscribe();
+        let deadline = async move {
+            loop {
+                let expired = reason
+                    .borrow_and_update()
+                    .as_ref()
+                    .is_some_and(|(code, _)| *code == ErrorCode::EXECUTION_TIMEOUT);
+                if expired || reason.changed().await.is_err() {
+                    break expired;
+                }
+            }
+        };
+        tokio::select! {
+            output = step => Some(output),
+            true = deadline => None,
+        }
+    }
+
+    /// Resultado de um step interrompido pelo prazo no meio da execução.
+    pub fn interrupted_result(&self, step_id: &str) -> StepResult {
+        self.timed_out_steps.fetch_add(1, Ordering::SeqCst);
+        let message = self
+            .reason()
+            .map(|(_, message)| message)
+            .unwrap_or_default();
+        StepResult {
+            step_id: step_id.to_string(),
+            status: StepStatus::Failed,
+            error: Some(format!(
+                "[{}] {} (step interrompido)",
+                ErrorCode::EXECUTION_TIMEOUT,
+                message
+            )),
+            skip_reason: Some(SkipReason::TimedOut),
+            ..Default::default()
+        }
+    }
+
+    /// Resultado de um step que não vai rodar porque a execução parou.
+    ///
+    /// Prazo esgotado vira `skipped` (`timed_out`); os demais motivos, `cancelled`.
+    pub fn cancelled_result(&self, step_id: &str) -> Option<StepResult> {
+        let (code, message) = self.reason()?;
+        let (status, skip_reason) = if code == ErrorCode::EXECUTION_TIMEOUT {
+            self.timed_out_steps.fetch_add(1, Ordering::SeqCst);
+            (StepStatus::Skipped, SkipReason::TimedOut)
+        } else {
+            (StepStatus::Cancelled, SkipReason::Cancelled)
+        };
+        Some(StepResult {
+            step_id: step_id.to_string(),
+            status,
+            error: Some(format!("[{}] {}", code, message)),
+            skip_reason: Some(skip_reason),
+            ..Default::default()
+        })
+    }
+
+    /// Erro para `ExecutionReport.errors`, se a execução parou.
+    ///
+    /// Um prazo que venceu sem cortar nenhum step não é erro da execução.
+    pub fn report_error(&self) -> Option<ReportError> {
+        if self.deadline_passed() && !self.timed_out() {
+            return None;
+        }
+        self.reason()
+            .map(|(code, message)| ReportError::new(code, message))
+    }
+}
+
+// ============================================================================
+// PAUSA
+// ============================================================================
+
+/// Pausa compartilhada entre os steps (clones apontam para o mesmo estado).
+#[derive(Debug, Clone)]
+pub struct PauseGate {
+    paused: Arc<watch::Sender<bool>>,
+}
+
+impl Default for PauseGate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PauseGate {
+    /// Pausa aberta (steps começam normalmente).
+    pub fn new() -> Self {
+        let (paused, _) = watch::channel(false);
+        Self {
+            paused: Arc::new(paused),
+        }
+    }
+
+    /// Fecha a pausa: steps que ainda não começaram esperam.
+    pub fn pause(&self) {
+        self.paused.send_replace(true);
+    }
+
+    /// Reabre a pausa, liberando os steps que esperavam.
+    pub fn resume(&self) {
+        self.paused.send_replace(false);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        *self.paused.borrow()
+    }
+
+    /// Espera enquanto a pausa estiver fechada e `stop` não tiver disparado.
+    pub async fn wait(&self, stop: &StopSignal) {
+        let mut paused = self.paused.subscribe();
+        while *paused.borrow_and_update() && stop.reason().is_none() {
+            if paused.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_reason_wins() {
+        let signal = StopSignal::new();
+        assert!(signal.cancelled_result("a").is_none());
+        assert!(signal.report_error().is_none());
+
+        assert!(signal.stop(ErrorCode::EXECUTION_ABORTED, "Brain pediu abort"));
+        assert!(!signal.stop(ErrorCode::MEMORY_LIMIT_EXCEEDED, "tarde demais"));
+
+        let cancelled = signal.cancelled_result("a").unwrap();
+        assert_eq!(cancelled.status, StepStatus::Cancelled);
+        assert_eq!(cancelled.error.unwrap(), "[E4006] Brain pediu abort");
+        assert_eq!(signal.report_error().unwrap().code, "E4006");
+    }
+
+    #[tokio::test]
+    async fn test_deadline_skips_remaining_steps() {
+        let signal = StopSignal::new();
+        signal.deadline(Duration::from_millis(10)).await.unwrap();
+
+        // Prazo vencido sem step afetado: não é timeout da execução.
+        assert!(!signal.timed_out());
+        assert!(signal.report_error().is_none());
+        let skipped = signal.cancelled_result("late").unwrap();
+        assert_eq!(skipped.status, StepStatus::Skipped);
+        assert_eq!(skipped.skip_reason, Some(SkipReason::TimedOut));
+        assert!(skipped.error.unwrap().starts_with("[E1012]"));
+        assert!(signal.timed_out());
+        assert_eq!(signal.report_error().unwrap().code, "E1012");
+    }
+
+    #[tokio::test]
+    async fn test_deadline_interrupts_running_step() {
+        let signal = StopSignal::new();
+        let _deadline = signal.deadline(Duration::from_millis(20));
+
+        let started = std::time::Instant::now();
+        let output = signal
+            .within_deadline(tokio::time::sleep(Duration::from_secs(30)))
+            .await;
+        assert!(output.is_none());
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        let cut = signal.interrupted_result("slow");
+        assert_eq!(cut.status, StepStatus::Failed);
+        assert_eq!(cut.skip_reason, Some(SkipReason::TimedOut));
+        assert!(signal.timed_out());
+
+        // Outros motivos de parada não interrompem o step em andamento.
+        let aborted = StopSignal::new();
+        aborted.stop(ErrorCode::EXECUTION_ABORTED, "abort");
+        assert_eq!(aborted.within_deadline(async { 7 }).await, Some(7));
+    }
+}
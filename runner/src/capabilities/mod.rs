@@ -0,0 +1,239 @@
+//! # Módulo de Capacidades - `runner capabilities`
+//!
+//! Lista tudo o que este Runner sabe executar, para que o Brain gere planos
+//! usando apenas recursos suportados pelo binário instalado.
+//!
+//! ## Para todos entenderem:
+//!
+//! `runner version --json` responde "qual versão?"; `runner capabilities
+//! --json` responde "o que posso colocar no plano?":
+//!
+//! | Campo                     | Exemplo                                  |
+//! |---------------------------|------------------------------------------|
+//! | `actions`                 | `http_request`, `wait`, `assert`         |
+//! | `assertion_types`         | `status_code`, `json_body`, `latency`    |
+//! | `assertion_operators`     | `eq`, `lt`, `matches_regex`, `in`        |
+//! | `extraction_sources`      | `body`, `header`, `status_code`          |
+//! | `interpolation_functions` | `random_uuid`, `env:<NAME>`, `base64:<text>` |
+//! | `limits`                  | Padrões de `RUNNER_MAX_*`                |
+//!
+//! As listas ficam aqui, ao lado dos testes que conferem que o contexto e o
+//! extrator realmente aceitam cada item.
+
+use serde::Serialize;
+
+use crate::limits::{
+    DEFAULT_MAX_EXECUTION_SECS, DEFAULT_MAX_PARALLEL, DEFAULT_MAX_RETRIES_TOTAL, DEFAULT_MAX_STEPS,
+    DEFAULT_MAX_STEP_TIMEOUT_SECS,
+};
+use crate::validation::{KNOWN_ACTIONS, SUPPORTED_SPEC_VERSION};
+
+/// Tipos de assertion (`assertions[].type`).
+///
+/// `variable` só vale na action `assert`; os binários (`content_type`,
+/// `body_size`, `body_signature`, `body_sha256`) em respostas não-JSON.
+pub const ASSERTION_TYPES: &[&str] = &[
+    "status_code",
+    "status_range",
+    "json_body",
+    "header",
+    "content_encoding",
+    "latency",
+    "json_schema",
+    "content_type",
+    "body_size",
+    "body_signature",
+    "body_sha256",
+    "first_byte_ms",
+    "chunk_count",
+    "stream_content",
+    "graphql_errors",
+    "cache_behavior",
+    "variable",
+];
+
+/// Operadores de assertion (`assertions[].operator`).
+pub const ASSERTION_OPERATORS: &[&str] = &[
+    "eq",
+    "neq",
+    "lt",
+    "gt",
+    "lte",
+    "gte",
+    "contains",
+    "matches_regex",
+    "exists",
+    "not_exists",
+    "in",
+    "not_in",
+    "semver_gte",
+    "date_before",
+    "date_after",
+    "uuid_valid",
+    "email_valid",
+    "url_valid",
+    "approx_eq",
+    "matches_subset",
+];
+
+/// Fontes de extração (`extract[].source`).
+pub const EXTRACTION_SOURCES: &[&str] = &["body", "header", "status_code"];
+
+/// Funções de interpolação (`${...}`); `<...>` indica o argumento.
+pub const INTERPOLATION_FUNCTIONS: &[&str] = &[
+    "random_uuid",
+    "random_int",
+    "timestamp",
+    "timestamp_ms",
+    "now",
+    "now_local",
+    "env:<NAME>",
+    "ENV_<NAME>",
+    "base64:<text>",
+    "sha256:<text>",
+];
+
+// ============================================================================
+// ESTRUTURAS
+// ============================================================================
+
+/// Limites padrão (sobrescrevíveis pelas variáveis `RUNNER_MAX_*`).
+#[derive(Debug, Serialize)]
+pub struct LimitDefaults {
+    pub max_steps: usize,
+    pub max_parallel: usize,
+    pub max_retries_total: u32,
+    pub max_execution_secs: u64,
+    pub max_step_timeout_secs: u64,
+}
+
+/// Capacidades deste binário.
+#[derive(Debug, Serialize)]
+pub struct Capabilities {
+    /// Versão do Runner.
+    pub version: &'static str,
+
+    /// `spec_version` de UTDL aceita.
+    pub spec_version: &'static str,
+
+    pub actions: Vec<&'static str>,
+    pub assertion_types: Vec<&'static str>,
+    pub assertion_operators: Vec<&'static str>,
+    pub extraction_sources: Vec<&'static str>,
+    pub interpolation_functions: Vec<&'static str>,
+    pub limits: LimitDefaults,
+}
+
+impl Capabilities {
+    /// Capacidades do binário em execução.
+    pub fn current() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            spec_version: SUPPORTED_SPEC_VERSION,
+            actions: KNOWN_ACTIONS.to_vec(),
+            assertion_types: ASSERTION_TYPES.to_vec(),
+            assertion_operators: ASSERTION_OPERATORS.to_vec(),
+            extraction_sources: EXTRACTION_SOURCES.to_vec(),
+            interpolation_functions: INTERPOLATION_FUNCTIONS.to_vec(),
+            limits: LimitDefaults {
+                max_steps: DEFAULT_MAX_STEPS,
+                max_parallel: DEFAULT_MAX_PARALLEL,
+                max_retries_total: DEFAULT_MAX_RETRIES_TOTAL,
+                max_execution_secs: DEFAULT_MAX_EXECUTION_SECS,
+                max_step_timeout_secs: DEFAULT_MAX_STEP_TIMEOUT_SECS,
+            },
+        }
+    }
+
+    /// Texto legível de `runner capabilities` (sem `--json`).
+    pub fn human(&self) -> String {
+        let limits = &self.limits;
+        [
+            format!("runner {} (spec_version {})", self.version, self.spec_version),
+            format!("actions: {}", self.actions.join(", ")),
+            format!("assertion types: {}", self.assertion_types.join(", ")),
+            format!("assertion operators: {}", self.assertion_operators.join(", ")),
+            format!("extraction sources: {}", self.extraction_sources.join(", ")),
+            format!(
+                "interpolation: {}",
+                self.interpolation_functions
+                    .iter()
+                    .map(|f| format!("${{{}}}", f))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            format!(
+                "limits: max_steps={} max_parallel={} max_retries_total={} max_execution={}s max_step_timeout={}s",
+                limits.max_steps,
+                limits.max_parallel,
+                limits.max_retries_total,
+                limits.max_execution_secs,
+                limits.max_step_timeout_secs
+            ),
+        ]
+        .join("\n")
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::Context;
+    use crate::extractors::Extractor;
+    use crate::protocol::Extraction;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_interpolation_functions_resolve() {
+        std::env::set_var("AQA_CAPABILITIES_TEST", "ok");
+        let ctx = Context::new();
+
+        for function in INTERPOLATION_FUNCTIONS {
+            let token = function
+                .replace("<NAME>", "AQA_CAPABILITIES_TEST")
+                .replace("<text>", "abc");
+            let token = match token.strip_prefix("ENV_") {
+                Some(_) => "ENV_AQA_CAPABILITIES_TEST".to_string(),
+                None => token,
+            };
+            let result = ctx.interpolate_str(&format!("${{{}}}", token));
+            assert!(result.is_ok(), "{} não resolveu: {:?}", function, result);
+        }
+    }
+
+    #[test]
+    fn test_extraction_sources_are_accepted() {
+        let body = serde_json::json!({ "id": 1 });
+        let headers = HashMap::from([("x-id".to_string(), "1".to_string())]);
+
+        for source in EXTRACTION_SOURCES {
+            let path = if *source == "header" { "x-id" } else { "$.id" };
+            let extraction: Extraction = serde_json::from_value(
+                serde_json::json!({ "source": source, "path": path, "target": "value" }),
+            )
+            .unwrap();
+            let (results, _) =
+                Extractor::process_with_status(&[extraction], Some(&body), &headers, Some(200));
+            assert!(
+                results[0].success,
+                "fonte {} rejeitada: {:?}",
+                source, results[0].error
+            );
+        }
+    }
+
+    #[test]
+    fn test_capabilities_json() {
+        let json = serde_json::to_value(Capabilities::current()).unwrap();
+        assert_eq!(json["spec_version"], SUPPORTED_SPEC_VERSION);
+        assert_eq!(json["limits"]["max_steps"], DEFAULT_MAX_STEPS);
+        assert!(json["assertion_types"]
+            .as_array()
+            .unwrap()
+            .contains(&"json_body".into()));
+    }
+}
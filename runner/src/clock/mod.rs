@@ -0,0 +1,192 @@
+//! # Módulo de Relógio - Abstração de Tempo Injetável
+//!
+//! Este módulo abstrai as **pausas** feitas pelo Runner (steps `wait`/`sleep`
+//! e backoff entre retries) atrás de um trait, para que o tempo possa ser
+//! acelerado ou pulado em modos de teste.
+//!
+//! ## Para todos entenderem:
+//!
+//! Um plano com `wait` de 30 segundos leva 30 segundos para rodar.
+//! Quando só queremos saber se o plano está correto (CI, desenvolvimento),
+//! esperar de verdade é desperdício. Com o `VirtualClock`, a espera é
+//! registrada mas não acontece (ou acontece em escala reduzida).
+//!
+//! ## Implementações:
+//!
+//! | Relógio         | Comportamento                                    |
+//! |-----------------|--------------------------------------------------|
+//! | `SystemClock`   | Espera o tempo real (padrão)                     |
+//! | `VirtualClock`  | Espera `duração × scale` e acumula tempo virtual |
+//!
+//! ## Exemplo de uso:
+//!
+//! ```bash
+//! # Pula todas as esperas
+//! runner execute --file plano.json --fast-wait
+//!
+//! # Espera 10% do tempo configurado
+//! runner execute --file plano.json --wait-scale 0.1
+//! ```
+
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+// ============================================================================
+// TRAIT CLOCK
+// ============================================================================
+
+/// Contrato para qualquer fonte de pausas usada pelo Runner.
+///
+/// Executores e a lógica de retry recebem um `SharedClock` em vez de
+/// chamar `tokio::time::sleep` diretamente.
+#[async_trait]
+pub trait Clock: Send + Sync {
+    /// Pausa a execução pela duração solicitada (ou equivalente virtual).
+    async fn sleep(&self, duration: Duration);
+
+    /// Indica se o relógio é virtual (esperas aceleradas ou puladas).
+    fn is_virtual(&self) -> bool {
+        false
+    }
+}
+
+/// Relógio compartilhável entre executores e tasks paralelas.
+pub type SharedClock = Arc<dyn Clock>;
+
+/// Cria o relógio padrão (tempo real).
+pub fn system_clock() -> SharedClock {
+    Arc::new(SystemClock)
+}
+
+// ============================================================================
+// SYSTEM CLOCK
+// ============================================================================
+
+/// Relógio real: delega para `tokio::time::sleep`.
+#[derive(Debug, Default)]
+pub struct SystemClock;
+
+#[async_trait]
+impl Clock for SystemClock {
+    async fn sleep(&self, duration: Duration) {
+        tokio::time::sleep(duration).await;
+    }
+}
+
+// ============================================================================
+// VIRTUAL CLOCK
+// ============================================================================
+
+/// Relógio virtual para `--fast-wait` e `--wait-scale`.
+///
+/// Cada pausa solicitada é somada em `virtual_elapsed`, mas o tempo
+/// realmente esperado é `duração × scale`:
+/// - `scale = 0.0`: pula a espera
+/// - `scale = 0.1`: espera 10% do tempo
+#[derive(Debug)]
+pub struct VirtualClock {
+    /// Fator aplicado às esperas reais (0.0 a 1.0).
+    scale: f64,
+
+    /// Total de milissegundos "esperados" virtualmente.
+    virtual_elapsed_ms: AtomicU64,
+}
+
+impl VirtualClock {
+    /// Cria um relógio virtual com o fator de escala informado.
+    ///
+    /// O fator é limitado ao intervalo 0.0-1.0 (nunca aumenta esperas).
+    pub fn new(scale: f64) -> Self {
+        let scale = if scale.is_finite() {
+            scale.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        Self {
+            scale,
+            virtual_elapsed_ms: AtomicU64::new(0),
+        }
+    }
+
+    /// Relógio que pula todas as esperas.
+    pub fn instant() -> Self {
+        Self::new(0.0)
+    }
+
+    /// Total de tempo virtual acumulado em milissegundos.
+    #[allow(dead_code)]
+    pub fn virtual_elapsed_ms(&self) -> u64 {
+        self.virtual_elapsed_ms.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl Clock for VirtualClock {
+    async fn sleep(&self, duration: Duration) {
+        self.virtual_elapsed_ms
+            .fetch_add(duration.as_millis() as u64, Ordering::SeqCst);
+
+        if self.scale > 0.0 {
+            tokio::time::sleep(duration.mul_f64(self.scale)).await;
+        }
+    }
+
+    fn is_virtual(&self) -> bool {
+        true
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[tokio::test]
+    async fn test_virtual_clock_skips_wait() {
+        let clock = VirtualClock::instant();
+        let start = Instant::now();
+
+        clock.sleep(Duration::from_secs(10)).await;
+
+        assert!(start.elapsed() < Duration::from_millis(50));
+        assert_eq!(clock.virtual_elapsed_ms(), 10_000);
+        assert!(clock.is_virtual());
+    }
+
+    #[tokio::test]
+    async fn test_virtual_clock_scales_wait() {
+        let clock = VirtualClock::new(0.1);
+        let start = Instant::now();
+
+        clock.sleep(Duration::from_millis(500)).await;
+
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(50));
+        assert!(elapsed < Duration::from_millis(300));
+        assert_eq!(clock.virtual_elapsed_ms(), 500);
+    }
+
+    #[test]
+    fn test_virtual_clock_clamps_scale() {
+        assert_eq!(VirtualClock::new(5.0).scale, 1.0);
+        assert_eq!(VirtualClock::new(-1.0).scale, 0.0);
+        assert_eq!(VirtualClock::new(f64::NAN).scale, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_system_clock_waits() {
+        let clock = SystemClock;
+        let start = Instant::now();
+
+        clock.sleep(Duration::from_millis(20)).await;
+
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        assert!(!clock.is_virtual());
+    }
+}
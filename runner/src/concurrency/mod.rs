@@ -0,0 +1,321 @@
+//! # Módulo de Concorrência - `--max-parallel auto`
+//!
+//! Limita quantos steps rodam ao mesmo tempo no modo `--parallel`: com um
+//! número fixo (semáforo) ou adaptando o limite ao que o ambiente aguenta.
+//!
+//! ## Para todos entenderem:
+//!
+//! Um ambiente de staging com capacidade desconhecida responde bem a 4
+//! requisições simultâneas e devolve `429` com 16. Um limite fixo ou
+//! desperdiça capacidade ou derruba o ambiente. O modo `auto` usa AIMD
+//! (Additive Increase, Multiplicative Decrease), o mesmo princípio do
+//! controle de congestionamento do TCP:
+//!
+//! | Sinal observado                                  | Ajuste do limite       |
+//! |--------------------------------------------------|------------------------|
+//! | `limit` steps seguidos sem congestionamento      | `+1` (até o máximo)    |
+//! | `429`/`502`/`503`/`504`, timeout ou sem conexão  | `÷2` (até 1)           |
+//! | Latência acima de 3× a média recente             | `÷2` (até 1)           |
+//!
+//! Depois de uma redução, novas reduções só acontecem após `limit` steps
+//! concluídos: uma rajada de erros do mesmo lote não derruba o limite a 1.
+//!
+//! ## Exemplo:
+//!
+//! ```bash
+//! runner execute --file plan.json --parallel --max-parallel auto
+//! ```
+
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{Notify, OwnedSemaphorePermit, Semaphore};
+use tracing::{info, warn};
+
+use crate::protocol::StepResult;
+
+/// Limite inicial do modo `auto` (antes de observar qualquer resposta).
+const INITIAL_LIMIT: usize = 4;
+
+/// Latência acima de `LATENCY_SPIKE_FACTOR` × média conta como congestionamento.
+const LATENCY_SPIKE_FACTOR: f64 = 3.0;
+
+/// Amostras necessárias antes de a latência ser considerada.
+const LATENCY_WARMUP_SAMPLES: usize = 5;
+
+/// Peso de cada nova amostra na média móvel da latência.
+const LATENCY_EWMA_ALPHA: f64 = 0.2;
+
+// ============================================================================
+// OPÇÃO DA CLI
+// ============================================================================
+
+/// Valor de `--max-parallel`: um número fixo ou `auto`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MaxParallel {
+    Fixed(usize),
+    Auto,
+}
+
+impl FromStr for MaxParallel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("auto") {
+            return Ok(Self::Auto);
+        }
+        s.parse()
+            .map(Self::Fixed)
+            .map_err(|_| format!("esperado um número ou 'auto', recebido '{}'", s))
+    }
+}
+
+// ============================================================================
+// LIMITADOR
+// ============================================================================
+
+/// Limite de steps simultâneos usado pelo `DagPlanner`.
+#[derive(Clone)]
+pub enum ConcurrencyLimit {
+    Fixed(Arc<Semaphore>),
+    Adaptive(Arc<AimdLimiter>),
+}
+
+/// Vaga ocupada por um step; liberada ao sair de escopo.
+pub enum Permit {
+    Fixed { _permit: OwnedSemaphorePermit },
+    Adaptive(Arc<AimdLimiter>),
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        if let Permit::Adaptive(limiter) = self {
+            limiter.release();
+        }
+    }
+}
+
+impl ConcurrencyLimit {
+    /// Limite fixo de `max` steps.
+    pub fn fixed(max: usize) -> Self {
+        Self::Fixed(Arc::new(Semaphore::new(max.max(1))))
+    }
+
+    /// Limite adaptativo entre 1 e `max` steps.
+    pub fn adaptive(max: usize) -> Self {
+        Self::Adaptive(Arc::new(AimdLimiter::new(max)))
+    }
+
+    /// Aguarda uma vaga.
+    pub async fn acquire(&self) -> Permit {
+        match self {
+            Self::Fixed(semaphore) => Permit::Fixed {
+                _permit: Arc::clone(semaphore)
+                    .acquire_owned()
+                    .await
+                    .expect("Semaphore closed"),
+            },
+            Self::Adaptive(limiter) => {
+                limiter.acquire().await;
+                Permit::Adaptive(Arc::clone(limiter))
+            }
+        }
+    }
+
+    /// Alimenta o modo `auto` com o resultado de um step.
+    pub fn record(&self, result: &StepResult) {
+        if let Self::Adaptive(limiter) = self {
+            limiter.record(result);
+        }
+    }
+
+    /// Limite atual do modo `auto` (`None` no modo fixo).
+    pub fn adaptive_limit(&self) -> Option<usize> {
+        match self {
+            Self::Fixed(_) => None,
+            Self::Adaptive(limiter) => Some(limiter.current()),
+        }
+    }
+}
+
+/// Limitador AIMD: cresce devagar, recua rápido.
+pub struct AimdLimiter {
+    max: usize,
+    state: Mutex<AimdState>,
+    notify: Notify,
+}
+
+struct AimdState {
+    limit: usize,
+    in_flight: usize,
+    /// Steps sem congestionamento desde o último aumento.
+    clean: usize,
+    /// Steps concluídos desde a última redução.
+    since_decrease: usize,
+    /// Média móvel da latência das respostas sem congestionamento.
+    latency_ms: Option<f64>,
+    samples: usize,
+}
+
+impl AimdLimiter {
+    fn new(max: usize) -> Self {
+        let max = max.max(1);
+        Self {
+            max,
+            state: Mutex::new(AimdState {
+                limit: INITIAL_LIMIT.min(max),
+                in_flight: 0,
+                clean: 0,
+                since_decrease: usize::MAX,
+                latency_ms: None,
+                samples: 0,
+            }),
+            notify: Notify::new(),
+        }
+    }
+
+    async fn acquire(&self) {
+        loop {
+            // Registra o interesse antes de conferir, para não perder um `release`.
+            let notified = self.notify.notified();
+            {
+                let mut state = self.state.lock().expect("AIMD state poisoned");
+                if state.in_flight < state.limit {
+                    state.in_flight += 1;
+                    return;
+                }
+            }
+            notified.await;
+        }
+    }
+
+    fn release(&self) {
+        self.state.lock().expect("AIMD state poisoned").in_flight -= 1;
+        self.notify.notify_waiters();
+    }
+
+    fn current(&self) -> usize {
+        self.state.lock().expect("AIMD state poisoned").limit
+    }
+
+    fn record(&self, result: &StepResult) {
+        let latency_ms = result
+            .http_details
+            .as_ref()
+            .map_or(result.duration_ms, |http| http.latency_ms) as f64;
+        let mut state = self.state.lock().expect("AIMD state poisoned");
+        state.since_decrease = state.since_decrease.saturating_add(1);
+
+        let spike = state.samples >= LATENCY_WARMUP_SAMPLES
+            && state
+                .latency_ms
+                .is_some_and(|avg| latency_ms > avg * LATENCY_SPIKE_FACTOR);
+
+        if is_congestion(result) || spike {
+            if state.since_decrease >= state.limit {
+                let previous = state.limit;
+                state.limit = (state.limit / 2).max(1);
+                state.since_decrease = 0;
+                state.clean = 0;
+                warn!(
+                    step_id = %result.step_id,
+                    from = previous,
+                    to = state.limit,
+                    latency_spike = spike,
+                    "Congestion detected; reducing parallelism"
+                );
+            }
+            return;
+        }
+
+        state.latency_ms = Some(match state.latency_ms {
+            Some(avg) => avg + LATENCY_EWMA_ALPHA * (latency_ms - avg),
+            None => latency_ms,
+        });
+        state.samples += 1;
+        state.clean += 1;
+        if state.clean >= state.limit && state.limit < self.max {
+            state.limit += 1;
+            state.clean = 0;
+            info!(to = state.limit, "Raising parallelism");
+            drop(state);
+            self.notify.notify_waiters();
+        }
+    }
+}
+
+/// Resposta que indica ambiente sobrecarregado.
+fn is_congestion(result: &StepResult) -> bool {
+    match &result.http_details {
+        Some(http) => matches!(http.status_code, 0 | 429 | 502 | 503 | 504),
+        None => result.timeout_capture.is_some(),
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn result(status_code: u16, latency_ms: u64) -> StepResult {
+        serde_json::from_value(json!({
+            "step_id": "s", "status": "passed", "duration_ms": latency_ms,
+            "http_details": {
+                "method": "GET", "url": "http://api", "status_code": status_code,
+                "latency_ms": latency_ms
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_max_parallel() {
+        assert_eq!("auto".parse(), Ok(MaxParallel::Auto));
+        assert_eq!("8".parse(), Ok(MaxParallel::Fixed(8)));
+        assert!("many".parse::<MaxParallel>().is_err());
+    }
+
+    #[test]
+    fn test_aimd_increases_additively_and_halves_on_429() {
+        let limiter = AimdLimiter::new(10);
+        assert_eq!(limiter.current(), 4);
+
+        for _ in 0..4 {
+            limiter.record(&result(200, 50));
+        }
+        assert_eq!(limiter.current(), 5);
+
+        limiter.record(&result(429, 50));
+        assert_eq!(limiter.current(), 2);
+        // Outro 429 do mesmo lote não reduz de novo.
+        limiter.record(&result(429, 50));
+        assert_eq!(limiter.current(), 2);
+    }
+
+    #[test]
+    fn test_latency_spike_counts_as_congestion() {
+        let limiter = AimdLimiter::new(4);
+        for _ in 0..8 {
+            limiter.record(&result(200, 100));
+        }
+        assert_eq!(limiter.current(), 4);
+
+        limiter.record(&result(200, 900));
+        assert_eq!(limiter.current(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_limit_blocks_above_current() {
+        let limit = ConcurrencyLimit::adaptive(1);
+        let first = limit.acquire().await;
+        let second = tokio::time::timeout(std::time::Duration::from_millis(20), limit.acquire());
+        assert!(second.await.is_err());
+
+        drop(first);
+        let third = tokio::time::timeout(std::time::Duration::from_millis(20), limit.acquire());
+        assert!(third.await.is_ok());
+    }
+}
@@ -33,10 +33,23 @@
 //! | `${ENV_VAR}`     | Variável de ambiente (formato legado) | (valor da variável)     |
 //! | `${base64:text}` | Codifica texto em Base64           | `dGV4dA==`                 |
 //! | `${sha256:text}` | Hash SHA-256 do texto (hex)        | `9f86d081884c7d659a2f...`  |
+//!
+//! ## Sintaxe legada `{{var}}`:
+//!
+//! Planos gerados às vezes usam `{{user_id}}` no lugar de `${user_id}`.
+//! O placeholder é resolvido igual ao `${...}` quando o nome é conhecido;
+//! senão o texto fica como está (pode ser um template Mustache de verdade).
+//! O relatório ganha um aviso W004 com a troca sugerida (ver `warnings`).
+//!
+//! Com `--seed N`, `${random_uuid}` (UUID v5) e `${random_int}` passam a
+//! ser reprodutíveis: veja o módulo `random`.
+//!
+//! Com `clock_skew_ms` no step, `${now}`, `${now_local}`, `${timestamp}` e
+//! `${timestamp_ms}` são deslocados (simula um cliente com relógio errado).
 
 use anyhow::{anyhow, Result};
 use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine as _};
-use chrono::{Local, Utc};
+use chrono::{DateTime, Local, Utc};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde_json::{Map, Value};
@@ -44,6 +57,8 @@ use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+use crate::random::SeededRandom;
+
 // ============================================================================
 // EXPRESSÃO REGULAR PARA INTERPOLAÇÃO
 // ============================================================================
@@ -70,6 +85,17 @@ use uuid::Uuid;
 static INTERPOLATION_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"\$\{([A-Za-z0-9_.:-]+)\}").expect("valid interpolation regex"));
 
+/// Placeholders `${...}` (grupo 1) ou legados `{{ ... }}` (grupo 2).
+static PLACEHOLDER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\$\{([A-Za-z0-9_.:-]+)\}|\{\{\s*([A-Za-z0-9_.:-]+)\s*\}\}")
+        .expect("valid placeholder regex")
+});
+
+/// Placeholder legado `{{ nome }}`; o nome fica no grupo 1.
+pub static LEGACY_PLACEHOLDER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\{\{\s*([A-Za-z0-9_.:-]+)\s*\}\}").expect("valid legacy placeholder regex")
+});
+
 // ============================================================================
 // ESTRUTURA CONTEXT
 // ============================================================================
@@ -94,13 +120,29 @@ static INTERPOLATION_RE: Lazy<Regex> =
 /// let result = ctx.interpolate_str("User ${user_id} has ${count} items")?;
 /// // → "User 123 has 42 items"
 /// ```
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Context {
     /// HashMap que armazena todas as variáveis.
     ///
     /// Chave: nome da variável (String)
     /// Valor: qualquer valor JSON (String, Number, Bool, Array, Object, Null)
     pub variables: HashMap<String, Value>,
+
+    /// Gerador determinístico (`--seed`). `None` = aleatório de verdade.
+    pub random: Option<SeededRandom>,
+
+    /// Nomes das variáveis de cada ator de `config.actors` (ver `actors`).
+    pub actor_variables: HashMap<String, Vec<String>>,
+
+    /// Desvio de relógio do step em execução (`step.clock_skew_ms`).
+    pub clock_skew_ms: i64,
+
+    /// Extrações com `scope: step`, por step de origem (ver `scoping`).
+    pub step_variables: HashMap<String, HashMap<String, Value>>,
+
+    /// Teto de iterações simultâneas do `parallel_foreach`
+    /// (`ExecutionLimits.max_parallel`; 0 = sem teto).
+    pub max_parallel: usize,
 }
 
 impl Context {
@@ -114,6 +156,31 @@ impl Context {
     pub fn new() -> Self {
         Self {
             variables: HashMap::new(),
+            random: None,
+            actor_variables: HashMap::new(),
+            clock_skew_ms: 0,
+            step_variables: HashMap::new(),
+            max_parallel: 0,
+        }
+    }
+
+    /// Agora, deslocado por `clock_skew_ms`.
+    pub fn now(&self) -> DateTime<Utc> {
+        Utc::now() + chrono::Duration::milliseconds(self.clock_skew_ms)
+    }
+
+    /// Torna `${random_int}` e `${random_uuid}` reprodutíveis (`--seed`).
+    pub fn set_seed(&mut self, seed: u64) {
+        self.random = Some(SeededRandom::new(seed));
+    }
+
+    /// Define o escopo dos valores aleatórios (ID do step em execução).
+    ///
+    /// Com semente, cada step gera sua própria sequência, independente
+    /// da ordem em que os steps rodam. Sem semente, não faz nada.
+    pub fn enter_random_scope(&mut self, scope: &str) {
+        if let Some(random) = &mut self.random {
+            random.enter_scope(scope);
         }
     }
 
@@ -217,17 +284,21 @@ impl Context {
         let mut last_index = 0;
 
         // Itera sobre todos os matches da regex.
-        for capture in INTERPOLATION_RE.captures_iter(input) {
+        for capture in PLACEHOLDER_RE.captures_iter(input) {
             let matched = capture.get(0).unwrap(); // O match completo (ex: "${token}")
 
             // Adiciona o texto antes do match.
             result.push_str(&input[last_index..matched.start()]);
 
-            // Extrai o nome do token (grupo 1 da regex).
-            let token = capture.get(1).unwrap().as_str(); // Ex: "token"
-
-            // Resolve o token para seu valor.
-            let resolved = self.resolve_token(token)?;
+            // Resolve o token (grupo 1) para seu valor; o legado (grupo 2)
+            // fica como texto se não resolver.
+            let resolved = match (capture.get(1), capture.get(2)) {
+                (Some(token), _) => self.resolve_token(token.as_str())?,
+                (None, Some(legacy)) => self
+                    .resolve_token(legacy.as_str())
+                    .unwrap_or_else(|_| matched.as_str().to_string()),
+                (None, None) => unreachable!("placeholder sem nome"),
+            };
 
             // Adiciona o valor resolvido.
             result.push_str(&resolved);
@@ -290,6 +361,48 @@ impl Context {
         }
     }
 
+    /// Interpola um valor JSON preservando o tipo de placeholders isolados.
+    ///
+    /// Igual a `interpolate_value`, mas se uma string for exatamente um
+    /// placeholder de variável do contexto (ex: `"${items}"`), o valor
+    /// original é retornado com seu tipo (array, número, objeto...).
+    ///
+    /// ## Exemplo:
+    /// ```rust
+    /// ctx.set("count", json!(3));
+    ///
+    /// ctx.interpolate_value(&json!("${count}"))?;        // → "3"
+    /// ctx.interpolate_value_typed(&json!("${count}"))?;  // → 3
+    /// ```
+    pub fn interpolate_value_typed(&self, value: &Value) -> Result<Value> {
+        match value {
+            Value::String(s) => {
+                if let Some(captures) = INTERPOLATION_RE.captures(s) {
+                    let whole = captures.get(0).unwrap();
+                    if whole.start() == 0 && whole.end() == s.len() {
+                        if let Some(existing) = self.variables.get(&captures[1]) {
+                            return Ok(existing.clone());
+                        }
+                    }
+                }
+                Ok(Value::String(self.interpolate_str(s)?))
+            }
+            Value::Array(items) => items
+                .iter()
+                .map(|item| self.interpolate_value_typed(item))
+                .collect::<Result<Vec<_>>>()
+                .map(Value::Array),
+            Value::Object(map) => {
+                let mut new_map = Map::with_capacity(map.len());
+                for (k, v) in map {
+                    new_map.insert(k.clone(), self.interpolate_value_typed(v)?);
+                }
+                Ok(Value::Object(new_map))
+            }
+            _ => Ok(value.clone()),
+        }
+    }
+
     /// Resolve um token para seu valor como string.
     ///
     /// Esta função é chamada internamente por `interpolate_str` para
@@ -315,24 +428,37 @@ impl Context {
         match token {
             // Gera um UUID v4 aleatório.
             // Útil para criar IDs únicos em testes.
-            "random_uuid" => return Ok(Uuid::new_v4().to_string()),
+            // Com `--seed`, é um UUID v5 derivado da semente.
+            "random_uuid" => {
+                let uuid = match &self.random {
+                    Some(random) => random.next_uuid(),
+                    None => Uuid::new_v4(),
+                };
+                return Ok(uuid.to_string());
+            }
 
             // Retorna o timestamp Unix em segundos.
             // Útil para campos de data/hora.
-            "timestamp" => return Ok(Utc::now().timestamp().to_string()),
+            "timestamp" => return Ok(self.now().timestamp().to_string()),
 
             // Retorna o timestamp Unix em milissegundos.
             // Útil para maior precisão.
-            "timestamp_ms" => return Ok(Utc::now().timestamp_millis().to_string()),
+            "timestamp_ms" => return Ok(self.now().timestamp_millis().to_string()),
 
             // Retorna a data/hora atual em formato ISO8601 UTC.
-            "now" => return Ok(Utc::now().to_rfc3339()),
+            "now" => return Ok(self.now().to_rfc3339()),
 
             // Retorna a data/hora atual em formato ISO8601 com timezone local.
-            "now_local" => return Ok(Local::now().to_rfc3339()),
+            "now_local" => return Ok(self.now().with_timezone(&Local).to_rfc3339()),
 
             // Gera um inteiro aleatório de 32 bits (0 a 4.294.967.295).
-            "random_int" => return Ok(rand::random::<u32>().to_string()),
+            "random_int" => {
+                let value = match &self.random {
+                    Some(random) => random.next_u32(),
+                    None => rand::random::<u32>(),
+                };
+                return Ok(value.to_string());
+            }
 
             // Não é uma função dinâmica conhecida, continua para próximas opções.
             _ => {}
@@ -463,6 +589,41 @@ mod tests {
         assert!(result.len() > 20);
     }
 
+    #[test]
+    fn test_clock_skew_shifts_time_functions() {
+        let mut ctx = Context::new();
+        ctx.clock_skew_ms = -3_600_000;
+        let skewed: i64 = ctx
+            .interpolate_str("${timestamp}")
+            .unwrap()
+            .parse()
+            .unwrap();
+        let real = Utc::now().timestamp();
+        assert!((real - skewed - 3600).abs() <= 1, "{} vs {}", skewed, real);
+
+        let now = ctx.interpolate_str("${now}").unwrap();
+        let now = DateTime::parse_from_rfc3339(&now).unwrap();
+        assert!((Utc::now() - now.with_timezone(&Utc)).num_minutes() >= 59);
+    }
+
+    #[test]
+    fn test_seeded_random_is_reproducible() {
+        let render = || {
+            let mut ctx = Context::new();
+            ctx.set_seed(42);
+            ctx.enter_random_scope("create_user");
+            ctx.interpolate_str("${random_uuid}/${random_int}/${random_int}")
+                .unwrap()
+        };
+
+        let first = render();
+        assert_eq!(first, render());
+
+        let parts: Vec<&str> = first.split('/').collect();
+        assert_eq!(Uuid::parse_str(parts[0]).unwrap().get_version_num(), 5);
+        assert_ne!(parts[1], parts[2]);
+    }
+
     #[test]
     fn test_random_int_interpolation() {
         let ctx = Context::new();
@@ -530,6 +691,18 @@ mod tests {
         std::env::remove_var("MY_TOKEN");
     }
 
+    #[test]
+    fn test_legacy_mustache_placeholders() {
+        let mut ctx = Context::new();
+        ctx.set("user_id", Value::String("42".to_string()));
+
+        let result = ctx
+            .interpolate_str("/users/{{user_id}}/{{ user_id }}?q={{unknown}}")
+            .unwrap();
+
+        assert_eq!(result, "/users/42/42?q={{unknown}}");
+    }
+
     #[test]
     fn test_env_missing_variable() {
         let ctx = Context::new();
@@ -582,6 +755,23 @@ mod tests {
         assert_eq!(result, "Basic YWRtaW46c2VjcmV0MTIz");
     }
 
+    #[test]
+    fn test_interpolate_value_typed_preserves_types() {
+        let mut ctx = Context::new();
+        ctx.set("items", serde_json::json!([1, 2, 3]));
+        ctx.set("name", Value::String("ana".to_string()));
+
+        let result = ctx
+            .interpolate_value_typed(&serde_json::json!({
+                "list": "${items}",
+                "greeting": "oi ${name}"
+            }))
+            .unwrap();
+
+        assert_eq!(result["list"], serde_json::json!([1, 2, 3]));
+        assert_eq!(result["greeting"], serde_json::json!("oi ana"));
+    }
+
     #[test]
     fn test_sha256_for_cache_key() {
         let ctx = Context::new();
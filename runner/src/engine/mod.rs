@@ -0,0 +1,205 @@
+//! # Módulo de Engine - Execução Embutida com Controle
+//!
+//! API para rodar um plano de dentro de outro serviço (modo biblioteca):
+//! `ExecutionHandle` começa a execução em segundo plano e permite
+//! cancelar, pausar e receber cada resultado assim que o step termina.
+//!
+//! ## Para todos entenderem:
+//!
+//! A CLI roda um plano e só devolve o controle no fim. Um serviço que
+//! gerencia várias execuções (ex: o Brain ou um orquestrador de CI)
+//! precisa de um "controle remoto" para cada uma:
+//!
+//! ```rust,ignore
+//! let mut handle = ExecutionHandle::start(plan.steps, executors, context, limits);
+//! let mut results = handle.results().unwrap();
+//!
+//! while let Some(result) = results.recv().await {
+//!     if result.status == StepStatus::Failed {
+//!         handle.cancel(); // steps que ainda não começaram → cancelled
+//!     }
+//! }
+//! let all = handle.wait().await;
+//! ```
+//!
+//! | Método       | Efeito                                                 |
+//! |--------------|--------------------------------------------------------|
+//! | `cancel()`   | Steps não iniciados viram `cancelled` (E4006)          |
+//! | `pause()`    | Nenhum step novo começa; os em andamento terminam      |
+//! | `resume()`   | Libera os steps que esperavam                          |
+//! | `results()`  | Canal com cada `StepResult` assim que o step termina   |
+//! | `wait()`     | Espera o fim e devolve todos os resultados             |
+//!
+//! Cada handle tem seu próprio contexto, sinal de parada e pausa: vários
+//! planos podem rodar ao mesmo tempo no mesmo processo.
+
+use std::sync::Arc;
+use tokio::sync::{mpsc, RwLock};
+use tokio::task::JoinHandle;
+
+use crate::cancel::{PauseGate, StopSignal};
+use crate::context::Context;
+use crate::errors::ErrorCode;
+use crate::executors::StepExecutor;
+use crate::limits::ExecutionLimits;
+use crate::planner::DagPlanner;
+use crate::protocol::{ReportError, Step, StepResult};
+
+/// Controle de uma execução em segundo plano.
+pub struct ExecutionHandle {
+    stop: Arc<StopSignal>,
+    pause: PauseGate,
+    results: Option<mpsc::UnboundedReceiver<StepResult>>,
+    task: JoinHandle<Vec<StepResult>>,
+}
+
+impl ExecutionHandle {
+    /// Começa a executar os steps (respeitando `depends_on`) em segundo plano.
+    pub fn start(
+        steps: Vec<Step>,
+        executors: Vec<Box<dyn StepExecutor + Send + Sync>>,
+        context: Context,
+        limits: ExecutionLimits,
+    ) -> Self {
+        let stop = StopSignal::new();
+        let pause = PauseGate::new();
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        let planner = DagPlanner::new(steps)
+            .with_stop_signal(Arc::clone(&stop))
+            .with_pause_gate(pause.clone())
+            .with_result_channel(tx);
+        let task = tokio::spawn(planner.execute(
+            Arc::new(executors),
+            Arc::new(RwLock::new(context)),
+            limits,
+        ));
+
+        Self {
+            stop,
+            pause,
+            results: Some(rx),
+            task,
+        }
+    }
+
+    /// Cancela os steps que ainda não começaram (os em andamento terminam).
+    pub fn cancel(&self) {
+        self.stop.stop(
+            ErrorCode::EXECUTION_ABORTED,
+            "Execução cancelada via ExecutionHandle::cancel()",
+        );
+        // Steps parados na pausa precisam acordar para serem cancelados.
+        self.pause.resume();
+    }
+
+    /// Segura os steps que ainda não começaram até `resume()`.
+    pub fn pause(&self) {
+        self.pause.pause();
+    }
+
+    /// Libera os steps segurados por `pause()`.
+    pub fn resume(&self) {
+        self.pause.resume();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.pause.is_paused()
+    }
+
+    /// Canal com cada resultado assim que o step termina (só pode ser pego uma vez).
+    pub fn results(&mut self) -> Option<mpsc::UnboundedReceiver<StepResult>> {
+        self.results.take()
+    }
+
+    /// Erro da execução para `ExecutionReport.errors`, se foi cancelada.
+    pub fn error(&self) -> Option<ReportError> {
+        self.stop.report_error()
+    }
+
+    /// Espera o fim da execução e devolve os resultados de todos os steps.
+    pub async fn wait(self) -> Vec<StepResult> {
+        self.task.await.unwrap_or_default()
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executors::set_variable::SetVariableExecutor;
+    use crate::protocol::{SkipReason, StepStatus};
+    use serde_json::json;
+    use std::time::Duration;
+
+    fn chain() -> Vec<Step> {
+        ["first", "second", "third"]
+            .iter()
+            .enumerate()
+            .map(|(i, id)| Step {
+                id: id.to_string(),
+                action: "set_variable".to_string(),
+                params: json!({ "variables": { *id: i } }),
+                depends_on: if i == 0 {
+                    vec![]
+                } else {
+                    vec![["first", "second"][i - 1].to_string()]
+                },
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn executors() -> Vec<Box<dyn StepExecutor + Send + Sync>> {
+        vec![Box::new(SetVariableExecutor::new())]
+    }
+
+    #[tokio::test]
+    async fn test_pause_then_cancel_streams_results() {
+        let mut handle = ExecutionHandle::start(
+            chain(),
+            executors(),
+            Context::new(),
+            ExecutionLimits::default(),
+        );
+        handle.pause();
+        let mut results = handle.results().unwrap();
+
+        // Pausado logo no início: no máximo o primeiro step já tinha começado.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(handle.is_paused());
+        assert!(results.try_recv().map_or(true, |r| r.step_id == "first"));
+
+        handle.cancel();
+        let all = handle.wait().await;
+
+        assert_eq!(all.len(), 3);
+        let third = all.iter().find(|r| r.step_id == "third").unwrap();
+        assert_eq!(third.status, StepStatus::Cancelled);
+        assert_eq!(third.skip_reason, Some(SkipReason::Cancelled));
+        assert!(third.error.as_deref().unwrap().starts_with("[E4006]"));
+    }
+
+    #[tokio::test]
+    async fn test_results_arrive_as_steps_finish() {
+        let mut handle = ExecutionHandle::start(
+            chain(),
+            executors(),
+            Context::new(),
+            ExecutionLimits::default(),
+        );
+        let mut results = handle.results().unwrap();
+
+        let mut order = Vec::new();
+        while let Some(result) = results.recv().await {
+            assert_eq!(result.status, StepStatus::Passed);
+            order.push(result.step_id);
+        }
+        assert_eq!(order, ["first", "second", "third"]);
+        assert!(handle.error().is_none());
+        assert_eq!(handle.wait().await.len(), 3);
+    }
+}
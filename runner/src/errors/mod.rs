@@ -59,7 +59,7 @@ use std::fmt;
 ///
 /// É como um CEP: a primeira parte diz a região,
 /// o resto é o endereço específico.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ErrorCode(u16);
 
 #[allow(dead_code)]
@@ -118,6 +118,14 @@ impl ErrorCode {
     /// Causa: Execução do plano demorou mais que o limite.
     pub const EXECUTION_TIMEOUT: Self = Self(1012);
 
+    /// ID de step duplicado.
+    /// Causa: Dois ou mais steps usam o mesmo `id`.
+    pub const DUPLICATE_STEP_ID: Self = Self(1013);
+
+    /// `recovery_policy` inválida.
+    /// Causa: strategy desconhecida, max_attempts 0, backoff_factor <= 0 ou backoff absurdo.
+    pub const INVALID_RECOVERY_POLICY: Self = Self(1014);
+
     // ========================================================================
     // E2xxx: Execução HTTP
     // ========================================================================
@@ -200,11 +208,11 @@ impl ErrorCode {
     // Erros de setup, variáveis de ambiente, arquivos.
 
     /// Variável de ambiente não definida.
-    /// Causa: {{env:VAR}} usada mas VAR não existe.
+    /// Causa: ${env:VAR} usada mas VAR não existe.
     pub const ENV_VAR_NOT_FOUND: Self = Self(4001);
 
     /// Variável de contexto não encontrada.
-    /// Causa: {{var_name}} usada mas nunca foi extraída.
+    /// Causa: ${var_name} usada mas nunca foi extraída.
     pub const CONTEXT_VAR_NOT_FOUND: Self = Self(4002);
 
     /// Arquivo de plano não encontrado.
@@ -215,6 +223,15 @@ impl ErrorCode {
     /// Causa: Runner não tem permissão para ler arquivo.
     pub const FILE_PERMISSION_ERROR: Self = Self(4004);
 
+    /// Plano travado por outra execução.
+    /// Causa: `--lock-name` já adquirido por outro job (arquivo local ou Redis).
+    pub const PLAN_LOCKED: Self = Self(4005);
+
+    /// Execução abortada de fora do Runner.
+    /// Causa: O receptor de `--progress-url` respondeu `{"action": "abort"}`
+    /// ou o serviço que embute o Runner chamou `ExecutionHandle::cancel()`.
+    pub const EXECUTION_ABORTED: Self = Self(4006);
+
     // ========================================================================
     // E5xxx: Erros Internos
     // ========================================================================
@@ -232,6 +249,10 @@ impl ErrorCode {
     /// Causa: Problema ao converter dados internamente.
     pub const SERIALIZATION_ERROR: Self = Self(5003);
 
+    /// Limite de memória excedido.
+    /// Causa: Dados retidos (resultados, bodies, contexto) passaram de `RUNNER_MAX_MEMORY_MB`.
+    pub const MEMORY_LIMIT_EXCEEDED: Self = Self(5004);
+
     // ========================================================================
     // MÉTODOS
     // ========================================================================
@@ -282,6 +303,8 @@ impl ErrorCode {
             1010 => "Limite de steps excedido",
             1011 => "Limite de retries excedido",
             1012 => "Timeout de execução excedido",
+            1013 => "ID de step duplicado",
+            1014 => "recovery_policy inválida",
             // E2xxx: HTTP
             2001 => "Timeout HTTP",
             2002 => "Erro de conexão",
@@ -305,15 +328,334 @@ impl ErrorCode {
             4002 => "Variável de contexto não encontrada",
             4003 => "Arquivo de plano não encontrado",
             4004 => "Erro de permissão",
+            4005 => "Plano travado por outra execução",
+            4006 => "Execução abortada externamente",
             // E5xxx: Interno
             5001 => "Erro interno",
             5002 => "Executor não encontrado",
             5003 => "Erro de serialização",
+            5004 => "Limite de memória excedido",
             _ => "Erro desconhecido",
         }
     }
 }
 
+// ============================================================================
+// EXPLICAÇÃO DOS CÓDIGOS (`runner explain`)
+// ============================================================================
+
+/// Ficha de um código: nome, causas comuns e como resolver.
+struct Explanation {
+    code: u16,
+    name: &'static str,
+    causes: &'static [&'static str],
+    remediation: &'static str,
+}
+
+/// Fichas de todos os códigos conhecidos (mesma ordem de `docs/error_codes.md`).
+const EXPLANATIONS: &[Explanation] = &[
+    // E1xxx: Validação
+    Explanation {
+        code: 1001,
+        name: "EMPTY_PLAN",
+        causes: &["O array `steps` do plano está vazio ou ausente"],
+        remediation: "Adicione pelo menos um step ao plano.",
+    },
+    Explanation {
+        code: 1002,
+        name: "UNSUPPORTED_SPEC_VERSION",
+        causes: &["`spec_version` diferente de \"0.1\"", "Plano gerado por uma versão mais nova do Brain"],
+        remediation: "Use `\"spec_version\": \"0.1\"` ou atualize o Runner.",
+    },
+    Explanation {
+        code: 1003,
+        name: "UNKNOWN_ACTION",
+        causes: &["Erro de digitação na `action`", "Action de uma versão mais nova do UTDL"],
+        remediation: "Use uma action suportada: http_request, wait, sleep, set_variable, log, assert, transform.",
+    },
+    Explanation {
+        code: 1004,
+        name: "MISSING_PARAM",
+        causes: &["`http_request` sem `method` ou `path`", "`wait` sem `duration_ms`", "`assert` sem assertions"],
+        remediation: "Confira os parâmetros obrigatórios da action no schema UTDL.",
+    },
+    Explanation {
+        code: 1005,
+        name: "UNKNOWN_DEPENDENCY",
+        causes: &["`depends_on` com ID digitado errado", "Step dependido foi removido ou renomeado"],
+        remediation: "Verifique se o step referenciado em `depends_on` existe.",
+    },
+    Explanation {
+        code: 1006,
+        name: "CIRCULAR_DEPENDENCY",
+        causes: &["Step depende de si mesmo", "Ciclo A → B → A em `depends_on`"],
+        remediation: "Reorganize as dependências para eliminar o ciclo.",
+    },
+    Explanation {
+        code: 1007,
+        name: "INVALID_HTTP_METHOD",
+        causes: &["`method` fora de GET, POST, PUT, DELETE, PATCH, HEAD, OPTIONS"],
+        remediation: "Use um método HTTP válido.",
+    },
+    Explanation {
+        code: 1008,
+        name: "EMPTY_STEP_ID",
+        causes: &["Campo `id` vazio ou só com espaços"],
+        remediation: "Preencha o campo `id` de cada step.",
+    },
+    Explanation {
+        code: 1009,
+        name: "INVALID_PLAN_FORMAT",
+        causes: &["JSON com sintaxe inválida", "Campo obrigatório ausente (ex: `config.timeout_ms`)"],
+        remediation: "Valide o plano contra `schemas/utdl.schema.json`.",
+    },
+    Explanation {
+        code: 1010,
+        name: "MAX_STEPS_EXCEEDED",
+        causes: &["Plano com mais steps que `RUNNER_MAX_STEPS`"],
+        remediation: "Divida o plano ou aumente `RUNNER_MAX_STEPS`.",
+    },
+    Explanation {
+        code: 1011,
+        name: "MAX_RETRIES_EXCEEDED",
+        causes: &["Soma de `max_attempts` maior que `RUNNER_MAX_RETRIES`"],
+        remediation: "Reduza `recovery_policy.max_attempts` ou aumente `RUNNER_MAX_RETRIES`.",
+    },
+    Explanation {
+        code: 1012,
+        name: "EXECUTION_TIMEOUT",
+        causes: &["Steps lentos ou waits longos", "Retries com backoff alto"],
+        remediation: "Otimize os steps ou aumente `RUNNER_MAX_EXECUTION_SECS`. Os steps que não começaram ficam `skipped` (`timed_out`) e o Runner sai com código 124.",
+    },
+    Explanation {
+        code: 1013,
+        name: "DUPLICATE_STEP_ID",
+        causes: &["Step copiado sem trocar o `id`"],
+        remediation: "Renomeie os steps repetidos e ajuste os `depends_on` que os referenciam.",
+    },
+    Explanation {
+        code: 1014,
+        name: "INVALID_RECOVERY_POLICY",
+        causes: &[
+            "`strategy` com erro de digitação (ex: `\"retyr\"`)",
+            "`max_attempts: 0` ou `backoff_factor` <= 0",
+            "Backoff que esperaria mais de 5 minutos entre tentativas",
+        ],
+        remediation: "Use `strategy` retry, fail_fast ou ignore, `max_attempts` >= 1, `backoff_factor` > 0 e backoffs de até 5 minutos.",
+    },
+    // E2xxx: HTTP
+    Explanation {
+        code: 2001,
+        name: "HTTP_TIMEOUT",
+        causes: &["Servidor lento ou sobrecarregado", "`timeout_ms` baixo demais"],
+        remediation: "Aumente `config.timeout_ms` (ou o timeout do step) ou investigue a lentidão do servidor.",
+    },
+    Explanation {
+        code: 2002,
+        name: "HTTP_CONNECTION_ERROR",
+        causes: &["DNS não resolve o host", "Firewall ou VPN bloqueando", "Servidor fora do ar"],
+        remediation: "Verifique `base_url`, DNS e se o servidor está acessível (ex: `config.wait_for`).",
+    },
+    Explanation {
+        code: 2003,
+        name: "HTTP_ERROR_STATUS",
+        causes: &["Credenciais inválidas ou expiradas (401/403)", "Payload inválido (400/422)", "Erro no servidor (5xx)"],
+        remediation: "Verifique credenciais, payload e o estado do servidor.",
+    },
+    Explanation {
+        code: 2004,
+        name: "HTTP_INVALID_JSON",
+        causes: &["Endpoint devolve HTML/texto (ex: página de erro)", "Body comprimido com `decompress: false`"],
+        remediation: "Confira o `Content-Type` da resposta; para respostas não JSON use assertions binárias.",
+    },
+    Explanation {
+        code: 2005,
+        name: "HTTP_TLS_ERROR",
+        causes: &["Certificado expirado ou autoassinado", "Cadeia de certificados incompleta"],
+        remediation: "Corrija o certificado ou configure a CA em `config.http` (desabilitar validação só em dev).",
+    },
+    // E3xxx: Assertion
+    Explanation {
+        code: 3001,
+        name: "ASSERTION_STATUS_CODE",
+        causes: &["API mudou o status devolvido", "Dados de teste em estado diferente do esperado"],
+        remediation: "Compare o status esperado com `http_details.status` no relatório.",
+    },
+    Explanation {
+        code: 3002,
+        name: "ASSERTION_JSON_BODY",
+        causes: &["Valor diferente do esperado", "Tipo diferente (\"1\" vs 1)"],
+        remediation: "Verifique o path e o valor esperado contra o body no relatório (`--report-detail full`).",
+    },
+    Explanation {
+        code: 3003,
+        name: "ASSERTION_HEADER",
+        causes: &["Header ausente", "Valor do header diferente do esperado"],
+        remediation: "Verifique nome e valor do header (nomes não diferenciam maiúsculas).",
+    },
+    Explanation {
+        code: 3004,
+        name: "ASSERTION_LATENCY",
+        causes: &["Endpoint mais lento que o limite", "Ambiente de teste sobrecarregado"],
+        remediation: "Otimize o endpoint ou ajuste o limite de latência.",
+    },
+    Explanation {
+        code: 3005,
+        name: "ASSERTION_PATH_NOT_FOUND",
+        causes: &["Path JSON digitado errado", "Resposta sem o campo (ex: erro em vez de sucesso)"],
+        remediation: "Verifique se o path existe no body da resposta.",
+    },
+    Explanation {
+        code: 3010,
+        name: "EXTRACTION_PATH_NOT_FOUND",
+        causes: &["Path de `extract` não existe no body", "Resposta de erro com formato diferente"],
+        remediation: "Confira o `path` da extração contra o body; use `critical: false` se o campo for opcional.",
+    },
+    Explanation {
+        code: 3011,
+        name: "EXTRACTION_HEADER_NOT_FOUND",
+        causes: &["Header não enviado pelo servidor", "Header removido por proxy/CDN"],
+        remediation: "Confira o nome do header na resposta (`http_details`).",
+    },
+    Explanation {
+        code: 3012,
+        name: "EXTRACTION_REGEX_NO_MATCH",
+        causes: &["Regex não casa com o body", "Body mudou de formato"],
+        remediation: "Teste a regex contra o body salvo no relatório.",
+    },
+    Explanation {
+        code: 3013,
+        name: "EXTRACTION_INVALID_SOURCE",
+        causes: &["`source` diferente de body, header ou status_code"],
+        remediation: "Use uma `source` suportada em `extract`.",
+    },
+    Explanation {
+        code: 3014,
+        name: "EXTRACTION_INVALID_REGEX",
+        causes: &["Regex com sintaxe inválida", "Escape faltando em `\\` no JSON"],
+        remediation: "Corrija a regex (lembre que `\\` precisa ser escapado no JSON).",
+    },
+    // E4xxx: Configuração
+    Explanation {
+        code: 4001,
+        name: "ENV_VAR_NOT_FOUND",
+        causes: &["Variável de ambiente não exportada no CI", "Nome digitado errado"],
+        remediation: "Defina a variável de ambiente antes de executar.",
+    },
+    Explanation {
+        code: 4002,
+        name: "CONTEXT_VAR_NOT_FOUND",
+        causes: &["Variável usada antes do step que a extrai", "Extração falhou ou foi pulada"],
+        remediation: "Garanta que o step que extrai a variável execute antes (adicione-o em `depends_on`).",
+    },
+    Explanation {
+        code: 4003,
+        name: "PLAN_FILE_NOT_FOUND",
+        causes: &["Caminho de `--file` errado", "Diretório de trabalho diferente do esperado"],
+        remediation: "Verifique o caminho do arquivo de plano.",
+    },
+    Explanation {
+        code: 4004,
+        name: "FILE_PERMISSION_ERROR",
+        causes: &["Arquivo sem permissão de leitura", "Pasta de saída sem permissão de escrita"],
+        remediation: "Verifique as permissões do arquivo e da pasta.",
+    },
+    Explanation {
+        code: 4005,
+        name: "PLAN_LOCKED",
+        causes: &[
+            "Outro job de CI está executando o mesmo plano (mesmo `--lock-name`)",
+            "Execução anterior interrompida deixou o lock até o TTL expirar",
+        ],
+        remediation: "Aguarde a outra execução terminar (o holder aparece na mensagem) ou remova o lock vencido.",
+    },
+    Explanation {
+        code: 4006,
+        name: "EXECUTION_ABORTED",
+        causes: &[
+            "O receptor de `--progress-url` pediu para abortar (ex: falha crítica no meio do plano)",
+            "O serviço que embute o Runner chamou `ExecutionHandle::cancel()`",
+        ],
+        remediation: "Veja o motivo na mensagem; os steps que não começaram ficam `cancelled` no relatório.",
+    },
+    // E5xxx: Interno
+    Explanation {
+        code: 5001,
+        name: "INTERNAL_ERROR",
+        causes: &["Bug no Runner"],
+        remediation: "Abra uma issue com o código, a mensagem completa e o plano UTDL.",
+    },
+    Explanation {
+        code: 5002,
+        name: "NO_EXECUTOR_FOR_ACTION",
+        causes: &["Action aceita pela validação sem executor registrado (bug)"],
+        remediation: "Abra uma issue com o código, a mensagem completa e o plano UTDL.",
+    },
+    Explanation {
+        code: 5003,
+        name: "SERIALIZATION_ERROR",
+        causes: &["Dado que não pode ser convertido para JSON (bug)"],
+        remediation: "Abra uma issue com o código, a mensagem completa e o plano UTDL.",
+    },
+    Explanation {
+        code: 5004,
+        name: "MEMORY_LIMIT_EXCEEDED",
+        causes: &[
+            "Respostas grandes capturadas em muitos steps ou iterações",
+            "Contexto acumulando bodies inteiros em variáveis extraídas",
+            "`RUNNER_MAX_MEMORY_MB` baixo demais para o plano",
+        ],
+        remediation: "Extraia só os campos necessários, use `--report-detail minimal` ou aumente `RUNNER_MAX_MEMORY_MB`.",
+    },
+];
+
+impl ErrorCode {
+    /// Todos os códigos conhecidos, em ordem.
+    pub fn all() -> impl Iterator<Item = ErrorCode> {
+        EXPLANATIONS.iter().map(|e| ErrorCode(e.code))
+    }
+
+    /// Interpreta `"E3010"`, `"e3010"` ou `"3010"` (apenas códigos conhecidos).
+    pub fn parse(text: &str) -> Option<Self> {
+        let digits = text.trim();
+        let digits = digits.strip_prefix(['E', 'e']).unwrap_or(digits);
+        let code: u16 = digits.parse().ok()?;
+        Self::all().find(|c| c.0 == code)
+    }
+
+    fn explanation(&self) -> Option<&'static Explanation> {
+        EXPLANATIONS.iter().find(|e| e.code == self.0)
+    }
+
+    /// Nome da constante (ex: `EXTRACTION_PATH_NOT_FOUND`).
+    pub fn name(&self) -> &'static str {
+        self.explanation().map_or("UNKNOWN", |e| e.name)
+    }
+
+    /// Texto completo de `runner explain`: descrição, categoria, causas e solução.
+    pub fn explain(&self) -> String {
+        let mut text = format!(
+            "{} {}\n\nDescrição: {}\nCategoria: {} (E{}xxx)\n",
+            self,
+            self.name(),
+            self.description(),
+            self.category(),
+            self.0 / 1000
+        );
+        if let Some(explanation) = self.explanation() {
+            text.push_str("\nCausas comuns:\n");
+            for cause in explanation.causes {
+                text.push_str(&format!("  - {}\n", cause));
+            }
+            text.push_str(&format!(
+                "\nComo resolver:\n  {}\n",
+                explanation.remediation
+            ));
+        }
+        text
+    }
+}
+
 /// Implementação de Display para ErrorCode.
 ///
 /// Permite usar ErrorCode em format!() e println!().
@@ -329,10 +671,7 @@ impl fmt::Display for ErrorCode {
 
 /// Categoria de erro baseada no primeiro dígito do código.
 ///
-/// Útil para agrupar erros em relatórios ou dashboards.
-///
-/// NOTA: Enum preparado para uso futuro em relatórios estruturados.
-#[allow(dead_code)]
+/// Útil para agrupar erros em relatórios ou dashboards (ver `triage`).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ErrorCategory {
     /// Erros de validação/parsing (E1xxx).
@@ -360,6 +699,20 @@ pub enum ErrorCategory {
     Unknown,
 }
 
+impl ErrorCategory {
+    /// Nome estável para JSON (`summary.failures[].category`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Validation => "validation",
+            Self::HttpExecution => "http_execution",
+            Self::Assertion => "assertion",
+            Self::Configuration => "configuration",
+            Self::Internal => "internal",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
 /// Implementação de Display para ErrorCategory.
 impl fmt::Display for ErrorCategory {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -490,6 +843,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_and_explain() {
+        assert_eq!(
+            ErrorCode::parse("E3010"),
+            Some(ErrorCode::EXTRACTION_PATH_NOT_FOUND)
+        );
+        assert_eq!(
+            ErrorCode::parse("e1013"),
+            Some(ErrorCode::DUPLICATE_STEP_ID)
+        );
+        assert_eq!(ErrorCode::parse("2001"), Some(ErrorCode::HTTP_TIMEOUT));
+        assert_eq!(ErrorCode::parse("E9999"), None);
+        assert_eq!(ErrorCode::parse("abc"), None);
+
+        let text = ErrorCode::EXTRACTION_PATH_NOT_FOUND.explain();
+        assert!(text.starts_with("E3010 EXTRACTION_PATH_NOT_FOUND"));
+        assert!(text.contains("Categoria: Assertion (E3xxx)"));
+        assert!(text.contains("Causas comuns:"));
+        assert!(text.contains("Como resolver:"));
+    }
+
+    #[test]
+    fn test_every_code_has_description() {
+        for code in ErrorCode::all() {
+            assert_ne!(code.description(), "Erro desconhecido", "{}", code);
+            assert_ne!(code.category(), ErrorCategory::Unknown, "{}", code);
+        }
+    }
+
     #[test]
     fn test_structured_error_display() {
         let err = StructuredError::new(
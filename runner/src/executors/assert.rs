@@ -0,0 +1,336 @@
+//! # Executor Assert - Verificações sobre o Contexto
+//!
+//! Este executor avalia assertions **sem fazer requisição**, usando apenas
+//! as variáveis já presentes no contexto (extraídas por steps anteriores).
+//!
+//! ## Para todos entenderem:
+//!
+//! Depois de ramos paralelos (ex: criar pedido e consultar estoque ao
+//! mesmo tempo), queremos comparar os resultados dos dois. O step `assert`
+//! é um "ponto de encontro" que depende dos dois ramos e verifica as
+//! variáveis extraídas, aparecendo no relatório como um step próprio.
+//!
+//! ## Exemplo de uso no UTDL:
+//!
+//! ```json
+//! {
+//!   "id": "conferir_totais",
+//!   "action": "assert",
+//!   "depends_on": ["criar_pedido", "consultar_carrinho"],
+//!   "assertions": [
+//!     { "type": "variable", "path": "order_total", "operator": "eq", "value": "${cart_total}" },
+//!     { "type": "variable", "path": "order.status", "operator": "matches_regex", "value": "^(PAID|PENDING)$" },
+//!     { "type": "variable", "path": "coupon", "operator": "not_exists", "value": null }
+//!   ]
+//! }
+//! ```
+//!
+//! ## Caminhos:
+//!
+//! `path` é o nome da variável. Se não existir uma variável com o nome
+//! completo, o trecho após o primeiro ponto navega dentro do valor
+//! (`order.status` → campo `status` da variável `order`).
+//!
+//! ## Operadores:
+//!
+//! `eq`, `neq`, `gt`, `lt`, `gte`, `lte`, `contains`, `matches_regex`,
+//! `exists`, `not_exists` e os semânticos de `value_operators` (`semver_gte`,
+//! `date_before`, `uuid_valid`...), além de `approx_eq` com `tolerance`.
+//! O `value` é interpolado, então pode referenciar outra variável
+//! (`"${cart_total}"`) preservando o tipo.
+//! Com `locale` (ex: `{ "decimal": "," }`), variáveis extraídas como
+//! `"1.234,56"` são comparadas como número.
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use regex::Regex;
+use serde_json::{json, Value};
+use std::time::Instant;
+use tracing::{info, instrument, warn};
+
+use crate::context::Context;
+use crate::protocol::{Assertion, Step, StepCondition, StepResult, StepStatus, Tolerance};
+
+use super::value_operators::{approx_eq, evaluate_semantic, localize};
+use super::StepExecutor;
+
+// ============================================================================
+// FUNÇÕES AUXILIARES
+// ============================================================================
+
+/// Resolve um caminho de variável no contexto.
+///
+/// Tenta o nome completo primeiro; senão, usa o primeiro segmento como
+/// variável e o restante como caminho dentro do valor.
+fn resolve_path<'a>(context: &'a Context, path: &str) -> Option<&'a Value> {
+    if let Some(value) = context.get(path) {
+        return Some(value);
+    }
+
+    let (name, rest) = path.split_once('.')?;
+    let root = context.get(name)?;
+    root.pointer(&format!("/{}", rest.replace('.', "/")))
+}
+
+/// Compara dois valores numéricos com a função informada.
+fn compare_numbers<F>(actual: &Value, expected: &Value, cmp: F) -> bool
+where
+    F: Fn(f64, f64) -> bool,
+{
+    match (actual.as_f64(), expected.as_f64()) {
+        (Some(a), Some(b)) => cmp(a, b),
+        _ => false,
+    }
+}
+
+/// Avalia um operador entre o valor atual e o esperado (também usado pelo `grpc_call`).
+pub fn evaluate(
+    operator: &str,
+    actual: &Value,
+    expected: &Value,
+    tolerance: Option<&Tolerance>,
+) -> bool {
+    match operator {
+        "eq" => actual == expected,
+        "neq" => actual != expected,
+        "gt" => compare_numbers(actual, expected, |a, b| a > b),
+        "lt" => compare_numbers(actual, expected, |a, b| a < b),
+        "gte" | "ge" => compare_numbers(actual, expected, |a, b| a >= b),
+        "lte" | "le" => compare_numbers(actual, expected, |a, b| a <= b),
+        "contains" => match actual {
+            Value::String(s) => expected.as_str().map(|n| s.contains(n)).unwrap_or(false),
+            Value::Array(items) => items.contains(expected),
+            _ => false,
+        },
+        "matches_regex" | "regex" => match (actual.as_str(), expected.as_str()) {
+            (Some(s), Some(pattern)) => match Regex::new(pattern) {
+                Ok(re) => re.is_match(s),
+                Err(_) => {
+                    warn!(pattern = %pattern, "Invalid regex pattern in assertion");
+                    false
+                }
+            },
+            _ => false,
+        },
+        "exists" => true,
+        "approx_eq" => approx_eq(actual, expected, tolerance),
+        other => evaluate_semantic(other, actual, expected).unwrap_or(false),
+    }
+}
+
+/// Avalia a condição de um step (`Step.condition`) sobre o contexto.
+///
+/// Variável ausente torna a condição falsa, exceto com `not_exists`.
+pub fn condition_holds(condition: &StepCondition, context: &Context) -> Result<bool> {
+    let expected = context.interpolate_value_typed(&condition.value)?;
+    Ok(match resolve_path(context, &condition.path) {
+        None => condition.operator == "not_exists",
+        Some(_) if condition.operator == "not_exists" => false,
+        Some(actual) => evaluate(&condition.operator, actual, &expected, None),
+    })
+}
+
+// ============================================================================
+// ASSERT EXECUTOR
+// ============================================================================
+
+/// Executor para a ação `assert`.
+///
+/// Não altera o contexto; apenas lê variáveis.
+#[derive(Debug, Default)]
+pub struct AssertExecutor;
+
+impl AssertExecutor {
+    /// Cria um novo AssertExecutor.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Avalia as assertions e retorna a mensagem da primeira que falhar.
+    ///
+    /// Erros de interpolação do `value` são propagados como `Err`.
+    fn check_assertions(assertions: &[Assertion], context: &Context) -> Result<Option<String>> {
+        for assertion in assertions {
+            if assertion.assertion_type != "variable" {
+                return Ok(Some(format!(
+                    "Assertion type '{}' não suportado em assert (use 'variable')",
+                    assertion.assertion_type
+                )));
+            }
+
+            let path = assertion
+                .path
+                .as_deref()
+                .ok_or_else(|| anyhow!("Assertion 'variable' requer 'path'"))?;
+            let expected = context.interpolate_value_typed(&assertion.value)?;
+
+            match resolve_path(context, path) {
+                None if assertion.operator == "not_exists" => continue,
+                None => {
+                    return Ok(Some(format!(
+                        "Assertion failed: variable '{}' not found in context",
+                        path
+                    )));
+                }
+                Some(actual) => {
+                    let actual = localize(actual, assertion.locale.as_ref());
+                    if !evaluate(
+                        &assertion.operator,
+                        &actual,
+                        &expected,
+                        assertion.tolerance.as_ref(),
+                    ) {
+                        return Ok(Some(format!(
+                            "Assertion failed: variable '{}' {} {} (got {})",
+                            path, assertion.operator, expected, actual
+                        )));
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// JSON Schema dos params de `assert`: nenhum (as verificações ficam em `assertions`).
+pub fn params_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {},
+        "additionalProperties": false
+    })
+}
+
+// ============================================================================
+// IMPLEMENTAÇÃO DO TRAIT
+// ============================================================================
+
+#[async_trait]
+impl StepExecutor for AssertExecutor {
+    fn can_handle(&self, action: &str) -> bool {
+        action == "assert"
+    }
+
+    /// Schema dos params de `assert`, usado na validação do plano.
+    fn params_schema(&self, _action: &str) -> Option<Value> {
+        Some(params_schema())
+    }
+
+    #[instrument(skip(self, context), fields(step_id = %step.id))]
+    async fn execute(&self, step: &Step, context: &mut Context) -> Result<StepResult> {
+        let start = Instant::now();
+
+        let failure = Self::check_assertions(&step.assertions, context)?;
+
+        let status = if failure.is_some() {
+            StepStatus::Failed
+        } else {
+            StepStatus::Passed
+        };
+
+        info!(
+            step_id = %step.id,
+            assertions = step.assertions.len(),
+            status = ?status,
+            "🔎 Assertions de contexto avaliadas"
+        );
+
+        let snapshot = context.variables.clone();
+        Ok(StepResult {
+            step_id: step.id.clone(),
+            status,
+            duration_ms: start.elapsed().as_millis() as u64,
+            error: failure,
+            context_before: Some(snapshot.clone()),
+            context_after: Some(snapshot),
+            ..Default::default()
+        })
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn variable_assertion(path: &str, operator: &str, value: Value) -> Assertion {
+        Assertion {
+            assertion_type: "variable".to_string(),
+            operator: operator.to_string(),
+            value,
+            path: Some(path.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn create_step(assertions: Vec<Assertion>) -> Step {
+        Step {
+            id: "join_check".to_string(),
+            description: None,
+            depends_on: vec![],
+            action: "assert".to_string(),
+            params: json!({}),
+            assertions,
+            extract: vec![],
+            recovery_policy: None,
+            ..Default::default()
+        }
+    }
+
+    fn create_context() -> Context {
+        let mut context = Context::new();
+        context.set("order_total", json!(99.5));
+        context.set("cart_total", json!(99.5));
+        context.set("order", json!({ "status": "PAID", "items": ["a", "b"] }));
+        context
+    }
+
+    #[tokio::test]
+    async fn test_assert_compares_variables() {
+        let executor = AssertExecutor::new();
+        let mut context = create_context();
+
+        let step = create_step(vec![
+            variable_assertion("order_total", "eq", json!("${cart_total}")),
+            variable_assertion("order.status", "matches_regex", json!("^(PAID|PENDING)$")),
+            variable_assertion("order.items", "contains", json!("b")),
+            variable_assertion("order_total", "gt", json!(10)),
+            variable_assertion("coupon", "not_exists", Value::Null),
+        ]);
+
+        let result = executor.execute(&step, &mut context).await.unwrap();
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+    }
+
+    #[tokio::test]
+    async fn test_assert_reports_failure() {
+        let executor = AssertExecutor::new();
+        let mut context = create_context();
+
+        let step = create_step(vec![variable_assertion(
+            "order.status",
+            "eq",
+            json!("CANCELLED"),
+        )]);
+
+        let result = executor.execute(&step, &mut context).await.unwrap();
+        assert_eq!(result.status, StepStatus::Failed);
+        assert!(result.error.unwrap().contains("order.status"));
+    }
+
+    #[tokio::test]
+    async fn test_assert_missing_variable_fails() {
+        let executor = AssertExecutor::new();
+        let mut context = create_context();
+
+        let step = create_step(vec![variable_assertion("missing", "exists", Value::Null)]);
+
+        let result = executor.execute(&step, &mut context).await.unwrap();
+        assert_eq!(result.status, StepStatus::Failed);
+    }
+}
@@ -1,6 +1,37 @@
-//! # GraphQL Executor - Plugin de Exemplo
+//! # GraphQL Executor - Plugin de Exemplo e `graphql_request`
 //!
-//! Este módulo demonstra como criar um executor customizado para GraphQL.
+//! Este módulo demonstra como criar um executor customizado para GraphQL
+//! e define a action embutida `graphql_request`.
+//!
+//! ## `graphql_request` (embutida):
+//!
+//! Escrever GraphQL como `http_request` exige montar o body, o header e um
+//! JSONPath `$.data...` em cada extração. Com `graphql_request`:
+//!
+//! ```json
+//! {
+//!   "id": "get_user",
+//!   "action": "graphql_request",
+//!   "params": {
+//!     "query": "query GetUser($id: ID!) { user(id: $id) { id name } }",
+//!     "variables": { "id": "${user_id}" },
+//!     "operation_name": "GetUser"
+//!   },
+//!   "extract": [{ "source": "body", "path": "user.name", "target": "user_name" }]
+//! }
+//! ```
+//!
+//! | Comportamento                    | Detalhe                                        |
+//! |----------------------------------|------------------------------------------------|
+//! | Requisição                       | `POST` em `endpoint` (padrão `/graphql`), JSON |
+//! | `200` com `errors`               | Falha, a menos que o step teste `graphql_errors` |
+//! | Path sem `$` (extração, `json_body`) | Relativo a `data`: `user.name` → `$.data.user.name` |
+//! | Demais params (`headers`, `session`, `timeout`...) | Os mesmos do `http_request` |
+//!
+//! O step é executado pelo `HttpExecutor` (ver `to_http_step`), então
+//! autenticação, sessões, retries e `http_details` funcionam igual.
+//!
+//! ## Plugin de exemplo:
 //!
 //! ## Para desenvolvedores de plugins:
 //!
@@ -50,8 +81,9 @@
 //! ```
 
 use crate::context::Context;
+use crate::executors::graphql_errors;
 use crate::executors::StepExecutor;
-use crate::protocol::{Step, StepResult, StepStatus};
+use crate::protocol::{Assertion, Step, StepResult, StepStatus};
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use reqwest::Client;
@@ -141,6 +173,86 @@ impl GraphqlExecutor {
     }
 }
 
+// ============================================================================
+// GRAPHQL_REQUEST
+// ============================================================================
+
+/// Chaves de topo de uma resposta GraphQL (paths que não ganham `data.`).
+const TOP_LEVEL_KEYS: &[&str] = &["data", "errors", "extensions"];
+
+/// Path relativo a `data` (paths com `$`, `/` ou `regex:` ficam como estão).
+pub fn data_path(path: &str) -> String {
+    let root = path.split(['.', '[']).next().unwrap_or_default();
+    if path.starts_with(['$', '/']) || path.starts_with("regex:") || TOP_LEVEL_KEYS.contains(&root)
+    {
+        path.to_string()
+    } else {
+        format!("$.data.{}", path)
+    }
+}
+
+/// Converte um step `graphql_request` no `http_request` equivalente.
+///
+/// `query`, `variables` e `operation_name` (ou `operationName`) viram o
+/// body; `endpoint` vira o `path`. Sem assertion sobre os erros, é
+/// acrescentada `graphql_errors not_exists`.
+pub fn to_http_step(step: &Step) -> Result<Step> {
+    let mut params = step
+        .params
+        .as_object()
+        .cloned()
+        .ok_or_else(|| anyhow!("params de graphql_request deve ser um objeto"))?;
+
+    let query = params
+        .remove("query")
+        .and_then(|q| q.as_str().map(String::from))
+        .ok_or_else(|| anyhow!("Missing 'query' in GraphQL params"))?;
+    let variables = params.remove("variables").unwrap_or(json!({}));
+    let operation_name = params
+        .remove("operation_name")
+        .or_else(|| params.remove("operationName"))
+        .and_then(|o| o.as_str().map(String::from));
+    let endpoint = params
+        .remove("endpoint")
+        .unwrap_or_else(|| json!("/graphql"));
+
+    params.insert("method".to_string(), json!("POST"));
+    params.insert("path".to_string(), endpoint);
+    params.insert(
+        "body".to_string(),
+        GraphqlExecutor::build_request_body(&query, variables, operation_name),
+    );
+
+    let mut http = step.clone();
+    http.action = "http_request".to_string();
+    http.params = Value::Object(params);
+    for assertion in &mut http.assertions {
+        if assertion.assertion_type == "json_body" {
+            assertion.path = assertion.path.as_deref().map(data_path);
+        }
+    }
+    for extraction in &mut http.extract {
+        if extraction.source.eq_ignore_ascii_case("body") {
+            extraction.path = data_path(&extraction.path);
+        }
+    }
+
+    // Um 200 com `errors` reprova o step, a menos que o plano os espere.
+    let checks_errors = http.assertions.iter().any(|a| {
+        a.assertion_type == "graphql_errors"
+            || a.path.as_deref().is_some_and(|p| p.starts_with("$.errors"))
+    });
+    if !checks_errors {
+        http.assertions.push(Assertion {
+            assertion_type: "graphql_errors".to_string(),
+            operator: "not_exists".to_string(),
+            value: Value::Null,
+            ..Default::default()
+        });
+    }
+    Ok(http)
+}
+
 /// Extrai valor de JSON usando path simplificado ($.data.user.name).
 fn jsonpath_select(json: &Value, path: &str) -> Option<Value> {
     let path = path.trim_start_matches("$.");
@@ -171,14 +283,38 @@ fn jsonpath_select(json: &Value, path: &str) -> Option<Value> {
     Some(current.clone())
 }
 
+/// JSON Schema dos params de `graphql_request`: os de `http_request`, exceto o que vira a requisição
+/// GraphQL (`method`, `path`, `body`...).
+pub fn params_schema() -> Value {
+    let mut schema = super::http::params_schema();
+    let properties = schema["properties"]
+        .as_object_mut()
+        .expect("schema de http_request");
+    for generated in [
+        "method",
+        "path",
+        "body",
+        "body_file",
+        "body_file_interpolate",
+    ] {
+        properties.remove(generated);
+    }
+    properties.insert("query".to_string(), json!({ "type": "string" }));
+    properties.insert("variables".to_string(), json!({ "type": "object" }));
+    properties.insert("operation_name".to_string(), json!({ "type": "string" }));
+    properties.insert("operationName".to_string(), json!({ "type": "string" }));
+    properties.insert("endpoint".to_string(), json!({ "type": "string" }));
+    schema["required"] = json!(["query"]);
+    schema
+}
+
 #[async_trait]
 impl StepExecutor for GraphqlExecutor {
-    /// Aceita action "graphql" ou "graphql_request".
+    /// Aceita as actions do plugin ("graphql", "graphql_query", "graphql_mutation").
+    ///
+    /// `graphql_request` é embutida e atendida pelo `HttpExecutor`.
     fn can_handle(&self, action: &str) -> bool {
-        matches!(
-            action,
-            "graphql" | "graphql_request" | "graphql_query" | "graphql_mutation"
-        )
+        matches!(action, "graphql" | "graphql_query" | "graphql_mutation")
     }
 
     /// Executa requisição GraphQL.
@@ -229,10 +365,11 @@ impl StepExecutor for GraphqlExecutor {
 
         // Adiciona verificação implícita: sem erros GraphQL (se não esperados)
         let expects_errors = step.assertions.iter().any(|x| {
-            x.path
-                .as_ref()
-                .map(|p| p.contains("errors"))
-                .unwrap_or(false)
+            x.assertion_type == "graphql_errors"
+                || x.path
+                    .as_ref()
+                    .map(|p| p.contains("errors"))
+                    .unwrap_or(false)
         });
 
         if !graphql_errors.is_empty() && !expects_errors {
@@ -242,6 +379,15 @@ impl StepExecutor for GraphqlExecutor {
 
         // Processa assertions do step
         for assertion in &step.assertions {
+            if assertion.assertion_type == "graphql_errors" {
+                if let Some(error) = graphql_errors::check_graphql_errors(assertion, &response_body)
+                {
+                    all_passed = false;
+                    error_message = Some(error);
+                }
+                continue;
+            }
+
             let passed = match assertion.assertion_type.as_str() {
                 "status_code" => {
                     let expected = assertion.value.as_u64().unwrap_or(0) as u16;
@@ -298,6 +444,7 @@ impl StepExecutor for GraphqlExecutor {
             context_after: None,
             extractions: None,
             http_details: None, // TODO: Adicionar detalhes GraphQL futuramente
+            ..Default::default()
         })
     }
 }
@@ -311,12 +458,72 @@ mod tests {
         let executor = GraphqlExecutor::new("http://localhost:4000".to_string());
 
         assert!(executor.can_handle("graphql"));
-        assert!(executor.can_handle("graphql_request"));
+        assert!(!executor.can_handle("graphql_request"));
         assert!(executor.can_handle("graphql_query"));
         assert!(executor.can_handle("graphql_mutation"));
         assert!(!executor.can_handle("http_request"));
     }
 
+    #[test]
+    fn test_to_http_step_builds_post_and_data_paths() {
+        let step: Step = serde_json::from_value(json!({
+            "id": "get_user",
+            "action": "graphql_request",
+            "params": {
+                "query": "query GetUser($id: ID!) { user(id: $id) { name } }",
+                "variables": { "id": "${user_id}" },
+                "operation_name": "GetUser",
+                "headers": { "X-Tenant": "acme" }
+            },
+            "assertions": [
+                { "type": "json_body", "operator": "eq", "path": "user.name", "value": "John" }
+            ],
+            "extract": [
+                { "source": "body", "path": "user.name", "target": "name" },
+                { "source": "body", "path": "$.extensions.cost", "target": "cost" }
+            ]
+        }))
+        .unwrap();
+
+        let http = to_http_step(&step).unwrap();
+        assert_eq!(http.action, "http_request");
+        assert_eq!(http.params["method"], "POST");
+        assert_eq!(http.params["path"], "/graphql");
+        assert_eq!(http.params["headers"]["X-Tenant"], "acme");
+        assert_eq!(http.params["body"]["operationName"], "GetUser");
+        assert_eq!(http.params["body"]["variables"]["id"], "${user_id}");
+        assert_eq!(http.assertions[0].path.as_deref(), Some("$.data.user.name"));
+        assert_eq!(http.extract[0].path, "$.data.user.name");
+        assert_eq!(http.extract[1].path, "$.extensions.cost");
+
+        // Sem assertion sobre erros, um 200 com `errors` falha.
+        assert_eq!(http.assertions[1].assertion_type, "graphql_errors");
+        assert_eq!(http.assertions[1].operator, "not_exists");
+    }
+
+    #[test]
+    fn test_to_http_step_keeps_explicit_error_checks() {
+        let step: Step = serde_json::from_value(json!({
+            "id": "forbidden",
+            "action": "graphql_request",
+            "params": { "query": "{ secret }", "endpoint": "/api/graphql" },
+            "assertions": [
+                { "type": "graphql_errors", "operator": "contains", "value": "FORBIDDEN" }
+            ]
+        }))
+        .unwrap();
+
+        let http = to_http_step(&step).unwrap();
+        assert_eq!(http.params["path"], "/api/graphql");
+        assert_eq!(http.assertions.len(), 1);
+        assert_eq!(data_path("errors[0].message"), "errors[0].message");
+        assert!(to_http_step(&Step {
+            params: json!({}),
+            ..step
+        })
+        .is_err());
+    }
+
     #[test]
     fn test_extract_query() {
         let params = json!({
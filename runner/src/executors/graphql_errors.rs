@@ -0,0 +1,206 @@
+//! # Assertions `graphql_errors` - Erros de GraphQL sem JSONPath Manual
+//!
+//! Usada pelo `HttpExecutor` (GraphQL sobre `http_request`) e pelo
+//! `GraphqlExecutor`.
+//!
+//! ## Para todos entenderem:
+//!
+//! GraphQL responde `200 OK` mesmo quando a operação falha: os erros vêm
+//! em `errors`. Testar `$.errors[0].extensions.code` na mão quebra quando a
+//! ordem dos erros muda. Esta assertion olha para **todos** os erros:
+//!
+//! | Operador      | `value`        | Passa quando                                  |
+//! |---------------|----------------|-----------------------------------------------|
+//! | `not_exists`  | -              | Nenhum erro                                   |
+//! | `exists`      | -              | Pelo menos um erro                            |
+//! | `contains`    | `"FORBIDDEN"`  | Algum erro tem `extensions.code` = FORBIDDEN  |
+//! | `neq`         | `"FORBIDDEN"`  | Nenhum erro tem esse código                   |
+//! | `eq`          | `"FORBIDDEN"`  | Há erros e todos têm esse código              |
+//! | `eq`, `lt`... | número         | Compara a quantidade de erros                 |
+//!
+//! Com `path`, só contam os erros daquele campo (`"path": "user.email"`
+//! casa com `["user", "email"]` e `["user", "email", 0]`).
+//!
+//! ## Exemplo:
+//!
+//! ```json
+//! { "type": "graphql_errors", "operator": "contains", "value": "FORBIDDEN", "path": "user.email" }
+//! ```
+
+use serde_json::Value;
+
+use crate::protocol::Assertion;
+
+/// Erro de GraphQL resumido (código e caminho).
+#[derive(Debug, PartialEq)]
+struct GraphqlError {
+    code: Option<String>,
+    path: String,
+}
+
+/// Valida uma assertion `graphql_errors` contra o body da resposta.
+pub fn check_graphql_errors(assertion: &Assertion, body: &Value) -> Option<String> {
+    let filter = assertion.path.as_deref().map(normalize_path);
+    let errors: Vec<GraphqlError> = collect_errors(body)
+        .into_iter()
+        .filter(|e| {
+            filter
+                .as_deref()
+                .is_none_or(|f| e.path == f || e.path.starts_with(&format!("{}.", f)))
+        })
+        .collect();
+    let codes: Vec<&str> = errors.iter().filter_map(|e| e.code.as_deref()).collect();
+
+    let operator = assertion.operator.as_str();
+    let passed = match (&assertion.value, operator) {
+        (_, "not_exists") => errors.is_empty(),
+        (_, "exists") => !errors.is_empty(),
+        (Value::Number(n), _) => {
+            let count = errors.len() as u64;
+            let expected = n.as_u64().unwrap_or(0);
+            match operator {
+                "eq" => count == expected,
+                "neq" => count != expected,
+                "lt" => count < expected,
+                "lte" | "le" => count <= expected,
+                "gt" => count > expected,
+                "gte" | "ge" => count >= expected,
+                other => return Some(unsupported(other)),
+            }
+        }
+        (Value::String(code), "contains") => codes.contains(&code.as_str()),
+        (Value::String(code), "neq") => !codes.contains(&code.as_str()),
+        (Value::String(code), "eq") => {
+            !errors.is_empty() && errors.iter().all(|e| e.code.as_deref() == Some(code))
+        }
+        (_, other) => return Some(unsupported(other)),
+    };
+
+    (!passed).then(|| {
+        let scope = assertion
+            .path
+            .as_ref()
+            .map(|p| format!(" at '{}'", p))
+            .unwrap_or_default();
+        format!(
+            "Assertion failed: graphql_errors{} {} {} (got {} error(s), codes {:?})",
+            scope,
+            assertion.operator,
+            assertion.value,
+            errors.len(),
+            codes
+        )
+    })
+}
+
+fn unsupported(operator: &str) -> String {
+    format!(
+        "Assertion failed: operator '{}' is not supported for graphql_errors",
+        operator
+    )
+}
+
+/// Lê `errors[]` com `extensions.code` e `path` juntado por pontos.
+fn collect_errors(body: &Value) -> Vec<GraphqlError> {
+    let Some(errors) = body.get("errors").and_then(|e| e.as_array()) else {
+        return Vec::new();
+    };
+    errors
+        .iter()
+        .map(|error| GraphqlError {
+            code: error
+                .pointer("/extensions/code")
+                .and_then(|c| c.as_str())
+                .map(String::from),
+            path: error
+                .get("path")
+                .and_then(|p| p.as_array())
+                .map(|parts| {
+                    parts
+                        .iter()
+                        .map(|p| match p {
+                            Value::String(s) => s.clone(),
+                            other => other.to_string(),
+                        })
+                        .collect::<Vec<_>>()
+                        .join(".")
+                })
+                .unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// `$.user.items[0].name` → `user.items.0.name`.
+fn normalize_path(path: &str) -> String {
+    path.trim_start_matches("$.")
+        .replace('[', ".")
+        .replace(']', "")
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn assertion(operator: &str, value: Value, path: Option<&str>) -> Assertion {
+        Assertion {
+            assertion_type: "graphql_errors".to_string(),
+            operator: operator.to_string(),
+            value,
+            path: path.map(String::from),
+            ..Default::default()
+        }
+    }
+
+    fn body() -> Value {
+        json!({
+            "data": { "user": null },
+            "errors": [
+                { "message": "Not allowed", "path": ["user", "email"], "extensions": { "code": "FORBIDDEN" } },
+                { "message": "Bad id", "path": ["orders", 0, "id"], "extensions": { "code": "BAD_USER_INPUT" } }
+            ]
+        })
+    }
+
+    #[test]
+    fn test_no_errors_and_counts() {
+        let ok = json!({ "data": { "user": { "id": 1 } } });
+        assert!(check_graphql_errors(&assertion("not_exists", Value::Null, None), &ok).is_none());
+        assert!(
+            check_graphql_errors(&assertion("not_exists", Value::Null, None), &body()).is_some()
+        );
+        assert!(check_graphql_errors(&assertion("eq", json!(2), None), &body()).is_none());
+    }
+
+    #[test]
+    fn test_codes_in_any_order() {
+        assert!(check_graphql_errors(
+            &assertion("contains", json!("BAD_USER_INPUT"), None),
+            &body()
+        )
+        .is_none());
+        assert!(
+            check_graphql_errors(&assertion("neq", json!("INTERNAL"), None), &body()).is_none()
+        );
+
+        let error =
+            check_graphql_errors(&assertion("eq", json!("FORBIDDEN"), None), &body()).unwrap();
+        assert!(error.contains("BAD_USER_INPUT"), "{}", error);
+    }
+
+    #[test]
+    fn test_path_specific_errors() {
+        let user_email = assertion("eq", json!("FORBIDDEN"), Some("user.email"));
+        assert!(check_graphql_errors(&user_email, &body()).is_none());
+
+        let order = assertion("contains", json!("BAD_USER_INPUT"), Some("$.orders[0]"));
+        assert!(check_graphql_errors(&order, &body()).is_none());
+
+        let user = assertion("neq", json!("BAD_USER_INPUT"), Some("user"));
+        assert!(check_graphql_errors(&user, &body()).is_none());
+    }
+}
@@ -0,0 +1,927 @@
+//! # Executor gRPC - Chamadas Unárias com Mensagens Dinâmicas
+//!
+//! Este executor faz uma chamada unária a um serviço gRPC interno, com a
+//! mensagem escrita em JSON no plano, e verifica o `grpc-status` e os
+//! campos da resposta, do mesmo jeito que um step HTTP verifica o body.
+//!
+//! ## Para todos entenderem:
+//!
+//! gRPC não manda JSON: a mensagem é protobuf binário, e para montá-la é
+//! preciso saber o formato (o `.proto`). O executor descobre o formato de
+//! um de dois jeitos:
+//!
+//! | Fonte              | Como                                                     |
+//! |--------------------|----------------------------------------------------------|
+//! | `descriptor_set`   | Arquivo gerado com `protoc --descriptor_set_out=orders.pb --include_imports` |
+//! | Reflection         | Sem `descriptor_set`, pergunta ao próprio servidor (`grpc.reflection.v1`/`v1alpha`) |
+//!
+//! ```json
+//! {
+//!   "id": "get_order",
+//!   "action": "grpc_call",
+//!   "params": {
+//!     "host": "orders.internal", "port": 50051,
+//!     "method": "orders.v1.Orders/GetOrder",
+//!     "message": { "id": "${order_id}" },
+//!     "metadata": { "authorization": "Bearer ${token}" }
+//!   },
+//!   "assertions": [
+//!     { "type": "grpc_status", "operator": "eq", "value": "OK" },
+//!     { "type": "message", "path": "status", "operator": "eq", "value": "PAID" }
+//!   ],
+//!   "extract": [{ "source": "message", "path": "$.total_cents", "target": "total" }]
+//! }
+//! ```
+//!
+//! ## Parâmetros:
+//!
+//! | Parâmetro        | Descrição                                                 |
+//! |------------------|-----------------------------------------------------------|
+//! | `host`, `port`   | Servidor gRPC (h2c, sem TLS)                              |
+//! | `method`         | `pacote.Servico/Metodo` (obrigatório)                     |
+//! | `message`        | Requisição em JSON (interpolada; padrão `{}`)             |
+//! | `metadata`       | Metadados enviados (headers gRPC), interpolados           |
+//! | `descriptor_set` | `FileDescriptorSet` binário (relativo ao plano)           |
+//! | `timeout_ms`     | Orçamento da chamada inteira (padrão: config ou 5000)     |
+//!
+//! ## Assertions e extrações:
+//!
+//! - `grpc_status`: `eq`, `neq`, `in`, `not_in` com o nome (`"NOT_FOUND"`)
+//!   ou o número (`5`); sem nenhuma, o step falha se o status não for `OK`
+//! - `message`: campo da resposta (`path` como no `json_body`, nomes do
+//!   `.proto`) com os operadores da ação `assert`
+//! - Extração de `message` (JSONPath ou `regex:`) e de `grpc_status` (nome)
+//!
+//! A resposta também fica em `${<step_id>.response}` e o status em
+//! `${<step_id>.grpc_status}`. Inteiros de 64 bits viram números JSON.
+//!
+//! ## Limitações:
+//!
+//! - Só chamadas unárias (sem streaming) e só h2c, como o `grpc_health`.
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use async_trait::async_trait;
+use prost::bytes::{Buf, BufMut};
+use prost::Message as _;
+use prost_reflect::{DescriptorPool, DynamicMessage, MethodDescriptor, SerializeOptions};
+use prost_types::{FileDescriptorProto, FileDescriptorSet};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder};
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::metadata::{MetadataKey, MetadataValue};
+use tonic::transport::{Channel, Endpoint};
+use tonic::{Code, Status};
+use tracing::{info, instrument};
+
+use crate::context::Context;
+use crate::extractors::{ExtractionResult, Extractor};
+use crate::protocol::{Assertion, Extraction, SocketDetails, Step, StepResult, StepStatus};
+
+use super::assert::evaluate;
+use super::http::json_pointer;
+use super::tcp::hex;
+use super::value_operators::localize;
+use super::StepExecutor;
+
+/// Nomes dos códigos de `grpc-status`, na ordem numérica.
+const STATUS_NAMES: [&str; 17] = [
+    "OK",
+    "CANCELLED",
+    "UNKNOWN",
+    "INVALID_ARGUMENT",
+    "DEADLINE_EXCEEDED",
+    "NOT_FOUND",
+    "ALREADY_EXISTS",
+    "PERMISSION_DENIED",
+    "RESOURCE_EXHAUSTED",
+    "FAILED_PRECONDITION",
+    "ABORTED",
+    "OUT_OF_RANGE",
+    "UNIMPLEMENTED",
+    "INTERNAL",
+    "UNAVAILABLE",
+    "DATA_LOSS",
+    "UNAUTHENTICATED",
+];
+
+/// Métodos de reflection tentados, do mais novo ao mais antigo.
+const REFLECTION_PATHS: [&str; 2] = [
+    "/grpc.reflection.v1.ServerReflection/ServerReflectionInfo",
+    "/grpc.reflection.v1alpha.ServerReflection/ServerReflectionInfo",
+];
+
+/// Nome do código de `grpc-status` (ex: 5 → `NOT_FOUND`).
+pub fn status_name(code: Code) -> &'static str {
+    STATUS_NAMES
+        .get(code as usize)
+        .copied()
+        .unwrap_or("UNKNOWN")
+}
+
+// ============================================================================
+// CODEC E REFLECTION
+// ============================================================================
+
+/// Codec de bytes crus: a (de)serialização fica com o `prost-reflect`.
+#[derive(Debug, Clone, Copy, Default)]
+struct RawCodec;
+
+impl Codec for RawCodec {
+    type Encode = Vec<u8>;
+    type Decode = Vec<u8>;
+    type Encoder = RawCodec;
+    type Decoder = RawCodec;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        RawCodec
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        RawCodec
+    }
+}
+
+impl Encoder for RawCodec {
+    type Item = Vec<u8>;
+    type Error = Status;
+
+    fn encode(&mut self, item: Vec<u8>, dst: &mut EncodeBuf<'_>) -> Result<(), Status> {
+        dst.put_slice(&item);
+        Ok(())
+    }
+}
+
+impl Decoder for RawCodec {
+    type Item = Vec<u8>;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Vec<u8>>, Status> {
+        Ok(Some(src.copy_to_bytes(src.remaining()).to_vec()))
+    }
+}
+
+/// `ServerReflectionRequest` (só as consultas usadas).
+#[derive(Clone, PartialEq, prost::Message)]
+struct ReflectionRequest {
+    #[prost(string, optional, tag = "3")]
+    file_by_filename: Option<String>,
+    #[prost(string, optional, tag = "4")]
+    file_containing_symbol: Option<String>,
+}
+
+/// `ServerReflectionResponse` (só as respostas usadas).
+#[derive(Clone, PartialEq, prost::Message)]
+struct ReflectionResponse {
+    #[prost(message, optional, tag = "4")]
+    file_descriptor_response: Option<FileDescriptorResponse>,
+    #[prost(message, optional, tag = "7")]
+    error_response: Option<ReflectionError>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct FileDescriptorResponse {
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    file_descriptor_proto: Vec<Vec<u8>>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ReflectionError {
+    #[prost(int32, tag = "1")]
+    error_code: i32,
+    #[prost(string, tag = "2")]
+    error_message: String,
+}
+
+/// Uma consulta de reflection; tenta `v1` e cai para `v1alpha` se não existir.
+async fn reflection_query(
+    channel: &Channel,
+    request: ReflectionRequest,
+) -> Result<Vec<FileDescriptorProto>> {
+    let bytes = request.encode_to_vec();
+    for path in REFLECTION_PATHS {
+        let mut grpc = tonic::client::Grpc::new(channel.clone());
+        grpc.ready().await?;
+        let outgoing = futures::stream::iter([bytes.clone()]);
+        let response = match grpc
+            .streaming(
+                tonic::Request::new(outgoing),
+                PathAndQuery::from_static(path),
+                RawCodec,
+            )
+            .await
+        {
+            Err(status) if status.code() == Code::Unimplemented => continue,
+            Err(status) => bail!("Reflection falhou: {}", status.message()),
+            Ok(response) => response,
+        };
+        let reply = response
+            .into_inner()
+            .message()
+            .await?
+            .ok_or_else(|| anyhow!("Reflection sem resposta"))?;
+        let reply = ReflectionResponse::decode(reply.as_slice())?;
+        if let Some(error) = reply.error_response {
+            bail!(
+                "Reflection: {} (código {})",
+                error.error_message,
+                error.error_code
+            );
+        }
+        return reply
+            .file_descriptor_response
+            .map(|files| files.file_descriptor_proto)
+            .unwrap_or_default()
+            .iter()
+            .map(|file| Ok(FileDescriptorProto::decode(file.as_slice())?))
+            .collect();
+    }
+    bail!("Servidor sem reflection (grpc.reflection.v1/v1alpha); informe 'descriptor_set'")
+}
+
+/// Monta o pool com o arquivo que define `service` e suas dependências.
+async fn reflect(channel: &Channel, service: &str) -> Result<DescriptorPool> {
+    let mut files: HashMap<String, FileDescriptorProto> = HashMap::new();
+    let mut pending = vec![ReflectionRequest {
+        file_containing_symbol: Some(service.to_string()),
+        ..Default::default()
+    }];
+    while let Some(request) = pending.pop() {
+        for file in reflection_query(channel, request).await? {
+            files.entry(file.name().to_string()).or_insert(file);
+        }
+        let requested: HashSet<_> = pending
+            .iter()
+            .filter_map(|r| r.file_by_filename.clone())
+            .collect();
+        let missing: Vec<String> = files
+            .values()
+            .flat_map(|file| file.dependency.iter())
+            .filter(|dep| !files.contains_key(*dep) && !requested.contains(*dep))
+            .cloned()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        pending.extend(missing.into_iter().map(|name| ReflectionRequest {
+            file_by_filename: Some(name),
+            ..Default::default()
+        }));
+    }
+    Ok(DescriptorPool::from_file_descriptor_set(
+        FileDescriptorSet {
+            file: files.into_values().collect(),
+        },
+    )?)
+}
+
+/// Encontra `pacote.Servico/Metodo` (ou `pacote.Servico.Metodo`) no pool.
+fn find_method(pool: &DescriptorPool, method: &str) -> Result<MethodDescriptor> {
+    let (service, name) = method
+        .split_once('/')
+        .or_else(|| method.rsplit_once('.'))
+        .ok_or_else(|| {
+            anyhow!(
+                "'method' deve ser 'pacote.Servico/Metodo' (recebido '{}')",
+                method
+            )
+        })?;
+    let service = pool
+        .get_service_by_name(service.trim_start_matches('.'))
+        .ok_or_else(|| anyhow!("Serviço '{}' não encontrado nos descritores", service))?;
+    let method = service
+        .methods()
+        .find(|m| m.name() == name)
+        .ok_or_else(|| anyhow!("Método '{}' não existe em '{}'", name, service.full_name()))?;
+    if method.is_client_streaming() || method.is_server_streaming() {
+        bail!(
+            "'{}' é streaming; grpc_call só faz chamadas unárias",
+            method.full_name()
+        );
+    }
+    Ok(method)
+}
+
+// ============================================================================
+// ASSERTIONS E EXTRAÇÕES
+// ============================================================================
+
+/// Resultado de uma chamada: status e resposta (JSON, quando `OK`).
+#[derive(Debug, Clone)]
+pub struct CallOutcome {
+    pub status: Code,
+    pub status_message: String,
+    pub response: Value,
+    pub received: Vec<u8>,
+}
+
+/// `grpc_status` esperado: nome (`"NOT_FOUND"`) ou número (`5`).
+fn expected_status(value: &Value) -> Option<&'static str> {
+    match value {
+        Value::String(name) => STATUS_NAMES
+            .iter()
+            .find(|n| n.eq_ignore_ascii_case(name))
+            .copied(),
+        Value::Number(n) => STATUS_NAMES.get(n.as_u64()? as usize).copied(),
+        _ => None,
+    }
+}
+
+fn compare_status(assertion: &Assertion, actual: &str) -> Option<bool> {
+    let listed = || -> Option<bool> {
+        let values = assertion.value.as_array()?;
+        Some(values.iter().any(|v| expected_status(v) == Some(actual)))
+    };
+    Some(match assertion.operator.as_str() {
+        "eq" => expected_status(&assertion.value)? == actual,
+        "neq" => expected_status(&assertion.value)? != actual,
+        "in" => listed()?,
+        "not_in" => !listed()?,
+        _ => return None,
+    })
+}
+
+/// Avalia as assertions sobre a chamada; retorna a primeira falha.
+pub fn check_call(assertions: &[Assertion], outcome: &CallOutcome) -> Option<String> {
+    let status = status_name(outcome.status);
+    let checks_status = assertions.iter().any(|a| a.assertion_type == "grpc_status");
+    if !checks_status && outcome.status != Code::Ok {
+        return Some(format!(
+            "grpc-status {} ({}): {}",
+            status, outcome.status as i32, outcome.status_message
+        ));
+    }
+
+    assertions
+        .iter()
+        .find_map(|assertion| match assertion.assertion_type.as_str() {
+            "grpc_status" => match compare_status(assertion, status) {
+                Some(true) => None,
+                Some(false) => Some(format!(
+                    "Assertion failed: grpc_status {} {} (got {}: {})",
+                    assertion.operator, assertion.value, status, outcome.status_message
+                )),
+                None => Some(format!(
+                    "Operador '{}' ou valor {} inválido em grpc_status",
+                    assertion.operator, assertion.value
+                )),
+            },
+            "message" => {
+                let path = assertion.path.as_deref().unwrap_or("$");
+                match outcome.response.pointer(&json_pointer(path)) {
+                    None if assertion.operator == "not_exists" => None,
+                    None => Some(format!(
+                        "Assertion failed: path '{}' not found in gRPC response",
+                        path
+                    )),
+                    Some(actual) if assertion.operator == "not_exists" => Some(format!(
+                        "Assertion failed: message '{}' not_exists (got {})",
+                        path, actual
+                    )),
+                    Some(actual) => (!evaluate(
+                        &assertion.operator,
+                        &localize(actual, assertion.locale.as_ref()),
+                        &assertion.value,
+                        assertion.tolerance.as_ref(),
+                    ))
+                    .then(|| {
+                        format!(
+                            "Assertion failed: message '{}' {} {} (got {})",
+                            path, assertion.operator, assertion.value, actual
+                        )
+                    }),
+                }
+            }
+            other => Some(format!(
+                "Assertion type '{}' não suportado em grpc_call",
+                other
+            )),
+        })
+}
+
+/// Aplica as extrações do step sobre a resposta (`message`) e o status.
+pub fn extract_call(
+    extractions: &[Extraction],
+    outcome: &CallOutcome,
+) -> (Vec<ExtractionResult>, HashMap<String, Value>) {
+    let mut results = Vec::with_capacity(extractions.len());
+    let mut values = HashMap::new();
+    for extraction in extractions {
+        let source = extraction.source.to_lowercase();
+        let mut result = match source.as_str() {
+            "grpc_status" => ExtractionResult::success(
+                extraction.target.clone(),
+                source.clone(),
+                extraction.path.clone(),
+                json!(status_name(outcome.status)),
+            ),
+            "message" => {
+                let as_body = Extraction {
+                    source: "body".to_string(),
+                    ..extraction.clone()
+                };
+                let (mut single, _) = Extractor::process(
+                    std::slice::from_ref(&as_body),
+                    Some(&outcome.response),
+                    &HashMap::new(),
+                );
+                let mut result = single.remove(0);
+                result.source = source.clone();
+                result
+            }
+            other => ExtractionResult::failure(
+                extraction.target.clone(),
+                other.to_string(),
+                extraction.path.clone(),
+                format!(
+                    "Fonte '{}' inválida em grpc_call. Use 'message' ou 'grpc_status'.",
+                    other
+                ),
+            ),
+        };
+        result.is_critical = extraction.critical;
+        if result.success {
+            if let Some(value) = &result.value {
+                values.insert(result.target.clone(), value.clone());
+            }
+        }
+        results.push(result);
+    }
+    (results, values)
+}
+
+// ============================================================================
+// GRPC EXECUTOR
+// ============================================================================
+
+/// Executor para a ação `grpc_call`.
+#[derive(Debug, Default)]
+pub struct GrpcExecutor;
+
+impl GrpcExecutor {
+    /// Cria um novo GrpcExecutor.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Chamada já resolvida a partir dos params do step.
+struct CallSpec {
+    address: String,
+    method: String,
+    message: Value,
+    metadata: Vec<(String, String)>,
+    descriptor_set: Option<String>,
+}
+
+/// Carrega os descritores (arquivo ou reflection) e faz a chamada unária.
+async fn call(spec: &CallSpec) -> Result<(CallOutcome, usize)> {
+    let channel = Endpoint::from_shared(format!("http://{}", spec.address))?
+        .connect()
+        .await
+        .map_err(|e| anyhow!("Falha ao conectar em {}: {}", spec.address, e))?;
+
+    let pool = match &spec.descriptor_set {
+        Some(path) => {
+            let bytes = std::fs::read(path)
+                .with_context(|| format!("Falha ao ler descriptor_set '{}'", path))?;
+            DescriptorPool::decode(bytes.as_slice())
+                .with_context(|| format!("descriptor_set '{}' inválido", path))?
+        }
+        None => {
+            let service = spec.method.split('/').next().unwrap_or(&spec.method);
+            reflect(&channel, service).await?
+        }
+    };
+    let method = find_method(&pool, &spec.method)?;
+
+    let request =
+        DynamicMessage::deserialize(method.input(), spec.message.clone()).map_err(|e| {
+            anyhow!(
+                "'message' não casa com {}: {}",
+                method.input().full_name(),
+                e
+            )
+        })?;
+    let bytes = request.encode_to_vec();
+    let sent = bytes.len();
+
+    let mut request = tonic::Request::new(bytes);
+    for (key, value) in &spec.metadata {
+        let key = MetadataKey::from_bytes(key.to_lowercase().as_bytes())
+            .map_err(|_| anyhow!("Metadata inválido: '{}'", key))?;
+        let value = MetadataValue::try_from(value.as_str())
+            .map_err(|_| anyhow!("Valor de metadata inválido em '{}'", key))?;
+        request.metadata_mut().insert(key, value);
+    }
+
+    let path = format!("/{}/{}", method.parent_service().full_name(), method.name());
+    let mut grpc = tonic::client::Grpc::new(channel);
+    grpc.ready().await?;
+    let outcome = match grpc
+        .unary(request, PathAndQuery::try_from(path)?, RawCodec)
+        .await
+    {
+        Ok(response) => {
+            let received = response.into_inner();
+            let message = DynamicMessage::decode(method.output(), received.as_slice())?;
+            let options = SerializeOptions::new()
+                .use_proto_field_name(true)
+                .stringify_64_bit_integers(false)
+                .skip_default_fields(false);
+            let response =
+                message.serialize_with_options(serde_json::value::Serializer, &options)?;
+            CallOutcome {
+                status: Code::Ok,
+                status_message: String::new(),
+                response,
+                received,
+            }
+        }
+        Err(status) => CallOutcome {
+            status: status.code(),
+            status_message: status.message().to_string(),
+            response: Value::Null,
+            received: Vec::new(),
+        },
+    };
+    Ok((outcome, sent))
+}
+
+/// Lê os params do step (com interpolação).
+fn call_spec(params: &Value, context: &Context) -> Result<CallSpec> {
+    let host = params
+        .get("host")
+        .and_then(|h| h.as_str())
+        .ok_or_else(|| anyhow!("Missing 'host' in params"))?;
+    let port = params
+        .get("port")
+        .and_then(|p| p.as_u64())
+        .filter(|p| *p <= u16::MAX as u64)
+        .ok_or_else(|| anyhow!("Missing or invalid 'port' in params"))?;
+    let method = params
+        .get("method")
+        .and_then(|m| m.as_str())
+        .ok_or_else(|| anyhow!("Missing 'method' in params"))?;
+    let message = context.interpolate_value_typed(params.get("message").unwrap_or(&json!({})))?;
+    let metadata = params
+        .get("metadata")
+        .and_then(|m| m.as_object())
+        .map(|m| {
+            m.iter()
+                .map(|(k, v)| {
+                    let text = v.as_str().map_or_else(|| v.to_string(), str::to_string);
+                    Ok((k.clone(), context.interpolate_str(&text)?))
+                })
+                .collect::<Result<Vec<_>>>()
+        })
+        .transpose()?
+        .unwrap_or_default();
+    let descriptor_set = params
+        .get("descriptor_set")
+        .and_then(|d| d.as_str())
+        .map(|path| {
+            let path = Path::new(path);
+            match context.get("plan_dir").and_then(|d| d.as_str()) {
+                Some(dir) if path.is_relative() => Path::new(dir).join(path),
+                _ => path.to_path_buf(),
+            }
+            .to_string_lossy()
+            .into_owned()
+        });
+
+    Ok(CallSpec {
+        address: format!("{}:{}", context.interpolate_str(host)?, port),
+        method: context.interpolate_str(method)?,
+        message,
+        metadata,
+        descriptor_set,
+    })
+}
+
+/// JSON Schema dos params de `grpc_call` (ver `StepExecutor::params_schema`).
+pub fn params_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["host", "port", "method"],
+        "properties": {
+            "host": { "type": "string" },
+            "port": { "type": "integer", "minimum": 1, "maximum": 65535 },
+            "method": { "type": "string" },
+            "message": {},
+            "metadata": { "type": "object" },
+            "descriptor_set": { "type": "string" },
+            "timeout_ms": { "type": "integer", "minimum": 1 }
+        },
+        "additionalProperties": false
+    })
+}
+
+#[async_trait]
+impl StepExecutor for GrpcExecutor {
+    fn can_handle(&self, action: &str) -> bool {
+        action == "grpc_call"
+    }
+
+    /// Schema dos params de `grpc_call`, usado na validação do plano.
+    fn params_schema(&self, _action: &str) -> Option<Value> {
+        Some(params_schema())
+    }
+
+    #[instrument(skip(self, context), fields(step_id = %step.id))]
+    async fn execute(&self, step: &Step, context: &mut Context) -> Result<StepResult> {
+        let start = Instant::now();
+        let context_before = context.variables.clone();
+        let spec = call_spec(&step.params, context)?;
+        let timeout_ms = step
+            .params
+            .get("timeout_ms")
+            .and_then(|t| t.as_u64())
+            .or_else(|| context.get("timeout_ms").and_then(|t| t.as_u64()))
+            .unwrap_or(5000);
+
+        let outcome = tokio::time::timeout(Duration::from_millis(timeout_ms), call(&spec))
+            .await
+            .unwrap_or_else(|_| Err(anyhow!("Timeout de {}ms na chamada gRPC", timeout_ms)));
+        let latency_ms = start.elapsed().as_millis() as u64;
+
+        let (error, extractions, sent, received) = match outcome {
+            Ok((outcome, sent)) => {
+                let failure = check_call(&step.assertions, &outcome);
+                let (extractions, values) = extract_call(&step.extract, &outcome);
+                for (key, value) in values {
+                    context.set(key, value);
+                }
+                let status = status_name(outcome.status);
+                context.set(format!("{}.grpc_status", step.id), json!(status));
+                context.set(format!("{}.response", step.id), outcome.response.clone());
+                info!(address = %spec.address, method = %spec.method, status, latency_ms, "gRPC call");
+                (failure, Some(extractions), sent, outcome.received)
+            }
+            Err(e) => (
+                Some(format!("{:#} ({})", e, spec.method)),
+                None,
+                0,
+                Vec::new(),
+            ),
+        };
+
+        Ok(StepResult {
+            step_id: step.id.clone(),
+            status: if error.is_some() {
+                StepStatus::Failed
+            } else {
+                StepStatus::Passed
+            },
+            duration_ms: latency_ms,
+            error,
+            context_before: Some(context_before),
+            context_after: Some(context.variables.clone()),
+            extractions: extractions.filter(|e| !e.is_empty()),
+            socket_details: Some(SocketDetails {
+                protocol: "grpc".to_string(),
+                address: spec.address,
+                bytes_sent: sent,
+                bytes_received: received.len(),
+                latency_ms,
+                reply_hex: hex(&received),
+            }),
+            ..Default::default()
+        })
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::grpc_health::{frame, unframe};
+    use super::*;
+    use hyper::header::HeaderMap;
+    use hyper::service::{make_service_fn, service_fn};
+    use prost_types::field_descriptor_proto::{Label, Type};
+    use prost_types::{
+        DescriptorProto, FieldDescriptorProto, MethodDescriptorProto, ServiceDescriptorProto,
+    };
+
+    fn field(name: &str, number: i32, kind: Type) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            label: Some(Label::Optional as i32),
+            r#type: Some(kind as i32),
+            ..Default::default()
+        }
+    }
+
+    /// `orders.proto`: `Orders.GetOrder(GetOrderRequest) returns (Order)`.
+    fn orders_file() -> FileDescriptorProto {
+        FileDescriptorProto {
+            name: Some("orders.proto".to_string()),
+            package: Some("orders.v1".to_string()),
+            syntax: Some("proto3".to_string()),
+            message_type: vec![
+                DescriptorProto {
+                    name: Some("GetOrderRequest".to_string()),
+                    field: vec![field("id", 1, Type::String)],
+                    ..Default::default()
+                },
+                DescriptorProto {
+                    name: Some("Order".to_string()),
+                    field: vec![
+                        field("id", 1, Type::String),
+                        field("total_cents", 2, Type::Int64),
+                        field("status", 3, Type::String),
+                    ],
+                    ..Default::default()
+                },
+            ],
+            service: vec![ServiceDescriptorProto {
+                name: Some("Orders".to_string()),
+                method: vec![MethodDescriptorProto {
+                    name: Some("GetOrder".to_string()),
+                    input_type: Some(".orders.v1.GetOrderRequest".to_string()),
+                    output_type: Some(".orders.v1.Order".to_string()),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn respond(message: Option<Vec<u8>>, status: &str) -> hyper::Response<hyper::Body> {
+        let (mut sender, body) = hyper::Body::channel();
+        let status = status.to_string();
+        tokio::spawn(async move {
+            if let Some(message) = message {
+                sender.send_data(frame(&message).into()).await.unwrap();
+            }
+            let mut trailers = HeaderMap::new();
+            trailers.insert("grpc-status", status.parse().unwrap());
+            sender.send_trailers(trailers).await.unwrap();
+        });
+        hyper::Response::builder()
+            .header("content-type", "application/grpc")
+            .body(body)
+            .unwrap()
+    }
+
+    /// Servidor h2c com reflection v1 e `GetOrder` (`missing` → NOT_FOUND).
+    fn orders_server() -> u16 {
+        let pool = DescriptorPool::from_file_descriptor_set(FileDescriptorSet {
+            file: vec![orders_file()],
+        })
+        .unwrap();
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let make_service = make_service_fn(move |_| {
+            let pool = pool.clone();
+            async move {
+                Ok::<_, std::convert::Infallible>(service_fn(
+                    move |request: hyper::Request<hyper::Body>| {
+                        let pool = pool.clone();
+                        async move {
+                            let path = request.uri().path().to_string();
+                            let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                            let message = unframe(&body).unwrap().to_vec();
+                            let response = if path == REFLECTION_PATHS[0] {
+                                let reply = ReflectionResponse {
+                                    file_descriptor_response: Some(FileDescriptorResponse {
+                                        file_descriptor_proto: vec![orders_file().encode_to_vec()],
+                                    }),
+                                    error_response: None,
+                                };
+                                respond(Some(reply.encode_to_vec()), "0")
+                            } else {
+                                let input = pool
+                                    .get_message_by_name("orders.v1.GetOrderRequest")
+                                    .unwrap();
+                                let request =
+                                    DynamicMessage::decode(input, message.as_slice()).unwrap();
+                                let id = request
+                                    .get_field_by_name("id")
+                                    .unwrap()
+                                    .as_str()
+                                    .unwrap()
+                                    .to_string();
+                                if id == "missing" {
+                                    respond(None, "5")
+                                } else {
+                                    let output =
+                                        pool.get_message_by_name("orders.v1.Order").unwrap();
+                                    let order = DynamicMessage::deserialize(
+                                        output,
+                                        json!({ "id": id, "total_cents": 1999, "status": "PAID" }),
+                                    )
+                                    .unwrap();
+                                    respond(Some(order.encode_to_vec()), "0")
+                                }
+                            };
+                            Ok::<_, std::convert::Infallible>(response)
+                        }
+                    },
+                ))
+            }
+        });
+        tokio::spawn(
+            hyper::Server::from_tcp(listener)
+                .unwrap()
+                .http2_only(true)
+                .serve(make_service),
+        );
+        port
+    }
+
+    fn step(port: u16, params: Value, assertions: Value, extract: Value) -> Step {
+        let mut params = params;
+        params["host"] = json!("127.0.0.1");
+        params["port"] = json!(port);
+        params["method"] = json!("orders.v1.Orders/GetOrder");
+        serde_json::from_value(json!({
+            "id": "get_order",
+            "action": "grpc_call",
+            "params": params,
+            "assertions": assertions,
+            "extract": extract
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_grpc_call_via_reflection_asserts_and_extracts() {
+        let port = orders_server();
+        let mut context = Context::new();
+        context.set("order_id", json!("o-42"));
+
+        let result = GrpcExecutor::new()
+            .execute(
+                &step(
+                    port,
+                    json!({ "message": { "id": "${order_id}" } }),
+                    json!([
+                        { "type": "grpc_status", "operator": "eq", "value": "OK" },
+                        { "type": "message", "path": "status", "operator": "eq", "value": "PAID" },
+                        { "type": "message", "path": "$.total_cents", "operator": "gt", "value": 1000 }
+                    ]),
+                    json!([{ "source": "message", "path": "$.total_cents", "target": "total" }]),
+                ),
+                &mut context,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+        assert_eq!(context.get("total"), Some(&json!(1999)));
+        assert_eq!(context.get("get_order.grpc_status"), Some(&json!("OK")));
+        assert_eq!(context.get("get_order.response").unwrap()["id"], "o-42");
+    }
+
+    #[tokio::test]
+    async fn test_grpc_call_with_descriptor_set_and_error_status() {
+        let port = orders_server();
+        let dir = std::env::temp_dir().join(format!("grpc-call-{}", port));
+        std::fs::create_dir_all(&dir).unwrap();
+        let descriptor = FileDescriptorSet {
+            file: vec![orders_file()],
+        };
+        std::fs::write(dir.join("orders.pb"), descriptor.encode_to_vec()).unwrap();
+        let mut context = Context::new();
+        context.set("plan_dir", json!(dir.to_string_lossy()));
+
+        // Sem assertion de grpc_status, NOT_FOUND reprova o step.
+        let params = json!({ "descriptor_set": "orders.pb", "message": { "id": "missing" } });
+        let result = GrpcExecutor::new()
+            .execute(
+                &step(port, params.clone(), json!([]), json!([])),
+                &mut context,
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Failed);
+        assert!(result.error.unwrap().contains("NOT_FOUND"));
+
+        // Com a assertion, o status esperado passa.
+        let result = GrpcExecutor::new()
+            .execute(
+                &step(
+                    port,
+                    params,
+                    json!([{ "type": "grpc_status", "operator": "in", "value": [5, "UNAVAILABLE"] }]),
+                    json!([]),
+                ),
+                &mut context,
+            )
+            .await
+            .unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+        assert_eq!(
+            context.get("get_order.grpc_status"),
+            Some(&json!("NOT_FOUND"))
+        );
+    }
+}
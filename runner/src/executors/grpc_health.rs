@@ -0,0 +1,391 @@
+//! # Executor gRPC Health - Protocolo Padrão de Health Check
+//!
+//! Este executor chama `grpc.health.v1.Health/Check` num servidor gRPC e
+//! compara o status devolvido, para servir de preflight (`depends_on`) dos
+//! steps que falam gRPC.
+//!
+//! ## Para todos entenderem:
+//!
+//! Servidores gRPC expõem um serviço de health padronizado: pergunta-se por
+//! um serviço (ou `""` para o servidor todo) e a resposta é `SERVING`,
+//! `NOT_SERVING`, etc. Não é preciso `.proto` nem reflection:
+//!
+//! ```json
+//! {
+//!   "id": "orders_up",
+//!   "action": "grpc_health",
+//!   "params": { "host": "orders.internal", "port": 50051, "service": "orders.v1.Orders" }
+//! }
+//! ```
+//!
+//! | Parâmetro    | Padrão       | Descrição                                   |
+//! |--------------|--------------|---------------------------------------------|
+//! | `service`    | `""`         | Serviço consultado (`""` = servidor todo)   |
+//! | `expect`     | `"SERVING"`  | Status esperado                             |
+//! | `timeout_ms` | config       | Orçamento da chamada inteira                |
+//!
+//! O status recebido fica em `${<step_id>.status}`. Um serviço que o
+//! servidor não conhece (`grpc-status` 5, NOT_FOUND) vira `SERVICE_UNKNOWN`.
+//!
+//! ## Limitações:
+//!
+//! - Só HTTP/2 sem TLS (h2c), o comum para checagens dentro do cluster.
+//! - Sem `Watch` (streaming): uma única consulta por step.
+
+use anyhow::{anyhow, bail, Result};
+use async_trait::async_trait;
+use hyper::body::HttpBody;
+use hyper::header::{HeaderMap, CONTENT_TYPE, TE};
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+use tracing::{info, instrument};
+
+use crate::context::Context;
+use crate::protocol::{SocketDetails, Step, StepResult, StepStatus};
+
+use super::tcp::{hex, REPLY_PREVIEW_BYTES};
+use super::StepExecutor;
+
+/// Método do protocolo de health.
+const HEALTH_CHECK_PATH: &str = "/grpc.health.v1.Health/Check";
+
+/// `grpc-status` para serviço desconhecido (NOT_FOUND).
+const GRPC_NOT_FOUND: &str = "5";
+
+// ============================================================================
+// FRAMING E PROTOBUF
+// ============================================================================
+
+/// Envelopa uma mensagem no framing gRPC (flag de compressão + tamanho).
+pub fn frame(message: &[u8]) -> Vec<u8> {
+    let mut framed = Vec::with_capacity(message.len() + 5);
+    framed.push(0);
+    framed.extend_from_slice(&(message.len() as u32).to_be_bytes());
+    framed.extend_from_slice(message);
+    framed
+}
+
+/// Extrai a primeira mensagem de um body gRPC.
+pub fn unframe(body: &[u8]) -> Result<&[u8]> {
+    if body.len() < 5 {
+        bail!("Resposta gRPC sem mensagem ({} bytes)", body.len());
+    }
+    if body[0] != 0 {
+        bail!("Resposta gRPC comprimida não é suportada");
+    }
+    let length = u32::from_be_bytes([body[1], body[2], body[3], body[4]]) as usize;
+    body.get(5..5 + length)
+        .ok_or_else(|| anyhow!("Resposta gRPC truncada"))
+}
+
+/// `HealthCheckRequest { string service = 1; }`.
+fn encode_request(service: &str) -> Vec<u8> {
+    let mut message = Vec::new();
+    if !service.is_empty() {
+        message.push(0x0a);
+        let mut length = service.len();
+        while length >= 0x80 {
+            message.push((length as u8) | 0x80);
+            length >>= 7;
+        }
+        message.push(length as u8);
+        message.extend_from_slice(service.as_bytes());
+    }
+    message
+}
+
+/// `HealthCheckResponse { ServingStatus status = 1; }` (ausente = UNKNOWN).
+fn decode_status(message: &[u8]) -> Result<&'static str> {
+    let mut status = 0u64;
+    if let Some((&0x08, rest)) = message.split_first() {
+        for (i, byte) in rest.iter().enumerate().take(10) {
+            status |= ((byte & 0x7f) as u64) << (7 * i);
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+    } else if !message.is_empty() {
+        bail!("HealthCheckResponse inesperada: {}", hex(message));
+    }
+    Ok(match status {
+        0 => "UNKNOWN",
+        1 => "SERVING",
+        2 => "NOT_SERVING",
+        3 => "SERVICE_UNKNOWN",
+        _ => bail!("ServingStatus desconhecido: {}", status),
+    })
+}
+
+/// `grpc-status` e `grpc-message` (dos trailers ou, se "trailers-only", dos headers).
+fn grpc_status(headers: &HeaderMap) -> Option<(String, String)> {
+    let status = headers.get("grpc-status")?.to_str().ok()?.to_string();
+    let message = headers
+        .get("grpc-message")
+        .and_then(|m| m.to_str().ok())
+        .map(|m| {
+            urlencoding::decode(m)
+                .map(|m| m.into_owned())
+                .unwrap_or_default()
+        })
+        .unwrap_or_default();
+    Some((status, message))
+}
+
+// ============================================================================
+// GRPC HEALTH EXECUTOR
+// ============================================================================
+
+/// Executor para a ação `grpc_health`.
+#[derive(Debug, Default)]
+pub struct GrpcHealthExecutor;
+
+impl GrpcHealthExecutor {
+    /// Cria um novo GrpcHealthExecutor.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Faz a chamada `Check` e devolve (status, bytes recebidos).
+async fn check(address: &str, service: &str) -> Result<(&'static str, Vec<u8>)> {
+    let client = hyper::Client::builder()
+        .http2_only(true)
+        .build_http::<hyper::Body>();
+    let request = hyper::Request::post(format!("http://{}{}", address, HEALTH_CHECK_PATH))
+        .header(CONTENT_TYPE, "application/grpc")
+        .header(TE, "trailers")
+        .body(hyper::Body::from(frame(&encode_request(service))))?;
+
+    let response = client
+        .request(request)
+        .await
+        .map_err(|e| anyhow!("Falha na chamada gRPC a {}: {}", address, e))?;
+    if response.status() != hyper::StatusCode::OK {
+        bail!("Servidor gRPC respondeu HTTP {}", response.status());
+    }
+    let trailers_only = grpc_status(response.headers());
+    let mut body = response.into_body();
+    let mut received = Vec::new();
+    while let Some(chunk) = body.data().await {
+        received.extend_from_slice(&chunk?);
+    }
+    let trailers = body.trailers().await?;
+
+    let (status, message) = trailers
+        .as_ref()
+        .and_then(grpc_status)
+        .or(trailers_only)
+        .ok_or_else(|| anyhow!("Resposta gRPC sem grpc-status"))?;
+    match status.as_str() {
+        "0" => Ok((decode_status(unframe(&received)?)?, received)),
+        GRPC_NOT_FOUND => Ok(("SERVICE_UNKNOWN", received)),
+        _ => bail!("grpc-status {}: {}", status, message),
+    }
+}
+
+/// JSON Schema dos params de `grpc_health` (ver `StepExecutor::params_schema`).
+pub fn params_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["host", "port"],
+        "properties": {
+            "host": { "type": "string" },
+            "port": { "type": "integer", "minimum": 1, "maximum": 65535 },
+            "service": { "type": "string" },
+            "expect": {
+                "type": "string",
+                "enum": ["UNKNOWN", "SERVING", "NOT_SERVING", "SERVICE_UNKNOWN"]
+            },
+            "timeout_ms": { "type": "integer", "minimum": 1 }
+        },
+        "additionalProperties": false
+    })
+}
+
+#[async_trait]
+impl StepExecutor for GrpcHealthExecutor {
+    fn can_handle(&self, action: &str) -> bool {
+        action == "grpc_health"
+    }
+
+    /// Schema dos params de `grpc_health`, usado na validação do plano.
+    fn params_schema(&self, _action: &str) -> Option<Value> {
+        Some(params_schema())
+    }
+
+    #[instrument(skip(self, context), fields(step_id = %step.id))]
+    async fn execute(&self, step: &Step, context: &mut Context) -> Result<StepResult> {
+        let start = Instant::now();
+        let context_before = context.variables.clone();
+        let params = &step.params;
+
+        let host = params
+            .get("host")
+            .and_then(|h| h.as_str())
+            .ok_or_else(|| anyhow!("Missing 'host' in params"))?;
+        let port = params
+            .get("port")
+            .and_then(|p| p.as_u64())
+            .filter(|p| *p <= u16::MAX as u64)
+            .ok_or_else(|| anyhow!("Missing or invalid 'port' in params"))?;
+        let address = format!("{}:{}", context.interpolate_str(host)?, port);
+        let service = context
+            .interpolate_str(params.get("service").and_then(|s| s.as_str()).unwrap_or(""))?;
+        let expect = params
+            .get("expect")
+            .and_then(|e| e.as_str())
+            .unwrap_or("SERVING");
+        let timeout_ms = params
+            .get("timeout_ms")
+            .and_then(|t| t.as_u64())
+            .or_else(|| context.get("timeout_ms").and_then(|t| t.as_u64()))
+            .unwrap_or(5000);
+
+        let sent = frame(&encode_request(&service)).len();
+        let outcome =
+            tokio::time::timeout(Duration::from_millis(timeout_ms), check(&address, &service))
+                .await
+                .unwrap_or_else(|_| {
+                    Err(anyhow!("Timeout de {}ms no health check gRPC", timeout_ms))
+                });
+        let latency_ms = start.elapsed().as_millis() as u64;
+
+        let (error, received) = match outcome {
+            Ok((status, received)) => {
+                context.set(format!("{}.status", step.id), json!(status));
+                info!(%address, service = %service, status, "gRPC health check");
+                let error = (status != expect).then(|| {
+                    format!(
+                        "Serviço '{}' em {}: status {} (esperado {})",
+                        service, address, status, expect
+                    )
+                });
+                (error, received)
+            }
+            Err(e) => (Some(format!("{:#}", e)), Vec::new()),
+        };
+
+        Ok(StepResult {
+            step_id: step.id.clone(),
+            status: if error.is_some() {
+                StepStatus::Failed
+            } else {
+                StepStatus::Passed
+            },
+            duration_ms: latency_ms,
+            error,
+            context_before: Some(context_before),
+            context_after: Some(context.variables.clone()),
+            socket_details: Some(SocketDetails {
+                protocol: "grpc".to_string(),
+                address,
+                bytes_sent: sent,
+                bytes_received: received.len(),
+                latency_ms,
+                reply_hex: hex(&received[..received.len().min(REPLY_PREVIEW_BYTES)]),
+            }),
+            ..Default::default()
+        })
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::service::{make_service_fn, service_fn};
+
+    /// Servidor h2c com `orders` SERVING, `billing` NOT_SERVING e o resto NOT_FOUND.
+    fn health_server() -> u16 {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let make_service = make_service_fn(|_| async {
+            Ok::<_, std::convert::Infallible>(service_fn(
+                |request: hyper::Request<hyper::Body>| async move {
+                    let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                    let service = String::from_utf8_lossy(
+                        unframe(&body).unwrap().get(2..).unwrap_or_default(),
+                    )
+                    .to_string();
+                    let status: u8 = match service.as_str() {
+                        "" | "orders" => 1,
+                        "billing" => 2,
+                        _ => {
+                            let response = hyper::Response::builder()
+                                .header("grpc-status", "5")
+                                .body(hyper::Body::empty())
+                                .unwrap();
+                            return Ok::<_, std::convert::Infallible>(response);
+                        }
+                    };
+                    let (mut sender, body) = hyper::Body::channel();
+                    tokio::spawn(async move {
+                        sender
+                            .send_data(frame(&[0x08, status]).into())
+                            .await
+                            .unwrap();
+                        let mut trailers = HeaderMap::new();
+                        trailers.insert("grpc-status", "0".parse().unwrap());
+                        sender.send_trailers(trailers).await.unwrap();
+                    });
+                    Ok(hyper::Response::new(body))
+                },
+            ))
+        });
+        tokio::spawn(
+            hyper::Server::from_tcp(listener)
+                .unwrap()
+                .http2_only(true)
+                .serve(make_service),
+        );
+        port
+    }
+
+    fn step(port: u16, service: &str) -> Step {
+        Step {
+            id: "health".to_string(),
+            action: "grpc_health".to_string(),
+            params: json!({ "host": "127.0.0.1", "port": port, "service": service }),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_grpc_health_reports_serving_status() {
+        let port = health_server();
+        let executor = GrpcHealthExecutor::new();
+        let mut context = Context::new();
+
+        let result = executor
+            .execute(&step(port, "orders"), &mut context)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+        assert_eq!(context.get("health.status"), Some(&json!("SERVING")));
+        assert_eq!(result.socket_details.unwrap().protocol, "grpc");
+
+        let result = executor
+            .execute(&step(port, "billing"), &mut context)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Failed);
+        assert!(result.error.unwrap().contains("NOT_SERVING"));
+
+        let mut unknown = step(port, "ghost");
+        unknown.params["expect"] = json!("SERVICE_UNKNOWN");
+        let result = executor.execute(&unknown, &mut context).await.unwrap();
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+    }
+
+    #[test]
+    fn test_health_request_encoding() {
+        assert_eq!(encode_request(""), Vec::<u8>::new());
+        assert_eq!(encode_request("ab"), vec![0x0a, 2, b'a', b'b']);
+        assert_eq!(frame(&[0x08, 1]), vec![0, 0, 0, 0, 2, 0x08, 1]);
+        assert_eq!(decode_status(&[]).unwrap(), "UNKNOWN");
+        assert_eq!(decode_status(&[0x08, 2]).unwrap(), "NOT_SERVING");
+    }
+}
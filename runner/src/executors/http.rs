@@ -33,18 +33,43 @@
 //! └──────────────────────────────────────────────────────────────┘
 //! ```
 
+use super::graphql_errors::check_graphql_errors;
+use super::http_auth::ChallengeAuth;
+use super::http_binary::{check_binary_assertion, is_binary_assertion};
+use super::http_body_file::FileBody;
+use super::http_cache::{FetchedResponse, ResponseCache};
+use super::http_compression::{check_content_encoding, decode_body};
+use super::http_connections::{self, ConnectionTracker};
+use super::http_excerpt::with_excerpt;
+use super::http_headers::{HeaderCapture, SentRequest};
+use super::http_interceptor::{InterceptorFactory, InterceptorRegistry, ProxyClients};
+use super::http_revalidation::{self, Revalidation};
+use super::http_session::{resolve_url, HttpSession};
+use super::http_signer::SignerRegistry;
+use super::http_stream::{check_stream_assertion, is_stream_assertion, read_body, StreamStats};
+use super::http_timeout::{self, BodyTimeout};
+use super::http_timing::{DnsProbe, TimingResolver, DNS_PROBE};
+use super::http_unix::UnixSocket;
+use super::http_version::{self, HttpVersion, VersionClients};
+use super::value_operators::{approx_eq, evaluate_semantic, localize};
 use super::StepExecutor;
+use crate::auth::OidcSession;
 use crate::context::Context;
 use crate::extractors::{ExtractionResult, Extractor};
-use crate::protocol::{Assertion, Extraction, HttpDetails, Step, StepResult, StepStatus};
+use crate::protocol::{
+    Assertion, AssertionFailure, Config, Extraction, HttpClientConfig, HttpDetails, HttpTiming,
+    IpPreference, Step, StepResult, StepStatus,
+};
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use jsonschema::JSONSchema;
 use regex::Regex;
-use reqwest::{header::HeaderMap, Client, Method};
-use serde_json::Value;
+use reqwest::header::{HeaderMap, CONTENT_LENGTH, CONTENT_TYPE, DATE};
+use reqwest::{Client, ClientBuilder, Method};
+use serde_json::{json, Value};
 use std::collections::HashMap;
-use std::time::Instant;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 // ============================================================================
 // FUNÇÕES AUXILIARES
@@ -79,6 +104,228 @@ where
     }
 }
 
+/// Registra os tempos da requisição como atributos do span OTEL.
+fn record_timing(span: &tracing::Span, timing: &HttpTiming) {
+    if let Some(dns_ms) = timing.dns_ms {
+        span.record("http.timing.dns_ms", dns_ms as i64);
+    }
+    span.record("http.timing.ttfb_ms", timing.ttfb_ms as i64);
+    span.record("http.timing.transfer_ms", timing.transfer_ms as i64);
+}
+
+/// Header que identifica o step de origem de cada requisição.
+const STEP_ID_HEADER: &str = "X-Step-Id";
+
+/// Monta os headers de correlação para um step.
+///
+/// Usa o nome em `correlation_header` do contexto (vazio = desativado) com o
+/// `execution_id` do run, mais `X-Step-Id` com o ID do step. Headers já
+/// definidos explicitamente no step não são sobrescritos.
+fn correlation_headers(
+    context: &Context,
+    step_id: &str,
+    explicit: Option<&serde_json::Map<String, Value>>,
+) -> Vec<(String, String)> {
+    let header = match context.get("correlation_header").and_then(|h| h.as_str()) {
+        Some(h) if !h.trim().is_empty() => h,
+        _ => return Vec::new(),
+    };
+    let execution_id = match context.get("execution_id").and_then(|v| v.as_str()) {
+        Some(id) => id,
+        None => return Vec::new(),
+    };
+
+    let is_explicit = |name: &str| {
+        explicit
+            .map(|h| h.keys().any(|k| k.eq_ignore_ascii_case(name)))
+            .unwrap_or(false)
+    };
+
+    [
+        (header, execution_id.to_string()),
+        (STEP_ID_HEADER, step_id.to_string()),
+    ]
+    .into_iter()
+    .filter(|(name, _)| !is_explicit(name))
+    .map(|(name, value)| (name.to_string(), value))
+    .collect()
+}
+
+/// Headers de uma requisição do step, um por nome.
+///
+/// `global_headers` do plano, headers do ator ou sessão, correlação e, por
+/// último, `params.headers`. Um nome repetido (sem diferenciar maiúsculas)
+/// substitui o anterior: o `RequestBuilder` acumula valores com `.header()`,
+/// então a lista já sai sem duplicatas.
+pub(super) fn request_headers(
+    step: &Step,
+    session: Option<&HttpSession>,
+    context: &Context,
+) -> Result<Vec<(String, String)>> {
+    let mut headers: Vec<(String, String)> = Vec::new();
+    let mut insert = |name: &str, value: String| {
+        headers.retain(|(existing, _)| !existing.eq_ignore_ascii_case(name));
+        headers.push((name.to_string(), value));
+    };
+    let global_headers = context.get("global_headers").and_then(|h| h.as_object());
+    for (name, value) in global_headers.into_iter().flatten() {
+        if let Some(value) = value.as_str() {
+            insert(name, context.interpolate_str(value)?);
+        }
+    }
+    for (name, value) in session.map(|s| &s.headers).into_iter().flatten() {
+        insert(name, context.interpolate_str(value)?);
+    }
+    let step_headers = step.params.get("headers").and_then(|h| h.as_object());
+    for (name, value) in correlation_headers(context, &step.id, step_headers) {
+        insert(&name, value);
+    }
+    for (name, value) in step_headers.into_iter().flatten() {
+        if let Some(value) = value.as_str() {
+            insert(name, context.interpolate_str(value)?);
+        }
+    }
+    Ok(headers)
+}
+
+/// Converte o path de uma assertion `json_body` para JSON Pointer.
+///
+/// Aceita `data.user.id`, `$.data.user.id`, `/data/user/id` e `$` (body inteiro).
+pub fn json_pointer(path: &str) -> String {
+    // Remove prefixo $. do JSONPath se presente
+    let clean_path = path.strip_prefix("$.").unwrap_or(path);
+    if clean_path == "$" {
+        // Body inteiro (ex: matches_subset sobre a resposta).
+        String::new()
+    } else if clean_path.starts_with('/') {
+        clean_path.to_string()
+    } else {
+        format!("/{}", clean_path.replace('.', "/"))
+    }
+}
+
+/// Valor observado na resposta para uma assertion (`None` se não se aplica).
+fn actual_value(assertion: &Assertion, ctx: &ResponseContext) -> Option<Value> {
+    match assertion.assertion_type.as_str() {
+        "status_code" | "status_range" => Some(Value::from(ctx.status)),
+        "latency" => Some(Value::from(ctx.duration_ms)),
+        "json_body" => {
+            let path = assertion.path.as_deref()?;
+            ctx.body.pointer(&json_pointer(path)).cloned()
+        }
+        "header" => {
+            let name = assertion.path.as_deref()?;
+            let values: Vec<Value> = ctx
+                .headers
+                .get_all(name)
+                .iter()
+                .map(|v| Value::from(v.to_str().unwrap_or("<binary>")))
+                .collect();
+            match values.len() {
+                0 => None,
+                1 => values.into_iter().next(),
+                _ => Some(Value::Array(values)),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Avalia uma assertion do tipo `header`.
+///
+/// O nome do header é comparado sem diferenciar maiúsculas/minúsculas e
+/// todos os valores recebidos são considerados (headers repetidos, como
+/// `Set-Cookie`). Com `match: "any"` (padrão) basta um valor atender ao
+/// operador; com `match: "all"`, todos precisam atender.
+///
+/// Retorna a mensagem de erro (com todos os valores recebidos) se falhar.
+fn check_header_assertion(
+    assertion: &Assertion,
+    header_name: &str,
+    headers: &HeaderMap,
+) -> Option<String> {
+    let values: Vec<&str> = headers
+        .get_all(header_name.to_ascii_lowercase().as_str())
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .collect();
+    let match_all = assertion.match_mode.as_deref() == Some("all");
+    let expected = assertion.value.as_str().unwrap_or("");
+
+    let predicate: Box<dyn Fn(&str) -> bool> = match assertion.operator.as_str() {
+        "exists" | "not_exists" => {
+            let exists = !values.is_empty();
+            if exists == (assertion.operator == "exists") {
+                return None;
+            }
+            return Some(format!(
+                "Assertion failed: header '{}' should {} (got {})",
+                header_name,
+                if exists { "not exist" } else { "exist" },
+                describe_values(&values)
+            ));
+        }
+        "neq" => {
+            // Nenhum valor pode ser igual ao esperado.
+            if values.iter().all(|v| *v != expected) {
+                return None;
+            }
+            return Some(format!(
+                "Assertion failed: header '{}' should not equal '{}' (got {})",
+                header_name,
+                expected,
+                describe_values(&values)
+            ));
+        }
+        "eq" => Box::new(move |v| v == expected),
+        "contains" => Box::new(move |v| v.contains(expected)),
+        "matches_regex" | "matches" | "regex" => match Regex::new(expected) {
+            Ok(re) => Box::new(move |v| re.is_match(v)),
+            Err(e) => {
+                return Some(format!(
+                    "Assertion failed: invalid regex '{}' for header '{}': {}",
+                    expected, header_name, e
+                ));
+            }
+        },
+        other => {
+            return Some(format!(
+                "Assertion failed: operator '{}' not supported for header assertions",
+                other
+            ));
+        }
+    };
+
+    let passed = if match_all {
+        !values.is_empty() && values.iter().all(|v| predicate(v))
+    } else {
+        values.iter().any(|v| predicate(v))
+    };
+
+    if passed {
+        None
+    } else {
+        Some(format!(
+            "Assertion failed: header '{}' {} '{}' ({} value{}) (got {})",
+            header_name,
+            assertion.operator,
+            expected,
+            if match_all { "all" } else { "any" },
+            if match_all { "s" } else { "" },
+            describe_values(&values)
+        ))
+    }
+}
+
+/// Formata os valores recebidos de um header para mensagens de erro.
+fn describe_values(values: &[&str]) -> String {
+    if values.is_empty() {
+        "<missing>".to_string()
+    } else {
+        format!("{:?}", values)
+    }
+}
+
 // ============================================================================
 // CONTEXTO DE RESPOSTA
 // ============================================================================
@@ -95,11 +342,17 @@ struct ResponseContext<'a> {
     /// Será `Value::Null` se não for JSON válido.
     body: &'a Value,
 
+    /// Bytes brutos do body (assertions binárias).
+    raw_body: &'a [u8],
+
     /// Headers da resposta.
     headers: &'a HeaderMap,
 
     /// Tempo de resposta em milissegundos.
     duration_ms: u64,
+
+    /// Primeiro byte e chunks do body (assertions de streaming).
+    stream: StreamStats,
 }
 
 // ============================================================================
@@ -117,394 +370,680 @@ struct ResponseContext<'a> {
 /// - Extração de dados da resposta
 /// - Instrumentação OpenTelemetry
 pub struct HttpExecutor {
+    /// Cliente padrão, sessões, atores e login OIDC.
+    clients: HttpClients,
+
+    /// Regras de `config.auto_extract`, aplicadas a toda resposta.
+    auto_extract: Vec<Extraction>,
+
+    /// Cache de respostas GET/HEAD (`config.http.response_cache`).
+    cache: Option<ResponseCache>,
+
+    /// Conexões já usadas, para marcar o reuso do pool.
+    connections: ConnectionTracker,
+
+    /// Clientes com o protocolo fixado (`params.http_version`).
+    versions: VersionClients,
+
+    /// Assinadores de `config.signers` e os tipos conhecidos.
+    signers: SignerRegistry,
+
+    /// Tipos de interceptor para `params.interceptors`.
+    interceptors: InterceptorRegistry,
+
+    /// Clientes por proxy (`params.proxy`).
+    proxies: ProxyClients,
+
+    /// Política de `config.http.header_capture` para `response_headers`.
+    header_capture: HeaderCapture,
+}
+
+/// Cliente padrão, sessões, atores e login OIDC de uma execução.
+///
+/// Compartilhado (ver `HttpExecutor::clients`) com executores que também
+/// disparam requisições, como o `rate_limit_probe`: saem com a mesma
+/// configuração de `config.http`, os mesmos cookies e o mesmo token.
+#[derive(Clone, Default)]
+pub struct HttpClients {
     /// Cliente HTTP reutilizável.
     ///
     /// Reusar o cliente é mais eficiente porque mantém
     /// o connection pool entre requisições.
-    client: Client,
+    pub client: Client,
+
+    /// Sessões de `config.sessions` (cliente próprio com cookies por sessão).
+    sessions: Arc<HashMap<String, HttpSession>>,
+
+    /// Sessões dos atores de `config.actors`.
+    actors: Arc<HashMap<String, HttpSession>>,
+
+    /// Login OIDC de `config.auth.oidc` (token renovado antes de cada step).
+    oidc: Option<Arc<OidcSession>>,
+}
+
+impl HttpClients {
+    /// Sessão do step: a do ator (`step.actor`) ou a de `step.session`.
+    pub fn session(&self, step: &Step) -> Result<Option<&HttpSession>> {
+        match (&step.actor, &step.session) {
+            (Some(actor), _) => self
+                .actors
+                .get(actor)
+                .map(Some)
+                .ok_or_else(|| anyhow!("Ator '{}' não existe em config.actors", actor)),
+            (None, Some(name)) => self
+                .sessions
+                .get(name)
+                .map(Some)
+                .ok_or_else(|| anyhow!("Sessão '{}' não existe em config.sessions", name)),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// Garante o token de `config.auth` no contexto (sem `config.auth`, nada).
+    pub async fn authenticate(&self, context: &mut Context) -> Result<()> {
+        match &self.oidc {
+            Some(oidc) => oidc.ensure_token(context).await,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Default for HttpExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl HttpExecutor {
     /// Cria um novo HttpExecutor.
     ///
     /// O cliente HTTP é criado uma vez e reutilizado para todas as requisições.
+    /// Usa o `TimingResolver` para medir o tempo de DNS de cada requisição.
+    /// O Runner usa `from_config`; este construtor mantém os padrões do reqwest
+    /// (usado pelo `runner agent`, que não conhece o `config` do plano).
     pub fn new() -> Self {
+        let client = Client::builder()
+            .dns_resolver(Arc::new(TimingResolver::default()))
+            .build()
+            .unwrap_or_else(|_| Client::new());
         Self {
-            client: Client::new(),
+            clients: HttpClients {
+                client,
+                ..Default::default()
+            },
+            auto_extract: Vec::new(),
+            cache: None,
+            connections: ConnectionTracker::default(),
+            versions: VersionClients::new(|| {
+                Client::builder().dns_resolver(Arc::new(TimingResolver::default()))
+            })
+            .expect("Falha ao criar clientes HTTP/1.1 e HTTP/2"),
+            signers: SignerRegistry::new(HashMap::new()),
+            interceptors: InterceptorRegistry::default(),
+            proxies: ProxyClients::new(|| {
+                Client::builder().dns_resolver(Arc::new(TimingResolver::default()))
+            }),
+            header_capture: HeaderCapture::default(),
+        }
+    }
+
+    /// Cria um HttpExecutor com o cliente configurado pelo plano.
+    ///
+    /// Aplica `config.timeout_ms` como timeout padrão do cliente (steps com
+    /// `timeout_ms` próprio continuam sobrescrevendo) e as opções de
+    /// `config.http`: pool de conexões, janela adaptativa HTTP/2 e user-agent.
+    /// Guarda também as regras de `config.auto_extract` e cria um cliente
+    /// com cookie jar para cada sessão de `config.sessions` e cada ator de
+    /// `config.actors`. Com `config.http.response_cache`, liga o cache de
+    /// respostas da execução.
+    ///
+    /// Retorna erro se o cliente não puder ser construído
+    /// (ex: user-agent com caracteres inválidos).
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let client = Self::client_builder(config)
+            .build()
+            .map_err(|e| anyhow!("Falha ao criar cliente HTTP a partir de config.http: {}", e))?;
+
+        let mut sessions = HashMap::new();
+        for (name, session) in &config.sessions {
+            let session_client = Self::client_builder(config)
+                .cookie_store(true)
+                .build()
+                .map_err(|e| anyhow!("Falha ao criar cliente HTTP da sessão '{}': {}", name, e))?;
+            sessions.insert(
+                name.clone(),
+                HttpSession::new(name, session, session_client),
+            );
+        }
+
+        let mut actors = HashMap::new();
+        for (name, actor) in &config.actors {
+            let actor_client = Self::client_builder(config)
+                .cookie_store(true)
+                .build()
+                .map_err(|e| anyhow!("Falha ao criar cliente HTTP do ator '{}': {}", name, e))?;
+            actors.insert(
+                name.clone(),
+                HttpSession::for_actor(name, actor, actor_client),
+            );
+        }
+
+        let versions = VersionClients::new(|| Self::client_builder(config))
+            .map_err(|e| anyhow!("Falha ao criar clientes HTTP por versão: {}", e))?;
+
+        let oidc = config
+            .auth
+            .as_ref()
+            .and_then(|auth| auth.oidc.clone())
+            .map(|oidc| Arc::new(OidcSession::new(oidc, client.clone())));
+
+        let (http, timeout_ms, ip_preference) =
+            (config.http.clone(), config.timeout_ms, config.ip_preference);
+        let proxies =
+            ProxyClients::new(move || Self::base_client_builder(&http, timeout_ms, ip_preference));
+
+        Ok(Self {
+            clients: HttpClients {
+                client,
+                sessions: Arc::new(sessions),
+                actors: Arc::new(actors),
+                oidc,
+            },
+            auto_extract: config.auto_extract.clone(),
+            cache: config.http.response_cache.then(ResponseCache::default),
+            connections: ConnectionTracker::default(),
+            versions,
+            signers: SignerRegistry::new(config.signers.clone()),
+            interceptors: InterceptorRegistry::new(
+                HeaderCapture::new(&config.http.header_capture)
+                    .redaction()
+                    .clone(),
+            ),
+            proxies,
+            header_capture: HeaderCapture::new(&config.http.header_capture),
+        })
+    }
+
+    /// Registra um tipo de interceptor (plugin) para `params.interceptors`.
+    ///
+    /// Os embutidos são `latency`, `header_rewrite` e `record`.
+    pub fn register_interceptor(&mut self, kind: &str, factory: InterceptorFactory) {
+        self.interceptors.register(kind, factory);
+    }
+
+    /// Faz o login de `config.auth` e publica `auth.*` no contexto.
+    ///
+    /// Sem `config.auth`, não faz nada.
+    pub async fn authenticate(&self, context: &mut Context) -> Result<()> {
+        self.clients.authenticate(context).await
+    }
+
+    /// Cliente, sessões e login da execução, para outros executores HTTP.
+    pub fn clients(&self) -> HttpClients {
+        self.clients.clone()
+    }
+
+    /// Builder com as opções de `config.timeout_ms` e `config.http`.
+    fn client_builder(config: &Config) -> ClientBuilder {
+        Self::base_client_builder(&config.http, config.timeout_ms, config.ip_preference)
+    }
+
+    fn base_client_builder(
+        http: &HttpClientConfig,
+        timeout_ms: u64,
+        ip_preference: IpPreference,
+    ) -> ClientBuilder {
+        let mut builder = Client::builder()
+            .dns_resolver(Arc::new(TimingResolver::new(ip_preference)))
+            .http2_adaptive_window(http.http2_adaptive_window);
+
+        if timeout_ms > 0 {
+            builder = builder.timeout(Duration::from_millis(timeout_ms));
         }
+        if let Some(max_idle) = http.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max_idle);
+        }
+        if let Some(idle_ms) = http.pool_idle_timeout_ms {
+            builder = builder.pool_idle_timeout(Duration::from_millis(idle_ms));
+        }
+        if let Some(user_agent) = &http.user_agent {
+            builder = builder.user_agent(user_agent.as_str());
+        }
+        builder
+    }
+
+    /// Mensagem da primeira assertion que falhou (`None` se todas passaram).
+    ///
+    /// Atalho dos testes: a execução usa `assertion_failures` direto.
+    #[cfg(test)]
+    fn validate_assertions(
+        &self,
+        assertions: &[Assertion],
+        ctx: &ResponseContext,
+    ) -> Option<String> {
+        self.assertion_failures(assertions, ctx)
+            .into_iter()
+            .next()
+            .map(|failure| failure.message)
     }
 
     /// Valida todas as assertions contra a resposta.
     ///
     /// Itera sobre cada assertion definida no step e verifica
-    /// se a resposta atende aos critérios.
+    /// se a resposta atende aos critérios. Cada assertion é avaliada
+    /// uma única vez: a mensagem de erro do step é a da primeira falha.
     ///
     /// ## Parâmetros:
     /// - `assertions`: Lista de assertions do step
     /// - `ctx`: Contexto da resposta (status, body, headers, latency)
     ///
     /// ## Retorno:
-    /// - `None` se todas as assertions passaram
-    /// - `Some(String)` com a mensagem de erro da primeira que falhou
-    fn validate_assertions(
+    /// Lista estruturada das que falharam (`assertion_failures`), com o
+    /// valor esperado e o valor observado de cada uma. Vazia se todas passaram.
+    fn assertion_failures(
         &self,
         assertions: &[Assertion],
         ctx: &ResponseContext,
-    ) -> Option<String> {
-        for assertion in assertions {
-            match assertion.assertion_type.as_str() {
-                // ============================================================
-                // ASSERTION: STATUS_CODE
-                // ============================================================
-                // Valida o código de status HTTP da resposta.
-                // Exemplo: { "type": "status_code", "operator": "eq", "value": 200 }
-                "status_code" => {
-                    let expected = assertion.value.as_u64().unwrap_or(0) as u16;
-                    let passed = match assertion.operator.as_str() {
-                        "eq" => ctx.status == expected,
-                        "neq" => ctx.status != expected,
-                        "lt" => ctx.status < expected,
-                        "gt" => ctx.status > expected,
-                        "lte" | "le" => ctx.status <= expected,
-                        "gte" | "ge" => ctx.status >= expected,
-                        _ => false,
+    ) -> Vec<AssertionFailure> {
+        assertions
+            .iter()
+            .filter_map(|assertion| {
+                let message = self.check_assertion(assertion, ctx)?;
+                Some(AssertionFailure {
+                    assertion_type: assertion.assertion_type.clone(),
+                    operator: assertion.operator.clone(),
+                    path: assertion.path.clone(),
+                    expected: assertion.value.clone(),
+                    actual: actual_value(assertion, ctx),
+                    message,
+                })
+            })
+            .collect()
+    }
+
+    /// Avalia uma assertion: `Some(mensagem)` se ela falhou.
+    fn check_assertion(&self, assertion: &Assertion, ctx: &ResponseContext) -> Option<String> {
+        match assertion.assertion_type.as_str() {
+            // ============================================================
+            // ASSERTION: STATUS_CODE
+            // ============================================================
+            // Valida o código de status HTTP da resposta.
+            // Exemplo: { "type": "status_code", "operator": "eq", "value": 200 }
+            // Lista: { "type": "status_code", "operator": "in", "value": [200, 201, 204] }
+            "status_code" => {
+                if matches!(assertion.operator.as_str(), "in" | "not_in") {
+                    let allowed: Vec<u64> = match assertion.value.as_array() {
+                        Some(items) => items.iter().filter_map(|v| v.as_u64()).collect(),
+                        None => {
+                            return Some(format!(
+                                "Assertion failed: status_code {} requires an array of status codes (got {})",
+                                assertion.operator, assertion.value
+                            ));
+                        }
                     };
-                    if !passed {
+                    let found = allowed.contains(&(ctx.status as u64));
+                    if found != (assertion.operator == "in") {
                         return Some(format!(
                             "Assertion failed: status_code {} {} (got {})",
-                            assertion.operator, expected, ctx.status
+                            assertion.operator, assertion.value, ctx.status
                         ));
                     }
+                    return None;
                 }
 
-                // ============================================================
-                // ASSERTION: STATUS_RANGE
-                // ============================================================
-                // Valida que o status HTTP está em um range (2xx, 3xx, 4xx, 5xx).
-                // Exemplo: { "type": "status_range", "operator": "eq", "value": "2xx" }
-                // Também suporta ranges customizados: "4xx", "5xx", etc.
-                "status_range" => {
-                    let range_str = assertion.value.as_str().unwrap_or("");
-
-                    // Determina o range esperado
-                    let (min_status, max_status) = match range_str.to_lowercase().as_str() {
-                        "1xx" => (100, 199),
-                        "2xx" | "success" => (200, 299),
-                        "3xx" | "redirect" => (300, 399),
-                        "4xx" | "client_error" => (400, 499),
-                        "5xx" | "server_error" => (500, 599),
-                        _ => {
-                            // Tenta parsear como range customizado "NNN-NNN"
-                            if let Some((min_str, max_str)) = range_str.split_once('-') {
-                                let min = min_str.trim().parse::<u16>().unwrap_or(0);
-                                let max = max_str.trim().parse::<u16>().unwrap_or(0);
-                                (min, max)
-                            } else {
-                                (0, 0) // Range inválido
-                            }
+                let expected = assertion.value.as_u64().unwrap_or(0) as u16;
+                let passed = match assertion.operator.as_str() {
+                    "eq" => ctx.status == expected,
+                    "neq" => ctx.status != expected,
+                    "lt" => ctx.status < expected,
+                    "gt" => ctx.status > expected,
+                    "lte" | "le" => ctx.status <= expected,
+                    "gte" | "ge" => ctx.status >= expected,
+                    _ => false,
+                };
+                if !passed {
+                    return Some(format!(
+                        "Assertion failed: status_code {} {} (got {})",
+                        assertion.operator, expected, ctx.status
+                    ));
+                }
+            }
+
+            // ============================================================
+            // ASSERTION: STATUS_RANGE
+            // ============================================================
+            // Valida que o status HTTP está em um range (2xx, 3xx, 4xx, 5xx).
+            // Exemplo: { "type": "status_range", "operator": "eq", "value": "2xx" }
+            // Também suporta ranges customizados: "4xx", "5xx", etc.
+            "status_range" => {
+                let range_str = assertion.value.as_str().unwrap_or("");
+
+                // Determina o range esperado
+                let (min_status, max_status) = match range_str.to_lowercase().as_str() {
+                    "1xx" => (100, 199),
+                    "2xx" | "success" => (200, 299),
+                    "3xx" | "redirect" => (300, 399),
+                    "4xx" | "client_error" => (400, 499),
+                    "5xx" | "server_error" => (500, 599),
+                    _ => {
+                        // Tenta parsear como range customizado "NNN-NNN"
+                        if let Some((min_str, max_str)) = range_str.split_once('-') {
+                            let min = min_str.trim().parse::<u16>().unwrap_or(0);
+                            let max = max_str.trim().parse::<u16>().unwrap_or(0);
+                            (min, max)
+                        } else {
+                            (0, 0) // Range inválido
                         }
-                    };
+                    }
+                };
 
-                    let in_range = ctx.status >= min_status && ctx.status <= max_status;
-                    let passed = match assertion.operator.as_str() {
-                        "eq" | "in" => in_range,
-                        "neq" | "not_in" => !in_range,
-                        _ => in_range, // Default: eq
-                    };
+                let in_range = ctx.status >= min_status && ctx.status <= max_status;
+                let passed = match assertion.operator.as_str() {
+                    "eq" | "in" => in_range,
+                    "neq" | "not_in" => !in_range,
+                    _ => in_range, // Default: eq
+                };
 
-                    if !passed {
-                        return Some(format!(
-                            "Assertion failed: status_range {} '{}' ({}-{}) (got {})",
-                            assertion.operator, range_str, min_status, max_status, ctx.status
-                        ));
-                    }
+                if !passed {
+                    return Some(format!(
+                        "Assertion failed: status_range {} '{}' ({}-{}) (got {})",
+                        assertion.operator, range_str, min_status, max_status, ctx.status
+                    ));
                 }
+            }
 
-                // ============================================================
-                // ASSERTION: JSON_BODY
-                // ============================================================
-                // Valida um campo específico do body JSON.
-                // Exemplo: { "type": "json_body", "path": "data.id", "operator": "eq", "value": 123 }
-                "json_body" => {
-                    if let Some(path) = &assertion.path {
-                        // Remove prefixo $. do JSONPath se presente
-                        let clean_path = path.strip_prefix("$.").unwrap_or(path);
-
-                        // Converte o path para formato JSON Pointer.
-                        // "data.user.id" → "/data/user/id"
-                        let pointer = if clean_path.starts_with('/') {
-                            clean_path.to_string()
-                        } else {
-                            format!("/{}", clean_path.replace('.', "/"))
-                        };
-
-                        // Tenta encontrar o valor no body usando JSON Pointer.
-                        if let Some(actual) = ctx.body.pointer(&pointer) {
-                            let passed = match assertion.operator.as_str() {
-                                "eq" => actual == &assertion.value,
-                                "neq" => actual != &assertion.value,
-                                "contains" => actual
-                                    .as_str()
-                                    .map(|s| {
-                                        assertion
-                                            .value
-                                            .as_str()
-                                            .map(|needle| s.contains(needle))
-                                            .unwrap_or(false)
-                                    })
-                                    .unwrap_or(false),
-                                // ========================================================
-                                // OPERADOR: MATCHES_REGEX
-                                // ========================================================
-                                // Valida que o valor corresponde a uma expressão regular.
-                                // Exemplo: { "operator": "matches_regex", "value": "^[A-Z]{2}\\d{4}$" }
-                                "matches_regex" | "regex" => {
-                                    if let (Some(actual_str), Some(pattern)) =
-                                        (actual.as_str(), assertion.value.as_str())
-                                    {
-                                        match Regex::new(pattern) {
-                                            Ok(re) => re.is_match(actual_str),
-                                            Err(_) => {
-                                                tracing::warn!(
-                                                    pattern = %pattern,
-                                                    "Invalid regex pattern in assertion"
-                                                );
-                                                false
-                                            }
+            // ============================================================
+            // ASSERTION: JSON_BODY
+            // ============================================================
+            // Valida um campo específico do body JSON.
+            // Exemplo: { "type": "json_body", "path": "data.id", "operator": "eq", "value": 123 }
+            "json_body" => {
+                if let Some(path) = &assertion.path {
+                    // "data.user.id" → "/data/user/id"
+                    let pointer = json_pointer(path);
+
+                    // Tenta encontrar o valor no body usando JSON Pointer.
+                    if let Some(actual) = ctx.body.pointer(&pointer) {
+                        // "1.234,56" → 1234.56 quando a assertion tem `locale`.
+                        let actual = localize(actual, assertion.locale.as_ref());
+                        let actual = actual.as_ref();
+                        let passed = match assertion.operator.as_str() {
+                            "eq" => actual == &assertion.value,
+                            "neq" => actual != &assertion.value,
+                            "contains" => actual
+                                .as_str()
+                                .map(|s| {
+                                    assertion
+                                        .value
+                                        .as_str()
+                                        .map(|needle| s.contains(needle))
+                                        .unwrap_or(false)
+                                })
+                                .unwrap_or(false),
+                            // ========================================================
+                            // OPERADOR: MATCHES_REGEX
+                            // ========================================================
+                            // Valida que o valor corresponde a uma expressão regular.
+                            // Exemplo: { "operator": "matches_regex", "value": "^[A-Z]{2}\\d{4}$" }
+                            "matches_regex" | "regex" => {
+                                if let (Some(actual_str), Some(pattern)) =
+                                    (actual.as_str(), assertion.value.as_str())
+                                {
+                                    match Regex::new(pattern) {
+                                        Ok(re) => re.is_match(actual_str),
+                                        Err(_) => {
+                                            tracing::warn!(
+                                                pattern = %pattern,
+                                                "Invalid regex pattern in assertion"
+                                            );
+                                            false
                                         }
-                                    } else {
-                                        false
                                     }
+                                } else {
+                                    false
                                 }
-                                "exists" => true,      // Se chegou aqui, existe
-                                "not_exists" => false, // Se chegou aqui, existe → falha
-                                "gt" => compare_values(actual, &assertion.value, |a, b| a > b),
-                                "lt" => compare_values(actual, &assertion.value, |a, b| a < b),
-                                "gte" | "ge" => {
-                                    compare_values(actual, &assertion.value, |a, b| a >= b)
-                                }
-                                "lte" | "le" => {
-                                    compare_values(actual, &assertion.value, |a, b| a <= b)
-                                }
-                                _ => false,
-                            };
+                            }
+                            "exists" => true,      // Se chegou aqui, existe
+                            "not_exists" => false, // Se chegou aqui, existe → falha
+                            "gt" => compare_values(actual, &assertion.value, |a, b| a > b),
+                            "lt" => compare_values(actual, &assertion.value, |a, b| a < b),
+                            "gte" | "ge" => compare_values(actual, &assertion.value, |a, b| a >= b),
+                            "lte" | "le" => compare_values(actual, &assertion.value, |a, b| a <= b),
+                            "approx_eq" => {
+                                approx_eq(actual, &assertion.value, assertion.tolerance.as_ref())
+                            }
+                            // semver_gte, date_before/after, uuid/email/url_valid, matches_subset
+                            other => {
+                                evaluate_semantic(other, actual, &assertion.value).unwrap_or(false)
+                            }
+                        };
 
-                            if !passed {
-                                return Some(format!(
+                        if !passed {
+                            return Some(with_excerpt(
+                                format!(
                                     "Assertion failed: json_body '{}' {} {} (got {})",
                                     path, assertion.operator, assertion.value, actual
-                                ));
-                            }
+                                ),
+                                ctx.body,
+                                &pointer,
+                                self.header_capture.redaction(),
+                            ));
+                        }
+                    } else {
+                        // O path não foi encontrado no body.
+                        if assertion.operator == "not_exists" {
+                            return None; // OK, não existe como esperado
+                        }
+                        let message = if assertion.operator == "exists" {
+                            format!(
+                                "Assertion failed: path '{}' should exist but was not found",
+                                path
+                            )
                         } else {
-                            // O path não foi encontrado no body.
-                            if assertion.operator == "not_exists" {
-                                continue; // OK, não existe como esperado
-                            }
-                            if assertion.operator == "exists" {
-                                return Some(format!(
-                                    "Assertion failed: path '{}' should exist but was not found",
-                                    path
-                                ));
-                            }
-                            return Some(format!(
+                            format!(
                                 "Assertion failed: path '{}' not found in response body",
                                 path
-                            ));
-                        }
+                            )
+                        };
+                        // Mostra o que veio no lugar (pai do path, mascarado).
+                        return Some(with_excerpt(
+                            message,
+                            ctx.body,
+                            &pointer,
+                            self.header_capture.redaction(),
+                        ));
                     }
                 }
+            }
 
-                // ============================================================
-                // ASSERTION: HEADER
-                // ============================================================
-                // Valida um header da resposta HTTP.
-                // Exemplo: { "type": "header", "path": "Content-Type", "operator": "contains", "value": "json" }
-                "header" => {
-                    if let Some(header_name) = &assertion.path {
-                        // Tenta obter o valor do header.
-                        let header_value =
-                            ctx.headers.get(header_name).and_then(|v| v.to_str().ok());
-
-                        match assertion.operator.as_str() {
-                            "exists" => {
-                                if header_value.is_none() {
-                                    return Some(format!(
-                                        "Assertion failed: header '{}' should exist",
-                                        header_name
-                                    ));
-                                }
-                            }
-                            "not_exists" => {
-                                if header_value.is_some() {
-                                    return Some(format!(
-                                        "Assertion failed: header '{}' should not exist",
-                                        header_name
-                                    ));
-                                }
-                            }
-                            "eq" => {
-                                let expected = assertion.value.as_str().unwrap_or("");
-                                if header_value != Some(expected) {
-                                    return Some(format!(
-                                        "Assertion failed: header '{}' {} '{}' (got '{}')",
-                                        header_name,
-                                        assertion.operator,
-                                        expected,
-                                        header_value.unwrap_or("<missing>")
-                                    ));
-                                }
-                            }
-                            "neq" => {
-                                let expected = assertion.value.as_str().unwrap_or("");
-                                if header_value == Some(expected) {
-                                    return Some(format!(
-                                        "Assertion failed: header '{}' should not equal '{}'",
-                                        header_name, expected
-                                    ));
-                                }
-                            }
-                            "contains" => {
-                                let needle = assertion.value.as_str().unwrap_or("");
-                                let contains =
-                                    header_value.map(|v| v.contains(needle)).unwrap_or(false);
-                                if !contains {
-                                    return Some(format!(
-                                        "Assertion failed: header '{}' should contain '{}' (got '{}')",
-                                        header_name, needle, header_value.unwrap_or("<missing>")
-                                    ));
-                                }
-                            }
-                            _ => {}
-                        }
+            // ============================================================
+            // ASSERTION: HEADER
+            // ============================================================
+            // Valida um header da resposta HTTP.
+            // Exemplo: { "type": "header", "path": "Content-Type", "operator": "contains", "value": "json" }
+            "header" => {
+                if let Some(header_name) = &assertion.path {
+                    if let Some(error) = check_header_assertion(assertion, header_name, ctx.headers)
+                    {
+                        return Some(error);
                     }
                 }
+            }
 
-                // ============================================================
-                // ASSERTION: LATENCY
-                // ============================================================
-                // Valida o tempo de resposta da requisição.
-                // Exemplo: { "type": "latency", "operator": "lt", "value": 500 }
-                "latency" => {
-                    let expected = assertion.value.as_u64().unwrap_or(0);
-                    let passed = match assertion.operator.as_str() {
-                        "lt" => ctx.duration_ms < expected,
-                        "lte" | "le" => ctx.duration_ms <= expected,
-                        "gt" => ctx.duration_ms > expected,
-                        "gte" | "ge" => ctx.duration_ms >= expected,
-                        "eq" => ctx.duration_ms == expected,
-                        _ => false,
-                    };
-                    if !passed {
-                        return Some(format!(
-                            "Assertion failed: latency {} {}ms (got {}ms)",
-                            assertion.operator, expected, ctx.duration_ms
-                        ));
-                    }
+            // ============================================================
+            // ASSERTIONS BINÁRIAS
+            // ============================================================
+            // Validam os bytes da resposta (imagens, PDFs, exports).
+            // Exemplo: { "type": "body_signature", "operator": "eq", "value": "pdf" }
+            // ============================================================
+            // ASSERTIONS DE STREAMING (first_byte_ms, chunk_count, stream_content)
+            // ============================================================
+            // Exemplo: { "type": "first_byte_ms", "operator": "lt", "value": 300 }
+            stream if is_stream_assertion(stream) => {
+                if let Some(error) = check_stream_assertion(assertion, &ctx.stream, ctx.raw_body) {
+                    return Some(error);
                 }
+            }
 
-                // ============================================================
-                // ASSERTION: JSON_SCHEMA
-                // ============================================================
-                // Valida que o body da resposta (ou parte dele) está em conformidade
-                // com um JSON Schema.
-                //
-                // Formatos suportados:
-                // 1. Schema inline no campo value:
-                //    { "type": "json_schema", "operator": "valid", "value": {"type": "object", ...} }
-                //
-                // 2. Validação de sub-path com schema:
-                //    { "type": "json_schema", "path": "data.user", "operator": "valid", "value": {...} }
-                //
-                // Operadores:
-                // - "valid" / "conforms": Body deve conformar ao schema
-                // - "invalid" / "not_conforms": Body NÃO deve conformar (para testes negativos)
-                "json_schema" => {
-                    // O schema é passado como value
-                    let schema = &assertion.value;
-
-                    // Valor a validar (body inteiro ou sub-path)
-                    let value_to_validate = if let Some(path) = &assertion.path {
-                        // Remove prefixo $. do JSONPath se presente
-                        let clean_path = path.strip_prefix("$.").unwrap_or(path);
-                        let pointer = if clean_path.starts_with('/') {
-                            clean_path.to_string()
-                        } else {
-                            format!("/{}", clean_path.replace('.', "/"))
-                        };
+            // ============================================================
+            // ASSERTION: GRAPHQL_ERRORS
+            // ============================================================
+            // GraphQL sobre http_request: erros por código e por campo.
+            // Exemplo: { "type": "graphql_errors", "operator": "not_exists", "value": null }
+            "graphql_errors" => {
+                if let Some(error) = check_graphql_errors(assertion, ctx.body) {
+                    return Some(error);
+                }
+            }
 
-                        match ctx.body.pointer(&pointer) {
-                            Some(v) => v.clone(),
-                            None => {
-                                return Some(format!(
-                                    "Assertion failed: json_schema path '{}' not found in response",
-                                    path
-                                ));
-                            }
-                        }
-                    } else {
-                        ctx.body.clone()
-                    };
+            binary if is_binary_assertion(binary) => {
+                if let Some(error) = check_binary_assertion(assertion, ctx.raw_body, ctx.headers) {
+                    return Some(error);
+                }
+            }
 
-                    // Compila o schema
-                    let compiled_schema = match JSONSchema::compile(schema) {
-                        Ok(s) => s,
-                        Err(e) => {
-                            tracing::warn!(
-                                error = %e,
-                                "Invalid JSON Schema in assertion"
-                            );
-                            return Some(format!("Assertion failed: invalid JSON Schema - {}", e));
-                        }
-                    };
+            // ============================================================
+            // ASSERTION: CONTENT_ENCODING
+            // ============================================================
+            // Valida a compressão da resposta (ausente = "identity").
+            // Exemplo: { "type": "content_encoding", "operator": "eq", "value": "gzip" }
+            "content_encoding" => {
+                if let Some(error) = check_content_encoding(assertion, ctx.headers) {
+                    return Some(error);
+                }
+            }
 
-                    // Valida o valor contra o schema
-                    let validation_result = compiled_schema.validate(&value_to_validate);
-                    let is_valid = validation_result.is_ok();
+            // ============================================================
+            // ASSERTION: LATENCY
+            // ============================================================
+            // Valida o tempo de resposta da requisição.
+            // Exemplo: { "type": "latency", "operator": "lt", "value": 500 }
+            "latency" => {
+                let expected = assertion.value.as_u64().unwrap_or(0);
+                let passed = match assertion.operator.as_str() {
+                    "lt" => ctx.duration_ms < expected,
+                    "lte" | "le" => ctx.duration_ms <= expected,
+                    "gt" => ctx.duration_ms > expected,
+                    "gte" | "ge" => ctx.duration_ms >= expected,
+                    "eq" => ctx.duration_ms == expected,
+                    _ => false,
+                };
+                if !passed {
+                    return Some(format!(
+                        "Assertion failed: latency {} {}ms (got {}ms)",
+                        assertion.operator, expected, ctx.duration_ms
+                    ));
+                }
+            }
 
-                    let passed = match assertion.operator.as_str() {
-                        "valid" | "conforms" | "eq" => is_valid,
-                        "invalid" | "not_conforms" | "neq" => !is_valid,
-                        _ => is_valid, // Default: valid
+            // ============================================================
+            // ASSERTION: JSON_SCHEMA
+            // ============================================================
+            // Valida que o body da resposta (ou parte dele) está em conformidade
+            // com um JSON Schema.
+            //
+            // Formatos suportados:
+            // 1. Schema inline no campo value:
+            //    { "type": "json_schema", "operator": "valid", "value": {"type": "object", ...} }
+            //
+            // 2. Validação de sub-path com schema:
+            //    { "type": "json_schema", "path": "data.user", "operator": "valid", "value": {...} }
+            //
+            // Operadores:
+            // - "valid" / "conforms": Body deve conformar ao schema
+            // - "invalid" / "not_conforms": Body NÃO deve conformar (para testes negativos)
+            "json_schema" => {
+                // O schema é passado como value
+                let schema = &assertion.value;
+
+                // Valor a validar (body inteiro ou sub-path)
+                let value_to_validate = if let Some(path) = &assertion.path {
+                    // Remove prefixo $. do JSONPath se presente
+                    let clean_path = path.strip_prefix("$.").unwrap_or(path);
+                    let pointer = if clean_path.starts_with('/') {
+                        clean_path.to_string()
+                    } else {
+                        format!("/{}", clean_path.replace('.', "/"))
                     };
 
-                    if !passed {
-                        if is_valid {
-                            // Esperava inválido, mas era válido
-                            return Some(
-                                "Assertion failed: json_schema expected invalid but body conforms to schema".to_string()
-                            );
-                        } else {
-                            // Esperava válido, mas era inválido
-                            // Coleta erros de validação para mensagem detalhada
-                            let errors: Vec<String> = compiled_schema
-                                .validate(&value_to_validate)
-                                .err()
-                                .map(|iter| {
-                                    iter.map(|e| format!("{} at {}", e, e.instance_path))
-                                        .take(3) // Limita a 3 erros para não poluir
-                                        .collect()
-                                })
-                                .unwrap_or_default();
-
+                    match ctx.body.pointer(&pointer) {
+                        Some(v) => v.clone(),
+                        None => {
                             return Some(format!(
-                                "Assertion failed: json_schema validation errors: [{}]",
-                                errors.join("; ")
+                                "Assertion failed: json_schema path '{}' not found in response",
+                                path
                             ));
                         }
                     }
-                }
+                } else {
+                    ctx.body.clone()
+                };
 
-                // Tipo de assertion desconhecido.
-                _ => {
-                    tracing::warn!(
-                        assertion_type = %assertion.assertion_type,
-                        "Unknown assertion type, skipping"
-                    );
+                // Compila o schema
+                let compiled_schema = match JSONSchema::compile(schema) {
+                    Ok(s) => s,
+                    Err(e) => {
+                        tracing::warn!(
+                            error = %e,
+                            "Invalid JSON Schema in assertion"
+                        );
+                        return Some(format!("Assertion failed: invalid JSON Schema - {}", e));
+                    }
+                };
+
+                // Valida o valor contra o schema
+                let validation_result = compiled_schema.validate(&value_to_validate);
+                let is_valid = validation_result.is_ok();
+
+                let passed = match assertion.operator.as_str() {
+                    "valid" | "conforms" | "eq" => is_valid,
+                    "invalid" | "not_conforms" | "neq" => !is_valid,
+                    _ => is_valid, // Default: valid
+                };
+
+                if !passed {
+                    if is_valid {
+                        // Esperava inválido, mas era válido
+                        return Some(
+                            "Assertion failed: json_schema expected invalid but body conforms to schema".to_string()
+                        );
+                    } else {
+                        // Esperava válido, mas era inválido
+                        // Coleta erros de validação para mensagem detalhada
+                        let errors: Vec<String> = compiled_schema
+                            .validate(&value_to_validate)
+                            .err()
+                            .map(|iter| {
+                                iter.map(|e| format!("{} at {}", e, e.instance_path))
+                                    .take(3) // Limita a 3 erros para não poluir
+                                    .collect()
+                            })
+                            .unwrap_or_default();
+
+                        return Some(format!(
+                            "Assertion failed: json_schema validation errors: [{}]",
+                            errors.join("; ")
+                        ));
+                    }
                 }
             }
-        }
 
-        // Todas as assertions passaram.
+            // ============================================================
+            // ASSERTION: CACHE_BEHAVIOR
+            // ============================================================
+            // Precisa de uma segunda requisição: avaliada em `execute`
+            // (ver `http_revalidation`).
+            // Exemplo: { "type": "cache_behavior", "operator": "eq", "value": "not_modified" }
+            http_revalidation::CACHE_BEHAVIOR => {}
+
+            // Tipo de assertion desconhecido.
+            _ => {
+                tracing::warn!(
+                    assertion_type = %assertion.assertion_type,
+                    "Unknown assertion type, skipping"
+                );
+            }
+        }
         None
     }
 
@@ -568,6 +1107,110 @@ impl HttpExecutor {
 
         results
     }
+
+    /// Aplica as regras de `config.auto_extract` à resposta.
+    ///
+    /// Cada valor encontrado é salvo como `<step_id>.<target>` (ex:
+    /// `${login.request_id}`), sem colidir entre steps. Regras sem valor
+    /// na resposta são ignoradas: a ideia é coletar "quando presente".
+    ///
+    /// ## Retorno:
+    /// Resultados das extrações que encontraram valor (para o relatório).
+    fn apply_auto_extractions(
+        &self,
+        step_id: &str,
+        body: &Value,
+        headers: &HeaderMap,
+        status: u16,
+        context: &mut Context,
+    ) -> Vec<ExtractionResult> {
+        if self.auto_extract.is_empty() {
+            return Vec::new();
+        }
+
+        let headers_map: HashMap<String, String> = headers
+            .iter()
+            .filter_map(|(k, v)| {
+                v.to_str()
+                    .ok()
+                    .map(|v_str| (k.as_str().to_string(), v_str.to_string()))
+            })
+            .collect();
+
+        let (results, _) = Extractor::process_with_status(
+            &self.auto_extract,
+            Some(body),
+            &headers_map,
+            Some(status),
+        );
+
+        results
+            .into_iter()
+            .filter(|result| result.success && result.value.as_ref().is_some_and(|v| !v.is_null()))
+            .map(|mut result| {
+                result.target = format!("{}.{}", step_id, result.target);
+                if let Some(value) = &result.value {
+                    context.set(result.target.clone(), value.clone());
+                }
+                result
+            })
+            .collect()
+    }
+}
+
+/// JSON Schema dos params de `http_request` (ver `StepExecutor::params_schema`).
+pub fn params_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["method", "path"],
+        "properties": {
+            "method": { "type": "string" },
+            "path": { "type": "string" },
+            "headers": {
+                "type": "object",
+                "additionalProperties": { "type": "string" }
+            },
+            "query_params": { "type": "object" },
+            "query": { "type": "object" },
+            "body": {},
+            "body_file": { "type": "string" },
+            "body_file_interpolate": { "type": "boolean" },
+            "timeout_ms": { "type": "integer", "minimum": 1 },
+            "decompress": { "type": "boolean" },
+            "signer": { "type": "string" },
+            "proxy": { "type": "string" },
+            "interceptors": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["type"],
+                    "properties": {
+                        "type": { "type": "string" },
+                        "delay_ms": { "type": "integer", "minimum": 0 },
+                        "set": {
+                            "type": "object",
+                            "additionalProperties": { "type": "string" }
+                        },
+                        "remove": { "type": "array", "items": { "type": "string" } }
+                    }
+                }
+            },
+            "unix_socket": { "type": "string" },
+            "http_version": { "type": "string", "enum": ["1.1", "2"] },
+            "cache": { "type": "boolean" },
+            "auth": {
+                "type": "object",
+                "required": ["type", "username", "password"],
+                "properties": {
+                    "type": { "type": "string", "enum": ["basic", "digest", "ntlm", "negotiate"] },
+                    "username": { "type": "string" },
+                    "password": { "type": "string" }
+                },
+                "additionalProperties": false
+            }
+        },
+        "additionalProperties": false
+    })
 }
 
 // ============================================================================
@@ -576,9 +1219,17 @@ impl HttpExecutor {
 
 #[async_trait]
 impl StepExecutor for HttpExecutor {
-    /// Retorna true se a action for "http_request".
+    /// Retorna true se a action for "http_request" ou "graphql_request".
     fn can_handle(&self, action: &str) -> bool {
-        action == "http_request"
+        action == "http_request" || action == "graphql_request"
+    }
+
+    /// Schema dos params de `http_request` ou, para `graphql_request`, os do GraphQL.
+    fn params_schema(&self, action: &str) -> Option<Value> {
+        Some(match action {
+            "graphql_request" => super::graphql::params_schema(),
+            _ => params_schema(),
+        })
     }
 
     /// Executa uma requisição HTTP.
@@ -594,6 +1245,9 @@ impl StepExecutor for HttpExecutor {
             http.url = tracing::field::Empty,
             http.status_code = tracing::field::Empty,
             http.duration_ms = tracing::field::Empty,
+            http.timing.dns_ms = tracing::field::Empty,
+            http.timing.ttfb_ms = tracing::field::Empty,
+            http.timing.transfer_ms = tracing::field::Empty,
             otel.kind = "client"
         )
     )]
@@ -601,9 +1255,21 @@ impl StepExecutor for HttpExecutor {
         let span = tracing::Span::current();
         let start_time = Instant::now();
 
+        // `graphql_request` vira um POST comum (ver `graphql::to_http_step`).
+        let graphql_step;
+        let step = if step.action == "graphql_request" {
+            graphql_step = super::graphql::to_http_step(step)?;
+            &graphql_step
+        } else {
+            step
+        };
+
         // ====================================================================
         // SNAPSHOT: CONTEXTO ANTES DA EXECUÇÃO
         // ====================================================================
+        // Token OIDC ainda válido (renovado se estiver para expirar).
+        self.authenticate(context).await?;
+
         let context_before = context.variables.clone();
 
         // ====================================================================
@@ -633,22 +1299,20 @@ impl StepExecutor for HttpExecutor {
 
         // Se o path já é uma URL completa, usa diretamente.
         // Senão, combina com a base_url do contexto.
-        let mut url = if interpolated_path.starts_with("http") {
-            interpolated_path
-        } else {
-            let base = context
-                .get("base_url")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            format!("{}{}", base.trim_end_matches('/'), interpolated_path)
+        // Com `unix://` (ou `params.unix_socket`), o host é o socket local.
+        let unix_socket = UnixSocket::from_target(params, context, &interpolated_path)?;
+        let mut url = match &unix_socket {
+            Some(_) => UnixSocket::request_url(&interpolated_path),
+            None => resolve_url(context, &interpolated_path),
         };
 
         // ====================================================================
         // PASSO 2.1: QUERY PARAMETERS
         // ====================================================================
 
-        // Adiciona query_params à URL se presentes.
-        if let Some(query_params) = params.get("query_params").and_then(|q| q.as_object()) {
+        // Adiciona query_params (ou `query`, nome do schema UTDL) à URL se presentes.
+        let query_params = params.get("query_params").or_else(|| params.get("query"));
+        if let Some(query_params) = query_params.and_then(|q| q.as_object()) {
             let mut query_parts: Vec<String> = Vec::new();
             for (k, v) in query_params {
                 // Interpola o valor do parâmetro
@@ -671,6 +1335,12 @@ impl StepExecutor for HttpExecutor {
         let method = Method::from_bytes(method_str.as_bytes())
             .map_err(|e| anyhow!("Invalid HTTP method: {}", e))?;
 
+        // A requisição usa a URL HTTP; relatório, span e cache, a do socket.
+        let request_url = url.clone();
+        if let Some(socket) = &unix_socket {
+            url = socket.display_url(&request_url);
+        }
+
         // Registra atributos no span OTEL.
         span.record("http.method", method_str);
         span.record("http.url", &url);
@@ -679,179 +1349,1783 @@ impl StepExecutor for HttpExecutor {
         // PASSO 3: CONSTRUÇÃO DA REQUISIÇÃO
         // ====================================================================
 
-        let mut request_builder = self.client.request(method, &url);
+        // Steps de um ator ou sessão usam o cliente dele (cookies e pool próprios).
+        let session = self.clients.session(step)?;
+        if unix_socket.is_some() && session.is_some() {
+            return Err(anyhow!(
+                "unix_socket não pode ser combinado com session/actor"
+            ));
+        }
 
-        // Aplica global_headers primeiro (do config do plano).
-        if let Some(global_headers) = context.get("global_headers").and_then(|h| h.as_object()) {
-            for (k, v) in global_headers {
-                if let Some(v_str) = v.as_str() {
-                    let value = context.interpolate_str(v_str)?;
-                    request_builder = request_builder.header(k, value);
-                }
+        // `http_version` fixa o protocolo (cliente HTTP/1.1 ou HTTP/2 próprio).
+        let version = HttpVersion::from_params(params)?;
+        let client = match (version, session) {
+            (Some(_), Some(_)) => {
+                return Err(anyhow!(
+                    "http_version não pode ser combinado com session/actor"
+                ))
             }
-        }
+            (Some(version), None) => self.versions.client(version),
+            (None, session) => session.map_or(&self.clients.client, |s| &s.client),
+        };
 
-        // Adiciona headers do step (sobrescrevem global_headers).
-        if let Some(headers) = params.get("headers").and_then(|h| h.as_object()) {
-            for (k, v) in headers {
-                if let Some(v_str) = v.as_str() {
-                    let value = context.interpolate_str(v_str)?;
-                    request_builder = request_builder.header(k, value);
-                }
+        // `proxy`: cliente próprio que sai pelo proxy do step.
+        let proxy_client = match params.get("proxy").and_then(|p| p.as_str()) {
+            Some(_) if version.is_some() || session.is_some() || unix_socket.is_some() => {
+                return Err(anyhow!(
+                    "proxy não pode ser combinado com http_version, session/actor ou unix_socket"
+                ))
+            }
+            Some(proxy) => Some(self.proxies.client(&context.interpolate_str(proxy)?)?),
+            None => None,
+        };
+        let client = proxy_client.as_ref().unwrap_or(client);
+
+        let mut request_builder = client.request(method.clone(), &request_url);
+        for (name, value) in request_headers(step, session, context)? {
+            request_builder = request_builder.header(name, value);
+        }
+        let step_headers = params.get("headers").and_then(|h| h.as_object());
+
+        // Relógio simulado (`clock_skew_ms`): `Date` deslocado, se o step não definir.
+        if context.clock_skew_ms != 0 {
+            let global_headers = context.get("global_headers").and_then(|h| h.as_object());
+            let has_date = [step_headers, global_headers]
+                .into_iter()
+                .flatten()
+                .any(|h| h.keys().any(|k| k.eq_ignore_ascii_case("date")));
+            if !has_date {
+                let date = context.now().format("%a, %d %b %Y %H:%M:%S GMT");
+                request_builder = request_builder.header(DATE, date.to_string());
             }
         }
 
         // Adiciona body (com interpolação recursiva).
+        let mut request_body = None;
         if let Some(body) = params.get("body") {
             let resolved = context.interpolate_value(body)?;
             request_builder = request_builder.json(&resolved);
+            request_body = Some(resolved);
+        }
+
+        // Body de arquivo (`body_file`): interpolado ou em streaming do disco.
+        if let Some(raw_path) = params.get("body_file").and_then(|f| f.as_str()) {
+            let interpolate = params
+                .get("body_file_interpolate")
+                .and_then(|i| i.as_bool())
+                .unwrap_or(true);
+            let file = FileBody::open(raw_path, interpolate, context).await?;
+            let global_headers = context.get("global_headers").and_then(|h| h.as_object());
+            let has_content_type = [step_headers, global_headers]
+                .into_iter()
+                .flatten()
+                .any(|h| h.keys().any(|k| k.eq_ignore_ascii_case("content-type")));
+            if !has_content_type {
+                request_builder = request_builder.header(CONTENT_TYPE, file.content_type());
+            }
+            request_body = Some(serde_json::json!({
+                "body_file": file.path.to_string_lossy(),
+                "bytes": file.size,
+            }));
+            request_builder = request_builder
+                .header(CONTENT_LENGTH, file.size)
+                .body(file.body);
+        }
+
+        // Autenticação por desafio (`params.auth`: basic, digest, ntlm).
+        let step_auth = ChallengeAuth::from_params(params, context)?;
+        if unix_socket.is_some() && step_auth.is_some() {
+            return Err(anyhow!("unix_socket não pode ser combinado com auth"));
+        }
+
+        // Assinatura (`params.signer`): calculada sobre a requisição final.
+        let signer = match params.get("signer").and_then(|s| s.as_str()) {
+            Some(_) if step_auth.is_some() => {
+                return Err(anyhow!("signer não pode ser combinado com auth"))
+            }
+            Some(name) => Some(self.signers.build(name, context)?),
+            None => None,
+        };
+        let signed_at = context.now();
+
+        // Middlewares do step (`params.interceptors`), na ordem declarada.
+        let interceptors = self.interceptors.chain(params, context)?;
+
+        // GET/HEAD iguais na execução vão à rede uma vez (`"cache": false` desativa).
+        let cache = self.cache.as_ref().filter(|_| {
+            ResponseCache::is_cacheable(&method)
+                && params.get("cache").and_then(|c| c.as_bool()) != Some(false)
+        });
+        // Body em streaming não pode ser fotografado: vai sem cache.
+        let cache_preview = cache.and_then(|_| request_builder.try_clone());
+        let cache_key = match cache_preview {
+            Some(preview) => {
+                let identity = step
+                    .actor
+                    .as_deref()
+                    .or(step.session.as_deref())
+                    .or(step_auth.as_ref().map(|a| a.username.as_str()));
+                // Headers como sairão (inclusive o token da sessão).
+                let mut preview = preview.build()?;
+                if let Some(session) = session {
+                    session.authorize(&mut preview)?;
+                }
+                Some(ResponseCache::key(
+                    &method,
+                    &url,
+                    request_body.as_ref(),
+                    identity,
+                    preview.headers(),
+                ))
+            }
+            None => None,
+        };
+
+        // Bodies só entram no relatório com `--report-detail full`.
+        let capture_bodies = context.get("report_detail").and_then(|d| d.as_str()) == Some("full");
+        if !capture_bodies {
+            request_body = None;
+        }
+
+        // Aplica timeout (do step ou global).
+        let timeout_ms = params
+            .get("timeout_ms")
+            .and_then(|t| t.as_u64())
+            .or_else(|| context.get("timeout_ms").and_then(|t| t.as_u64()))
+            .unwrap_or(30000); // Default: 30 segundos
+
+        request_builder = request_builder.timeout(Duration::from_millis(timeout_ms));
+
+        // `cache_behavior` repete a requisição (body em streaming não pode ser repetido).
+        let revalidation_builder =
+            http_revalidation::is_requested(&step.assertions).then(|| request_builder.try_clone());
+
+        // ====================================================================
+        // PASSO 4: EXECUÇÃO DA REQUISIÇÃO
+        // ====================================================================
+
+        // A sonda registra o DNS se uma conexão nova for aberta nesta requisição.
+        let dns_probe = Arc::new(DnsProbe::default());
+        // Headers e tamanho do que foi enviado (sem envio em cache hits).
+        let sent = std::sync::Mutex::new(None::<SentRequest>);
+        let send_start = Instant::now();
+        let fetch = || async {
+            let send = async {
+                let mut request = request_builder.build()?;
+                if let Some(auth) = &step_auth {
+                    interceptors.on_request(&mut request).await?;
+                    *sent.lock().unwrap() = Some(SentRequest::of(&request));
+                    return auth.execute(client, request).await;
+                }
+                if let Some(session) = session {
+                    session.authorize(&mut request)?;
+                }
+                interceptors.on_request(&mut request).await?;
+                if let Some(signer) = &signer {
+                    signer.sign(&mut request, signed_at).await?;
+                }
+                *sent.lock().unwrap() = Some(SentRequest::of(&request));
+                if let Some(socket) = &unix_socket {
+                    return socket.send(request, version).await;
+                }
+                let Some(session) = session else {
+                    return Ok(client.execute(request).await?);
+                };
+
+                let retry = request.try_clone();
+                let response = client.execute(request).await?;
+                match retry {
+                    // Token vencido: renova e repete a requisição uma vez.
+                    Some(mut retry) if session.should_refresh(response.status().as_u16()) => {
+                        session.refresh_token(context).await?;
+                        session.authorize(&mut retry)?;
+                        if let Some(signer) = &signer {
+                            signer.sign(&mut retry, signed_at).await?;
+                        }
+                        Ok(client.execute(retry).await?)
+                    }
+                    _ => Ok(response),
+                }
+            };
+            let response: Result<reqwest::Response> =
+                DNS_PROBE.scope(Arc::clone(&dns_probe), send).await;
+            let response = response?;
+            // O relógio da rede começa depois dos interceptores e da assinatura.
+            let send_start = sent
+                .lock()
+                .unwrap()
+                .as_ref()
+                .map_or(send_start, |sent| sent.at);
+            let ttfb_ms = send_start.elapsed().as_millis() as u64;
+            let connection_reused = self.connections.observe(&response);
+            let http_version = http_version::negotiated(response.version());
+            let peer_addr = http_connections::peer_addr(&response);
+            let final_url = response.url().to_string();
+            let status = response.status().as_u16();
+            let headers = response.headers().clone();
+            interceptors.on_response(status, &headers);
+            let transfer_start = Instant::now();
+            let (body, stream, error) = read_body(response, send_start).await;
+            if stream.timed_out {
+                return Err(anyhow::Error::new(BodyTimeout {
+                    status,
+                    headers,
+                    bytes_received: body.len(),
+                    stream,
+                }));
+            }
+            // Conexão caiu no meio do body: o que chegou está incompleto.
+            if let Some(e) = error {
+                return Err(anyhow::Error::new(e).context(format!(
+                    "Body interrompido após {} bytes (status {})",
+                    body.len(),
+                    status
+                )));
+            }
+            Ok(FetchedResponse {
+                status,
+                headers,
+                body,
+                ttfb_ms,
+                transfer_ms: transfer_start.elapsed().as_millis() as u64,
+                stream,
+                connection_reused,
+                http_version,
+                peer_addr,
+                final_url,
+            })
+        };
+        let response = match (cache, cache_key) {
+            (Some(cache), Some(key)) => cache.get_or_fetch(key, fetch).await,
+            _ => fetch().await.map(|r| (Arc::new(r), false)),
+        };
+        if let Some(recorded) = interceptors.recordings() {
+            context.set(format!("{}.recorded", step.id), recorded);
+        }
+        let sent_request = sent.lock().unwrap().take();
+        let sent_at = sent_request.as_ref().map_or(send_start, |sent| sent.at);
+        let request_headers = sent_request
+            .as_ref()
+            .and_then(|sent| self.header_capture.capture(&sent.headers).0);
+        let request_bytes = sent_request.and_then(|sent| sent.body_bytes);
+
+        // ====================================================================
+        // PASSO 5: PROCESSAMENTO DA RESPOSTA
+        // ====================================================================
+
+        match response {
+            Ok((fetched, cache_hit)) => {
+                let status = fetched.status;
+                let headers = fetched.headers.clone();
+                let raw_bytes = &fetched.body;
+                let peer_addr = (!cache_hit)
+                    .then(|| fetched.peer_addr.map(|addr| addr.to_string()))
+                    .flatten();
+
+                // Cache hit: sem rede, só o tempo de espera pela resposta guardada.
+                let (ttfb_ms, transfer_ms) = if cache_hit {
+                    (send_start.elapsed().as_millis() as u64, 0)
+                } else {
+                    (fetched.ttfb_ms, fetched.transfer_ms)
+                };
+                let duration = (sent_at - start_time).as_millis() as u64 + ttfb_ms;
+
+                // Descompacta conforme Content-Encoding (`"decompress": false` desativa).
+                let decompress = params
+                    .get("decompress")
+                    .and_then(|d| d.as_bool())
+                    .unwrap_or(true);
+                let (body_bytes, compression) = decode_body(raw_bytes, &headers, decompress)?;
+                let body_json: Value = serde_json::from_slice(&body_bytes).unwrap_or(Value::Null);
+                let response_body =
+                    capture_bodies.then(|| String::from_utf8_lossy(&body_bytes).into_owned());
+                let (response_headers, response_headers_truncated) =
+                    self.header_capture.capture(&headers);
+                // Só aparece se redirecionamentos levaram a outra URL.
+                let final_url = reqwest::Url::parse(&request_url)
+                    .ok()
+                    .filter(|requested| {
+                        unix_socket.is_none() && requested.as_str() != fetched.final_url
+                    })
+                    .map(|_| fetched.final_url.clone());
+
+                let timing = HttpTiming {
+                    dns_ms: (!cache_hit).then(|| dns_probe.dns_ms()).flatten(),
+                    ttfb_ms,
+                    transfer_ms,
+                    total_ms: ttfb_ms + transfer_ms,
+                    first_byte_ms: fetched.stream.first_byte_ms,
+                    chunk_count: Some(fetched.stream.chunk_count).filter(|&c| c > 0),
+                    connection_reused: (!cache_hit).then_some(fetched.connection_reused).flatten(),
+                };
+
+                // Registra atributos da resposta no span OTEL.
+                span.record("http.status_code", status as i64);
+                span.record("http.duration_ms", duration as i64);
+                record_timing(&span, &timing);
+
+                tracing::info!(
+                    method = %method_str,
+                    %url,
+                    status,
+                    duration_ms = duration,
+                    cache_hit,
+                    "HTTP step finished"
+                );
+
+                // `cache_behavior`: a mesma requisição com If-None-Match/If-Modified-Since.
+                let revalidation = match revalidation_builder {
+                    None => None,
+                    Some(None) => Some(Revalidation::Failed(
+                        "request body is streamed from body_file and cannot be resent".to_string(),
+                    )),
+                    Some(Some(_))
+                        if http_revalidation::conditional_headers(&headers).is_empty() =>
+                    {
+                        Some(Revalidation::NoValidators)
+                    }
+                    Some(Some(builder)) => {
+                        let conditional = http_revalidation::conditional_headers(&headers);
+                        let send = async {
+                            // Mesma cadeia da requisição principal (interceptores inclusos).
+                            let mut request = builder.headers(conditional).build()?;
+                            let response = if let Some(auth) = &step_auth {
+                                interceptors.on_request(&mut request).await?;
+                                auth.execute(client, request).await?
+                            } else {
+                                if let Some(session) = session {
+                                    session.authorize(&mut request)?;
+                                }
+                                interceptors.on_request(&mut request).await?;
+                                if let Some(signer) = &signer {
+                                    signer.sign(&mut request, signed_at).await?;
+                                }
+                                match &unix_socket {
+                                    Some(socket) => socket.send(request, version).await?,
+                                    None => client.execute(request).await?,
+                                }
+                            };
+                            interceptors
+                                .on_response(response.status().as_u16(), response.headers());
+                            Revalidation::read(response).await
+                        };
+                        Some(
+                            send.await
+                                .unwrap_or_else(|e| Revalidation::Failed(format!("{:#}", e))),
+                        )
+                    }
+                };
+                if revalidation.is_some() {
+                    if let Some(recorded) = interceptors.recordings() {
+                        context.set(format!("{}.recorded", step.id), recorded);
+                    }
+                }
+                let cache_failures = revalidation
+                    .as_ref()
+                    .map(|r| http_revalidation::failures(&step.assertions, &headers, r))
+                    .unwrap_or_default();
+
+                // Extrações globais (config.auto_extract), mesmo se o step falhar.
+                let auto_results =
+                    self.apply_auto_extractions(&step.id, &body_json, &headers, status, context);
+
+                // Cria o contexto de resposta para validação.
+                let response_ctx = ResponseContext {
+                    status,
+                    body: &body_json,
+                    raw_body: &body_bytes,
+                    headers: &headers,
+                    duration_ms: duration,
+                    stream: fetched.stream,
+                };
+
+                // Valida as assertions.
+                // Aquecimento: a latência do cold start não é avaliada.
+                let assertions: Vec<Assertion> = step
+                    .assertions
+                    .iter()
+                    .filter(|a| !(step.warmup && a.assertion_type == "latency"))
+                    .cloned()
+                    .collect();
+                let mut failures = self.assertion_failures(&assertions, &response_ctx);
+                failures.extend(cache_failures);
+                if let Some(error_msg) = failures.first().map(|f| f.message.clone()) {
+                    tracing::warn!(error = %error_msg, "Assertion failed");
+                    return Ok(StepResult {
+                        step_id: step.id.clone(),
+                        status: StepStatus::Failed,
+                        duration_ms: duration,
+                        attempt: 1,
+                        error: Some(error_msg),
+                        assertion_failures: failures,
+                        context_before: Some(context_before),
+                        context_after: Some(context.variables.clone()),
+                        extractions: if auto_results.is_empty() {
+                            None
+                        } else {
+                            Some(auto_results)
+                        },
+                        http_details: Some(HttpDetails {
+                            method: method_str.to_string(),
+                            url: url.clone(),
+                            final_url: final_url.clone(),
+                            status_code: status,
+                            latency_ms: duration,
+                            request_headers: request_headers.clone(),
+                            response_headers: response_headers.clone(),
+                            response_headers_truncated,
+                            timing: Some(timing.clone()),
+                            request_body: request_body.clone(),
+                            response_body: response_body.clone(),
+                            request_bytes,
+                            response_bytes: Some(raw_bytes.len() as u64),
+                            compression: compression.clone(),
+                            cache_hit,
+                            http_version: Some(fetched.http_version.clone()),
+                            peer_addr: peer_addr.clone(),
+                        }),
+                        ..Default::default()
+                    });
+                }
+
+                // Aplica as extrações.
+                let mut extraction_results = auto_results;
+                extraction_results.extend(self.apply_extractions(
+                    &step.extract,
+                    &body_json,
+                    &headers,
+                    context,
+                ));
+
+                // Captura contexto após extrações
+                let context_after = context.variables.clone();
+
+                // Retorna sucesso.
+                Ok(StepResult {
+                    step_id: step.id.clone(),
+                    status: StepStatus::Passed,
+                    duration_ms: duration,
+                    attempt: 1,
+                    error: None,
+                    context_before: Some(context_before),
+                    context_after: Some(context_after),
+                    extractions: if extraction_results.is_empty() {
+                        None
+                    } else {
+                        Some(extraction_results)
+                    },
+                    http_details: Some(HttpDetails {
+                        method: method_str.to_string(),
+                        url: url.clone(),
+                        final_url,
+                        status_code: status,
+                        latency_ms: duration,
+                        request_headers,
+                        response_headers,
+                        response_headers_truncated,
+                        timing: Some(timing),
+                        request_body,
+                        response_body,
+                        request_bytes,
+                        response_bytes: Some(raw_bytes.len() as u64),
+                        compression,
+                        cache_hit,
+                        http_version: Some(fetched.http_version.clone()),
+                        peer_addr,
+                    }),
+                    ..Default::default()
+                })
+            }
+            Err(e) => {
+                // Erro na requisição (rede, DNS, timeout, etc.)
+                let ttfb_ms = send_start.elapsed().as_millis() as u64;
+                let duration = start_time.elapsed().as_millis() as u64;
+                tracing::error!(error = %format!("{:#}", e), "HTTP request failed");
+                Ok(StepResult {
+                    step_id: step.id.clone(),
+                    status: StepStatus::Failed,
+                    duration_ms: duration,
+                    attempt: 1,
+                    error: Some(format!("{:#}", e)),
+                    timeout_capture: http_timeout::capture(
+                        &e,
+                        timeout_ms,
+                        ttfb_ms,
+                        self.header_capture.redaction(),
+                    ),
+                    context_before: Some(context_before),
+                    context_after: Some(context.variables.clone()),
+                    extractions: None,
+                    http_details: Some(HttpDetails {
+                        method: method_str.to_string(),
+                        url: url.clone(),
+                        final_url: None,
+                        status_code: 0, // Sem resposta (erro de rede)
+                        latency_ms: duration,
+                        request_headers,
+                        response_headers: None,
+                        response_headers_truncated: false,
+                        // Sem resposta: só o DNS (se houve) é conhecido.
+                        timing: Some(HttpTiming {
+                            dns_ms: dns_probe.dns_ms(),
+                            ttfb_ms,
+                            transfer_ms: 0,
+                            total_ms: ttfb_ms,
+                            ..Default::default()
+                        }),
+                        request_body,
+                        response_body: None,
+                        request_bytes,
+                        response_bytes: None,
+                        compression: None,
+                        cache_hit: false,
+                        http_version: None,
+                        peer_addr: None,
+                    }),
+                    ..Default::default()
+                })
+            }
+        }
+    }
+}
+
+// ============================================================================
+// TESTES UNITÁRIOS
+// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{Assertion, AssertionLocale};
+    use serde_json::json;
+
+    /// Cria um HttpExecutor para testes
+    fn create_test_executor() -> HttpExecutor {
+        HttpExecutor::new()
+    }
+
+    // ========================================================================
+    // Testes: headers de correlação
+    // ========================================================================
+
+    #[test]
+    fn test_correlation_headers_default() {
+        let mut context = Context::new();
+        context.set("execution_id", json!("exec-123"));
+        context.set("correlation_header", json!("X-Execution-Id"));
+
+        let headers = correlation_headers(&context, "login", None);
+        assert_eq!(
+            headers,
+            vec![
+                ("X-Execution-Id".to_string(), "exec-123".to_string()),
+                ("X-Step-Id".to_string(), "login".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_correlation_headers_disabled_or_overridden() {
+        let mut context = Context::new();
+        context.set("execution_id", json!("exec-123"));
+        context.set("correlation_header", json!(""));
+        assert!(correlation_headers(&context, "login", None).is_empty());
+
+        context.set("correlation_header", json!("X-Correlation-Id"));
+        let explicit = json!({ "x-correlation-id": "manual" });
+        let headers = correlation_headers(&context, "login", explicit.as_object());
+        assert_eq!(
+            headers,
+            vec![("X-Step-Id".to_string(), "login".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_request_headers_send_each_name_once() {
+        let mut context = Context::new();
+        context.set("execution_id", json!("exec-123"));
+        context.set("correlation_header", json!("X-Execution-Id"));
+        context.set(
+            "global_headers",
+            json!({ "x-execution-id": "global", "Accept": "text/plain" }),
+        );
+        let step = Step {
+            id: "login".to_string(),
+            params: json!({ "headers": { "ACCEPT": "application/json" } }),
+            ..Default::default()
+        };
+
+        let headers = request_headers(&step, None, &context).unwrap();
+        let named = |name: &str| {
+            headers
+                .iter()
+                .filter(|(n, _)| n.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(named("x-execution-id"), ["exec-123"]);
+        assert_eq!(named("accept"), ["application/json"]);
+    }
+
+    // ========================================================================
+    // Testes: timing da requisição
+    // ========================================================================
+
+    /// Servidor HTTP mínimo que responde uma única requisição com o body JSON.
+    async fn serve_once(body: &'static str) -> u16 {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = socket.read(&mut buf).await;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+        port
+    }
+
+    #[tokio::test]
+    async fn test_response_cache_hits_network_once() {
+        // O servidor responde uma única vez: a segunda requisição só passa pelo cache.
+        let port = serve_once(r#"{"currency":"BRL"}"#).await;
+        let executor =
+            HttpExecutor::from_config(&create_config(crate::protocol::HttpClientConfig {
+                response_cache: true,
+                ..Default::default()
+            }))
+            .unwrap();
+        let url = format!("http://localhost:{}/config", port);
+        let step = |id: &str, params: Value| Step {
+            id: id.to_string(),
+            action: "http_request".to_string(),
+            params,
+            assertions: vec![Assertion {
+                assertion_type: "json_body".to_string(),
+                operator: "eq".to_string(),
+                value: json!("BRL"),
+                path: Some("$.currency".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let mut context = Context::new();
+        let mut hits = Vec::new();
+        for id in ["first", "second"] {
+            let result = executor
+                .execute(
+                    &step(id, json!({ "method": "GET", "path": url })),
+                    &mut context,
+                )
+                .await
+                .unwrap();
+            assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+            hits.push(result.http_details.unwrap().cache_hit);
+        }
+        assert_eq!(hits, vec![false, true]);
+
+        let uncached = step(
+            "uncached",
+            json!({ "method": "GET", "path": url, "cache": false }),
+        );
+        let result = executor.execute(&uncached, &mut context).await.unwrap();
+        assert_eq!(result.status, StepStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn test_warmup_ignores_latency_assertions() {
+        let executor = create_test_executor();
+        let mut context = Context::new();
+        let mut step = Step {
+            id: "warm".to_string(),
+            action: "http_request".to_string(),
+            assertions: vec![
+                Assertion {
+                    assertion_type: "latency".to_string(),
+                    operator: "lt".to_string(),
+                    value: json!(0),
+                    ..Default::default()
+                },
+                Assertion {
+                    assertion_type: "status_code".to_string(),
+                    operator: "eq".to_string(),
+                    value: json!(200),
+                    ..Default::default()
+                },
+            ],
+            warmup: true,
+            ..Default::default()
+        };
+
+        for (warmup, expected) in [(true, StepStatus::Passed), (false, StepStatus::Failed)] {
+            let port = serve_once(r#"{}"#).await;
+            step.warmup = warmup;
+            step.params = json!({ "method": "GET", "path": format!("http://localhost:{}/", port) });
+            let result = executor.execute(&step, &mut context).await.unwrap();
+            assert_eq!(result.status, expected, "warmup={}", warmup);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_graphql_errors_over_http_request() {
+        let executor = create_test_executor();
+        let mut context = Context::new();
+        let body = r#"{"data":{"user":null},"errors":[
+            {"message":"Bad id","path":["orders",0],"extensions":{"code":"BAD_USER_INPUT"}},
+            {"message":"Not allowed","path":["user","email"],"extensions":{"code":"FORBIDDEN"}}]}"#;
+
+        for (value, path, expected) in [
+            (json!("FORBIDDEN"), Some("user.email"), StepStatus::Passed),
+            (json!("FORBIDDEN"), None, StepStatus::Failed),
+        ] {
+            let port = serve_once(body).await;
+            let step = Step {
+                id: "gql".to_string(),
+                action: "http_request".to_string(),
+                params: json!({ "method": "POST", "path": format!("http://localhost:{}/graphql", port) }),
+                assertions: vec![Assertion {
+                    assertion_type: "graphql_errors".to_string(),
+                    operator: "eq".to_string(),
+                    value,
+                    path: path.map(String::from),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            };
+            let result = executor.execute(&step, &mut context).await.unwrap();
+            assert_eq!(result.status, expected, "{:?}", result.error);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_digest_auth_answers_challenge() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // 401 com desafio Digest até receber `Authorization: Digest ...`.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 4096];
+                let n = socket.read(&mut buf).await.unwrap();
+                let request = String::from_utf8_lossy(&buf[..n]).to_lowercase();
+                let response = if request.contains("authorization: digest username=\"qa\"")
+                    && request.contains("uri=\"/intranet?x=1\"")
+                {
+                    "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}"
+                } else {
+                    "HTTP/1.1 401 Unauthorized\r\nWWW-Authenticate: Digest realm=\"intranet\", qop=\"auth\", nonce=\"abc\"\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                };
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let executor = create_test_executor();
+        let mut context = Context::new();
+        let step = Step {
+            id: "intranet".to_string(),
+            action: "http_request".to_string(),
+            params: json!({
+                "method": "GET",
+                "path": format!("http://localhost:{}/intranet?x=1", port),
+                "auth": { "type": "digest", "username": "qa", "password": "pw" }
+            }),
+            assertions: vec![Assertion {
+                assertion_type: "status_code".to_string(),
+                operator: "eq".to_string(),
+                value: json!(200),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let result = executor.execute(&step, &mut context).await.unwrap();
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+    }
+
+    #[tokio::test]
+    async fn test_second_request_reuses_pooled_connection() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Keep-alive: responde a todas as requisições na mesma conexão.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            while socket.read(&mut buf).await.unwrap_or(0) > 0 {
+                socket
+                    .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\n{}")
+                    .await
+                    .unwrap();
+            }
+        });
+
+        let executor = create_test_executor();
+        let mut context = Context::new();
+        let step = Step {
+            id: "ping".to_string(),
+            action: "http_request".to_string(),
+            params: json!({ "method": "GET", "path": format!("http://127.0.0.1:{}/", port) }),
+            ..Default::default()
+        };
+        let reused = |result: &StepResult| {
+            result
+                .http_details
+                .as_ref()
+                .and_then(|h| h.timing.as_ref())
+                .and_then(|t| t.connection_reused)
+        };
+
+        let first = executor.execute(&step, &mut context).await.unwrap();
+        let second = executor.execute(&step, &mut context).await.unwrap();
+        assert_eq!(reused(&first), Some(false));
+        assert_eq!(reused(&second), Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_http_version_selects_protocol() {
+        use hyper::service::{make_service_fn, service_fn};
+
+        // O servidor do hyper aceita HTTP/1.1 e HTTP/2 com prior knowledge.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let make_service = make_service_fn(|_| async {
+            Ok::<_, std::convert::Infallible>(service_fn(|_| async {
+                Ok::<_, std::convert::Infallible>(hyper::Response::new(hyper::Body::from("{}")))
+            }))
+        });
+        tokio::spawn(
+            hyper::Server::from_tcp(listener)
+                .unwrap()
+                .serve(make_service),
+        );
+
+        let executor = create_test_executor();
+        let mut context = Context::new();
+        let step = |version: Option<&str>| {
+            let mut params =
+                json!({ "method": "GET", "path": format!("http://127.0.0.1:{}/", port) });
+            if let Some(version) = version {
+                params["http_version"] = json!(version);
+            }
+            Step {
+                id: "proto".to_string(),
+                action: "http_request".to_string(),
+                params,
+                ..Default::default()
+            }
+        };
+        let version = |result: StepResult| result.http_details.unwrap().http_version.unwrap();
+
+        let default = executor.execute(&step(None), &mut context).await.unwrap();
+        assert_eq!(version(default), "HTTP/1.1");
+        let h2 = executor
+            .execute(&step(Some("2")), &mut context)
+            .await
+            .unwrap();
+        assert_eq!(h2.status, StepStatus::Passed, "{:?}", h2.error);
+        assert_eq!(version(h2), "HTTP/2");
+        let h1 = executor
+            .execute(&step(Some("1.1")), &mut context)
+            .await
+            .unwrap();
+        assert_eq!(version(h1), "HTTP/1.1");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unix_socket_base_url() {
+        use hyper::service::service_fn;
+
+        let dir = std::env::temp_dir().join(format!("runner-unix-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let socket = dir.join("app.sock");
+        let listener = tokio::net::UnixListener::bind(&socket).unwrap();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let service = service_fn(|request: hyper::Request<hyper::Body>| async move {
+                    let body = json!({
+                        "path": request.uri().to_string(),
+                        "host": request.headers()["host"].to_str().unwrap(),
+                    });
+                    Ok::<_, std::convert::Infallible>(hyper::Response::new(hyper::Body::from(
+                        body.to_string(),
+                    )))
+                });
+                tokio::spawn(hyper::server::conn::Http::new().serve_connection(stream, service));
+            }
+        });
+
+        let executor = create_test_executor();
+        let mut context = Context::new();
+        context.set("base_url", json!(format!("unix://{}", socket.display())));
+        let step = Step {
+            id: "health".to_string(),
+            action: "http_request".to_string(),
+            params: json!({ "method": "GET", "path": "/health", "query_params": { "deep": "1" } }),
+            assertions: vec![Assertion {
+                assertion_type: "json_body".to_string(),
+                path: Some("path".to_string()),
+                operator: "eq".to_string(),
+                value: json!("/health?deep=1"),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let result = executor.execute(&step, &mut context).await.unwrap();
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+        let details = result.http_details.unwrap();
+        assert_eq!(
+            details.url,
+            format!("unix://{}:/health?deep=1", socket.display())
+        );
+        assert_eq!(details.http_version.as_deref(), Some("HTTP/1.1"));
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_body_file_is_streamed_with_content_length() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let payload = "x".repeat(256 * 1024);
+        let path = std::env::temp_dir().join(format!("aqa-upload-{}.bin", std::process::id()));
+        std::fs::write(&path, &payload).unwrap();
+
+        // Responde com o número de bytes de body recebidos.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut received = Vec::new();
+            let mut buf = [0u8; 65536];
+            let received_body = loop {
+                let n = socket.read(&mut buf).await.unwrap();
+                received.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&received).to_lowercase();
+                if let Some(end) = text.find("\r\n\r\n") {
+                    let length: usize = text
+                        .lines()
+                        .find_map(|l| l.strip_prefix("content-length: "))
+                        .map_or(0, |v| v.trim().parse().unwrap());
+                    if received.len() - end - 4 >= length {
+                        break length;
+                    }
+                }
+            };
+            let body = format!(r#"{{"bytes":{}}}"#, received_body);
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        let step = Step {
+            id: "upload".to_string(),
+            action: "http_request".to_string(),
+            params: json!({
+                "method": "POST",
+                "path": format!("http://localhost:{}/upload", port),
+                "body_file": path.to_string_lossy(),
+                "body_file_interpolate": false
+            }),
+            assertions: vec![Assertion {
+                assertion_type: "json_body".to_string(),
+                operator: "eq".to_string(),
+                value: json!(payload.len()),
+                path: Some("$.bytes".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let result = create_test_executor()
+            .execute(&step, &mut Context::new())
+            .await
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+    }
+
+    #[tokio::test]
+    async fn test_streamed_body_read_chunk_by_chunk() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Resposta chunked em 3 pedaços, com pausa entre eles.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = socket.read(&mut buf).await;
+            socket
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\nConnection: close\r\n\r\n",
+                )
+                .await
+                .unwrap();
+            for event in ["data: a\n\n", "data: b\n\n", "data: [DONE]\n\n"] {
+                let chunk = format!("{:x}\r\n{}\r\n", event.len(), event);
+                socket.write_all(chunk.as_bytes()).await.unwrap();
+                socket.flush().await.unwrap();
+                tokio::time::sleep(Duration::from_millis(30)).await;
+            }
+            socket.write_all(b"0\r\n\r\n").await.unwrap();
+        });
+
+        let assertions: Vec<Assertion> = serde_json::from_value(json!([
+            { "type": "first_byte_ms", "operator": "lt", "value": 1000 },
+            { "type": "chunk_count", "operator": "eq", "value": 3 },
+            { "type": "stream_content", "operator": "contains", "value": "data: [DONE]" }
+        ]))
+        .unwrap();
+        let step = Step {
+            id: "sse".to_string(),
+            action: "http_request".to_string(),
+            params: json!({ "method": "GET", "path": format!("http://localhost:{}/events", port) }),
+            assertions,
+            ..Default::default()
+        };
+        let result = create_test_executor()
+            .execute(&step, &mut Context::new())
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+
+        let timing = result.http_details.unwrap().timing.unwrap();
+        assert_eq!(timing.chunk_count, Some(3));
+        assert!(timing.first_byte_ms.unwrap() < timing.total_ms);
+    }
+
+    #[tokio::test]
+    async fn test_body_cut_mid_stream_fails_the_step() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Promete 100 bytes, entrega 10 e fecha a conexão.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = socket.read(&mut buf).await;
+            socket
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 100\r\n\r\n{\"partial\"")
+                .await
+                .unwrap();
+        });
+
+        let step = Step {
+            id: "cut".to_string(),
+            action: "http_request".to_string(),
+            params: json!({ "method": "GET", "path": format!("http://localhost:{}/", port) }),
+            ..Default::default()
+        };
+        let result = create_test_executor()
+            .execute(&step, &mut Context::new())
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Failed);
+        assert!(result
+            .error
+            .unwrap()
+            .contains("Body interrompido após 10 bytes (status 200)"));
+    }
+
+    #[tokio::test]
+    async fn test_http_timing_is_reported() {
+        let port = serve_once(r#"{"ok":true}"#).await;
+
+        let step = Step {
+            id: "timed".to_string(),
+            action: "http_request".to_string(),
+            params: json!({ "method": "GET", "path": format!("http://localhost:{}/", port) }),
+            ..Default::default()
+        };
+        let mut context = Context::new();
+        let result = create_test_executor()
+            .execute(&step, &mut context)
+            .await
+            .unwrap();
+
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+        let timing = result.http_details.unwrap().timing.unwrap();
+        assert!(timing.dns_ms.is_some());
+        assert_eq!(timing.total_ms, timing.ttfb_ms + timing.transfer_ms);
+    }
+
+    #[tokio::test]
+    async fn test_bodies_captured_only_with_full_detail() {
+        for (detail, expect_bodies) in [("standard", false), ("full", true)] {
+            let port = serve_once(r#"{"id":1}"#).await;
+            let step = Step {
+                id: "create".to_string(),
+                action: "http_request".to_string(),
+                params: json!({
+                    "method": "POST",
+                    "path": format!("http://localhost:{}/", port),
+                    "body": { "name": "${name}" }
+                }),
+                ..Default::default()
+            };
+            let mut context = Context::new();
+            context.set("name", json!("Ana"));
+            context.set("report_detail", json!(detail));
+
+            let result = create_test_executor()
+                .execute(&step, &mut context)
+                .await
+                .unwrap();
+            let http = result.http_details.unwrap();
+
+            if expect_bodies {
+                assert_eq!(http.request_body, Some(json!({ "name": "Ana" })));
+                assert_eq!(http.response_body.as_deref(), Some(r#"{"id":1}"#));
+            } else {
+                assert!(http.request_body.is_none());
+                assert!(http.response_body.is_none());
+            }
+        }
+    }
+
+    // ========================================================================
+    // Testes: cliente configurado via config.http
+    // ========================================================================
+
+    fn create_config(http: crate::protocol::HttpClientConfig) -> Config {
+        Config {
+            base_url: "http://localhost".to_string(),
+            timeout_ms: 5000,
+            global_headers: HashMap::new(),
+            variables: HashMap::new(),
+            correlation_header: crate::protocol::default_correlation_header(),
+            http,
+            ip_preference: Default::default(),
+            wait_for: None,
+            request_templates: HashMap::new(),
+            auto_extract: vec![],
+            sessions: Default::default(),
+            actors: Default::default(),
+            agents: Default::default(),
+            signers: Default::default(),
+            regions: Vec::new(),
+            quality_gate: None,
+            auth: None,
+            webhook: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_from_config_sends_user_agent() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Responde 200 apenas se o User-Agent configurado chegar.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 2048];
+            let n = socket.read(&mut buf).await.unwrap_or(0);
+            let request = String::from_utf8_lossy(&buf[..n]).to_lowercase();
+            let status = if request.contains("user-agent: aqa-test/1.0") {
+                "200 OK"
+            } else {
+                "400 Bad Request"
+            };
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                status
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        let executor =
+            HttpExecutor::from_config(&create_config(crate::protocol::HttpClientConfig {
+                pool_max_idle_per_host: Some(2),
+                pool_idle_timeout_ms: Some(1000),
+                http2_adaptive_window: true,
+                user_agent: Some("aqa-test/1.0".to_string()),
+                ..Default::default()
+            }))
+            .unwrap();
+
+        let step = Step {
+            id: "ua".to_string(),
+            action: "http_request".to_string(),
+            params: json!({ "method": "GET", "path": format!("http://localhost:{}/", port) }),
+            assertions: vec![Assertion {
+                assertion_type: "status_code".to_string(),
+                operator: "eq".to_string(),
+                value: json!(200),
+                path: None,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let result = executor.execute(&step, &mut Context::new()).await.unwrap();
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+    }
+
+    #[tokio::test]
+    async fn test_ip_preference_v4_reports_peer_addr() {
+        let port = serve_once("{}").await;
+        let mut config = create_config(Default::default());
+        config.ip_preference = crate::protocol::IpPreference::V4;
+        let executor = HttpExecutor::from_config(&config).unwrap();
+        let step = Step {
+            id: "dual_stack".to_string(),
+            action: "http_request".to_string(),
+            params: json!({ "method": "GET", "path": format!("http://localhost:{}/", port) }),
+            ..Default::default()
+        };
+
+        let result = executor.execute(&step, &mut Context::new()).await.unwrap();
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+        assert_eq!(
+            result.http_details.unwrap().peer_addr,
+            Some(format!("127.0.0.1:{}", port))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_plugin_signer_runs_before_send() {
+        use super::super::http_signer::RequestSigner;
+        use hyper::service::{make_service_fn, service_fn};
+
+        struct Vendor(String);
+        #[async_trait]
+        impl RequestSigner for Vendor {
+            async fn sign(
+                &self,
+                request: &mut reqwest::Request,
+                _now: chrono::DateTime<chrono::Utc>,
+            ) -> Result<()> {
+                let value = format!("{} {}", self.0, request.url().path());
+                request.headers_mut().insert("x-vendor-sig", value.parse()?);
+                Ok(())
+            }
         }
 
-        // Aplica timeout (do step ou global).
-        let timeout_ms = params
-            .get("timeout_ms")
-            .and_then(|t| t.as_u64())
-            .or_else(|| context.get("timeout_ms").and_then(|t| t.as_u64()))
-            .unwrap_or(30000); // Default: 30 segundos
+        // O servidor devolve o header de assinatura recebido.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let make_service = make_service_fn(|_| async {
+            Ok::<_, std::convert::Infallible>(service_fn(
+                |request: hyper::Request<hyper::Body>| async move {
+                    let signature = request.headers().get("x-vendor-sig").cloned();
+                    let body = json!({ "sig": signature.map(|s| s.to_str().unwrap().to_string()) });
+                    Ok::<_, std::convert::Infallible>(hyper::Response::new(hyper::Body::from(
+                        body.to_string(),
+                    )))
+                },
+            ))
+        });
+        tokio::spawn(
+            hyper::Server::from_tcp(listener)
+                .unwrap()
+                .serve(make_service),
+        );
 
-        request_builder = request_builder.timeout(std::time::Duration::from_millis(timeout_ms));
+        let mut config = create_config(Default::default());
+        config.signers = serde_json::from_value(json!({
+            "partner": { "type": "vendor", "key": "${partner_key}" }
+        }))
+        .unwrap();
+        let mut executor = HttpExecutor::from_config(&config).unwrap();
+        executor.signers.register(
+            "vendor",
+            Arc::new(|options| {
+                let key = options["key"].as_str().unwrap_or_default().to_string();
+                Ok(Box::new(Vendor(key)) as Box<dyn RequestSigner>)
+            }),
+        );
+        let mut context = Context::new();
+        context.set("partner_key", json!("k1"));
+        let step = Step {
+            id: "signed".to_string(),
+            action: "http_request".to_string(),
+            params: json!({
+                "method": "GET",
+                "path": format!("http://127.0.0.1:{}/orders", port),
+                "signer": "partner"
+            }),
+            assertions: vec![Assertion {
+                assertion_type: "json_body".to_string(),
+                path: Some("sig".to_string()),
+                operator: "eq".to_string(),
+                value: json!("k1 /orders"),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
 
-        // ====================================================================
-        // PASSO 4: EXECUÇÃO DA REQUISIÇÃO
-        // ====================================================================
+        let result = executor.execute(&step, &mut context).await.unwrap();
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+    }
 
-        let response = request_builder.send().await;
-        let duration = start_time.elapsed().as_millis() as u64;
+    #[tokio::test]
+    async fn test_proxy_and_interceptors_per_step() {
+        use hyper::service::{make_service_fn, service_fn};
+
+        // O "proxy" devolve a URI absoluta recebida e o header reescrito.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let make_service = make_service_fn(|_| async {
+            Ok::<_, std::convert::Infallible>(service_fn(
+                |request: hyper::Request<hyper::Body>| async move {
+                    let tenant = request.headers().get("x-tenant").cloned();
+                    let body = json!({
+                        "uri": request.uri().to_string(),
+                        "tenant": tenant.map(|t| t.to_str().unwrap().to_string()),
+                    });
+                    Ok::<_, std::convert::Infallible>(hyper::Response::new(hyper::Body::from(
+                        body.to_string(),
+                    )))
+                },
+            ))
+        });
+        tokio::spawn(
+            hyper::Server::from_tcp(listener)
+                .unwrap()
+                .serve(make_service),
+        );
 
-        // ====================================================================
-        // PASSO 5: PROCESSAMENTO DA RESPOSTA
-        // ====================================================================
+        let executor = create_test_executor();
+        let mut context = Context::new();
+        let step = Step {
+            id: "via_proxy".to_string(),
+            action: "http_request".to_string(),
+            params: json!({
+                "method": "GET",
+                "path": "http://orders.invalid/orders",
+                "proxy": format!("http://127.0.0.1:{}", port),
+                "interceptors": [
+                    { "type": "header_rewrite", "set": { "X-Tenant": "acme" } },
+                    { "type": "record" }
+                ]
+            }),
+            assertions: vec![
+                Assertion {
+                    assertion_type: "json_body".to_string(),
+                    path: Some("uri".to_string()),
+                    operator: "eq".to_string(),
+                    value: json!("http://orders.invalid/orders"),
+                    ..Default::default()
+                },
+                Assertion {
+                    assertion_type: "json_body".to_string(),
+                    path: Some("tenant".to_string()),
+                    operator: "eq".to_string(),
+                    value: json!("acme"),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
 
-        match response {
-            Ok(resp) => {
-                let status = resp.status().as_u16();
-                let headers = resp.headers().clone();
-                let raw_body = resp.text().await.unwrap_or_default();
-                let body_json: Value = serde_json::from_str(&raw_body).unwrap_or(Value::Null);
+        let result = executor.execute(&step, &mut context).await.unwrap();
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+        let recorded = context.get("via_proxy.recorded").unwrap();
+        assert_eq!(recorded["response"]["status"], json!(200));
+    }
 
-                // Registra atributos da resposta no span OTEL.
-                span.record("http.status_code", status as i64);
-                span.record("http.duration_ms", duration as i64);
+    #[tokio::test]
+    async fn test_http_details_sizes_headers_and_final_url() {
+        use hyper::service::{make_service_fn, service_fn};
+
+        // `/old` redireciona para `/new`, que responde 11 bytes.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let make_service = make_service_fn(|_| async {
+            Ok::<_, std::convert::Infallible>(service_fn(
+                |request: hyper::Request<hyper::Body>| async move {
+                    let response = if request.uri().path() == "/old" {
+                        hyper::Response::builder()
+                            .status(302)
+                            .header("location", "/new")
+                            .body(hyper::Body::empty())
+                    } else {
+                        hyper::Response::builder()
+                            .header("x-request-id", "req-7")
+                            .body(hyper::Body::from(r#"{"ok":true}"#))
+                    };
+                    Ok::<_, std::convert::Infallible>(response.unwrap())
+                },
+            ))
+        });
+        tokio::spawn(
+            hyper::Server::from_tcp(listener)
+                .unwrap()
+                .serve(make_service),
+        );
 
-                tracing::info!(
-                    method = %method_str,
-                    %url,
-                    status,
-                    duration_ms = duration,
-                    "HTTP step finished"
-                );
+        let executor = create_test_executor();
+        let mut context = Context::new();
+        let step = Step {
+            id: "moved".to_string(),
+            action: "http_request".to_string(),
+            params: json!({
+                "method": "POST",
+                "path": format!("http://127.0.0.1:{}/old", port),
+                "headers": { "Authorization": "Bearer secret" },
+                "body": { "a": 1 }
+            }),
+            ..Default::default()
+        };
 
-                // Cria o contexto de resposta para validação.
-                let response_ctx = ResponseContext {
-                    status,
-                    body: &body_json,
-                    headers: &headers,
-                    duration_ms: duration,
-                };
+        let result = executor.execute(&step, &mut context).await.unwrap();
+        let details = result.http_details.unwrap();
+        assert_eq!(
+            details.final_url,
+            Some(format!("http://127.0.0.1:{}/new", port))
+        );
+        assert_eq!(details.request_bytes, Some(7));
+        assert_eq!(details.response_bytes, Some(11));
+        assert_eq!(details.request_headers.unwrap()["authorization"], "***");
+        assert_eq!(details.response_headers.unwrap()["x-request-id"], "req-7");
+    }
 
-                // Valida as assertions.
-                if let Some(error_msg) = self.validate_assertions(&step.assertions, &response_ctx) {
-                    tracing::warn!(error = %error_msg, "Assertion failed");
-                    return Ok(StepResult {
-                        step_id: step.id.clone(),
-                        status: StepStatus::Failed,
-                        duration_ms: duration,
-                        attempt: 1,
-                        error: Some(error_msg),
-                        context_before: Some(context_before),
-                        context_after: Some(context.variables.clone()),
-                        extractions: None,
-                        http_details: Some(HttpDetails {
-                            method: method_str.to_string(),
-                            url: url.clone(),
-                            status_code: status,
-                            latency_ms: duration,
-                            request_headers: None,
-                            response_headers: None,
-                        }),
-                    });
-                }
+    #[tokio::test]
+    async fn test_graphql_request_fails_on_errors_and_extracts_from_data() {
+        use hyper::service::{make_service_fn, service_fn};
+
+        // Responde 200 sempre; `errors` quando a operação é `Broken`.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let make_service = make_service_fn(|_| async {
+            Ok::<_, std::convert::Infallible>(service_fn(
+                |request: hyper::Request<hyper::Body>| async move {
+                    let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                    let body: Value = serde_json::from_slice(&body).unwrap();
+                    let reply = if body["operationName"] == "Broken" {
+                        r#"{"data":null,"errors":[{"message":"boom"}]}"#.to_string()
+                    } else {
+                        format!(
+                            r#"{{"data":{{"user":{{"id":"{}"}}}}}}"#,
+                            body["variables"]["id"].as_str().unwrap()
+                        )
+                    };
+                    Ok::<_, std::convert::Infallible>(hyper::Response::new(hyper::Body::from(
+                        reply,
+                    )))
+                },
+            ))
+        });
+        tokio::spawn(
+            hyper::Server::from_tcp(listener)
+                .unwrap()
+                .serve(make_service),
+        );
 
-                // Aplica as extrações.
-                let extraction_results =
-                    self.apply_extractions(&step.extract, &body_json, &headers, context);
+        let executor = create_test_executor();
+        let mut context = Context::new();
+        context.set("user_id", json!("u-1"));
+        let step = |operation: &str| -> Step {
+            serde_json::from_value(json!({
+                "id": "user",
+                "action": "graphql_request",
+                "params": {
+                    "endpoint": format!("http://127.0.0.1:{}/graphql", port),
+                    "query": "query Q($id: ID!) { user(id: $id) { id } }",
+                    "variables": { "id": "${user_id}" },
+                    "operation_name": operation
+                },
+                "extract": [{ "source": "body", "path": "user.id", "target": "found" }]
+            }))
+            .unwrap()
+        };
 
-                // Captura contexto após extrações
-                let context_after = context.variables.clone();
+        let ok = executor
+            .execute(&step("GetUser"), &mut context)
+            .await
+            .unwrap();
+        assert_eq!(ok.status, StepStatus::Passed, "{:?}", ok.error);
+        assert_eq!(context.get("found"), Some(&json!("u-1")));
+        assert_eq!(ok.http_details.unwrap().method, "POST");
+
+        let broken = executor
+            .execute(&step("Broken"), &mut context)
+            .await
+            .unwrap();
+        assert_eq!(broken.status, StepStatus::Failed);
+        assert!(broken.error.unwrap().contains("graphql_errors"));
+    }
 
-                // Retorna sucesso.
-                Ok(StepResult {
-                    step_id: step.id.clone(),
-                    status: StepStatus::Passed,
-                    duration_ms: duration,
-                    attempt: 1,
-                    error: None,
-                    context_before: Some(context_before),
-                    context_after: Some(context_after),
-                    extractions: if extraction_results.is_empty() {
-                        None
+    #[test]
+    fn test_from_config_rejects_invalid_user_agent() {
+        let config = create_config(crate::protocol::HttpClientConfig {
+            user_agent: Some("quebra\nlinha".to_string()),
+            ..Default::default()
+        });
+        assert!(HttpExecutor::from_config(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_auto_extract_namespaced_by_step() {
+        let port = serve_once(r#"{"error":{"code":"E42"}}"#).await;
+
+        let mut config = create_config(Default::default());
+        config.auto_extract = serde_json::from_value(json!([
+            { "source": "body", "path": "error.code", "target": "error_code" },
+            { "source": "header", "path": "X-Request-Id", "target": "request_id" }
+        ]))
+        .unwrap();
+        let executor = HttpExecutor::from_config(&config).unwrap();
+
+        let step = Step {
+            id: "login".to_string(),
+            action: "http_request".to_string(),
+            params: json!({ "method": "GET", "path": format!("http://localhost:{}/", port) }),
+            ..Default::default()
+        };
+        let mut context = Context::new();
+        let result = executor.execute(&step, &mut context).await.unwrap();
+
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+        assert_eq!(context.get("login.error_code"), Some(&json!("E42")));
+        // Header ausente na resposta: regra ignorada, sem falhar o step.
+        assert!(context.get("login.request_id").is_none());
+    }
+
+    /// API com login por cookie, rota protegida por token renovável e
+    /// `/whoami`, que só aceita a cliente Ana sem o cookie de `buyer`.
+    async fn serve_session_api() -> u16 {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 4096];
+                    let n = socket.read(&mut buf).await.unwrap_or(0);
+                    let request = String::from_utf8_lossy(&buf[..n]).to_lowercase();
+                    let authorized = (request.starts_with("get /me")
+                        && request.contains("sid=buyer"))
+                        || (request.starts_with("get /whoami")
+                            && request.contains("x-user: ana@mail.com")
+                            && !request.contains("sid=buyer"))
+                        || (request.starts_with("get /orders")
+                            && request.contains("authorization: bearer fresh"));
+                    let (status, extra, body) = if request.starts_with("post /login") {
+                        ("200 OK", "Set-Cookie: sid=buyer\r\n", "{}")
+                    } else if request.starts_with("post /refresh") {
+                        ("200 OK", "", r#"{"token":"fresh"}"#)
+                    } else if authorized {
+                        ("200 OK", "", "{}")
                     } else {
-                        Some(extraction_results)
-                    },
-                    http_details: Some(HttpDetails {
-                        method: method_str.to_string(),
-                        url: url.clone(),
-                        status_code: status,
-                        latency_ms: duration,
-                        request_headers: None,
-                        response_headers: None,
-                    }),
-                })
-            }
-            Err(e) => {
-                // Erro na requisição (rede, DNS, timeout, etc.)
-                tracing::error!(error = %e, "HTTP request failed");
-                Ok(StepResult {
-                    step_id: step.id.clone(),
-                    status: StepStatus::Failed,
-                    duration_ms: duration,
-                    attempt: 1,
-                    error: Some(e.to_string()),
-                    context_before: Some(context_before),
-                    context_after: Some(context.variables.clone()),
-                    extractions: None,
-                    http_details: Some(HttpDetails {
-                        method: method_str.to_string(),
-                        url: url.clone(),
-                        status_code: 0, // Sem resposta (erro de rede)
-                        latency_ms: duration,
-                        request_headers: None,
-                        response_headers: None,
-                    }),
-                })
+                        ("401 Unauthorized", "", "{}")
+                    };
+                    let response = format!(
+                        "HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        extra,
+                        body.len(),
+                        body
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
             }
+        });
+        port
+    }
+
+    #[tokio::test]
+    async fn test_session_shares_cookies_and_refreshes_token() {
+        let port = serve_session_api().await;
+        let mut config = create_config(Default::default());
+        config.sessions = serde_json::from_value(json!({
+            "buyer": { "refresh": { "path": "/refresh", "token_path": "$.token" } }
+        }))
+        .unwrap();
+        let executor = HttpExecutor::from_config(&config).unwrap();
+
+        let mut context = Context::new();
+        context.set("base_url", json!(format!("http://localhost:{}", port)));
+        let step = |id: &str, method: &str, path: &str, session: Option<&str>| Step {
+            id: id.to_string(),
+            action: "http_request".to_string(),
+            params: json!({ "method": method, "path": path }),
+            assertions: vec![Assertion {
+                assertion_type: "status_code".to_string(),
+                operator: "eq".to_string(),
+                value: json!(200),
+                ..Default::default()
+            }],
+            session: session.map(String::from),
+            ..Default::default()
+        };
+
+        let login = step("login", "POST", "/login", Some("buyer"));
+        let me = step("me", "GET", "/me", Some("buyer"));
+        let me_outside = step("me_outside", "GET", "/me", None);
+        let orders = step("orders", "GET", "/orders", Some("buyer"));
+
+        for (step, expected) in [
+            (&login, StepStatus::Passed),
+            (&me, StepStatus::Passed),
+            // Fora da sessão, o cookie não é enviado.
+            (&me_outside, StepStatus::Failed),
+            // 401 → refresh → repetição com o token novo.
+            (&orders, StepStatus::Passed),
+        ] {
+            let result = executor.execute(step, &mut context).await.unwrap();
+            assert_eq!(result.status, expected, "{}: {:?}", step.id, result.error);
         }
     }
-}
 
-// ============================================================================
-// TESTES UNITÁRIOS
-// ============================================================================
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::protocol::Assertion;
-    use serde_json::json;
+    #[tokio::test]
+    async fn test_cache_behavior_sends_conditional_request() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // /good honra If-None-Match; /bad sempre devolve o body completo.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 4096];
+                    let n = socket.read(&mut buf).await.unwrap_or(0);
+                    let request = String::from_utf8_lossy(&buf[..n]).to_lowercase();
+                    let conditional = request.contains("if-none-match: \"v1\"");
+                    // /tenant só reconhece a revalidação com o header do interceptor.
+                    let honored = request.starts_with("get /good")
+                        || (request.starts_with("get /tenant")
+                            && request.contains("x-tenant: acme"));
+                    let (status, body) = if conditional && honored {
+                        ("304 Not Modified", "")
+                    } else {
+                        ("200 OK", r#"{"currency":"BRL"}"#)
+                    };
+                    let response = format!(
+                        "HTTP/1.1 {}\r\nETag: \"v1\"\r\nCache-Control: max-age=60\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
 
-    /// Cria um HttpExecutor para testes
-    fn create_test_executor() -> HttpExecutor {
-        HttpExecutor::new()
+        let executor = create_test_executor();
+        let mut context = Context::new();
+        context.set("base_url", json!(format!("http://localhost:{}", port)));
+        let step = |path: &str| Step {
+            id: "config".to_string(),
+            action: "http_request".to_string(),
+            params: json!({ "method": "GET", "path": path }),
+            assertions: vec![Assertion {
+                assertion_type: "cache_behavior".to_string(),
+                operator: "eq".to_string(),
+                value: json!("not_modified"),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let good = executor
+            .execute(&step("/good"), &mut context)
+            .await
+            .unwrap();
+        assert_eq!(good.status, StepStatus::Passed, "{:?}", good.error);
+        assert_eq!(good.http_details.unwrap().status_code, 200);
+
+        let bad = executor.execute(&step("/bad"), &mut context).await.unwrap();
+        assert_eq!(bad.status, StepStatus::Failed);
+        assert!(bad
+            .error
+            .unwrap()
+            .contains("conditional request returned 200"));
+        assert_eq!(bad.assertion_failures[0].actual, Some(json!(200)));
+
+        let mut tenant = step("/tenant");
+        tenant.params["interceptors"] =
+            json!([{ "type": "header_rewrite", "set": { "X-Tenant": "acme" } }]);
+        let tenant = executor.execute(&tenant, &mut context).await.unwrap();
+        assert_eq!(tenant.status, StepStatus::Passed, "{:?}", tenant.error);
+    }
+
+    #[tokio::test]
+    async fn test_actor_headers_use_actor_variables_and_own_cookies() {
+        use crate::clock::SystemClock;
+
+        let port = serve_session_api().await;
+        let mut config = create_config(Default::default());
+        config.actors = serde_json::from_value(json!({
+            "customer": {
+                "variables": { "email": "ana@mail.com" },
+                "headers": { "X-User": "${email}" }
+            },
+            "admin": { "variables": { "email": "root@shop.com" } }
+        }))
+        .unwrap();
+        let executor = HttpExecutor::from_config(&config).unwrap();
+
+        let mut context = Context::new();
+        context.set("base_url", json!(format!("http://localhost:{}", port)));
+        crate::actors::register(&mut context, &config.actors);
+
+        // O admin recebe o cookie `sid=buyer`; ele não vaza para o cliente.
+        let login: Step = serde_json::from_value(json!({
+            "id": "admin_login", "action": "http_request", "actor": "admin",
+            "params": { "method": "POST", "path": "/login" }
+        }))
+        .unwrap();
+        let whoami: Step = serde_json::from_value(json!({
+            "id": "whoami", "action": "http_request", "actor": "customer",
+            "params": { "method": "GET", "path": "/whoami" },
+            "assertions": [{ "type": "status_code", "operator": "eq", "value": 200 }]
+        }))
+        .unwrap();
+
+        crate::foreach::execute_step(&login, &executor, &mut context, &SystemClock)
+            .await
+            .unwrap();
+        let result = crate::foreach::execute_step(&whoami, &executor, &mut context, &SystemClock)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+        assert!(context.get("email").is_none());
     }
 
     // ========================================================================
     // Testes: status_code assertions
     // ========================================================================
 
+    #[test]
+    fn test_binary_assertions_use_raw_body() {
+        let executor = create_test_executor();
+        let body = Value::Null;
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", "application/pdf".parse().unwrap());
+        let ctx = ResponseContext {
+            status: 200,
+            body: &body,
+            raw_body: b"%PDF-1.7\n",
+            headers: &headers,
+            duration_ms: 10,
+            stream: StreamStats::default(),
+        };
+        let assertions: Vec<Assertion> = serde_json::from_value(json!([
+            { "type": "content_type", "operator": "eq", "value": "application/pdf" },
+            { "type": "body_signature", "operator": "eq", "value": "pdf" },
+            { "type": "body_size", "operator": "eq", "value": 9 }
+        ]))
+        .unwrap();
+
+        assert!(executor.validate_assertions(&assertions, &ctx).is_none());
+    }
+
     #[test]
     fn test_status_code_eq_pass() {
         let executor = create_test_executor();
@@ -860,20 +3134,109 @@ mod tests {
         let ctx = ResponseContext {
             status: 200,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
         let assertions = vec![Assertion {
             assertion_type: "status_code".to_string(),
             operator: "eq".to_string(),
             value: json!(200),
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
         assert!(result.is_none(), "Assertion should pass");
     }
 
+    #[test]
+    fn test_status_code_in_list() {
+        let executor = create_test_executor();
+        let body = json!({});
+        let headers = HeaderMap::new();
+        let assertion = |operator: &str, value: Value| Assertion {
+            assertion_type: "status_code".to_string(),
+            operator: operator.to_string(),
+            value,
+            path: None,
+            ..Default::default()
+        };
+
+        for (status, operator, expect_pass) in [
+            (201, "in", true),
+            (500, "in", false),
+            (500, "not_in", true),
+            (204, "not_in", false),
+        ] {
+            let ctx = ResponseContext {
+                status,
+                body: &body,
+                raw_body: &[],
+                headers: &headers,
+                duration_ms: 100,
+                stream: StreamStats::default(),
+            };
+            let assertions = vec![assertion(operator, json!([200, 201, 204]))];
+            let result = executor.validate_assertions(&assertions, &ctx);
+            assert_eq!(result.is_none(), expect_pass, "{} {}", status, operator);
+        }
+
+        // Valor que não é array é erro de assertion.
+        let ctx = ResponseContext {
+            status: 200,
+            body: &body,
+            raw_body: &[],
+            headers: &headers,
+            duration_ms: 100,
+            stream: StreamStats::default(),
+        };
+        let result = executor.validate_assertions(&[assertion("in", json!(200))], &ctx);
+        assert!(result.unwrap().contains("requires an array"));
+    }
+
+    #[test]
+    fn test_json_body_with_locale() {
+        let executor = create_test_executor();
+        let body = json!({ "total": "R$ 1.234,56", "due": "05/07/2024" });
+        let headers = HeaderMap::new();
+        let ctx = ResponseContext {
+            status: 200,
+            body: &body,
+            raw_body: &[],
+            headers: &headers,
+            duration_ms: 100,
+            stream: StreamStats::default(),
+        };
+        let pt_br = AssertionLocale {
+            decimal: Some(",".to_string()),
+            date_format: Some("%d/%m/%Y".to_string()),
+        };
+        let assertion = |path: &str, operator: &str, value: Value| Assertion {
+            assertion_type: "json_body".to_string(),
+            operator: operator.to_string(),
+            value,
+            path: Some(path.to_string()),
+            locale: Some(pt_br.clone()),
+            ..Default::default()
+        };
+
+        let assertions = vec![
+            assertion("total", "gt", json!(1000)),
+            assertion("total", "eq", json!(1234.56)),
+            assertion("due", "date_after", json!("2024-06-30")),
+        ];
+        assert!(executor.validate_assertions(&assertions, &ctx).is_none());
+
+        // Sem locale, "R$ 1.234,56" não é número.
+        let plain = Assertion {
+            locale: None,
+            ..assertion("total", "gt", json!(1000))
+        };
+        assert!(executor.validate_assertions(&[plain], &ctx).is_some());
+    }
+
     #[test]
     fn test_status_code_eq_fail() {
         let executor = create_test_executor();
@@ -882,14 +3245,17 @@ mod tests {
         let ctx = ResponseContext {
             status: 404,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
         let assertions = vec![Assertion {
             assertion_type: "status_code".to_string(),
             operator: "eq".to_string(),
             value: json!(200),
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -897,6 +3263,125 @@ mod tests {
         assert!(result.unwrap().contains("404"));
     }
 
+    // ========================================================================
+    // Testes: header assertions
+    // ========================================================================
+
+    fn header_assertion(name: &str, operator: &str, value: Value, mode: Option<&str>) -> Assertion {
+        Assertion {
+            assertion_type: "header".to_string(),
+            operator: operator.to_string(),
+            value,
+            path: Some(name.to_string()),
+            match_mode: mode.map(String::from),
+            ..Default::default()
+        }
+    }
+
+    fn cookie_headers() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.append("set-cookie", "session=abc; HttpOnly".parse().unwrap());
+        headers.append("set-cookie", "theme=dark".parse().unwrap());
+        headers.insert("content-type", "application/json".parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_header_multi_value_any_and_all() {
+        let executor = create_test_executor();
+        let body = json!({});
+        let headers = cookie_headers();
+        let ctx = ResponseContext {
+            status: 200,
+            body: &body,
+            raw_body: &[],
+            headers: &headers,
+            duration_ms: 100,
+            stream: StreamStats::default(),
+        };
+
+        // Nome em maiúsculas e segundo valor do header repetido.
+        let any = header_assertion("Set-Cookie", "contains", json!("theme="), None);
+        assert!(executor.validate_assertions(&[any], &ctx).is_none());
+
+        let all = header_assertion("SET-COOKIE", "contains", json!("="), Some("all"));
+        assert!(executor.validate_assertions(&[all], &ctx).is_none());
+
+        // Falha com "all": mensagem lista todos os valores recebidos.
+        let all_http_only =
+            header_assertion("Set-Cookie", "contains", json!("HttpOnly"), Some("all"));
+        let error = executor
+            .validate_assertions(&[all_http_only], &ctx)
+            .unwrap();
+        assert!(error.contains("session=abc; HttpOnly"));
+        assert!(error.contains("theme=dark"));
+    }
+
+    #[test]
+    fn test_header_exists_and_not_exists() {
+        let executor = create_test_executor();
+        let body = json!({});
+        let headers = cookie_headers();
+        let ctx = ResponseContext {
+            status: 200,
+            body: &body,
+            raw_body: &[],
+            headers: &headers,
+            duration_ms: 100,
+            stream: StreamStats::default(),
+        };
+
+        let exists = header_assertion("Content-Type", "exists", Value::Null, None);
+        let not_exists = header_assertion("X-Debug", "not_exists", Value::Null, None);
+        assert!(executor
+            .validate_assertions(&[exists, not_exists], &ctx)
+            .is_none());
+
+        let neq = header_assertion("set-cookie", "neq", json!("theme=dark"), None);
+        let error = executor.validate_assertions(&[neq], &ctx).unwrap();
+        assert!(error.contains("should not equal"));
+
+        let missing = header_assertion("X-Request-Id", "eq", json!("1"), None);
+        let error = executor.validate_assertions(&[missing], &ctx).unwrap();
+        assert!(error.contains("<missing>"));
+    }
+
+    #[test]
+    fn test_assertion_failures_are_structured() {
+        let executor = create_test_executor();
+        let body = json!({ "data": { "id": 41, "name": "Ana" } });
+        let headers = cookie_headers();
+        let ctx = ResponseContext {
+            status: 201,
+            body: &body,
+            raw_body: &[],
+            headers: &headers,
+            duration_ms: 100,
+            stream: StreamStats::default(),
+        };
+        let assertions: Vec<Assertion> = serde_json::from_value(json!([
+            { "type": "status_code", "operator": "eq", "value": 200 },
+            { "type": "json_body", "path": "data.name", "operator": "eq", "value": "Ana" },
+            { "type": "json_body", "path": "data.id", "operator": "eq", "value": 42 },
+            { "type": "json_body", "path": "data.email", "operator": "exists", "value": null }
+        ]))
+        .unwrap();
+
+        let failures = executor.assertion_failures(&assertions, &ctx);
+        assert_eq!(failures.len(), 3);
+        assert_eq!(
+            (&failures[0].expected, &failures[0].actual),
+            (&json!(200), &Some(json!(201)))
+        );
+        assert_eq!(failures[1].path.as_deref(), Some("data.id"));
+        assert_eq!(failures[1].actual, Some(json!(41)));
+        assert!(failures[2].actual.is_none());
+        assert_eq!(
+            Some(failures[0].message.clone()),
+            executor.validate_assertions(&assertions, &ctx)
+        );
+    }
+
     // ========================================================================
     // Testes: status_range assertions
     // ========================================================================
@@ -909,14 +3394,17 @@ mod tests {
         let ctx = ResponseContext {
             status: 200,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
         let assertions = vec![Assertion {
             assertion_type: "status_range".to_string(),
             operator: "eq".to_string(),
             value: json!("2xx"),
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -931,14 +3419,17 @@ mod tests {
         let ctx = ResponseContext {
             status: 201,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
         let assertions = vec![Assertion {
             assertion_type: "status_range".to_string(),
             operator: "eq".to_string(),
             value: json!("2xx"),
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -953,14 +3444,17 @@ mod tests {
         let ctx = ResponseContext {
             status: 404,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
         let assertions = vec![Assertion {
             assertion_type: "status_range".to_string(),
             operator: "eq".to_string(),
             value: json!("2xx"),
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -975,14 +3469,17 @@ mod tests {
         let ctx = ResponseContext {
             status: 404,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
         let assertions = vec![Assertion {
             assertion_type: "status_range".to_string(),
             operator: "eq".to_string(),
             value: json!("4xx"),
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -997,14 +3494,17 @@ mod tests {
         let ctx = ResponseContext {
             status: 500,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
         let assertions = vec![Assertion {
             assertion_type: "status_range".to_string(),
             operator: "eq".to_string(),
             value: json!("5xx"),
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1019,14 +3519,17 @@ mod tests {
         let ctx = ResponseContext {
             status: 204,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
         let assertions = vec![Assertion {
             assertion_type: "status_range".to_string(),
             operator: "eq".to_string(),
             value: json!("success"),
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1041,14 +3544,17 @@ mod tests {
         let ctx = ResponseContext {
             status: 422,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
         let assertions = vec![Assertion {
             assertion_type: "status_range".to_string(),
             operator: "eq".to_string(),
             value: json!("client_error"),
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1066,14 +3572,17 @@ mod tests {
         let ctx = ResponseContext {
             status: 200,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
         let assertions = vec![Assertion {
             assertion_type: "status_range".to_string(),
             operator: "not_in".to_string(),
             value: json!("4xx"),
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1088,14 +3597,17 @@ mod tests {
         let ctx = ResponseContext {
             status: 250,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
         let assertions = vec![Assertion {
             assertion_type: "status_range".to_string(),
             operator: "eq".to_string(),
             value: json!("200-299"),
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1113,14 +3625,17 @@ mod tests {
         let ctx = ResponseContext {
             status: 400,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
         let assertions = vec![Assertion {
             assertion_type: "status_range".to_string(),
             operator: "eq".to_string(),
             value: json!("4xx"),
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1138,14 +3653,17 @@ mod tests {
         let ctx = ResponseContext {
             status: 499,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
         let assertions = vec![Assertion {
             assertion_type: "status_range".to_string(),
             operator: "eq".to_string(),
             value: json!("4xx"),
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1155,6 +3673,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_matches_subset_on_whole_body() {
+        let executor = create_test_executor();
+        let body = json!({"id": 1, "user": {"name": "Ana", "roles": ["admin", "qa"]}});
+        let headers = HeaderMap::new();
+        let ctx = ResponseContext {
+            status: 200,
+            body: &body,
+            raw_body: &[],
+            headers: &headers,
+            duration_ms: 100,
+            stream: StreamStats::default(),
+        };
+        let subset = |value: Value| {
+            vec![Assertion {
+                assertion_type: "json_body".to_string(),
+                operator: "matches_subset".to_string(),
+                value,
+                path: Some("$".to_string()),
+                ..Default::default()
+            }]
+        };
+
+        let matching = subset(json!({"user": {"roles": ["qa"]}}));
+        assert!(executor.validate_assertions(&matching, &ctx).is_none());
+        let missing = subset(json!({"user": {"roles": ["owner"]}}));
+        assert!(executor.validate_assertions(&missing, &ctx).is_some());
+    }
+
     // ========================================================================
     // Testes: matches_regex assertions
     // ========================================================================
@@ -1167,14 +3714,17 @@ mod tests {
         let ctx = ResponseContext {
             status: 200,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
         let assertions = vec![Assertion {
             assertion_type: "json_body".to_string(),
             operator: "matches_regex".to_string(),
             value: json!("^[A-Z]{2}\\d{4}$"),
             path: Some("code".to_string()),
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1192,14 +3742,17 @@ mod tests {
         let ctx = ResponseContext {
             status: 200,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
         let assertions = vec![Assertion {
             assertion_type: "json_body".to_string(),
             operator: "matches_regex".to_string(),
             value: json!("^[A-Z]{2}\\d{4}$"),
             path: Some("code".to_string()),
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1214,14 +3767,17 @@ mod tests {
         let ctx = ResponseContext {
             status: 200,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
         let assertions = vec![Assertion {
             assertion_type: "json_body".to_string(),
             operator: "matches_regex".to_string(),
             value: json!(r"^[\w.-]+@[\w.-]+\.\w+$"),
             path: Some("email".to_string()),
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1236,14 +3792,17 @@ mod tests {
         let ctx = ResponseContext {
             status: 200,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
         let assertions = vec![Assertion {
             assertion_type: "json_body".to_string(),
             operator: "regex".to_string(), // Test alias
             value: json!("^[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$"),
             path: Some("id".to_string()),
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1258,14 +3817,17 @@ mod tests {
         let ctx = ResponseContext {
             status: 200,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
         let assertions = vec![Assertion {
             assertion_type: "json_body".to_string(),
             operator: "matches_regex".to_string(),
             value: json!("([invalid"), // Invalid regex
             path: Some("code".to_string()),
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1280,14 +3842,17 @@ mod tests {
         let ctx = ResponseContext {
             status: 200,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
         let assertions = vec![Assertion {
             assertion_type: "json_body".to_string(),
             operator: "matches_regex".to_string(),
             value: json!("\\d+"),
             path: Some("count".to_string()),
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1310,8 +3875,10 @@ mod tests {
         let ctx = ResponseContext {
             status: 200,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
 
         // Schema que espera um objeto com name (string), age (integer), email (string)
@@ -1330,6 +3897,7 @@ mod tests {
             operator: "valid".to_string(),
             value: schema,
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1347,8 +3915,10 @@ mod tests {
         let ctx = ResponseContext {
             status: 200,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
 
         let schema = json!({
@@ -1365,6 +3935,7 @@ mod tests {
             operator: "valid".to_string(),
             value: schema,
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1386,8 +3957,10 @@ mod tests {
         let ctx = ResponseContext {
             status: 200,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
 
         let schema = json!({
@@ -1403,6 +3976,7 @@ mod tests {
             operator: "valid".to_string(),
             value: schema,
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1420,8 +3994,10 @@ mod tests {
         let ctx = ResponseContext {
             status: 400,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
 
         let schema = json!({
@@ -1437,6 +4013,7 @@ mod tests {
             operator: "invalid".to_string(),
             value: schema,
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1461,8 +4038,10 @@ mod tests {
         let ctx = ResponseContext {
             status: 200,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
 
         // Valida apenas o sub-objeto data.user
@@ -1480,6 +4059,7 @@ mod tests {
             operator: "valid".to_string(),
             value: schema,
             path: Some("data.user".to_string()),
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1494,8 +4074,10 @@ mod tests {
         let ctx = ResponseContext {
             status: 200,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
 
         let schema = json!({"type": "object"});
@@ -1504,6 +4086,7 @@ mod tests {
             operator: "valid".to_string(),
             value: schema,
             path: Some("nonexistent.path".to_string()),
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1524,8 +4107,10 @@ mod tests {
         let ctx = ResponseContext {
             status: 200,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
 
         let schema = json!({
@@ -1545,6 +4130,7 @@ mod tests {
             operator: "valid".to_string(),
             value: schema,
             path: Some("items".to_string()),
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1561,8 +4147,10 @@ mod tests {
         let ctx = ResponseContext {
             status: 200,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
 
         let schema = json!({
@@ -1580,6 +4168,7 @@ mod tests {
             operator: "valid".to_string(),
             value: schema,
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1596,8 +4185,10 @@ mod tests {
         let ctx = ResponseContext {
             status: 200,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
 
         let schema = json!({
@@ -1615,6 +4206,7 @@ mod tests {
             operator: "valid".to_string(),
             value: schema,
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1629,8 +4221,10 @@ mod tests {
         let ctx = ResponseContext {
             status: 200,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
 
         let schema = json!({
@@ -1645,6 +4239,7 @@ mod tests {
             operator: "conforms".to_string(), // Alias for "valid"
             value: schema,
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1659,8 +4254,10 @@ mod tests {
         let ctx = ResponseContext {
             status: 200,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
 
         let schema = json!({
@@ -1675,6 +4272,7 @@ mod tests {
             operator: "not_conforms".to_string(), // Alias for "invalid"
             value: schema,
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1692,8 +4290,10 @@ mod tests {
         let ctx = ResponseContext {
             status: 200,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
 
         let schema = json!({
@@ -1712,6 +4312,7 @@ mod tests {
             operator: "valid".to_string(),
             value: schema,
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1726,8 +4327,10 @@ mod tests {
         let ctx = ResponseContext {
             status: 200,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
 
         let schema = json!({
@@ -1745,6 +4348,7 @@ mod tests {
             operator: "valid".to_string(),
             value: schema,
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1759,8 +4363,10 @@ mod tests {
         let ctx = ResponseContext {
             status: 200,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
 
         // Schema inválido (type errado)
@@ -1773,6 +4379,7 @@ mod tests {
             operator: "valid".to_string(),
             value: schema,
             path: None,
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
@@ -1790,8 +4397,10 @@ mod tests {
         let ctx = ResponseContext {
             status: 200,
             body: &body,
+            raw_body: &[],
             headers: &headers,
             duration_ms: 100,
+            stream: StreamStats::default(),
         };
 
         let schema = json!({
@@ -1807,6 +4416,7 @@ mod tests {
             operator: "valid".to_string(),
             value: schema,
             path: Some("$.data".to_string()),
+            ..Default::default()
         }];
 
         let result = executor.validate_assertions(&assertions, &ctx);
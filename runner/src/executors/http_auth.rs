@@ -0,0 +1,537 @@
+//! # Autenticação por Desafio - Digest e NTLM/Negotiate
+//!
+//! Auxiliar do `HttpExecutor` para `params.auth`: esquemas em que o servidor
+//! responde `401` com um desafio e o cliente repete a requisição com a
+//! resposta calculada.
+//!
+//! ## Para todos entenderem:
+//!
+//! Serviços de intranet antigos (IIS, SharePoint, appliances) não aceitam
+//! `Bearer`. Com `auth`, o step continua sendo uma requisição só:
+//!
+//! | `type`   | Ida e volta                                        |
+//! |----------|----------------------------------------------------|
+//! | `basic`  | 1 (header `Basic` direto)                          |
+//! | `digest` | 2 (401 com `nonce` → requisição com `response`)    |
+//! | `ntlm`   | 3 (401 → NEGOTIATE → CHALLENGE → AUTHENTICATE)     |
+//!
+//! ## Exemplo:
+//!
+//! ```json
+//! "params": {
+//!   "method": "GET", "path": "/intranet/report",
+//!   "auth": { "type": "ntlm", "username": "CORP\\qa", "password": "${env:QA_PASSWORD}" }
+//! }
+//! ```
+//!
+//! ## Limitações:
+//!
+//! - NTLM usa NTLMv2 e responde também a desafios `Negotiate` (servidores
+//!   Windows aceitam o token NTLM dentro do Negotiate); Kerberos não.
+//! - NTLM autentica a conexão: o handshake depende do keep-alive.
+//! - O body precisa poder ser reenviado (não vale `body_file` em streaming).
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use hmac::{Hmac, Mac};
+use md4::Md4;
+use md5::Md5;
+use rand::Rng;
+use reqwest::header::{HeaderValue, AUTHORIZATION, WWW_AUTHENTICATE};
+use reqwest::{Client, Request, Response, StatusCode};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::context::Context;
+
+/// Assinatura das mensagens NTLM.
+const NTLMSSP: &[u8; 8] = b"NTLMSSP\0";
+
+/// Flags da mensagem NEGOTIATE: Unicode, target, NTLM, always sign,
+/// extended session security, target info, 128 e 56 bits.
+const NTLM_FLAGS: u32 = 0x0000_0001
+    | 0x0000_0004
+    | 0x0000_0200
+    | 0x0000_8000
+    | 0x0008_0000
+    | 0x0080_0000
+    | 0x2000_0000
+    | 0x8000_0000;
+
+// ============================================================================
+// CONFIGURAÇÃO
+// ============================================================================
+
+/// Esquema de `params.auth.type`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AuthScheme {
+    Basic,
+    Digest,
+    Ntlm,
+}
+
+/// Credenciais de `params.auth` (já interpoladas).
+#[derive(Debug, Clone)]
+pub struct ChallengeAuth {
+    pub scheme: AuthScheme,
+    pub username: String,
+    pub password: String,
+}
+
+impl ChallengeAuth {
+    /// Lê `params.auth`. Retorna `None` se o step não tiver `auth`.
+    pub fn from_params(params: &Value, context: &Context) -> Result<Option<Self>> {
+        let Some(auth) = params.get("auth") else {
+            return Ok(None);
+        };
+        let field = |name: &str| -> Result<String> {
+            let value = auth
+                .get(name)
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow!("params.auth.{} é obrigatório", name))?;
+            context.interpolate_str(value)
+        };
+        let scheme = match field("type")?.to_ascii_lowercase().as_str() {
+            "basic" => AuthScheme::Basic,
+            "digest" => AuthScheme::Digest,
+            "ntlm" | "negotiate" => AuthScheme::Ntlm,
+            other => bail!(
+                "params.auth.type '{}' não suportado (use basic, digest ou ntlm)",
+                other
+            ),
+        };
+        Ok(Some(Self {
+            scheme,
+            username: field("username")?,
+            password: field("password")?,
+        }))
+    }
+
+    /// Envia a requisição, respondendo aos desafios do servidor.
+    pub async fn execute(&self, client: &Client, mut request: Request) -> Result<Response> {
+        if self.scheme == AuthScheme::Basic {
+            let token = STANDARD.encode(format!("{}:{}", self.username, self.password));
+            set_authorization(&mut request, &format!("Basic {}", token))?;
+            return Ok(client.execute(request).await?);
+        }
+
+        let replay = || {
+            request
+                .try_clone()
+                .ok_or_else(|| anyhow!("params.auth exige um body que possa ser reenviado"))
+        };
+
+        let response = client.execute(replay()?).await?;
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+        let challenges = www_authenticate(&response);
+
+        match self.scheme {
+            AuthScheme::Digest => {
+                let Some(challenge) = find_challenge(&challenges, "Digest") else {
+                    return Ok(response);
+                };
+                let uri = match request.url().query() {
+                    Some(query) => format!("{}?{}", request.url().path(), query),
+                    None => request.url().path().to_string(),
+                };
+                let header = digest_authorization(
+                    challenge,
+                    request.method().as_str(),
+                    &uri,
+                    &self.username,
+                    &self.password,
+                    &format!("{:016x}", rand::thread_rng().gen::<u64>()),
+                )?;
+                set_authorization(&mut request, &header)?;
+                Ok(client.execute(request).await?)
+            }
+            AuthScheme::Ntlm => {
+                let scheme = if find_challenge(&challenges, "NTLM").is_some() {
+                    "NTLM"
+                } else if find_challenge(&challenges, "Negotiate").is_some() {
+                    "Negotiate"
+                } else {
+                    return Ok(response);
+                };
+                // Consome o body para a conexão voltar ao pool (mesma conexão).
+                let _ = response.bytes().await;
+
+                let mut negotiate = replay()?;
+                let token = STANDARD.encode(ntlm_negotiate());
+                set_authorization(&mut negotiate, &format!("{} {}", scheme, token))?;
+                let response = client.execute(negotiate).await?;
+                let challenge = www_authenticate(&response)
+                    .iter()
+                    .find_map(|c| c.strip_prefix(scheme).map(str::trim).map(String::from))
+                    .filter(|c| !c.is_empty())
+                    .ok_or_else(|| anyhow!("Servidor não enviou o desafio NTLM"))?;
+                let _ = response.bytes().await;
+
+                let challenge = STANDARD
+                    .decode(challenge)
+                    .context("Desafio NTLM inválido (base64)")?;
+                let (server_challenge, target_info) = ntlm_parse_challenge(&challenge)?;
+                let (domain, user) = match self.username.split_once('\\') {
+                    Some((domain, user)) => (domain, user),
+                    None => ("", self.username.as_str()),
+                };
+                let message = ntlm_authenticate(
+                    domain,
+                    user,
+                    &self.password,
+                    &server_challenge,
+                    &target_info,
+                    rand::thread_rng().gen(),
+                    windows_timestamp(),
+                );
+                set_authorization(
+                    &mut request,
+                    &format!("{} {}", scheme, STANDARD.encode(message)),
+                )?;
+                Ok(client.execute(request).await?)
+            }
+            AuthScheme::Basic => unreachable!(),
+        }
+    }
+}
+
+fn set_authorization(request: &mut Request, value: &str) -> Result<()> {
+    let value = HeaderValue::from_str(value).context("Header Authorization inválido")?;
+    request.headers_mut().insert(AUTHORIZATION, value);
+    Ok(())
+}
+
+/// Valores de `WWW-Authenticate` (um por header).
+fn www_authenticate(response: &Response) -> Vec<String> {
+    response
+        .headers()
+        .get_all(WWW_AUTHENTICATE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .map(String::from)
+        .collect()
+}
+
+fn find_challenge<'a>(challenges: &'a [String], scheme: &str) -> Option<&'a str> {
+    challenges.iter().find_map(|c| {
+        let (name, rest) = c.split_once(' ').unwrap_or((c.as_str(), ""));
+        name.eq_ignore_ascii_case(scheme).then_some(rest.trim())
+    })
+}
+
+// ============================================================================
+// DIGEST (RFC 7616)
+// ============================================================================
+
+/// Parâmetros `chave="valor"` de um desafio Digest.
+fn digest_params(challenge: &str) -> Vec<(String, String)> {
+    let mut params = Vec::new();
+    let mut rest = challenge.trim();
+    while let Some((key, after)) = rest.split_once('=') {
+        let key = key
+            .trim()
+            .trim_start_matches(',')
+            .trim()
+            .to_ascii_lowercase();
+        let after = after.trim_start();
+        let (value, remaining) = match after.strip_prefix('"') {
+            Some(quoted) => {
+                let end = quoted.find('"').unwrap_or(quoted.len());
+                (&quoted[..end], quoted.get(end + 1..).unwrap_or(""))
+            }
+            None => {
+                let end = after.find(',').unwrap_or(after.len());
+                (after[..end].trim(), &after[end..])
+            }
+        };
+        params.push((key, value.to_string()));
+        rest = remaining.trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+    }
+    params
+}
+
+/// Header `Authorization: Digest ...` para o desafio.
+fn digest_authorization(
+    challenge: &str,
+    method: &str,
+    uri: &str,
+    username: &str,
+    password: &str,
+    cnonce: &str,
+) -> Result<String> {
+    let params = digest_params(challenge);
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    };
+    let realm = param("realm").unwrap_or("");
+    let nonce = param("nonce").ok_or_else(|| anyhow!("Desafio Digest sem nonce"))?;
+    let algorithm = param("algorithm").unwrap_or("MD5");
+    let hash: fn(&str) -> String = match algorithm.to_ascii_uppercase().as_str() {
+        "MD5" => |s| hex(&Md5::digest(s.as_bytes())),
+        "SHA-256" => |s| hex(&Sha256::digest(s.as_bytes())),
+        other => bail!("Algoritmo Digest '{}' não suportado", other),
+    };
+    let qop = param("qop").map(|q| q.split(',').any(|q| q.trim() == "auth"));
+    if qop == Some(false) {
+        bail!("Digest: só qop=auth é suportado");
+    }
+
+    let ha1 = hash(&format!("{}:{}:{}", username, realm, password));
+    let ha2 = hash(&format!("{}:{}", method, uri));
+    let nc = "00000001";
+    let response = match qop {
+        Some(_) => hash(&format!("{}:{}:{}:{}:auth:{}", ha1, nonce, nc, cnonce, ha2)),
+        None => hash(&format!("{}:{}:{}", ha1, nonce, ha2)),
+    };
+
+    let mut header = format!(
+        r#"Digest username="{}", realm="{}", nonce="{}", uri="{}", algorithm={}, response="{}""#,
+        username, realm, nonce, uri, algorithm, response
+    );
+    if qop.is_some() {
+        header.push_str(&format!(r#", qop=auth, nc={}, cnonce="{}""#, nc, cnonce));
+    }
+    if let Some(opaque) = param("opaque") {
+        header.push_str(&format!(r#", opaque="{}""#, opaque));
+    }
+    Ok(header)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// ============================================================================
+// NTLMv2 (MS-NLMP)
+// ============================================================================
+
+type HmacMd5 = Hmac<Md5>;
+
+fn utf16le(s: &str) -> Vec<u8> {
+    s.encode_utf16().flat_map(u16::to_le_bytes).collect()
+}
+
+fn hmac_md5(key: &[u8], parts: &[&[u8]]) -> [u8; 16] {
+    let mut mac = HmacMd5::new_from_slice(key).expect("HMAC aceita qualquer chave");
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+/// NTOWFv2: HMAC-MD5(MD4(senha), MAIÚSCULAS(usuário) + domínio).
+fn ntowf_v2(domain: &str, user: &str, password: &str) -> [u8; 16] {
+    let nt_hash = Md4::digest(utf16le(password));
+    hmac_md5(&nt_hash, &[&utf16le(&(user.to_uppercase() + domain))])
+}
+
+/// Mensagem NEGOTIATE (tipo 1), sem domínio nem estação.
+fn ntlm_negotiate() -> Vec<u8> {
+    let mut message = NTLMSSP.to_vec();
+    message.extend_from_slice(&1u32.to_le_bytes());
+    message.extend_from_slice(&NTLM_FLAGS.to_le_bytes());
+    message.extend_from_slice(&[0u8; 16]);
+    message
+}
+
+/// Lê o desafio do servidor e o target info da mensagem CHALLENGE (tipo 2).
+fn ntlm_parse_challenge(message: &[u8]) -> Result<([u8; 8], Vec<u8>)> {
+    if message.len() < 32 || &message[..8] != NTLMSSP || message[8..12] != 2u32.to_le_bytes() {
+        bail!("Mensagem NTLM CHALLENGE inválida");
+    }
+    let mut server_challenge = [0u8; 8];
+    server_challenge.copy_from_slice(&message[24..32]);
+
+    let target_info = if message.len() >= 48 {
+        let len = u16::from_le_bytes([message[40], message[41]]) as usize;
+        let offset =
+            u32::from_le_bytes([message[44], message[45], message[46], message[47]]) as usize;
+        message
+            .get(offset..offset + len)
+            .ok_or_else(|| anyhow!("Target info NTLM fora da mensagem"))?
+            .to_vec()
+    } else {
+        Vec::new()
+    };
+    Ok((server_challenge, target_info))
+}
+
+/// NTProofStr + blob (resposta NTLMv2).
+fn ntlm_v2_response(
+    key: &[u8; 16],
+    server_challenge: &[u8; 8],
+    client_challenge: &[u8; 8],
+    timestamp: u64,
+    target_info: &[u8],
+) -> Vec<u8> {
+    let mut blob = vec![1, 1, 0, 0, 0, 0, 0, 0];
+    blob.extend_from_slice(&timestamp.to_le_bytes());
+    blob.extend_from_slice(client_challenge);
+    blob.extend_from_slice(&[0u8; 4]);
+    blob.extend_from_slice(target_info);
+    blob.extend_from_slice(&[0u8; 4]);
+
+    let proof = hmac_md5(key, &[server_challenge, &blob]);
+    [proof.as_slice(), &blob].concat()
+}
+
+/// Mensagem AUTHENTICATE (tipo 3).
+fn ntlm_authenticate(
+    domain: &str,
+    user: &str,
+    password: &str,
+    server_challenge: &[u8; 8],
+    target_info: &[u8],
+    client_challenge: [u8; 8],
+    timestamp: u64,
+) -> Vec<u8> {
+    let key = ntowf_v2(domain, user, password);
+    let nt = ntlm_v2_response(
+        &key,
+        server_challenge,
+        &client_challenge,
+        timestamp,
+        target_info,
+    );
+    let lm = [
+        hmac_md5(&key, &[server_challenge, &client_challenge]).as_slice(),
+        &client_challenge,
+    ]
+    .concat();
+    let payloads = [
+        lm,
+        nt,
+        utf16le(domain),
+        utf16le(user),
+        Vec::new(), // estação
+        Vec::new(), // session key
+    ];
+
+    let mut message = NTLMSSP.to_vec();
+    message.extend_from_slice(&3u32.to_le_bytes());
+    let mut offset = 64u32;
+    for payload in &payloads {
+        let len = payload.len() as u16;
+        message.extend_from_slice(&len.to_le_bytes());
+        message.extend_from_slice(&len.to_le_bytes());
+        message.extend_from_slice(&offset.to_le_bytes());
+        offset += payload.len() as u32;
+    }
+    message.extend_from_slice(&NTLM_FLAGS.to_le_bytes());
+    for payload in &payloads {
+        message.extend_from_slice(payload);
+    }
+    message
+}
+
+/// Agora em unidades de 100 ns desde 1601-01-01 (FILETIME).
+fn windows_timestamp() -> u64 {
+    const EPOCH_DIFF_SECS: u64 = 11_644_473_600;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    (now.as_secs() + EPOCH_DIFF_SECS) * 10_000_000 + u64::from(now.subsec_nanos() / 100)
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_digest_rfc2617_example() {
+        let challenge = r#"realm="testrealm@host.com", qop="auth,auth-int", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093", opaque="5ccc069c403ebaf9f0171e9517f40e41""#;
+        let header = digest_authorization(
+            challenge,
+            "GET",
+            "/dir/index.html",
+            "Mufasa",
+            "Circle Of Life",
+            "0a4f113b",
+        )
+        .unwrap();
+        assert!(
+            header.contains(r#"response="6629fae49393a05397450978507c4ef1""#),
+            "{}",
+            header
+        );
+        assert!(header.contains(r#"opaque="5ccc069c403ebaf9f0171e9517f40e41""#));
+        assert!(header.contains("nc=00000001"));
+    }
+
+    #[test]
+    fn test_ntlm_v2_ms_nlmp_vectors() {
+        // MS-NLMP 4.2.4: User / Domain / Password.
+        let key = ntowf_v2("Domain", "User", "Password");
+        assert_eq!(hex(&key), "0c868a403bfd7a93a3001ef22ef02e3f");
+
+        let mut target_info = Vec::new();
+        for (id, value) in [(2u16, "Domain"), (1u16, "Server")] {
+            let value = utf16le(value);
+            target_info.extend_from_slice(&id.to_le_bytes());
+            target_info.extend_from_slice(&(value.len() as u16).to_le_bytes());
+            target_info.extend_from_slice(&value);
+        }
+        target_info.extend_from_slice(&[0u8; 4]);
+
+        let response = ntlm_v2_response(
+            &key,
+            &[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef],
+            &[0xaa; 8],
+            0,
+            &target_info,
+        );
+        assert_eq!(hex(&response[..16]), "68cd0ab851e51c96aabc927bebef6a1c");
+    }
+
+    #[test]
+    fn test_ntlm_messages_round_trip() {
+        let negotiate = ntlm_negotiate();
+        assert_eq!(&negotiate[..8], NTLMSSP);
+        assert_eq!(negotiate.len(), 32);
+
+        // CHALLENGE mínimo com target info no offset 48.
+        let mut challenge = NTLMSSP.to_vec();
+        challenge.extend_from_slice(&2u32.to_le_bytes());
+        challenge.extend_from_slice(&[0u8; 8]);
+        challenge.extend_from_slice(&NTLM_FLAGS.to_le_bytes());
+        challenge.extend_from_slice(&[7u8; 8]);
+        challenge.extend_from_slice(&[0u8; 8]);
+        challenge.extend_from_slice(&[4, 0, 4, 0, 48, 0, 0, 0]);
+        challenge.extend_from_slice(&[0, 0, 0, 0]);
+        let (server_challenge, target_info) = ntlm_parse_challenge(&challenge).unwrap();
+        assert_eq!(server_challenge, [7u8; 8]);
+        assert_eq!(target_info, vec![0, 0, 0, 0]);
+
+        assert!(ntlm_parse_challenge(b"garbage").is_err());
+    }
+
+    #[test]
+    fn test_from_params() {
+        let mut context = Context::new();
+        context.set("pw", json!("s3cret"));
+        let params =
+            json!({ "auth": { "type": "Digest", "username": "CORP\\qa", "password": "${pw}" } });
+        let auth = ChallengeAuth::from_params(&params, &context)
+            .unwrap()
+            .unwrap();
+        assert_eq!(auth.scheme, AuthScheme::Digest);
+        assert_eq!(auth.password, "s3cret");
+
+        let kerberos = json!({ "auth": { "type": "kerberos", "username": "a", "password": "b" } });
+        assert!(ChallengeAuth::from_params(&kerberos, &context).is_err());
+        assert!(ChallengeAuth::from_params(&json!({}), &context)
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,288 @@
+//! # Assertions Binárias - Respostas que Não São JSON
+//!
+//! Auxiliar do `HttpExecutor` para validar respostas binárias (imagens,
+//! PDFs, exports, arquivos compactados) sem tratá-las como JSON.
+//!
+//! ## Para todos entenderem:
+//!
+//! Um endpoint `/relatorio.pdf` não devolve JSON: o `json_body` não serve.
+//! O que importa é "veio um PDF?", "do tamanho certo?", "é o arquivo
+//! esperado?". Estas assertions olham os **bytes** da resposta.
+//!
+//! ## Tipos suportados:
+//!
+//! | Tipo             | Valor                                    | Operadores                    |
+//! |------------------|------------------------------------------|-------------------------------|
+//! | `content_type`   | Media type (`"application/pdf"`)         | `eq`, `neq`, `contains`       |
+//! | `body_size`      | Tamanho em bytes                         | `eq`, `neq`, `lt`, `lte`, `gt`, `gte` |
+//! | `body_signature` | Formato (`"pdf"`, `"png"`) ou hex        | `eq`, `neq`                   |
+//! | `body_sha256`    | Hash SHA-256 em hex                      | `eq`, `neq`                   |
+//!
+//! ## Exemplo:
+//!
+//! ```json
+//! "assertions": [
+//!   { "type": "content_type", "operator": "eq", "value": "application/pdf" },
+//!   { "type": "body_signature", "operator": "eq", "value": "pdf" },
+//!   { "type": "body_size", "operator": "gt", "value": 1024 }
+//! ]
+//! ```
+//!
+//! Em `content_type`, parâmetros como `; charset=utf-8` são ignorados no
+//! `eq`/`neq` e a comparação não diferencia maiúsculas.
+
+use reqwest::header::{HeaderMap, CONTENT_TYPE};
+use sha2::{Digest, Sha256};
+
+use crate::protocol::Assertion;
+
+/// Bytes esperados a partir de um offset do body.
+type SignaturePart = (usize, &'static [u8]);
+
+/// Assinaturas ("magic numbers") conhecidas pelo nome: bytes esperados
+/// em cada offset (o WebP é um `RIFF` com `WEBP` no offset 8).
+const SIGNATURES: &[(&str, &[SignaturePart])] = &[
+    ("pdf", &[(0, b"%PDF-")]),
+    ("png", &[(0, b"\x89PNG\r\n\x1a\n")]),
+    ("jpeg", &[(0, b"\xFF\xD8\xFF")]),
+    ("jpg", &[(0, b"\xFF\xD8\xFF")]),
+    ("gif", &[(0, b"GIF8")]),
+    ("webp", &[(0, b"RIFF"), (8, b"WEBP")]),
+    ("zip", &[(0, b"PK\x03\x04")]),
+    ("gzip", &[(0, b"\x1F\x8B")]),
+];
+
+// ============================================================================
+// DESPACHO
+// ============================================================================
+
+/// Retorna true se `assertion_type` é uma assertion binária.
+pub fn is_binary_assertion(assertion_type: &str) -> bool {
+    matches!(
+        assertion_type,
+        "content_type" | "body_size" | "body_signature" | "body_sha256"
+    )
+}
+
+/// Valida uma assertion binária sobre os bytes e headers da resposta.
+///
+/// ## Retorno:
+/// - `None` se passou
+/// - `Some(String)` com a mensagem de erro
+pub fn check_binary_assertion(
+    assertion: &Assertion,
+    body: &[u8],
+    headers: &HeaderMap,
+) -> Option<String> {
+    match assertion.assertion_type.as_str() {
+        "content_type" => check_content_type(assertion, headers),
+        "body_size" => check_body_size(assertion, body.len() as u64),
+        "body_signature" => check_signature(assertion, body),
+        "body_sha256" => check_sha256(assertion, body),
+        other => Some(format!(
+            "Assertion failed: unknown binary assertion '{}'",
+            other
+        )),
+    }
+}
+
+// ============================================================================
+// VERIFICAÇÕES
+// ============================================================================
+
+fn check_content_type(assertion: &Assertion, headers: &HeaderMap) -> Option<String> {
+    let expected = assertion.value.as_str().unwrap_or("").to_lowercase();
+    let actual = headers
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_lowercase();
+    let media_type = actual.split(';').next().unwrap_or("").trim();
+
+    let passed = match assertion.operator.as_str() {
+        "eq" => media_type == expected,
+        "neq" => media_type != expected,
+        "contains" => actual.contains(&expected),
+        other => return Some(unsupported("content_type", other)),
+    };
+
+    (!passed).then(|| {
+        format!(
+            "Assertion failed: content_type {} '{}' (got '{}')",
+            assertion.operator, expected, actual
+        )
+    })
+}
+
+fn check_body_size(assertion: &Assertion, size: u64) -> Option<String> {
+    let Some(expected) = assertion.value.as_u64() else {
+        return Some(
+            "Assertion failed: body_size value must be a non-negative integer".to_string(),
+        );
+    };
+
+    let passed = match assertion.operator.as_str() {
+        "eq" => size == expected,
+        "neq" => size != expected,
+        "lt" => size < expected,
+        "lte" | "le" => size <= expected,
+        "gt" => size > expected,
+        "gte" | "ge" => size >= expected,
+        other => return Some(unsupported("body_size", other)),
+    };
+
+    (!passed).then(|| {
+        format!(
+            "Assertion failed: body_size {} {} bytes (got {} bytes)",
+            assertion.operator, expected, size
+        )
+    })
+}
+
+fn check_signature(assertion: &Assertion, body: &[u8]) -> Option<String> {
+    let name = assertion.value.as_str().unwrap_or("");
+    let signature: Vec<(usize, Vec<u8>)> = match SIGNATURES
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(name))
+    {
+        Some((_, parts)) => parts
+            .iter()
+            .map(|(at, bytes)| (*at, bytes.to_vec()))
+            .collect(),
+        None => match decode_hex(name) {
+            Some(bytes) if !bytes.is_empty() => vec![(0, bytes)],
+            _ => {
+                return Some(format!(
+                    "Assertion failed: body_signature '{}' is not a known format or hex string",
+                    name
+                ))
+            }
+        },
+    };
+
+    let matches = signature
+        .iter()
+        .all(|(at, bytes)| body.get(*at..).is_some_and(|rest| rest.starts_with(bytes)));
+    let passed = match assertion.operator.as_str() {
+        "eq" => matches,
+        "neq" => !matches,
+        other => return Some(unsupported("body_signature", other)),
+    };
+
+    (!passed).then(|| {
+        let head: String = body.iter().take(8).map(|b| format!("{:02x}", b)).collect();
+        format!(
+            "Assertion failed: body_signature {} '{}' (body starts with {})",
+            assertion.operator, name, head
+        )
+    })
+}
+
+fn check_sha256(assertion: &Assertion, body: &[u8]) -> Option<String> {
+    let expected = assertion.value.as_str().unwrap_or("").to_lowercase();
+    let actual = format!("{:x}", Sha256::digest(body));
+
+    let passed = match assertion.operator.as_str() {
+        "eq" => actual == expected,
+        "neq" => actual != expected,
+        other => return Some(unsupported("body_sha256", other)),
+    };
+
+    (!passed).then(|| {
+        format!(
+            "Assertion failed: body_sha256 {} {} (got {})",
+            assertion.operator, expected, actual
+        )
+    })
+}
+
+fn unsupported(assertion_type: &str, operator: &str) -> String {
+    format!(
+        "Assertion failed: operator '{}' not supported for {}",
+        operator, assertion_type
+    )
+}
+
+/// Decodifica uma string hex (espaços e prefixo `0x` são ignorados).
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    let digits: String = text
+        .trim_start_matches("0x")
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    if !digits.len().is_multiple_of(2) || !digits.is_ascii() {
+        return None;
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).ok())
+        .collect()
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+    use serde_json::{json, Value};
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR";
+
+    fn assertion(assertion_type: &str, operator: &str, value: Value) -> Assertion {
+        Assertion {
+            assertion_type: assertion_type.to_string(),
+            operator: operator.to_string(),
+            value,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_content_type_ignores_parameters() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("Image/PNG; q=1"));
+
+        let eq = assertion("content_type", "eq", json!("image/png"));
+        assert!(check_binary_assertion(&eq, PNG, &headers).is_none());
+
+        let wrong = assertion("content_type", "eq", json!("application/json"));
+        assert!(check_binary_assertion(&wrong, PNG, &headers).is_some());
+    }
+
+    #[test]
+    fn test_body_size_and_signature() {
+        let headers = HeaderMap::new();
+
+        let size = assertion("body_size", "eq", json!(PNG.len()));
+        assert!(check_binary_assertion(&size, PNG, &headers).is_none());
+
+        let named = assertion("body_signature", "eq", json!("png"));
+        assert!(check_binary_assertion(&named, PNG, &headers).is_none());
+
+        let hex = assertion("body_signature", "eq", json!("89 50 4E 47"));
+        assert!(check_binary_assertion(&hex, PNG, &headers).is_none());
+
+        let pdf = assertion("body_signature", "eq", json!("pdf"));
+        let error = check_binary_assertion(&pdf, PNG, &headers).unwrap();
+        assert!(error.contains("89504e47"));
+
+        // Só `RIFF` não basta: WAV e AVI também começam assim.
+        let webp = assertion("body_signature", "eq", json!("webp"));
+        assert!(check_binary_assertion(&webp, b"RIFF\x24\0\0\0WEBPVP8 ", &headers).is_none());
+        assert!(check_binary_assertion(&webp, b"RIFF\x24\0\0\0WAVEfmt ", &headers).is_some());
+        assert!(check_binary_assertion(&webp, b"RIFF", &headers).is_some());
+    }
+
+    #[test]
+    fn test_body_sha256() {
+        let headers = HeaderMap::new();
+        let digest = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+        let ok = assertion("body_sha256", "eq", json!(digest.to_uppercase()));
+        assert!(check_binary_assertion(&ok, b"hello", &headers).is_none());
+        assert!(check_binary_assertion(&ok, b"hello!", &headers).is_some());
+    }
+}
@@ -0,0 +1,131 @@
+//! # Body de Arquivo - Uploads Grandes sem Inflar o Plano
+//!
+//! Auxiliar do `HttpExecutor` para `params.body_file`: o body da requisição
+//! vem de um arquivo em disco em vez de ficar embutido no plano.
+//!
+//! ## Para todos entenderem:
+//!
+//! Um payload de 50 MB dentro do JSON do plano deixa o arquivo ilegível e
+//! ocupa memória do início ao fim da execução. Com `body_file`:
+//!
+//! | `body_file_interpolate` | Leitura                         | `${...}` |
+//! |-------------------------|---------------------------------|----------|
+//! | `true` (padrão)         | Arquivo inteiro, interpolado    | Sim      |
+//! | `false`                 | Streaming do disco, em pedaços  | Não      |
+//!
+//! Para uploads de vários MB, use `"body_file_interpolate": false`: o
+//! arquivo é enviado direto do disco, com `Content-Length`.
+//!
+//! ## Caminho:
+//!
+//! Caminhos relativos partem do diretório do plano (`plan_dir` no
+//! contexto), para que o plano funcione de qualquer diretório de trabalho.
+
+use anyhow::{Context as _, Result};
+use reqwest::Body;
+use std::path::{Path, PathBuf};
+use tokio_util::io::ReaderStream;
+
+use crate::context::Context;
+
+/// Body pronto para envio.
+pub struct FileBody {
+    /// Caminho resolvido do arquivo.
+    pub path: PathBuf,
+    /// Tamanho em bytes (vira `Content-Length`).
+    pub size: u64,
+    /// Body da requisição (em memória ou streaming).
+    pub body: Body,
+}
+
+impl FileBody {
+    /// Abre `body_file`, interpolando o conteúdo se `interpolate`.
+    pub async fn open(raw_path: &str, interpolate: bool, context: &Context) -> Result<Self> {
+        let path = resolve_path(&context.interpolate_str(raw_path)?, context);
+        let failure = || format!("Falha ao ler body_file {:?}", path);
+
+        if interpolate {
+            let text = tokio::fs::read_to_string(&path)
+                .await
+                .with_context(failure)?;
+            let text = context.interpolate_str(&text)?;
+            return Ok(Self {
+                size: text.len() as u64,
+                body: Body::from(text),
+                path,
+            });
+        }
+
+        let file = tokio::fs::File::open(&path).await.with_context(failure)?;
+        let size = file.metadata().await.with_context(failure)?.len();
+        Ok(Self {
+            path,
+            size,
+            body: Body::wrap_stream(ReaderStream::new(file)),
+        })
+    }
+
+    /// `Content-Type` padrão pela extensão (o header do step tem prioridade).
+    pub fn content_type(&self) -> &'static str {
+        match self.path.extension().and_then(|e| e.to_str()) {
+            Some("json") => "application/json",
+            Some("xml") => "application/xml",
+            Some("txt") | Some("csv") => "text/plain",
+            _ => "application/octet-stream",
+        }
+    }
+}
+
+/// Caminho relativo ao diretório do plano (ou ao diretório atual, sem `plan_dir`).
+fn resolve_path(path: &str, context: &Context) -> PathBuf {
+    let path = Path::new(path);
+    match context.get("plan_dir").and_then(|d| d.as_str()) {
+        Some(dir) if path.is_relative() => Path::new(dir).join(path),
+        _ => path.to_path_buf(),
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_relative_to_plan_dir_and_interpolation() {
+        let dir = std::env::temp_dir().join(format!("aqa-body-file-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("payloads")).unwrap();
+        std::fs::write(dir.join("payloads/order.json"), r#"{"user":"${user}"}"#).unwrap();
+
+        let mut context = Context::new();
+        context.set("plan_dir", json!(dir.to_string_lossy()));
+        context.set("user", json!("ana"));
+
+        let body = FileBody::open("./payloads/order.json", true, &context)
+            .await
+            .unwrap();
+        assert_eq!(body.size, r#"{"user":"ana"}"#.len() as u64);
+        assert_eq!(body.content_type(), "application/json");
+
+        // Sem interpolação: o tamanho é o do arquivo em disco.
+        let raw = FileBody::open("payloads/order.json", false, &context)
+            .await
+            .unwrap();
+        assert_eq!(raw.size, r#"{"user":"${user}"}"#.len() as u64);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_missing_file_names_path() {
+        let context = Context::new();
+        let error = FileBody::open("/nonexistent/big.bin", false, &context)
+            .await
+            .err()
+            .unwrap();
+        assert!(format!("{:#}", error).contains("big.bin"));
+    }
+}
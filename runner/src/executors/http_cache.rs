@@ -0,0 +1,247 @@
+//! # Cache de Respostas - Uma Requisição por Execução
+//!
+//! Auxiliar do `HttpExecutor` para `config.http.response_cache`: requisições
+//! GET/HEAD idênticas dentro da mesma execução vão à rede uma única vez.
+//!
+//! ## Para todos entenderem:
+//!
+//! Um `parallel_foreach` sobre 500 produtos que consulta `/config/currency`
+//! em cada iteração faz 500 requisições iguais. Com o cache:
+//!
+//! | Requisição                         | Rede | Resultado                    |
+//! |------------------------------------|------|------------------------------|
+//! | 1ª `GET /config/currency`          | Sim  | Resposta guardada            |
+//! | Demais `GET /config/currency`      | Não  | Resposta guardada (`cache_hit`) |
+//! | `GET /config/currency?region=eu`   | Sim  | Outra chave (URL diferente)  |
+//! | `POST /orders`                     | Sim  | Nunca cacheado               |
+//!
+//! ## Chave:
+//!
+//! Método + URL (com query) + body + identidade (ator ou sessão do step) +
+//! hash dos headers de credencial (`Authorization`, `Cookie`, `X-Api-Key`...,
+//! ver `http_headers::Redaction`), para que o cache nunca entregue a
+//! resposta de um usuário a outro, mesmo com o token trocado entre steps.
+//!
+//! Requisições iguais em paralelo esperam a primeira (sem rajada na API).
+//! Erros de rede não são guardados; cada step pode desativar com
+//! `"cache": false` nos params.
+
+use anyhow::Result;
+
+use super::http_headers::Redaction;
+use super::http_stream::StreamStats;
+use reqwest::header::HeaderMap;
+use reqwest::Method;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+/// Resposta lida por completo (o que o executor precisa para validar).
+#[derive(Debug)]
+pub struct FetchedResponse {
+    pub status: u16,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+    pub ttfb_ms: u64,
+    pub transfer_ms: u64,
+    pub stream: StreamStats,
+    pub connection_reused: Option<bool>,
+    pub http_version: String,
+    pub peer_addr: Option<SocketAddr>,
+    pub final_url: String,
+}
+
+/// Cache de respostas de uma execução.
+#[derive(Default)]
+pub struct ResponseCache {
+    entries: Mutex<HashMap<String, Arc<OnceCell<Arc<FetchedResponse>>>>>,
+}
+
+impl ResponseCache {
+    /// Só métodos seguros (sem efeito colateral) são cacheados.
+    pub fn is_cacheable(method: &Method) -> bool {
+        *method == Method::GET || *method == Method::HEAD
+    }
+
+    /// Chave de uma requisição (os headers de credencial entram só como hash).
+    pub fn key(
+        method: &Method,
+        url: &str,
+        body: Option<&Value>,
+        identity: Option<&str>,
+        headers: &HeaderMap,
+    ) -> String {
+        let redaction = Redaction::default();
+        let mut secrets: Vec<(&str, &[u8])> = headers
+            .iter()
+            .filter(|(name, _)| redaction.is_secret(name.as_str()))
+            .map(|(name, value)| (name.as_str(), value.as_bytes()))
+            .collect();
+        secrets.sort();
+        let mut credentials = Sha256::new();
+        for (name, value) in secrets {
+            credentials.update(name.as_bytes());
+            credentials.update(b"\0");
+            credentials.update(value);
+            credentials.update(b"\0");
+        }
+        format!(
+            "{} {:x} {} {} {}",
+            identity.unwrap_or("-"),
+            credentials.finalize(),
+            method,
+            url,
+            body.map(Value::to_string).unwrap_or_default()
+        )
+    }
+
+    /// Resposta guardada para `key` ou executa `fetch` (uma vez por chave).
+    ///
+    /// Retorna `(resposta, cache_hit)`.
+    pub async fn get_or_fetch<F, Fut>(
+        &self,
+        key: String,
+        fetch: F,
+    ) -> Result<(Arc<FetchedResponse>, bool)>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<FetchedResponse>>,
+    {
+        let cell = {
+            let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+            Arc::clone(entries.entry(key).or_default())
+        };
+
+        let mut fetched = false;
+        let response = cell
+            .get_or_try_init(|| async {
+                fetched = true;
+                fetch().await.map(Arc::new)
+            })
+            .await?;
+        Ok((Arc::clone(response), !fetched))
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn response() -> FetchedResponse {
+        FetchedResponse {
+            status: 200,
+            headers: HeaderMap::new(),
+            body: b"{}".to_vec(),
+            ttfb_ms: 5,
+            transfer_ms: 1,
+            stream: StreamStats::default(),
+            connection_reused: None,
+            http_version: "HTTP/1.1".to_string(),
+            peer_addr: None,
+            final_url: "http://localhost/".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_identical_requests_fetch_once() {
+        let cache = Arc::new(ResponseCache::default());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let key = ResponseCache::key(
+            &Method::GET,
+            "http://api/config",
+            None,
+            None,
+            &HeaderMap::new(),
+        );
+
+        let tasks: Vec<_> = (0..8)
+            .map(|_| {
+                let (cache, calls, key) = (Arc::clone(&cache), Arc::clone(&calls), key.clone());
+                tokio::spawn(async move {
+                    cache
+                        .get_or_fetch(key, || async {
+                            calls.fetch_add(1, Ordering::SeqCst);
+                            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                            Ok(response())
+                        })
+                        .await
+                        .unwrap()
+                        .1
+                })
+            })
+            .collect();
+
+        let mut hits = 0;
+        for task in tasks {
+            hits += task.await.unwrap() as usize;
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(hits, 7);
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_not_cached() {
+        let cache = ResponseCache::default();
+        let key = ResponseCache::key(
+            &Method::GET,
+            "http://api/flaky",
+            None,
+            None,
+            &HeaderMap::new(),
+        );
+
+        let failed = cache
+            .get_or_fetch(key.clone(), || async { Err(anyhow::anyhow!("timeout")) })
+            .await;
+        assert!(failed.is_err());
+
+        let (_, hit) = cache
+            .get_or_fetch(key, || async { Ok(response()) })
+            .await
+            .unwrap();
+        assert!(!hit);
+    }
+
+    #[test]
+    fn test_key_separates_identity_and_body() {
+        let url = "http://api/me";
+        let admin = ResponseCache::key(&Method::GET, url, None, Some("admin"), &HeaderMap::new());
+        let customer =
+            ResponseCache::key(&Method::GET, url, None, Some("customer"), &HeaderMap::new());
+        assert_ne!(admin, customer);
+
+        let body = json!({ "q": 1 });
+        assert_ne!(
+            ResponseCache::key(&Method::GET, url, Some(&body), None, &HeaderMap::new()),
+            ResponseCache::key(&Method::GET, url, None, None, &HeaderMap::new())
+        );
+
+        // Tokens diferentes: chaves diferentes; headers comuns não separam.
+        let headers = |token: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                "authorization",
+                format!("Bearer {}", token).parse().unwrap(),
+            );
+            headers.insert("x-step-id", token.parse().unwrap());
+            headers
+        };
+        let key = |headers: &HeaderMap| ResponseCache::key(&Method::GET, url, None, None, headers);
+        assert_ne!(key(&headers("alice")), key(&headers("bob")));
+        let mut other_step = headers("alice");
+        other_step.insert("x-step-id", "other".parse().unwrap());
+        assert_eq!(key(&headers("alice")), key(&other_step));
+        assert!(!key(&headers("alice")).contains("alice"));
+        assert!(!ResponseCache::is_cacheable(&Method::POST));
+    }
+}
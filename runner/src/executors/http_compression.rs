@@ -0,0 +1,247 @@
+//! # Compressão HTTP - Descompactação Controlada e Tamanhos
+//!
+//! Auxiliar do `HttpExecutor` que descompacta o body conforme o
+//! `Content-Encoding` da resposta e registra os tamanhos antes e depois.
+//!
+//! ## Para todos entenderem:
+//!
+//! Um cliente que envia `Accept-Encoding: gzip` espera receber a resposta
+//! comprimida. Para testar isso, o Runner precisa:
+//!
+//! 1. Ver o `Content-Encoding` que o servidor devolveu (assertion
+//!    `content_encoding`).
+//! 2. Descompactar o body para as assertions de JSON continuarem
+//!    funcionando, ou **não** descompactar (`"decompress": false`) para
+//!    inspecionar os bytes comprimidos.
+//! 3. Registrar o tamanho comprimido e o descompactado no relatório.
+//!
+//! ## Encodings suportados:
+//!
+//! | Encoding  | Descompacta? |
+//! |-----------|--------------|
+//! | `gzip`    | Sim          |
+//! | `deflate` | Sim          |
+//! | `br`      | Sim          |
+//! | outros    | Não (body mantido como recebido, com aviso) |
+//!
+//! O Runner não envia `Accept-Encoding` por conta própria: o step define o
+//! header quando quer negociar compressão.
+//!
+//! O body descompactado é limitado a `MAX_DECODED_BYTES`: poucos KB de gzip
+//! podem virar GBs (bomba de compressão) e derrubar o Runner. Acima do
+//! limite, o step falha.
+
+use anyhow::{anyhow, Result};
+use reqwest::header::{HeaderMap, CONTENT_ENCODING};
+use std::io::Read;
+
+use crate::protocol::{Assertion, HttpCompression};
+
+// ============================================================================
+// DESCOMPACTAÇÃO
+// ============================================================================
+
+/// Maior body descompactado aceito (64 MiB).
+const MAX_DECODED_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Descompacta o body conforme o `Content-Encoding`.
+///
+/// ## Parâmetros:
+/// - `raw`: Bytes como recebidos
+/// - `headers`: Headers da resposta
+/// - `decompress`: `false` mantém os bytes comprimidos (só mede)
+///
+/// ## Retorno:
+/// Body final e, se havia `Content-Encoding`, os tamanhos medidos.
+/// Erro se o body não puder ser descompactado (ex: gzip corrompido) ou se
+/// passar de `MAX_DECODED_BYTES`.
+pub fn decode_body(
+    raw: &[u8],
+    headers: &HeaderMap,
+    decompress: bool,
+) -> Result<(Vec<u8>, Option<HttpCompression>)> {
+    let encoding = content_encoding(headers);
+    if encoding == "identity" {
+        return Ok((raw.to_vec(), None));
+    }
+
+    let mut compression = HttpCompression {
+        encoding: encoding.clone(),
+        compressed_bytes: raw.len() as u64,
+        decompressed_bytes: None,
+    };
+    if !decompress {
+        return Ok((raw.to_vec(), Some(compression)));
+    }
+
+    // Encodings aplicados em sequência são desfeitos na ordem inversa.
+    let mut body = raw.to_vec();
+    for coding in encoding.split(',').map(str::trim).rev() {
+        body = match decode_one(coding, &body, MAX_DECODED_BYTES)? {
+            Some(decoded) => decoded,
+            None => {
+                tracing::warn!(encoding = %coding, "Content-Encoding não suportado; body mantido comprimido");
+                return Ok((raw.to_vec(), Some(compression)));
+            }
+        };
+    }
+
+    compression.decompressed_bytes = Some(body.len() as u64);
+    Ok((body, Some(compression)))
+}
+
+/// Descompacta um único encoding (`None` se não suportado), até `limit` bytes.
+fn decode_one(coding: &str, data: &[u8], limit: u64) -> Result<Option<Vec<u8>>> {
+    let decoder: Box<dyn Read + '_> = match coding {
+        "gzip" | "x-gzip" => Box::new(flate2::read::MultiGzDecoder::new(data)),
+        "deflate" => Box::new(flate2::read::ZlibDecoder::new(data)),
+        "br" => Box::new(brotli_decompressor::Decompressor::new(data, 4096)),
+        "identity" => return Ok(Some(data.to_vec())),
+        _ => return Ok(None),
+    };
+    // Um byte além do limite basta para saber que passou.
+    let mut out = Vec::new();
+    decoder
+        .take(limit + 1)
+        .read_to_end(&mut out)
+        .map_err(|e| anyhow!("Falha ao descompactar resposta ({}): {}", coding, e))?;
+    if out.len() as u64 > limit {
+        return Err(anyhow!(
+            "Resposta descompactada ({}) passa do limite de {} bytes",
+            coding,
+            limit
+        ));
+    }
+    Ok(Some(out))
+}
+
+/// `Content-Encoding` normalizado (minúsculas; `identity` se ausente).
+fn content_encoding(headers: &HeaderMap) -> String {
+    headers
+        .get(CONTENT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.trim().to_lowercase())
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| "identity".to_string())
+}
+
+// ============================================================================
+// ASSERTION: CONTENT_ENCODING
+// ============================================================================
+
+/// Valida o `Content-Encoding` da resposta (ausente equivale a `identity`).
+///
+/// Exemplo: `{ "type": "content_encoding", "operator": "eq", "value": "gzip" }`
+pub fn check_content_encoding(assertion: &Assertion, headers: &HeaderMap) -> Option<String> {
+    let actual = content_encoding(headers);
+    let expected = assertion.value.as_str().unwrap_or("").to_lowercase();
+
+    let passed = match assertion.operator.as_str() {
+        "eq" => actual == expected,
+        "neq" => actual != expected,
+        "contains" => actual.contains(&expected),
+        other => {
+            return Some(format!(
+                "Assertion failed: operator '{}' not supported for content_encoding",
+                other
+            ))
+        }
+    };
+
+    (!passed).then(|| {
+        format!(
+            "Assertion failed: content_encoding {} '{}' (got '{}')",
+            assertion.operator, expected, actual
+        )
+    })
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use reqwest::header::HeaderValue;
+    use serde_json::json;
+    use std::io::Write;
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn encoded(encoding: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_ENCODING, HeaderValue::from_static(encoding));
+        headers
+    }
+
+    #[test]
+    fn test_gzip_is_decoded_and_sizes_recorded() {
+        let plain = br#"{"items":[1,2,3,4,5,6,7,8,9,10]}"#;
+        let compressed = gzip(plain);
+
+        let (body, compression) = decode_body(&compressed, &encoded("gzip"), true).unwrap();
+        let compression = compression.unwrap();
+
+        assert_eq!(body, plain);
+        assert_eq!(compression.encoding, "gzip");
+        assert_eq!(compression.compressed_bytes, compressed.len() as u64);
+        assert_eq!(compression.decompressed_bytes, Some(plain.len() as u64));
+    }
+
+    #[test]
+    fn test_decompress_disabled_keeps_raw_bytes() {
+        let compressed = gzip(b"hello");
+
+        let (body, compression) = decode_body(&compressed, &encoded("gzip"), false).unwrap();
+
+        assert_eq!(body, compressed);
+        assert_eq!(compression.unwrap().decompressed_bytes, None);
+    }
+
+    #[test]
+    fn test_identity_and_corrupt_body() {
+        let (body, compression) = decode_body(b"plain", &HeaderMap::new(), true).unwrap();
+        assert_eq!(body, b"plain");
+        assert!(compression.is_none());
+
+        assert!(decode_body(b"not gzip", &encoded("gzip"), true).is_err());
+    }
+
+    #[test]
+    fn test_decoded_size_is_bounded() {
+        let compressed = gzip(&[0u8; 4096]);
+        assert_eq!(
+            decode_one("gzip", &compressed, 4096)
+                .unwrap()
+                .unwrap()
+                .len(),
+            4096
+        );
+
+        let error = decode_one("gzip", &compressed, 1024)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("passa do limite de 1024 bytes"));
+    }
+
+    #[test]
+    fn test_content_encoding_assertion() {
+        let assertion = Assertion {
+            assertion_type: "content_encoding".to_string(),
+            operator: "eq".to_string(),
+            value: json!("GZIP"),
+            ..Default::default()
+        };
+
+        assert!(check_content_encoding(&assertion, &encoded("gzip")).is_none());
+        let error = check_content_encoding(&assertion, &HeaderMap::new()).unwrap();
+        assert!(error.contains("identity"));
+    }
+}
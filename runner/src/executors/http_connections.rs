@@ -0,0 +1,161 @@
+//! # Reuso de Conexões - Pool por Host
+//!
+//! Auxiliar do `HttpExecutor`: marca se cada requisição usou uma conexão
+//! do pool ou abriu uma nova, e resume a contagem por host no relatório.
+//!
+//! ## Para todos entenderem:
+//!
+//! Abrir uma conexão HTTPS custa um handshake TCP + TLS (dezenas a
+//! centenas de ms). Em modo `--parallel`, vários steps disputam o pool e
+//! alguns acabam abrindo conexões novas: a latência oscila sem que o
+//! servidor tenha mudado. O relatório mostra isso em dois lugares:
+//!
+//! | Campo                                  | Conteúdo                             |
+//! |----------------------------------------|--------------------------------------|
+//! | `http_details.timing.connection_reused` | Conexão do pool (`true`) ou nova (`false`) |
+//! | `summary.connections`                   | Conexões novas e reusadas por host  |
+//!
+//! ## Como funciona:
+//!
+//! Cada conexão TCP é identificada pelo par (endereço local, endereço
+//! remoto): a porta local efêmera é única enquanto a conexão existe. A
+//! primeira resposta vista com um par é uma conexão nova; as seguintes
+//! reusaram a mesma conexão.
+
+use hyper::client::connect::HttpInfo;
+use reqwest::Url;
+use std::collections::{BTreeMap, HashSet};
+use std::net::SocketAddr;
+use std::sync::Mutex;
+
+use crate::protocol::{HostConnections, StepResult};
+
+// ============================================================================
+// RASTREADOR
+// ============================================================================
+
+/// Conexões já vistas pelo executor (compartilhado entre sessões e atores).
+#[derive(Debug, Default)]
+pub struct ConnectionTracker {
+    seen: Mutex<HashSet<(SocketAddr, SocketAddr)>>,
+}
+
+impl ConnectionTracker {
+    /// `Some(true)` se a resposta veio por uma conexão já usada antes.
+    ///
+    /// `None` se o cliente não informou os endereços da conexão.
+    pub fn observe(&self, response: &reqwest::Response) -> Option<bool> {
+        let info = response.extensions().get::<HttpInfo>()?;
+        Some(self.observe_addrs(info.local_addr(), info.remote_addr()))
+    }
+
+    fn observe_addrs(&self, local: SocketAddr, remote: SocketAddr) -> bool {
+        let mut seen = self.seen.lock().expect("connection tracker poisoned");
+        !seen.insert((local, remote))
+    }
+}
+
+/// Endereço do servidor da conexão usada (`None` sem informação de conexão).
+pub fn peer_addr(response: &reqwest::Response) -> Option<SocketAddr> {
+    response
+        .extensions()
+        .get::<HttpInfo>()
+        .map(|info| info.remote_addr())
+}
+
+// ============================================================================
+// RESUMO
+// ============================================================================
+
+/// Conexões novas e reusadas por host (`host:porta`), em ordem alfabética.
+pub fn summarize(results: &[StepResult]) -> Vec<HostConnections> {
+    let mut hosts: BTreeMap<String, HostConnections> = BTreeMap::new();
+    let all = results
+        .iter()
+        .chain(results.iter().flat_map(|r| r.iterations.iter().flatten()));
+
+    for http in all.filter_map(|r| r.http_details.as_ref()) {
+        let Some(reused) = http.timing.as_ref().and_then(|t| t.connection_reused) else {
+            continue;
+        };
+        let Some(host) = host_of(&http.url) else {
+            continue;
+        };
+        let entry = hosts
+            .entry(host.clone())
+            .or_insert_with(|| HostConnections {
+                host,
+                new_connections: 0,
+                reused_connections: 0,
+            });
+        if reused {
+            entry.reused_connections += 1;
+        } else {
+            entry.new_connections += 1;
+        }
+    }
+    hosts.into_values().collect()
+}
+
+fn host_of(url: &str) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+    Some(format!(
+        "{}:{}",
+        url.host_str()?,
+        url.port_or_known_default()?
+    ))
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_same_connection_is_reused() {
+        let tracker = ConnectionTracker::default();
+        let server: SocketAddr = "10.0.0.1:443".parse().unwrap();
+        let first: SocketAddr = "127.0.0.1:50001".parse().unwrap();
+        let second: SocketAddr = "127.0.0.1:50002".parse().unwrap();
+
+        assert!(!tracker.observe_addrs(first, server));
+        assert!(tracker.observe_addrs(first, server));
+        assert!(!tracker.observe_addrs(second, server));
+    }
+
+    #[test]
+    fn test_summarize_counts_per_host() {
+        let step = |url: &str, reused: bool| {
+            json!({
+                "step_id": "s", "status": "passed", "duration_ms": 1,
+                "http_details": {
+                    "method": "GET", "url": url, "status_code": 200, "latency_ms": 1,
+                    "timing": { "ttfb_ms": 1, "transfer_ms": 0, "total_ms": 1,
+                                "connection_reused": reused }
+                }
+            })
+        };
+        let results: Vec<StepResult> = serde_json::from_value(json!([
+            step("https://api.example.com/users", false),
+            step("https://api.example.com/orders", true),
+            {
+                "step_id": "fanout", "status": "passed", "duration_ms": 1,
+                "iterations": [step("http://auth.local:8080/token", false)]
+            }
+        ]))
+        .unwrap();
+
+        let summary = summarize(&results);
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[0].host, "api.example.com:443");
+        assert_eq!(
+            (summary[0].new_connections, summary[0].reused_connections),
+            (1, 1)
+        );
+        assert_eq!(summary[1].host, "auth.local:8080");
+    }
+}
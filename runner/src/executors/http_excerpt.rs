@@ -0,0 +1,154 @@
+//! # Trecho da Resposta - Contexto nas Falhas de `json_body`
+//!
+//! Auxiliar do `HttpExecutor`: quando uma assertion `json_body` falha, anexa
+//! ao erro o trecho do body em volta do path verificado.
+//!
+//! ## Para todos entenderem:
+//!
+//! "path 'data.user.email' not found in response body" não diz se o campo
+//! mudou de nome, se `user` veio `null` ou se a API respondeu outro
+//! formato. Com o trecho, o relatório já responde:
+//!
+//! ```text
+//! Assertion failed: path 'data.user.email' not found in response body
+//!   near 'data.user': {"id":42,"mail":"ana@mail.com","password":"***"}
+//! ```
+//!
+//! | Regra        | Como                                                         |
+//! |--------------|--------------------------------------------------------------|
+//! | Vizinhança   | Objeto pai do path (ou o ancestral mais próximo que existe)  |
+//! | Tamanho      | Até 400 caracteres, cortado com `…`                          |
+//! | Segredos     | Campos como `password`, `token`, `*_secret` viram `***`      |
+//!
+//! Os segredos seguem a mesma redação dos headers (`http_headers::Redaction`,
+//! inclusive os nomes de `config.http.header_capture.redact`).
+//!
+//! Os bodies completos continuam só com `--report-detail full`.
+
+use serde_json::{Map, Value};
+
+use super::http_headers::Redaction;
+
+/// Tamanho máximo do trecho anexado ao erro.
+const MAX_EXCERPT_CHARS: usize = 400;
+
+/// Trecho do body em volta de `pointer` (JSON Pointer da assertion).
+pub fn excerpt(body: &Value, pointer: &str, redaction: &Redaction) -> Option<String> {
+    let segments: Vec<&str> = pointer.split('/').skip(1).collect();
+    // Do pai do path em direção à raiz: o primeiro ancestral que existe.
+    let (depth, neighborhood) = (0..segments.len()).rev().find_map(|depth| {
+        let ancestor: String = segments[..depth]
+            .iter()
+            .map(|segment| format!("/{}", segment))
+            .collect();
+        body.pointer(&ancestor).map(|value| (depth, value))
+    })?;
+
+    let location = if depth == 0 {
+        "$".to_string()
+    } else {
+        segments[..depth].join(".")
+    };
+    Some(format!(
+        "near '{}': {}",
+        location,
+        cap(&masked(neighborhood, redaction))
+    ))
+}
+
+/// Anexa o trecho à mensagem de falha (inalterada se não houver trecho).
+pub fn with_excerpt(message: String, body: &Value, pointer: &str, redaction: &Redaction) -> String {
+    match excerpt(body, pointer, redaction) {
+        Some(excerpt) => format!("{}\n  {}", message, excerpt),
+        None => message,
+    }
+}
+
+// ============================================================================
+// MÁSCARA E LIMITE
+// ============================================================================
+
+fn masked(value: &Value, redaction: &Redaction) -> String {
+    mask(value.clone(), redaction).to_string()
+}
+
+fn mask(value: Value, redaction: &Redaction) -> Value {
+    match value {
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| {
+                    let value = if redaction.is_secret(&key) {
+                        Value::String("***".to_string())
+                    } else {
+                        mask(value, redaction)
+                    };
+                    (key, value)
+                })
+                .collect::<Map<String, Value>>(),
+        ),
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(|item| mask(item, redaction))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+fn cap(text: &str) -> String {
+    match text.char_indices().nth(MAX_EXCERPT_CHARS) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_excerpt_shows_nearest_existing_ancestor_masked() {
+        let body = json!({
+            "data": { "user": { "id": 42, "mail": "ana@mail.com", "password": "hunter2",
+                                "session": { "access_token": "abc" }, "ssn": "123" } },
+            "meta": { "page": 1 }
+        });
+
+        let redaction = Redaction::new(&["SSN".to_string()]);
+        let text = excerpt(&body, "/data/user/email", &redaction).unwrap();
+        assert!(text.starts_with("near 'data.user': {"), "{}", text);
+        assert!(text.contains("\"mail\":\"ana@mail.com\""));
+        assert!(text.contains("\"password\":\"***\""));
+        assert!(text.contains("\"access_token\":\"***\""));
+        assert!(text.contains("\"ssn\":\"***\""));
+        assert!(!text.contains("meta"));
+
+        let text = excerpt(&body, "/data/orders/0/id", &redaction).unwrap();
+        assert!(text.starts_with("near 'data': "), "{}", text);
+        assert!(excerpt(&body, "/status", &redaction)
+            .unwrap()
+            .starts_with("near '$': "));
+    }
+
+    #[test]
+    fn test_excerpt_is_size_capped() {
+        let body = json!({ "items": vec!["x".repeat(50); 40] });
+        let redaction = Redaction::default();
+        let text = with_excerpt("Assertion failed".to_string(), &body, "/total", &redaction);
+        let (_, excerpt) = text.split_once("\n  ").unwrap();
+        assert!(excerpt.ends_with('…'));
+        assert!(excerpt.chars().count() <= MAX_EXCERPT_CHARS + "near '$': ".len() + 1);
+
+        assert_eq!(
+            with_excerpt("msg".to_string(), &Value::Null, "", &redaction),
+            "msg".to_string()
+        );
+    }
+}
@@ -0,0 +1,251 @@
+//! # Captura de Headers - O que Vai para o Relatório
+//!
+//! Auxiliar do `HttpExecutor`: transforma os headers da resposta (e os da
+//! requisição enviada) nos mapas de `http_details`, mascarando credenciais
+//! e limitando o tamanho.
+//!
+//! ## Para todos entenderem:
+//!
+//! Quem investiga uma falha quase sempre pergunta pelo `X-Request-Id` (para
+//! achar o log do servidor) e pelos headers de cache (`Cache-Control`,
+//! `Age`, `ETag`). Por isso todo step HTTP guarda os headers recebidos:
+//!
+//! | Situação                       | No relatório                          |
+//! |--------------------------------|---------------------------------------|
+//! | Header comum                   | Valor como veio                       |
+//! | Header repetido (`Vary`, ...)  | Valores unidos por `, `               |
+//! | Credencial ou `redact`         | `***`                                 |
+//! | Passou de `max_bytes`          | Descartado; `response_headers_truncated` |
+//!
+//! Os headers entram em ordem alfabética, então o corte é estável entre
+//! execuções.
+
+use reqwest::header::{HeaderMap, CONTENT_LENGTH};
+use reqwest::Request;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Instant;
+
+use crate::protocol::HeaderCaptureConfig;
+
+/// Credenciais sempre mascaradas: headers e campos de body com estes nomes
+/// ou terminados em `-<nome>`/`_<nome>` (`Set-Cookie`, `X-Api-Key`,
+/// `access_token`, `client_secret`...).
+pub const SECRET_HEADERS: &[&str] = &[
+    "authorization",
+    "cookie",
+    "token",
+    "secret",
+    "password",
+    "passwd",
+    "api_key",
+    "apikey",
+];
+
+/// Lista única de redação: `SECRET_HEADERS` mais `config.http.header_capture.redact`.
+///
+/// Usada nos headers do relatório, nas gravações do interceptor `record`,
+/// na captura de timeout e nos trechos de body das mensagens de erro.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Redaction {
+    extra: Vec<String>,
+}
+
+impl Redaction {
+    /// Redação com os nomes extras de `config.http.header_capture.redact`.
+    pub fn new(extra: &[String]) -> Self {
+        Self {
+            extra: extra.iter().map(|name| normalize(name)).collect(),
+        }
+    }
+
+    /// O nome (header ou campo) guarda uma credencial.
+    pub fn is_secret(&self, name: &str) -> bool {
+        let name = normalize(name);
+        SECRET_HEADERS
+            .iter()
+            .any(|secret| name == *secret || name.ends_with(&format!("_{}", secret)))
+            || self.extra.contains(&name)
+    }
+
+    /// Headers com credenciais mascaradas (`***`), para relatórios e gravações.
+    pub fn headers(&self, headers: &HeaderMap) -> HashMap<String, String> {
+        headers
+            .iter()
+            .map(|(name, value)| {
+                let value = if self.is_secret(name.as_str()) {
+                    "***".to_string()
+                } else {
+                    value.to_str().unwrap_or("<binary>").to_string()
+                };
+                (name.as_str().to_string(), value)
+            })
+            .collect()
+    }
+}
+
+/// `X-Api-Key` e `x_api_key` são o mesmo nome.
+fn normalize(name: &str) -> String {
+    name.to_ascii_lowercase().replace('-', "_")
+}
+
+/// Política de captura do executor (a partir de `config.http.header_capture`).
+#[derive(Debug, Clone)]
+pub struct HeaderCapture {
+    redaction: Redaction,
+    max_bytes: usize,
+}
+
+impl Default for HeaderCapture {
+    fn default() -> Self {
+        Self::new(&HeaderCaptureConfig::default())
+    }
+}
+
+impl HeaderCapture {
+    pub fn new(config: &HeaderCaptureConfig) -> Self {
+        Self {
+            redaction: Redaction::new(&config.redact),
+            max_bytes: config.max_bytes,
+        }
+    }
+
+    /// Redação da execução (para gravações, timeouts e trechos de erro).
+    pub fn redaction(&self) -> &Redaction {
+        &self.redaction
+    }
+
+    /// Mapa para o relatório e se ele foi truncado (`None` com `max_bytes: 0`).
+    pub fn capture(&self, headers: &HeaderMap) -> (Option<HashMap<String, String>>, bool) {
+        if self.max_bytes == 0 {
+            return (None, false);
+        }
+
+        let mut joined: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for (name, value) in headers {
+            joined
+                .entry(name.as_str())
+                .or_default()
+                .push(value.to_str().unwrap_or("<binary>"));
+        }
+
+        let mut captured = HashMap::new();
+        let mut used = 0;
+        for (name, values) in joined {
+            let value = if self.redaction.is_secret(name) {
+                "***".to_string()
+            } else {
+                values.join(", ")
+            };
+            used += name.len() + value.len();
+            if used > self.max_bytes {
+                return (Some(captured), true);
+            }
+            captured.insert(name.to_string(), value);
+        }
+        (Some(captured), false)
+    }
+}
+
+/// O que saiu na requisição: headers finais, tamanho do body e quando.
+#[derive(Debug, Clone)]
+pub struct SentRequest {
+    pub headers: HeaderMap,
+    pub body_bytes: Option<u64>,
+    /// Fim da preparação (interceptores e assinatura): início do envio.
+    pub at: Instant,
+}
+
+impl SentRequest {
+    /// Fotografa a requisição; bodies em streaming usam o `Content-Length`.
+    pub fn of(request: &Request) -> Self {
+        let body_bytes = match request.body() {
+            Some(body) => body.as_bytes().map(|b| b.len() as u64).or_else(|| {
+                request
+                    .headers()
+                    .get(CONTENT_LENGTH)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse().ok())
+            }),
+            None => None,
+        };
+        Self {
+            headers: request.headers().clone(),
+            body_bytes,
+            at: Instant::now(),
+        }
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn headers() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-request-id", HeaderValue::from_static("req-42"));
+        headers.append("vary", HeaderValue::from_static("Accept"));
+        headers.append("vary", HeaderValue::from_static("Origin"));
+        headers.insert("set-cookie", HeaderValue::from_static("sid=abc"));
+        headers.insert("x-internal-token", HeaderValue::from_static("t0k3n"));
+        headers
+    }
+
+    #[test]
+    fn test_capture_joins_and_redacts() {
+        let capture = HeaderCapture::new(&HeaderCaptureConfig {
+            redact: vec!["X-Internal-Token".to_string()],
+            ..Default::default()
+        });
+        let (captured, truncated) = capture.capture(&headers());
+        let captured = captured.unwrap();
+
+        assert!(!truncated);
+        assert_eq!(captured["x-request-id"], "req-42");
+        assert_eq!(captured["vary"], "Accept, Origin");
+        assert_eq!(captured["set-cookie"], "***");
+        assert_eq!(captured["x-internal-token"], "***");
+    }
+
+    #[test]
+    fn test_redaction_list_covers_headers_and_fields() {
+        let redaction = Redaction::new(&["X-Tenant-Ssn".to_string()]);
+        for name in [
+            "Authorization",
+            "Proxy-Authorization",
+            "Set-Cookie",
+            "X-Api-Key",
+            "x-auth-token",
+            "client_secret",
+            "password",
+            "x_tenant_ssn",
+        ] {
+            assert!(redaction.is_secret(name), "{}", name);
+        }
+        assert!(!redaction.is_secret("x-request-id"));
+        assert!(!redaction.is_secret("tokens_left"));
+    }
+
+    #[test]
+    fn test_capture_respects_size_cap() {
+        let capture = HeaderCapture::new(&HeaderCaptureConfig {
+            redact: Vec::new(),
+            max_bytes: 31,
+        });
+        let (captured, truncated) = capture.capture(&headers());
+
+        // Ordem alfabética: set-cookie (13) e vary (18) cabem; o resto não.
+        assert!(truncated);
+        assert_eq!(captured.unwrap().len(), 2);
+
+        let disabled = HeaderCapture::new(&HeaderCaptureConfig {
+            redact: Vec::new(),
+            max_bytes: 0,
+        });
+        assert_eq!(disabled.capture(&headers()), (None, false));
+    }
+}
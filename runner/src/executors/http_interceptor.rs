@@ -0,0 +1,402 @@
+//! # Interceptores - Cadeia de Middlewares por Step
+//!
+//! Auxiliar do `HttpExecutor`: uma lista ordenada de interceptores que
+//! enxergam (e podem alterar) a requisição antes do envio e a resposta
+//! quando ela chega.
+//!
+//! ## Para todos entenderem:
+//!
+//! Às vezes um step precisa de um "desvio" só para ele: simular um cliente
+//! lento, trocar um header para um teste negativo, guardar o que foi
+//! enviado. Em vez de um parâmetro novo para cada caso, o step declara a
+//! cadeia:
+//!
+//! ```json
+//! "params": {
+//!   "method": "GET", "path": "/orders",
+//!   "proxy": "http://mitm.internal:8080",
+//!   "interceptors": [
+//!     { "type": "latency", "delay_ms": 300 },
+//!     { "type": "header_rewrite", "set": { "X-Tenant": "${tenant}" }, "remove": ["Accept-Encoding"] },
+//!     { "type": "record" }
+//!   ]
+//! }
+//! ```
+//!
+//! ## Interceptores embutidos:
+//!
+//! | `type`           | Opções                 | Efeito                                            |
+//! |------------------|------------------------|---------------------------------------------------|
+//! | `latency`        | `delay_ms`             | Espera antes de enviar                            |
+//! | `header_rewrite` | `set` (objeto), `remove` (lista) | Define/remove headers da requisição     |
+//! | `record`         | -                      | Guarda requisição e resposta em `${<step_id>.recorded}` |
+//!
+//! ## Ordem:
+//!
+//! A requisição passa pelos interceptores na ordem da lista, depois da
+//! sessão e antes do `signer` (a assinatura cobre os headers reescritos).
+//! A resposta passa na ordem inversa, como numa pilha de middlewares.
+//! Respostas vindas do cache da execução não passam pela cadeia.
+//!
+//! ## Plugins:
+//!
+//! Outros tipos entram com `HttpExecutor::register_interceptor(tipo, fábrica)`.
+//!
+//! ## Proxy por step:
+//!
+//! `params.proxy` manda o step por um proxy (ex: um MITM de gravação) sem
+//! mudar os outros steps. Não combina com `session`/`actor`, `http_version`
+//! nem `unix_socket`, que já usam clientes próprios.
+
+use anyhow::{anyhow, Context as _, Result};
+use async_trait::async_trait;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Client, ClientBuilder, Proxy, Request};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::http_headers::Redaction;
+use crate::context::Context;
+
+/// Middleware de um step HTTP.
+#[async_trait]
+pub trait Interceptor: Send + Sync {
+    /// Chamado antes do envio, na ordem da cadeia.
+    async fn on_request(&self, _request: &mut Request) -> Result<()> {
+        Ok(())
+    }
+
+    /// Chamado quando status e headers chegam, na ordem inversa.
+    fn on_response(&self, _status: u16, _headers: &HeaderMap) {}
+
+    /// O que o interceptor registrou (vai para `${<step_id>.recorded}`).
+    fn recording(&self) -> Option<Value> {
+        None
+    }
+}
+
+/// Cria um interceptor a partir das opções de `params.interceptors[i]`.
+pub type InterceptorFactory =
+    Arc<dyn Fn(&Map<String, Value>) -> Result<Box<dyn Interceptor>> + Send + Sync>;
+
+// ============================================================================
+// REGISTRO E CADEIA
+// ============================================================================
+
+/// Tipos de interceptor conhecidos.
+pub struct InterceptorRegistry {
+    factories: HashMap<String, InterceptorFactory>,
+}
+
+impl Default for InterceptorRegistry {
+    fn default() -> Self {
+        Self::new(Redaction::default())
+    }
+}
+
+impl InterceptorRegistry {
+    /// Registro com os tipos embutidos (`latency`, `header_rewrite`, `record`).
+    ///
+    /// O `record` mascara credenciais com a redação da execução.
+    pub fn new(redaction: Redaction) -> Self {
+        let mut registry = Self {
+            factories: HashMap::new(),
+        };
+        registry.register(
+            "latency",
+            Arc::new(|o| Ok(Box::new(LatencyInterceptor::new(o)?) as _)),
+        );
+        registry.register(
+            "header_rewrite",
+            Arc::new(|o| Ok(Box::new(HeaderRewriteInterceptor::new(o)?) as _)),
+        );
+        registry.register(
+            "record",
+            Arc::new(move |_| Ok(Box::new(RecordInterceptor::new(redaction.clone())) as _)),
+        );
+        registry
+    }
+
+    /// Registra (ou substitui) um tipo de interceptor.
+    pub fn register(&mut self, kind: &str, factory: InterceptorFactory) {
+        self.factories.insert(kind.to_string(), factory);
+    }
+
+    /// Cadeia do step (`params.interceptors`), com as opções interpoladas.
+    pub fn chain(&self, params: &Value, context: &Context) -> Result<InterceptorChain> {
+        let Some(entries) = params.get("interceptors") else {
+            return Ok(InterceptorChain::default());
+        };
+        let entries = entries
+            .as_array()
+            .ok_or_else(|| anyhow!("'interceptors' deve ser uma lista"))?;
+
+        let mut interceptors = Vec::with_capacity(entries.len());
+        for (index, entry) in entries.iter().enumerate() {
+            let options = match context.interpolate_value(entry)? {
+                Value::Object(options) => options,
+                _ => return Err(anyhow!("interceptors[{}] deve ser um objeto", index)),
+            };
+            let kind = options
+                .get("type")
+                .and_then(|t| t.as_str())
+                .ok_or_else(|| anyhow!("interceptors[{}] sem 'type'", index))?;
+            let factory = self
+                .factories
+                .get(kind)
+                .ok_or_else(|| anyhow!("interceptors[{}]: tipo '{}' desconhecido", index, kind))?;
+            interceptors.push(
+                factory(&options).with_context(|| format!("interceptors[{}] inválido", index))?,
+            );
+        }
+        Ok(InterceptorChain { interceptors })
+    }
+}
+
+/// Interceptores de um step, na ordem declarada.
+#[derive(Default)]
+pub struct InterceptorChain {
+    interceptors: Vec<Box<dyn Interceptor>>,
+}
+
+impl InterceptorChain {
+    pub async fn on_request(&self, request: &mut Request) -> Result<()> {
+        for interceptor in &self.interceptors {
+            interceptor.on_request(request).await?;
+        }
+        Ok(())
+    }
+
+    pub fn on_response(&self, status: u16, headers: &HeaderMap) {
+        for interceptor in self.interceptors.iter().rev() {
+            interceptor.on_response(status, headers);
+        }
+    }
+
+    /// Registros da cadeia (`None` se nenhum interceptor gravou nada).
+    pub fn recordings(&self) -> Option<Value> {
+        let recorded: Vec<Value> = self
+            .interceptors
+            .iter()
+            .filter_map(|i| i.recording())
+            .collect();
+        match recorded.len() {
+            0 => None,
+            1 => recorded.into_iter().next(),
+            _ => Some(Value::Array(recorded)),
+        }
+    }
+}
+
+// ============================================================================
+// PROXY POR STEP
+// ============================================================================
+
+/// Clientes que saem por um proxy (`params.proxy`), um por URL de proxy.
+///
+/// Criados na primeira vez que um step usa o proxy, com as mesmas opções
+/// (`config.http`, timeout, resolver) do cliente principal.
+pub struct ProxyClients {
+    builder: Box<dyn Fn() -> ClientBuilder + Send + Sync>,
+    clients: Mutex<HashMap<String, Client>>,
+}
+
+impl ProxyClients {
+    pub fn new(builder: impl Fn() -> ClientBuilder + Send + Sync + 'static) -> Self {
+        Self {
+            builder: Box::new(builder),
+            clients: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Cliente para o proxy `url` (`http://` ou `https://`).
+    pub fn client(&self, url: &str) -> Result<Client> {
+        let mut clients = self.clients.lock().unwrap();
+        if let Some(client) = clients.get(url) {
+            return Ok(client.clone());
+        }
+        let proxy = Proxy::all(url).map_err(|e| anyhow!("proxy '{}' inválido: {}", url, e))?;
+        let client = (self.builder)().proxy(proxy).build()?;
+        clients.insert(url.to_string(), client.clone());
+        Ok(client)
+    }
+}
+
+// ============================================================================
+// INTERCEPTORES EMBUTIDOS
+// ============================================================================
+
+/// Atrasa o envio (`delay_ms`), simulando um cliente lento.
+struct LatencyInterceptor {
+    delay: Duration,
+}
+
+impl LatencyInterceptor {
+    fn new(options: &Map<String, Value>) -> Result<Self> {
+        let delay_ms = options
+            .get("delay_ms")
+            .and_then(|d| d.as_u64())
+            .ok_or_else(|| anyhow!("latency exige 'delay_ms'"))?;
+        Ok(Self {
+            delay: Duration::from_millis(delay_ms),
+        })
+    }
+}
+
+#[async_trait]
+impl Interceptor for LatencyInterceptor {
+    async fn on_request(&self, _request: &mut Request) -> Result<()> {
+        tokio::time::sleep(self.delay).await;
+        Ok(())
+    }
+}
+
+/// Define (`set`) e remove (`remove`) headers da requisição.
+struct HeaderRewriteInterceptor {
+    set: Vec<(HeaderName, HeaderValue)>,
+    remove: Vec<HeaderName>,
+}
+
+impl HeaderRewriteInterceptor {
+    fn new(options: &Map<String, Value>) -> Result<Self> {
+        let mut set = Vec::new();
+        for (name, value) in options
+            .get("set")
+            .and_then(|s| s.as_object())
+            .into_iter()
+            .flatten()
+        {
+            let value = value
+                .as_str()
+                .ok_or_else(|| anyhow!("header_rewrite: valor de '{}' deve ser texto", name))?;
+            set.push((
+                HeaderName::try_from(name.as_str())?,
+                HeaderValue::from_str(value)?,
+            ));
+        }
+        let remove = options
+            .get("remove")
+            .and_then(|r| r.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|name| name.as_str())
+            .map(HeaderName::try_from)
+            .collect::<Result<_, _>>()?;
+        Ok(Self { set, remove })
+    }
+}
+
+#[async_trait]
+impl Interceptor for HeaderRewriteInterceptor {
+    async fn on_request(&self, request: &mut Request) -> Result<()> {
+        let headers = request.headers_mut();
+        for name in &self.remove {
+            headers.remove(name);
+        }
+        for (name, value) in &self.set {
+            headers.insert(name.clone(), value.clone());
+        }
+        Ok(())
+    }
+}
+
+/// Guarda método, URL e headers enviados e status/headers recebidos.
+///
+/// Credenciais (`Authorization`, `Set-Cookie`, `redact`...) aparecem como `***`.
+struct RecordInterceptor {
+    redaction: Redaction,
+    recorded: Mutex<Map<String, Value>>,
+}
+
+impl RecordInterceptor {
+    fn new(redaction: Redaction) -> Self {
+        Self {
+            redaction,
+            recorded: Mutex::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl Interceptor for RecordInterceptor {
+    async fn on_request(&self, request: &mut Request) -> Result<()> {
+        let mut recorded = self.recorded.lock().unwrap();
+        recorded.insert(
+            "request".to_string(),
+            json!({
+                "method": request.method().as_str(),
+                "url": request.url().as_str(),
+                "headers": self.redaction.headers(request.headers()),
+            }),
+        );
+        Ok(())
+    }
+
+    fn on_response(&self, status: u16, headers: &HeaderMap) {
+        let mut recorded = self.recorded.lock().unwrap();
+        recorded.insert(
+            "response".to_string(),
+            json!({ "status": status, "headers": self.redaction.headers(headers) }),
+        );
+    }
+
+    fn recording(&self) -> Option<Value> {
+        let recorded = self.recorded.lock().unwrap();
+        (!recorded.is_empty()).then(|| Value::Object(recorded.clone()))
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_chain_rewrites_and_records_in_order() {
+        let mut context = Context::new();
+        context.set("tenant", json!("acme"));
+        let params = json!({
+            "interceptors": [
+                { "type": "header_rewrite", "set": { "X-Tenant": "${tenant}" }, "remove": ["x-debug"] },
+                { "type": "record" }
+            ]
+        });
+        let chain = InterceptorRegistry::default()
+            .chain(&params, &context)
+            .unwrap();
+
+        let mut request = reqwest::Client::new()
+            .get("http://api.test/orders")
+            .header("x-debug", "1")
+            .header("authorization", "Bearer secret")
+            .build()
+            .unwrap();
+        chain.on_request(&mut request).await.unwrap();
+        assert_eq!(request.headers()["x-tenant"], "acme");
+        assert!(!request.headers().contains_key("x-debug"));
+        chain.on_response(204, &HeaderMap::new());
+
+        let recorded = chain.recordings().unwrap();
+        assert_eq!(recorded["request"]["headers"]["x-tenant"], json!("acme"));
+        assert_eq!(
+            recorded["request"]["headers"]["authorization"],
+            json!("***")
+        );
+        assert_eq!(recorded["response"]["status"], json!(204));
+    }
+
+    #[test]
+    fn test_unknown_interceptor_type() {
+        let params = json!({ "interceptors": [{ "type": "chaos" }] });
+        let error = InterceptorRegistry::default()
+            .chain(&params, &Context::new())
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("tipo 'chaos' desconhecido"));
+    }
+}
@@ -0,0 +1,284 @@
+//! # Revalidação - Assertion `cache_behavior`
+//!
+//! Auxiliar do `HttpExecutor` que confere se o endpoint responde certo a
+//! uma requisição condicional, como fazem CDNs e caches de navegador.
+//!
+//! ## Para todos entenderem:
+//!
+//! Um cache guarda a resposta junto com o `ETag`/`Last-Modified` dela e,
+//! mais tarde, pergunta ao servidor "mudou desde então?" (`If-None-Match`,
+//! `If-Modified-Since`). Se não mudou, o servidor responde `304 Not
+//! Modified` sem body e o cache reaproveita o que tem. Um servidor que
+//! ignora a pergunta faz o cache baixar tudo de novo; um `304` malformado
+//! faz o cache servir conteúdo velho.
+//!
+//! Com `cache_behavior`, o step faz a requisição normal e, logo em
+//! seguida, a mesma requisição com os validadores da primeira resposta:
+//!
+//! ```json
+//! { "type": "cache_behavior", "operator": "eq", "value": "not_modified" }
+//! ```
+//!
+//! | `value`          | Passa quando a requisição condicional...                     |
+//! |------------------|--------------------------------------------------------------|
+//! | `"not_modified"` | Volta `304`, sem body, com o mesmo `ETag` e os headers de cache da primeira (`Cache-Control`, `Expires`, `Vary`) |
+//! | `"modified"`     | Volta a resposta completa (conteúdo que muda a cada chamada) |
+//!
+//! Sem `ETag` nem `Last-Modified` na primeira resposta não há o que
+//! revalidar: `not_modified` falha dizendo isso e `modified` passa.
+
+use anyhow::Result;
+use reqwest::header::{
+    HeaderMap, HeaderValue, CACHE_CONTROL, ETAG, EXPIRES, IF_MODIFIED_SINCE, IF_NONE_MATCH,
+    LAST_MODIFIED, VARY,
+};
+use serde_json::json;
+
+use crate::protocol::{Assertion, AssertionFailure};
+
+/// Tipo da assertion tratada por este módulo.
+pub const CACHE_BEHAVIOR: &str = "cache_behavior";
+
+/// O que aconteceu com a requisição condicional.
+#[derive(Debug)]
+pub enum Revalidation {
+    /// A condicional foi enviada e respondida.
+    Response {
+        status: u16,
+        headers: HeaderMap,
+        body_bytes: usize,
+    },
+    /// A primeira resposta não tinha `ETag` nem `Last-Modified`.
+    NoValidators,
+    /// A condicional não pôde ser enviada (rede, body em streaming...).
+    Failed(String),
+}
+
+impl Revalidation {
+    /// Lê a resposta da condicional (o body só importa pelo tamanho).
+    pub async fn read(response: reqwest::Response) -> Result<Self> {
+        let status = response.status().as_u16();
+        let headers = response.headers().clone();
+        let body = response.bytes().await?;
+        Ok(Self::Response {
+            status,
+            headers,
+            body_bytes: body.len(),
+        })
+    }
+}
+
+/// True se o step tem alguma assertion `cache_behavior` (pede a condicional).
+pub fn is_requested(assertions: &[Assertion]) -> bool {
+    assertions
+        .iter()
+        .any(|a| a.assertion_type == CACHE_BEHAVIOR)
+}
+
+/// Validadores da primeira resposta como headers condicionais (vazio se não houver).
+pub fn conditional_headers(first: &HeaderMap) -> HeaderMap {
+    let mut conditional = HeaderMap::new();
+    if let Some(etag) = first.get(ETAG) {
+        conditional.insert(IF_NONE_MATCH, etag.clone());
+    }
+    if let Some(modified) = first.get(LAST_MODIFIED) {
+        conditional.insert(IF_MODIFIED_SINCE, modified.clone());
+    }
+    conditional
+}
+
+/// Assertions `cache_behavior` que falharam, no formato de `assertion_failures`.
+pub fn failures(
+    assertions: &[Assertion],
+    first: &HeaderMap,
+    revalidation: &Revalidation,
+) -> Vec<AssertionFailure> {
+    let actual = match revalidation {
+        Revalidation::Response { status, .. } => Some(json!(status)),
+        _ => None,
+    };
+    assertions
+        .iter()
+        .filter(|a| a.assertion_type == CACHE_BEHAVIOR)
+        .filter_map(|assertion| {
+            let message = check(assertion, first, revalidation)?;
+            Some(AssertionFailure {
+                assertion_type: assertion.assertion_type.clone(),
+                operator: assertion.operator.clone(),
+                path: assertion.path.clone(),
+                expected: assertion.value.clone(),
+                actual: actual.clone(),
+                message,
+            })
+        })
+        .collect()
+}
+
+/// Avalia uma assertion `cache_behavior`: `Some(mensagem)` se falhou.
+fn check(assertion: &Assertion, first: &HeaderMap, revalidation: &Revalidation) -> Option<String> {
+    let expected = assertion.value.as_str().unwrap_or_default();
+    let fail = |reason: String| {
+        Some(format!(
+            "Assertion failed: cache_behavior {} ({})",
+            assertion.value, reason
+        ))
+    };
+    if assertion.operator != "eq" {
+        return fail(format!(
+            "operator '{}' not supported, use 'eq'",
+            assertion.operator
+        ));
+    }
+
+    let (status, headers, body_bytes) = match (revalidation, expected) {
+        (Revalidation::Failed(error), _) => {
+            return fail(format!("conditional request failed: {}", error))
+        }
+        (Revalidation::NoValidators, "modified") => return None,
+        (Revalidation::NoValidators, _) => {
+            return fail("response has no ETag or Last-Modified to revalidate".to_string())
+        }
+        (
+            Revalidation::Response {
+                status,
+                headers,
+                body_bytes,
+            },
+            _,
+        ) => (*status, headers, *body_bytes),
+    };
+
+    match expected {
+        "not_modified" => {
+            if status != 304 {
+                return fail(format!("conditional request returned {}", status));
+            }
+            if body_bytes > 0 {
+                return fail(format!("304 carried a {}-byte body", body_bytes));
+            }
+            if let Some(etag) = first.get(ETAG) {
+                match headers.get(ETAG) {
+                    Some(same) if same == etag => {}
+                    Some(other) => {
+                        return fail(format!(
+                            "304 ETag {} differs from {}",
+                            text(other),
+                            text(etag)
+                        ))
+                    }
+                    None => return fail("304 is missing the ETag of the full response".into()),
+                }
+            }
+            // RFC 9110: o 304 repete os headers que iriam num 200.
+            [CACHE_CONTROL, EXPIRES, VARY]
+                .into_iter()
+                .find(|name| first.contains_key(name) && !headers.contains_key(name))
+                .and_then(|name| {
+                    fail(format!(
+                        "304 is missing the {} header of the full response",
+                        name
+                    ))
+                })
+        }
+        "modified" if status == 304 => {
+            fail("conditional request returned 304, expected the full response".into())
+        }
+        "modified" => None,
+        _ => fail("value must be 'not_modified' or 'modified'".into()),
+    }
+}
+
+fn text(value: &HeaderValue) -> &str {
+    value.to_str().unwrap_or("<binary>")
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assertion(value: &str) -> Assertion {
+        Assertion {
+            assertion_type: CACHE_BEHAVIOR.to_string(),
+            operator: "eq".to_string(),
+            value: json!(value),
+            ..Default::default()
+        }
+    }
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.parse().unwrap(), v.parse().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_conditional_headers_from_validators() {
+        let first = headers(&[
+            ("etag", "\"v1\""),
+            ("last-modified", "Wed, 01 May 2024 10:00:00 GMT"),
+        ]);
+        let conditional = conditional_headers(&first);
+        assert_eq!(conditional[IF_NONE_MATCH], "\"v1\"");
+        assert_eq!(
+            conditional[IF_MODIFIED_SINCE],
+            "Wed, 01 May 2024 10:00:00 GMT"
+        );
+        assert!(conditional_headers(&HeaderMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_not_modified_semantics() {
+        let first = headers(&[("etag", "\"v1\""), ("cache-control", "max-age=60")]);
+        let response =
+            |status, pairs: &[(&'static str, &'static str)], body_bytes| Revalidation::Response {
+                status,
+                headers: headers(pairs),
+                body_bytes,
+            };
+        let not_modified = [assertion("not_modified")];
+
+        let ok = response(
+            304,
+            &[("etag", "\"v1\""), ("cache-control", "max-age=60")],
+            0,
+        );
+        assert!(failures(&not_modified, &first, &ok).is_empty());
+
+        let cases = [
+            (response(200, &[("etag", "\"v1\"")], 512), "returned 200"),
+            (
+                response(
+                    304,
+                    &[("etag", "\"v1\""), ("cache-control", "max-age=60")],
+                    12,
+                ),
+                "12-byte body",
+            ),
+            (
+                response(
+                    304,
+                    &[("etag", "\"v2\""), ("cache-control", "max-age=60")],
+                    0,
+                ),
+                "differs",
+            ),
+            (response(304, &[("etag", "\"v1\"")], 0), "cache-control"),
+            (Revalidation::NoValidators, "no ETag or Last-Modified"),
+        ];
+        for (revalidation, reason) in cases {
+            let failed = failures(&not_modified, &first, &revalidation);
+            assert!(failed[0].message.contains(reason), "{}", failed[0].message);
+        }
+
+        // `modified`: passa sem validadores, falha se o servidor respondeu 304.
+        let modified = [assertion("modified")];
+        assert!(failures(&modified, &first, &Revalidation::NoValidators).is_empty());
+        let failed = failures(&modified, &first, &ok);
+        assert_eq!(failed[0].actual, Some(json!(304)));
+    }
+}
@@ -0,0 +1,205 @@
+//! # Sessões HTTP - Cookies, Conexões e Token por "Usuário"
+//!
+//! Auxiliar do `HttpExecutor` para `config.sessions`: cada sessão tem o
+//! próprio cliente HTTP e, opcionalmente, um hook de renovação de token.
+//!
+//! ## Para todos entenderem:
+//!
+//! | Recurso            | Steps sem sessão   | Steps da sessão `buyer`          |
+//! |--------------------|--------------------|----------------------------------|
+//! | Cookies            | Não guardados      | Cookie jar próprio de `buyer`    |
+//! | Pool de conexões   | Compartilhado      | Próprio de `buyer`               |
+//! | Token (`refresh`)  | -                  | Renovado em 401 e reaplicado     |
+//!
+//! Duas sessões (`buyer` e `seller`) rodando em paralelo se comportam como
+//! dois usuários distintos: um `Set-Cookie` recebido por um nunca é
+//! enviado pelo outro.
+//!
+//! ## Renovação de token:
+//!
+//! Quando um step da sessão recebe um status de `refresh.on_status`
+//! (padrão: 401), a requisição de `refresh` é feita com o cliente da
+//! sessão, o token é lido em `token_path` e o step é repetido **uma** vez
+//! com `<header>: <prefix><token>`. As requisições seguintes da sessão já
+//! saem com o token novo.
+//!
+//! Cada ator de `config.actors` também ganha uma sessão (com os headers
+//! dele); `step.actor` tem prioridade sobre `step.session`.
+
+use crate::context::Context;
+use crate::extractors::navigate_json;
+use crate::protocol::{ActorConfig, SessionConfig, TokenRefresh};
+use anyhow::{anyhow, bail, Context as _, Result};
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::{Client, Method, Request};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Sessão HTTP nomeada de `config.sessions`.
+pub struct HttpSession {
+    /// Nome da sessão (para logs e erros).
+    name: String,
+
+    /// Cliente com cookie jar e pool próprios.
+    pub client: Client,
+
+    /// Hook de renovação (opcional).
+    refresh: Option<TokenRefresh>,
+
+    /// Headers extras de toda requisição da sessão (headers do ator).
+    pub headers: HashMap<String, String>,
+
+    /// Último token obtido pelo hook.
+    token: Mutex<Option<String>>,
+}
+
+impl HttpSession {
+    /// Cria a sessão com um cliente já construído (com `cookie_store(true)`).
+    pub fn new(name: &str, config: &SessionConfig, client: Client) -> Self {
+        Self {
+            name: name.to_string(),
+            client,
+            refresh: config.refresh.clone(),
+            headers: HashMap::new(),
+            token: Mutex::new(None),
+        }
+    }
+
+    /// Sessão de um ator de `config.actors` (com os headers dele).
+    pub fn for_actor(name: &str, actor: &ActorConfig, client: Client) -> Self {
+        let config = SessionConfig {
+            refresh: actor.refresh.clone(),
+        };
+        Self {
+            headers: actor.headers.clone(),
+            ..Self::new(name, &config, client)
+        }
+    }
+
+    /// True se `status` deve disparar a renovação do token.
+    pub fn should_refresh(&self, status: u16) -> bool {
+        self.refresh
+            .as_ref()
+            .is_some_and(|r| r.on_status.contains(&status))
+    }
+
+    /// Aplica o token atual da sessão (se houver) ao header configurado.
+    ///
+    /// Substitui o valor vindo do step: após uma renovação, o `${token}`
+    /// do plano já estaria vencido.
+    pub fn authorize(&self, request: &mut Request) -> Result<()> {
+        let (Some(refresh), Some(token)) = (&self.refresh, self.current_token()) else {
+            return Ok(());
+        };
+        let name = HeaderName::from_bytes(refresh.header.as_bytes())
+            .map_err(|e| anyhow!("Sessão '{}': header inválido: {}", self.name, e))?;
+        let value = HeaderValue::from_str(&format!("{}{}", refresh.prefix, token))
+            .map_err(|e| anyhow!("Sessão '{}': token inválido para header: {}", self.name, e))?;
+        request.headers_mut().insert(name, value);
+        Ok(())
+    }
+
+    fn current_token(&self) -> Option<String> {
+        self.token.lock().ok().and_then(|t| t.clone())
+    }
+
+    /// Executa o hook de renovação e guarda o token novo.
+    pub async fn refresh_token(&self, context: &Context) -> Result<()> {
+        let Some(refresh) = &self.refresh else {
+            return Ok(());
+        };
+        let failure = || format!("Falha ao renovar o token da sessão '{}'", self.name);
+
+        let path = context.interpolate_str(&refresh.path)?;
+        let method = Method::from_bytes(refresh.method.to_uppercase().as_bytes())
+            .map_err(|e| anyhow!("Método inválido em refresh: {}", e))?;
+        let mut builder = self.client.request(method, resolve_url(context, &path));
+        for (name, value) in &refresh.headers {
+            builder = builder.header(name, context.interpolate_str(value)?);
+        }
+        if let Some(body) = &refresh.body {
+            builder = builder.json(&context.interpolate_value(body)?);
+        }
+
+        let response = builder.send().await.with_context(failure)?;
+        let status = response.status();
+        if !status.is_success() {
+            bail!("{}: refresh respondeu {}", failure(), status);
+        }
+        let body: Value = response.json().await.with_context(failure)?;
+        let token = match navigate_json(&body, &refresh.token_path).with_context(failure)? {
+            Value::String(token) => token,
+            Value::Null => bail!(
+                "{}: '{}' ausente na resposta",
+                failure(),
+                refresh.token_path
+            ),
+            other => other.to_string(),
+        };
+
+        tracing::info!(session = %self.name, "Token da sessão renovado");
+        if let Ok(mut current) = self.token.lock() {
+            *current = Some(token);
+        }
+        Ok(())
+    }
+}
+
+/// URL completa de um path (absoluto, ou relativo à `base_url` do contexto).
+pub fn resolve_url(context: &Context, path: &str) -> String {
+    if path.starts_with("http") {
+        return path.to_string();
+    }
+    let base = context
+        .get("base_url")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    format!("{}{}", base.trim_end_matches('/'), path)
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn session(refresh: Value) -> HttpSession {
+        let config: SessionConfig = serde_json::from_value(json!({ "refresh": refresh })).unwrap();
+        HttpSession::new("buyer", &config, Client::new())
+    }
+
+    #[test]
+    fn test_refresh_defaults_and_trigger() {
+        let session = session(json!({ "path": "/auth/refresh", "token_path": "$.token" }));
+        assert!(session.should_refresh(401));
+        assert!(!session.should_refresh(403));
+
+        let refresh = session.refresh.as_ref().unwrap();
+        assert_eq!(refresh.method, "POST");
+        assert_eq!(refresh.header, "Authorization");
+        assert_eq!(refresh.prefix, "Bearer ");
+    }
+
+    #[test]
+    fn test_authorize_replaces_step_header() {
+        let session = session(json!({ "path": "/r", "token_path": "$.token" }));
+        let mut request = Client::new()
+            .get("http://localhost/me")
+            .header("Authorization", "Bearer stale")
+            .build()
+            .unwrap();
+
+        // Sem token renovado, o header do step é mantido.
+        session.authorize(&mut request).unwrap();
+        assert_eq!(request.headers()["authorization"], "Bearer stale");
+
+        *session.token.lock().unwrap() = Some("fresh".to_string());
+        session.authorize(&mut request).unwrap();
+        assert_eq!(request.headers()["authorization"], "Bearer fresh");
+        assert_eq!(request.headers().get_all("authorization").iter().count(), 1);
+    }
+}
@@ -0,0 +1,595 @@
+//! # Assinatura de Requisições - Ponto de Extensão `signer`
+//!
+//! Auxiliar do `HttpExecutor`: assina a requisição já interpolada, logo
+//! antes do envio, com um assinador escolhido pelo step.
+//!
+//! ## Para todos entenderem:
+//!
+//! APIs de parceiros e gateways costumam exigir que cada requisição leve
+//! uma assinatura calculada sobre método, path, body e horário. Em vez de
+//! um executor copiado para cada esquema, o plano declara os assinadores e
+//! o step escolhe um pelo nome:
+//!
+//! ```json
+//! "config": {
+//!   "signers": {
+//!     "partner": { "type": "hmac", "secret": "${env:PARTNER_SECRET}" },
+//!     "aws":     { "type": "sigv4", "access_key": "${env:AWS_ACCESS_KEY_ID}",
+//!                  "secret_key": "${env:AWS_SECRET_ACCESS_KEY}",
+//!                  "region": "us-east-1", "service": "execute-api" }
+//!   }
+//! }
+//! ```
+//!
+//! E no step: `"params": { "method": "POST", "path": "/orders", "signer": "partner" }`.
+//! Para assinar todos os steps, coloque `signer` em um `request_templates`.
+//!
+//! ## Assinadores embutidos:
+//!
+//! | `type`   | Opções                                                      | Headers adicionados                 |
+//! |----------|-------------------------------------------------------------|-------------------------------------|
+//! | `hmac`   | `secret`, `header`, `timestamp_header`, `algorithm`, `encoding` | `X-Timestamp`, `X-Signature`    |
+//! | `sigv4`  | `access_key`, `secret_key`, `region`, `service`, `session_token` | `Authorization`, `X-Amz-Date` |
+//! | `script` | `command`, `timeout_ms`, `env`                              | Os que o script devolver            |
+//!
+//! O `hmac` assina `MÉTODO\nPATH?QUERY\nTIMESTAMP\nBODY`. O `script`
+//! recebe no stdin `{ "method", "url", "headers", "body" }` e devolve no
+//! stdout `{ "headers": { ... } }`, para esquemas proprietários. Ele roda
+//! sem o ambiente do runner: só `PATH`, `HOME`, `LANG` e `TMPDIR`, mais as
+//! variáveis de `env` (ex: `"env": { "KEY": "${env:PARTNER_KEY}" }`).
+//!
+//! ## Novos tipos:
+//!
+//! Cada tipo é uma fábrica registrada em `SignerRegistry::new`: ela recebe
+//! as opções (já interpoladas) e devolve um `RequestSigner`. O horário
+//! passado ao assinador respeita `clock_skew_ms`.
+
+use anyhow::{anyhow, Context as _, Result};
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::Request;
+use serde_json::{json, Map, Value};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+use crate::context::Context;
+use crate::protocol::SignerConfig;
+
+/// Assina uma requisição (headers, query) antes do envio.
+#[async_trait]
+pub trait RequestSigner: Send + Sync {
+    async fn sign(&self, request: &mut Request, now: DateTime<Utc>) -> Result<()>;
+}
+
+/// Cria um assinador a partir das opções de `config.signers.<nome>`.
+pub type SignerFactory =
+    Arc<dyn Fn(&Map<String, Value>) -> Result<Box<dyn RequestSigner>> + Send + Sync>;
+
+// ============================================================================
+// REGISTRO
+// ============================================================================
+
+/// Assinadores declarados no plano e os tipos conhecidos.
+pub struct SignerRegistry {
+    configs: HashMap<String, SignerConfig>,
+    factories: HashMap<String, SignerFactory>,
+}
+
+impl SignerRegistry {
+    /// Registro com os tipos embutidos (`hmac`, `sigv4`, `script`).
+    pub fn new(configs: HashMap<String, SignerConfig>) -> Self {
+        let mut registry = Self {
+            configs,
+            factories: HashMap::new(),
+        };
+        registry.register("hmac", Arc::new(|o| Ok(Box::new(HmacSigner::new(o)?) as _)));
+        registry.register(
+            "sigv4",
+            Arc::new(|o| Ok(Box::new(SigV4Signer::new(o)?) as _)),
+        );
+        registry.register(
+            "script",
+            Arc::new(|o| Ok(Box::new(ScriptSigner::new(o)?) as _)),
+        );
+        registry
+    }
+
+    /// Registra (ou substitui) um tipo de assinador.
+    pub fn register(&mut self, kind: &str, factory: SignerFactory) {
+        self.factories.insert(kind.to_string(), factory);
+    }
+
+    /// Assinador `name`, com as opções interpoladas no contexto do step.
+    pub fn build(&self, name: &str, context: &Context) -> Result<Box<dyn RequestSigner>> {
+        let config = self
+            .configs
+            .get(name)
+            .ok_or_else(|| anyhow!("Assinador '{}' não existe em config.signers", name))?;
+        let factory = self
+            .factories
+            .get(&config.kind)
+            .ok_or_else(|| anyhow!("Assinador '{}': tipo '{}' desconhecido", name, config.kind))?;
+        let options = match context.interpolate_value(&Value::Object(config.options.clone()))? {
+            Value::Object(options) => options,
+            _ => Map::new(),
+        };
+        factory(&options).with_context(|| format!("Assinador '{}' inválido", name))
+    }
+}
+
+// ============================================================================
+// HMAC
+// ============================================================================
+
+struct HmacSigner {
+    secret: String,
+    header: String,
+    timestamp_header: String,
+    sha1: bool,
+    base64: bool,
+}
+
+impl HmacSigner {
+    fn new(options: &Map<String, Value>) -> Result<Self> {
+        Ok(Self {
+            secret: required(options, "secret")?.to_string(),
+            header: optional(options, "header")
+                .unwrap_or("X-Signature")
+                .to_string(),
+            timestamp_header: optional(options, "timestamp_header")
+                .unwrap_or("X-Timestamp")
+                .to_string(),
+            sha1: match optional(options, "algorithm").unwrap_or("sha256") {
+                "sha256" => false,
+                "sha1" => true,
+                other => return Err(anyhow!("algorithm '{}' (use sha256 ou sha1)", other)),
+            },
+            base64: match optional(options, "encoding").unwrap_or("hex") {
+                "hex" => false,
+                "base64" => true,
+                other => return Err(anyhow!("encoding '{}' (use hex ou base64)", other)),
+            },
+        })
+    }
+}
+
+#[async_trait]
+impl RequestSigner for HmacSigner {
+    async fn sign(&self, request: &mut Request, now: DateTime<Utc>) -> Result<()> {
+        let timestamp = now.timestamp().to_string();
+        let mut message = format!(
+            "{}\n{}\n{}\n",
+            request.method(),
+            path_and_query(request),
+            timestamp
+        )
+        .into_bytes();
+        message.extend_from_slice(body_bytes(request)?);
+
+        let digest = if self.sha1 {
+            hmac_bytes::<Hmac<Sha1>>(self.secret.as_bytes(), &message)
+        } else {
+            hmac_bytes::<Hmac<Sha256>>(self.secret.as_bytes(), &message)
+        };
+        let signature = if self.base64 {
+            STANDARD.encode(digest)
+        } else {
+            hex(&digest)
+        };
+        set_header(request, &self.timestamp_header, &timestamp)?;
+        set_header(request, &self.header, &signature)
+    }
+}
+
+// ============================================================================
+// AWS SIGV4
+// ============================================================================
+
+struct SigV4Signer {
+    access_key: String,
+    secret_key: String,
+    region: String,
+    service: String,
+    session_token: Option<String>,
+}
+
+impl SigV4Signer {
+    fn new(options: &Map<String, Value>) -> Result<Self> {
+        Ok(Self {
+            access_key: required(options, "access_key")?.to_string(),
+            secret_key: required(options, "secret_key")?.to_string(),
+            region: required(options, "region")?.to_string(),
+            service: required(options, "service")?.to_string(),
+            session_token: optional(options, "session_token").map(str::to_string),
+        })
+    }
+}
+
+#[async_trait]
+impl RequestSigner for SigV4Signer {
+    async fn sign(&self, request: &mut Request, now: DateTime<Utc>) -> Result<()> {
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        // Bodies em streaming não podem ser lidos antes do envio.
+        let payload_hash = match request.body() {
+            Some(body) if body.as_bytes().is_none() => "UNSIGNED-PAYLOAD".to_string(),
+            _ => hex(&Sha256::digest(body_bytes(request)?)),
+        };
+
+        set_header(request, "x-amz-date", &amz_date)?;
+        if let Some(token) = &self.session_token {
+            set_header(request, "x-amz-security-token", token)?;
+        }
+        if self.service == "s3" {
+            set_header(request, "x-amz-content-sha256", &payload_hash)?;
+        }
+
+        // Headers assinados: host, content-type e todos os x-amz-*.
+        let url = request.url();
+        let host = match url.port() {
+            Some(port) => format!("{}:{}", url.host_str().unwrap_or_default(), port),
+            None => url.host_str().unwrap_or_default().to_string(),
+        };
+        let mut headers: Vec<(String, String)> = vec![("host".to_string(), host)];
+        for (name, value) in request.headers() {
+            let name = name.as_str();
+            if name == "content-type" || name.starts_with("x-amz-") {
+                headers.push((name.to_string(), value.to_str()?.trim().to_string()));
+            }
+        }
+        headers.sort();
+        let canonical_headers: String = headers
+            .iter()
+            .map(|(name, value)| format!("{}:{}\n", name, value))
+            .collect();
+        let signed_headers = headers
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>()
+            .join(";");
+
+        let mut query: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(k, v)| {
+                (
+                    urlencoding::encode(&k).into_owned(),
+                    urlencoding::encode(&v).into_owned(),
+                )
+            })
+            .collect();
+        query.sort();
+        let canonical_query = query
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let canonical_request = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            request.method(),
+            canonical_uri(&self.service, url.path()),
+            canonical_query,
+            canonical_headers,
+            signed_headers,
+            payload_hash
+        );
+        let scope = format!("{}/{}/{}/aws4_request", date, self.region, self.service);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let key = [&self.region, &self.service, "aws4_request"].iter().fold(
+            hmac_bytes::<Hmac<Sha256>>(
+                format!("AWS4{}", self.secret_key).as_bytes(),
+                date.as_bytes(),
+            ),
+            |key, part| hmac_bytes::<Hmac<Sha256>>(&key, part.as_bytes()),
+        );
+        let signature = hex(&hmac_bytes::<Hmac<Sha256>>(&key, string_to_sign.as_bytes()));
+
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key, scope, signed_headers, signature
+        );
+        set_header(request, "authorization", &authorization)
+    }
+}
+
+/// Path canônico do SigV4. A URL já traz o path codificado; fora do S3 a
+/// AWS codifica cada segmento mais uma vez (`%20` vira `%2520`).
+fn canonical_uri(service: &str, path: &str) -> String {
+    if service == "s3" {
+        return path.to_string();
+    }
+    path.split('/')
+        .map(|segment| urlencoding::encode(segment).into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// ============================================================================
+// SCRIPT
+// ============================================================================
+
+/// Variáveis do runner que o script herda; o resto vem de `env`.
+const SCRIPT_INHERITED_ENV: &[&str] = &["PATH", "HOME", "LANG", "TMPDIR"];
+
+struct ScriptSigner {
+    command: String,
+    timeout: Duration,
+    env: Vec<(String, String)>,
+}
+
+impl ScriptSigner {
+    fn new(options: &Map<String, Value>) -> Result<Self> {
+        let timeout_ms = options
+            .get("timeout_ms")
+            .and_then(|t| t.as_u64())
+            .unwrap_or(10_000);
+        let env = options
+            .get("env")
+            .and_then(|e| e.as_object())
+            .into_iter()
+            .flatten()
+            .map(|(name, value)| {
+                let value = value
+                    .as_str()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| value.to_string());
+                (name.clone(), value)
+            })
+            .collect();
+        Ok(Self {
+            command: required(options, "command")?.to_string(),
+            timeout: Duration::from_millis(timeout_ms),
+            env,
+        })
+    }
+}
+
+#[async_trait]
+impl RequestSigner for ScriptSigner {
+    async fn sign(&self, request: &mut Request, now: DateTime<Utc>) -> Result<()> {
+        let headers: Map<String, Value> = request
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+                (name.to_string(), Value::String(value))
+            })
+            .collect();
+        let input = json!({
+            "method": request.method().as_str(),
+            "url": request.url().as_str(),
+            "headers": headers,
+            "body": String::from_utf8_lossy(body_bytes(request)?),
+            "timestamp": now.timestamp(),
+        });
+
+        let mut child = tokio::process::Command::new("sh")
+            .arg("-c")
+            .arg(&self.command)
+            // Segredos do runner (tokens, chaves) não vazam para o script.
+            .env_clear()
+            .envs(
+                SCRIPT_INHERITED_ENV
+                    .iter()
+                    .filter_map(|name| Some((*name, std::env::var_os(name)?))),
+            )
+            .envs(self.env.iter().map(|(name, value)| (name, value)))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| {
+                format!("Falha ao iniciar o script de assinatura '{}'", self.command)
+            })?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // Scripts que não leem o stdin podem terminar antes da escrita.
+            match stdin.write_all(input.to_string().as_bytes()).await {
+                Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => return Err(e.into()),
+                _ => {}
+            }
+        }
+        let output = tokio::time::timeout(self.timeout, child.wait_with_output())
+            .await
+            .map_err(|_| anyhow!("Script de assinatura excedeu {:?}", self.timeout))??;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "Script de assinatura falhou ({}): {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        let reply: Value = serde_json::from_slice(&output.stdout)
+            .context("Script de assinatura deve responder JSON { \"headers\": {...} }")?;
+        for (name, value) in reply
+            .get("headers")
+            .and_then(|h| h.as_object())
+            .into_iter()
+            .flatten()
+        {
+            let value = value
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| value.to_string());
+            set_header(request, name, &value)?;
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// AUXILIARES
+// ============================================================================
+
+fn required<'a>(options: &'a Map<String, Value>, name: &str) -> Result<&'a str> {
+    optional(options, name).ok_or_else(|| anyhow!("opção '{}' obrigatória", name))
+}
+
+fn optional<'a>(options: &'a Map<String, Value>, name: &str) -> Option<&'a str> {
+    options.get(name).and_then(|v| v.as_str())
+}
+
+/// Body em memória (vazio se não houver); streaming não pode ser assinado.
+fn body_bytes(request: &Request) -> Result<&[u8]> {
+    match request.body() {
+        None => Ok(&[]),
+        Some(body) => body
+            .as_bytes()
+            .ok_or_else(|| anyhow!("body em streaming não pode ser assinado")),
+    }
+}
+
+fn path_and_query(request: &Request) -> String {
+    let url = request.url();
+    match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    }
+}
+
+fn set_header(request: &mut Request, name: &str, value: &str) -> Result<()> {
+    request.headers_mut().insert(
+        HeaderName::from_bytes(name.as_bytes())?,
+        HeaderValue::from_str(value)?,
+    );
+    Ok(())
+}
+
+fn hmac_bytes<M: Mac + hmac::digest::KeyInit>(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = <M as Mac>::new_from_slice(key).expect("HMAC aceita qualquer chave");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use reqwest::Method;
+
+    fn request(method: Method, url: &str) -> Request {
+        Request::new(method, url.parse().unwrap())
+    }
+
+    fn options(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[tokio::test]
+    async fn test_sigv4_matches_aws_get_vanilla() {
+        // Caso `get-vanilla` da suíte de testes da AWS.
+        let signer = SigV4Signer::new(&options(json!({
+            "access_key": "AKIDEXAMPLE",
+            "secret_key": "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "region": "us-east-1",
+            "service": "service"
+        })))
+        .unwrap();
+        let mut request = request(Method::GET, "https://example.amazonaws.com/");
+        let now = Utc.with_ymd_and_hms(2015, 8, 30, 12, 36, 0).unwrap();
+        signer.sign(&mut request, now).await.unwrap();
+
+        assert_eq!(request.headers()["x-amz-date"], "20150830T123600Z");
+        assert_eq!(
+            request.headers()["authorization"],
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+             SignedHeaders=host;x-amz-date, \
+             Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+        );
+    }
+
+    #[test]
+    fn test_sigv4_encodes_path_twice_except_for_s3() {
+        let url: reqwest::Url = "https://example.amazonaws.com/a b/ሴ".parse().unwrap();
+        assert_eq!(
+            canonical_uri("execute-api", url.path()),
+            "/a%2520b/%25E1%2588%25B4"
+        );
+        assert_eq!(canonical_uri("s3", url.path()), "/a%20b/%E1%88%B4");
+        assert_eq!(canonical_uri("execute-api", "/"), "/");
+    }
+
+    #[tokio::test]
+    async fn test_hmac_signs_method_path_timestamp_and_body() {
+        let signer =
+            HmacSigner::new(&options(json!({ "secret": "k", "header": "X-Sig" }))).unwrap();
+        let mut request = request(Method::POST, "http://api/orders?x=1");
+        *request.body_mut() = Some("{\"a\":1}".into());
+        let now = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        signer.sign(&mut request, now).await.unwrap();
+
+        let expected =
+            hmac_bytes::<Hmac<Sha256>>(b"k", b"POST\n/orders?x=1\n1700000000\n{\"a\":1}");
+        assert_eq!(request.headers()["x-timestamp"], "1700000000");
+        assert_eq!(request.headers()["x-sig"], hex(&expected).as_str());
+    }
+
+    #[tokio::test]
+    async fn test_registry_resolves_plugins_and_script() {
+        struct Fixed;
+        #[async_trait]
+        impl RequestSigner for Fixed {
+            async fn sign(&self, request: &mut Request, _now: DateTime<Utc>) -> Result<()> {
+                set_header(request, "x-plugin", "ok")
+            }
+        }
+
+        let configs: HashMap<String, SignerConfig> = serde_json::from_value(json!({
+            "custom": { "type": "vendor" },
+            "script": { "type": "script", "command": "echo '{\"headers\":{\"X-Script\":\"${token}\",\"X-Env\":\"'$KEY-$CARGO'\"}}'",
+                        "env": { "KEY": "k1" } },
+            "unknown": { "type": "nope" }
+        }))
+        .unwrap();
+        let mut registry = SignerRegistry::new(configs);
+        registry.register("vendor", Arc::new(|_| Ok(Box::new(Fixed) as _)));
+        let mut context = Context::new();
+        context.set("token", json!("abc"));
+
+        let mut request = request(Method::GET, "http://api/");
+        let now = Utc::now();
+        registry
+            .build("custom", &context)
+            .unwrap()
+            .sign(&mut request, now)
+            .await
+            .unwrap();
+        registry
+            .build("script", &context)
+            .unwrap()
+            .sign(&mut request, now)
+            .await
+            .unwrap();
+        assert_eq!(request.headers()["x-plugin"], "ok");
+        assert_eq!(request.headers()["x-script"], "abc");
+        // Só `env` chega ao script; CARGO (definida pelo cargo test) não.
+        assert_eq!(request.headers()["x-env"], "k1-");
+
+        assert!(registry.build("unknown", &context).is_err());
+        assert!(registry.build("missing", &context).is_err());
+    }
+}
@@ -0,0 +1,256 @@
+//! # Assertions de Streaming - Primeiro Byte, Chunks e Conteúdo
+//!
+//! Auxiliar do `HttpExecutor` para endpoints que respondem aos poucos
+//! (Server-Sent Events, NDJSON, respostas de LLM token a token).
+//!
+//! ## Para todos entenderem:
+//!
+//! Em um endpoint de streaming, o que o usuário sente é "quanto tempo até
+//! aparecer a primeira palavra", não o tempo total. O body é lido como
+//! stream (chunk a chunk) e estas assertions olham para isso:
+//!
+//! | Tipo             | Valor                                  | Operadores                    |
+//! |------------------|----------------------------------------|-------------------------------|
+//! | `first_byte_ms`  | Ms do envio até o 1º chunk do body     | `eq`, `lt`, `lte`, `gt`, `gte` |
+//! | `chunk_count`    | Número de chunks recebidos             | `eq`, `neq`, `lt`, `lte`, `gt`, `gte` |
+//! | `stream_content` | Texto agregado de todos os chunks      | `eq`, `neq`, `contains`, `matches_regex` |
+//!
+//! ## Exemplo:
+//!
+//! ```json
+//! "assertions": [
+//!   { "type": "first_byte_ms", "operator": "lt", "value": 300 },
+//!   { "type": "chunk_count", "operator": "gte", "value": 2 },
+//!   { "type": "stream_content", "operator": "contains", "value": "data: [DONE]" }
+//! ]
+//! ```
+//!
+//! "Chunk" é cada pedaço entregue pela conexão (frame do HTTP/2 ou chunk
+//! do `Transfer-Encoding: chunked`); proxies podem juntar pedaços.
+
+use futures::StreamExt;
+use regex::Regex;
+use std::time::Instant;
+
+use crate::protocol::Assertion;
+
+/// Estatísticas da leitura do body.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StreamStats {
+    /// Ms do envio da requisição até o primeiro chunk (None: body vazio).
+    pub first_byte_ms: Option<u64>,
+    /// Chunks recebidos.
+    pub chunk_count: u32,
+    /// A leitura foi interrompida pelo timeout da requisição.
+    pub timed_out: bool,
+}
+
+/// Lê o body chunk a chunk, medindo o primeiro byte a partir de `send_start`.
+///
+/// Um erro no meio do stream encerra a leitura com o que já chegou e volta
+/// no terceiro item (o body está incompleto); se o erro foi o timeout,
+/// `timed_out` também fica marcado.
+pub async fn read_body(
+    response: reqwest::Response,
+    send_start: Instant,
+) -> (Vec<u8>, StreamStats, Option<reqwest::Error>) {
+    let mut body = Vec::new();
+    let mut stats = StreamStats::default();
+    let mut chunks = response.bytes_stream();
+    while let Some(next) = chunks.next().await {
+        let chunk = match next {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                stats.timed_out = e.is_timeout();
+                return (body, stats, Some(e));
+            }
+        };
+        if chunk.is_empty() {
+            continue;
+        }
+        stats
+            .first_byte_ms
+            .get_or_insert(send_start.elapsed().as_millis() as u64);
+        stats.chunk_count += 1;
+        body.extend_from_slice(&chunk);
+    }
+    (body, stats, None)
+}
+
+// ============================================================================
+// DESPACHO
+// ============================================================================
+
+/// Retorna true se `assertion_type` é uma assertion de streaming.
+pub fn is_stream_assertion(assertion_type: &str) -> bool {
+    matches!(
+        assertion_type,
+        "first_byte_ms" | "chunk_count" | "stream_content"
+    )
+}
+
+/// Valida uma assertion de streaming. Retorna a mensagem de erro se falhar.
+pub fn check_stream_assertion(
+    assertion: &Assertion,
+    stats: &StreamStats,
+    body: &[u8],
+) -> Option<String> {
+    match assertion.assertion_type.as_str() {
+        "first_byte_ms" => match stats.first_byte_ms {
+            Some(ms) => check_number(assertion, ms, "ms"),
+            None => Some("Assertion failed: first_byte_ms (no body received)".to_string()),
+        },
+        "chunk_count" => check_number(assertion, stats.chunk_count as u64, "chunks"),
+        "stream_content" => check_content(assertion, &String::from_utf8_lossy(body)),
+        other => Some(format!(
+            "Assertion failed: unknown stream assertion '{}'",
+            other
+        )),
+    }
+}
+
+// ============================================================================
+// VERIFICAÇÕES
+// ============================================================================
+
+fn check_number(assertion: &Assertion, actual: u64, unit: &str) -> Option<String> {
+    let name = &assertion.assertion_type;
+    let Some(expected) = assertion.value.as_u64() else {
+        return Some(format!(
+            "Assertion failed: {} value must be a non-negative integer",
+            name
+        ));
+    };
+
+    let passed = match assertion.operator.as_str() {
+        "eq" => actual == expected,
+        "neq" => actual != expected,
+        "lt" => actual < expected,
+        "lte" | "le" => actual <= expected,
+        "gt" => actual > expected,
+        "gte" | "ge" => actual >= expected,
+        other => return Some(unsupported(name, other)),
+    };
+
+    (!passed).then(|| {
+        format!(
+            "Assertion failed: {} {} {} {} (got {} {})",
+            name, assertion.operator, expected, unit, actual, unit
+        )
+    })
+}
+
+fn check_content(assertion: &Assertion, content: &str) -> Option<String> {
+    let expected = assertion.value.as_str().unwrap_or("");
+    let passed = match assertion.operator.as_str() {
+        "eq" => content == expected,
+        "neq" => content != expected,
+        "contains" => content.contains(expected),
+        "matches_regex" => match Regex::new(expected) {
+            Ok(re) => re.is_match(content),
+            Err(e) => {
+                return Some(format!(
+                    "Assertion failed: invalid regex '{}': {}",
+                    expected, e
+                ))
+            }
+        },
+        other => return Some(unsupported("stream_content", other)),
+    };
+
+    (!passed).then(|| {
+        format!(
+            "Assertion failed: stream_content {} {:?} ({} bytes streamed)",
+            assertion.operator,
+            expected,
+            content.len()
+        )
+    })
+}
+
+fn unsupported(assertion_type: &str, operator: &str) -> String {
+    format!(
+        "Assertion failed: operator '{}' is not supported for {}",
+        operator, assertion_type
+    )
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn assertion(assertion_type: &str, operator: &str, value: serde_json::Value) -> Assertion {
+        Assertion {
+            assertion_type: assertion_type.to_string(),
+            operator: operator.to_string(),
+            value,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_first_byte_and_chunk_count() {
+        let stats = StreamStats {
+            first_byte_ms: Some(120),
+            chunk_count: 5,
+            ..Default::default()
+        };
+        let body = b"";
+        assert!(check_stream_assertion(
+            &assertion("first_byte_ms", "lt", json!(300)),
+            &stats,
+            body
+        )
+        .is_none());
+        assert!(
+            check_stream_assertion(&assertion("chunk_count", "gte", json!(2)), &stats, body)
+                .is_none()
+        );
+
+        let error =
+            check_stream_assertion(&assertion("first_byte_ms", "lt", json!(100)), &stats, body)
+                .unwrap();
+        assert!(error.contains("got 120 ms"), "{}", error);
+
+        let empty = StreamStats::default();
+        assert!(check_stream_assertion(
+            &assertion("first_byte_ms", "lt", json!(100)),
+            &empty,
+            body
+        )
+        .is_some());
+    }
+
+    #[test]
+    fn test_stream_content_aggregated() {
+        let stats = StreamStats::default();
+        let body = b"data: {\"token\":\"Ol\"}\n\ndata: {\"token\":\"a\"}\n\ndata: [DONE]\n\n";
+        assert!(check_stream_assertion(
+            &assertion("stream_content", "contains", json!("data: [DONE]")),
+            &stats,
+            body
+        )
+        .is_none());
+        assert!(check_stream_assertion(
+            &assertion(
+                "stream_content",
+                "matches_regex",
+                json!("^(data: .+\\n\\n)+$")
+            ),
+            &stats,
+            body
+        )
+        .is_none());
+        assert!(check_stream_assertion(
+            &assertion("stream_content", "lt", json!("x")),
+            &stats,
+            body
+        )
+        .is_some());
+    }
+}
@@ -0,0 +1,136 @@
+//! # Captura no Timeout - O que Chegou Antes de Estourar
+//!
+//! Auxiliar do `HttpExecutor`: quando o `timeout_ms` do step dispara,
+//! registra em `StepResult.timeout_capture` o que já tinha sido observado.
+//!
+//! ## Para todos entenderem:
+//!
+//! "operation timed out" não diz se o upstream nem aceitou a conexão, se
+//! demorou para responder ou se mandou metade do body e travou. São três
+//! problemas diferentes:
+//!
+//! | `phase`               | O que aconteceu                           | Pista                       |
+//! |-----------------------|-------------------------------------------|-----------------------------|
+//! | `connect`             | Conexão não abriu a tempo                 | Rede, firewall, DNS         |
+//! | `waiting_for_headers` | Conectou, mas a resposta não começou      | Servidor lento (TTFB)       |
+//! | `reading_body`        | Status e headers chegaram, body incompleto | Stream travado, payload enorme |
+//!
+//! Em `reading_body`, o status, os headers e os bytes já recebidos vão
+//! para o relatório. Headers com credenciais são mascarados.
+
+use reqwest::header::HeaderMap;
+use std::fmt;
+
+use super::http_headers::Redaction;
+use super::http_stream::StreamStats;
+use crate::protocol::TimeoutCapture;
+
+/// Body interrompido pelo timeout depois de o status e os headers chegarem.
+#[derive(Debug)]
+pub struct BodyTimeout {
+    pub status: u16,
+    pub headers: HeaderMap,
+    pub bytes_received: usize,
+    pub stream: StreamStats,
+}
+
+impl fmt::Display for BodyTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "timed out reading body (status {}, {} bytes received)",
+            self.status, self.bytes_received
+        )
+    }
+}
+
+impl std::error::Error for BodyTimeout {}
+
+/// Monta a captura se `error` foi um timeout (`None` para outros erros).
+pub fn capture(
+    error: &anyhow::Error,
+    timeout_ms: u64,
+    elapsed_ms: u64,
+    redaction: &Redaction,
+) -> Option<TimeoutCapture> {
+    if let Some(body) = error.downcast_ref::<BodyTimeout>() {
+        return Some(TimeoutCapture {
+            phase: "reading_body".to_string(),
+            timeout_ms,
+            elapsed_ms,
+            bytes_received: body.bytes_received as u64,
+            status_code: Some(body.status),
+            response_headers: Some(redaction.headers(&body.headers)),
+            first_byte_ms: body.stream.first_byte_ms,
+        });
+    }
+
+    let request_error = error
+        .chain()
+        .find_map(|e| e.downcast_ref::<reqwest::Error>())
+        .filter(|e| e.is_timeout())?;
+    let phase = if request_error.is_connect() {
+        "connect"
+    } else {
+        "waiting_for_headers"
+    };
+    Some(TimeoutCapture {
+        phase: phase.to_string(),
+        timeout_ms,
+        elapsed_ms,
+        bytes_received: 0,
+        status_code: None,
+        response_headers: None,
+        first_byte_ms: None,
+    })
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_body_timeout_capture_masks_credentials() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "content-type",
+            HeaderValue::from_static("application/x-ndjson"),
+        );
+        headers.insert("set-cookie", HeaderValue::from_static("session=abc"));
+        let error = anyhow::Error::new(BodyTimeout {
+            status: 200,
+            headers,
+            bytes_received: 1532,
+            stream: StreamStats {
+                first_byte_ms: Some(40),
+                chunk_count: 3,
+                timed_out: true,
+            },
+        });
+
+        let capture = capture(&error, 1000, 1003, &Redaction::default()).unwrap();
+        assert_eq!(capture.phase, "reading_body");
+        assert_eq!(capture.bytes_received, 1532);
+        assert_eq!(capture.status_code, Some(200));
+        assert_eq!(capture.first_byte_ms, Some(40));
+        let headers = capture.response_headers.unwrap();
+        assert_eq!(headers["content-type"], "application/x-ndjson");
+        assert_eq!(headers["set-cookie"], "***");
+    }
+
+    #[test]
+    fn test_other_errors_have_no_capture() {
+        assert!(capture(
+            &anyhow::anyhow!("connection refused"),
+            1000,
+            5,
+            &Redaction::default()
+        )
+        .is_none());
+    }
+}
@@ -0,0 +1,187 @@
+//! # Medição de Tempos HTTP - DNS, TTFB e Transferência
+//!
+//! Auxiliar do `HttpExecutor` que decompõe o tempo de uma requisição em
+//! fases, para que um step lento indique **onde** o tempo foi gasto.
+//!
+//! ## Para todos entenderem:
+//!
+//! "A requisição levou 2 segundos" não diz se o problema é a rede ou o
+//! servidor. Separando as fases:
+//!
+//! | Fase          | O que mede                                          |
+//! |---------------|-----------------------------------------------------|
+//! | `dns_ms`      | Resolução do nome do host (só em conexões novas)    |
+//! | `ttfb_ms`     | Envio até o primeiro byte (headers) da resposta     |
+//! | `transfer_ms` | Leitura do body da resposta                         |
+//!
+//! Um `ttfb_ms` alto com `dns_ms` baixo aponta para o servidor; um
+//! `transfer_ms` alto aponta para payload grande ou rede lenta.
+//!
+//! ## Limitações:
+//!
+//! O reqwest 0.11 não expõe ganchos para connect e handshake TLS; esses
+//! tempos ficam embutidos no `ttfb_ms` de conexões novas. O DNS é medido
+//! por um resolver próprio, registrado na task da requisição: quando a
+//! conexão vem do pool, não há resolução e `dns_ms` fica ausente.
+//!
+//! O mesmo resolver aplica `config.ip_preference`: com `v4` ou `v6`, só os
+//! endereços daquela família chegam ao conector. O endereço efetivamente
+//! usado aparece em `http_details.peer_addr`.
+
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use std::io;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+// Tipo do nome a resolver (não reexportado pelo reqwest 0.11).
+use hyper::client::connect::dns::Name;
+
+use crate::protocol::IpPreference;
+
+// ============================================================================
+// SONDA DE DNS
+// ============================================================================
+
+/// Valor sentinela: nenhuma resolução registrada.
+const NOT_MEASURED: u64 = u64::MAX;
+
+/// Registro do tempo de DNS de uma requisição.
+///
+/// Compartilhado entre a requisição e o resolver via task-local.
+#[derive(Debug)]
+pub struct DnsProbe {
+    dns_ms: AtomicU64,
+}
+
+impl Default for DnsProbe {
+    fn default() -> Self {
+        Self {
+            dns_ms: AtomicU64::new(NOT_MEASURED),
+        }
+    }
+}
+
+impl DnsProbe {
+    /// Tempo de DNS medido, se houve resolução durante a requisição.
+    pub fn dns_ms(&self) -> Option<u64> {
+        match self.dns_ms.load(Ordering::SeqCst) {
+            NOT_MEASURED => None,
+            ms => Some(ms),
+        }
+    }
+
+    fn record(&self, ms: u64) {
+        self.dns_ms.store(ms, Ordering::SeqCst);
+    }
+}
+
+tokio::task_local! {
+    /// Sonda da requisição em andamento (definida com `DNS_PROBE.scope`).
+    pub static DNS_PROBE: Arc<DnsProbe>;
+}
+
+// ============================================================================
+// RESOLVER COM MEDIÇÃO
+// ============================================================================
+
+/// Resolver DNS que registra a duração da resolução na sonda da task.
+///
+/// Usa o resolver do sistema (`tokio::net::lookup_host`), equivalente
+/// ao padrão do reqwest, e filtra os endereços por `config.ip_preference`.
+#[derive(Debug, Default)]
+pub struct TimingResolver {
+    preference: IpPreference,
+}
+
+impl TimingResolver {
+    pub fn new(preference: IpPreference) -> Self {
+        Self { preference }
+    }
+}
+
+impl Resolve for TimingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        // Captura a sonda agora: `resolve` roda dentro da task da requisição.
+        let probe = DNS_PROBE.try_with(Arc::clone).ok();
+        let host = name.as_str().to_string();
+        let preference = self.preference;
+
+        Box::pin(async move {
+            let start = Instant::now();
+            let addrs: Vec<_> = tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+
+            if let Some(probe) = probe {
+                probe.record(start.elapsed().as_millis() as u64);
+            }
+
+            let addrs = filter_family(addrs, preference);
+            if addrs.is_empty() {
+                return Err(Box::new(io::Error::new(
+                    io::ErrorKind::AddrNotAvailable,
+                    format!(
+                        "host '{}' sem endereço {:?} (ip_preference)",
+                        host, preference
+                    ),
+                ))
+                    as Box<dyn std::error::Error + Send + Sync>);
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Mantém só os endereços da família pedida (`auto` mantém todos).
+fn filter_family(addrs: Vec<SocketAddr>, preference: IpPreference) -> Vec<SocketAddr> {
+    match preference {
+        IpPreference::Auto => addrs,
+        IpPreference::V4 => addrs.into_iter().filter(SocketAddr::is_ipv4).collect(),
+        IpPreference::V6 => addrs.into_iter().filter(SocketAddr::is_ipv6).collect(),
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_resolver_records_dns_inside_scope() {
+        let probe = Arc::new(DnsProbe::default());
+        assert_eq!(probe.dns_ms(), None);
+
+        let name: Name = "localhost".parse().unwrap();
+        let addrs = DNS_PROBE
+            .scope(Arc::clone(&probe), async {
+                // `resolve` precisa ser chamado dentro do escopo da sonda.
+                TimingResolver::default().resolve(name).await
+            })
+            .await
+            .unwrap();
+
+        assert!(addrs.count() > 0);
+        assert!(probe.dns_ms().is_some());
+    }
+
+    #[test]
+    fn test_ip_preference_filters_family() {
+        let addrs: Vec<SocketAddr> =
+            vec!["[::1]:0".parse().unwrap(), "127.0.0.1:0".parse().unwrap()];
+        assert_eq!(filter_family(addrs.clone(), IpPreference::Auto).len(), 2);
+        assert_eq!(
+            filter_family(addrs.clone(), IpPreference::V4),
+            vec!["127.0.0.1:0".parse::<SocketAddr>().unwrap()]
+        );
+        assert!(filter_family(addrs, IpPreference::V6)[0].is_ipv6());
+    }
+
+    #[tokio::test]
+    async fn test_resolver_works_outside_scope() {
+        let name: Name = "localhost".parse().unwrap();
+        assert!(TimingResolver::default().resolve(name).await.is_ok());
+    }
+}
@@ -0,0 +1,222 @@
+//! # Unix Domain Socket - Serviços Expostos Só Localmente
+//!
+//! Auxiliar do `HttpExecutor`: envia a requisição HTTP por um socket Unix
+//! em vez de TCP.
+//!
+//! ## Para todos entenderem:
+//!
+//! Sidecars e daemons (Docker, Envoy admin, agentes de métricas) costumam
+//! escutar só em `/var/run/*.sock`, sem porta TCP. O plano aponta para o
+//! socket e os paths continuam iguais:
+//!
+//! ```json
+//! "config": { "base_url": "unix:///var/run/app.sock" }
+//! ```
+//!
+//! | Onde                            | Exemplo                                  |
+//! |---------------------------------|------------------------------------------|
+//! | `config.base_url`               | `"unix:///var/run/app.sock"`             |
+//! | `params.unix_socket` (por step) | `"/var/run/docker.sock"` (sobrescreve)   |
+//!
+//! A requisição sai com `Host: localhost` (ou o host de um `path` com URL
+//! completa) e aparece no relatório como `unix:///var/run/app.sock:/health`.
+//!
+//! ## Limitações:
+//!
+//! - Uma conexão por requisição (sem pool; `connection_reused` ausente).
+//! - Não combina com `session`/`actor` nem com `auth` (cliente próprio).
+//! - `body_file` com `body_file_interpolate: false` (streaming) não é
+//!   suportado; o body precisa estar em memória.
+
+use anyhow::{anyhow, Result};
+use reqwest::header::HOST;
+use reqwest::Url;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+use super::http_version::HttpVersion;
+use crate::context::Context;
+
+/// Prefixo da `base_url` que aponta para um socket.
+const UNIX_SCHEME: &str = "unix://";
+
+/// Socket Unix de destino de um step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnixSocket {
+    pub path: PathBuf,
+}
+
+impl UnixSocket {
+    /// Socket do step: `params.unix_socket` ou `base_url` com `unix://`.
+    ///
+    /// Um `path` com URL completa ignora a `base_url` (mas não `unix_socket`).
+    pub fn from_target(params: &Value, context: &Context, path: &str) -> Result<Option<Self>> {
+        if let Some(socket) = params.get("unix_socket").and_then(|s| s.as_str()) {
+            return Ok(Some(Self {
+                path: PathBuf::from(context.interpolate_str(socket)?),
+            }));
+        }
+        if path.starts_with("http") {
+            return Ok(None);
+        }
+        let base = context
+            .get("base_url")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        Ok(base.strip_prefix(UNIX_SCHEME).map(|socket| Self {
+            path: PathBuf::from(socket.trim_end_matches('/')),
+        }))
+    }
+
+    /// URL HTTP da requisição (host `localhost` se o path for relativo).
+    pub fn request_url(path: &str) -> String {
+        if path.starts_with("http") {
+            path.to_string()
+        } else {
+            format!("http://localhost{}", path)
+        }
+    }
+
+    /// URL do relatório: `unix:///var/run/app.sock:/health?x=1`.
+    pub fn display_url(&self, request_url: &str) -> String {
+        let target = Url::parse(request_url)
+            .map(|url| origin_form(&url))
+            .unwrap_or_else(|_| request_url.to_string());
+        format!("{}{}:{}", UNIX_SCHEME, self.path.display(), target)
+    }
+
+    /// Envia a requisição pelo socket (HTTP/2 com `http_version: "2"`).
+    pub async fn send(
+        &self,
+        request: reqwest::Request,
+        version: Option<HttpVersion>,
+    ) -> Result<reqwest::Response> {
+        let http2 = version == Some(HttpVersion::Http2);
+        let body = match request.body() {
+            None => hyper::Body::empty(),
+            Some(body) => hyper::Body::from(
+                body.as_bytes()
+                    .ok_or_else(|| {
+                        anyhow!("body_file em streaming não é suportado com unix socket")
+                    })?
+                    .to_vec(),
+            ),
+        };
+
+        // HTTP/2 exige a URL completa (:authority); HTTP/1.1 usa só o path.
+        let uri = if http2 {
+            request.url().to_string()
+        } else {
+            origin_form(request.url())
+        };
+        let mut builder = hyper::Request::builder()
+            .method(request.method().clone())
+            .uri(uri);
+        for (name, value) in request.headers() {
+            builder = builder.header(name, value);
+        }
+        if !http2 && !request.headers().contains_key(HOST) {
+            builder = builder.header(HOST, host_header(request.url()));
+        }
+        let hyper_request = builder.body(body)?;
+
+        let stream = connect(&self.path).await.map_err(|e| {
+            anyhow!(
+                "Falha ao conectar no socket '{}': {}",
+                self.path.display(),
+                e
+            )
+        })?;
+        let (mut sender, connection) = hyper::client::conn::Builder::new()
+            .http2_only(http2)
+            .handshake(stream)
+            .await?;
+        tokio::spawn(async move {
+            let _ = connection.await;
+        });
+
+        let send = sender.send_request(hyper_request);
+        let response = match request.timeout() {
+            Some(timeout) => tokio::time::timeout(*timeout, send).await.map_err(|_| {
+                anyhow!(
+                    "Timeout de {}ms aguardando a resposta do socket",
+                    timeout.as_millis()
+                )
+            })??,
+            None => send.await?,
+        };
+        Ok(reqwest::Response::from(response))
+    }
+}
+
+#[cfg(unix)]
+async fn connect(path: &Path) -> std::io::Result<tokio::net::UnixStream> {
+    tokio::net::UnixStream::connect(path).await
+}
+
+/// Fora de Unix não há socket local: o step falha com erro de conexão.
+#[cfg(not(unix))]
+async fn connect(_path: &Path) -> std::io::Result<tokio::io::DuplexStream> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "unix sockets não são suportados nesta plataforma",
+    ))
+}
+
+/// Path + query da URL (`/health?x=1`).
+fn origin_form(url: &Url) -> String {
+    match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    }
+}
+
+fn host_header(url: &Url) -> String {
+    let host = url.host_str().unwrap_or("localhost");
+    match url.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_socket_from_base_url_or_params() {
+        let mut context = Context::new();
+        context.set("base_url", json!("unix:///var/run/app.sock"));
+        context.set("sock", json!("/tmp/other.sock"));
+
+        let socket = UnixSocket::from_target(&json!({}), &context, "/health")
+            .unwrap()
+            .unwrap();
+        assert_eq!(socket.path, PathBuf::from("/var/run/app.sock"));
+        assert_eq!(
+            socket.display_url(&UnixSocket::request_url("/health?x=1")),
+            "unix:///var/run/app.sock:/health?x=1"
+        );
+
+        let params = json!({ "unix_socket": "${sock}" });
+        let socket = UnixSocket::from_target(&params, &context, "/health")
+            .unwrap()
+            .unwrap();
+        assert_eq!(socket.path, PathBuf::from("/tmp/other.sock"));
+
+        assert!(
+            UnixSocket::from_target(&json!({}), &context, "http://api/x")
+                .unwrap()
+                .is_none()
+        );
+        context.set("base_url", json!("http://localhost:8080"));
+        assert!(UnixSocket::from_target(&json!({}), &context, "/health")
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,113 @@
+//! # Versão do Protocolo - HTTP/1.1 ou HTTP/2 por Step
+//!
+//! Auxiliar do `HttpExecutor` para `params.http_version`: escolhe o
+//! protocolo de cada requisição e informa o que foi de fato negociado.
+//!
+//! ## Para todos entenderem:
+//!
+//! Sem configuração, o cliente fala HTTP/1.1 em `http://` e deixa o TLS
+//! (ALPN) decidir em `https://`. Alguns bugs só aparecem em um dos dois
+//! (multiplexação, headers em minúsculas, `Connection: close`). O step
+//! pode fixar o protocolo:
+//!
+//! | `http_version` | Cliente                                   | Em `http://`        |
+//! |----------------|-------------------------------------------|---------------------|
+//! | ausente        | Padrão (ALPN em HTTPS)                    | HTTP/1.1            |
+//! | `"1.1"`        | Só HTTP/1.1                               | HTTP/1.1            |
+//! | `"2"`          | HTTP/2 com prior knowledge (sem upgrade)  | HTTP/2 (h2c)        |
+//!
+//! O protocolo usado vai para `http_details.http_version` (`HTTP/1.1`,
+//! `HTTP/2`). Com `"2"`, um servidor que não fala HTTP/2 faz o step falhar
+//! com erro de conexão, que é justamente o que se quer verificar.
+//!
+//! `http_version` não pode ser combinado com `session`/`actor`: esses steps
+//! usam o cliente (e os cookies) da sessão.
+
+use anyhow::{anyhow, Result};
+use reqwest::{Client, ClientBuilder, Version};
+use serde_json::Value;
+
+/// Protocolo pedido em `params.http_version`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HttpVersion {
+    Http1,
+    Http2,
+}
+
+impl HttpVersion {
+    /// Lê `params.http_version` (`None` se ausente).
+    pub fn from_params(params: &Value) -> Result<Option<Self>> {
+        let Some(value) = params.get("http_version") else {
+            return Ok(None);
+        };
+        match value.as_str().unwrap_or_default() {
+            "1.1" => Ok(Some(Self::Http1)),
+            "2" => Ok(Some(Self::Http2)),
+            _ => Err(anyhow!(
+                "http_version inválido: {} (use \"1.1\" ou \"2\")",
+                value
+            )),
+        }
+    }
+}
+
+/// Clientes com o protocolo fixado (mesmas opções de `config.http`).
+pub struct VersionClients {
+    http1: Client,
+    http2: Client,
+}
+
+impl VersionClients {
+    /// Cria os dois clientes a partir do builder base do executor.
+    pub fn new(builder: impl Fn() -> ClientBuilder) -> Result<Self> {
+        Ok(Self {
+            http1: builder().http1_only().build()?,
+            http2: builder().http2_prior_knowledge().build()?,
+        })
+    }
+
+    pub fn client(&self, version: HttpVersion) -> &Client {
+        match version {
+            HttpVersion::Http1 => &self.http1,
+            HttpVersion::Http2 => &self.http2,
+        }
+    }
+}
+
+/// Nome do protocolo negociado (`HTTP/1.1`, `HTTP/2`).
+pub fn negotiated(version: Version) -> String {
+    match version {
+        Version::HTTP_09 => "HTTP/0.9",
+        Version::HTTP_10 => "HTTP/1.0",
+        Version::HTTP_11 => "HTTP/1.1",
+        Version::HTTP_2 => "HTTP/2",
+        Version::HTTP_3 => "HTTP/3",
+        _ => "unknown",
+    }
+    .to_string()
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_http_version() {
+        assert_eq!(HttpVersion::from_params(&json!({})).unwrap(), None);
+        assert_eq!(
+            HttpVersion::from_params(&json!({ "http_version": "2" })).unwrap(),
+            Some(HttpVersion::Http2)
+        );
+        assert_eq!(
+            HttpVersion::from_params(&json!({ "http_version": "1.1" })).unwrap(),
+            Some(HttpVersion::Http1)
+        );
+        assert!(HttpVersion::from_params(&json!({ "http_version": 2 })).is_err());
+        assert_eq!(negotiated(Version::HTTP_2), "HTTP/2");
+    }
+}
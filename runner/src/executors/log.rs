@@ -0,0 +1,156 @@
+//! # Executor Log - Mensagens no Relatório
+//!
+//! Este executor emite uma mensagem interpolada no log do Runner e a
+//! registra no resultado do step, para que apareça no relatório.
+//!
+//! ## Para todos entenderem:
+//!
+//! É como um "print" dentro do plano: serve para documentar o que está
+//! acontecendo e mostrar valores do contexto sem fazer nenhuma chamada.
+//!
+//! ## Exemplo de uso no UTDL:
+//!
+//! ```json
+//! {
+//!   "id": "log_pedido",
+//!   "action": "log",
+//!   "params": {
+//!     "message": "Pedido criado: ${order_id}",
+//!     "level": "info"
+//!   }
+//! }
+//! ```
+//!
+//! O campo `level` é opcional (`info` por padrão; aceita `debug`, `warn`).
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::time::Instant;
+use tracing::{debug, info, instrument, warn};
+
+use crate::context::Context;
+use crate::protocol::{Step, StepResult, StepStatus};
+
+use super::StepExecutor;
+
+// ============================================================================
+// LOG EXECUTOR
+// ============================================================================
+
+/// Executor para a ação `log`.
+///
+/// Não altera o contexto; apenas lê variáveis para interpolação.
+#[derive(Debug, Default)]
+pub struct LogExecutor;
+
+impl LogExecutor {
+    /// Cria um novo LogExecutor.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// JSON Schema dos params de `log` (ver `StepExecutor::params_schema`).
+pub fn params_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["message"],
+        "properties": {
+            "message": { "type": "string" },
+            "level": { "type": "string" }
+        },
+        "additionalProperties": false
+    })
+}
+
+// ============================================================================
+// IMPLEMENTAÇÃO DO TRAIT
+// ============================================================================
+
+#[async_trait]
+impl StepExecutor for LogExecutor {
+    fn can_handle(&self, action: &str) -> bool {
+        action == "log"
+    }
+
+    /// Schema dos params de `log`, usado na validação do plano.
+    fn params_schema(&self, _action: &str) -> Option<Value> {
+        Some(params_schema())
+    }
+
+    #[instrument(skip(self, context), fields(step_id = %step.id))]
+    async fn execute(&self, step: &Step, context: &mut Context) -> Result<StepResult> {
+        let start = Instant::now();
+
+        let template = step
+            .params
+            .get("message")
+            .and_then(|m| m.as_str())
+            .ok_or_else(|| {
+                anyhow!("Parâmetros incompletos para log: forneça 'message' (string)")
+            })?;
+
+        let message = context.interpolate_str(template)?;
+
+        match step.params.get("level").and_then(|l| l.as_str()) {
+            Some("debug") => debug!(step_id = %step.id, "{}", message),
+            Some("warn") | Some("warning") => warn!(step_id = %step.id, "{}", message),
+            _ => info!(step_id = %step.id, "💬 {}", message),
+        }
+
+        Ok(StepResult {
+            step_id: step.id.clone(),
+            status: StepStatus::Passed,
+            duration_ms: start.elapsed().as_millis() as u64,
+            logs: Some(vec![message]),
+            ..Default::default()
+        })
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn create_step(params: serde_json::Value) -> Step {
+        Step {
+            id: "log_step".to_string(),
+            description: None,
+            depends_on: vec![],
+            action: "log".to_string(),
+            params,
+            assertions: vec![],
+            extract: vec![],
+            recovery_policy: None,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_log_interpolates_message() {
+        let executor = LogExecutor::new();
+        let mut context = Context::new();
+        context.set("order_id", json!(42));
+
+        let step = create_step(json!({ "message": "Pedido criado: ${order_id}" }));
+        let result = executor.execute(&step, &mut context).await.unwrap();
+
+        assert_eq!(result.status, StepStatus::Passed);
+        assert_eq!(result.logs, Some(vec!["Pedido criado: 42".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn test_log_requires_message() {
+        let executor = LogExecutor::new();
+        let mut context = Context::new();
+
+        let step = create_step(json!({}));
+        assert!(executor.execute(&step, &mut context).await.is_err());
+    }
+}
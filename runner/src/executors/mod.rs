@@ -33,25 +33,127 @@
 //! 2. Implemente a struct com `StepExecutor` trait
 //! 3. Adicione `pub mod browser;` aqui
 //! 4. Registre no `main.rs` na lista de executores
+//! 5. Implemente `StepExecutor::params_schema` e registre em `builtin` (validação)
 //!
 //! ## Submódulos:
 //! - `http`: Requisições HTTP com suporte a assertions e extractions
 //! - `wait`: Delays/pausas na execução
+//! - `set_variable`: Atribuição de variáveis no contexto
+//! - `log`: Mensagens interpoladas no relatório
+//! - `assert`: Assertions sobre variáveis do contexto (sem requisição)
+//! - `transform`: Remodelagem de variáveis (JSONPath + operações)
+//! - `tcp`: Bytes crus por socket TCP (protocolos não-HTTP)
+//! - `udp`: Datagramas UDP (health probes de DNS, syslog, jogos)
+//! - `grpc_health`: Health check gRPC padrão (`grpc.health.v1`)
+//! - `shell`: Comandos locais (seed, CLIs) com exit code e saída
+//! - `rate_limit_probe`: Rajada num ritmo fixo para testar o throttling da API
+//! - `webhook`: Espera callbacks de entrada (webhooks) num listener local
 
 /// Submódulo para execução de requisições HTTP.
 pub mod http;
 
+/// Submódulo auxiliar do HTTP: autenticação por desafio (Digest, NTLM).
+pub mod http_auth;
+
+/// Submódulo auxiliar do HTTP: assertions sobre respostas binárias.
+pub mod http_binary;
+
+/// Submódulo auxiliar do HTTP: body lido de arquivo (`body_file`).
+pub mod http_body_file;
+
+/// Submódulo auxiliar do HTTP: cache de respostas GET/HEAD por execução.
+pub mod http_cache;
+
+/// Submódulo auxiliar do HTTP: reuso de conexões do pool por host.
+pub mod http_connections;
+
+/// Submódulo auxiliar do HTTP: trecho do body nas falhas de `json_body`.
+pub mod http_excerpt;
+
+/// Submódulo auxiliar do HTTP: headers de resposta no relatório (mascarados, com limite).
+pub mod http_headers;
+
+/// Submódulo auxiliar do HTTP: interceptores por step e proxy (`interceptors`, `proxy`).
+pub mod http_interceptor;
+
+/// Submódulo auxiliar do HTTP: descompactação e tamanhos (`Content-Encoding`).
+pub mod http_compression;
+
+/// Submódulo auxiliar do HTTP: requisição condicional da assertion `cache_behavior`.
+pub mod http_revalidation;
+
+/// Submódulo auxiliar do HTTP: sessões nomeadas (cookies, pool e token por usuário).
+pub mod http_session;
+
+/// Submódulo auxiliar do HTTP: assinatura de requisições (`signer`).
+pub mod http_signer;
+
+/// Submódulo auxiliar do HTTP: leitura em stream e assertions de streaming.
+pub mod http_stream;
+
+/// Submódulo auxiliar do HTTP: medição de DNS, TTFB e transferência.
+pub mod http_timing;
+
+/// Submódulo auxiliar do HTTP: o que foi observado até o timeout do step.
+pub mod http_timeout;
+
+/// Submódulo auxiliar do HTTP: requisições por unix domain socket.
+pub mod http_unix;
+
+/// Submódulo auxiliar do HTTP: HTTP/1.1 ou HTTP/2 por step (`http_version`).
+pub mod http_version;
+
 /// Submódulo para delays/pausas (wait e sleep).
 pub mod wait;
 
 /// Submódulo para requisições GraphQL (plugin de exemplo).
 pub mod graphql;
 
+/// Submódulo auxiliar de GraphQL: assertions `graphql_errors` (HTTP e GraphQL).
+pub mod graphql_errors;
+
+/// Submódulo para atribuição de variáveis (set_variable).
+pub mod set_variable;
+
+/// Submódulo para mensagens no relatório (log).
+pub mod log;
+
+/// Submódulo para assertions sobre o contexto (assert).
+pub mod assert;
+
+/// Submódulo para remodelagem de variáveis (transform).
+pub mod transform;
+
+/// Submódulo para troca de bytes crus por TCP (tcp_send).
+pub mod tcp;
+
+/// Submódulo para datagramas UDP (udp_send).
+pub mod udp;
+
+/// Submódulo para o health check padrão de gRPC (grpc_health).
+pub mod grpc_health;
+
+/// Submódulo para chamadas gRPC unárias com mensagens dinâmicas (grpc_call).
+pub mod grpc;
+
+/// Submódulo para comandos locais (shell_command).
+pub mod shell;
+
+/// Submódulo para verificar a política de rate limit (rate_limit_probe).
+pub mod rate_limit_probe;
+
+/// Submódulo para esperar callbacks de entrada (webhook_wait).
+pub mod webhook;
+
+/// Submódulo auxiliar: operadores semânticos (semver, datas, UUID, e-mail, URL).
+pub mod value_operators;
+
 // Imports necessários para o trait.
 use crate::context::Context;
 use crate::protocol::{Step, StepResult};
 use anyhow::Result;
 use async_trait::async_trait;
+use serde_json::Value;
 
 // ============================================================================
 // TRAIT STEP EXECUTOR
@@ -116,6 +218,14 @@ pub trait StepExecutor: Send + Sync {
     /// ```
     fn can_handle(&self, action: &str) -> bool;
 
+    /// Verifica se este executor é responsável pelo step.
+    ///
+    /// Por padrão, decide pela action; steps com `agent` ficam para o
+    /// `RemoteAgentExecutor` (ver `agents`).
+    fn can_handle_step(&self, step: &Step) -> bool {
+        step.agent.is_none() && self.can_handle(&step.action)
+    }
+
     /// Executa a lógica do step e retorna o resultado.
     ///
     /// Esta é a função principal onde a "mágica" acontece.
@@ -134,4 +244,53 @@ pub trait StepExecutor: Send + Sync {
     /// - Assertions que falham devem retornar `Ok(StepResult { status: Failed, ... })`
     /// - `Err` é reservado para erros inesperados (panic, IO error, etc.)
     async fn execute(&self, step: &Step, context: &mut Context) -> Result<StepResult>;
+
+    /// JSON Schema dos params que este executor aceita para `action`.
+    ///
+    /// `None` (padrão) desliga a checagem dos params, como nos plugins.
+    fn params_schema(&self, _action: &str) -> Option<Value> {
+        None
+    }
+}
+
+// ============================================================================
+// SCHEMAS DOS PARAMS
+// ============================================================================
+
+/// Executores embutidos, consultados pela validação dos params.
+///
+/// ## Para todos entenderem:
+///
+/// Sem schema, `"duration_ms": "fast"` ou `"methd": "GET"` só aparecem na
+/// execução (ou nem aparecem: o parâmetro é ignorado em silêncio). Cada
+/// executor descreve os params que aceita, e a validação do plano recusa
+/// tipos errados e nomes desconhecidos antes do primeiro step:
+///
+/// ```text
+/// Step 'login': parâmetro 'methd' não é aceito por http_request (aceitos: auth, body, ...)
+/// Step 'pause': parâmetro 'duration_ms' inválido: "fast" is not of type "integer"
+/// ```
+///
+/// Actions sem schema (plugins) não são checadas. O schema vem do próprio
+/// executor (`StepExecutor::params_schema`), a mesma fonte que o teste de
+/// `schemas/utdl.schema.json` confere.
+///
+/// Os executores embutidos vêm com a configuração padrão; a execução monta os mesmos tipos com o cliente, o relógio e o workspace
+/// da execução; o schema dos params não depende dessa configuração.
+pub fn builtin() -> Vec<Box<dyn StepExecutor + Send + Sync>> {
+    vec![
+        Box::new(http::HttpExecutor::new()),
+        Box::new(wait::WaitExecutor::new()),
+        Box::new(set_variable::SetVariableExecutor::new()),
+        Box::new(log::LogExecutor::new()),
+        Box::new(assert::AssertExecutor::new()),
+        Box::new(transform::TransformExecutor::new()),
+        Box::new(tcp::TcpExecutor::new()),
+        Box::new(udp::UdpExecutor::new()),
+        Box::new(grpc_health::GrpcHealthExecutor::new()),
+        Box::new(grpc::GrpcExecutor::new()),
+        Box::new(shell::ShellExecutor::new()),
+        Box::new(rate_limit_probe::RateLimitProbeExecutor::default()),
+        Box::new(webhook::WebhookWaitExecutor::new(None)),
+    ]
 }
@@ -0,0 +1,575 @@
+//! # Executor de Rate Limit - Verificando a Política de Throttling
+//!
+//! Este executor dispara uma rajada de requisições num ritmo fixo e
+//! verifica **quando** a API começa a limitar (429), se ela diz quanto
+//! esperar (`Retry-After`) e se volta a responder depois da espera.
+//!
+//! ## Para todos entenderem:
+//!
+//! A documentação diz "10 requisições por segundo por cliente". Ninguém
+//! confere até um cliente legítimo ser bloqueado com 5 ou um robô passar
+//! com 50. Este step testa a política em si:
+//!
+//! ```json
+//! {
+//!   "id": "search_throttling",
+//!   "action": "rate_limit_probe",
+//!   "params": { "path": "/search?q=x", "requests": 15, "rate_per_sec": 20 },
+//!   "assertions": [
+//!     { "type": "limit_onset", "operator": "eq", "value": 11 },
+//!     { "type": "retry_after", "operator": "exists", "value": true },
+//!     { "type": "recovered", "operator": "eq", "value": true }
+//!   ]
+//! }
+//! ```
+//!
+//! ## Parâmetros:
+//!
+//! | Parâmetro          | Padrão         | Descrição                                      |
+//! |--------------------|----------------|------------------------------------------------|
+//! | `path`             | (obrigatório)  | Caminho (com `base_url`) ou URL completa       |
+//! | `method`           | `GET`          | Método HTTP                                    |
+//! | `headers`, `body`  | -              | Como no `http_request` (interpolados)          |
+//! | `requests`         | (obrigatório)  | Tamanho da rajada (até 10000)                  |
+//! | `rate_per_sec`     | (obrigatório)  | Ritmo de envio, até 10000/s (não espera a resposta anterior) |
+//! | `limit_status`     | `429`          | Status que indica limitação                    |
+//! | `recover_after_ms` | `Retry-After`  | Espera antes da requisição de recuperação      |
+//! | `timeout_ms`       | config         | Timeout de cada requisição                     |
+//!
+//! ## Assertions:
+//!
+//! | Tipo              | Valor observado                                    |
+//! |-------------------|----------------------------------------------------|
+//! | `limit_onset`     | Posição da primeira resposta limitada (1, 2, 3...) |
+//! | `throttled_count` | Quantas respostas vieram limitadas                 |
+//! | `retry_after`     | `Retry-After` da primeira limitada, em segundos    |
+//! | `recovered`       | `true` se a requisição após a espera não foi limitada |
+//!
+//! Operadores numéricos (`eq`, `lt`, `gte`...) e `exists`/`not_exists`.
+//! Sem assertion de `limit_onset` ou `throttled_count`, o step falha se
+//! nenhuma requisição foi limitada. A recuperação só é testada com uma
+//! assertion `recovered`: espera `recover_after_ms` (ou o `Retry-After`,
+//! até 60s; sem nenhum, 1s) e faz mais uma requisição.
+//!
+//! A rajada usa o mesmo cliente dos steps `http_request` (`config.http`),
+//! o login de `config.auth`, a sessão ou o ator do step e os headers de
+//! correlação: o limite medido é o do usuário real, não o de um anônimo.
+//!
+//! Os resultados ficam em `${<step_id>.limit_onset}`,
+//! `${<step_id>.throttled_count}` e `${<step_id>.recovered}`. As esperas
+//! são reais (o `--fast-wait` não as encurta): a janela é do servidor.
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Client, Method, Request};
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+use tokio::time::MissedTickBehavior;
+use tracing::{info, instrument};
+
+use crate::context::Context;
+use crate::protocol::{Assertion, RateLimitDetails, Step, StepResult, StepStatus};
+
+use super::assert::evaluate;
+use super::http::{request_headers, HttpClients};
+use super::http_session::{resolve_url, HttpSession};
+use super::StepExecutor;
+
+/// Status de limitação padrão (Too Many Requests).
+const DEFAULT_LIMIT_STATUS: u16 = 429;
+
+/// Espera de recuperação sem `recover_after_ms` nem `Retry-After`.
+const DEFAULT_RECOVERY_WAIT: Duration = Duration::from_secs(1);
+
+/// Maior espera de recuperação vinda do `Retry-After`.
+const MAX_RECOVERY_WAIT: Duration = Duration::from_secs(60);
+
+/// Maior rajada aceita.
+const MAX_REQUESTS: u64 = 10_000;
+
+/// Maior ritmo aceito (requisições por segundo).
+const MAX_RATE_PER_SEC: f64 = 10_000.0;
+
+/// Assertions próprias desta action.
+const ASSERTION_TYPES: &[&str] = &["limit_onset", "throttled_count", "retry_after", "recovered"];
+
+// ============================================================================
+// RESULTADO DA RAJADA
+// ============================================================================
+
+/// O que a rajada observou.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProbeOutcome {
+    /// Status de cada requisição (0 = sem resposta).
+    pub statuses: Vec<u16>,
+    pub limit_status: u16,
+    /// `Retry-After` da primeira resposta limitada.
+    pub retry_after: Option<String>,
+    /// Status da requisição de recuperação, se feita.
+    pub recovery_status: Option<u16>,
+}
+
+impl ProbeOutcome {
+    pub fn limit_onset(&self) -> Option<u32> {
+        self.statuses
+            .iter()
+            .position(|&s| s == self.limit_status)
+            .map(|i| i as u32 + 1)
+    }
+
+    pub fn throttled(&self) -> u32 {
+        self.statuses
+            .iter()
+            .filter(|&&s| s == self.limit_status)
+            .count() as u32
+    }
+
+    /// Valor observado para uma assertion (`None` = ausente).
+    fn observed(&self, assertion_type: &str) -> Option<Value> {
+        match assertion_type {
+            "limit_onset" => self.limit_onset().map(Value::from),
+            "throttled_count" => Some(Value::from(self.throttled())),
+            "retry_after" => {
+                let raw = self.retry_after.as_deref()?;
+                Some(retry_after_secs(raw).map_or_else(|| json!(raw), Value::from))
+            }
+            "recovered" => self
+                .recovery_status
+                .map(|status| json!(status != 0 && status != self.limit_status)),
+            _ => None,
+        }
+    }
+}
+
+/// `Retry-After` em segundos: número ou data HTTP (`Wed, 21 Oct 2015 07:28:00 GMT`).
+fn retry_after_secs(raw: &str) -> Option<u64> {
+    let raw = raw.trim();
+    raw.parse().ok().or_else(|| {
+        let at = DateTime::parse_from_rfc2822(raw).ok()?;
+        Some((at.with_timezone(&Utc) - Utc::now()).num_seconds().max(0) as u64)
+    })
+}
+
+/// Avalia as assertions sobre a rajada; retorna a primeira falha.
+pub fn check_probe(assertions: &[Assertion], outcome: &ProbeOutcome) -> Option<String> {
+    let checks_onset = assertions
+        .iter()
+        .any(|a| matches!(a.assertion_type.as_str(), "limit_onset" | "throttled_count"));
+    if !checks_onset && outcome.throttled() == 0 {
+        return Some(format!(
+            "Nenhuma das {} requisições foi limitada ({})",
+            outcome.statuses.len(),
+            outcome.limit_status
+        ));
+    }
+
+    assertions.iter().find_map(|assertion| {
+        if !ASSERTION_TYPES.contains(&assertion.assertion_type.as_str()) {
+            return Some(format!(
+                "Assertion type '{}' não suportado em rate_limit_probe",
+                assertion.assertion_type
+            ));
+        }
+        let actual = outcome.observed(&assertion.assertion_type);
+        let passed = match (assertion.operator.as_str(), &actual) {
+            ("exists", actual) => actual.is_some(),
+            ("not_exists", actual) => actual.is_none(),
+            (_, None) => false,
+            (operator, Some(actual)) => evaluate(operator, actual, &assertion.value, None),
+        };
+        (!passed).then(|| {
+            format!(
+                "Assertion failed: {} {} {} (got {})",
+                assertion.assertion_type,
+                assertion.operator,
+                assertion.value,
+                actual.map_or("nenhum".to_string(), |a| a.to_string())
+            )
+        })
+    })
+}
+
+// ============================================================================
+// RATE LIMIT PROBE EXECUTOR
+// ============================================================================
+
+/// Executor para a ação `rate_limit_probe`.
+#[derive(Default)]
+pub struct RateLimitProbeExecutor {
+    clients: HttpClients,
+}
+
+impl RateLimitProbeExecutor {
+    /// Cria um RateLimitProbeExecutor sobre os clientes do `HttpExecutor`.
+    pub fn with_clients(clients: HttpClients) -> Self {
+        Self { clients }
+    }
+}
+
+/// Monta a requisição do step (a mesma para toda a rajada).
+fn build_request(
+    client: &Client,
+    step: &Step,
+    session: Option<&HttpSession>,
+    context: &Context,
+    timeout: Duration,
+) -> Result<Request> {
+    let params = &step.params;
+    let path = params
+        .get("path")
+        .and_then(|p| p.as_str())
+        .ok_or_else(|| anyhow!("Missing 'path' in params"))?;
+    let url = resolve_url(context, &context.interpolate_str(path)?);
+    let method = params
+        .get("method")
+        .and_then(|m| m.as_str())
+        .unwrap_or("GET");
+    let method = Method::from_bytes(method.to_uppercase().as_bytes())
+        .map_err(|e| anyhow!("Invalid HTTP method: {}", e))?;
+
+    let mut request = client.request(method, url).timeout(timeout);
+    for (name, value) in request_headers(step, session, context)? {
+        request = request.header(name, value);
+    }
+    if let Some(body) = params.get("body") {
+        request = request.json(&context.interpolate_value(body)?);
+    }
+    let mut request = request.build()?;
+    if let Some(session) = session {
+        session.authorize(&mut request)?;
+    }
+    Ok(request)
+}
+
+/// Intervalo entre envios (ao menos 1µs; nunca entra em pânico).
+fn send_interval(rate: f64) -> Result<Duration> {
+    Duration::try_from_secs_f64(1.0 / rate)
+        .map(|period| period.max(Duration::from_micros(1)))
+        .map_err(|_| anyhow!("'rate_per_sec' inválido: {}", rate))
+}
+
+/// Envia uma requisição: (status, `Retry-After`). Sem resposta, o erro.
+async fn send(client: Client, request: Request) -> Result<(u16, Option<String>)> {
+    let response = client.execute(request).await?;
+    let retry_after = response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .map(String::from);
+    Ok((response.status().as_u16(), retry_after))
+}
+
+/// JSON Schema dos params de `rate_limit_probe` (ver `StepExecutor::params_schema`).
+pub fn params_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["path", "requests", "rate_per_sec"],
+        "properties": {
+            "path": { "type": "string" },
+            "method": { "type": "string" },
+            "headers": {
+                "type": "object",
+                "additionalProperties": { "type": "string" }
+            },
+            "body": {},
+            "requests": { "type": "integer", "minimum": 1, "maximum": MAX_REQUESTS },
+            "rate_per_sec": { "type": "number", "exclusiveMinimum": 0, "maximum": MAX_RATE_PER_SEC },
+            "limit_status": { "type": "integer", "minimum": 100, "maximum": 599 },
+            "recover_after_ms": { "type": "integer", "minimum": 0 },
+            "timeout_ms": { "type": "integer", "minimum": 1 }
+        },
+        "additionalProperties": false
+    })
+}
+
+#[async_trait]
+impl StepExecutor for RateLimitProbeExecutor {
+    fn can_handle(&self, action: &str) -> bool {
+        action == "rate_limit_probe"
+    }
+
+    /// Schema dos params de `rate_limit_probe`, usado na validação do plano.
+    fn params_schema(&self, _action: &str) -> Option<Value> {
+        Some(params_schema())
+    }
+
+    #[instrument(skip(self, context), fields(step_id = %step.id))]
+    async fn execute(&self, step: &Step, context: &mut Context) -> Result<StepResult> {
+        let started = Instant::now();
+        let context_before = context.variables.clone();
+        let params = &step.params;
+
+        let requests = params
+            .get("requests")
+            .and_then(|r| r.as_u64())
+            .filter(|r| (1..=MAX_REQUESTS).contains(r))
+            .ok_or_else(|| anyhow!("Missing or invalid 'requests' in params"))?;
+        let rate = params
+            .get("rate_per_sec")
+            .and_then(|r| r.as_f64())
+            .filter(|r| *r > 0.0 && *r <= MAX_RATE_PER_SEC)
+            .ok_or_else(|| anyhow!("Missing or invalid 'rate_per_sec' in params"))?;
+        let period = send_interval(rate)?;
+        let limit_status = params
+            .get("limit_status")
+            .and_then(|s| s.as_u64())
+            .map_or(DEFAULT_LIMIT_STATUS, |s| s as u16);
+        let timeout_ms = params
+            .get("timeout_ms")
+            .and_then(|t| t.as_u64())
+            .or_else(|| context.get("timeout_ms").and_then(|t| t.as_u64()))
+            .unwrap_or(30000);
+        self.clients.authenticate(context).await?;
+        let session = self.clients.session(step)?;
+        let client = session.map_or(&self.clients.client, |s| &s.client);
+        let request = build_request(
+            client,
+            step,
+            session,
+            context,
+            Duration::from_millis(timeout_ms),
+        )?;
+        let url = request.url().to_string();
+        let repeat = || {
+            request
+                .try_clone()
+                .ok_or_else(|| anyhow!("Requisição do rate_limit_probe não pode ser repetida"))
+        };
+
+        // Rajada no ritmo pedido: cada envio sai no seu horário, sem esperar o anterior.
+        let mut ticks = tokio::time::interval(period);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Burst);
+        let mut in_flight = Vec::with_capacity(requests as usize);
+        for _ in 0..requests {
+            ticks.tick().await;
+            in_flight.push(tokio::spawn(send(client.clone(), repeat()?)));
+        }
+
+        let mut outcome = ProbeOutcome {
+            limit_status,
+            ..Default::default()
+        };
+        let mut transport_errors = Vec::new();
+        for task in in_flight {
+            match task.await.map_err(anyhow::Error::from).and_then(|r| r) {
+                Ok((status, retry_after)) => {
+                    if status == limit_status && outcome.limit_onset().is_none() {
+                        outcome.retry_after = retry_after;
+                    }
+                    outcome.statuses.push(status);
+                }
+                Err(e) => {
+                    transport_errors.push(format!("{:#}", e));
+                    outcome.statuses.push(0);
+                }
+            }
+        }
+
+        // Recuperação: só com assertion `recovered` e se houve limitação.
+        let wants_recovery = step
+            .assertions
+            .iter()
+            .any(|a| a.assertion_type == "recovered");
+        if wants_recovery && outcome.throttled() > 0 {
+            let wait = params
+                .get("recover_after_ms")
+                .and_then(|w| w.as_u64())
+                .map(Duration::from_millis)
+                .or_else(|| {
+                    let secs = retry_after_secs(outcome.retry_after.as_deref()?)?;
+                    Some(Duration::from_secs(secs).min(MAX_RECOVERY_WAIT))
+                })
+                .unwrap_or(DEFAULT_RECOVERY_WAIT);
+            tokio::time::sleep(wait).await;
+            outcome.recovery_status = Some(match repeat() {
+                Ok(request) => send(client.clone(), request)
+                    .await
+                    .map_or(0, |(status, _)| status),
+                Err(_) => 0,
+            });
+        }
+        let duration_ms = started.elapsed().as_millis() as u64;
+
+        let error = match transport_errors.first() {
+            Some(first) => Some(format!(
+                "{} de {} requisições ficaram sem resposta: {}",
+                transport_errors.len(),
+                requests,
+                first
+            )),
+            None => check_probe(&step.assertions, &outcome),
+        };
+
+        if let Some(onset) = outcome.limit_onset() {
+            context.set(format!("{}.limit_onset", step.id), json!(onset));
+        }
+        context.set(
+            format!("{}.throttled_count", step.id),
+            json!(outcome.throttled()),
+        );
+        if let Some(recovered) = outcome.observed("recovered") {
+            context.set(format!("{}.recovered", step.id), recovered);
+        }
+        info!(
+            %url,
+            requests,
+            rate_per_sec = rate,
+            limit_onset = ?outcome.limit_onset(),
+            throttled = outcome.throttled(),
+            "Rate limit probe finished"
+        );
+
+        Ok(StepResult {
+            step_id: step.id.clone(),
+            status: if error.is_some() {
+                StepStatus::Failed
+            } else {
+                StepStatus::Passed
+            },
+            duration_ms,
+            error,
+            context_before: Some(context_before),
+            context_after: Some(context.variables.clone()),
+            rate_limit_details: Some(RateLimitDetails {
+                url,
+                rate_per_sec: rate,
+                limit_onset: outcome.limit_onset(),
+                throttled: outcome.throttled(),
+                statuses: outcome.statuses,
+                retry_after: outcome.retry_after,
+                recovery_status: outcome.recovery_status,
+                duration_ms,
+            }),
+            ..Default::default()
+        })
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn assertion(assertion_type: &str, operator: &str, value: Value) -> Assertion {
+        Assertion {
+            assertion_type: assertion_type.to_string(),
+            operator: operator.to_string(),
+            value,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_check_probe_onset_retry_after_and_recovery() {
+        let outcome = ProbeOutcome {
+            statuses: vec![200, 200, 200, 429, 429],
+            limit_status: 429,
+            retry_after: Some("2".to_string()),
+            recovery_status: Some(200),
+        };
+        assert_eq!(outcome.limit_onset(), Some(4));
+        assert_eq!(outcome.throttled(), 2);
+
+        let passing = [
+            assertion("limit_onset", "eq", json!(4)),
+            assertion("retry_after", "lte", json!(5)),
+            assertion("recovered", "eq", json!(true)),
+        ];
+        assert!(check_probe(&passing, &outcome).is_none());
+
+        let late = [assertion("limit_onset", "gte", json!(11))];
+        assert!(check_probe(&late, &outcome)
+            .unwrap()
+            .contains("limit_onset gte 11 (got 4)"));
+
+        // Sem assertion de onset, rajada sem nenhum 429 falha.
+        let unlimited = ProbeOutcome {
+            statuses: vec![200; 5],
+            limit_status: 429,
+            ..Default::default()
+        };
+        assert!(check_probe(&[], &unlimited)
+            .unwrap()
+            .starts_with("Nenhuma das 5"));
+        let no_limit = [assertion("limit_onset", "not_exists", json!(true))];
+        assert!(check_probe(&no_limit, &unlimited).is_none());
+    }
+
+    #[test]
+    fn test_send_interval_never_panics() {
+        assert_eq!(send_interval(50.0).unwrap(), Duration::from_millis(20));
+        assert_eq!(send_interval(1e12).unwrap(), Duration::from_micros(1));
+        assert!(send_interval(1e-300).is_err());
+        assert!(params_schema()["properties"]["rate_per_sec"]["maximum"].is_number());
+    }
+
+    #[tokio::test]
+    async fn test_probe_against_token_bucket_server() {
+        // Aceita 3 requisições; depois 429 com Retry-After até o reset.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let served = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&served);
+        let correlated = Arc::new(AtomicU32::new(0));
+        let tagged = Arc::clone(&correlated);
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let counter = Arc::clone(&counter);
+                let tagged = Arc::clone(&tagged);
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 2048];
+                    let n = socket.read(&mut buf).await.unwrap_or(0);
+                    let request = String::from_utf8_lossy(&buf[..n]).to_lowercase();
+                    if request.contains("x-step-id: probe") {
+                        tagged.fetch_add(1, Ordering::SeqCst);
+                    }
+                    let allowed = request.starts_with("get /reset")
+                        || counter.fetch_add(1, Ordering::SeqCst) < 3;
+                    let response = if allowed {
+                        "HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    } else {
+                        "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 1\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    };
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+
+        let mut context = Context::new();
+        context.set("base_url", json!(format!("http://127.0.0.1:{}", port)));
+        context.set("execution_id", json!("exec-1"));
+        context.set("correlation_header", json!("X-Execution-Id"));
+        let step = Step {
+            id: "probe".to_string(),
+            action: "rate_limit_probe".to_string(),
+            params: json!({ "path": "/search", "requests": 5, "rate_per_sec": 50 }),
+            assertions: vec![
+                assertion("limit_onset", "eq", json!(4)),
+                assertion("retry_after", "exists", json!(true)),
+            ],
+            ..Default::default()
+        };
+        let result = RateLimitProbeExecutor::default()
+            .execute(&step, &mut context)
+            .await
+            .unwrap();
+
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+        let details = result.rate_limit_details.unwrap();
+        assert_eq!(details.statuses, [200, 200, 200, 429, 429]);
+        assert_eq!(details.retry_after.as_deref(), Some("1"));
+        assert!(details.recovery_status.is_none());
+        assert_eq!(context.get("probe.limit_onset"), Some(&json!(4)));
+        assert_eq!(served.load(Ordering::SeqCst), 5);
+        assert_eq!(correlated.load(Ordering::SeqCst), 5);
+    }
+}
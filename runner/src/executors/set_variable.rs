@@ -0,0 +1,222 @@
+//! # Executor SetVariable - Atribuição de Variáveis no Contexto
+//!
+//! Este executor permite que um plano **calcule e grave valores** no
+//! contexto sem precisar de uma requisição HTTP ou de um wait "falso".
+//!
+//! ## Para todos entenderem:
+//!
+//! Às vezes precisamos montar um valor a partir de outros (ex: juntar
+//! nome e sobrenome) ou fixar uma constante usada por vários steps.
+//! Em vez de fazer uma chamada HTTP só para extrair algo, usamos
+//! `set_variable` e o valor fica disponível como `${nome}`.
+//!
+//! ## Exemplo de uso no UTDL:
+//!
+//! ```json
+//! {
+//!   "id": "montar_nome",
+//!   "action": "set_variable",
+//!   "params": {
+//!     "variables": {
+//!       "full_name": "${first} ${last}",
+//!       "page_size": 50
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! Também é aceito o formato curto para uma única variável:
+//!
+//! ```json
+//! { "name": "full_name", "value": "${first} ${last}" }
+//! ```
+//!
+//! ## Tipos preservados:
+//!
+//! Se o valor for exatamente um placeholder (ex: `"${items}"`), o valor
+//! original é copiado com seu tipo (array, número, objeto...).
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde_json::{json, Map, Value};
+use std::time::Instant;
+use tracing::{info, instrument};
+
+use crate::context::Context;
+use crate::protocol::{Step, StepResult, StepStatus};
+
+use super::StepExecutor;
+
+// ============================================================================
+// SET VARIABLE EXECUTOR
+// ============================================================================
+
+/// Executor para a ação `set_variable`.
+///
+/// Não possui estado, então pode ser usado em execução paralela.
+#[derive(Debug, Default)]
+pub struct SetVariableExecutor;
+
+impl SetVariableExecutor {
+    /// Cria um novo SetVariableExecutor.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Normaliza os parâmetros para um mapa `nome → valor`.
+    ///
+    /// Aceita `{ "variables": { ... } }` ou `{ "name": "...", "value": ... }`.
+    fn collect_assignments(step: &Step) -> Result<Map<String, Value>> {
+        if let Some(variables) = step.params.get("variables") {
+            return variables.as_object().cloned().ok_or_else(|| {
+                anyhow!("Parâmetros inválidos para set_variable: 'variables' deve ser um objeto")
+            });
+        }
+
+        if let Some(name) = step.params.get("name").and_then(|n| n.as_str()) {
+            let value = step.params.get("value").cloned().unwrap_or(Value::Null);
+            let mut map = Map::new();
+            map.insert(name.to_string(), value);
+            return Ok(map);
+        }
+
+        Err(anyhow!(
+            "Parâmetros incompletos para set_variable: forneça 'variables' ou 'name'/'value'"
+        ))
+    }
+}
+
+/// JSON Schema dos params de `set_variable` (ver `StepExecutor::params_schema`).
+pub fn params_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "variables": { "type": "object" },
+            "name": { "type": "string" },
+            "value": {}
+        },
+        "additionalProperties": false
+    })
+}
+
+// ============================================================================
+// IMPLEMENTAÇÃO DO TRAIT
+// ============================================================================
+
+#[async_trait]
+impl StepExecutor for SetVariableExecutor {
+    fn can_handle(&self, action: &str) -> bool {
+        action == "set_variable"
+    }
+
+    /// Schema dos params de `set_variable`, usado na validação do plano.
+    fn params_schema(&self, _action: &str) -> Option<Value> {
+        Some(params_schema())
+    }
+
+    /// Interpola os valores e grava no contexto.
+    ///
+    /// As variáveis são interpoladas com o contexto **anterior** ao step,
+    /// então uma atribuição não enxerga as outras do mesmo step.
+    #[instrument(skip(self, context), fields(step_id = %step.id))]
+    async fn execute(&self, step: &Step, context: &mut Context) -> Result<StepResult> {
+        let start = Instant::now();
+        let context_before = context.variables.clone();
+
+        let assignments = Self::collect_assignments(step)?;
+
+        // Interpola tudo antes de gravar (sem efeitos parciais em caso de erro).
+        let mut resolved = Vec::with_capacity(assignments.len());
+        for (name, raw) in &assignments {
+            resolved.push((name.clone(), context.interpolate_value_typed(raw)?));
+        }
+
+        for (name, value) in resolved {
+            context.set(name, value);
+        }
+
+        info!(
+            step_id = %step.id,
+            variables = ?assignments.keys().collect::<Vec<_>>(),
+            "📝 Variáveis definidas"
+        );
+
+        Ok(StepResult {
+            step_id: step.id.clone(),
+            status: StepStatus::Passed,
+            duration_ms: start.elapsed().as_millis() as u64,
+            context_before: Some(context_before),
+            context_after: Some(context.variables.clone()),
+            ..Default::default()
+        })
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn create_step(params: Value) -> Step {
+        Step {
+            id: "set_vars".to_string(),
+            description: None,
+            depends_on: vec![],
+            action: "set_variable".to_string(),
+            params,
+            assertions: vec![],
+            extract: vec![],
+            recovery_policy: None,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_variable_interpolates_values() {
+        let executor = SetVariableExecutor::new();
+        let mut context = Context::new();
+        context.set("first", json!("Ana"));
+        context.set("last", json!("Silva"));
+        context.set("ids", json!([1, 2]));
+
+        let step = create_step(json!({
+            "variables": {
+                "full_name": "${first} ${last}",
+                "copy": "${ids}",
+                "limit": 10
+            }
+        }));
+
+        let result = executor.execute(&step, &mut context).await.unwrap();
+
+        assert_eq!(result.status, StepStatus::Passed);
+        assert_eq!(context.get("full_name"), Some(&json!("Ana Silva")));
+        assert_eq!(context.get("copy"), Some(&json!([1, 2])));
+        assert_eq!(context.get("limit"), Some(&json!(10)));
+        assert!(!result.context_before.unwrap().contains_key("full_name"));
+    }
+
+    #[tokio::test]
+    async fn test_set_variable_short_form() {
+        let executor = SetVariableExecutor::new();
+        let mut context = Context::new();
+
+        let step = create_step(json!({ "name": "env", "value": "staging" }));
+        executor.execute(&step, &mut context).await.unwrap();
+
+        assert_eq!(context.get("env"), Some(&json!("staging")));
+    }
+
+    #[tokio::test]
+    async fn test_set_variable_missing_params_fails() {
+        let executor = SetVariableExecutor::new();
+        let mut context = Context::new();
+
+        let step = create_step(json!({}));
+        assert!(executor.execute(&step, &mut context).await.is_err());
+    }
+}
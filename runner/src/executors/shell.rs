@@ -0,0 +1,674 @@
+//! # Executor de Comandos - Processos Locais Entre Steps
+//!
+//! Este executor roda um programa local (CLI, script de seed, migração) e
+//! verifica o código de saída e a saída, para fluxos que precisam preparar
+//! dados entre requisições HTTP.
+//!
+//! ## Para todos entenderem:
+//!
+//! Antes de testar `GET /orders`, alguém precisa criar os pedidos no banco.
+//! Em vez de um script fora do plano, o seed vira um step:
+//!
+//! ```json
+//! {
+//!   "id": "seed",
+//!   "action": "shell_command",
+//!   "params": {
+//!     "command": "./scripts/seed.sh",
+//!     "args": ["--tenant", "${tenant_id}", "--json"],
+//!     "env": { "DATABASE_URL": "${db_url}" },
+//!     "timeout_ms": 60000
+//!   },
+//!   "assertions": [
+//!     { "type": "exit_code", "operator": "eq", "value": 0 },
+//!     { "type": "stdout", "operator": "contains", "value": "created" }
+//!   ],
+//!   "extract": [
+//!     { "source": "stdout", "path": "$.orders[0].id", "target": "order_id" }
+//!   ]
+//! }
+//! ```
+//!
+//! ## Parâmetros:
+//!
+//! | Parâmetro    | Descrição                                                |
+//! |--------------|----------------------------------------------------------|
+//! | `command`    | Programa (obrigatório; caminhos relativos ao plano)      |
+//! | `args`       | Lista de argumentos, cada um interpolado                 |
+//! | `env`        | Variáveis de ambiente do processo                        |
+//! | `inherit_env`| Variáveis copiadas do Runner (padrão: PATH, HOME, LANG)  |
+//! | `workdir`    | Diretório de trabalho dentro do diretório do step        |
+//! | `cwd`        | Diretório de trabalho relativo ao plano (sobrepõe acima) |
+//! | `stdin`      | Texto enviado na entrada padrão                          |
+//! | `timeout_ms` | Prazo do processo (padrão: 30000); estourou, é morto     |
+//!
+//! Rodar programas locais exige opt-in: sem `runner execute --allow-shell`,
+//! o executor nem é registrado e um plano com `shell_command` falha na
+//! validação.
+//!
+//! O processo **não herda** o ambiente do Runner (tokens, credenciais da
+//! CI): recebe só `inherit_env`, `env` e `TMPDIR`/`TEMP`/`TMP` apontando
+//! para o diretório do step, que também é o diretório de trabalho padrão
+//! (ver `isolation`).
+//!
+//! Não há shell no meio: `args` chegam ao programa como estão, sem
+//! expansão de `*`, `$VAR` ou `|`. Para isso, use `"command": "sh"` com
+//! `"args": ["-c", "..."]`.
+//!
+//! ## Assertions e extrações:
+//!
+//! - `exit_code`: `eq`, `neq`, `in`, `not_in`, `gt`, `lt`, `gte`, `lte`
+//!   (sem nenhuma, o step falha se o código não for 0)
+//! - `stdout` / `stderr`: os operadores de texto do `tcp_send`
+//! - Extração de `stdout`: JSONPath se a saída for JSON, `regex:` sempre;
+//!   `stderr` aceita `regex:`; `exit_code` extrai o código
+//!
+//! A saída também fica em `${<step_id>.stdout}`, `${<step_id>.stderr}` e
+//! `${<step_id>.exit_code}`.
+
+use anyhow::{anyhow, Context as _, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::process::Command;
+use tracing::{info, instrument};
+
+use crate::context::Context;
+use crate::extractors::{ExtractionResult, Extractor};
+use crate::isolation::{RunWorkspace, StepEnv};
+use crate::protocol::{Assertion, CommandDetails, Extraction, Step, StepResult, StepStatus};
+
+use super::tcp::{compare_text, unsupported_operator};
+use super::StepExecutor;
+
+/// Prazo padrão de um comando.
+const DEFAULT_TIMEOUT_MS: u64 = 30_000;
+
+/// Saída guardada no contexto por stream (o resto é descartado).
+const MAX_OUTPUT_BYTES: usize = 1024 * 1024;
+
+/// Saída guardada em `command_details` (relatório).
+const OUTPUT_PREVIEW_BYTES: usize = 4096;
+
+// ============================================================================
+// SAÍDA DO PROCESSO
+// ============================================================================
+
+/// O que o processo produziu.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommandOutput {
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Texto da saída, cortado em `limit` bytes (sem partir caracteres).
+fn truncate(bytes: &[u8], limit: usize) -> String {
+    let text = String::from_utf8_lossy(bytes);
+    if text.len() <= limit {
+        return text.into_owned();
+    }
+    let mut end = limit;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text[..end].to_string()
+}
+
+/// Avalia as assertions sobre a saída; retorna a primeira falha.
+pub fn check_output(assertions: &[Assertion], output: &CommandOutput) -> Option<String> {
+    let checks_exit_code = assertions.iter().any(|a| a.assertion_type == "exit_code");
+    if !checks_exit_code && output.exit_code != Some(0) {
+        return Some(match output.exit_code {
+            Some(code) => format!(
+                "Comando terminou com código {} (stderr: {})",
+                code,
+                output.stderr.trim()
+            ),
+            None => "Comando encerrado por sinal, sem código de saída".to_string(),
+        });
+    }
+
+    assertions.iter().find_map(|assertion| {
+        let ok = match assertion.assertion_type.as_str() {
+            "exit_code" => compare_exit_code(assertion, output.exit_code)
+                .ok_or_else(|| unsupported_operator(&assertion.operator)),
+            "stdout" => compare_text(
+                &assertion.operator,
+                &output.stdout,
+                assertion.value.as_str().unwrap_or_default(),
+            ),
+            "stderr" => compare_text(
+                &assertion.operator,
+                &output.stderr,
+                assertion.value.as_str().unwrap_or_default(),
+            ),
+            other => {
+                return Some(format!(
+                    "Assertion type '{}' não suportado em shell_command",
+                    other
+                ))
+            }
+        };
+        match ok {
+            Ok(true) => None,
+            Ok(false) => Some(format!(
+                "Assertion failed: {} {} {} (exit_code: {})",
+                assertion.assertion_type,
+                assertion.operator,
+                assertion.value,
+                output
+                    .exit_code
+                    .map_or("nenhum".to_string(), |c| c.to_string())
+            )),
+            Err(e) => Some(format!("{} em {}", e, assertion.assertion_type)),
+        }
+    })
+}
+
+fn compare_exit_code(assertion: &Assertion, actual: Option<i32>) -> Option<bool> {
+    // Sem código (morto por sinal) nenhuma comparação passa.
+    let Some(actual) = actual.map(i64::from) else {
+        return Some(false);
+    };
+    let listed = || {
+        assertion
+            .value
+            .as_array()
+            .map(|codes| codes.iter().any(|c| c.as_i64() == Some(actual)))
+    };
+    let expected = assertion.value.as_i64();
+    Some(match assertion.operator.as_str() {
+        "eq" => expected? == actual,
+        "neq" => expected? != actual,
+        "gt" => actual > expected?,
+        "lt" => actual < expected?,
+        "gte" => actual >= expected?,
+        "lte" => actual <= expected?,
+        "in" => listed()?,
+        "not_in" => !listed()?,
+        _ => return None,
+    })
+}
+
+/// Aplica as extrações do step sobre a saída.
+///
+/// `stdout` e `stderr` viram o "body" do extrator (JSON quando possível);
+/// `exit_code` é extraído direto.
+pub fn extract_output(
+    extractions: &[Extraction],
+    output: &CommandOutput,
+) -> (Vec<ExtractionResult>, HashMap<String, Value>) {
+    let stdout = serde_json::from_str(output.stdout.trim())
+        .unwrap_or_else(|_| Value::String(output.stdout.clone()));
+    let stderr = Value::String(output.stderr.clone());
+
+    let mut results = Vec::with_capacity(extractions.len());
+    let mut values = HashMap::new();
+    for extraction in extractions {
+        let source = extraction.source.to_lowercase();
+        let mut result = match source.as_str() {
+            "exit_code" => match output.exit_code {
+                Some(code) => ExtractionResult::success(
+                    extraction.target.clone(),
+                    source.clone(),
+                    extraction.path.clone(),
+                    json!(code),
+                ),
+                None => ExtractionResult::failure(
+                    extraction.target.clone(),
+                    source.clone(),
+                    extraction.path.clone(),
+                    "Processo sem código de saída (morto por sinal)".to_string(),
+                ),
+            },
+            "stdout" | "stderr" => {
+                let body = if source == "stdout" { &stdout } else { &stderr };
+                let as_body = Extraction {
+                    source: "body".to_string(),
+                    ..extraction.clone()
+                };
+                let (mut single, _) =
+                    Extractor::process(std::slice::from_ref(&as_body), Some(body), &HashMap::new());
+                let mut result = single.remove(0);
+                result.source = source.clone();
+                result
+            }
+            other => ExtractionResult::failure(
+                extraction.target.clone(),
+                other.to_string(),
+                extraction.path.clone(),
+                format!(
+                    "Fonte '{}' inválida em shell_command. Use 'stdout', 'stderr' ou 'exit_code'.",
+                    other
+                ),
+            ),
+        };
+        result.is_critical = extraction.critical;
+        if result.success {
+            if let Some(value) = &result.value {
+                values.insert(result.target.clone(), value.clone());
+            }
+        }
+        results.push(result);
+    }
+    (results, values)
+}
+
+// ============================================================================
+// SHELL EXECUTOR
+// ============================================================================
+
+/// Executor para a ação `shell_command`.
+pub struct ShellExecutor {
+    /// Diretório da execução (um subdiretório por step).
+    workspace: Arc<RunWorkspace>,
+}
+
+impl ShellExecutor {
+    /// Cria um ShellExecutor com workspace próprio (removido junto com o executor).
+    pub fn new() -> Self {
+        Self::with_workspace(Arc::new(RunWorkspace::new(
+            &uuid::Uuid::new_v4().to_string(),
+        )))
+    }
+
+    /// Cria um ShellExecutor que usa o workspace da execução.
+    pub fn with_workspace(workspace: Arc<RunWorkspace>) -> Self {
+        Self { workspace }
+    }
+}
+
+impl Default for ShellExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Caminho relativo ao diretório do plano (ou ao diretório atual, sem `plan_dir`).
+fn plan_relative(path: &str, context: &Context) -> PathBuf {
+    let path = Path::new(path);
+    match context.get("plan_dir").and_then(|d| d.as_str()) {
+        Some(dir) if path.is_relative() => Path::new(dir).join(path),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Lê e interpola `command`, `args`, `cwd` e `stdin`; o ambiente vem de `StepEnv`.
+fn build_command(
+    step: &Step,
+    context: &Context,
+    workspace: &RunWorkspace,
+) -> Result<(Command, Vec<String>, Option<String>)> {
+    let params = &step.params;
+    let program = params
+        .get("command")
+        .and_then(|c| c.as_str())
+        .ok_or_else(|| anyhow!("Missing 'command' in params"))?;
+    let program = context.interpolate_str(program)?;
+
+    let mut args = Vec::new();
+    if let Some(list) = params.get("args") {
+        let list = list
+            .as_array()
+            .ok_or_else(|| anyhow!("'args' deve ser uma lista de strings"))?;
+        for arg in list {
+            let arg = match arg {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            args.push(context.interpolate_str(&arg)?);
+        }
+    }
+
+    // Programas com caminho (`./seed.sh`) partem do plano; nomes soltos usam o PATH.
+    let resolved = if program.contains('/') {
+        plan_relative(&program, context)
+    } else {
+        PathBuf::from(&program)
+    };
+    let mut command = Command::new(resolved);
+    command.args(&args);
+    StepEnv::from_step(step, context, workspace)?.apply(&mut command);
+    if let Some(cwd) = params.get("cwd").and_then(|c| c.as_str()) {
+        command.current_dir(plan_relative(&context.interpolate_str(cwd)?, context));
+    }
+
+    let stdin = params
+        .get("stdin")
+        .and_then(|s| s.as_str())
+        .map(|s| context.interpolate_str(s))
+        .transpose()?;
+
+    let mut shown = vec![program];
+    shown.extend(args);
+    Ok((command, shown, stdin))
+}
+
+/// Roda o processo até o fim ou o prazo (estourou, o processo é morto).
+async fn run(
+    mut command: Command,
+    stdin: Option<String>,
+    timeout_ms: u64,
+) -> Result<CommandOutput> {
+    command
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let mut child = command.spawn().context("Falha ao iniciar o comando")?;
+    let stdin_pipe = child.stdin.take();
+    let stdout = child
+        .stdout
+        .take()
+        .context("stdout do comando indisponível")?;
+    let stderr = child
+        .stderr
+        .take()
+        .context("stderr do comando indisponível")?;
+
+    // Escrita e leitura ao mesmo tempo, dentro do prazo: um processo que
+    // enche o pipe de saída antes de ler todo o stdin não trava o step.
+    let io = async {
+        let write = async {
+            if let (Some(input), Some(mut pipe)) = (stdin, stdin_pipe) {
+                // Processos que não leem o stdin podem terminar antes da escrita.
+                match pipe.write_all(input.as_bytes()).await {
+                    Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => return Err(e),
+                    _ => {}
+                }
+                // `pipe` sai de escopo aqui: o processo recebe EOF.
+            }
+            Ok(())
+        };
+        let (written, stdout, stderr) =
+            tokio::join!(write, read_bounded(stdout), read_bounded(stderr));
+        written?;
+        let status = child.wait().await?;
+        Ok::<_, std::io::Error>((status, stdout?, stderr?))
+    };
+    let (status, stdout, stderr) = tokio::time::timeout(Duration::from_millis(timeout_ms), io)
+        .await
+        .map_err(|_| anyhow!("Timeout de {}ms aguardando o comando", timeout_ms))??;
+
+    Ok(CommandOutput {
+        exit_code: status.code(),
+        stdout: truncate(&stdout, MAX_OUTPUT_BYTES),
+        stderr: truncate(&stderr, MAX_OUTPUT_BYTES),
+    })
+}
+
+/// Lê até `MAX_OUTPUT_BYTES` (+1 para `truncate` saber que cortou) e
+/// descarta o resto, sem deixar de drenar o pipe (cheio, o processo trava).
+async fn read_bounded(mut pipe: impl AsyncRead + Unpin) -> std::io::Result<Vec<u8>> {
+    let mut kept = Vec::new();
+    (&mut pipe)
+        .take(MAX_OUTPUT_BYTES as u64 + 1)
+        .read_to_end(&mut kept)
+        .await?;
+    tokio::io::copy(&mut pipe, &mut tokio::io::sink()).await?;
+    Ok(kept)
+}
+
+/// JSON Schema dos params de `shell_command` (ver `StepExecutor::params_schema`).
+pub fn params_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["command"],
+        "properties": {
+            "command": { "type": "string" },
+            "args": { "type": "array", "items": { "type": "string" } },
+            "env": { "type": "object" },
+            "inherit_env": { "type": "array", "items": { "type": "string" } },
+            "workdir": { "type": "string" },
+            "cwd": { "type": "string" },
+            "stdin": { "type": "string" },
+            "timeout_ms": { "type": "integer", "minimum": 1 }
+        },
+        "additionalProperties": false
+    })
+}
+
+#[async_trait]
+impl StepExecutor for ShellExecutor {
+    fn can_handle(&self, action: &str) -> bool {
+        action == "shell_command"
+    }
+
+    /// Schema dos params de `shell_command`, usado na validação do plano.
+    fn params_schema(&self, _action: &str) -> Option<Value> {
+        Some(params_schema())
+    }
+
+    #[instrument(skip(self, context), fields(step_id = %step.id))]
+    async fn execute(&self, step: &Step, context: &mut Context) -> Result<StepResult> {
+        let started = Instant::now();
+        let context_before = context.variables.clone();
+        let (command, shown, stdin) = build_command(step, context, &self.workspace)?;
+        let timeout_ms = step
+            .params
+            .get("timeout_ms")
+            .and_then(|t| t.as_u64())
+            .unwrap_or(DEFAULT_TIMEOUT_MS);
+
+        let outcome = run(command, stdin, timeout_ms).await;
+        let latency_ms = started.elapsed().as_millis() as u64;
+
+        let (output, error, extractions) = match outcome {
+            Ok(output) => {
+                let failure = check_output(&step.assertions, &output);
+                let (extractions, values) = extract_output(&step.extract, &output);
+                for (key, value) in values {
+                    context.set(key, value);
+                }
+                context.set(format!("{}.stdout", step.id), json!(output.stdout));
+                context.set(format!("{}.stderr", step.id), json!(output.stderr));
+                if let Some(code) = output.exit_code {
+                    context.set(format!("{}.exit_code", step.id), json!(code));
+                }
+                (output, failure, Some(extractions))
+            }
+            Err(e) => (
+                CommandOutput::default(),
+                Some(format!("{:#} ({})", e, shown.join(" "))),
+                None,
+            ),
+        };
+
+        info!(
+            command = %shown.join(" "),
+            exit_code = ?output.exit_code,
+            latency_ms,
+            "Command finished"
+        );
+
+        Ok(StepResult {
+            step_id: step.id.clone(),
+            status: if error.is_some() {
+                StepStatus::Failed
+            } else {
+                StepStatus::Passed
+            },
+            duration_ms: latency_ms,
+            error,
+            context_before: Some(context_before),
+            context_after: Some(context.variables.clone()),
+            extractions: extractions.filter(|e| !e.is_empty()),
+            command_details: Some(CommandDetails {
+                command: shown,
+                exit_code: output.exit_code,
+                latency_ms,
+                stdout: Some(truncate(output.stdout.as_bytes(), OUTPUT_PREVIEW_BYTES)),
+                stderr: Some(truncate(output.stderr.as_bytes(), OUTPUT_PREVIEW_BYTES)),
+            }),
+            ..Default::default()
+        })
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use super::*;
+
+    fn step(params: Value, assertions: Vec<Assertion>, extract: Vec<Extraction>) -> Step {
+        Step {
+            id: "seed".to_string(),
+            action: "shell_command".to_string(),
+            params,
+            assertions,
+            extract,
+            ..Default::default()
+        }
+    }
+
+    fn assertion(assertion_type: &str, operator: &str, value: Value) -> Assertion {
+        Assertion {
+            assertion_type: assertion_type.to_string(),
+            operator: operator.to_string(),
+            value,
+            ..Default::default()
+        }
+    }
+
+    fn extraction(source: &str, path: &str, target: &str) -> Extraction {
+        serde_json::from_value(json!({ "source": source, "path": path, "target": target })).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_shell_command_json_stdout_and_extractions() {
+        let mut context = Context::new();
+        context.set("tenant", json!("acme"));
+        let step = step(
+            json!({
+                "command": "sh",
+                "args": ["-c", "echo \"{\\\"tenant\\\": \\\"$1\\\", \\\"id\\\": 7}\"; echo warn >&2", "seed", "${tenant}"]
+            }),
+            vec![assertion("stdout", "contains", json!("acme"))],
+            vec![
+                extraction("stdout", "$.id", "order_id"),
+                extraction("stderr", "regex:(w\\w+)", "first_warning"),
+                extraction("exit_code", "", "code"),
+            ],
+        );
+
+        let result = ShellExecutor::new()
+            .execute(&step, &mut context)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+        assert_eq!(context.get("order_id"), Some(&json!(7)));
+        assert_eq!(context.get("first_warning"), Some(&json!("warn")));
+        assert_eq!(context.get("code"), Some(&json!(0)));
+        assert_eq!(context.get("seed.stderr"), Some(&json!("warn\n")));
+        let details = result.command_details.unwrap();
+        assert_eq!(details.command[0], "sh");
+        assert_eq!(details.exit_code, Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_shell_command_exit_code_rules() {
+        let mut context = Context::new();
+        let failing = json!({ "command": "sh", "args": ["-c", "echo boom >&2; exit 3"] });
+
+        // Sem assertion de exit_code, código diferente de 0 falha.
+        let result = ShellExecutor::new()
+            .execute(&step(failing.clone(), vec![], vec![]), &mut context)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Failed);
+        assert!(result.error.unwrap().contains("código 3 (stderr: boom)"));
+
+        let expected = step(
+            failing,
+            vec![assertion("exit_code", "in", json!([2, 3]))],
+            vec![],
+        );
+        let result = ShellExecutor::new()
+            .execute(&expected, &mut context)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+    }
+
+    #[tokio::test]
+    async fn test_shell_command_does_not_inherit_runner_env() {
+        std::env::set_var("AQA_SHELL_SECRET", "leak");
+        let mut context = Context::new();
+        let step = step(
+            json!({
+                "command": "sh",
+                "args": ["-c", "echo \"[$AQA_SHELL_SECRET]\" $(pwd)"]
+            }),
+            vec![assertion("stdout", "starts_with", json!("[] "))],
+            vec![],
+        );
+        let result = ShellExecutor::new()
+            .execute(&step, &mut context)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+        let stdout = context.get("seed.stdout").unwrap().as_str().unwrap();
+        assert!(stdout.trim_end().ends_with("/seed"), "{}", stdout);
+    }
+
+    #[tokio::test]
+    async fn test_shell_command_timeout_and_stdin() {
+        let mut context = Context::new();
+        let slow = step(
+            json!({ "command": "sleep", "args": ["5"], "timeout_ms": 100 }),
+            vec![],
+            vec![],
+        );
+        let result = ShellExecutor::new()
+            .execute(&slow, &mut context)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Failed);
+        assert!(result.error.unwrap().contains("Timeout de 100ms"));
+
+        let cat = step(
+            json!({ "command": "cat", "stdin": "hello" }),
+            vec![assertion("stdout", "eq", json!("hello"))],
+            vec![],
+        );
+        let result = ShellExecutor::new()
+            .execute(&cat, &mut context)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+    }
+
+    #[tokio::test]
+    async fn test_shell_command_large_stdin_and_output_are_bounded() {
+        let mut context = Context::new();
+        // `cat` devolve o stdin: sem escrita e leitura simultâneas, o pipe
+        // de saída enche e a escrita do stdin nunca termina.
+        let input = "x".repeat(MAX_OUTPUT_BYTES * 3);
+        let cat = step(
+            json!({ "command": "cat", "stdin": input, "timeout_ms": 10_000 }),
+            vec![],
+            vec![],
+        );
+        let result = ShellExecutor::new()
+            .execute(&cat, &mut context)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+        let stdout = context.get("seed.stdout").unwrap().as_str().unwrap();
+        assert_eq!(stdout.len(), MAX_OUTPUT_BYTES);
+    }
+}
@@ -0,0 +1,599 @@
+//! # Executor TCP - Bytes Crus por Socket
+//!
+//! Este executor abre uma conexão TCP, envia bytes e lê a resposta, para
+//! testar protocolos que não são HTTP (protocolos de linha, binários
+//! proprietários, Redis/SMTP "na mão").
+//!
+//! ## Para todos entenderem:
+//!
+//! Um serviço de cotação responde `PONG\r\n` a um `PING\r\n` na porta 7000.
+//! Não há URL nem status code: só bytes indo e voltando. O step descreve o
+//! que enviar, até onde ler e o que a resposta deve conter:
+//!
+//! ```json
+//! {
+//!   "id": "ping",
+//!   "action": "tcp_send",
+//!   "params": {
+//!     "host": "quotes.internal", "port": 7000,
+//!     "data": "PING ${client_id}\r\n",
+//!     "read_until": "\r\n",
+//!     "timeout_ms": 2000
+//!   },
+//!   "assertions": [
+//!     { "type": "reply_text", "operator": "matches_regex", "value": "^PONG" },
+//!     { "type": "reply_hex", "operator": "ends_with", "value": "0d0a" }
+//!   ]
+//! }
+//! ```
+//!
+//! ## Envio (`data` + `encoding`):
+//!
+//! | `encoding`        | `data`                          |
+//! |-------------------|---------------------------------|
+//! | `text` (padrão)   | Texto, com interpolação         |
+//! | `hex`             | `"0a ff 10"` (espaços ignorados) |
+//! | `base64`          | `"Cv8Q"`                        |
+//!
+//! ## Leitura:
+//!
+//! | Parâmetro           | Para de ler quando...                           |
+//! |---------------------|-------------------------------------------------|
+//! | `read_until`        | O delimitador chega (falha se não chegar)       |
+//! | `read_bytes`        | N bytes chegaram (falha se não chegarem)        |
+//! | nenhum dos dois     | O servidor fecha a conexão ou o timeout vence   |
+//!
+//! ## Assertions:
+//!
+//! - `reply_text`: `eq`, `neq`, `contains`, `starts_with`, `ends_with`, `matches_regex`
+//! - `reply_hex`: os mesmos, comparando bytes (o valor precisa de pares de dígitos hex)
+//! - `reply_length`: `eq`, `gt`, `lt`, `gte`, `lte` sobre o tamanho em bytes
+//!
+//! A resposta também fica em `${<step_id>.reply}` (texto) e
+//! `${<step_id>.reply_hex}` para os steps seguintes.
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use regex::Regex;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::timeout_at;
+use tracing::{info, instrument};
+
+use crate::context::Context;
+use crate::protocol::{Assertion, SocketDetails, Step, StepResult, StepStatus};
+
+use super::StepExecutor;
+
+/// Maior resposta lida (o resto é descartado).
+const MAX_REPLY_BYTES: usize = 1024 * 1024;
+
+/// Bytes da resposta guardados em `socket_details.reply_hex`.
+pub const REPLY_PREVIEW_BYTES: usize = 256;
+
+// ============================================================================
+// ENVIO E LEITURA
+// ============================================================================
+
+/// Decodifica `data` conforme `encoding`.
+pub fn decode_payload(data: &str, encoding: &str) -> Result<Vec<u8>> {
+    match encoding {
+        "text" => Ok(data.as_bytes().to_vec()),
+        "hex" => {
+            let digits: String = data.chars().filter(|c| !c.is_whitespace()).collect();
+            if !digits.len().is_multiple_of(2) {
+                return Err(anyhow!("data em hex com número ímpar de dígitos"));
+            }
+            (0..digits.len())
+                .step_by(2)
+                .map(|i| {
+                    u8::from_str_radix(&digits[i..i + 2], 16)
+                        .map_err(|_| anyhow!("data em hex inválido: '{}'", &digits[i..i + 2]))
+                })
+                .collect()
+        }
+        "base64" => STANDARD
+            .decode(data.trim())
+            .map_err(|e| anyhow!("data em base64 inválido: {}", e)),
+        other => Err(anyhow!(
+            "encoding '{}' inválido (use text, hex ou base64)",
+            other
+        )),
+    }
+}
+
+/// Critério de fim da leitura.
+#[derive(Debug, Clone, PartialEq)]
+enum ReadUntil {
+    Delimiter(Vec<u8>),
+    Length(usize),
+    Close,
+}
+
+/// Lê a resposta até o critério ou o prazo.
+async fn read_reply<R: AsyncReadExt + Unpin>(
+    reader: &mut R,
+    until: &ReadUntil,
+    deadline: tokio::time::Instant,
+) -> Result<Vec<u8>> {
+    let mut reply = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        let done = match until {
+            ReadUntil::Delimiter(delimiter) => reply
+                .windows(delimiter.len())
+                .position(|w| w == delimiter.as_slice())
+                .map(|at| reply.truncate(at + delimiter.len()))
+                .is_some(),
+            ReadUntil::Length(length) => {
+                if reply.len() >= *length {
+                    reply.truncate(*length);
+                }
+                reply.len() >= *length
+            }
+            ReadUntil::Close => false,
+        };
+        if done || reply.len() >= MAX_REPLY_BYTES {
+            return Ok(reply);
+        }
+
+        let read = match timeout_at(deadline, reader.read(&mut chunk)).await {
+            Ok(read) => read?,
+            // Sem critério, o timeout só encerra a leitura.
+            Err(_) if *until == ReadUntil::Close => return Ok(reply),
+            Err(_) => {
+                return Err(anyhow!(
+                    "Timeout aguardando {} ({} bytes recebidos: {})",
+                    describe(until),
+                    reply.len(),
+                    hex(&reply[..reply.len().min(REPLY_PREVIEW_BYTES)])
+                ))
+            }
+        };
+        if read == 0 {
+            return match until {
+                ReadUntil::Close => Ok(reply),
+                _ => Err(anyhow!(
+                    "Conexão fechada antes de {} ({} bytes recebidos)",
+                    describe(until),
+                    reply.len()
+                )),
+            };
+        }
+        reply.extend_from_slice(&chunk[..read]);
+    }
+}
+
+fn describe(until: &ReadUntil) -> String {
+    match until {
+        ReadUntil::Delimiter(delimiter) => format!("o delimitador {}", hex(delimiter)),
+        ReadUntil::Length(length) => format!("{} bytes", length),
+        ReadUntil::Close => "o fim da conexão".to_string(),
+    }
+}
+
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// ============================================================================
+// ASSERTIONS
+// ============================================================================
+
+/// Avalia as assertions sobre a resposta; retorna a primeira falha.
+pub fn check_reply(assertions: &[Assertion], reply: &[u8]) -> Option<String> {
+    assertions.iter().find_map(|assertion| {
+        let ok = match assertion.assertion_type.as_str() {
+            "reply_text" => compare_text(
+                &assertion.operator,
+                &String::from_utf8_lossy(reply),
+                assertion.value.as_str().unwrap_or_default(),
+            ),
+            "reply_hex" => compare_hex(
+                &assertion.operator,
+                reply,
+                assertion.value.as_str().unwrap_or_default(),
+            ),
+            "reply_length" => {
+                let expected = assertion.value.as_u64().unwrap_or_default() as usize;
+                match assertion.operator.as_str() {
+                    "eq" => Ok(reply.len() == expected),
+                    "gt" => Ok(reply.len() > expected),
+                    "lt" => Ok(reply.len() < expected),
+                    "gte" => Ok(reply.len() >= expected),
+                    "lte" => Ok(reply.len() <= expected),
+                    other => Err(unsupported_operator(other)),
+                }
+            }
+            other => {
+                return Some(format!(
+                    "Assertion type '{}' não suportado em tcp_send",
+                    other
+                ))
+            }
+        };
+        match ok {
+            Ok(true) => None,
+            Ok(false) => Some(format!(
+                "Assertion failed: {} {} {} (reply: {})",
+                assertion.assertion_type,
+                assertion.operator,
+                assertion.value,
+                hex(&reply[..reply.len().min(REPLY_PREVIEW_BYTES)])
+            )),
+            Err(e) => Some(format!("{} em {}", e, assertion.assertion_type)),
+        }
+    })
+}
+
+/// Comparação de texto compartilhada pelas assertions de saída (`reply_text`, `stdout`).
+///
+/// `Err` quando a assertion não pode ser avaliada (operador desconhecido,
+/// regex inválida), para não reportar isso como um simples "falhou".
+pub fn compare_text(operator: &str, actual: &str, expected: &str) -> Result<bool, String> {
+    Ok(match operator {
+        "eq" => actual == expected,
+        "neq" => actual != expected,
+        "contains" => actual.contains(expected),
+        "starts_with" => actual.starts_with(expected),
+        "ends_with" => actual.ends_with(expected),
+        "matches_regex" | "regex" => Regex::new(expected)
+            .map_err(|e| format!("Regex inválida '{}': {}", expected, e))?
+            .is_match(actual),
+        other => return Err(unsupported_operator(other)),
+    })
+}
+
+/// `reply_hex` compara bytes, não o texto hex: "0d0a" não casa
+/// com "x0d0ay" deslocado meio byte. Só a regex olha o texto hex.
+fn compare_hex(operator: &str, reply: &[u8], expected: &str) -> Result<bool, String> {
+    if matches!(operator, "matches_regex" | "regex") {
+        return compare_text(operator, &hex(reply), expected);
+    }
+    let expected = decode_payload(expected, "hex").map_err(|_| {
+        format!(
+            "Valor hex inválido '{}' (bytes em pares de dígitos)",
+            expected
+        )
+    })?;
+    Ok(match operator {
+        "eq" => reply == expected.as_slice(),
+        "neq" => reply != expected.as_slice(),
+        "contains" => {
+            expected.is_empty()
+                || reply
+                    .windows(expected.len())
+                    .any(|w| w == expected.as_slice())
+        }
+        "starts_with" => reply.starts_with(&expected),
+        "ends_with" => reply.ends_with(&expected),
+        other => return Err(unsupported_operator(other)),
+    })
+}
+
+/// Mensagem de operador desconhecido, compartilhada com `shell_command`.
+pub fn unsupported_operator(operator: &str) -> String {
+    format!("Operador '{}' não suportado", operator)
+}
+
+// ============================================================================
+// TCP EXECUTOR
+// ============================================================================
+
+/// Executor para a ação `tcp_send`.
+#[derive(Debug, Default)]
+pub struct TcpExecutor;
+
+impl TcpExecutor {
+    /// Cria um novo TcpExecutor.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Destino e bytes de um step de socket (`tcp_send`, `udp_send`).
+pub struct SocketRequest {
+    pub address: String,
+    pub payload: Vec<u8>,
+    pub timeout_ms: u64,
+    started: Instant,
+    context_before: HashMap<String, Value>,
+}
+
+impl SocketRequest {
+    /// Lê `host`, `port`, `data`, `encoding` e `timeout_ms` dos params.
+    pub fn from_params(params: &Value, context: &Context) -> Result<Self> {
+        let started = Instant::now();
+        let host = params
+            .get("host")
+            .and_then(|h| h.as_str())
+            .ok_or_else(|| anyhow!("Missing 'host' in params"))?;
+        let host = context.interpolate_str(host)?;
+        let port = params
+            .get("port")
+            .and_then(|p| p.as_u64())
+            .filter(|p| *p <= u16::MAX as u64)
+            .ok_or_else(|| anyhow!("Missing or invalid 'port' in params"))?;
+
+        let encoding = params
+            .get("encoding")
+            .and_then(|e| e.as_str())
+            .unwrap_or("text");
+        let data = params.get("data").and_then(|d| d.as_str()).unwrap_or("");
+        let timeout_ms = params
+            .get("timeout_ms")
+            .and_then(|t| t.as_u64())
+            .or_else(|| context.get("timeout_ms").and_then(|t| t.as_u64()))
+            .unwrap_or(5000);
+
+        Ok(Self {
+            address: format!("{}:{}", host, port),
+            payload: decode_payload(&context.interpolate_str(data)?, encoding)?,
+            timeout_ms,
+            started,
+            context_before: context.variables.clone(),
+        })
+    }
+
+    /// Resultado do step: assertions sobre a resposta e `socket_details`.
+    ///
+    /// Com sucesso, a resposta fica em `${<step_id>.reply}` e `${<step_id>.reply_hex}`.
+    pub fn into_result(
+        self,
+        protocol: &str,
+        step: &Step,
+        context: &mut Context,
+        exchange: Result<Vec<u8>>,
+        latency_ms: u64,
+    ) -> StepResult {
+        let (reply, error) = match exchange {
+            Ok(reply) => {
+                let failure = check_reply(&step.assertions, &reply);
+                (reply, failure)
+            }
+            Err(e) => (Vec::new(), Some(format!("{:#}", e))),
+        };
+
+        if error.is_none() {
+            context.set(
+                format!("{}.reply", step.id),
+                json!(String::from_utf8_lossy(&reply)),
+            );
+            context.set(format!("{}.reply_hex", step.id), Value::String(hex(&reply)));
+        }
+
+        info!(
+            protocol,
+            address = %self.address,
+            bytes_sent = self.payload.len(),
+            bytes_received = reply.len(),
+            latency_ms,
+            "Socket exchange finished"
+        );
+
+        StepResult {
+            step_id: step.id.clone(),
+            status: if error.is_some() {
+                StepStatus::Failed
+            } else {
+                StepStatus::Passed
+            },
+            duration_ms: self.started.elapsed().as_millis() as u64,
+            error,
+            context_before: Some(self.context_before),
+            context_after: Some(context.variables.clone()),
+            socket_details: Some(SocketDetails {
+                protocol: protocol.to_string(),
+                address: self.address,
+                bytes_sent: self.payload.len(),
+                bytes_received: reply.len(),
+                latency_ms,
+                reply_hex: hex(&reply[..reply.len().min(REPLY_PREVIEW_BYTES)]),
+            }),
+            ..Default::default()
+        }
+    }
+}
+
+/// JSON Schema dos params de `tcp_send` (ver `StepExecutor::params_schema`).
+pub fn params_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["host", "port"],
+        "properties": {
+            "host": { "type": "string" },
+            "port": { "type": "integer", "minimum": 1, "maximum": 65535 },
+            "data": { "type": "string" },
+            "encoding": { "type": "string", "enum": ["text", "hex", "base64"] },
+            "read_until": { "type": "string", "minLength": 1 },
+            "read_bytes": { "type": "integer", "minimum": 0 },
+            "timeout_ms": { "type": "integer", "minimum": 1 }
+        },
+        "additionalProperties": false
+    })
+}
+
+#[async_trait]
+impl StepExecutor for TcpExecutor {
+    fn can_handle(&self, action: &str) -> bool {
+        action == "tcp_send"
+    }
+
+    /// Schema dos params de `tcp_send`, usado na validação do plano.
+    fn params_schema(&self, _action: &str) -> Option<Value> {
+        Some(params_schema())
+    }
+
+    #[instrument(skip(self, context), fields(step_id = %step.id))]
+    async fn execute(&self, step: &Step, context: &mut Context) -> Result<StepResult> {
+        let params = &step.params;
+        let request = SocketRequest::from_params(params, context)?;
+
+        let until = match (params.get("read_until"), params.get("read_bytes")) {
+            (Some(delimiter), _) => {
+                let delimiter = delimiter.as_str().unwrap_or_default();
+                if delimiter.is_empty() {
+                    return Err(anyhow!("'read_until' não pode ser vazio"));
+                }
+                ReadUntil::Delimiter(delimiter.as_bytes().to_vec())
+            }
+            (None, Some(length)) => ReadUntil::Length(
+                length
+                    .as_u64()
+                    .ok_or_else(|| anyhow!("'read_bytes' deve ser um inteiro"))?
+                    as usize,
+            ),
+            (None, None) => ReadUntil::Close,
+        };
+        let deadline = tokio::time::Instant::now() + Duration::from_millis(request.timeout_ms);
+
+        let sent_at = Instant::now();
+        let exchange = async {
+            let address = &request.address;
+            let mut stream = timeout_at(deadline, TcpStream::connect(address))
+                .await
+                .map_err(|_| anyhow!("Timeout conectando em {}", address))?
+                .map_err(|e| anyhow!("Falha ao conectar em {}: {}", address, e))?;
+            stream.write_all(&request.payload).await?;
+            read_reply(&mut stream, &until, deadline).await
+        }
+        .await;
+        let latency_ms = sent_at.elapsed().as_millis() as u64;
+
+        Ok(request.into_result("tcp", step, context, exchange, latency_ms))
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    /// Servidor de linha: responde `PONG <resto>\r\n` a cada linha.
+    async fn line_server() -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 256];
+                let read = socket.read(&mut buf).await.unwrap();
+                let line = String::from_utf8_lossy(&buf[..read]).replace("PING", "PONG");
+                // Resposta em dois pedaços, com bytes extras depois do delimitador.
+                let (first, second) = line.split_at(2);
+                socket.write_all(first.as_bytes()).await.unwrap();
+                socket.write_all(second.as_bytes()).await.unwrap();
+                socket.write_all(b"EXTRA").await.unwrap();
+            }
+        });
+        port
+    }
+
+    fn assertion(kind: &str, operator: &str, value: Value) -> Assertion {
+        Assertion {
+            assertion_type: kind.to_string(),
+            operator: operator.to_string(),
+            value,
+            ..Default::default()
+        }
+    }
+
+    fn step(params: Value, assertions: Vec<Assertion>) -> Step {
+        Step {
+            id: "ping".to_string(),
+            action: "tcp_send".to_string(),
+            params,
+            assertions,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tcp_send_reads_until_delimiter() {
+        let port = line_server().await;
+        let mut context = Context::new();
+        context.set("client", json!("c1"));
+        let step = step(
+            json!({ "host": "127.0.0.1", "port": port, "data": "PING ${client}\r\n", "read_until": "\r\n" }),
+            vec![
+                assertion("reply_text", "eq", json!("PONG c1\r\n")),
+                assertion("reply_hex", "ends_with", json!("0D 0A")),
+                assertion("reply_length", "eq", json!(9)),
+            ],
+        );
+
+        let result = TcpExecutor::new()
+            .execute(&step, &mut context)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+        let details = result.socket_details.unwrap();
+        assert_eq!(details.bytes_sent, 9);
+        assert_eq!(details.bytes_received, 9);
+        assert_eq!(context.get("ping.reply"), Some(&json!("PONG c1\r\n")));
+    }
+
+    #[tokio::test]
+    async fn test_tcp_send_fails_on_timeout_and_mismatch() {
+        let port = line_server().await;
+        let mut context = Context::new();
+
+        let waiting = step(
+            json!({ "host": "127.0.0.1", "port": port, "data": "PING\n", "read_until": "##", "timeout_ms": 200 }),
+            vec![],
+        );
+        let result = TcpExecutor::new()
+            .execute(&waiting, &mut context)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Failed);
+        assert!(result.error.unwrap().contains("delimitador 2323"));
+
+        let mismatch = step(
+            json!({ "host": "127.0.0.1", "port": port, "encoding": "hex", "data": "50494e470a",
+                    "read_bytes": 4 }),
+            vec![assertion("reply_text", "matches_regex", json!("^PING"))],
+        );
+        let result = TcpExecutor::new()
+            .execute(&mismatch, &mut context)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Failed);
+        assert!(result.error.unwrap().contains("504f4e47"));
+    }
+
+    #[test]
+    fn test_reply_hex_compares_bytes() {
+        let reply = [0x10, 0xd0, 0xa1];
+        let check = |operator: &str, value: &str| {
+            check_reply(&[assertion("reply_hex", operator, json!(value))], &reply)
+        };
+        // "0d0a" aparece no texto hex "10d0a1", mas não nos bytes.
+        assert!(check("contains", "0d0a")
+            .unwrap()
+            .starts_with("Assertion failed"));
+        assert_eq!(check("contains", "D0 A1"), None);
+        assert!(check("ends_with", "a")
+            .unwrap()
+            .contains("Valor hex inválido"));
+        assert!(check("matches_regex", "(")
+            .unwrap()
+            .contains("Regex inválida"));
+    }
+
+    #[test]
+    fn test_decode_payload_encodings() {
+        assert_eq!(decode_payload("0a ff", "hex").unwrap(), vec![0x0a, 0xff]);
+        assert_eq!(decode_payload("Cv8=", "base64").unwrap(), vec![0x0a, 0xff]);
+        assert!(decode_payload("abc", "hex").is_err());
+        assert!(decode_payload("x", "utf16").is_err());
+    }
+}
@@ -0,0 +1,498 @@
+//! # Executor Transform - Remodelagem de Dados do Contexto
+//!
+//! Este executor aplica um caminho JSONPath e/ou uma operação simples a
+//! variáveis do contexto e grava o resultado em uma nova variável.
+//!
+//! ## Para todos entenderem:
+//!
+//! Uma resposta trouxe uma lista de itens com preços, mas queremos
+//! verificar o **total**. Em vez de pedir isso à API, o step `transform`
+//! pega a lista, soma os preços e salva em `${cart_sum}` para uma
+//! assertion posterior.
+//!
+//! ## Exemplo de uso no UTDL:
+//!
+//! ```json
+//! {
+//!   "id": "somar_precos",
+//!   "action": "transform",
+//!   "params": {
+//!     "from": "cart",
+//!     "path": "$.items[*].price",
+//!     "op": "sum",
+//!     "target": "cart_sum"
+//!   }
+//! }
+//! ```
+//!
+//! Vários transforms podem ser agrupados em `{ "transforms": [ ... ] }`.
+//! `from` também aceita uma lista de variáveis, que viram um array
+//! (ex: `"from": ["subtotal", "shipping"], "op": "sum"`).
+//!
+//! ## Operações suportadas:
+//!
+//! | Operação                    | Resultado                               |
+//! |-----------------------------|-----------------------------------------|
+//! | `sum`, `avg`, `min`, `max`  | Agregação numérica de um array          |
+//! | `count`                     | Tamanho de array, objeto ou string      |
+//! | `first`, `last`             | Primeiro/último elemento                |
+//! | `unique`, `sort`, `reverse` | Array reorganizado                      |
+//! | `flatten`                   | Achata um nível de arrays aninhados     |
+//! | `keys`, `values`            | Chaves/valores de um objeto             |
+//! | `join`                      | String unida por `separator` (padrão ,) |
+//! | `upper`, `lower`, `trim`    | Transformação de string                 |
+//! | `to_string`, `to_number`    | Conversão de tipo                       |
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Number, Value};
+use std::time::Instant;
+use tracing::{info, instrument};
+
+use crate::context::Context;
+use crate::extractors::navigate_json;
+use crate::protocol::{Step, StepResult, StepStatus};
+
+use super::StepExecutor;
+
+// ============================================================================
+// PARÂMETROS DO TRANSFORM
+// ============================================================================
+
+/// Origem do transform: uma variável ou uma lista delas.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum TransformSource {
+    /// Nome de uma variável do contexto.
+    Single(String),
+    /// Várias variáveis, combinadas em um array.
+    Many(Vec<String>),
+}
+
+/// Uma transformação individual.
+#[derive(Debug, Clone, Deserialize)]
+struct TransformSpec {
+    /// Variável(is) de origem.
+    from: TransformSource,
+
+    /// Caminho JSONPath aplicado ao valor de origem (opcional).
+    #[serde(default)]
+    path: Option<String>,
+
+    /// Operação aplicada após o path (opcional).
+    #[serde(default)]
+    op: Option<String>,
+
+    /// Separador usado por `join`.
+    #[serde(default)]
+    separator: Option<String>,
+
+    /// Nome da variável onde o resultado será salvo.
+    target: String,
+}
+
+/// Parâmetros da action: um transform ou uma lista deles.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum TransformParams {
+    /// `{ "transforms": [ ... ] }`
+    Many { transforms: Vec<TransformSpec> },
+    /// `{ "from": ..., "target": ... }`
+    Single(TransformSpec),
+}
+
+impl TransformParams {
+    fn into_specs(self) -> Vec<TransformSpec> {
+        match self {
+            Self::Many { transforms } => transforms,
+            Self::Single(spec) => vec![spec],
+        }
+    }
+}
+
+// ============================================================================
+// OPERAÇÕES
+// ============================================================================
+
+/// Converte um f64 em Value, preferindo inteiro quando não há parte decimal.
+fn number_value(n: f64) -> Value {
+    if n.fract() == 0.0 && n.abs() < i64::MAX as f64 {
+        Value::from(n as i64)
+    } else {
+        Number::from_f64(n)
+            .map(Value::Number)
+            .unwrap_or(Value::Null)
+    }
+}
+
+/// Extrai os números de um array (erro se algum elemento não for numérico).
+fn numbers(op: &str, value: &Value) -> Result<Vec<f64>> {
+    let items = value
+        .as_array()
+        .ok_or_else(|| anyhow!("Operação '{}' requer um array, encontrado: {}", op, value))?;
+
+    items
+        .iter()
+        .map(|item| {
+            item.as_f64()
+                .or_else(|| item.as_str().and_then(|s| s.trim().parse().ok()))
+                .ok_or_else(|| anyhow!("Operação '{}': valor não numérico {}", op, item))
+        })
+        .collect()
+}
+
+/// Exige um array para a operação.
+fn array<'a>(op: &str, value: &'a Value) -> Result<&'a Vec<Value>> {
+    value
+        .as_array()
+        .ok_or_else(|| anyhow!("Operação '{}' requer um array, encontrado: {}", op, value))
+}
+
+/// Exige uma string para a operação.
+fn string<'a>(op: &str, value: &'a Value) -> Result<&'a str> {
+    value
+        .as_str()
+        .ok_or_else(|| anyhow!("Operação '{}' requer uma string, encontrado: {}", op, value))
+}
+
+/// Aplica uma operação a um valor.
+fn apply_op(op: &str, value: Value, separator: Option<&str>) -> Result<Value> {
+    let result = match op {
+        "sum" => number_value(numbers(op, &value)?.iter().sum()),
+        "avg" => {
+            let nums = numbers(op, &value)?;
+            if nums.is_empty() {
+                Value::Null
+            } else {
+                number_value(nums.iter().sum::<f64>() / nums.len() as f64)
+            }
+        }
+        "min" => numbers(op, &value)?
+            .into_iter()
+            .reduce(f64::min)
+            .map(number_value)
+            .unwrap_or(Value::Null),
+        "max" => numbers(op, &value)?
+            .into_iter()
+            .reduce(f64::max)
+            .map(number_value)
+            .unwrap_or(Value::Null),
+        "count" => match &value {
+            Value::Array(items) => Value::from(items.len()),
+            Value::Object(map) => Value::from(map.len()),
+            Value::String(s) => Value::from(s.chars().count()),
+            _ => return Err(anyhow!("Operação 'count' não aplicável a {}", value)),
+        },
+        "first" => array(op, &value)?.first().cloned().unwrap_or(Value::Null),
+        "last" => array(op, &value)?.last().cloned().unwrap_or(Value::Null),
+        "unique" => {
+            let mut unique: Vec<Value> = Vec::new();
+            for item in array(op, &value)? {
+                if !unique.contains(item) {
+                    unique.push(item.clone());
+                }
+            }
+            Value::Array(unique)
+        }
+        "sort" => {
+            let mut items = array(op, &value)?.clone();
+            items.sort_by(|a, b| match (a.as_f64(), b.as_f64()) {
+                (Some(x), Some(y)) => x.partial_cmp(&y).unwrap_or(std::cmp::Ordering::Equal),
+                _ => a.to_string().cmp(&b.to_string()),
+            });
+            Value::Array(items)
+        }
+        "reverse" => {
+            let mut items = array(op, &value)?.clone();
+            items.reverse();
+            Value::Array(items)
+        }
+        "flatten" => Value::Array(
+            array(op, &value)?
+                .iter()
+                .flat_map(|item| match item {
+                    Value::Array(inner) => inner.clone(),
+                    other => vec![other.clone()],
+                })
+                .collect(),
+        ),
+        "keys" => match &value {
+            Value::Object(map) => Value::Array(map.keys().cloned().map(Value::String).collect()),
+            _ => {
+                return Err(anyhow!(
+                    "Operação 'keys' requer um objeto, encontrado: {}",
+                    value
+                ))
+            }
+        },
+        "values" => match &value {
+            Value::Object(map) => Value::Array(map.values().cloned().collect()),
+            _ => {
+                return Err(anyhow!(
+                    "Operação 'values' requer um objeto, encontrado: {}",
+                    value
+                ))
+            }
+        },
+        "join" => Value::String(
+            array(op, &value)?
+                .iter()
+                .map(|item| match item {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(separator.unwrap_or(",")),
+        ),
+        "upper" => Value::String(string(op, &value)?.to_uppercase()),
+        "lower" => Value::String(string(op, &value)?.to_lowercase()),
+        "trim" => Value::String(string(op, &value)?.trim().to_string()),
+        "to_string" => match &value {
+            Value::String(_) => value,
+            other => Value::String(other.to_string()),
+        },
+        "to_number" => match &value {
+            Value::Number(_) => value,
+            Value::String(s) => s
+                .trim()
+                .parse::<f64>()
+                .map(number_value)
+                .map_err(|_| anyhow!("Operação 'to_number': '{}' não é numérico", s))?,
+            _ => return Err(anyhow!("Operação 'to_number' não aplicável a {}", value)),
+        },
+        other => return Err(anyhow!("Operação de transform desconhecida: '{}'", other)),
+    };
+
+    Ok(result)
+}
+
+// ============================================================================
+// TRANSFORM EXECUTOR
+// ============================================================================
+
+/// Executor para a ação `transform`.
+///
+/// Não possui estado, então pode ser usado em execução paralela.
+#[derive(Debug, Default)]
+pub struct TransformExecutor;
+
+impl TransformExecutor {
+    /// Cria um novo TransformExecutor.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Calcula o resultado de um transform a partir do contexto.
+    fn evaluate(spec: &TransformSpec, context: &Context) -> Result<Value> {
+        let lookup = |name: &str| {
+            context
+                .get(name)
+                .cloned()
+                .ok_or_else(|| anyhow!("Variável '{}' não encontrada no contexto", name))
+        };
+
+        let source = match &spec.from {
+            TransformSource::Single(name) => lookup(name)?,
+            TransformSource::Many(names) => Value::Array(
+                names
+                    .iter()
+                    .map(|name| lookup(name))
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+        };
+
+        let selected = match &spec.path {
+            Some(path) => navigate_json(&source, path)?,
+            None => source,
+        };
+
+        match &spec.op {
+            Some(op) => apply_op(op, selected, spec.separator.as_deref()),
+            None => Ok(selected),
+        }
+    }
+}
+
+/// JSON Schema dos params de `transform`: um transform ou a lista `transforms`.
+pub fn params_schema() -> Value {
+    let spec = json!({
+        "from": {
+            "anyOf": [
+                { "type": "string" },
+                { "type": "array", "items": { "type": "string" } }
+            ]
+        },
+        "path": { "type": "string" },
+        "op": { "type": "string" },
+        "separator": { "type": "string" },
+        "target": { "type": "string" }
+    });
+    let mut properties = spec.clone();
+    properties["transforms"] = json!({
+        "type": "array",
+        "items": { "type": "object", "properties": spec, "additionalProperties": false }
+    });
+    json!({
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false
+    })
+}
+
+// ============================================================================
+// IMPLEMENTAÇÃO DO TRAIT
+// ============================================================================
+
+#[async_trait]
+impl StepExecutor for TransformExecutor {
+    fn can_handle(&self, action: &str) -> bool {
+        action == "transform"
+    }
+
+    /// Schema dos params de `transform`, usado na validação do plano.
+    fn params_schema(&self, _action: &str) -> Option<Value> {
+        Some(params_schema())
+    }
+
+    /// Executa os transforms em ordem.
+    ///
+    /// Cada transform enxerga os resultados dos anteriores do mesmo step.
+    #[instrument(skip(self, context), fields(step_id = %step.id))]
+    async fn execute(&self, step: &Step, context: &mut Context) -> Result<StepResult> {
+        let start = Instant::now();
+        let context_before = context.variables.clone();
+
+        let params: TransformParams = serde_json::from_value(step.params.clone()).map_err(|e| {
+            anyhow!(
+                "Parâmetros inválidos para transform: {}. Esperado: {{ \"from\": ..., \"target\": ... }} ou {{ \"transforms\": [...] }}",
+                e
+            )
+        })?;
+
+        for spec in params.into_specs() {
+            let value = Self::evaluate(&spec, context)
+                .map_err(|e| anyhow!("Transform para '{}' falhou: {}", spec.target, e))?;
+
+            info!(
+                step_id = %step.id,
+                target = %spec.target,
+                op = spec.op.as_deref().unwrap_or("-"),
+                "🔧 Transform aplicado"
+            );
+
+            context.set(spec.target.clone(), value);
+        }
+
+        Ok(StepResult {
+            step_id: step.id.clone(),
+            status: StepStatus::Passed,
+            duration_ms: start.elapsed().as_millis() as u64,
+            context_before: Some(context_before),
+            context_after: Some(context.variables.clone()),
+            ..Default::default()
+        })
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn create_step(params: Value) -> Step {
+        Step {
+            id: "reshape".to_string(),
+            description: None,
+            depends_on: vec![],
+            action: "transform".to_string(),
+            params,
+            assertions: vec![],
+            extract: vec![],
+            recovery_policy: None,
+            ..Default::default()
+        }
+    }
+
+    fn create_context() -> Context {
+        let mut context = Context::new();
+        context.set(
+            "cart",
+            json!({ "items": [
+                { "id": "a", "price": 10.5 },
+                { "id": "b", "price": 4.5 },
+                { "id": "a", "price": 5 }
+            ]}),
+        );
+        context
+    }
+
+    #[tokio::test]
+    async fn test_transform_sums_prices() {
+        let executor = TransformExecutor::new();
+        let mut context = create_context();
+
+        let step = create_step(json!({
+            "from": "cart",
+            "path": "$.items[*].price",
+            "op": "sum",
+            "target": "cart_sum"
+        }));
+
+        let result = executor.execute(&step, &mut context).await.unwrap();
+        assert_eq!(result.status, StepStatus::Passed);
+        assert_eq!(context.get("cart_sum"), Some(&json!(20)));
+    }
+
+    #[tokio::test]
+    async fn test_transform_chain_of_transforms() {
+        let executor = TransformExecutor::new();
+        let mut context = create_context();
+
+        let step = create_step(json!({
+            "transforms": [
+                { "from": "cart", "path": "items[*].id", "op": "unique", "target": "ids" },
+                { "from": "ids", "op": "join", "separator": "|", "target": "ids_csv" }
+            ]
+        }));
+
+        executor.execute(&step, &mut context).await.unwrap();
+        assert_eq!(context.get("ids"), Some(&json!(["a", "b"])));
+        assert_eq!(context.get("ids_csv"), Some(&json!("a|b")));
+    }
+
+    #[tokio::test]
+    async fn test_transform_combines_multiple_sources() {
+        let executor = TransformExecutor::new();
+        let mut context = Context::new();
+        context.set("subtotal", json!(90));
+        context.set("shipping", json!("9.9"));
+
+        let step = create_step(json!({
+            "from": ["subtotal", "shipping"],
+            "op": "sum",
+            "target": "total"
+        }));
+
+        executor.execute(&step, &mut context).await.unwrap();
+        assert_eq!(context.get("total"), Some(&json!(99.9)));
+    }
+
+    #[tokio::test]
+    async fn test_transform_missing_variable_fails() {
+        let executor = TransformExecutor::new();
+        let mut context = Context::new();
+
+        let step = create_step(json!({ "from": "nope", "op": "count", "target": "n" }));
+        assert!(executor.execute(&step, &mut context).await.is_err());
+    }
+
+    #[test]
+    fn test_apply_op_unknown_fails() {
+        assert!(apply_op("explode", json!([1]), None).is_err());
+    }
+}
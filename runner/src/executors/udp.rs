@@ -0,0 +1,240 @@
+//! # Executor UDP - Datagramas para Health Probes
+//!
+//! Este executor envia um datagrama UDP e, opcionalmente, espera um datagrama
+//! de resposta, para checar serviços sem conexão (DNS, syslog, backends de
+//! jogos) gerenciados pela plataforma.
+//!
+//! ## Para todos entenderem:
+//!
+//! UDP não tem conexão nem confirmação: o datagrama sai e pode sumir no
+//! caminho. Por isso o step diz se espera resposta e por quanto tempo:
+//!
+//! ```json
+//! {
+//!   "id": "game_ping",
+//!   "action": "udp_send",
+//!   "params": {
+//!     "host": "game.internal", "port": 27015,
+//!     "data": "ff ff ff ff 54", "encoding": "hex",
+//!     "timeout_ms": 1000
+//!   },
+//!   "assertions": [
+//!     { "type": "reply_hex", "operator": "starts_with", "value": "ffffffff49" }
+//!   ]
+//! }
+//! ```
+//!
+//! | Parâmetro                  | Comportamento                                    |
+//! |----------------------------|--------------------------------------------------|
+//! | `await_reply: true` (padrão) | Espera um datagrama; falha se o timeout vencer |
+//! | `await_reply: false`       | Só envia (ex: syslog); passa se o envio der certo |
+//!
+//! `data`, `encoding`, as assertions (`reply_text`, `reply_hex`,
+//! `reply_length`) e as variáveis `${<step_id>.reply}` são as mesmas do
+//! `tcp_send` (ver `tcp`).
+//!
+//! ## Detalhe:
+//!
+//! O socket é "conectado" ao destino, então um ICMP port unreachable do
+//! servidor aparece como erro de recebimento em vez de um timeout mudo.
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+use tokio::net::{lookup_host, UdpSocket};
+use tokio::time::timeout_at;
+use tracing::instrument;
+
+use crate::context::Context;
+use crate::protocol::{Step, StepResult};
+
+use super::tcp::SocketRequest;
+use super::StepExecutor;
+
+/// Maior datagrama UDP possível.
+const MAX_DATAGRAM_BYTES: usize = 65_535;
+
+// ============================================================================
+// UDP EXECUTOR
+// ============================================================================
+
+/// Executor para a ação `udp_send`.
+#[derive(Debug, Default)]
+pub struct UdpExecutor;
+
+impl UdpExecutor {
+    /// Cria um novo UdpExecutor.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// JSON Schema dos params de `udp_send` (ver `StepExecutor::params_schema`).
+pub fn params_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["host", "port"],
+        "properties": {
+            "host": { "type": "string" },
+            "port": { "type": "integer", "minimum": 1, "maximum": 65535 },
+            "data": { "type": "string" },
+            "encoding": { "type": "string", "enum": ["text", "hex", "base64"] },
+            "await_reply": { "type": "boolean" },
+            "timeout_ms": { "type": "integer", "minimum": 1 }
+        },
+        "additionalProperties": false
+    })
+}
+
+#[async_trait]
+impl StepExecutor for UdpExecutor {
+    fn can_handle(&self, action: &str) -> bool {
+        action == "udp_send"
+    }
+
+    /// Schema dos params de `udp_send`, usado na validação do plano.
+    fn params_schema(&self, _action: &str) -> Option<Value> {
+        Some(params_schema())
+    }
+
+    #[instrument(skip(self, context), fields(step_id = %step.id))]
+    async fn execute(&self, step: &Step, context: &mut Context) -> Result<StepResult> {
+        let request = SocketRequest::from_params(&step.params, context)?;
+        let await_reply = step
+            .params
+            .get("await_reply")
+            .and_then(|a| a.as_bool())
+            .unwrap_or(true);
+        let deadline = tokio::time::Instant::now() + Duration::from_millis(request.timeout_ms);
+
+        let sent_at = Instant::now();
+        let exchange = async {
+            let address = &request.address;
+            let target = timeout_at(deadline, lookup_host(address))
+                .await
+                .map_err(|_| anyhow!("Timeout resolvendo {}", address))??
+                .next()
+                .ok_or_else(|| anyhow!("Nenhum endereço para {}", address))?;
+            let local = if target.is_ipv4() {
+                "0.0.0.0:0"
+            } else {
+                "[::]:0"
+            };
+            let socket = UdpSocket::bind(local).await?;
+            socket.connect(target).await?;
+            socket.send(&request.payload).await?;
+            if !await_reply {
+                return Ok(Vec::new());
+            }
+
+            let mut buf = vec![0u8; MAX_DATAGRAM_BYTES];
+            let read = timeout_at(deadline, socket.recv(&mut buf))
+                .await
+                .map_err(|_| {
+                    anyhow!(
+                        "Timeout de {}ms aguardando resposta de {}",
+                        request.timeout_ms,
+                        address
+                    )
+                })?
+                .map_err(|e| anyhow!("Falha ao receber de {}: {}", address, e))?;
+            buf.truncate(read);
+            Ok(buf)
+        }
+        .await;
+        let latency_ms = sent_at.elapsed().as_millis() as u64;
+
+        Ok(request.into_result("udp", step, context, exchange, latency_ms))
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{Assertion, StepStatus};
+    use serde_json::{json, Value};
+
+    /// Servidor de eco: devolve cada datagrama em maiúsculas.
+    async fn echo_server() -> u16 {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let port = socket.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let mut buf = [0u8; 512];
+            while let Ok((read, peer)) = socket.recv_from(&mut buf).await {
+                let reply = buf[..read].to_ascii_uppercase();
+                socket.send_to(&reply, peer).await.unwrap();
+            }
+        });
+        port
+    }
+
+    fn step(params: Value, assertions: Vec<Assertion>) -> Step {
+        Step {
+            id: "probe".to_string(),
+            action: "udp_send".to_string(),
+            params,
+            assertions,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_udp_send_awaits_reply() {
+        let port = echo_server().await;
+        let mut context = Context::new();
+        let step = step(
+            json!({ "host": "127.0.0.1", "port": port, "data": "ping" }),
+            vec![Assertion {
+                assertion_type: "reply_text".to_string(),
+                operator: "eq".to_string(),
+                value: json!("PING"),
+                ..Default::default()
+            }],
+        );
+
+        let result = UdpExecutor::new()
+            .execute(&step, &mut context)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+        let details = result.socket_details.unwrap();
+        assert_eq!(details.protocol, "udp");
+        assert_eq!(details.bytes_received, 4);
+        assert_eq!(context.get("probe.reply"), Some(&json!("PING")));
+    }
+
+    #[tokio::test]
+    async fn test_udp_send_without_reply_and_timeout() {
+        // Socket que recebe e nunca responde.
+        let silent = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let port = silent.local_addr().unwrap().port();
+        let mut context = Context::new();
+
+        let fire_and_forget = step(
+            json!({ "host": "127.0.0.1", "port": port, "data": "<14>hello", "await_reply": false }),
+            vec![],
+        );
+        let result = UdpExecutor::new()
+            .execute(&fire_and_forget, &mut context)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+
+        let waiting = step(
+            json!({ "host": "127.0.0.1", "port": port, "data": "ping", "timeout_ms": 150 }),
+            vec![],
+        );
+        let result = UdpExecutor::new()
+            .execute(&waiting, &mut context)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Failed);
+        assert!(result.error.unwrap().contains("Timeout de 150ms"));
+        drop(silent);
+    }
+}
@@ -0,0 +1,459 @@
+//! # Operadores Semânticos - Versões, Datas e Formatos
+//!
+//! Operadores de assertion que entendem o **tipo** do valor, usados por
+//! `json_body` (HTTP) e `variable` (ação `assert`).
+//!
+//! ## Para todos entenderem:
+//!
+//! Validar um e-mail ou uma data com `matches_regex` vira uma regex frágil
+//! copiada de plano em plano. Estes operadores fazem a validação certa:
+//!
+//! | Operador      | `value`                   | Passa quando                             |
+//! |---------------|---------------------------|------------------------------------------|
+//! | `semver_gte`  | `"2.1.0"`                 | Versão ≥ 2.1.0 (`2.1.0-rc.1` < `2.1.0`)  |
+//! | `date_before` | `"2025-01-01"` ou `"now"` | Data/hora anterior à informada           |
+//! | `date_after`  | `"2024-06-01T00:00:00Z"`  | Data/hora posterior à informada          |
+//! | `uuid_valid`  | `true` / `false`          | É (ou não é) um UUID canônico            |
+//! | `email_valid` | `true` / `false`          | É (ou não é) um e-mail válido            |
+//! | `url_valid`   | `true` / `false`          | É (ou não é) uma URL absoluta com host   |
+//! | `matches_subset` | objeto/array parcial   | O valor contém o esperado (em profundidade) |
+//!
+//! `approx_eq` compara números com a `tolerance` da assertion
+//! (`{ "abs": 0.01 }` e/ou `{ "rel": 0.001 }`); strings numéricas como
+//! `"19.99"` (decimais serializados como texto) também valem.
+//!
+//! Em `matches_subset`, objetos precisam ter as chaves esperadas (outras
+//! chaves são ignoradas) e cada item de um array esperado precisa casar
+//! com um item **diferente** do array atual, em qualquer ordem.
+//!
+//! Datas aceitam RFC 3339 (`2024-06-01T12:00:00Z`) ou só a data
+//! (`2024-06-01`, meia-noite UTC).
+//!
+//! APIs localizadas devolvem `"1.234,56"` ou `"01/06/2024"`. Com `locale`
+//! na assertion, o valor atual é convertido antes de qualquer operador:
+//!
+//! | `locale`                         | Atual           | Comparado como  |
+//! |----------------------------------|-----------------|-----------------|
+//! | `{ "decimal": "," }`             | `"R$ 1.234,56"` | `1234.56`       |
+//! | `{ "decimal": "." }`             | `"1,234.5"`     | `1234.5`        |
+//! | `{ "date_format": "%d/%m/%Y" }`  | `"01/06/2024"`  | `"2024-06-01"`  |
+//!
+//! Strings que não estão no formato informado ficam como vieram.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::{Number, Value};
+use std::borrow::Cow;
+use std::cmp::Ordering;
+
+use crate::protocol::{AssertionLocale, Tolerance};
+
+/// Tolerância padrão de `approx_eq` sem `tolerance` (erro de ponto flutuante).
+const DEFAULT_ABS_TOLERANCE: f64 = 1e-9;
+
+/// E-mail pragmático: `local@dominio.tld`, sem espaços.
+static EMAIL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^[A-Za-z0-9.!#$%&'*+/=?^_`{|}~-]+@[A-Za-z0-9](?:[A-Za-z0-9-]*[A-Za-z0-9])?(?:\.[A-Za-z0-9](?:[A-Za-z0-9-]*[A-Za-z0-9])?)+$")
+        .expect("regex de e-mail válida")
+});
+
+/// Avalia um operador semântico. Retorna `None` se o operador não for deste módulo.
+pub fn evaluate_semantic(operator: &str, actual: &Value, expected: &Value) -> Option<bool> {
+    let text = actual.as_str();
+    let passed = match operator {
+        "semver_gte" => match (
+            text.and_then(parse_semver),
+            expected.as_str().and_then(parse_semver),
+        ) {
+            (Some(a), Some(b)) => compare_semver(&a, &b) != Ordering::Less,
+            _ => false,
+        },
+        "date_before" | "date_after" => {
+            match (text.and_then(parse_date), parse_expected_date(expected)) {
+                (Some(a), Some(b)) if operator == "date_before" => a < b,
+                (Some(a), Some(b)) => a > b,
+                _ => false,
+            }
+        }
+        "uuid_valid" => expect_validity(expected, text.is_some_and(is_uuid)),
+        "email_valid" => expect_validity(expected, text.is_some_and(|t| EMAIL.is_match(t))),
+        "url_valid" => expect_validity(expected, text.is_some_and(is_url)),
+        "matches_subset" => is_subset(actual, expected),
+        _ => return None,
+    };
+    Some(passed)
+}
+
+/// Igualdade numérica com tolerância absoluta e/ou relativa.
+pub fn approx_eq(actual: &Value, expected: &Value, tolerance: Option<&Tolerance>) -> bool {
+    let number = |v: &Value| v.as_f64().or_else(|| v.as_str()?.trim().parse().ok());
+    let (Some(a), Some(e)) = (number(actual), number(expected)) else {
+        return false;
+    };
+    let abs =
+        tolerance
+            .and_then(|t| t.abs)
+            .unwrap_or(if tolerance.is_some_and(|t| t.rel.is_some()) {
+                0.0
+            } else {
+                DEFAULT_ABS_TOLERANCE
+            });
+    let rel = tolerance.and_then(|t| t.rel).unwrap_or(0.0) * a.abs().max(e.abs());
+    (a - e).abs() <= abs.max(rel)
+}
+
+/// True se `actual` contém `expected` (subconjunto em profundidade).
+pub fn is_subset(actual: &Value, expected: &Value) -> bool {
+    match (actual, expected) {
+        (Value::Object(actual), Value::Object(expected)) => expected
+            .iter()
+            .all(|(key, e)| actual.get(key).is_some_and(|a| is_subset(a, e))),
+        (Value::Array(actual), Value::Array(expected)) => {
+            // Cada esperado precisa de um item atual próprio: emparelhamento
+            // bipartido, pois o primeiro item que casa pode ser o único de outro.
+            let fits: Vec<Vec<bool>> = expected
+                .iter()
+                .map(|e| actual.iter().map(|a| is_subset(a, e)).collect())
+                .collect();
+            let mut owner: Vec<Option<usize>> = vec![None; actual.len()];
+            (0..expected.len()).all(|e| {
+                let mut visited = vec![false; actual.len()];
+                assign(e, &fits, &mut owner, &mut visited)
+            })
+        }
+        (Value::Number(a), Value::Number(e)) => a.as_f64() == e.as_f64(),
+        _ => actual == expected,
+    }
+}
+
+/// Caminho aumentante de Kuhn: dá ao esperado `e` um item atual livre,
+/// realocando (recursivamente) quem já ocupava o item.
+fn assign(e: usize, fits: &[Vec<bool>], owner: &mut [Option<usize>], visited: &mut [bool]) -> bool {
+    for a in 0..owner.len() {
+        if !fits[e][a] || visited[a] {
+            continue;
+        }
+        visited[a] = true;
+        if owner[a].is_none_or(|other| assign(other, fits, owner, visited)) {
+            owner[a] = Some(e);
+            return true;
+        }
+    }
+    false
+}
+
+/// `value: false` pede um valor inválido; qualquer outro valor, válido.
+fn expect_validity(expected: &Value, valid: bool) -> bool {
+    valid != (expected == &Value::Bool(false))
+}
+
+fn is_uuid(text: &str) -> bool {
+    text.len() == 36 && uuid::Uuid::parse_str(text).is_ok()
+}
+
+fn is_url(text: &str) -> bool {
+    reqwest::Url::parse(text).is_ok_and(|url| url.has_host())
+}
+
+// ============================================================================
+// DATAS
+// ============================================================================
+
+fn parse_date(text: &str) -> Option<DateTime<Utc>> {
+    if let Ok(date) = DateTime::parse_from_rfc3339(text) {
+        return Some(date.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|d| d.and_utc())
+}
+
+fn parse_expected_date(expected: &Value) -> Option<DateTime<Utc>> {
+    match expected.as_str()? {
+        "now" => Some(Utc::now()),
+        text => parse_date(text),
+    }
+}
+
+// ============================================================================
+// LOCALE
+// ============================================================================
+
+/// Converte o valor atual do formato regional para o canônico (número ou data ISO).
+pub fn localize<'a>(actual: &'a Value, locale: Option<&AssertionLocale>) -> Cow<'a, Value> {
+    let (Some(locale), Some(text)) = (locale, actual.as_str()) else {
+        return Cow::Borrowed(actual);
+    };
+    let text = text.trim();
+    if let Some(date) = locale
+        .date_format
+        .as_deref()
+        .and_then(|format| parse_local_date(text, format))
+    {
+        return Cow::Owned(Value::String(date));
+    }
+    match locale
+        .decimal
+        .as_deref()
+        .and_then(|decimal| parse_local_number(text, decimal))
+    {
+        Some(number) => Cow::Owned(Value::Number(number)),
+        None => Cow::Borrowed(actual),
+    }
+}
+
+/// `"R$ 1.234,56"` com decimal `","` → `1234.56`; `"1.234,00"` → `1234` (inteiro).
+fn parse_local_number(text: &str, decimal: &str) -> Option<Number> {
+    let decimal = decimal.chars().next()?;
+    // Símbolos de moeda/unidade nas pontas ("R$ ", " €", " kg").
+    let digits = text.trim_matches(|c: char| !c.is_ascii_digit() && c != '-' && c != '+');
+    let mut canonical = String::with_capacity(digits.len());
+    for c in digits.chars() {
+        match c {
+            c if c == decimal => canonical.push('.'),
+            '0'..='9' | '-' | '+' => canonical.push(c),
+            '.' | ',' | ' ' | '\u{a0}' | '\u{202f}' | '\'' => {} // milhar
+            _ => return None,
+        }
+    }
+    let value: f64 = canonical.parse().ok()?;
+    if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+        return Some(Number::from(value as i64));
+    }
+    Number::from_f64(value)
+}
+
+/// Data no `format` informado → `"2024-06-01"` (ou RFC 3339 se tiver hora).
+fn parse_local_date(text: &str, format: &str) -> Option<String> {
+    if let Ok(date_time) = NaiveDateTime::parse_from_str(text, format) {
+        return Some(date_time.and_utc().to_rfc3339());
+    }
+    NaiveDate::parse_from_str(text, format)
+        .ok()
+        .map(|date| date.format("%Y-%m-%d").to_string())
+}
+
+// ============================================================================
+// SEMVER
+// ============================================================================
+
+/// (major, minor, patch, pré-release).
+type SemVer = (u64, u64, u64, Vec<String>);
+
+fn parse_semver(text: &str) -> Option<SemVer> {
+    let text = text.trim().trim_start_matches('v');
+    let text = text.split('+').next()?; // build metadata não conta
+    let (core, pre) = match text.split_once('-') {
+        Some((core, pre)) => (core, pre.split('.').map(String::from).collect()),
+        None => (text, Vec::new()),
+    };
+    let mut parts = core.split('.').map(|p| p.parse::<u64>());
+    let major = parts.next()?.ok()?;
+    let minor = parts.next().unwrap_or(Ok(0)).ok()?;
+    let patch = parts.next().unwrap_or(Ok(0)).ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch, pre))
+}
+
+/// Ordem do semver 2.0: sem pré-release > com pré-release; identificadores
+/// numéricos comparam como número e vêm antes dos alfanuméricos.
+fn compare_semver(a: &SemVer, b: &SemVer) -> Ordering {
+    (a.0, a.1, a.2)
+        .cmp(&(b.0, b.1, b.2))
+        .then_with(|| match (a.3.is_empty(), b.3.is_empty()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => {
+                for (x, y) in a.3.iter().zip(&b.3) {
+                    let order = match (x.parse::<u64>(), y.parse::<u64>()) {
+                        (Ok(x), Ok(y)) => x.cmp(&y),
+                        (Ok(_), Err(_)) => Ordering::Less,
+                        (Err(_), Ok(_)) => Ordering::Greater,
+                        (Err(_), Err(_)) => x.cmp(y),
+                    };
+                    if order != Ordering::Equal {
+                        return order;
+                    }
+                }
+                a.3.len().cmp(&b.3.len())
+            }
+        })
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn check(operator: &str, actual: Value, expected: Value) -> bool {
+        evaluate_semantic(operator, &actual, &expected).unwrap()
+    }
+
+    #[test]
+    fn test_semver_gte() {
+        assert!(check("semver_gte", json!("2.10.0"), json!("2.9.1")));
+        assert!(check("semver_gte", json!("v2.1.0"), json!("2.1.0")));
+        assert!(!check("semver_gte", json!("2.1.0-rc.1"), json!("2.1.0")));
+        assert!(check(
+            "semver_gte",
+            json!("2.1.0-rc.10"),
+            json!("2.1.0-rc.2")
+        ));
+        assert!(!check("semver_gte", json!("not-a-version"), json!("1.0.0")));
+    }
+
+    #[test]
+    fn test_dates() {
+        assert!(check(
+            "date_before",
+            json!("2024-06-01T12:00:00-03:00"),
+            json!("2024-06-01T16:00:00Z")
+        ));
+        assert!(check(
+            "date_after",
+            json!("2024-06-02"),
+            json!("2024-06-01T23:59:59Z")
+        ));
+        assert!(check("date_before", json!("2000-01-01"), json!("now")));
+        assert!(!check(
+            "date_after",
+            json!("yesterday"),
+            json!("2000-01-01")
+        ));
+    }
+
+    #[test]
+    fn test_approx_eq_tolerances() {
+        let abs = Tolerance {
+            abs: Some(0.01),
+            rel: None,
+        };
+        assert!(approx_eq(&json!(0.1 + 0.2), &json!(0.3), None));
+        assert!(approx_eq(&json!(59.969), &json!(59.97), Some(&abs)));
+        assert!(approx_eq(&json!("59.975"), &json!(59.97), Some(&abs)));
+        assert!(!approx_eq(&json!(59.99), &json!(59.97), Some(&abs)));
+
+        let rel = Tolerance {
+            abs: None,
+            rel: Some(0.001),
+        };
+        assert!(approx_eq(&json!(100_050.0), &json!(100_000), Some(&rel)));
+        assert!(!approx_eq(&json!(0.0011), &json!(0.001), Some(&rel)));
+        assert!(!approx_eq(&json!("abc"), &json!(1), None));
+    }
+
+    #[test]
+    fn test_localized_numbers_and_dates() {
+        let pt_br = AssertionLocale {
+            decimal: Some(",".to_string()),
+            date_format: Some("%d/%m/%Y".to_string()),
+        };
+        let local = |v: Value| localize(&v, Some(&pt_br)).into_owned();
+        assert_eq!(local(json!("1.234,56")), json!(1234.56));
+        assert_eq!(local(json!("R$ 1.234,56")), json!(1234.56));
+        assert_eq!(local(json!("-1.000")), json!(-1000));
+        assert_eq!(local(json!("01/06/2024")), json!("2024-06-01"));
+        assert_eq!(local(json!("pendente")), json!("pendente"));
+        assert_eq!(local(json!(42)), json!(42));
+
+        let en_us = AssertionLocale {
+            decimal: Some(".".to_string()),
+            date_format: Some("%m/%d/%Y %H:%M".to_string()),
+        };
+        let local = |v: Value| localize(&v, Some(&en_us)).into_owned();
+        assert_eq!(local(json!("1,234.5")), json!(1234.5));
+        assert_eq!(
+            local(json!("06/01/2024 14:30")),
+            json!("2024-06-01T14:30:00+00:00")
+        );
+        assert!(check(
+            "date_before",
+            local(json!("06/01/2024 14:30")),
+            json!("2024-06-02")
+        ));
+        assert_eq!(
+            localize(&json!("1.234,56"), None).into_owned(),
+            json!("1.234,56")
+        );
+    }
+
+    #[test]
+    fn test_matches_subset() {
+        let order = json!({
+            "id": 42,
+            "status": "PAID",
+            "customer": { "id": 7, "name": "Ana", "email": "ana@mail.com" },
+            "items": [
+                { "sku": "A1", "qty": 2, "price": 10.0 },
+                { "sku": "B2", "qty": 1, "price": 5.5 }
+            ]
+        });
+        let expected = json!({
+            "status": "PAID",
+            "customer": { "name": "Ana" },
+            "items": [{ "sku": "B2" }, { "sku": "A1", "price": 10 }]
+        });
+        assert!(check("matches_subset", order.clone(), expected));
+
+        assert!(!check(
+            "matches_subset",
+            order.clone(),
+            json!({ "customer": { "name": "Bia" } })
+        ));
+        assert!(!check(
+            "matches_subset",
+            order.clone(),
+            json!({ "coupon": null })
+        ));
+        // Dois itens esperados não podem casar com o mesmo item atual.
+        assert!(!check(
+            "matches_subset",
+            order,
+            json!({ "items": [{ "sku": "A1" }, { "sku": "A1" }] })
+        ));
+        // O primeiro esperado casa com os dois itens; o segundo, só com o
+        // primeiro: a escolha gulosa falharia.
+        assert!(check(
+            "matches_subset",
+            json!([{ "sku": "A1", "qty": 2 }, { "sku": "A1" }]),
+            json!([{ "sku": "A1" }, { "qty": 2 }])
+        ));
+    }
+
+    #[test]
+    fn test_format_validity() {
+        assert!(check(
+            "uuid_valid",
+            json!("550e8400-e29b-41d4-a716-446655440000"),
+            json!(true)
+        ));
+        assert!(check(
+            "uuid_valid",
+            json!("550e8400e29b41d4a716446655440000"),
+            json!(false)
+        ));
+        assert!(check(
+            "email_valid",
+            json!("ana.souza+qa@shop.com.br"),
+            json!(true)
+        ));
+        assert!(!check("email_valid", json!("ana@shop"), json!(true)));
+        assert!(check(
+            "url_valid",
+            json!("https://cdn.shop.com/img/1.png"),
+            json!(true)
+        ));
+        assert!(!check("url_valid", json!("/img/1.png"), json!(true)));
+        assert!(!check("url_valid", json!(42), json!(true)));
+        assert!(evaluate_semantic("eq", &json!(1), &json!(1)).is_none());
+    }
+}
@@ -28,10 +28,11 @@
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use serde::Deserialize;
+use serde_json::{json, Value};
 use std::time::{Duration, Instant};
-use tokio::time::sleep;
 use tracing::{info, instrument};
 
+use crate::clock::{system_clock, SharedClock};
 use crate::context::Context;
 use crate::protocol::{Step, StepResult, StepStatus};
 
@@ -97,16 +98,24 @@ impl WaitParams {
 ///
 /// ## Thread Safety:
 ///
-/// O `WaitExecutor` não tem estado interno, então é completamente
-/// thread-safe e pode ser usado em execução paralela sem problemas.
-pub struct WaitExecutor;
+/// O único estado do `WaitExecutor` é o relógio compartilhado (`Arc`),
+/// então ele pode ser usado em execução paralela sem problemas.
+pub struct WaitExecutor {
+    /// Relógio usado para as pausas (real ou virtual).
+    clock: SharedClock,
+}
 
 impl WaitExecutor {
-    /// Cria um novo WaitExecutor.
-    ///
-    /// Como não há estado, isso apenas retorna uma instância vazia.
+    /// Cria um novo WaitExecutor usando o relógio real.
     pub fn new() -> Self {
-        Self
+        Self::with_clock(system_clock())
+    }
+
+    /// Cria um WaitExecutor com um relógio injetado.
+    ///
+    /// Usado com `VirtualClock` para `--fast-wait` / `--wait-scale`.
+    pub fn with_clock(clock: SharedClock) -> Self {
+        Self { clock }
     }
 }
 
@@ -119,6 +128,18 @@ impl Default for WaitExecutor {
     }
 }
 
+/// JSON Schema dos params de `wait`/`sleep` (ver `StepExecutor::params_schema`).
+pub fn params_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "duration_ms": { "type": "integer", "minimum": 0 },
+            "ms": { "type": "integer", "minimum": 0 }
+        },
+        "additionalProperties": false
+    })
+}
+
 // ============================================================================
 // IMPLEMENTAÇÃO DO TRAIT
 // ============================================================================
@@ -134,6 +155,11 @@ impl StepExecutor for WaitExecutor {
         action == "wait" || action == "sleep"
     }
 
+    /// Schema dos params de `wait`/`sleep`, usado na validação do plano.
+    fn params_schema(&self, _action: &str) -> Option<Value> {
+        Some(params_schema())
+    }
+
     /// Executa o delay.
     ///
     /// ## Fluxo:
@@ -182,12 +208,13 @@ impl StepExecutor for WaitExecutor {
             step_id = %step.id,
             action = %step.action,
             duration_ms = duration_ms,
+            virtual_clock = self.clock.is_virtual(),
             "⏳ Aguardando..."
         );
 
-        // Executa o delay.
-        // `sleep` é uma função assíncrona do Tokio que não bloqueia a thread.
-        sleep(Duration::from_millis(duration_ms)).await;
+        // Executa o delay através do relógio injetado.
+        // Com relógio virtual, a espera é pulada ou reduzida.
+        self.clock.sleep(Duration::from_millis(duration_ms)).await;
 
         // Calcula a duração real.
         let elapsed = start.elapsed().as_millis() as u64;
@@ -211,6 +238,7 @@ impl StepExecutor for WaitExecutor {
             context_after: None,
             extractions: None,
             http_details: None,
+            ..Default::default()
         })
     }
 }
@@ -235,6 +263,7 @@ mod tests {
             assertions: vec![],
             extract: vec![],
             recovery_policy: None,
+            ..Default::default()
         }
     }
 
@@ -270,6 +299,7 @@ mod tests {
             assertions: vec![],
             extract: vec![],
             recovery_policy: None,
+            ..Default::default()
         };
         let mut context = Context::new();
 
@@ -296,6 +326,7 @@ mod tests {
             assertions: vec![],
             extract: vec![],
             recovery_policy: None,
+            ..Default::default()
         };
         let mut context = Context::new();
 
@@ -317,6 +348,7 @@ mod tests {
             assertions: vec![],
             extract: vec![],
             recovery_policy: None,
+            ..Default::default()
         };
         let mut context = Context::new();
 
@@ -339,6 +371,7 @@ mod tests {
             assertions: vec![],
             extract: vec![],
             recovery_policy: None,
+            ..Default::default()
         };
         let mut context = Context::new();
 
@@ -350,6 +383,23 @@ mod tests {
         assert!(result.duration_ms < 150); // Confirma que não esperou 200ms
     }
 
+    #[tokio::test]
+    async fn test_wait_with_virtual_clock_skips_delay() {
+        use crate::clock::VirtualClock;
+        use std::sync::Arc;
+
+        let clock = Arc::new(VirtualClock::instant());
+        let executor = WaitExecutor::with_clock(clock.clone());
+        let step = create_wait_step(5_000);
+        let mut context = Context::new();
+
+        let result = executor.execute(&step, &mut context).await.unwrap();
+
+        assert_eq!(result.status, StepStatus::Passed);
+        assert!(result.duration_ms < 100); // Não esperou 5s de verdade
+        assert_eq!(clock.virtual_elapsed_ms(), 5_000);
+    }
+
     #[tokio::test]
     async fn test_wait_missing_duration() {
         let executor = WaitExecutor::new();
@@ -362,6 +412,7 @@ mod tests {
             assertions: vec![],
             extract: vec![],
             recovery_policy: None,
+            ..Default::default()
         };
         let mut context = Context::new();
 
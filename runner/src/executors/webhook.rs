@@ -0,0 +1,607 @@
+//! # Executor de Webhook - Esperando o Callback do Sistema
+//!
+//! Este executor espera uma requisição **de entrada**: o sistema testado
+//! chama de volta uma URL do Runner (webhook) e o step confere o payload.
+//!
+//! ## Para todos entenderem:
+//!
+//! Muitas integrações respondem `202 Accepted` e avisam depois: o
+//! pagamento foi aprovado, o relatório ficou pronto. Para testar o aviso,
+//! o Runner sobe um listener HTTP antes do primeiro step e divulga uma URL
+//! por step `webhook_wait` em `${<step_id>.url}`. Um step anterior
+//! cadastra essa URL; o `webhook_wait` espera o callback chegar:
+//!
+//! ```json
+//! { "id": "create_payment", "action": "http_request",
+//!   "params": { "method": "POST", "path": "/payments",
+//!               "body": { "amount": 100, "callback_url": "${payment_hook.url}" } } },
+//! { "id": "payment_hook", "action": "webhook_wait", "depends_on": ["create_payment"],
+//!   "params": { "match": { "$.event": "payment.approved" }, "timeout_ms": 20000 },
+//!   "assertions": [ { "type": "json_body", "path": "$.amount", "operator": "eq", "value": 100 } ],
+//!   "extract": [ { "source": "body", "path": "$.payment_id", "target": "payment_id" } ] }
+//! ```
+//!
+//! ## Parâmetros (todos opcionais):
+//!
+//! | Parâmetro    | Padrão  | Descrição                                             |
+//! |--------------|---------|-------------------------------------------------------|
+//! | `method`     | -       | Só aceita callbacks com este método (ex: `POST`)      |
+//! | `headers`    | -       | Headers exigidos (nome → valor, interpolados)         |
+//! | `match`      | -       | Campos exigidos no body JSON (JSONPath → valor)       |
+//! | `timeout_ms` | `30000` | Quanto esperar pelo callback                          |
+//!
+//! - Callbacks chegam a qualquer momento da execução (inclusive antes do
+//!   `webhook_wait` começar) e ficam guardados; o step consome o primeiro
+//!   que passa nos filtros. Os que não passam continuam guardados.
+//! - A URL é `/hooks/<token>/<step_id>`, com um token aleatório por
+//!   execução: quem não recebeu a URL não consegue injetar callbacks.
+//! - O listener responde `202` aos callbacks de steps `webhook_wait` do
+//!   plano; token errado ou step desconhecido recebe `404`, body acima de
+//!   1 MiB recebe `413` e, com 1000 callbacks não consumidos, `429`.
+//! - Assertions: `json_body` (body do callback) e `header` (`path` = nome).
+//!   Extrações: `body` e `header`. O body fica em `${<step_id>.body}`.
+//! - Fora da máquina do Runner, use `config.webhook.public_url` (túnel ou
+//!   relay apontando para `config.webhook.bind`).
+
+use anyhow::{anyhow, Context as _, Result};
+use async_trait::async_trait;
+use hyper::body::HttpBody as _;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, StatusCode};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use subtle::ConstantTimeEq;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tracing::{info, instrument};
+
+use crate::context::Context;
+use crate::extractors::Extractor;
+use crate::protocol::{Assertion, Step, StepResult, StepStatus, WebhookConfig};
+
+use super::assert::evaluate;
+use super::http::json_pointer;
+use super::value_operators::localize;
+use super::StepExecutor;
+
+/// Prefixo das URLs de callback (`/hooks/<token>/<step_id>`).
+const HOOKS_PREFIX: &str = "/hooks/";
+
+/// Maior body de callback aceito.
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Callbacks guardados sem consumo antes de o listener recusar novos.
+const MAX_INBOX: usize = 1000;
+
+/// Endereço padrão do listener (porta livre, só local).
+const DEFAULT_BIND: &str = "127.0.0.1:0";
+
+/// Espera padrão pelo callback.
+const DEFAULT_TIMEOUT_MS: u64 = 30_000;
+
+// ============================================================================
+// LISTENER
+// ============================================================================
+
+/// Um callback recebido pelo listener.
+#[derive(Debug, Clone)]
+pub struct Callback {
+    step_id: String,
+    method: String,
+    /// Nomes em minúsculas.
+    headers: HashMap<String, String>,
+    /// JSON se o body for JSON; senão, o texto (`null` se vazio).
+    body: Value,
+}
+
+/// Estado compartilhado entre o listener e o servidor HTTP.
+#[derive(Default)]
+struct Inbox {
+    /// Segredo da execução no caminho das URLs.
+    token: String,
+    /// Steps cadastrados por `register` (os únicos aceitos).
+    steps: Mutex<HashSet<String>>,
+    callbacks: Mutex<Vec<Callback>>,
+    arrived: Notify,
+}
+
+/// Listener HTTP da execução: recebe callbacks e os guarda por step.
+pub struct WebhookListener {
+    base_url: String,
+    inbox: Arc<Inbox>,
+    server: JoinHandle<()>,
+}
+
+impl WebhookListener {
+    /// Sobe o listener em `config.bind` (padrão: porta livre local).
+    pub fn start(config: Option<&WebhookConfig>) -> Result<Arc<Self>> {
+        let bind = config
+            .and_then(|c| c.bind.as_deref())
+            .unwrap_or(DEFAULT_BIND);
+        let listener = std::net::TcpListener::bind(bind)
+            .with_context(|| format!("Falha ao abrir o listener de webhooks em {}", bind))?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+        let base_url = match config.and_then(|c| c.public_url.as_deref()) {
+            Some(public) => public.trim_end_matches('/').to_string(),
+            None => format!("http://{}", addr),
+        };
+
+        let inbox = Arc::new(Inbox {
+            token: uuid::Uuid::new_v4().simple().to_string(),
+            ..Default::default()
+        });
+        let service_inbox = Arc::clone(&inbox);
+        let make_service = make_service_fn(move |_| {
+            let inbox = Arc::clone(&service_inbox);
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    let inbox = Arc::clone(&inbox);
+                    async move { Ok::<_, Infallible>(receive(request, &inbox).await) }
+                }))
+            }
+        });
+        let server = hyper::Server::from_tcp(listener)?.serve(make_service);
+        info!(%addr, %base_url, "Webhook listener started");
+        Ok(Arc::new(Self {
+            base_url,
+            inbox,
+            server: tokio::spawn(async move {
+                let _ = server.await;
+            }),
+        }))
+    }
+
+    /// URL divulgada para o step.
+    pub fn url(&self, step_id: &str) -> String {
+        format!(
+            "{}{}{}/{}",
+            self.base_url,
+            HOOKS_PREFIX,
+            self.inbox.token,
+            urlencoding::encode(step_id)
+        )
+    }
+
+    /// Aceita callbacks dos steps `webhook_wait` do plano e grava `${<step_id>.url}`.
+    pub fn register(&self, steps: &[Step], context: &mut Context) {
+        let mut registered = self.inbox.steps.lock().expect("webhook steps");
+        for step in steps.iter().filter(|s| s.action == "webhook_wait") {
+            registered.insert(step.id.clone());
+            context.set(format!("{}.url", step.id), json!(self.url(&step.id)));
+        }
+    }
+
+    /// Consome o primeiro callback do step que passa no filtro, esperando
+    /// até `timeout`. Sem nenhum, retorna quantos chegaram e não passaram.
+    async fn wait_for(
+        &self,
+        step_id: &str,
+        filter: &CallbackFilter,
+        timeout: Duration,
+    ) -> Result<Callback, usize> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            // Registra o interesse antes de olhar a caixa: nenhum aviso se perde.
+            let notified = self.inbox.arrived.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            let rejected = {
+                let mut inbox = self.inbox.callbacks.lock().expect("webhook inbox");
+                let mine = |c: &Callback| c.step_id == step_id;
+                if let Some(index) = inbox.iter().position(|c| mine(c) && filter.accepts(c)) {
+                    return Ok(inbox.remove(index));
+                }
+                inbox.iter().filter(|c| mine(c)).count()
+            };
+            if tokio::time::timeout_at(deadline, notified).await.is_err() {
+                return Err(rejected);
+            }
+        }
+    }
+}
+
+impl Drop for WebhookListener {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+/// Guarda um callback para `/hooks/<token>/<step_id>` e responde `202`.
+async fn receive(request: Request<Body>, inbox: &Inbox) -> Response<Body> {
+    let Some(step_id) = route(request.uri().path(), inbox) else {
+        return reply(StatusCode::NOT_FOUND, json!({ "error": "not found" }));
+    };
+    let method = request.method().to_string();
+    let headers = request
+        .headers()
+        .iter()
+        .filter_map(|(name, value)| {
+            Some((name.as_str().to_string(), value.to_str().ok()?.to_string()))
+        })
+        .collect();
+    let body = match read_body(request.into_body()).await {
+        Ok(bytes) if bytes.is_empty() => Value::Null,
+        Ok(bytes) => serde_json::from_slice(&bytes)
+            .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&bytes).into_owned())),
+        Err(status) => return reply(status, json!({ "error": status.to_string() })),
+    };
+
+    {
+        let mut callbacks = inbox.callbacks.lock().expect("webhook inbox");
+        if callbacks.len() >= MAX_INBOX {
+            return reply(
+                StatusCode::TOO_MANY_REQUESTS,
+                json!({ "error": "too many pending callbacks" }),
+            );
+        }
+        info!(step_id = %step_id, %method, "Webhook callback received");
+        callbacks.push(Callback {
+            step_id,
+            method,
+            headers,
+            body,
+        });
+    }
+    inbox.arrived.notify_waiters();
+    reply(StatusCode::ACCEPTED, json!({ "received": true }))
+}
+
+/// Step de `/hooks/<token>/<step_id>`, se o token confere e o step foi cadastrado.
+fn route(path: &str, inbox: &Inbox) -> Option<String> {
+    let (token, step_id) = path.strip_prefix(HOOKS_PREFIX)?.split_once('/')?;
+    if !bool::from(token.as_bytes().ct_eq(inbox.token.as_bytes())) {
+        return None;
+    }
+    let step_id = urlencoding::decode(step_id.trim_end_matches('/')).ok()?;
+    let registered = inbox.steps.lock().expect("webhook steps");
+    registered
+        .contains(step_id.as_ref())
+        .then(|| step_id.into_owned())
+}
+
+/// Lê o body até `MAX_BODY_BYTES` (acima disso, `413`).
+async fn read_body(mut body: Body) -> Result<Vec<u8>, StatusCode> {
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|_| StatusCode::BAD_REQUEST)?;
+        if bytes.len() + chunk.len() > MAX_BODY_BYTES {
+            return Err(StatusCode::PAYLOAD_TOO_LARGE);
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes)
+}
+
+fn reply(status: StatusCode, body: Value) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(hyper::header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .expect("valid response")
+}
+
+// ============================================================================
+// FILTRO E ASSERTIONS
+// ============================================================================
+
+/// Filtros do step (`method`, `headers`, `match`), já interpolados.
+#[derive(Debug, Default)]
+struct CallbackFilter {
+    method: Option<String>,
+    headers: Vec<(String, Value)>,
+    body: Vec<(String, Value)>,
+}
+
+impl CallbackFilter {
+    fn from_params(params: &Value, context: &Context) -> Result<Self> {
+        let pairs = |key: &str| -> Result<Vec<(String, Value)>> {
+            match params.get(key) {
+                Some(Value::Object(map)) => map
+                    .iter()
+                    .map(|(k, v)| Ok((k.clone(), context.interpolate_value(v)?)))
+                    .collect(),
+                Some(_) => Err(anyhow!("'{}' deve ser um objeto", key)),
+                None => Ok(Vec::new()),
+            }
+        };
+        Ok(Self {
+            method: params
+                .get("method")
+                .and_then(|m| m.as_str())
+                .map(str::to_uppercase),
+            headers: pairs("headers")?,
+            body: pairs("match")?,
+        })
+    }
+
+    fn accepts(&self, callback: &Callback) -> bool {
+        self.method.as_ref().is_none_or(|m| *m == callback.method)
+            && self.headers.iter().all(|(name, expected)| {
+                callback
+                    .headers
+                    .get(&name.to_lowercase())
+                    .is_some_and(|actual| expected.as_str() == Some(actual))
+            })
+            && self.body.iter().all(|(path, expected)| {
+                callback.body.pointer(&json_pointer(path)) == Some(expected)
+            })
+    }
+}
+
+/// Avalia as assertions sobre o callback; retorna a primeira falha.
+fn check_callback(assertions: &[Assertion], callback: &Callback) -> Option<String> {
+    assertions.iter().find_map(|assertion| {
+        let path = assertion.path.as_deref().unwrap_or("$");
+        let actual = match assertion.assertion_type.as_str() {
+            "json_body" => callback.body.pointer(&json_pointer(path)).cloned(),
+            "header" => callback.headers.get(&path.to_lowercase()).map(|v| json!(v)),
+            other => {
+                return Some(format!(
+                    "Assertion type '{}' não suportado em webhook_wait",
+                    other
+                ))
+            }
+        };
+        let passed = match (assertion.operator.as_str(), &actual) {
+            ("exists", actual) => actual.is_some(),
+            ("not_exists", actual) => actual.is_none(),
+            (_, None) => false,
+            (operator, Some(actual)) => evaluate(
+                operator,
+                &localize(actual, assertion.locale.as_ref()),
+                &assertion.value,
+                assertion.tolerance.as_ref(),
+            ),
+        };
+        (!passed).then(|| {
+            format!(
+                "Assertion failed: {} '{}' {} {} (got {})",
+                assertion.assertion_type,
+                path,
+                assertion.operator,
+                assertion.value,
+                actual.map_or("nenhum".to_string(), |a| a.to_string())
+            )
+        })
+    })
+}
+
+// ============================================================================
+// WEBHOOK WAIT EXECUTOR
+// ============================================================================
+
+/// Executor para a ação `webhook_wait`.
+#[derive(Default)]
+pub struct WebhookWaitExecutor {
+    /// Listener da execução (`None` se o plano não tem `webhook_wait`).
+    listener: Option<Arc<WebhookListener>>,
+}
+
+impl WebhookWaitExecutor {
+    /// Cria um novo WebhookWaitExecutor sobre o listener da execução.
+    pub fn new(listener: Option<Arc<WebhookListener>>) -> Self {
+        Self { listener }
+    }
+}
+
+/// JSON Schema dos params de `webhook_wait` (ver `StepExecutor::params_schema`).
+pub fn params_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "method": { "type": "string" },
+            "headers": { "type": "object", "additionalProperties": { "type": "string" } },
+            "match": { "type": "object" },
+            "timeout_ms": { "type": "integer", "minimum": 0 }
+        },
+        "additionalProperties": false
+    })
+}
+
+#[async_trait]
+impl StepExecutor for WebhookWaitExecutor {
+    fn can_handle(&self, action: &str) -> bool {
+        action == "webhook_wait"
+    }
+
+    /// Schema dos params de `webhook_wait`, usado na validação do plano.
+    fn params_schema(&self, _action: &str) -> Option<Value> {
+        Some(params_schema())
+    }
+
+    #[instrument(skip(self, context), fields(step_id = %step.id))]
+    async fn execute(&self, step: &Step, context: &mut Context) -> Result<StepResult> {
+        let started = Instant::now();
+        let context_before = context.variables.clone();
+        let listener = self
+            .listener
+            .as_ref()
+            .ok_or_else(|| anyhow!("Listener de webhooks não iniciado para esta execução"))?;
+        let filter = CallbackFilter::from_params(&step.params, context)?;
+        let timeout_ms = step
+            .params
+            .get("timeout_ms")
+            .and_then(|t| t.as_u64())
+            .unwrap_or(DEFAULT_TIMEOUT_MS);
+
+        let (error, extractions) = match listener
+            .wait_for(&step.id, &filter, Duration::from_millis(timeout_ms))
+            .await
+        {
+            Ok(callback) => {
+                let failure = check_callback(&step.assertions, &callback);
+                let (extractions, values) =
+                    Extractor::process(&step.extract, Some(&callback.body), &callback.headers);
+                for (key, value) in values {
+                    context.set(key, value);
+                }
+                context.set(format!("{}.body", step.id), callback.body);
+                (failure, Some(extractions))
+            }
+            Err(rejected) => (
+                Some(format!(
+                    "Nenhum callback em {} em {}ms ({} recebido(s) fora dos filtros)",
+                    listener.url(&step.id),
+                    timeout_ms,
+                    rejected
+                )),
+                None,
+            ),
+        };
+
+        Ok(StepResult {
+            step_id: step.id.clone(),
+            status: if error.is_some() {
+                StepStatus::Failed
+            } else {
+                StepStatus::Passed
+            },
+            duration_ms: started.elapsed().as_millis() as u64,
+            error,
+            context_before: Some(context_before),
+            context_after: Some(context.variables.clone()),
+            extractions: extractions.filter(|e| !e.is_empty()),
+            ..Default::default()
+        })
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::Extraction;
+
+    fn hook_step(params: Value) -> Step {
+        Step {
+            id: "payment_hook".to_string(),
+            action: "webhook_wait".to_string(),
+            params,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_waits_for_matching_callback_and_extracts() {
+        let listener = WebhookListener::start(None).unwrap();
+        let mut context = Context::new();
+        context.set("order", json!("ord-7"));
+        let mut step = hook_step(json!({
+            "method": "POST",
+            "match": { "$.event": "payment.approved", "order_id": "${order}" },
+            "timeout_ms": 5000
+        }));
+        step.assertions = vec![Assertion {
+            assertion_type: "json_body".to_string(),
+            operator: "eq".to_string(),
+            path: Some("$.amount".to_string()),
+            value: json!(100),
+            ..Default::default()
+        }];
+        step.extract = vec![serde_json::from_value::<Extraction>(json!({
+            "source": "body", "path": "$.payment_id", "target": "payment_id"
+        }))
+        .unwrap()];
+        listener.register(std::slice::from_ref(&step), &mut context);
+        let url = context
+            .get("payment_hook.url")
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert!(url.ends_with("/payment_hook"));
+        assert!(!url.ends_with("/hooks/payment_hook"), "{}", url);
+
+        // Um callback chega antes do step (e não casa); o certo chega depois.
+        let client = reqwest::Client::new();
+        let early = client
+            .post(&url)
+            .json(&json!({ "event": "payment.pending", "order_id": "ord-7" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(early.status(), 202);
+        let sender = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            client
+                .post(&url)
+                .json(&json!({
+                    "event": "payment.approved", "order_id": "ord-7",
+                    "amount": 100, "payment_id": "pay-1"
+                }))
+                .send()
+                .await
+                .unwrap();
+        });
+
+        let result = WebhookWaitExecutor::new(Some(Arc::clone(&listener)))
+            .execute(&step, &mut context)
+            .await
+            .unwrap();
+        sender.await.unwrap();
+
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+        assert_eq!(context.get("payment_id"), Some(&json!("pay-1")));
+        assert_eq!(
+            context.get("payment_hook.body").unwrap()["event"],
+            "payment.approved"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_times_out_reporting_rejected_callbacks() {
+        let listener = WebhookListener::start(None).unwrap();
+        let step = hook_step(json!({ "method": "PUT", "timeout_ms": 100 }));
+        listener.register(std::slice::from_ref(&step), &mut Context::new());
+        reqwest::Client::new()
+            .post(listener.url(&step.id))
+            .body("ping")
+            .send()
+            .await
+            .unwrap();
+
+        let result = WebhookWaitExecutor::new(Some(listener))
+            .execute(&step, &mut Context::new())
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Failed);
+        assert!(result
+            .error
+            .unwrap()
+            .contains("(1 recebido(s) fora dos filtros)"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unknown_routes_and_large_bodies() {
+        let listener = WebhookListener::start(None).unwrap();
+        let step = hook_step(json!({}));
+        listener.register(std::slice::from_ref(&step), &mut Context::new());
+        let client = reqwest::Client::new();
+        let status = |url: String, body: Vec<u8>| {
+            let client = client.clone();
+            async move { client.post(url).body(body).send().await.unwrap().status() }
+        };
+
+        let url = listener.url(&step.id);
+        let base = url.trim_end_matches("/payment_hook").to_string();
+        assert_eq!(status(listener.url("other_step"), vec![]).await, 404);
+        assert_eq!(
+            status(format!("{}/hooks/payment_hook", listener.base_url), vec![]).await,
+            404
+        );
+        assert_eq!(status(format!("{}/x", base), vec![]).await, 404);
+        assert_eq!(
+            status(url.clone(), vec![b'a'; MAX_BODY_BYTES + 1]).await,
+            413
+        );
+        assert_eq!(status(url, b"ok".to_vec()).await, 202);
+    }
+}
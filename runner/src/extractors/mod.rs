@@ -580,8 +580,10 @@ impl Extractor {
 /// - `$.parent.child` → Acesso aninhado
 /// - `$.array[0]` → Acesso a índice de array
 /// - `$.array[*]` → Todos os elementos (retorna array)
-#[allow(dead_code)]
-fn navigate_json(value: &Value, path: &str) -> Result<Value> {
+/// - `$.array[*].field` → Campo de cada elemento (retorna array)
+///
+/// Também usado pela action `transform` para navegar em variáveis.
+pub fn navigate_json(value: &Value, path: &str) -> Result<Value> {
     navigate_json_multi(value, path, false)
 }
 
@@ -597,10 +599,30 @@ fn navigate_json_multi(value: &Value, path: &str, all_values: bool) -> Result<Va
         return Ok(value.clone());
     }
 
+    navigate_segments(value, &split_path(clean_path), all_values)
+}
+
+/// Navega uma sequência de segmentos.
+///
+/// Após um wildcard `[*]`, os segmentos restantes são aplicados a cada
+/// elemento do array (projeção), e os resultados são coletados em um array.
+fn navigate_segments(value: &Value, segments: &[String], all_values: bool) -> Result<Value> {
     let mut current = value.clone();
 
-    for segment in split_path(clean_path) {
-        current = navigate_segment(&current, &segment, all_values)?;
+    for (i, segment) in segments.iter().enumerate() {
+        current = navigate_segment(&current, segment, all_values)?;
+
+        if segment == "[*]" && i + 1 < segments.len() {
+            let rest = &segments[i + 1..];
+            let projected = match &current {
+                Value::Array(items) => items
+                    .iter()
+                    .map(|item| navigate_segments(item, rest, all_values))
+                    .collect::<Result<Vec<_>>>()?,
+                _ => Vec::new(),
+            };
+            return Ok(Value::Array(projected));
+        }
     }
 
     Ok(current)
@@ -705,6 +727,7 @@ fn navigate_segment(value: &Value, segment: &str, _all_values: bool) -> Result<V
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::protocol::ExtractionScope;
     use serde_json::json;
 
     // ------------------------------------------------------------------------
@@ -739,6 +762,13 @@ mod tests {
         assert_eq!(result, json!([1, 2, 3]));
     }
 
+    #[test]
+    fn test_navigate_array_wildcard_projection() {
+        let json = json!({"items": [{"id": 1}, {"id": 2}]});
+        let result = navigate_json(&json, "$.items[*].id").unwrap();
+        assert_eq!(result, json!([1, 2]));
+    }
+
     #[test]
     fn test_navigate_without_dollar() {
         let json = json!({"data": {"value": 42}});
@@ -766,6 +796,7 @@ mod tests {
             target: "auth_token".to_string(),
             all_values: false,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, values) = Extractor::process(&[extraction], Some(&body), &HashMap::new());
@@ -793,6 +824,7 @@ mod tests {
                 target: "user_id".to_string(),
                 all_values: false,
                 critical: false,
+                scope: ExtractionScope::Plan,
             },
             Extraction {
                 source: "body".to_string(),
@@ -800,6 +832,7 @@ mod tests {
                 target: "user_name".to_string(),
                 all_values: false,
                 critical: false,
+                scope: ExtractionScope::Plan,
             },
         ];
 
@@ -820,6 +853,7 @@ mod tests {
             target: "result".to_string(),
             all_values: false,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, values) = Extractor::process(&[extraction], Some(&body), &HashMap::new());
@@ -846,6 +880,7 @@ mod tests {
             target: "request_id".to_string(),
             all_values: false,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, values) = Extractor::process(&[extraction], None, &headers);
@@ -866,6 +901,7 @@ mod tests {
             target: "content_type".to_string(),
             all_values: false,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, values) = Extractor::process(&[extraction], None, &headers);
@@ -883,6 +919,7 @@ mod tests {
             target: "missing".to_string(),
             all_values: false,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, _) = Extractor::process(&[extraction], None, &headers);
@@ -908,6 +945,7 @@ mod tests {
             target: "jwt_token".to_string(),
             all_values: false,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, values) = Extractor::process(&[extraction], Some(&body), &HashMap::new());
@@ -928,6 +966,7 @@ mod tests {
             target: "token".to_string(),
             all_values: false,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, _) = Extractor::process(&[extraction], Some(&body), &HashMap::new());
@@ -949,6 +988,7 @@ mod tests {
             target: "result".to_string(),
             all_values: false,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, _) = Extractor::process(&[extraction], Some(&body), &HashMap::new());
@@ -983,6 +1023,7 @@ mod tests {
                 target: "access_token".to_string(),
                 all_values: false,
                 critical: false,
+                scope: ExtractionScope::Plan,
             },
             Extraction {
                 source: "body".to_string(),
@@ -990,6 +1031,7 @@ mod tests {
                 target: "refresh_token".to_string(),
                 all_values: false,
                 critical: false,
+                scope: ExtractionScope::Plan,
             },
             Extraction {
                 source: "body".to_string(),
@@ -997,6 +1039,7 @@ mod tests {
                 target: "expires_in".to_string(),
                 all_values: false,
                 critical: false,
+                scope: ExtractionScope::Plan,
             },
             Extraction {
                 source: "header".to_string(),
@@ -1004,6 +1047,7 @@ mod tests {
                 target: "rate_limit".to_string(),
                 all_values: false,
                 critical: false,
+                scope: ExtractionScope::Plan,
             },
         ];
 
@@ -1030,6 +1074,7 @@ mod tests {
             target: "token".to_string(),
             all_values: false,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, _) = Extractor::process(&[extraction], None, &HashMap::new());
@@ -1046,6 +1091,7 @@ mod tests {
             target: "result".to_string(),
             all_values: false,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, _) = Extractor::process(&[extraction], None, &HashMap::new());
@@ -1063,6 +1109,7 @@ mod tests {
             target: "token".to_string(),
             all_values: false,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, _) = Extractor::process(&[extraction], Some(&body), &HashMap::new());
@@ -1084,6 +1131,7 @@ mod tests {
             target: "token".to_string(),
             all_values: false,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, _) = Extractor::process(&[extraction], Some(&body), &HashMap::new());
@@ -1100,6 +1148,7 @@ mod tests {
             target: "header".to_string(),
             all_values: false,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, _) = Extractor::process(&[extraction], None, &HashMap::new());
@@ -1117,6 +1166,7 @@ mod tests {
             target: "token".to_string(),
             all_values: false,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, _) = Extractor::process(&[extraction], Some(&body), &HashMap::new());
@@ -1134,6 +1184,7 @@ mod tests {
             target: "result".to_string(),
             all_values: false,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, _) = Extractor::process(&[extraction], None, &HashMap::new());
@@ -1151,6 +1202,7 @@ mod tests {
             target: "token".to_string(),
             all_values: false,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, _) = Extractor::process(&[extraction], Some(&body), &HashMap::new());
@@ -1190,6 +1242,7 @@ mod tests {
                 target: "auth_token".to_string(),
                 all_values: false,
                 critical: false,
+                scope: ExtractionScope::Plan,
             },
             Extraction {
                 source: "body".to_string(),
@@ -1197,6 +1250,7 @@ mod tests {
                 target: "user_id".to_string(),
                 all_values: false,
                 critical: false,
+                scope: ExtractionScope::Plan,
             },
             Extraction {
                 source: "header".to_string(),
@@ -1204,6 +1258,7 @@ mod tests {
                 target: "request_id".to_string(),
                 all_values: false,
                 critical: false,
+                scope: ExtractionScope::Plan,
             },
         ];
 
@@ -1250,6 +1305,7 @@ mod tests {
             target: "http_status".to_string(),
             all_values: false,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, values) =
@@ -1269,6 +1325,7 @@ mod tests {
             target: "http_status".to_string(),
             all_values: false,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, _) =
@@ -1295,6 +1352,7 @@ mod tests {
             target: "name".to_string(),
             all_values: false,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, _) = Extractor::process(&[extraction], Some(&body), &HashMap::new());
@@ -1312,6 +1370,7 @@ mod tests {
             target: "count".to_string(),
             all_values: false,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, _) = Extractor::process(&[extraction], Some(&body), &HashMap::new());
@@ -1329,6 +1388,7 @@ mod tests {
             target: "active".to_string(),
             all_values: false,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, _) = Extractor::process(&[extraction], Some(&body), &HashMap::new());
@@ -1346,6 +1406,7 @@ mod tests {
             target: "items".to_string(),
             all_values: false,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, _) = Extractor::process(&[extraction], Some(&body), &HashMap::new());
@@ -1367,6 +1428,7 @@ mod tests {
             target: "tokens".to_string(),
             all_values: true,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, values) = Extractor::process(&[extraction], Some(&body), &HashMap::new());
@@ -1390,6 +1452,7 @@ mod tests {
             target: "token".to_string(),
             all_values: false,
             critical: false,
+            scope: ExtractionScope::Plan,
         };
 
         let (results, values) = Extractor::process(&[extraction], Some(&body), &HashMap::new());
@@ -1412,6 +1475,7 @@ mod tests {
             target: "token".to_string(),
             all_values: false,
             critical: true, // Esta é crítica!
+            scope: ExtractionScope::Plan,
         };
 
         let (results, _) = Extractor::process(&[extraction], Some(&body), &HashMap::new());
@@ -1431,6 +1495,7 @@ mod tests {
             target: "optional".to_string(),
             all_values: false,
             critical: false, // Não é crítica
+            scope: ExtractionScope::Plan,
         };
 
         let (results, _) = Extractor::process(&[extraction], Some(&body), &HashMap::new());
@@ -1451,6 +1516,7 @@ mod tests {
                 target: "token".to_string(),
                 all_values: false,
                 critical: true, // Crítica, mas vai passar
+                scope: ExtractionScope::Plan,
             },
             Extraction {
                 source: "body".to_string(),
@@ -1458,6 +1524,7 @@ mod tests {
                 target: "missing".to_string(),
                 all_values: false,
                 critical: false, // Não crítica, vai falhar
+                scope: ExtractionScope::Plan,
             },
         ];
 
@@ -0,0 +1,680 @@
+//! # Módulo de Fan-out - `parallel_foreach`
+//!
+//! Executa um mesmo step uma vez para cada elemento de um array do
+//! contexto, com concorrência limitada, e agrega os resultados.
+//!
+//! ## Para todos entenderem:
+//!
+//! É como receber uma lista de 50 pedidos e conferir cada um: em vez de
+//! escrever 50 steps, o plano marca um step com `parallel_foreach` e o
+//! Runner cuida de repeti-lo, alguns ao mesmo tempo.
+//!
+//! ## Como funciona:
+//!
+//! ```text
+//!   user_ids = [7, 8, 9]
+//!
+//!   get_user ──┬── get_user[0]  (user_id=7, user_id_index=0)
+//!              ├── get_user[1]  (user_id=8, user_id_index=1)
+//!              └── get_user[2]  (user_id=9, user_id_index=2)
+//! ```
+//!
+//! - Cada iteração roda em uma **cópia** do contexto, com a variável do
+//!   elemento (`as`) e seu índice (`<as>_index`).
+//! - O step passa apenas se **todas** as iterações passarem.
+//! - Os resultados ficam em `iterations`, na ordem do array.
+//! - Cada variável extraída (`extract.target`) volta ao contexto principal
+//!   como um array indexado (`null` para iterações sem valor).
+
+use anyhow::{anyhow, Result};
+use futures::stream::{self, StreamExt};
+use serde_json::Value;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+use crate::actors;
+use crate::clock::Clock;
+use crate::context::Context;
+use crate::executors::{assert, StepExecutor};
+use crate::protocol::{LatencyBudget, ParallelForeach, SkipReason, Step, StepResult, StepStatus};
+use crate::scoping;
+
+// ============================================================================
+// PONTO DE ENTRADA
+// ============================================================================
+
+/// Executa um step, aplicando o fan-out se `parallel_foreach` estiver definido.
+///
+/// Steps sem `parallel_foreach` são repassados diretamente ao executor,
+/// então os chamadores (sequencial e DAG) podem usar sempre esta função.
+/// Também aplica `assertions_retry` e `expect_failure` (por iteração, no
+/// fan-out), as variáveis do ator do step (`step.actor`) e registra a
+/// latência contra `latency_budget_ms` (exceto em steps de `warmup`).
+/// Com `condition` falsa, o step nem chega ao executor e fica `not_run`.
+pub async fn execute_step(
+    step: &Step,
+    executor: &dyn StepExecutor,
+    context: &mut Context,
+    clock: &dyn Clock,
+) -> Result<StepResult> {
+    if let Some(condition) = &step.condition {
+        if !assert::condition_holds(condition, context)? {
+            info!(step_id = %step.id, path = %condition.path, "Condição falsa, step não executado");
+            return Ok(StepResult {
+                step_id: step.id.clone(),
+                status: StepStatus::NotRun,
+                error: Some(format!(
+                    "Condição falsa: '{}' {} {}",
+                    condition.path, condition.operator, condition.value
+                )),
+                skip_reason: Some(SkipReason::ConditionFalse),
+                context_before: Some(context.variables.clone()),
+                context_after: Some(context.variables.clone()),
+                ..Default::default()
+            });
+        }
+    }
+    context.enter_random_scope(&step.id);
+    let previous_skew = context.clock_skew_ms;
+    if let Some(skew) = step.clock_skew_ms {
+        context.clock_skew_ms = skew;
+    }
+    let actor_scope = step.actor.as_deref().map(|a| actors::enter(context, a));
+    let extraction_scope = scoping::enter(context, step);
+    let mut outcome = match &step.parallel_foreach {
+        Some(foreach) => execute_foreach(step, foreach, executor, context, clock).await,
+        None => apply_expect_failure(
+            step,
+            execute_with_assertions_retry(step, executor, context, clock).await,
+        ),
+    };
+    scoping::leave(context, step, extraction_scope);
+    if let Some(scope) = actor_scope {
+        actors::leave(context, scope);
+    }
+    context.clock_skew_ms = previous_skew;
+    if let Ok(result) = &mut outcome {
+        result.warmup = step.warmup;
+        // Aquecimento não conta para o SLO.
+        if let (Some(budget_ms), false) = (step.latency_budget_ms, step.warmup) {
+            result.latency_budget = Some(LatencyBudget::evaluate(budget_ms, result));
+        }
+    }
+    outcome
+}
+
+// ============================================================================
+// RETRY DE ASSERTIONS
+// ============================================================================
+
+/// Executa o step, reavaliando enquanto as assertions falharem (`assertions_retry`).
+///
+/// Só falhas com resposta são repetidas: erro do executor ou requisição
+/// sem resposta (status 0) ficam para a `recovery_policy`.
+async fn execute_with_assertions_retry(
+    step: &Step,
+    executor: &dyn StepExecutor,
+    context: &mut Context,
+    clock: &dyn Clock,
+) -> Result<StepResult> {
+    let Some(retry) = &step.assertions_retry else {
+        return executor.execute(step, context).await;
+    };
+    let max_attempts = retry.max_attempts.max(1);
+
+    let mut evaluation = 1;
+    loop {
+        let mut result = executor.execute(step, context).await?;
+        let assertion_failed = result.status == StepStatus::Failed
+            && result
+                .http_details
+                .as_ref()
+                .is_none_or(|http| http.status_code != 0);
+
+        if !assertion_failed || evaluation >= max_attempts {
+            result.assertion_attempts = Some(evaluation);
+            return Ok(result);
+        }
+
+        info!(
+            step_id = %step.id,
+            evaluation = evaluation,
+            max_attempts = max_attempts,
+            interval_ms = retry.interval_ms,
+            "Assertions falharam; reavaliando (assertions_retry)"
+        );
+        clock.sleep(Duration::from_millis(retry.interval_ms)).await;
+        evaluation += 1;
+    }
+}
+
+// ============================================================================
+// FALHA ESPERADA
+// ============================================================================
+
+/// Inverte o resultado de steps com `expect_failure: true`.
+///
+/// | Resultado do executor | Resultado final                          |
+/// |-----------------------|------------------------------------------|
+/// | Falhou (assertion)    | Passed, motivo em `expected_failure`     |
+/// | Erro (rede, params)   | Passed, motivo em `expected_failure`     |
+/// | Passou                | Failed ("esperava falha")                |
+fn apply_expect_failure(step: &Step, outcome: Result<StepResult>) -> Result<StepResult> {
+    if !step.expect_failure {
+        return outcome;
+    }
+
+    let mut result = match outcome {
+        Ok(result) => result,
+        Err(e) => StepResult {
+            step_id: step.id.clone(),
+            status: StepStatus::Failed,
+            error: Some(e.to_string()),
+            ..Default::default()
+        },
+    };
+
+    if result.status == StepStatus::Passed {
+        result.status = StepStatus::Failed;
+        result.error = Some(format!("Step '{}' tem expect_failure, mas passou", step.id));
+    } else {
+        result.status = StepStatus::Passed;
+        result.expected_failure = result.error.take();
+    }
+    Ok(result)
+}
+
+// ============================================================================
+// FAN-OUT
+// ============================================================================
+
+/// Resolve o array de itens a partir do nome (ou placeholder) da variável.
+fn resolve_items(foreach: &ParallelForeach, context: &Context) -> Result<Vec<Value>> {
+    let name = foreach
+        .items
+        .trim()
+        .strip_prefix("${")
+        .and_then(|s| s.strip_suffix('}'))
+        .unwrap_or(foreach.items.trim());
+
+    match context.get(name) {
+        Some(Value::Array(items)) => Ok(items.clone()),
+        Some(other) => Err(anyhow!(
+            "parallel_foreach: variável '{}' não é um array (encontrado: {})",
+            name,
+            other
+        )),
+        None => Err(anyhow!(
+            "parallel_foreach: variável '{}' não encontrada no contexto",
+            name
+        )),
+    }
+}
+
+/// Executa o step para cada item e agrega os resultados.
+async fn execute_foreach(
+    step: &Step,
+    foreach: &ParallelForeach,
+    executor: &dyn StepExecutor,
+    context: &mut Context,
+    clock: &dyn Clock,
+) -> Result<StepResult> {
+    let start = Instant::now();
+    let context_before = context.variables.clone();
+    let items = resolve_items(foreach, context)?;
+    // `ExecutionLimits.max_parallel` (`context.max_parallel`) é o teto; 0 = sem teto.
+    let limit = match context.max_parallel {
+        0 => usize::MAX,
+        n => n,
+    };
+    let max_parallel = foreach.max_parallel.min(limit).max(1);
+    let index_var = format!("{}_index", foreach.item_var);
+
+    info!(
+        step_id = %step.id,
+        iterations = items.len(),
+        max_parallel = max_parallel,
+        "🔀 Iniciando parallel_foreach"
+    );
+
+    // Prepara um contexto isolado por iteração.
+    let base = context.clone();
+    let runs = items.into_iter().enumerate().map(|(index, item)| {
+        let mut iteration_context = base.clone();
+        iteration_context.set(foreach.item_var.clone(), item);
+        iteration_context.set(index_var.clone(), Value::from(index));
+        iteration_context.enter_random_scope(&format!("{}[{}]", step.id, index));
+        let iteration_step = Step {
+            id: format!("{}[{}]", step.id, index),
+            parallel_foreach: None,
+            ..step.clone()
+        };
+
+        async move {
+            let outcome = execute_with_assertions_retry(
+                &iteration_step,
+                executor,
+                &mut iteration_context,
+                clock,
+            )
+            .await;
+            let result = match apply_expect_failure(&iteration_step, outcome) {
+                Ok(result) => result,
+                Err(e) => StepResult {
+                    step_id: iteration_step.id.clone(),
+                    status: StepStatus::Failed,
+                    error: Some(e.to_string()),
+                    ..Default::default()
+                },
+            };
+            (index, result, iteration_context)
+        }
+    });
+
+    // Executa no máximo `max_parallel` iterações por vez.
+    let mut outcomes: Vec<(usize, StepResult, Context)> = stream::iter(runs)
+        .buffer_unordered(max_parallel)
+        .collect()
+        .await;
+    outcomes.sort_by_key(|(index, _, _)| *index);
+
+    // Devolve as extrações ao contexto principal como arrays indexados.
+    for extraction in &step.extract {
+        let values: Vec<Value> = outcomes
+            .iter()
+            .map(|(_, _, ctx)| ctx.get(&extraction.target).cloned().unwrap_or(Value::Null))
+            .collect();
+        context.set(extraction.target.clone(), Value::Array(values));
+    }
+
+    let iterations: Vec<StepResult> = outcomes.into_iter().map(|(_, r, _)| r).collect();
+    let failed: Vec<&str> = iterations
+        .iter()
+        .filter(|r| r.status != StepStatus::Passed)
+        .map(|r| r.step_id.as_str())
+        .collect();
+
+    let (status, error) = if failed.is_empty() {
+        (StepStatus::Passed, None)
+    } else {
+        (
+            StepStatus::Failed,
+            Some(format!(
+                "{} de {} iterações falharam: {}",
+                failed.len(),
+                iterations.len(),
+                failed.join(", ")
+            )),
+        )
+    };
+
+    Ok(StepResult {
+        step_id: step.id.clone(),
+        status,
+        duration_ms: start.elapsed().as_millis() as u64,
+        error,
+        context_before: Some(context_before),
+        context_after: Some(context.variables.clone()),
+        iterations: Some(iterations),
+        ..Default::default()
+    })
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::{SystemClock, VirtualClock};
+    use crate::executors::transform::TransformExecutor;
+    use crate::protocol::{Extraction, ExtractionScope};
+    use async_trait::async_trait;
+    use serde_json::json;
+
+    /// Executor de teste: falha quando o item é negativo.
+    struct CheckPositiveExecutor;
+
+    #[async_trait]
+    impl StepExecutor for CheckPositiveExecutor {
+        fn can_handle(&self, action: &str) -> bool {
+            action == "check"
+        }
+
+        async fn execute(&self, step: &Step, context: &mut Context) -> Result<StepResult> {
+            let value = context.get("n").and_then(|v| v.as_i64()).unwrap_or(0);
+            Ok(StepResult {
+                step_id: step.id.clone(),
+                status: if value >= 0 {
+                    StepStatus::Passed
+                } else {
+                    StepStatus::Failed
+                },
+                ..Default::default()
+            })
+        }
+    }
+
+    fn foreach_step(action: &str, params: Value, items: &str, item_var: &str) -> Step {
+        Step {
+            id: "each".to_string(),
+            action: action.to_string(),
+            params,
+            parallel_foreach: Some(ParallelForeach {
+                items: items.to_string(),
+                item_var: item_var.to_string(),
+                max_parallel: 2,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_foreach_collects_indexed_results() {
+        let mut context = Context::new();
+        context.set("numbers", json!([1, -2, 3]));
+
+        let step = foreach_step("check", json!({}), "${numbers}", "n");
+        let result = execute_step(&step, &CheckPositiveExecutor, &mut context, &SystemClock)
+            .await
+            .unwrap();
+
+        assert_eq!(result.status, StepStatus::Failed);
+        let iterations = result.iterations.unwrap();
+        assert_eq!(iterations.len(), 3);
+        assert_eq!(iterations[1].step_id, "each[1]");
+        assert_eq!(iterations[1].status, StepStatus::Failed);
+        assert!(result.error.unwrap().contains("each[1]"));
+    }
+
+    #[tokio::test]
+    async fn test_foreach_merges_extractions_as_arrays() {
+        let mut context = Context::new();
+        context.set("words", json!(["a", "bc"]));
+
+        let mut step = foreach_step(
+            "transform",
+            json!({ "from": "word", "op": "upper", "target": "upper" }),
+            "words",
+            "word",
+        );
+        step.extract = vec![Extraction {
+            source: "body".to_string(),
+            path: "$".to_string(),
+            target: "upper".to_string(),
+            all_values: false,
+            critical: false,
+            scope: ExtractionScope::Plan,
+        }];
+
+        let result = execute_step(&step, &TransformExecutor::new(), &mut context, &SystemClock)
+            .await
+            .unwrap();
+
+        assert_eq!(result.status, StepStatus::Passed);
+        assert_eq!(context.get("upper"), Some(&json!(["A", "BC"])));
+        assert!(context.get("word").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_expect_failure_inverts_result() {
+        let mut context = Context::new();
+        let mut step = foreach_step("check", json!({}), "numbers", "n");
+        step.expect_failure = true;
+
+        // Sem fan-out: n negativo falha no executor, então o step passa.
+        step.parallel_foreach = None;
+        context.set("n", json!(-1));
+        let result = execute_step(&step, &CheckPositiveExecutor, &mut context, &SystemClock)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Passed);
+
+        context.set("n", json!(1));
+        let result = execute_step(&step, &CheckPositiveExecutor, &mut context, &SystemClock)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Failed);
+        assert!(result.error.unwrap().contains("expect_failure"));
+
+        // Com fan-out: cada iteração precisa falhar.
+        let mut step = foreach_step("check", json!({}), "numbers", "n");
+        step.expect_failure = true;
+        context.set("numbers", json!([-1, 2]));
+        let result = execute_step(&step, &CheckPositiveExecutor, &mut context, &SystemClock)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Failed);
+        assert!(result.error.unwrap().contains("each[1]"));
+    }
+
+    /// Executor de teste: a assertion só passa a partir da 3ª avaliação.
+    struct EventuallyConsistentExecutor(std::sync::atomic::AtomicU32);
+
+    #[async_trait]
+    impl StepExecutor for EventuallyConsistentExecutor {
+        fn can_handle(&self, _action: &str) -> bool {
+            true
+        }
+
+        async fn execute(&self, step: &Step, _context: &mut Context) -> Result<StepResult> {
+            let call = self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+            Ok(StepResult {
+                step_id: step.id.clone(),
+                status: if call >= 3 {
+                    StepStatus::Passed
+                } else {
+                    StepStatus::Failed
+                },
+                ..Default::default()
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_assertions_retry_until_pass() {
+        let clock = VirtualClock::instant();
+        let mut context = Context::new();
+        let mut step = foreach_step("search", json!({}), "items", "item");
+        step.parallel_foreach = None;
+        step.assertions_retry = Some(crate::protocol::AssertionsRetry {
+            max_attempts: 5,
+            interval_ms: 200,
+        });
+
+        let executor = EventuallyConsistentExecutor(Default::default());
+        let result = execute_step(&step, &executor, &mut context, &clock)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Passed);
+        assert_eq!(result.assertion_attempts, Some(3));
+        // Separado do retry da RecoveryPolicy.
+        assert_eq!(result.attempt, 1);
+        assert_eq!(clock.virtual_elapsed_ms(), 400);
+
+        // Tentativas esgotadas: falha com o número de avaliações feitas.
+        step.assertions_retry.as_mut().unwrap().max_attempts = 2;
+        let executor = EventuallyConsistentExecutor(Default::default());
+        let result = execute_step(&step, &executor, &mut context, &clock)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Failed);
+        assert_eq!(result.assertion_attempts, Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_latency_budget_recorded_without_failing() {
+        let mut context = Context::new();
+        let mut step = foreach_step("check", json!({}), "numbers", "n");
+        step.parallel_foreach = None;
+        step.latency_budget_ms = Some(0);
+        context.set("n", json!(1));
+
+        let mut slow = execute_step(&step, &CheckPositiveExecutor, &mut context, &SystemClock)
+            .await
+            .unwrap();
+        assert_eq!(slow.status, StepStatus::Passed);
+        assert_eq!(
+            slow.latency_budget,
+            Some(LatencyBudget {
+                budget_ms: 0,
+                latency_ms: 0,
+                exceeded: false
+            })
+        );
+
+        slow.step_id = "slow".to_string();
+        slow.latency_budget = Some(LatencyBudget {
+            budget_ms: 100,
+            latency_ms: 250,
+            exceeded: true,
+        });
+        let results = vec![slow, StepResult::default()];
+        let slo = crate::protocol::SloSummary::from_results(&results).unwrap();
+        assert_eq!(slo.steps_with_budget, 1);
+        assert_eq!(slo.compliance_pct, 0.0);
+        assert_eq!(slo.violations[0].step_id, "slow");
+        assert!(crate::protocol::SloSummary::from_results(&results[1..]).is_none());
+
+        // Aquecimento fica fora do SLO.
+        step.warmup = true;
+        let warm = execute_step(&step, &CheckPositiveExecutor, &mut context, &SystemClock)
+            .await
+            .unwrap();
+        assert!(warm.warmup);
+        assert!(warm.latency_budget.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_clock_skew_scoped_to_step() {
+        use crate::executors::set_variable::SetVariableExecutor;
+
+        let mut context = Context::new();
+        let step = Step {
+            id: "skewed".to_string(),
+            action: "set_variable".to_string(),
+            params: json!({ "name": "sent_at", "value": "${timestamp}" }),
+            clock_skew_ms: Some(-600_000),
+            ..Default::default()
+        };
+        execute_step(
+            &step,
+            &SetVariableExecutor::new(),
+            &mut context,
+            &SystemClock,
+        )
+        .await
+        .unwrap();
+
+        let sent_at: i64 = context
+            .get("sent_at")
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(chrono::Utc::now().timestamp() - sent_at >= 599);
+        assert_eq!(context.clock_skew_ms, 0);
+    }
+
+    #[tokio::test]
+    async fn test_foreach_requires_array() {
+        let mut context = Context::new();
+        context.set("numbers", json!(5));
+
+        let step = foreach_step("check", json!({}), "numbers", "n");
+        assert!(
+            execute_step(&step, &CheckPositiveExecutor, &mut context, &SystemClock)
+                .await
+                .is_err()
+        );
+    }
+
+    /// Executor de teste: mede quantas iterações rodam ao mesmo tempo.
+    #[derive(Default)]
+    struct PeakExecutor {
+        running: std::sync::atomic::AtomicUsize,
+        peak: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl StepExecutor for PeakExecutor {
+        fn can_handle(&self, action: &str) -> bool {
+            action == "peak"
+        }
+
+        async fn execute(&self, step: &Step, _context: &mut Context) -> Result<StepResult> {
+            use std::sync::atomic::Ordering;
+            let now = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            Ok(StepResult {
+                step_id: step.id.clone(),
+                status: StepStatus::Passed,
+                ..Default::default()
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_foreach_parallelism_is_capped_by_limits() {
+        let mut context = Context::new();
+        context.set("items", json!([1, 2, 3, 4, 5, 6]));
+        context.max_parallel = 2;
+
+        let mut step = foreach_step("peak", json!({}), "${items}", "n");
+        step.parallel_foreach.as_mut().unwrap().max_parallel = 8;
+        let executor = PeakExecutor::default();
+        let result = execute_step(&step, &executor, &mut context, &SystemClock)
+            .await
+            .unwrap();
+
+        assert_eq!(result.status, StepStatus::Passed);
+        assert_eq!(executor.peak.into_inner(), 2);
+        assert!(context.get("max_parallel").is_none());
+
+        // 0 (sem limite, como no DAG) não derruba o fan-out para 1.
+        context.max_parallel = 0;
+        let executor = PeakExecutor::default();
+        execute_step(&step, &executor, &mut context, &SystemClock)
+            .await
+            .unwrap();
+        assert_eq!(executor.peak.into_inner(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_false_condition_marks_step_not_run() {
+        let mut context = Context::new();
+        context.set("user", json!({ "role": "guest" }));
+        let mut step = Step {
+            id: "admin_only".to_string(),
+            action: "peak".to_string(),
+            condition: Some(
+                serde_json::from_value(json!({
+                    "path": "user.role", "operator": "eq", "value": "admin"
+                }))
+                .unwrap(),
+            ),
+            ..Default::default()
+        };
+        let executor = PeakExecutor::default();
+
+        let result = execute_step(&step, &executor, &mut context, &SystemClock)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::NotRun);
+        assert_eq!(result.skip_reason, Some(SkipReason::ConditionFalse));
+        assert!(result.status.is_success());
+        assert_eq!(executor.peak.load(std::sync::atomic::Ordering::SeqCst), 0);
+
+        // Condição verdadeira: o step roda normalmente.
+        step.condition.as_mut().unwrap().value = json!("guest");
+        let result = execute_step(&step, &executor, &mut context, &SystemClock)
+            .await
+            .unwrap();
+        assert_eq!(result.status, StepStatus::Passed);
+        assert_eq!(executor.peak.into_inner(), 1);
+    }
+}
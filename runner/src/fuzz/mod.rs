@@ -0,0 +1,246 @@
+//! # Módulo de Fuzz - Testes Negativos Derivados
+//!
+//! Implementa `runner fuzz`: a partir de um step `http_request` que já
+//! funciona, gera variações inválidas do body e espera que a API as
+//! rejeite com 4xx.
+//!
+//! ## Para todos entenderem:
+//!
+//! Um plano costuma testar o caminho feliz: "criar usuário com dados
+//! válidos retorna 201". A API também deveria recusar dados ruins com um
+//! 4xx, e não com 500 ou, pior, 201. Escrever esses casos à mão é
+//! repetitivo; o `fuzz` deriva um step por mutação:
+//!
+//! | Mutação         | Exemplo (`"email": "ana@mail.com"`)     | Step gerado                       |
+//! |-----------------|-----------------------------------------|-----------------------------------|
+//! | `missing`       | campo removido                          | `create_user__fuzz_missing_email` |
+//! | `wrong_type`    | `"email": 12345`                        | `..._wrong_type_email`            |
+//! | `oversized`     | string com 10.000 caracteres            | `..._oversized_email`             |
+//! | `invalid_enum`  | `"role": "__invalid_enum__"` (só em valores com cara de enum) | `..._invalid_enum_role` |
+//!
+//! Cada step derivado mantém URL, headers e `depends_on` do original,
+//! não extrai nada e tem uma única assertion: `status_range eq 4xx`.
+//!
+//! ```bash
+//! runner fuzz --file plan.json --step create_user --output fuzz.json
+//! runner execute --file fuzz.json
+//! ```
+//!
+//! O plano gerado contém as dependências do step (login, setup) e os
+//! steps derivados; o step original fica de fora.
+
+use anyhow::{anyhow, Result};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+use crate::protocol::{Assertion, Plan, Step};
+
+/// Tamanho da string usada na mutação `oversized`.
+const OVERSIZED_LEN: usize = 10_000;
+
+/// Valor usado na mutação `invalid_enum`.
+const INVALID_ENUM: &str = "__invalid_enum__";
+
+/// Tag adicionada aos steps derivados (filtrável por perfil).
+const FUZZ_TAG: &str = "fuzz";
+
+// ============================================================================
+// FUNÇÕES PRINCIPAIS
+// ============================================================================
+
+/// Reduz o plano às dependências de `step_id` mais as mutações do step.
+pub fn expand_plan(plan: &mut Plan, step_id: &str) -> Result<usize> {
+    let target = plan
+        .steps
+        .iter()
+        .find(|s| s.id == step_id)
+        .ok_or_else(|| anyhow!("Step '{}' não existe no plano", step_id))?;
+    let derived = mutations(target)?;
+    let count = derived.len();
+
+    let dependencies = dependency_closure(&plan.steps, target);
+    plan.steps.retain(|s| dependencies.contains(&s.id));
+    plan.steps.extend(derived);
+    Ok(count)
+}
+
+/// Steps derivados de um `http_request` com body JSON (objeto).
+pub fn mutations(step: &Step) -> Result<Vec<Step>> {
+    if step.action != "http_request" {
+        return Err(anyhow!(
+            "Step '{}' é '{}'; fuzz só se aplica a http_request",
+            step.id,
+            step.action
+        ));
+    }
+    let body = step
+        .params
+        .get("body")
+        .and_then(Value::as_object)
+        .ok_or_else(|| anyhow!("Step '{}' não tem params.body como objeto JSON", step.id))?;
+
+    let mut steps = Vec::new();
+    for (field, value) in body {
+        let mut variants = vec![("missing", None), ("wrong_type", Some(wrong_type(value)))];
+        if let Some(text) = value.as_str() {
+            variants.push(("oversized", Some(Value::String("A".repeat(OVERSIZED_LEN)))));
+            if looks_like_enum(text) {
+                variants.push((
+                    "invalid_enum",
+                    Some(Value::String(INVALID_ENUM.to_string())),
+                ));
+            }
+        }
+
+        for (kind, replacement) in variants {
+            let mut mutated: Map<String, Value> = body.clone();
+            match replacement {
+                Some(value) => mutated.insert(field.clone(), value),
+                None => mutated.remove(field),
+            };
+            steps.push(derive(step, kind, field, Value::Object(mutated)));
+        }
+    }
+    Ok(steps)
+}
+
+// ============================================================================
+// MUTAÇÕES
+// ============================================================================
+
+fn derive(step: &Step, kind: &str, field: &str, body: Value) -> Step {
+    let mut params = step.params.clone();
+    params["body"] = body;
+
+    let mut tags = step.tags.clone();
+    tags.push(FUZZ_TAG.to_string());
+
+    Step {
+        id: format!("{}__fuzz_{}_{}", step.id, kind, field),
+        description: Some(format!("{} ({}: {})", step.id, kind, field)),
+        depends_on: step.depends_on.clone(),
+        action: step.action.clone(),
+        params,
+        assertions: vec![Assertion {
+            assertion_type: "status_range".to_string(),
+            operator: "eq".to_string(),
+            value: Value::String("4xx".to_string()),
+            ..Default::default()
+        }],
+        tags,
+        session: step.session.clone(),
+        actor: step.actor.clone(),
+        agent: step.agent.clone(),
+        ..Default::default()
+    }
+}
+
+/// Valor de outro tipo JSON que o campo não deveria aceitar.
+fn wrong_type(value: &Value) -> Value {
+    match value {
+        Value::String(_) => Value::from(12345),
+        Value::Number(_) => Value::from("not-a-number"),
+        Value::Bool(_) => Value::from("not-a-boolean"),
+        Value::Array(_) | Value::Object(_) => Value::from("not-a-structure"),
+        Value::Null => Value::from(0),
+    }
+}
+
+/// Strings curtas em snake/kebab/UPPER case (`admin`, `PENDING`, `in-review`).
+fn looks_like_enum(text: &str) -> bool {
+    !text.is_empty()
+        && text.len() <= 32
+        && text.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        && text
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn dependency_closure(steps: &[Step], target: &Step) -> HashSet<String> {
+    let mut selected = HashSet::new();
+    let mut pending: Vec<&str> = target.depends_on.iter().map(String::as_str).collect();
+    while let Some(id) = pending.pop() {
+        if !selected.insert(id.to_string()) {
+            continue;
+        }
+        if let Some(step) = steps.iter().find(|s| s.id == id) {
+            pending.extend(step.depends_on.iter().map(String::as_str));
+        }
+    }
+    selected
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn plan() -> Plan {
+        serde_json::from_value(json!({
+            "spec_version": "0.1",
+            "meta": { "id": "p", "name": "Users", "created_at": "2024-01-01T00:00:00Z" },
+            "config": { "base_url": "http://api", "timeout_ms": 5000 },
+            "steps": [
+                { "id": "login", "action": "http_request",
+                  "params": { "method": "POST", "path": "/login" } },
+                { "id": "health", "action": "http_request",
+                  "params": { "method": "GET", "path": "/health" } },
+                { "id": "create_user", "action": "http_request", "depends_on": ["login"],
+                  "params": { "method": "POST", "path": "/users",
+                              "body": { "email": "ana@mail.com", "age": 30, "role": "admin" } },
+                  "assertions": [{ "type": "status_code", "operator": "eq", "value": 201 }],
+                  "extract": [{ "source": "body", "path": "id", "target": "user_id" }] }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_mutations_per_field() {
+        let plan = plan();
+        let steps = mutations(&plan.steps[2]).unwrap();
+        let ids: Vec<&str> = steps.iter().map(|s| s.id.as_str()).collect();
+
+        // age: missing + wrong_type; email: + oversized; role: + invalid_enum.
+        assert_eq!(steps.len(), 2 + 3 + 4);
+        assert!(ids.contains(&"create_user__fuzz_missing_age"));
+        assert!(!ids.contains(&"create_user__fuzz_invalid_enum_email"));
+
+        let enum_step = steps
+            .iter()
+            .find(|s| s.id == "create_user__fuzz_invalid_enum_role")
+            .unwrap();
+        assert_eq!(enum_step.params["body"]["role"], INVALID_ENUM);
+        assert_eq!(enum_step.params["body"]["age"], 30);
+        assert_eq!(enum_step.assertions[0].assertion_type, "status_range");
+        assert!(enum_step.extract.is_empty());
+        assert_eq!(enum_step.depends_on, vec!["login"]);
+
+        let missing = steps
+            .iter()
+            .find(|s| s.id == "create_user__fuzz_missing_email")
+            .unwrap();
+        assert!(missing.params["body"].get("email").is_none());
+    }
+
+    #[test]
+    fn test_expand_plan_keeps_only_dependencies_and_derived() {
+        let mut plan = plan();
+        let count = expand_plan(&mut plan, "create_user").unwrap();
+
+        assert_eq!(plan.steps[0].id, "login");
+        assert_eq!(plan.steps.len(), 1 + count);
+        assert!(plan
+            .steps
+            .iter()
+            .all(|s| s.id != "health" && s.id != "create_user"));
+
+        assert!(expand_plan(&mut plan, "missing").is_err());
+        let err = mutations(&plan.steps[0]).unwrap_err().to_string();
+        assert!(err.contains("params.body"), "{}", err);
+    }
+}
@@ -0,0 +1,208 @@
+//! # Módulo de Heartbeat - Sinal de Vida em Execuções Longas
+//!
+//! Emite periodicamente o progresso da execução (steps concluídos,
+//! falhos e total) enquanto o plano roda.
+//!
+//! ## Para todos entenderem:
+//!
+//! Uma suíte de 40 minutos que não imprime nada por 10 minutos está
+//! travada ou só lenta? Sem sinal de vida, o orquestrador (CI, Kubernetes)
+//! não sabe se deve matar o processo. O heartbeat é o "ainda estou aqui":
+//!
+//! | Canal                  | Quando                        | Formato                          |
+//! |------------------------|-------------------------------|----------------------------------|
+//! | Log                    | Sempre                        | `Heartbeat completed=12 total=40` |
+//! | Relatório parcial      | Com `--output`                | `{"event":"heartbeat",...}`      |
+//! | HTTP                   | Com `--heartbeat-url`         | `POST` com o mesmo JSON          |
+//!
+//! Se os contadores param de subir entre heartbeats, o run está travado em
+//! um step; se os heartbeats param, o processo morreu.
+//!
+//! ## Exemplo:
+//!
+//! ```bash
+//! runner execute --file plan.json --heartbeat-secs 15 --heartbeat-url http://orchestrator/runs/42/ping
+//! ```
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::protocol::{StepResult, StepStatus};
+use crate::streaming::ResultStream;
+
+/// Tempo máximo de cada ping HTTP (não pode atrasar o próximo heartbeat).
+const PING_TIMEOUT: Duration = Duration::from_secs(5);
+
+// ============================================================================
+// PROGRESSO
+// ============================================================================
+
+/// Contadores de progresso compartilhados entre os steps e o heartbeat.
+#[derive(Debug)]
+pub struct Progress {
+    total: usize,
+    completed: AtomicUsize,
+    failed: AtomicUsize,
+    started: Instant,
+}
+
+impl Progress {
+    /// Progresso de uma execução com `total` steps.
+    pub fn new(total: usize) -> Arc<Self> {
+        Arc::new(Self {
+            total,
+            completed: AtomicUsize::new(0),
+            failed: AtomicUsize::new(0),
+            started: Instant::now(),
+        })
+    }
+
+    /// Conta um step concluído.
+    pub fn record(&self, result: &StepResult) {
+        self.completed.fetch_add(1, Ordering::Relaxed);
+        if result.status == StepStatus::Failed {
+            self.failed.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Fotografia atual do progresso.
+    pub fn snapshot(&self, execution_id: &str) -> Heartbeat {
+        Heartbeat {
+            execution_id: execution_id.to_string(),
+            completed: self.completed.load(Ordering::Relaxed),
+            failed: self.failed.load(Ordering::Relaxed),
+            total: self.total,
+            elapsed_ms: self.started.elapsed().as_millis() as u64,
+        }
+    }
+}
+
+/// Um sinal de vida com o progresso da execução.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Heartbeat {
+    pub execution_id: String,
+    pub completed: usize,
+    pub failed: usize,
+    pub total: usize,
+    pub elapsed_ms: u64,
+}
+
+// ============================================================================
+// TAREFA EM BACKGROUND
+// ============================================================================
+
+/// Configuração do heartbeat (`--heartbeat-secs`, `--heartbeat-url`).
+#[derive(Debug, Clone)]
+pub struct HeartbeatOptions {
+    pub interval: Duration,
+    pub url: Option<String>,
+}
+
+/// Inicia o heartbeat; aborte o handle quando a execução terminar.
+pub fn spawn(
+    options: HeartbeatOptions,
+    execution_id: String,
+    progress: Arc<Progress>,
+    stream: Option<Arc<ResultStream>>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let client = options.url.as_ref().map(|_| {
+            reqwest::Client::builder()
+                .timeout(PING_TIMEOUT)
+                .build()
+                .unwrap_or_default()
+        });
+        let mut ticker = tokio::time::interval(options.interval);
+        // O primeiro tick é imediato: o início já é anunciado pelo log.
+        ticker.tick().await;
+
+        loop {
+            ticker.tick().await;
+            let beat = progress.snapshot(&execution_id);
+            info!(
+                completed = beat.completed,
+                failed = beat.failed,
+                total = beat.total,
+                elapsed_ms = beat.elapsed_ms,
+                "Heartbeat"
+            );
+            if let Some(stream) = &stream {
+                stream.heartbeat(&beat);
+            }
+            if let (Some(client), Some(url)) = (&client, &options.url) {
+                if let Err(e) = client.post(url).json(&beat).send().await {
+                    warn!(url = %url, error = %e, "Heartbeat ping failed");
+                }
+            }
+        }
+    })
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(status: StepStatus) -> StepResult {
+        StepResult {
+            status,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_progress_counts_completed_and_failed() {
+        let progress = Progress::new(3);
+        progress.record(&result(StepStatus::Passed));
+        progress.record(&result(StepStatus::Failed));
+
+        let beat = progress.snapshot("exec-1");
+        assert_eq!(beat.execution_id, "exec-1");
+        assert_eq!((beat.completed, beat.failed, beat.total), (2, 1, 3));
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_pings_url() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/ping", listener.local_addr().unwrap());
+        let progress = Progress::new(5);
+        progress.record(&result(StepStatus::Passed));
+
+        let handle = spawn(
+            HeartbeatOptions {
+                interval: Duration::from_millis(20),
+                url: Some(url),
+            },
+            "exec-9".to_string(),
+            progress,
+            None,
+        );
+
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buffer = vec![0u8; 4096];
+        let mut request = String::new();
+        while !request.contains("\"elapsed_ms\"") {
+            let n = socket.read(&mut buffer).await.unwrap();
+            request.push_str(&String::from_utf8_lossy(&buffer[..n]));
+        }
+        socket
+            .write_all(b"HTTP/1.1 204 No Content\r\n\r\n")
+            .await
+            .unwrap();
+        handle.abort();
+
+        assert!(request.starts_with("POST /ping"));
+        assert!(request.contains("\"execution_id\":\"exec-9\""));
+        assert!(request.contains("\"completed\":1"));
+        assert!(request.contains("\"total\":5"));
+    }
+}
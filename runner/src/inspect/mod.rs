@@ -0,0 +1,241 @@
+//! # Módulo de Inspeção - `runner context`
+//!
+//! Mostra, a partir de um relatório salvo, o que aconteceu com o contexto
+//! em um step: variáveis alteradas, extrações e a requisição enviada.
+//!
+//! ## Para todos entenderem:
+//!
+//! O relatório JSON tem tudo, mas encontrar "por que `${user_id}` estava
+//! vazio no step seguinte" exige navegar por centenas de linhas. O
+//! inspetor recorta só o step pedido:
+//!
+//! ```bash
+//! runner context --report report.json --step create_user
+//! ```
+//!
+//! ```text
+//! Step create_user: failed
+//!   erro: Assertion failed: status_code eq 201
+//!
+//! Variáveis
+//!   + user_email = "ana@mail.com"
+//!   ~ attempts = 2
+//!
+//! Extrações
+//!   ✗ user_id ← body $.id: Path not found
+//!
+//! Requisição
+//!   POST https://api/users → 400 (85ms)
+//!   Content-Type: application/json
+//!   {"email":"ana@mail.com"}
+//! ```
+//!
+//! Steps de `parallel_foreach` são encontrados pelo id da iteração
+//! (`create_user[2]`).
+
+use anyhow::{anyhow, Context as _, Result};
+use serde::Deserialize;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::protocol::{ContextDelta, StepResult};
+
+/// Campos do relatório usados pelo inspetor.
+#[derive(Debug, Deserialize)]
+struct SavedReport {
+    steps: Vec<StepResult>,
+}
+
+// ============================================================================
+// FUNÇÕES PRINCIPAIS
+// ============================================================================
+
+/// Lê o relatório e devolve o resultado do step `step_id`.
+pub fn load_step(path: &Path, step_id: &str) -> Result<StepResult> {
+    let content = crate::report_file::read_to_string(path)
+        .with_context(|| format!("Falha ao ler relatório {:?}", path))?;
+    let report: SavedReport =
+        serde_json::from_str(&content).with_context(|| format!("Relatório inválido {:?}", path))?;
+
+    find_step(report.steps, step_id).map_err(|available| {
+        anyhow!(
+            "Step '{}' não está no relatório. Disponíveis: {}",
+            step_id,
+            available.join(", ")
+        )
+    })
+}
+
+/// Formata a visão de depuração de um step.
+pub fn render(result: &StepResult) -> String {
+    let mut out = String::new();
+    let status = serde_json::to_value(&result.status)
+        .ok()
+        .and_then(|s| s.as_str().map(str::to_string))
+        .unwrap_or_default();
+    let _ = writeln!(out, "Step {}: {}", result.step_id, status);
+    if let Some(error) = &result.error {
+        let _ = writeln!(out, "  erro: {}", error);
+    }
+
+    let _ = writeln!(out, "\nVariáveis");
+    match variable_delta(result) {
+        Some(delta) if !is_empty(&delta) => {
+            for (name, value) in &delta.added {
+                let _ = writeln!(out, "  + {} = {}", name, value);
+            }
+            for (name, value) in &delta.changed {
+                let _ = writeln!(out, "  ~ {} = {}", name, value);
+            }
+            for name in &delta.removed {
+                let _ = writeln!(out, "  - {}", name);
+            }
+        }
+        Some(_) => out.push_str("  (nenhuma alteração)\n"),
+        None => out.push_str("  (sem snapshot do contexto no relatório)\n"),
+    }
+
+    let extractions = result.extractions.as_deref().unwrap_or_default();
+    if !extractions.is_empty() {
+        let _ = writeln!(out, "\nExtrações");
+        for extraction in extractions {
+            let origin = format!(
+                "{} ← {} {}",
+                extraction.target, extraction.source, extraction.path
+            );
+            match (&extraction.value, &extraction.error) {
+                (Some(value), _) if extraction.success => {
+                    let _ = writeln!(out, "  ✓ {} = {}", origin, value);
+                }
+                (_, error) => {
+                    let _ = writeln!(
+                        out,
+                        "  ✗ {}: {}",
+                        origin,
+                        error.as_deref().unwrap_or("sem valor")
+                    );
+                }
+            }
+        }
+    }
+
+    if let Some(http) = &result.http_details {
+        let _ = writeln!(out, "\nRequisição");
+        let _ = writeln!(
+            out,
+            "  {} {} → {} ({}ms)",
+            http.method, http.url, http.status_code, http.latency_ms
+        );
+        if let Some(headers) = &http.request_headers {
+            let mut headers: Vec<_> = headers.iter().collect();
+            headers.sort();
+            for (name, value) in headers {
+                let _ = writeln!(out, "  {}: {}", name, value);
+            }
+        }
+        if let Some(body) = &http.request_body {
+            let _ = writeln!(out, "  {}", body);
+        }
+    }
+
+    out
+}
+
+// ============================================================================
+// AUXILIARES
+// ============================================================================
+
+/// Busca o step (ou a iteração) pelo id; no erro, os ids disponíveis.
+fn find_step(steps: Vec<StepResult>, step_id: &str) -> Result<StepResult, Vec<String>> {
+    let mut available = Vec::new();
+    for result in steps {
+        if result.step_id == step_id {
+            return Ok(result);
+        }
+        available.push(result.step_id.clone());
+        if let Some(iterations) = result.iterations {
+            if let Some(found) = iterations.into_iter().find(|i| i.step_id == step_id) {
+                return Ok(found);
+            }
+        }
+    }
+    Err(available)
+}
+
+/// O delta do relatório, ou calculado a partir dos snapshots completos.
+fn variable_delta(result: &StepResult) -> Option<ContextDelta> {
+    if let Some(delta) = &result.context_delta {
+        return Some(delta.clone());
+    }
+    match (&result.context_before, &result.context_after) {
+        (Some(before), Some(after)) => Some(ContextDelta::between(before, after)),
+        _ => None,
+    }
+}
+
+fn is_empty(delta: &ContextDelta) -> bool {
+    delta.added.is_empty() && delta.changed.is_empty() && delta.removed.is_empty()
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn report() -> Vec<StepResult> {
+        serde_json::from_value(json!([
+            {
+                "step_id": "create_user", "status": "failed", "duration_ms": 90,
+                "error": "Assertion failed: status_code eq 201",
+                "context_before": { "attempts": 1, "stale": true },
+                "context_after": { "attempts": 2, "user_email": "ana@mail.com" },
+                "extractions": [
+                    { "target": "user_id", "source": "body", "path": "$.id",
+                      "error": "Path not found", "success": false }
+                ],
+                "http_details": {
+                    "method": "POST", "url": "https://api/users", "status_code": 400,
+                    "latency_ms": 85,
+                    "request_headers": { "Content-Type": "application/json" },
+                    "request_body": { "email": "ana@mail.com" }
+                }
+            },
+            {
+                "step_id": "fanout", "status": "passed", "duration_ms": 5,
+                "iterations": [
+                    { "step_id": "fanout[0]", "status": "passed", "duration_ms": 2,
+                      "context_delta": { "added": { "item": 1 } } }
+                ]
+            }
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_render_step_debug_view() {
+        let result = find_step(report(), "create_user").unwrap();
+        let text = render(&result);
+
+        assert!(text.starts_with("Step create_user: failed\n"));
+        assert!(text.contains("+ user_email = \"ana@mail.com\""));
+        assert!(text.contains("~ attempts = 2"));
+        assert!(text.contains("- stale"));
+        assert!(text.contains("✗ user_id ← body $.id: Path not found"));
+        assert!(text.contains("POST https://api/users → 400 (85ms)"));
+        assert!(text.contains("Content-Type: application/json"));
+        assert!(text.contains("{\"email\":\"ana@mail.com\"}"));
+    }
+
+    #[test]
+    fn test_find_iteration_and_unknown_step() {
+        let iteration = find_step(report(), "fanout[0]").unwrap();
+        assert!(render(&iteration).contains("+ item = 1"));
+
+        let available = find_step(report(), "missing").unwrap_err();
+        assert_eq!(available, vec!["create_user", "fanout"]);
+    }
+}
@@ -0,0 +1,334 @@
+//! # Módulo de Isolamento - Ambiente e Diretórios por Step
+//!
+//! Base dos executores que rodam processos ou mexem em arquivos: cada step
+//! recebe variáveis de ambiente explícitas e um diretório de trabalho
+//! próprio, apagado no teardown da execução.
+//!
+//! ## Para todos entenderem:
+//!
+//! Em agentes de CI compartilhados, um comando que herda o ambiente do
+//! Runner enxerga tokens de deploy, e arquivos temporários de uma execução
+//! aparecem na seguinte. Com isolamento:
+//!
+//! | Recurso             | Sem isolamento               | Com isolamento                         |
+//! |---------------------|------------------------------|----------------------------------------|
+//! | Variáveis de env    | Todas as do Runner           | Só `inherit_env` + `env` do step       |
+//! | Diretório de trabalho | Diretório atual do Runner  | `<run_dir>/<step_id>[/<workdir>]`      |
+//! | `TMPDIR`            | `/tmp` global                | Diretório do step                      |
+//! | Limpeza             | Manual                       | Automática no fim da execução          |
+//!
+//! ## Parâmetros do step:
+//!
+//! ```json
+//! "params": {
+//!   "env": { "DATABASE_URL": "${db_url}" },
+//!   "inherit_env": ["PATH", "HOME"],
+//!   "workdir": "fixtures"
+//! }
+//! ```
+//!
+//! - `env`: variáveis definidas para o processo (aceitam `${...}`).
+//! - `inherit_env`: nomes copiados do ambiente do Runner (padrão:
+//!   `DEFAULT_INHERITED_ENV`).
+//! - `workdir`: relativo ao diretório do step (sem `..`) ou absoluto.
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::context::Context;
+use crate::output::sanitize_file_name;
+use crate::protocol::Step;
+
+/// Variáveis herdadas do Runner quando o step não define `inherit_env`.
+pub const DEFAULT_INHERITED_ENV: &[&str] = &["PATH", "HOME", "LANG", "SYSTEMROOT"];
+
+/// Variáveis apontadas para o diretório do step (temporários ficam nele).
+const TEMP_ENV: &[&str] = &["TMPDIR", "TEMP", "TMP"];
+
+// ============================================================================
+// DIRETÓRIO DA EXECUÇÃO
+// ============================================================================
+
+/// Diretório temporário de uma execução, com um subdiretório por step.
+///
+/// Só é criado no primeiro `step_dir` e é removido em `cleanup` (ou no
+/// `Drop`, caso a execução seja interrompida). A raiz é criada de forma
+/// exclusiva (`0700` no Unix): se o caminho já existir, o step falha em vez
+/// de reaproveitar um diretório que outro processo pode ter preparado.
+pub struct RunWorkspace {
+    root: PathBuf,
+    created: Mutex<bool>,
+}
+
+impl RunWorkspace {
+    /// Workspace em `<temp>/aqa-run-<execution_id>-<aleatório>`.
+    pub fn new(execution_id: &str) -> Self {
+        Self::at(std::env::temp_dir().join(format!(
+            "aqa-run-{}-{}",
+            sanitize_file_name(execution_id),
+            uuid::Uuid::new_v4().simple()
+        )))
+    }
+
+    /// Workspace em um diretório específico.
+    pub fn at(root: PathBuf) -> Self {
+        Self {
+            root,
+            created: Mutex::new(false),
+        }
+    }
+
+    /// Diretório raiz da execução.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Diretório do step (criado se necessário).
+    pub fn step_dir(&self, step_id: &str) -> Result<PathBuf> {
+        {
+            let mut created = self
+                .created
+                .lock()
+                .map_err(|_| anyhow!("Workspace da execução indisponível"))?;
+            if !*created {
+                create_exclusive_dir(&self.root).with_context(|| {
+                    format!("Falha ao criar diretório da execução: {:?}", self.root)
+                })?;
+                *created = true;
+            }
+        }
+        let dir = self.root.join(sanitize_file_name(step_id));
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Falha ao criar diretório do step: {:?}", dir))?;
+        Ok(dir)
+    }
+
+    /// Remove o diretório da execução (teardown).
+    pub fn cleanup(&self) -> Result<()> {
+        let Ok(mut created) = self.created.lock() else {
+            return Ok(());
+        };
+        if *created && self.root.exists() {
+            std::fs::remove_dir_all(&self.root)
+                .with_context(|| format!("Falha ao remover {:?}", self.root))?;
+        }
+        *created = false;
+        Ok(())
+    }
+}
+
+impl Drop for RunWorkspace {
+    fn drop(&mut self) {
+        let _ = self.cleanup();
+    }
+}
+
+/// Cria o diretório só se ele ainda não existir (nunca reaproveita).
+fn create_exclusive_dir(path: &Path) -> std::io::Result<()> {
+    let mut builder = std::fs::DirBuilder::new();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    builder.create(path)
+}
+
+// ============================================================================
+// AMBIENTE DO STEP
+// ============================================================================
+
+/// Ambiente e diretório de trabalho de um processo disparado por um step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepEnv {
+    /// Variáveis exatas do processo (nada além disso é herdado).
+    pub vars: BTreeMap<String, String>,
+
+    /// Diretório de trabalho do processo.
+    pub workdir: PathBuf,
+}
+
+impl StepEnv {
+    /// Monta o ambiente a partir de `env`, `inherit_env` e `workdir` do step.
+    pub fn from_step(step: &Step, context: &Context, workspace: &RunWorkspace) -> Result<Self> {
+        let step_dir = workspace.step_dir(&step.id)?;
+        let mut vars = BTreeMap::new();
+
+        match step.params.get("inherit_env") {
+            None => inherit(&mut vars, DEFAULT_INHERITED_ENV.iter().copied()),
+            Some(Value::Array(names)) => {
+                inherit(&mut vars, names.iter().filter_map(|n| n.as_str()));
+            }
+            Some(_) => bail!("Step '{}': inherit_env deve ser uma lista", step.id),
+        }
+
+        for name in TEMP_ENV {
+            vars.insert(name.to_string(), step_dir.to_string_lossy().into_owned());
+        }
+
+        match step.params.get("env") {
+            None => {}
+            Some(Value::Object(env)) => {
+                for (name, value) in env {
+                    let value = match value {
+                        Value::String(s) => context.interpolate_str(s)?,
+                        other => other.to_string(),
+                    };
+                    vars.insert(name.clone(), value);
+                }
+            }
+            Some(_) => bail!("Step '{}': env deve ser um objeto", step.id),
+        }
+
+        let workdir = match step.params.get("workdir").and_then(|w| w.as_str()) {
+            None => step_dir,
+            Some(workdir) => {
+                let workdir = context.interpolate_str(workdir)?;
+                let path = Path::new(&workdir);
+                if path.is_absolute() {
+                    path.to_path_buf()
+                } else if path.components().any(|c| matches!(c, Component::ParentDir)) {
+                    bail!(
+                        "Step '{}': workdir relativo não pode sair do diretório do step",
+                        step.id
+                    );
+                } else {
+                    let dir = step_dir.join(path);
+                    std::fs::create_dir_all(&dir)
+                        .with_context(|| format!("Falha ao criar workdir: {:?}", dir))?;
+                    dir
+                }
+            }
+        };
+
+        Ok(Self { vars, workdir })
+    }
+
+    /// Aplica o ambiente a um comando (limpando o herdado do Runner).
+    pub fn apply(&self, command: &mut tokio::process::Command) {
+        command
+            .env_clear()
+            .envs(&self.vars)
+            .current_dir(&self.workdir);
+    }
+}
+
+fn inherit<'a>(vars: &mut BTreeMap<String, String>, names: impl Iterator<Item = &'a str>) {
+    for name in names {
+        if let Ok(value) = std::env::var(name) {
+            vars.insert(name.to_string(), value);
+        }
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn workspace(name: &str) -> RunWorkspace {
+        RunWorkspace::at(std::env::temp_dir().join(format!(
+            "aqa-isolation-{}-{}",
+            name,
+            std::process::id()
+        )))
+    }
+
+    fn step(params: Value) -> Step {
+        Step {
+            id: "seed/db".to_string(),
+            action: "shell_command".to_string(),
+            params,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_env_is_explicit() {
+        std::env::set_var("AQA_ISOLATION_SECRET", "leak");
+        let mut context = Context::new();
+        context.set("db_url", json!("postgres://db/test"));
+        let workspace = workspace("env");
+
+        let env = StepEnv::from_step(
+            &step(json!({ "env": { "DATABASE_URL": "${db_url}", "RETRIES": 3 } })),
+            &context,
+            &workspace,
+        )
+        .unwrap();
+        assert_eq!(env.vars["DATABASE_URL"], "postgres://db/test");
+        assert_eq!(env.vars["RETRIES"], "3");
+        assert!(!env.vars.contains_key("AQA_ISOLATION_SECRET"));
+        assert_eq!(env.vars["TMPDIR"], env.workdir.to_string_lossy());
+
+        let env = StepEnv::from_step(
+            &step(json!({ "inherit_env": ["AQA_ISOLATION_SECRET"] })),
+            &context,
+            &workspace,
+        )
+        .unwrap();
+        assert_eq!(env.vars["AQA_ISOLATION_SECRET"], "leak");
+        assert!(!env.vars.contains_key("PATH"));
+    }
+
+    #[test]
+    fn test_workdir_stays_in_step_dir() {
+        let context = Context::new();
+        let workspace = workspace("workdir");
+
+        let env = StepEnv::from_step(
+            &step(json!({ "workdir": "fixtures" })),
+            &context,
+            &workspace,
+        )
+        .unwrap();
+        assert!(env
+            .workdir
+            .starts_with(workspace.root().join(sanitize_file_name("seed/db"))));
+        assert!(env.workdir.is_dir());
+
+        let escape = StepEnv::from_step(
+            &step(json!({ "workdir": "../other" })),
+            &context,
+            &workspace,
+        );
+        assert!(escape.is_err());
+    }
+
+    #[test]
+    fn test_cleanup_removes_run_dir() {
+        let workspace = workspace("cleanup");
+        let dir = workspace.step_dir("write").unwrap();
+        std::fs::write(dir.join("out.txt"), "data").unwrap();
+
+        workspace.cleanup().unwrap();
+        assert!(!workspace.root().exists());
+
+        // Drop também limpa (execução interrompida).
+        let root = {
+            let workspace = self::workspace("drop");
+            workspace.step_dir("write").unwrap();
+            workspace.root().to_path_buf()
+        };
+        assert!(!root.exists());
+    }
+
+    #[test]
+    fn test_existing_run_dir_is_not_reused() {
+        let first = RunWorkspace::new("exec-1");
+        let second = RunWorkspace::new("exec-1");
+        assert_ne!(first.root(), second.root());
+
+        let taken = workspace("taken");
+        std::fs::create_dir_all(taken.root()).unwrap();
+        assert!(taken.step_dir("write").is_err());
+        std::fs::remove_dir_all(taken.root()).unwrap();
+    }
+}
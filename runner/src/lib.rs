@@ -0,0 +1,190 @@
+//! # Runner como Biblioteca - Motor de Execução Embutível
+//!
+//! Os módulos do runner, para a CLI (`main.rs`) e para serviços que
+//! rodam planos dentro do próprio processo.
+//!
+//! ## Para todos entenderem:
+//!
+//! A CLI é só uma das formas de usar o motor. Um serviço (ex: o Brain ou
+//! um orquestrador de CI) pode depender deste crate e controlar cada
+//! execução com um [`ExecutionHandle`]:
+//!
+//! ```rust,ignore
+//! use runner::{executors, ExecutionHandle};
+//!
+//! let mut handle = ExecutionHandle::start(plan.steps, executors::builtin(), context, limits);
+//! let mut results = handle.results().unwrap();
+//! while let Some(result) = results.recv().await { /* ... */ }
+//! let all = handle.wait().await;
+//! ```
+//!
+//! Veja o módulo [`engine`] para cancelar, pausar e retomar a execução.
+
+// ============================================================================
+// DECLARAÇÃO DE MÓDULOS
+// ============================================================================
+// Em Rust, `mod` importa um módulo (pasta ou arquivo) para uso neste arquivo.
+// Cada módulo é um "pacote" de código relacionado; `pub` o deixa visível
+// para a CLI (`main.rs`) e para quem embute o runner.
+
+/// Módulo de atores: variáveis e sessão HTTP por usuário (`config.actors`).
+pub mod actors;
+
+/// Módulo de agentes: steps executados por `runner agent` remotos (`step.agent`).
+pub mod agents;
+
+/// Módulo de autenticação: login OIDC gerenciado pelo Runner (`config.auth`).
+pub mod auth;
+
+/// Módulo de cancelamento: sinal de parada que cancela os steps restantes.
+pub mod cancel;
+
+/// Módulo de capacidades: o que este Runner suporta (`runner capabilities`).
+pub mod capabilities;
+
+/// Módulo de relógio: abstração de pausas (real ou virtual para --fast-wait).
+pub mod clock;
+
+/// Módulo de concorrência: limite fixo ou adaptativo (`--max-parallel auto`).
+pub mod concurrency;
+
+/// Módulo de contexto: gerencia variáveis, interpolação e estado da execução.
+pub mod context;
+
+/// Módulo de engine: execução embutida com `ExecutionHandle` (cancelar, pausar, resultados).
+pub mod engine;
+
+/// Módulo de erros: códigos de erro estruturados (E1xxx, E2xxx, etc.).
+pub mod errors;
+
+/// Módulo de executores: implementações de ações (HTTP, Wait, etc.).
+pub mod executors;
+
+/// Módulo de extração: captura dados de respostas HTTP para o contexto.
+pub mod extractors;
+
+/// Módulo de fan-out: `parallel_foreach` sobre arrays do contexto.
+pub mod foreach;
+
+/// Módulo de fuzz: testes negativos derivados de um step (`runner fuzz`).
+pub mod fuzz;
+
+/// Módulo de heartbeat: sinal de vida periódico com o progresso da execução.
+pub mod heartbeat;
+
+/// Módulo de inspeção: visão de depuração de um step (`runner context`).
+pub mod inspect;
+
+/// Módulo de isolamento: ambiente e diretório temporário por step.
+pub mod isolation;
+
+/// Módulo de limites: políticas de rate-limiting e proteção.
+pub mod limits;
+
+/// Módulo de lint: regras de estilo e confiabilidade (`runner lint`).
+pub mod lint;
+
+/// Módulo de lock: impede execuções simultâneas do mesmo plano (`--lock-name`).
+pub mod lock;
+
+/// Módulo de carregamento: lê e parseia arquivos UTDL (JSON).
+pub mod loader;
+
+/// Módulo de memória: limite de dados retidos na execução (`RUNNER_MAX_MEMORY_MB`).
+pub mod memory;
+
+/// Módulo de metadados: rastreabilidade CI/git no relatório.
+pub mod metadata;
+
+/// Módulo de overrides: variáveis de `--var` e `--vars-file`.
+pub mod overrides;
+
+/// Módulo de saída: criação de pastas e nomes de arquivo seguros em qualquer SO.
+pub mod output;
+
+/// Módulo de planejamento: DAG para execução paralela.
+pub mod planner;
+
+/// Módulo de preflight: aguarda o ambiente ficar pronto (`config.wait_for`).
+pub mod preflight;
+
+/// Módulo de perfis: presets de execução nomeados (`--profile`).
+pub mod profiles;
+
+/// Módulo de progress feed: resultados por step para `--progress-url`.
+pub mod progress_feed;
+
+/// Módulo de progress stream: eventos NDJSON para `--progress-stream`.
+pub mod progress_stream;
+
+/// Módulo de protocolo: estruturas de dados UTDL (Plan, Step, etc.).
+pub mod protocol;
+
+/// Módulo de quality gate: status final por orçamento de falhas e latência.
+pub mod quality_gate;
+
+/// Módulo de quarentena: falhas conhecidas que não reprovam a execução.
+pub mod quarantine;
+
+/// Módulo de aleatoriedade reprodutível: `--seed` para `${random_*}`.
+pub mod random;
+
+/// Módulo de regiões: o mesmo plano por região e o comparativo (`--regions`).
+pub mod regions;
+
+/// Módulo de arquivo de relatório: compressão `.gz`/`.zst` pela extensão.
+pub mod report_file;
+
+/// Módulo de re-execução: `--retry-failed` roda só os steps que falharam.
+pub mod rerun;
+
+/// Módulo de retry: políticas de recuperação (retry, fail_fast, ignore).
+pub mod retry;
+
+/// Módulo de escopo: extrações visíveis só aos dependentes (`scope: step`).
+pub mod scoping;
+
+/// Módulo de auto-atualização: `runner self-update` via GitHub Releases.
+#[cfg(feature = "self-update")]
+pub mod self_update;
+
+/// Módulo de seleção: filtros `--only`, `--skip` e `--tags` com dependências.
+pub mod selection;
+
+/// Módulo de assinatura: JWS destacado do relatório (`--sign-report`).
+pub mod signing;
+
+/// Módulo de cache de steps: variáveis de steps com `cache` reaproveitadas entre execuções.
+pub mod step_cache;
+
+/// Módulo de streaming: relatório parcial gravado conforme os steps terminam.
+pub mod streaming;
+
+/// Módulo de sugestões: assertions e extrações a partir de um relatório (`runner suggest`).
+pub mod suggest;
+
+/// Módulo de suíte: vários planos em sequência e steps `shared`.
+pub mod suite;
+
+/// Módulo de telemetria: integração OpenTelemetry.
+pub mod telemetry;
+
+/// Módulo de templates: fragmentos de params reutilizáveis (`request_templates`).
+pub mod templates;
+
+/// Módulo de linha do tempo: Gantt da execução paralela (`runner timeline`).
+pub mod timeline;
+
+/// Módulo de triagem: falhas agrupadas por código de erro.
+pub mod triage;
+
+/// Módulo de validação: verifica se o plano UTDL é válido.
+pub mod validation;
+
+/// Módulo de versão: `runner version` (compatibilidade com o Brain).
+pub mod version;
+
+/// Módulo de avisos: regressões de qualidade anotadas no relatório.
+pub mod warnings;
+
+pub use engine::ExecutionHandle;
@@ -29,6 +29,7 @@
 //! | max_retries_total  | 50     | Máximo de retries no plano todo     |
 //! | max_execution_secs | 300    | Timeout total de execução (5 min)   |
 //! | max_step_timeout   | 30     | Timeout por step (segundos)         |
+//! | max_memory_mb      | -      | Dados retidos na execução (`memory`) |
 
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
@@ -98,6 +99,10 @@ pub struct ExecutionLimits {
     /// Timeout máximo para cada step individual.
     /// Sobrescreve o timeout do step se for maior.
     pub max_step_timeout: Duration,
+
+    /// Limite dos dados retidos durante a execução, em bytes.
+    /// Se excedido, os steps restantes são cancelados (E5004). `None` = sem limite.
+    pub max_memory_bytes: Option<u64>,
 }
 
 impl Default for ExecutionLimits {
@@ -108,6 +113,7 @@ impl Default for ExecutionLimits {
             max_retries_total: DEFAULT_MAX_RETRIES_TOTAL,
             max_execution_time: Duration::from_secs(DEFAULT_MAX_EXECUTION_SECS),
             max_step_timeout: Duration::from_secs(DEFAULT_MAX_STEP_TIMEOUT_SECS),
+            max_memory_bytes: None,
         }
     }
 }
@@ -121,6 +127,7 @@ impl ExecutionLimits {
     /// - `RUNNER_MAX_RETRIES`: Máximo retries
     /// - `RUNNER_MAX_EXECUTION_SECS`: Timeout total
     /// - `RUNNER_MAX_STEP_TIMEOUT`: Timeout por step
+    /// - `RUNNER_MAX_MEMORY_MB`: Limite de dados retidos
     pub fn from_env() -> Self {
         let mut limits = Self::default();
 
@@ -154,6 +161,12 @@ impl ExecutionLimits {
             }
         }
 
+        if let Ok(val) = std::env::var("RUNNER_MAX_MEMORY_MB") {
+            if let Ok(n) = val.parse::<u64>() {
+                limits.max_memory_bytes = Some(n * 1024 * 1024);
+            }
+        }
+
         limits
     }
 
@@ -166,6 +179,7 @@ impl ExecutionLimits {
             max_retries_total: 5,
             max_execution_time: Duration::from_secs(30),
             max_step_timeout: Duration::from_secs(5),
+            max_memory_bytes: None,
         }
     }
 
@@ -178,6 +192,7 @@ impl ExecutionLimits {
             max_retries_total: 200,
             max_execution_time: Duration::from_secs(3600), // 1 hora
             max_step_timeout: Duration::from_secs(120),
+            max_memory_bytes: None,
         }
     }
 }
@@ -0,0 +1,613 @@
+//! # Módulo de Lint - Regras de Estilo e Confiabilidade
+//!
+//! Verificações **não fatais** que vão além da validação: o plano é válido
+//! e executa, mas provavelmente tem um problema.
+//!
+//! ## Para todos entenderem:
+//!
+//! A validação responde "o Runner consegue executar isto?". O lint
+//! responde "isto parece um bom teste?". Um `POST` sem nenhuma assertion
+//! executa normalmente, mas passa mesmo se a API devolver 500.
+//!
+//! ## Regras:
+//!
+//! | Código | Nome                  | O que detecta                                        |
+//! |--------|-----------------------|------------------------------------------------------|
+//! | L001   | `unreachable-step`    | Step que depende de um step inexistente, de um ciclo ou de um step que nunca passa |
+//! | L002   | `unused-extraction`   | Variável extraída que nenhum outro step usa          |
+//! | L003   | `missing-assertions`  | `POST`/`PUT`/`PATCH`/`DELETE` sem assertions         |
+//! | L004   | `hardcoded-secret`    | Token, senha ou chave escrita direto no plano        |
+//! | L005   | `aggressive-retry`    | Muitas tentativas ou backoff curto demais            |
+//!
+//! Todas as regras geram **avisos**. Com `--deny <regra>` (código ou nome,
+//! ou `all`), a regra passa a gerar **erro** e o `runner lint` sai com
+//! código 1.
+//!
+//! ## Exemplo:
+//!
+//! ```bash
+//! runner lint --file plan.json --deny L004 --deny missing-assertions
+//! ```
+//!
+//! ```text
+//! error[L004 hardcoded-secret] step 'login': header 'Authorization' tem valor literal; use ${variavel}
+//! warning[L002 unused-extraction] step 'create_user': variável 'user_id' é extraída mas nunca usada
+//! ```
+
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::protocol::{Plan, Step};
+use crate::validation::step_uses_variable;
+
+/// Número máximo de tentativas antes de `aggressive-retry` avisar.
+const MAX_REASONABLE_ATTEMPTS: u32 = 5;
+
+/// Backoff mínimo (ms) para políticas com mais de uma tentativa.
+const MIN_REASONABLE_BACKOFF_MS: u64 = 100;
+
+/// Nomes de headers/campos que costumam carregar segredos.
+const SECRET_NAMES: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "x-api-key",
+    "api-key",
+    "api_key",
+    "apikey",
+    "password",
+    "passwd",
+    "secret",
+    "client_secret",
+    "token",
+    "access_token",
+    "refresh_token",
+];
+
+// ============================================================================
+// REGRAS
+// ============================================================================
+
+/// Regras do linter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintRule {
+    UnreachableStep,
+    UnusedExtraction,
+    MissingAssertions,
+    HardcodedSecret,
+    AggressiveRetry,
+}
+
+impl LintRule {
+    /// Todas as regras, na ordem dos códigos.
+    pub const ALL: &'static [LintRule] = &[
+        LintRule::UnreachableStep,
+        LintRule::UnusedExtraction,
+        LintRule::MissingAssertions,
+        LintRule::HardcodedSecret,
+        LintRule::AggressiveRetry,
+    ];
+
+    /// Código estável da regra (`L001`...).
+    pub fn code(&self) -> &'static str {
+        match self {
+            LintRule::UnreachableStep => "L001",
+            LintRule::UnusedExtraction => "L002",
+            LintRule::MissingAssertions => "L003",
+            LintRule::HardcodedSecret => "L004",
+            LintRule::AggressiveRetry => "L005",
+        }
+    }
+
+    /// Nome legível da regra.
+    pub fn name(&self) -> &'static str {
+        match self {
+            LintRule::UnreachableStep => "unreachable-step",
+            LintRule::UnusedExtraction => "unused-extraction",
+            LintRule::MissingAssertions => "missing-assertions",
+            LintRule::HardcodedSecret => "hardcoded-secret",
+            LintRule::AggressiveRetry => "aggressive-retry",
+        }
+    }
+
+    /// Converte o argumento de `--deny` (código ou nome, sem diferenciar maiúsculas).
+    pub fn parse(text: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|r| r.code().eq_ignore_ascii_case(text) || r.name().eq_ignore_ascii_case(text))
+    }
+}
+
+/// Gravidade de um achado.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// Um problema encontrado pelo linter.
+#[derive(Debug, Clone)]
+pub struct LintFinding {
+    pub rule: LintRule,
+    pub severity: Severity,
+    /// Step onde o problema foi encontrado (`None` para `config`).
+    pub step_id: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        let location = match &self.step_id {
+            Some(id) => format!("step '{}'", id),
+            None => "config".to_string(),
+        };
+        write!(
+            f,
+            "{}[{} {}] {}: {}",
+            level,
+            self.rule.code(),
+            self.rule.name(),
+            location,
+            self.message
+        )
+    }
+}
+
+// ============================================================================
+// FUNÇÃO PRINCIPAL
+// ============================================================================
+
+/// Executa todas as regras sobre o plano.
+///
+/// ## Parâmetros:
+/// - `plan`: Plano carregado (templates já aplicados)
+/// - `deny`: Regras promovidas a erro
+pub fn lint_plan(plan: &Plan, deny: &HashSet<LintRule>) -> Vec<LintFinding> {
+    let mut linter = Linter {
+        deny,
+        findings: Vec::new(),
+    };
+
+    linter.check_unreachable(&plan.steps);
+    linter.check_unused_extractions(&plan.steps);
+    for step in &plan.steps {
+        linter.check_missing_assertions(step);
+        linter.check_step_secrets(step);
+        linter.check_retry(step);
+    }
+    linter.check_config_secrets(plan);
+
+    linter.findings
+}
+
+/// Acumula os achados aplicando `--deny`.
+struct Linter<'a> {
+    deny: &'a HashSet<LintRule>,
+    findings: Vec<LintFinding>,
+}
+
+impl Linter<'_> {
+    fn report(&mut self, rule: LintRule, step_id: Option<&str>, message: String) {
+        let severity = if self.deny.contains(&rule) {
+            Severity::Error
+        } else {
+            Severity::Warning
+        };
+        self.findings.push(LintFinding {
+            rule,
+            severity,
+            step_id: step_id.map(str::to_string),
+            message,
+        });
+    }
+
+    // ------------------------------------------------------------------------
+    // L001: unreachable-step
+    // ------------------------------------------------------------------------
+
+    /// Um step é inalcançável se depende de um step inexistente, de um ciclo
+    /// ou de um step que nunca passa (direta ou transitivamente).
+    fn check_unreachable(&mut self, steps: &[Step]) {
+        let by_id: HashMap<&str, &Step> = steps.iter().map(|s| (s.id.as_str(), s)).collect();
+        let mut memo: HashMap<&str, Option<String>> = HashMap::new();
+
+        for step in steps {
+            let mut visiting = HashSet::new();
+            let blocked: Vec<String> = step
+                .depends_on
+                .iter()
+                .filter_map(|dep| blocking_reason(dep, &by_id, &mut memo, &mut visiting))
+                .collect();
+            if let Some(reason) = blocked.into_iter().next() {
+                self.report(
+                    LintRule::UnreachableStep,
+                    Some(&step.id),
+                    format!("nunca será executado: {}", reason),
+                );
+            }
+        }
+    }
+
+    // ------------------------------------------------------------------------
+    // L002: unused-extraction
+    // ------------------------------------------------------------------------
+
+    fn check_unused_extractions(&mut self, steps: &[Step]) {
+        for step in steps {
+            for extraction in &step.extract {
+                let used = steps
+                    .iter()
+                    .any(|other| step_uses_variable(other, &extraction.target));
+                if !used {
+                    self.report(
+                        LintRule::UnusedExtraction,
+                        Some(&step.id),
+                        format!(
+                            "variável '{}' é extraída mas nunca usada",
+                            extraction.target
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    // ------------------------------------------------------------------------
+    // L003: missing-assertions
+    // ------------------------------------------------------------------------
+
+    fn check_missing_assertions(&mut self, step: &Step) {
+        if step.action != "http_request" || !step.assertions.is_empty() {
+            return;
+        }
+        let method = step
+            .params
+            .get("method")
+            .and_then(|m| m.as_str())
+            .unwrap_or("")
+            .to_uppercase();
+        if matches!(method.as_str(), "POST" | "PUT" | "PATCH" | "DELETE") {
+            self.report(
+                LintRule::MissingAssertions,
+                Some(&step.id),
+                format!(
+                    "{} sem assertions passa mesmo se a API falhar; valide ao menos o status_code",
+                    method
+                ),
+            );
+        }
+    }
+
+    // ------------------------------------------------------------------------
+    // L004: hardcoded-secret
+    // ------------------------------------------------------------------------
+
+    fn check_step_secrets(&mut self, step: &Step) {
+        if let Some(headers) = step.params.get("headers").and_then(|h| h.as_object()) {
+            for (name, value) in headers {
+                if let Some(text) = value.as_str() {
+                    if is_secret_name(name) && is_literal_secret(text) {
+                        self.report(
+                            LintRule::HardcodedSecret,
+                            Some(&step.id),
+                            format!("header '{}' tem valor literal; use ${{variavel}}", name),
+                        );
+                    }
+                }
+            }
+        }
+        if let Some(body) = step.params.get("body") {
+            for field in literal_secret_fields(body) {
+                self.report(
+                    LintRule::HardcodedSecret,
+                    Some(&step.id),
+                    format!(
+                        "campo '{}' do body tem valor literal; use ${{variavel}}",
+                        field
+                    ),
+                );
+            }
+        }
+    }
+
+    fn check_config_secrets(&mut self, plan: &Plan) {
+        let mut headers: Vec<_> = plan.config.global_headers.iter().collect();
+        headers.sort();
+        for (name, value) in headers {
+            if is_secret_name(name) && is_literal_secret(value) {
+                self.report(
+                    LintRule::HardcodedSecret,
+                    None,
+                    format!(
+                        "global_headers '{}' tem valor literal; use ${{variavel}}",
+                        name
+                    ),
+                );
+            }
+        }
+
+        let mut variables: Vec<_> = plan.config.variables.iter().collect();
+        variables.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, value) in variables {
+            if let Some(text) = value.as_str() {
+                if is_secret_name(name) && is_literal_secret(text) {
+                    self.report(
+                        LintRule::HardcodedSecret,
+                        None,
+                        format!("variável '{}' tem valor literal; injete via ambiente", name),
+                    );
+                }
+            }
+        }
+    }
+
+    // ------------------------------------------------------------------------
+    // L005: aggressive-retry
+    // ------------------------------------------------------------------------
+
+    fn check_retry(&mut self, step: &Step) {
+        let Some(policy) = &step.recovery_policy else {
+            return;
+        };
+        if policy.strategy != "retry" {
+            return;
+        }
+
+        if policy.max_attempts > MAX_REASONABLE_ATTEMPTS {
+            self.report(
+                LintRule::AggressiveRetry,
+                Some(&step.id),
+                format!(
+                    "max_attempts {} (acima de {}) pode mascarar falhas e sobrecarregar a API",
+                    policy.max_attempts, MAX_REASONABLE_ATTEMPTS
+                ),
+            );
+        }
+        if policy.max_attempts > 1 && policy.backoff_ms < MIN_REASONABLE_BACKOFF_MS {
+            self.report(
+                LintRule::AggressiveRetry,
+                Some(&step.id),
+                format!(
+                    "backoff_ms {} (abaixo de {}ms) repete quase sem pausa",
+                    policy.backoff_ms, MIN_REASONABLE_BACKOFF_MS
+                ),
+            );
+        }
+        if policy.backoff_factor < 1.0 {
+            self.report(
+                LintRule::AggressiveRetry,
+                Some(&step.id),
+                format!(
+                    "backoff_factor {} encurta o intervalo a cada tentativa",
+                    policy.backoff_factor
+                ),
+            );
+        }
+    }
+}
+
+// ============================================================================
+// AUXILIARES
+// ============================================================================
+
+/// Por que o step `id` nunca chega a passar (`None` se pode passar).
+fn blocking_reason<'a>(
+    id: &'a str,
+    by_id: &HashMap<&'a str, &'a Step>,
+    memo: &mut HashMap<&'a str, Option<String>>,
+    visiting: &mut HashSet<&'a str>,
+) -> Option<String> {
+    if let Some(cached) = memo.get(id) {
+        return cached.clone();
+    }
+    let Some(step) = by_id.get(id) else {
+        return Some(format!("depende de '{}', que não existe", id));
+    };
+    if !visiting.insert(id) {
+        return Some(format!("'{}' faz parte de um ciclo de dependências", id));
+    }
+
+    let reason = never_passes(step)
+        .map(|why| format!("'{}' nunca passa ({})", id, why))
+        .or_else(|| {
+            step.depends_on.iter().find_map(|dep| {
+                blocking_reason(dep, by_id, memo, visiting)
+                    .map(|_| format!("'{}' depende de um step que nunca passa", id))
+            })
+        });
+
+    visiting.remove(id);
+    memo.insert(id, reason.clone());
+    reason
+}
+
+/// Detecta assertions que nenhuma resposta satisfaz.
+fn never_passes(step: &Step) -> Option<String> {
+    let expected: HashSet<i64> = step
+        .assertions
+        .iter()
+        .filter(|a| a.assertion_type == "status_code" && a.operator == "eq")
+        .filter_map(|a| a.value.as_i64())
+        .collect();
+
+    if expected.len() > 1 {
+        let mut codes: Vec<_> = expected.into_iter().collect();
+        codes.sort();
+        return Some(format!("status_code eq {:?} ao mesmo tempo", codes));
+    }
+    expected
+        .into_iter()
+        .find(|code| !(100..=599).contains(code))
+        .map(|code| format!("status_code eq {} não é um status HTTP", code))
+}
+
+fn is_secret_name(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    SECRET_NAMES.contains(&lower.as_str())
+}
+
+/// Um valor é segredo literal se não vem de interpolação e não é vazio.
+fn is_literal_secret(value: &str) -> bool {
+    let value = value.trim();
+    !value.is_empty() && !value.contains("${")
+}
+
+/// Campos de segredo com valor literal no body (busca recursiva).
+fn literal_secret_fields(body: &Value) -> Vec<String> {
+    let mut fields = Vec::new();
+    match body {
+        Value::Object(map) => {
+            for (key, value) in map {
+                match value {
+                    Value::String(text) if is_secret_name(key) && is_literal_secret(text) => {
+                        fields.push(key.clone())
+                    }
+                    other => fields.extend(literal_secret_fields(other)),
+                }
+            }
+        }
+        Value::Array(items) => items
+            .iter()
+            .for_each(|v| fields.extend(literal_secret_fields(v))),
+        _ => {}
+    }
+    fields
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn plan(mut config: Value, steps: Value) -> Plan {
+        config["timeout_ms"] = json!(5000);
+        serde_json::from_value(json!({
+            "spec_version": "0.1",
+            "meta": { "id": "p", "name": "p", "created_at": "2024-01-01" },
+            "config": config,
+            "steps": steps
+        }))
+        .unwrap()
+    }
+
+    fn rules(findings: &[LintFinding]) -> Vec<(&'static str, Option<&str>)> {
+        findings
+            .iter()
+            .map(|f| (f.rule.code(), f.step_id.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn test_unreachable_steps() {
+        let plan = plan(
+            json!({ "base_url": "http://api" }),
+            json!([
+                { "id": "broken", "action": "http_request",
+                  "params": { "method": "GET", "path": "/" },
+                  "assertions": [
+                    { "type": "status_code", "operator": "eq", "value": 200 },
+                    { "type": "status_code", "operator": "eq", "value": 201 }
+                  ] },
+                { "id": "child", "action": "log", "params": { "message": "x" }, "depends_on": ["broken"] },
+                { "id": "grandchild", "action": "log", "params": { "message": "x" }, "depends_on": ["child"] },
+                { "id": "orphan", "action": "log", "params": { "message": "x" }, "depends_on": ["missing"] }
+            ]),
+        );
+
+        let findings = lint_plan(&plan, &HashSet::new());
+        assert_eq!(
+            rules(&findings),
+            vec![
+                ("L001", Some("child")),
+                ("L001", Some("grandchild")),
+                ("L001", Some("orphan")),
+            ]
+        );
+        assert!(findings[0].message.contains("[200, 201]"));
+    }
+
+    #[test]
+    fn test_unused_extraction_and_missing_assertions() {
+        let plan = plan(
+            json!({ "base_url": "http://api" }),
+            json!([
+                { "id": "create", "action": "http_request",
+                  "params": { "method": "POST", "path": "/users" },
+                  "extract": [
+                    { "source": "body", "path": "$.id", "target": "user_id" },
+                    { "source": "body", "path": "$.etag", "target": "etag" }
+                  ] },
+                { "id": "get", "action": "http_request",
+                  "params": { "method": "GET", "path": "/users/${user_id}" } }
+            ]),
+        );
+
+        let findings = lint_plan(&plan, &HashSet::new());
+        assert_eq!(
+            rules(&findings),
+            vec![("L002", Some("create")), ("L003", Some("create"))]
+        );
+        assert!(findings[0].message.contains("'etag'"));
+        assert!(findings.iter().all(|f| f.severity == Severity::Warning));
+    }
+
+    #[test]
+    fn test_hardcoded_secrets_and_deny() {
+        let plan = plan(
+            json!({
+                "base_url": "http://api",
+                "global_headers": { "X-Api-Key": "abc123" },
+                "variables": { "password": "${ENV_PASSWORD}" }
+            }),
+            json!([
+                { "id": "login", "action": "http_request",
+                  "params": { "method": "POST", "path": "/login",
+                              "headers": { "Authorization": "Bearer ${token}" },
+                              "body": { "user": { "name": "a", "password": "hunter2" } } },
+                  "assertions": [{ "type": "status_code", "operator": "eq", "value": 200 }] }
+            ]),
+        );
+
+        let deny: HashSet<_> = [LintRule::parse("hardcoded-secret").unwrap()].into();
+        let findings = lint_plan(&plan, &deny);
+
+        assert_eq!(
+            rules(&findings),
+            vec![("L004", Some("login")), ("L004", None)]
+        );
+        assert!(findings.iter().all(|f| f.severity == Severity::Error));
+        assert!(findings[0]
+            .to_string()
+            .starts_with("error[L004 hardcoded-secret] step 'login'"));
+    }
+
+    #[test]
+    fn test_aggressive_retry() {
+        let plan = plan(
+            json!({ "base_url": "http://api" }),
+            json!([
+                { "id": "poll", "action": "wait", "params": { "duration_ms": 1 },
+                  "recovery_policy": { "strategy": "retry", "max_attempts": 10, "backoff_ms": 10 } },
+                { "id": "ok", "action": "wait", "params": { "duration_ms": 1 },
+                  "recovery_policy": { "strategy": "retry", "max_attempts": 3, "backoff_ms": 500 } }
+            ]),
+        );
+
+        let findings = lint_plan(&plan, &HashSet::new());
+        assert_eq!(
+            rules(&findings),
+            vec![("L005", Some("poll")), ("L005", Some("poll"))]
+        );
+        assert_eq!(LintRule::parse("l005"), Some(LintRule::AggressiveRetry));
+        assert_eq!(LintRule::parse("nope"), None);
+    }
+}
@@ -6,7 +6,8 @@
 //!
 //! 1. Lê o conteúdo de um arquivo do sistema de arquivos
 //! 2. Converte o JSON em estruturas Rust (deserialização)
-//! 3. Retorna erros claros se algo der errado
+//! 3. Aplica os templates de requisição (`config.request_templates`)
+//! 4. Retorna erros claros se algo der errado
 //!
 //! ## Exemplo de uso:
 //!
@@ -25,6 +26,7 @@
 //! - Implementar cache de planos se necessário
 
 use crate::protocol::Plan;
+use crate::templates::apply_request_templates;
 use anyhow::{Context, Result};
 use std::fs;
 use std::path::Path;
@@ -63,8 +65,11 @@ pub fn load_plan_from_file<P: AsRef<Path>>(path: P) -> Result<Plan> {
 
     // Parseia o JSON para a estrutura Plan.
     // `serde_json::from_str` usa as anotações #[derive(Deserialize)] do Plan.
-    let plan = serde_json::from_str(&content)
+    let mut plan: Plan = serde_json::from_str(&content)
         .with_context(|| format!("Failed to parse plan JSON {:?}", path_ref))?;
 
+    // Aplica `config.request_templates` aos steps que declaram `template`.
+    apply_request_templates(&mut plan);
+
     Ok(plan)
 }
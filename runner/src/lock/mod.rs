@@ -0,0 +1,452 @@
+//! # Módulo de Lock - Execução Exclusiva de Planos (`--lock-name`)
+//!
+//! Impede que dois jobs de CI executem o mesmo plano destrutivo contra o
+//! mesmo ambiente ao mesmo tempo.
+//!
+//! ## Para todos entenderem:
+//!
+//! Dois pipelines rodando `checkout-suite` em staging apagam o carrinho um
+//! do outro e falham de forma aleatória. Com `--lock-name checkout-suite`,
+//! o segundo falha na hora com `E4005`, dizendo quem está com o lock:
+//!
+//! | Backend | Quando                                   | Alcance                   |
+//! |---------|------------------------------------------|---------------------------|
+//! | Arquivo | Padrão                                   | Jobs na mesma máquina     |
+//! | Redis   | `--lock-redis` ou `RUNNER_LOCK_REDIS_URL` | Jobs em qualquer agente   |
+//!
+//! ## TTL:
+//!
+//! O lock expira após o tempo máximo de execução (mais uma margem), para
+//! que um Runner morto no meio não trave o plano para sempre:
+//! - Redis: `SET NX PX <ttl>`.
+//! - Arquivo: um lock mais velho que o TTL é considerado abandonado.
+//!
+//! O arquivo nasce completo (temporário + `hard_link`, que falha se o lock
+//! existir) e a tomada de um lock abandonado é feita sob um guarda
+//! exclusivo (`.takeover`, criado com `O_EXCL`): dois Runners que veem o
+//! mesmo lock velho não podem assumi-lo ao mesmo tempo.
+//!
+//! A liberação só remove o lock se ele ainda pertencer a esta execução, e
+//! acontece também no `Drop` (saídas antecipadas não deixam o lock preso).
+
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use thiserror::Error;
+
+use crate::errors::ErrorCode;
+
+/// Variável de ambiente com a URL do Redis (alternativa a `--lock-redis`).
+pub const REDIS_URL_ENV: &str = "RUNNER_LOCK_REDIS_URL";
+
+/// Script de liberação: só apaga a chave se o valor ainda for o nosso.
+const REDIS_RELEASE_SCRIPT: &str =
+    "if redis.call('GET', KEYS[1]) == ARGV[1] then return redis.call('DEL', KEYS[1]) else return 0 end";
+
+// ============================================================================
+// ESTRUTURAS
+// ============================================================================
+
+/// Onde o lock é guardado.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LockBackend {
+    /// Arquivo `aqa-lock-<nome>.lock` no diretório indicado.
+    File { dir: PathBuf },
+    /// Chave `aqa:lock:<nome>` no Redis.
+    Redis { url: String },
+}
+
+impl LockBackend {
+    /// Redis se houver URL (flag ou `RUNNER_LOCK_REDIS_URL`), senão arquivo local.
+    pub fn from_options(redis_url: Option<&str>) -> Self {
+        let url = redis_url
+            .map(str::to_string)
+            .or_else(|| std::env::var(REDIS_URL_ENV).ok())
+            .filter(|u| !u.is_empty());
+        match url {
+            Some(url) => Self::Redis { url },
+            None => Self::File {
+                dir: std::env::temp_dir(),
+            },
+        }
+    }
+}
+
+/// Quem está com o lock (gravado no arquivo ou na chave do Redis).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LockHolder {
+    pub execution_id: String,
+    pub pid: u32,
+    pub acquired_at: String,
+}
+
+impl std::fmt::Display for LockHolder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "execução {} (pid {}, desde {})",
+            self.execution_id, self.pid, self.acquired_at
+        )
+    }
+}
+
+/// Erros ao adquirir o lock.
+#[derive(Debug, Error)]
+pub enum LockError {
+    #[error("{code} Lock '{name}' em uso pela {holder}", code = ErrorCode::PLAN_LOCKED)]
+    Held { name: String, holder: String },
+
+    #[error("Falha ao adquirir o lock '{name}': {source:#}")]
+    Backend {
+        name: String,
+        #[source]
+        source: anyhow::Error,
+    },
+}
+
+/// Lock adquirido; liberado por `release` ou, em qualquer saída, no `Drop`.
+pub struct PlanLock {
+    name: String,
+    backend: LockBackend,
+    /// Valor gravado (identifica esta execução na liberação).
+    value: String,
+    released: bool,
+}
+
+// ============================================================================
+// AQUISIÇÃO E LIBERAÇÃO
+// ============================================================================
+
+impl PlanLock {
+    /// Adquire o lock ou falha na hora (sem esperar) se outra execução o tem.
+    pub async fn acquire(
+        name: &str,
+        execution_id: &str,
+        backend: LockBackend,
+        ttl: Duration,
+    ) -> Result<Self, LockError> {
+        let holder = LockHolder {
+            execution_id: execution_id.to_string(),
+            pid: std::process::id(),
+            acquired_at: chrono::Utc::now().to_rfc3339(),
+        };
+        let value = serde_json::to_string(&holder).expect("LockHolder serializa");
+        let backend_error = |source: anyhow::Error| LockError::Backend {
+            name: name.to_string(),
+            source,
+        };
+
+        let current = match &backend {
+            LockBackend::File { dir } => {
+                acquire_file(&lock_path(dir, name), &value, ttl).map_err(backend_error)?
+            }
+            LockBackend::Redis { url } => acquire_redis(url, name, &value, ttl)
+                .await
+                .map_err(backend_error)?,
+        };
+
+        if let Some(current) = current {
+            let holder = serde_json::from_str::<LockHolder>(&current)
+                .map(|h| h.to_string())
+                .unwrap_or(current);
+            return Err(LockError::Held {
+                name: name.to_string(),
+                holder,
+            });
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            backend,
+            value,
+            released: false,
+        })
+    }
+
+    /// Libera o lock, se ele ainda for desta execução.
+    pub async fn release(mut self) -> anyhow::Result<()> {
+        self.released = true;
+        match &self.backend {
+            LockBackend::File { dir } => release_file(&lock_path(dir, &self.name), &self.value),
+            LockBackend::Redis { url } => {
+                let mut conn = redis_connection(url).await?;
+                redis::cmd("EVAL")
+                    .arg(REDIS_RELEASE_SCRIPT)
+                    .arg(1)
+                    .arg(redis_key(&self.name))
+                    .arg(&self.value)
+                    .query_async::<_, i64>(&mut conn)
+                    .await
+                    .context("Falha ao liberar lock no Redis")?;
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Drop for PlanLock {
+    fn drop(&mut self) {
+        if self.released {
+            return;
+        }
+        // Saídas antecipadas: libera aqui (no Redis, de forma síncrona; se
+        // falhar, o TTL cuida).
+        let _ = match &self.backend {
+            LockBackend::File { dir } => release_file(&lock_path(dir, &self.name), &self.value),
+            LockBackend::Redis { url } => release_redis_blocking(url, &self.name, &self.value),
+        };
+    }
+}
+
+fn lock_path(dir: &Path, name: &str) -> PathBuf {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    dir.join(format!("aqa-lock-{}.lock", name))
+}
+
+/// Cria o arquivo de forma exclusiva. Retorna o conteúdo atual se estiver em uso.
+fn acquire_file(path: &Path, value: &str, ttl: Duration) -> anyhow::Result<Option<String>> {
+    // O conteúdo vai primeiro para um temporário: o lock nunca aparece vazio.
+    let temp = path.with_extension(format!("lock.{}.tmp", std::process::id()));
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&temp)
+        .with_context(|| format!("Falha ao criar {:?}", temp))?;
+    file.write_all(value.as_bytes())
+        .with_context(|| format!("Falha ao gravar {:?}", temp))?;
+    drop(file);
+
+    let acquired = link_or_take_over(&temp, path, ttl);
+    let _ = std::fs::remove_file(&temp);
+    match acquired? {
+        true => Ok(None),
+        false => Ok(Some(std::fs::read_to_string(path).unwrap_or_default())),
+    }
+}
+
+/// Publica `temp` como o lock; se houver um abandonado, assume sob o guarda.
+fn link_or_take_over(temp: &Path, path: &Path, ttl: Duration) -> anyhow::Result<bool> {
+    match std::fs::hard_link(temp, path) {
+        Ok(()) => return Ok(true),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(e).with_context(|| format!("Falha ao criar {:?}", path)),
+    }
+    if !is_stale(path, ttl) {
+        return Ok(false);
+    }
+
+    let guard = path.with_extension("lock.takeover");
+    if is_stale(&guard, ttl) {
+        let _ = std::fs::remove_file(&guard);
+    }
+    match OpenOptions::new().write(true).create_new(true).open(&guard) {
+        Ok(_) => {}
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => return Ok(false),
+        Err(e) => return Err(e).with_context(|| format!("Falha ao criar {:?}", guard)),
+    }
+    // Com o guarda, confere de novo: outro Runner pode ter assumido antes.
+    let taken = (is_stale(path, ttl) || !path.exists()) && std::fs::rename(temp, path).is_ok();
+    let _ = std::fs::remove_file(&guard);
+    if taken {
+        tracing::warn!(path = ?path, "Lock abandonado (TTL expirado), assumindo");
+    }
+    Ok(taken)
+}
+
+/// O arquivo existe e é mais velho que o TTL.
+fn is_stale(path: &Path, ttl: Duration) -> bool {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age > ttl)
+}
+
+fn release_file(path: &Path, value: &str) -> anyhow::Result<()> {
+    match std::fs::read_to_string(path) {
+        Ok(current) if current == value => {
+            std::fs::remove_file(path).with_context(|| format!("Falha ao remover {:?}", path))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn redis_key(name: &str) -> String {
+    format!("aqa:lock:{}", name)
+}
+
+/// Liberação síncrona no Redis (usada no `Drop`, fora de um `.await`).
+fn release_redis_blocking(url: &str, name: &str, value: &str) -> anyhow::Result<()> {
+    let client = redis::Client::open(url).context("URL do Redis inválida")?;
+    let mut conn = client
+        .get_connection_with_timeout(Duration::from_secs(2))
+        .context("Falha ao conectar no Redis")?;
+    redis::cmd("EVAL")
+        .arg(REDIS_RELEASE_SCRIPT)
+        .arg(1)
+        .arg(redis_key(name))
+        .arg(value)
+        .query::<i64>(&mut conn)
+        .context("Falha ao liberar lock no Redis")?;
+    Ok(())
+}
+
+async fn redis_connection(url: &str) -> anyhow::Result<redis::aio::MultiplexedConnection> {
+    let client = redis::Client::open(url).context("URL do Redis inválida")?;
+    client
+        .get_multiplexed_async_connection()
+        .await
+        .context("Falha ao conectar no Redis")
+}
+
+/// `SET NX PX`: retorna o valor atual se a chave já existir.
+async fn acquire_redis(
+    url: &str,
+    name: &str,
+    value: &str,
+    ttl: Duration,
+) -> anyhow::Result<Option<String>> {
+    let mut conn = redis_connection(url).await?;
+    let key = redis_key(name);
+    let set: Option<String> = redis::cmd("SET")
+        .arg(&key)
+        .arg(value)
+        .arg("NX")
+        .arg("PX")
+        .arg(ttl.as_millis() as u64)
+        .query_async(&mut conn)
+        .await
+        .context("Falha no SET do lock")?;
+    if set.is_some() {
+        return Ok(None);
+    }
+    let current: Option<String> = redis::cmd("GET")
+        .arg(&key)
+        .query_async(&mut conn)
+        .await
+        .context("Falha ao ler o lock")?;
+    Ok(Some(current.unwrap_or_default()))
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backend(name: &str) -> LockBackend {
+        let dir = std::env::temp_dir().join(format!("aqa-lock-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        LockBackend::File { dir }
+    }
+
+    const TTL: Duration = Duration::from_secs(3600);
+
+    #[tokio::test]
+    async fn test_second_execution_fails_fast() {
+        let backend = backend("held");
+        let lock = PlanLock::acquire("checkout-suite", "exec-1", backend.clone(), TTL)
+            .await
+            .unwrap();
+
+        let err = PlanLock::acquire("checkout-suite", "exec-2", backend.clone(), TTL)
+            .await
+            .err()
+            .unwrap();
+        let message = err.to_string();
+        assert!(message.starts_with("E4005"), "{}", message);
+        assert!(message.contains("exec-1"), "{}", message);
+
+        // Outro nome não conflita.
+        assert!(PlanLock::acquire("other", "exec-2", backend.clone(), TTL)
+            .await
+            .is_ok());
+
+        lock.release().await.unwrap();
+        assert!(PlanLock::acquire("checkout-suite", "exec-2", backend, TTL)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_stale_lock_is_taken_over() {
+        let backend = backend("stale");
+        let old = PlanLock::acquire("suite", "crashed", backend.clone(), TTL)
+            .await
+            .unwrap();
+        std::mem::forget(old);
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let lock = PlanLock::acquire("suite", "exec-2", backend.clone(), Duration::from_millis(5))
+            .await
+            .unwrap();
+
+        // O dono antigo não remove o lock novo.
+        let LockBackend::File { dir } = &backend else {
+            unreachable!()
+        };
+        release_file(&lock_path(dir, "suite"), "crashed").unwrap();
+        assert!(lock_path(dir, "suite").exists());
+        drop(lock);
+        assert!(!lock_path(dir, "suite").exists());
+    }
+
+    #[tokio::test]
+    async fn test_takeover_in_progress_is_not_doubled() {
+        let backend = backend("takeover");
+        let LockBackend::File { dir } = &backend else {
+            unreachable!()
+        };
+        std::mem::forget(
+            PlanLock::acquire("suite", "crashed", backend.clone(), TTL)
+                .await
+                .unwrap(),
+        );
+        let path = lock_path(dir, "suite");
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(7200))
+            .unwrap();
+
+        // Outro Runner está assumindo o lock abandonado: este não assume junto.
+        let guard = path.with_extension("lock.takeover");
+        std::fs::write(&guard, "").unwrap();
+        let err = PlanLock::acquire("suite", "exec-2", backend.clone(), TTL)
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("crashed"), "{}", err);
+
+        std::fs::remove_file(&guard).unwrap();
+        assert!(PlanLock::acquire("suite", "exec-3", backend, TTL)
+            .await
+            .is_ok());
+    }
+
+    #[test]
+    fn test_backend_from_options() {
+        assert_eq!(
+            LockBackend::from_options(Some("redis://ci-redis:6379")),
+            LockBackend::Redis {
+                url: "redis://ci-redis:6379".to_string()
+            }
+        );
+    }
+}
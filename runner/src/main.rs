@@ -39,43 +39,19 @@
 //! ```
 
 // ============================================================================
-// DECLARAÇÃO DE MÓDULOS
+// MÓDULOS
 // ============================================================================
-// Em Rust, `mod` importa um módulo (pasta ou arquivo) para uso neste arquivo.
-// Cada módulo é um "pacote" de código relacionado.
-
-/// Módulo de contexto: gerencia variáveis, interpolação e estado da execução.
-mod context;
-
-/// Módulo de erros: códigos de erro estruturados (E1xxx, E2xxx, etc.).
-mod errors;
-
-/// Módulo de executores: implementações de ações (HTTP, Wait, etc.).
-mod executors;
-
-/// Módulo de extração: captura dados de respostas HTTP para o contexto.
-mod extractors;
-
-/// Módulo de limites: políticas de rate-limiting e proteção.
-mod limits;
-
-/// Módulo de carregamento: lê e parseia arquivos UTDL (JSON).
-mod loader;
-
-/// Módulo de planejamento: DAG para execução paralela.
-mod planner;
-
-/// Módulo de protocolo: estruturas de dados UTDL (Plan, Step, etc.).
-mod protocol;
-
-/// Módulo de retry: políticas de recuperação (retry, fail_fast, ignore).
-mod retry;
-
-/// Módulo de telemetria: integração OpenTelemetry.
-mod telemetry;
-
-/// Módulo de validação: verifica se o plano UTDL é válido.
-mod validation;
+// Os módulos ficam na biblioteca (`lib.rs`); a CLI usa os mesmos.
+
+#[cfg(feature = "self-update")]
+use runner::self_update;
+use runner::{
+    actors, agents, cancel, capabilities, clock, concurrency, context, errors, executors, foreach,
+    fuzz, heartbeat, inspect, isolation, limits, lint, loader, lock, memory, metadata, output,
+    overrides, planner, preflight, profiles, progress_feed, progress_stream, protocol,
+    quality_gate, quarantine, regions, report_file, rerun, retry, selection, signing, step_cache,
+    streaming, suggest, suite, telemetry, timeline, triage, validation, version, warnings,
+};
 
 // ============================================================================
 // IMPORTS (DEPENDÊNCIAS)
@@ -83,23 +59,64 @@ mod validation;
 // `use` traz itens de outros módulos para uso direto neste arquivo.
 
 // Imports internos (nossos módulos)
+use cancel::StopSignal;
+use capabilities::Capabilities;
+use clock::{system_clock, Clock, SharedClock, VirtualClock};
+use concurrency::MaxParallel;
 use context::Context;
-use executors::{http::HttpExecutor, wait::WaitExecutor, StepExecutor};
+use errors::ErrorCode;
+use executors::{
+    assert::AssertExecutor,
+    grpc::GrpcExecutor,
+    grpc_health::GrpcHealthExecutor,
+    http::HttpExecutor,
+    log::LogExecutor,
+    rate_limit_probe::RateLimitProbeExecutor,
+    set_variable::SetVariableExecutor,
+    shell::ShellExecutor,
+    tcp::TcpExecutor,
+    transform::TransformExecutor,
+    udp::UdpExecutor,
+    wait::WaitExecutor,
+    webhook::{WebhookListener, WebhookWaitExecutor},
+    StepExecutor,
+};
+use heartbeat::{HeartbeatOptions, Progress};
 use limits::ExecutionLimits;
-use planner::DagPlanner;
-use protocol::{ExecutionReport, ExecutionSummary, Step, StepStatus};
-use telemetry::{init_telemetry, shutdown_telemetry, TelemetryConfig};
+use lint::{LintRule, Severity};
+use lock::PlanLock;
+use memory::MemoryGuard;
+use metadata::{parse_key_value, RunMetadata};
+use planner::{DagPlanner, ResultSinks};
+use progress_stream::ProgressStream;
+use protocol::{
+    ExecutionReport, ExecutionSummary, Region, ReportDetail, Step, StepStatus, REPORT_VERSION,
+};
+use quarantine::Quarantine;
+use signing::ReportSigner;
+use streaming::{ResultStream, StreamHeader};
+use telemetry::{
+    attempt_span, init_telemetry, install_panic_hook, plan_span, shutdown_telemetry, step_span,
+    TelemetryConfig,
+};
+use version::VersionInfo;
 
 // Imports externos (bibliotecas de terceiros)
 use chrono::Utc; // Data/hora em UTC
 use clap::{Parser, Subcommand}; // Parser de argumentos CLI
-use std::fs; // Operações de sistema de arquivos
-use std::path::PathBuf; // Tipo para caminhos de arquivo
+use std::collections::{HashMap, HashSet}; // Mapa e conjunto sem repetição
+use std::path::{Path, PathBuf}; // Tipos para caminhos de arquivo
+use std::process::ExitCode; // Código de saída retornado pelo main
 use std::sync::Arc; // Ponteiro atômico para compartilhar dados entre threads
+use std::time::Duration; // Intervalos de tempo (TTL do lock)
 use tokio::sync::RwLock; // Lock de leitura/escrita assíncrono
-use tracing::{error, info, Level}; // Macros de logging estruturado
+use tracing::{error, info, warn, Instrument, Level}; // Macros de logging estruturado
 use uuid::Uuid; // Geração de UUIDs
 
+/// Código de saída quando o prazo da execução (`RUNNER_MAX_EXECUTION_SECS`)
+/// estoura: o mesmo do `timeout` do coreutils, para o CI separar de falha.
+const EXIT_DEADLINE_EXCEEDED: u8 = 124;
+
 // ============================================================================
 // DEFINIÇÃO DA CLI (INTERFACE DE LINHA DE COMANDO)
 // ============================================================================
@@ -119,16 +136,19 @@ use uuid::Uuid; // Geração de UUIDs
 #[command(name = "runner")]
 #[command(about = "Autonomous Quality Agent Runner - Motor de execução de testes", long_about = None)]
 struct Cli {
-    /// Subcomando a ser executado (atualmente só temos `execute`).
+    /// Subcomando a ser executado (`execute`, `lint`, `explain`, `version`...).
     #[command(subcommand)]
     command: Commands,
 }
 
 /// Enum que define os subcomandos disponíveis.
 ///
-/// Cada variante do enum representa um subcomando diferente.
-/// Atualmente só temos `Execute`, mas podemos adicionar outros no futuro
-/// como `validate`, `lint`, `export`, etc.
+/// Cada variante do enum representa um subcomando diferente:
+/// `Execute` roda o plano, `Lint` só analisa o arquivo, `Explain`
+/// detalha um código de erro, `Capabilities` lista o que é suportado e
+/// `Version`/`SelfUpdate` cuidam do binário.
+// `Execute` concentra as flags da CLI; o enum é criado uma vez só.
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 enum Commands {
     /// Executa um plano de testes UTDL.
@@ -138,13 +158,18 @@ enum Commands {
     Execute {
         /// Caminho para o arquivo UTDL (JSON com o plano de testes).
         ///
+        /// Repetido, roda uma suíte: os planos executam em sequência, com
+        /// um relatório por plano, e steps `shared: true` rodam uma vez.
         /// Exemplo: `--file ./plans/login_test.utdl.json`
-        #[arg(short, long)]
-        file: PathBuf,
+        #[arg(short, long, required = true)]
+        file: Vec<PathBuf>,
 
         /// Caminho para salvar o relatório de execução (opcional).
         ///
         /// Se não especificado, o relatório é impresso no console.
+        /// Durante a execução, os resultados são gravados em
+        /// `<output>.partial.jsonl` (removido ao salvar o relatório final).
+        /// Extensões `.gz` e `.zst` gravam o relatório comprimido.
         /// Exemplo: `--output ./reports/resultado.json`
         #[arg(short, long)]
         output: Option<PathBuf>,
@@ -156,6 +181,15 @@ enum Commands {
         #[arg(long, default_value = "false")]
         parallel: bool,
 
+        /// Máximo de steps simultâneos no modo `--parallel`: número ou `auto`.
+        ///
+        /// Com `auto`, o limite começa baixo, sobe enquanto o ambiente
+        /// responde bem e cai pela metade com 429/5xx, timeouts ou picos de
+        /// latência (AIMD). O teto é `RUNNER_MAX_PARALLEL`.
+        /// Exemplo: `--max-parallel auto`
+        #[arg(long, value_name = "N|auto")]
+        max_parallel: Option<MaxParallel>,
+
         /// Habilita exportação de traces para OpenTelemetry.
         ///
         /// Envia spans/traces para um collector OTEL para
@@ -191,6 +225,312 @@ enum Commands {
         /// Útil para rastreabilidade em sistemas externos.
         #[arg(long)]
         execution_id: Option<String>,
+
+        /// Pula as esperas de `wait`/`sleep` e os backoffs de retry.
+        ///
+        /// Usa um relógio virtual: as durações são registradas mas não
+        /// aguardadas. Útil para validar planos com esperas longas em segundos.
+        #[arg(long, default_value = "false")]
+        fast_wait: bool,
+
+        /// Fator de escala das esperas (0.0 a 1.0). Implica `--fast-wait`.
+        ///
+        /// Exemplo: `--wait-scale 0.1` espera 10% do tempo configurado.
+        #[arg(long)]
+        wait_scale: Option<f64>,
+
+        /// Metadado customizado incluído no relatório (repetível).
+        ///
+        /// Exemplo: `--meta release=1.4.0 --meta ticket=QA-42`
+        /// Somado aos dados de CI/git detectados automaticamente.
+        #[arg(long = "meta", value_name = "KEY=VALUE", value_parser = parse_key_value)]
+        meta: Vec<(String, String)>,
+
+        /// Variável do contexto definida na execução (repetível).
+        ///
+        /// Sobrepõe `config.variables` e `--vars-file`. O valor é lido como
+        /// JSON quando possível; senão, como texto.
+        /// Exemplo: `--var env=prod --var limit=10`
+        #[arg(long = "var", value_name = "KEY=VALUE", value_parser = overrides::parse_var)]
+        var: Vec<(String, serde_json::Value)>,
+
+        /// Arquivo JSON (objeto nome → valor) que sobrepõe `config.variables`.
+        ///
+        /// Exemplo: `--vars-file staging.json`
+        #[arg(long, value_name = "FILE")]
+        vars_file: Option<PathBuf>,
+
+        /// Mantém os snapshots completos do contexto em cada step.
+        ///
+        /// Por padrão o relatório guarda apenas o `context_delta`
+        /// (variáveis adicionadas, alteradas e removidas pelo step).
+        #[arg(long, default_value = "false")]
+        full_context: bool,
+
+        /// Nível de detalhe do relatório: minimal, standard ou full.
+        ///
+        /// - `minimal`: status, duração, erros e dados HTTP básicos
+        /// - `standard`: + delta do contexto e extrações (padrão)
+        /// - `full`: + snapshots completos do contexto e bodies HTTP
+        #[arg(long, value_enum)]
+        report_detail: Option<ReportDetail>,
+
+        /// Reexecuta apenas os steps que falharam (ou foram pulados) em um
+        /// relatório anterior, mais as dependências deles.
+        ///
+        /// O novo relatório mescla os resultados novos com os que já
+        /// tinham passado. Exemplo: `--retry-failed ./reports/nightly.json`
+        #[arg(long, value_name = "REPORT")]
+        retry_failed: Option<PathBuf>,
+
+        /// Lista de steps em quarentena (YAML/JSON com `steps` e `tags`).
+        ///
+        /// Falhas desses steps aparecem no relatório (status `quarantined`),
+        /// mas não afetam o status geral nem o exit code.
+        /// Exemplo: `--quarantine ./quarantine.yaml`
+        #[arg(long, value_name = "FILE")]
+        quarantine: Option<PathBuf>,
+
+        /// Semente para valores aleatórios reprodutíveis.
+        ///
+        /// Com a mesma semente, `${random_int}` e `${random_uuid}` geram os
+        /// mesmos valores em cada step, inclusive no modo `--parallel`.
+        /// A semente usada aparece no relatório (`seed`).
+        #[arg(long, value_name = "N")]
+        seed: Option<u64>,
+
+        /// Chave privada PEM para assinar o relatório salvo (requer `--output`).
+        ///
+        /// Grava `<output>.sig` com um JWS sobre o SHA-256 do relatório.
+        /// Chaves Ed25519 (EdDSA), EC P-256 (ES256) ou RSA (RS256).
+        /// Exemplo: `--sign-report ./keys/report-signing.pem`
+        #[arg(long, value_name = "KEY", requires = "output")]
+        sign_report: Option<PathBuf>,
+
+        /// Nome do lock de execução exclusiva.
+        ///
+        /// Se outra execução já tem o lock, falha na hora com E4005.
+        /// Local (arquivo) por padrão; com `--lock-redis` vale entre agentes.
+        /// Exemplo: `--lock-name checkout-suite`
+        #[arg(long, value_name = "NAME")]
+        lock_name: Option<String>,
+
+        /// URL do Redis para o lock (ou `RUNNER_LOCK_REDIS_URL`).
+        ///
+        /// Exemplo: `--lock-redis redis://ci-redis:6379`
+        #[arg(long, value_name = "URL", requires = "lock_name")]
+        lock_redis: Option<String>,
+
+        /// Intervalo (segundos) entre heartbeats com o progresso da execução.
+        ///
+        /// Cada heartbeat vai para o log, para o relatório parcial (com
+        /// `--output`) e para `--heartbeat-url`. `0` desativa (padrão: 30).
+        #[arg(long, value_name = "SECS")]
+        heartbeat_secs: Option<u64>,
+
+        /// URL que recebe um `POST` JSON a cada heartbeat.
+        ///
+        /// Exemplo: `--heartbeat-url http://orchestrator/runs/42/ping`
+        #[arg(long, value_name = "URL")]
+        heartbeat_url: Option<String>,
+
+        /// URL que recebe um `POST` JSON a cada step que termina.
+        ///
+        /// Traz status, código de erro e extrações; a resposta
+        /// `{"action": "abort"}` cancela os steps que ainda não começaram.
+        /// Exemplo: `--progress-url http://brain/runs/42/events`
+        #[arg(long, value_name = "URL")]
+        progress_url: Option<String>,
+
+        /// Escreve um evento JSON por linha (NDJSON) enquanto o plano roda.
+        ///
+        /// `-` usa o stdout (a execução fica silenciosa e os logs vão para o
+        /// stderr); outro valor é um arquivo ou named pipe.
+        /// Exemplo: `--progress-stream - | jq -c .`
+        #[arg(long, value_name = "TARGET")]
+        progress_stream: Option<String>,
+
+        /// Ignora o cache de steps (`cache`): todos executam e regravam.
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Executa só estes steps e as dependências deles (repetível ou `a,b`).
+        ///
+        /// Exemplo: `--only create_order`
+        #[arg(long, value_name = "STEP_ID", value_delimiter = ',')]
+        only: Vec<String>,
+
+        /// Não executa estes steps nem os que dependem deles.
+        ///
+        /// Exemplo: `--skip slow_report`
+        #[arg(long, value_name = "STEP_ID", value_delimiter = ',')]
+        skip: Vec<String>,
+
+        /// Executa só steps com alguma destas tags (e as dependências).
+        ///
+        /// Tem prioridade sobre as `tags` do `--profile`.
+        /// Exemplo: `--tags smoke,auth`
+        #[arg(long, value_name = "TAG", value_delimiter = ',')]
+        tags: Vec<String>,
+
+        /// Perfil de execução (ex: `smoke`, `regression`, `nightly`).
+        ///
+        /// Aplica tags, retries, limites, nível de detalhe e notificações
+        /// definidos no arquivo de perfis. Flags explícitas têm prioridade.
+        /// Exemplo: `--profile smoke`
+        #[arg(long, value_name = "NAME")]
+        profile: Option<String>,
+
+        /// Arquivo de perfis (padrão: `runner.profiles.yaml`).
+        #[arg(long, value_name = "FILE", requires = "profile")]
+        profiles_file: Option<PathBuf>,
+
+        /// Executa o plano em cada região de `config.regions` (`all` ou `eu,us`).
+        ///
+        /// Com `--output report.json`, grava `report.<região>.json` para cada
+        /// uma e o comparativo (status e latência por step) em
+        /// `report.regions.json`.
+        #[arg(long, value_name = "all|NAME,...")]
+        regions: Option<String>,
+
+        /// Permite steps `shell_command` (programas locais na máquina do Runner).
+        ///
+        /// Sem esta flag, um plano com `shell_command` falha na validação:
+        /// planos gerados pelo Brain ou recebidos pela rede não rodam
+        /// comandos locais sem autorização explícita.
+        #[arg(long, default_value = "false")]
+        allow_shell: bool,
+    },
+
+    /// Analisa um plano UTDL sem executá-lo (regras de estilo e confiabilidade).
+    ///
+    /// Além dos erros de validação, aponta problemas como steps
+    /// inalcançáveis, extrações não usadas e segredos no plano.
+    /// Os achados são avisos, a menos que a regra esteja em `--deny`.
+    Lint {
+        /// Caminho para o arquivo UTDL.
+        #[arg(short, long)]
+        file: PathBuf,
+
+        /// Promove uma regra a erro (código `L004` ou nome `hardcoded-secret`; `all` para todas).
+        ///
+        /// Pode ser repetido. Com algum erro, o comando sai com código 1.
+        #[arg(long, value_name = "RULE")]
+        deny: Vec<String>,
+    },
+
+    /// Mostra o que um step fez com o contexto, a partir de um relatório salvo.
+    ///
+    /// Exibe as variáveis alteradas, as extrações e a requisição enviada.
+    /// Exemplo: `runner context --report report.json --step create_user`.
+    Context {
+        /// Relatório JSON gerado por `execute --output`.
+        #[arg(long)]
+        report: PathBuf,
+
+        /// ID do step (ou da iteração, ex: `create_user[2]`).
+        #[arg(long)]
+        step: String,
+    },
+
+    /// Gera testes negativos a partir de um step `http_request`.
+    ///
+    /// Cada campo do body vira steps com o campo ausente, com tipo errado,
+    /// gigante ou com enum inválido, todos esperando um 4xx. O plano gerado
+    /// mantém só as dependências do step.
+    /// Exemplo: `runner fuzz --file plan.json --step create_user --output fuzz.json`.
+    Fuzz {
+        /// Caminho para o arquivo UTDL.
+        #[arg(short, long)]
+        file: PathBuf,
+
+        /// ID do step usado como base das mutações.
+        #[arg(long)]
+        step: String,
+
+        /// Arquivo para o plano gerado (padrão: stdout).
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Propõe assertions e extrações a partir das respostas de um relatório.
+    ///
+    /// Gera um patch por `step_id` que passou (status, schema do body, campos de ID).
+    /// Os bodies só estão no relatório com `--report-detail full`.
+    /// Exemplo: `runner suggest --report report.json --output suggestions.json`.
+    Suggest {
+        /// Relatório JSON gerado por `execute --output`.
+        #[arg(long)]
+        report: PathBuf,
+
+        /// Arquivo para o patch (padrão: stdout).
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Desenha a execução de um relatório como um Gantt (um worker por linha).
+    ///
+    /// Exemplo: `runner timeline report.json --format mermaid-gantt`.
+    Timeline {
+        /// Relatório JSON gerado por `execute --output`.
+        report: PathBuf,
+
+        /// Formato: `html` (página autocontida) ou `mermaid-gantt`.
+        #[arg(long, value_enum, default_value = "html")]
+        format: timeline::TimelineFormat,
+
+        /// Arquivo de saída (padrão: stdout).
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Atende steps despachados por um Runner coordenador (`step.agent`).
+    ///
+    /// Com `RUNNER_AGENT_TOKEN` definido, exige `Authorization: Bearer`;
+    /// sem ele, só escuta em loopback.
+    /// Exemplo: `RUNNER_AGENT_TOKEN=... runner agent --listen 0.0.0.0:7700`.
+    Agent {
+        /// Endereço de escuta (fora de loopback, exige `RUNNER_AGENT_TOKEN`).
+        #[arg(long, default_value = "127.0.0.1:7700")]
+        listen: std::net::SocketAddr,
+    },
+
+    /// Explica um código de erro estruturado (descrição, causas e solução).
+    ///
+    /// Exemplo: `runner explain E3010`. Sem código, lista todos.
+    Explain {
+        /// Código do erro (`E3010`, `e3010` ou `3010`).
+        code: Option<String>,
+    },
+
+    /// Lista actions, assertions, extrações, funções de interpolação e limites suportados.
+    Capabilities {
+        /// Saída em JSON (para o Brain gerar só planos suportados).
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Mostra a versão, o commit e o que este Runner suporta.
+    Version {
+        /// Saída em JSON (para o Brain verificar compatibilidade).
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Atualiza o Runner para a última GitHub Release (ou `--version`).
+    #[cfg(feature = "self-update")]
+    SelfUpdate {
+        /// Apenas informa se há uma versão nova, sem baixar.
+        #[arg(long)]
+        check: bool,
+
+        /// Instala uma versão específica (ex: `0.6.0`).
+        #[arg(long, value_name = "VERSION")]
+        version: Option<String>,
+
+        /// Instala mesmo se a release não publicar o checksum `.sha256`.
+        #[arg(long)]
+        insecure: bool,
     },
 }
 
@@ -209,8 +549,11 @@ enum Commands {
 /// 2. Configura a telemetria (logging e OTEL)
 /// 3. Chama a função apropriada baseado no subcomando
 /// 4. Encerra a telemetria antes de sair
+///
+/// Erros fatais retornam até aqui como `ExitCode` (em vez de
+/// `process::exit`), para que o flush da telemetria sempre aconteça.
 #[tokio::main]
-async fn main() {
+async fn main() -> ExitCode {
     // Parseia os argumentos da linha de comando.
     // Se os argumentos forem inválidos, `clap` exibe uma mensagem de erro e sai.
     let cli = Cli::parse();
@@ -222,19 +565,50 @@ async fn main() {
             file,
             output,
             parallel,
+            max_parallel,
             otel,
             otel_endpoint,
             silent,
             verbose,
             execution_id,
+            fast_wait,
+            wait_scale,
+            meta,
+            var,
+            vars_file,
+            full_context,
+            report_detail,
+            retry_failed,
+            quarantine,
+            seed,
+            sign_report,
+            lock_name,
+            lock_redis,
+            heartbeat_secs,
+            heartbeat_url,
+            progress_url,
+            progress_stream,
+            no_cache,
+            only,
+            skip,
+            tags,
+            profile,
+            profiles_file,
+            regions,
+            allow_shell,
         } => {
             // Gera ou usa o execution_id fornecido.
             let exec_id = execution_id
                 .clone()
                 .unwrap_or_else(|| Uuid::new_v4().to_string());
 
+            // Com `--progress-stream -`, o stdout é só dos eventos.
+            let events_on_stdout = progress_stream.as_deref() == Some(progress_stream::STDOUT);
+            let silent = &(*silent || events_on_stdout);
+
             // Carrega configuração de telemetria das variáveis de ambiente.
             let mut telemetry_config = TelemetryConfig::from_env();
+            telemetry_config.console_to_stderr = events_on_stdout;
 
             // Configura nível de log baseado nos flags silent/verbose.
             telemetry_config.log_level = if *silent {
@@ -269,12 +643,204 @@ async fn main() {
                     .try_init();
             }
 
+            // Garante o flush dos traces mesmo se o Runner entrar em panic.
+            install_panic_hook();
+
+            let progress_stream = match progress_stream {
+                Some(target) => match ProgressStream::open(target, &exec_id) {
+                    Ok(stream) => Some(Arc::new(stream)),
+                    Err(e) => {
+                        error!("{:#}", e);
+                        shutdown_telemetry();
+                        return ExitCode::FAILURE;
+                    }
+                },
+                None => None,
+            };
+
+            // Seleciona o relógio: virtual se --fast-wait/--wait-scale, senão real.
+            let clock: SharedClock = match (*fast_wait, wait_scale) {
+                (_, Some(scale)) => Arc::new(VirtualClock::new(*scale)),
+                (true, None) => Arc::new(VirtualClock::instant()),
+                (false, None) => system_clock(),
+            };
+
+            // Carrega o perfil (--profile); as flags explícitas têm prioridade.
+            let mut profile = match profile {
+                Some(name) => match profiles::load(profiles_file.as_deref(), name) {
+                    Ok(profile) => {
+                        if !*silent {
+                            info!(profile = %name, "Run profile loaded");
+                        }
+                        profile
+                    }
+                    Err(e) => {
+                        error!("{:#}", e);
+                        shutdown_telemetry();
+                        return ExitCode::FAILURE;
+                    }
+                },
+                None => profiles::Profile::default(),
+            };
+            // --tags tem prioridade sobre as tags do perfil.
+            if !tags.is_empty() {
+                profile.tags.clear();
+            }
+            let heartbeat_secs = heartbeat_secs.or(profile.heartbeat_secs).unwrap_or(30);
+
+            // Variáveis de --vars-file e --var (sobrepõem config.variables).
+            let variables = match overrides::load(vars_file.as_deref(), var) {
+                Ok(variables) => variables,
+                Err(e) => {
+                    error!("{:#}", e);
+                    shutdown_telemetry();
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            // Coleta metadados de CI/git + pares --meta.
+            let run_metadata = RunMetadata::collect(meta);
+
             // Executa o plano de testes.
             // `*parallel` dereferencia o valor booleano.
-            execute_plan(file, output, *parallel, &exec_id, *silent).await;
+            let options = RunOptions {
+                parallel: *parallel || profile.parallel.unwrap_or(false),
+                max_parallel: *max_parallel,
+                silent: *silent,
+                clock,
+                run_metadata,
+                full_context: *full_context,
+                report_detail: report_detail.or(profile.report_detail).unwrap_or_default(),
+                retry_failed: retry_failed.clone(),
+                quarantine: quarantine.clone().or_else(|| profile.quarantine.clone()),
+                seed: *seed,
+                sign_report: sign_report.clone(),
+                lock: lock_name
+                    .clone()
+                    .map(|name| (name, lock::LockBackend::from_options(lock_redis.as_deref()))),
+                heartbeat: (heartbeat_secs > 0).then(|| HeartbeatOptions {
+                    interval: std::time::Duration::from_secs(heartbeat_secs),
+                    url: heartbeat_url
+                        .clone()
+                        .or_else(|| profile.heartbeat_url.clone()),
+                }),
+                progress_url: progress_url.clone(),
+                progress_stream,
+                no_cache: *no_cache,
+                profile,
+                region: None,
+                variables,
+                filter: selection::StepFilter {
+                    only: only.clone(),
+                    skip: skip.clone(),
+                    tags: tags.clone(),
+                },
+                shared: None,
+                allow_shell: *allow_shell,
+            };
+            let exit_code = match (file.as_slice(), regions) {
+                ([file], Some(spec)) => {
+                    execute_regions(file, output, &exec_id, options, spec).await
+                }
+                ([file], None) => execute_plan(file, output, &exec_id, options).await.0,
+                (_, Some(_)) => {
+                    error!("--regions não pode ser combinado com vários --file");
+                    ExitCode::FAILURE
+                }
+                (_, None) if retry_failed.is_some() => {
+                    error!("--retry-failed não pode ser combinado com vários --file");
+                    ExitCode::FAILURE
+                }
+                (files, None) => execute_suite(files, output, &exec_id, options).await,
+            };
 
             // Encerra a telemetria, garantindo que todos os traces sejam enviados.
             shutdown_telemetry();
+            exit_code
+        }
+        Commands::Lint { file, deny } => lint_plan_file(file, deny),
+        Commands::Context { report, step } => match inspect::load_step(report, step) {
+            Ok(result) => {
+                print!("{}", inspect::render(&result));
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("❌ {:#}", e);
+                ExitCode::FAILURE
+            }
+        },
+        Commands::Fuzz { file, step, output } => fuzz_plan(file, step, output.as_deref()),
+        Commands::Suggest { report, output } => suggest_from_report(report, output.as_deref()),
+        Commands::Timeline {
+            report,
+            format,
+            output,
+        } => render_timeline(report, *format, output.as_deref()),
+        Commands::Agent { listen } => {
+            if let Err(e) = init_telemetry(TelemetryConfig::from_env()) {
+                eprintln!("Warning: Failed to initialize telemetry: {}", e);
+            }
+            let token = std::env::var(agents::AGENT_TOKEN_ENV).ok();
+            let served = match std::net::TcpListener::bind(listen) {
+                Ok(listener) => agents::serve(listener, token).await,
+                Err(e) => Err(e.into()),
+            };
+            shutdown_telemetry();
+            match served {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(e) => {
+                    eprintln!("❌ {:#}", e);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Commands::Explain { code } => explain_error_code(code.as_deref()),
+        Commands::Capabilities { json } => {
+            let capabilities = Capabilities::current();
+            if *json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&capabilities)
+                        .expect("Failed to serialize capabilities")
+                );
+            } else {
+                println!("{}", capabilities.human());
+            }
+            ExitCode::SUCCESS
+        }
+        Commands::Version { json } => {
+            let info = VersionInfo::current();
+            if *json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&info).expect("Failed to serialize version")
+                );
+            } else {
+                println!("{}", info.human());
+            }
+            ExitCode::SUCCESS
+        }
+        #[cfg(feature = "self-update")]
+        Commands::SelfUpdate {
+            check,
+            version,
+            insecure,
+        } => {
+            let options = self_update::UpdateOptions {
+                check_only: *check,
+                version: version.clone(),
+                insecure: *insecure,
+            };
+            match self_update::run(options).await {
+                Ok(message) => {
+                    println!("{}", message);
+                    ExitCode::SUCCESS
+                }
+                Err(e) => {
+                    eprintln!("❌ Self-update failed: {:#}", e);
+                    ExitCode::FAILURE
+                }
+            }
         }
     }
 }
@@ -283,6 +849,55 @@ async fn main() {
 // FUNÇÃO DE EXECUÇÃO DO PLANO
 // ============================================================================
 
+/// Opções de execução de um plano, vindas da CLI.
+#[derive(Clone)]
+struct RunOptions {
+    /// Se deve usar execução paralela (DAG).
+    parallel: bool,
+    /// Limite de paralelismo da CLI (`--max-parallel`), se informado.
+    max_parallel: Option<MaxParallel>,
+    /// Se true, suprime logs informativos.
+    silent: bool,
+    /// Relógio usado por waits e backoffs (real ou virtual).
+    clock: SharedClock,
+    /// Metadados de CI/git incluídos no relatório.
+    run_metadata: RunMetadata,
+    /// Se true, mantém snapshots completos do contexto (senão, só o delta).
+    full_context: bool,
+    /// Nível de detalhe do relatório (`full` implica `full_context`).
+    report_detail: ReportDetail,
+    /// Relatório anterior cujos steps falhos devem ser reexecutados.
+    retry_failed: Option<PathBuf>,
+    /// Lista de steps cujas falhas não afetam o status geral.
+    quarantine: Option<PathBuf>,
+    /// Semente dos valores aleatórios (`--seed`).
+    seed: Option<u64>,
+    /// Chave privada para assinar o relatório (`--sign-report`).
+    sign_report: Option<PathBuf>,
+    /// Nome e backend do lock de execução exclusiva (`--lock-name`).
+    lock: Option<(String, lock::LockBackend)>,
+    /// Heartbeat periódico (`--heartbeat-secs`, `--heartbeat-url`).
+    heartbeat: Option<HeartbeatOptions>,
+    /// URL dos eventos por step (`--progress-url`).
+    progress_url: Option<String>,
+    /// Eventos NDJSON (`--progress-stream`), um destino para todos os planos.
+    progress_stream: Option<Arc<ProgressStream>>,
+    /// Ignora as entradas do cache de steps (`--no-cache`).
+    no_cache: bool,
+    /// Perfil de execução (`--profile`): tags, retries e limites.
+    profile: profiles::Profile,
+    /// Região de `config.regions` em que o plano roda (`--regions`).
+    region: Option<Region>,
+    /// Variáveis de `--vars-file` e `--var`, aplicadas por cima do plano e da região.
+    variables: HashMap<String, serde_json::Value>,
+    /// Filtros de steps (`--only`, `--skip`, `--tags`).
+    filter: selection::StepFilter,
+    /// Steps `shared` já executados na suíte (vários `--file`).
+    shared: Option<suite::SharedSteps>,
+    /// Registra o executor `shell_command` (`--allow-shell`).
+    allow_shell: bool,
+}
+
 /// Executa um plano de testes UTDL.
 ///
 /// Esta é a função principal que orquestra toda a execução:
@@ -298,47 +913,206 @@ async fn main() {
 /// ## Parâmetros:
 /// - `file_path`: Caminho para o arquivo UTDL
 /// - `output_path`: Onde salvar o relatório (ou None para stdout)
-/// - `parallel`: Se deve usar execução paralela (DAG)
 /// - `execution_id`: UUID único desta execução
-/// - `silent`: Se true, suprime logs informativos
+/// - `options`: Modo de execução, relógio, metadados, etc. (`RunOptions`)
+///
+/// ## Retorno:
+/// `ExitCode::FAILURE` em erros fatais ou se algum step falhou, com o
+/// relatório gerado (ausente em erros fatais antes da execução).
 async fn execute_plan(
     file_path: &PathBuf,
     output_path: &Option<PathBuf>,
-    parallel: bool,
     execution_id: &str,
-    silent: bool,
-) {
+    options: RunOptions,
+) -> (ExitCode, Option<ExecutionReport>) {
+    let RunOptions {
+        parallel,
+        max_parallel,
+        silent,
+        clock,
+        run_metadata,
+        full_context,
+        report_detail,
+        retry_failed,
+        quarantine,
+        seed,
+        sign_report,
+        lock,
+        heartbeat: heartbeat_options,
+        progress_url,
+        progress_stream,
+        no_cache,
+        profile,
+        region,
+        variables,
+        filter,
+        shared,
+        allow_shell,
+    } = options;
+    let full_context = full_context || report_detail == ReportDetail::Full;
+
     if !silent {
         info!(execution_id = %execution_id, "Runner initializing");
     }
     let start_time = Utc::now();
 
     // 1. Carrega o plano do arquivo JSON.
-    let plan = match loader::load_plan_from_file(file_path) {
+    let mut plan = match loader::load_plan_from_file(file_path) {
         Ok(p) => p,
         Err(e) => {
             error!(error = %e, "Failed to load plan");
-            std::process::exit(1);
+            return (ExitCode::FAILURE, None);
         }
     };
     if !silent {
         info!(plan_id = %plan.meta.id, plan_name = %plan.meta.name, "Plan loaded");
     }
 
+    // 1.1. Com --regions, usa a base_url e as variáveis da região.
+    if let Some(region) = &region {
+        plan.config.base_url = region.base_url.clone();
+        plan.config.variables.extend(region.variables.clone());
+        if !silent {
+            info!(region = %region.name, base_url = %region.base_url, "Running plan in region");
+        }
+    }
+
+    // 1.2. --vars-file e --var sobrepõem as variáveis do plano (e da região).
+    if !variables.is_empty() {
+        if !silent {
+            let mut names: Vec<&String> = variables.keys().collect();
+            names.sort();
+            info!(variables = ?names, "Variable overrides applied");
+        }
+        plan.config.variables.extend(variables);
+    }
+
     // 2. Valida a estrutura do plano antes de executar.
-    if let Err(errors) = validation::validate_plan(&plan) {
+    // `shell_command` só é aceito com --allow-shell.
+    let mut errors = validation::validate_plan(&plan).err().unwrap_or_default();
+    if !allow_shell {
+        errors.extend(validation::shell_not_allowed(&plan));
+    }
+    if !errors.is_empty() {
         error!("Plan validation failed with {} error(s):", errors.len());
         for err in &errors {
             error!("  - {}", err);
         }
-        std::process::exit(1);
+        return (ExitCode::FAILURE, None);
     }
     if !silent {
         info!("Plan validation passed");
     }
 
-    // 2.5. Valida limites de execução.
-    let limits = ExecutionLimits::from_env();
+    // 2.1. Avisos de fluxo de dados: variáveis usadas antes de o produtor rodar.
+    for warning in validation::dependency_warnings(&plan, parallel) {
+        if warning.is_ordering_bug() {
+            warn!("{}", warning);
+        } else if !silent {
+            info!("{}", warning);
+        }
+    }
+
+    // 2.2. Carrega a chave de assinatura (--sign-report) antes de executar.
+    let signer = match &sign_report {
+        Some(path) => match ReportSigner::load(path) {
+            Ok(signer) => Some(signer),
+            Err(e) => {
+                error!(error = %e, "Failed to load report signing key");
+                return (ExitCode::FAILURE, None);
+            }
+        },
+        None => None,
+    };
+
+    // 2.3. Carrega a lista de quarentena (--quarantine).
+    let quarantine = match &quarantine {
+        Some(path) => match Quarantine::load(path) {
+            Ok(list) => Some(list),
+            Err(e) => {
+                error!(error = %e, "Failed to load quarantine list");
+                return (ExitCode::FAILURE, None);
+            }
+        },
+        None => None,
+    };
+    let quarantined_steps = quarantine.as_ref().map(|_| plan.steps.clone());
+
+    // --only/--skip citam IDs do plano inteiro, antes das podas abaixo.
+    if let Err(e) = filter.check_ids(&plan.steps) {
+        error!("{:#}", e);
+        return (ExitCode::FAILURE, None);
+    }
+
+    // 2.4. Com --retry-failed, mantém só os steps que falharam (e suas dependências).
+    let previous_report = match &retry_failed {
+        Some(path) => match rerun::load_previous_report(path, &plan.meta.id) {
+            Ok(report) => Some(report),
+            Err(e) => {
+                error!(error = %e, "Failed to load previous report");
+                return (ExitCode::FAILURE, None);
+            }
+        },
+        None => None,
+    };
+    let all_steps = previous_report.as_ref().map(|_| plan.steps.clone());
+    if let Some(previous) = &previous_report {
+        let selected = rerun::select_steps(&plan.steps, previous);
+        plan.steps.retain(|s| selected.contains(&s.id));
+        if !silent {
+            info!(
+                previous_execution_id = %previous.execution_id,
+                retrying = plan.steps.len(),
+                total_steps = all_steps.as_ref().map_or(0, Vec::len),
+                "Retrying failed steps from previous report"
+            );
+        }
+    }
+    let retry_of = previous_report.as_ref().map(|p| p.execution_id.clone());
+
+    // 2.5. Aplica o perfil: filtro por tags e tentativas de retry.
+    if let Some(selected) = profile.select_steps(&plan.steps) {
+        let before = plan.steps.len();
+        plan.steps.retain(|s| selected.contains(&s.id));
+        if !silent {
+            info!(
+                tags = ?profile.tags,
+                selected = plan.steps.len(),
+                total_steps = before,
+                "Steps selected by profile tags"
+            );
+        }
+    }
+    profile.apply_retries(&mut plan.steps);
+
+    // 2.5.1. Filtros --only/--skip/--tags (os excluídos entram no relatório como not_run).
+    let plan_order: Vec<String> = all_steps
+        .as_deref()
+        .unwrap_or(&plan.steps)
+        .iter()
+        .map(|s| s.id.clone())
+        .collect();
+    let filtered_out = if filter.is_empty() {
+        Vec::new()
+    } else {
+        let (selected, excluded) = filter.select(&plan.steps);
+        plan.steps.retain(|s| selected.contains(&s.id));
+        if !silent {
+            info!(
+                selected = plan.steps.len(),
+                filtered_out = excluded.len(),
+                "Steps selected by --only/--skip/--tags"
+            );
+        }
+        excluded
+    };
+
+    // 2.6. Valida limites de execução.
+    let mut limits = ExecutionLimits::from_env();
+    profile.apply_limits(&mut limits);
+    if let Some(MaxParallel::Fixed(n)) = max_parallel {
+        limits.max_parallel = n;
+    }
     let total_retries: u32 = plan
         .steps
         .iter()
@@ -355,9 +1129,39 @@ async fn execute_plan(
         for v in &limit_result.violations {
             error!("  - {}", v.message);
         }
-        std::process::exit(1);
+        return (ExitCode::FAILURE, None);
     }
 
+    // 2.7. Aguarda o ambiente ficar pronto (config.wait_for).
+    if let Some(wait_for) = &plan.config.wait_for {
+        if let Err(e) = preflight::wait_until_ready(wait_for, &plan.config.base_url).await {
+            error!(error = %e, "Preflight failed");
+            return (ExitCode::FAILURE, None);
+        }
+    }
+
+    // 2.8. Adquire o lock de execução exclusiva (--lock-name). É liberado no
+    // fim ou, em qualquer `return` antecipado, no `Drop` do `PlanLock`.
+    let plan_lock = match lock {
+        Some((name, backend)) => {
+            // Expira depois do tempo máximo de execução (Runner morto não trava o plano).
+            let ttl = limits.max_execution_time + Duration::from_secs(60);
+            match PlanLock::acquire(&name, execution_id, backend, ttl).await {
+                Ok(plan_lock) => {
+                    if !silent {
+                        info!(lock = %name, "Execution lock acquired");
+                    }
+                    Some(plan_lock)
+                }
+                Err(e) => {
+                    error!("{}", e);
+                    return (ExitCode::FAILURE, None);
+                }
+            }
+        }
+        None => None,
+    };
+
     // 3. Inicializa o contexto e os executores.
     let mut context = Context::new();
     context.set(
@@ -368,6 +1172,14 @@ async fn execute_plan(
         "execution_id",
         serde_json::Value::String(execution_id.to_string()),
     );
+    // Diretório do plano: base dos caminhos relativos (ex: `body_file`).
+    if let Some(plan_dir) = file_path.parent() {
+        let plan_dir = plan_dir.canonicalize().unwrap_or(plan_dir.to_path_buf());
+        context.set(
+            "plan_dir",
+            serde_json::Value::String(plan_dir.to_string_lossy().into_owned()),
+        );
+    }
     // Adiciona timeout_ms global ao contexto
     context.set(
         "timeout_ms",
@@ -382,36 +1194,238 @@ async fn execute_plan(
         .collect::<serde_json::Map<String, serde_json::Value>>()
         .into();
     context.set("global_headers", global_headers_value);
+    context.set(
+        "correlation_header",
+        serde_json::Value::String(plan.config.correlation_header.clone()),
+    );
+    context.set(
+        "report_detail",
+        serde_json::Value::String(report_detail.as_str().to_string()),
+    );
     context.extend(&plan.config.variables);
+    actors::register(&mut context, &plan.config.actors);
+    // Teto do `parallel_foreach` (fora das variáveis: o plano não o sobrescreve).
+    context.max_parallel = limits.max_parallel;
+    if let Some(seed) = seed {
+        context.set_seed(seed);
+    }
+
+    // Listener de callbacks, aberto antes do primeiro step para que a URL
+    // de cada `webhook_wait` possa ser cadastrada por um step anterior.
+    let webhooks = if plan.steps.iter().any(|s| s.action == "webhook_wait") {
+        match WebhookListener::start(plan.config.webhook.as_ref()) {
+            Ok(listener) => {
+                listener.register(&plan.steps, &mut context);
+                Some(listener)
+            }
+            Err(e) => {
+                error!(error = %format!("{:#}", e), "Failed to start webhook listener");
+                return (ExitCode::FAILURE, None);
+            }
+        }
+    } else {
+        None
+    };
+
+    // Diretório temporário da execução (um subdiretório por step), removido no teardown.
+    let workspace = Arc::new(isolation::RunWorkspace::new(execution_id));
 
     // Cria os executores para cada tipo de action.
-    let http_executor = HttpExecutor::new();
-    let wait_executor = WaitExecutor::new();
+    let http_executor = match HttpExecutor::from_config(&plan.config) {
+        Ok(executor) => executor,
+        Err(e) => {
+            error!(error = %e, "Failed to build HTTP client");
+            return (ExitCode::FAILURE, None);
+        }
+    };
+    // Login de `config.auth` antes do primeiro step (o device flow pede ação humana).
+    if let Err(e) = http_executor.authenticate(&mut context).await {
+        error!(error = %format!("{:#}", e), "Authentication failed");
+        return (ExitCode::FAILURE, None);
+    }
+    let wait_executor = WaitExecutor::with_clock(clock.clone());
     let graphql_executor = executors::graphql::GraphqlExecutor::default();
-    let executors: Vec<Box<dyn StepExecutor + Send + Sync>> = vec![
+    let rate_limit_executor = RateLimitProbeExecutor::with_clients(http_executor.clients());
+    let mut executors: Vec<Box<dyn StepExecutor + Send + Sync>> = vec![
         Box::new(http_executor),
         Box::new(wait_executor),
         Box::new(graphql_executor),
+        Box::new(SetVariableExecutor::new()),
+        Box::new(LogExecutor::new()),
+        Box::new(AssertExecutor::new()),
+        Box::new(TransformExecutor::new()),
+        Box::new(TcpExecutor::new()),
+        Box::new(UdpExecutor::new()),
+        Box::new(GrpcHealthExecutor::new()),
+        Box::new(GrpcExecutor::new()),
+        Box::new(rate_limit_executor),
+        Box::new(WebhookWaitExecutor::new(webhooks)),
+        Box::new(agents::RemoteAgentExecutor::new(plan.config.agents.clone())),
     ];
+    if allow_shell {
+        executors.push(Box::new(ShellExecutor::with_workspace(workspace.clone())));
+    }
+    // No modo suíte, steps `shared` já executados são atendidos primeiro.
+    if let Some(shared) = &shared {
+        executors.insert(0, Box::new(suite::SharedStepExecutor::new(shared.clone())));
+    }
+    let shared_steps: Vec<Step> = plan.steps.iter().filter(|s| s.shared).cloned().collect();
+    // Steps com `cache` válido no disco não executam (exceto com `--no-cache`).
+    let cached_steps: Vec<Step> = plan
+        .steps
+        .iter()
+        .filter(|s| s.cache.is_some())
+        .cloned()
+        .collect();
+    let step_cache = (!cached_steps.is_empty()).then(|| step_cache::StepCacheStore::open(&context));
+    if let (Some(store), false) = (&step_cache, no_cache) {
+        executors.insert(
+            0,
+            Box::new(step_cache::StepCacheExecutor::new(store.clone())),
+        );
+    }
 
     // 4. Executa os steps (paralelo ou sequencial).
     if !silent {
-        info!(parallel = parallel, "Starting execution");
+        info!(
+            parallel = parallel,
+            virtual_clock = clock.is_virtual(),
+            "Starting execution"
+        );
     }
 
+    // Relatório parcial: cada resultado é gravado assim que o step termina.
+    let stream = output_path.as_ref().and_then(|path| {
+        let header = StreamHeader {
+            report_version: REPORT_VERSION.to_string(),
+            execution_id: execution_id.to_string(),
+            plan_id: plan.meta.id.clone(),
+            plan_name: plan.meta.name.clone(),
+            start_time: start_time.to_rfc3339(),
+        };
+        match ResultStream::create(path, &header, report_detail) {
+            Ok(stream) => Some(Arc::new(stream)),
+            Err(e) => {
+                error!(error = %e, "Partial report disabled");
+                None
+            }
+        }
+    });
+
+    // Span raiz com os metadados do plano (nome, tags) para filtrar traces.
+    let plan_span = plan_span(&plan.meta, execution_id);
+
+    // Avisos de qualidade do plano (antes de os steps serem consumidos).
+    let mut run_warnings = warnings::plan_warnings(&plan);
+
+    // Sinal de vida periódico com o progresso (execuções longas).
+    let progress = heartbeat_options
+        .as_ref()
+        .map(|_| Progress::new(plan.steps.len()));
+    let heartbeat_task = heartbeat_options
+        .zip(progress.clone())
+        .map(|(options, progress)| {
+            heartbeat::spawn(options, execution_id.to_string(), progress, stream.clone())
+        });
+
+    // Motivo de parada compartilhado: memória estourada, abort do Brain ou prazo.
+    let stop = StopSignal::new();
+    let deadline_task = stop.deadline(limits.max_execution_time);
+
+    // Dados retidos (resultados, bodies, contexto) contra RUNNER_MAX_MEMORY_MB.
+    let memory = MemoryGuard::new(limits.max_memory_bytes, Arc::clone(&stop));
+
+    // Resultados por step para o Brain (--progress-url).
+    let (feed, feed_task) = match progress_url {
+        Some(url) => {
+            let (feed, task) =
+                progress_feed::spawn(url, execution_id, plan.steps.len(), Arc::clone(&stop));
+            (Some(feed), Some(task))
+        }
+        None => (None, None),
+    };
+
+    let sinks = ResultSinks {
+        stream: stream.clone(),
+        progress,
+        feed: feed.clone(),
+        events: progress_stream.clone(),
+        channel: None,
+        memory: Arc::clone(&memory),
+    };
     let step_results = if parallel {
         // Execução paralela usando DAG.
-        let planner = DagPlanner::new(plan.steps);
+        let planner = DagPlanner::new(plan.steps)
+            .with_full_context(full_context)
+            .with_sinks(sinks)
+            .with_adaptive_parallelism(max_parallel == Some(MaxParallel::Auto))
+            .with_stop_signal(Arc::clone(&stop))
+            .with_clock(clock.clone());
         let executors_arc = Arc::new(executors);
         let context_arc = Arc::new(RwLock::new(context));
 
-        planner.execute(executors_arc, context_arc, limits).await
+        planner
+            .execute(executors_arc, context_arc, limits)
+            .instrument(plan_span)
+            .await
     } else {
         // Execução sequencial (comportamento padrão).
-        execute_sequential(plan.steps, executors, context).await
+        execute_sequential(
+            plan.steps,
+            executors,
+            context,
+            clock,
+            full_context,
+            &sinks,
+            &stop,
+        )
+        .instrument(plan_span)
+        .await
+    };
+    deadline_task.abort();
+    if let Some(task) = heartbeat_task {
+        task.abort();
+    }
+
+    // Com --retry-failed, completa o relatório com os resultados anteriores.
+    let step_results = match (previous_report, &all_steps) {
+        (Some(previous), Some(all_steps)) => {
+            rerun::merge_results(all_steps, previous, step_results)
+        }
+        _ => step_results,
+    };
+    let step_results = if filtered_out.is_empty() {
+        step_results
+    } else {
+        selection::merge(&plan_order, step_results, filtered_out)
     };
 
-    let all_passed = step_results.iter().all(|r| r.status == StepStatus::Passed);
+    // Falhas de steps em quarentena são marcadas e não reprovam a execução.
+    let mut step_results = step_results;
+    if let (Some(quarantine), Some(steps)) = (&quarantine, &quarantined_steps) {
+        let marked = quarantine.apply(steps, &mut step_results);
+        if marked > 0 && !silent {
+            info!(
+                quarantined = marked,
+                "Quarantined step failures ignored for status"
+            );
+        }
+    }
+
+    if let Err(e) = workspace.cleanup() {
+        warn!(error = %e, path = ?workspace.root(), "Failed to remove run workspace");
+    }
+    if let Some(plan_lock) = plan_lock {
+        if let Err(e) = plan_lock.release().await {
+            warn!(error = %e, "Failed to release execution lock");
+        }
+    }
+
+    let mut all_passed = step_results.iter().all(|r| r.status.is_success());
+    let run_errors: Vec<protocol::ReportError> = stop.report_error().into_iter().collect();
+    for run_error in &run_errors {
+        eprintln!("❌ [{}] {}", run_error.code, run_error.message);
+    }
 
     let end_time = Utc::now();
     let duration_ms = (end_time - start_time).num_milliseconds() as u64;
@@ -420,10 +1434,70 @@ async fn execute_plan(
         info!("Execution finished");
     }
 
+    // 4.2. Na suíte, guarda as variáveis dos steps `shared` para os próximos planos.
+    if let Some(shared) = &shared {
+        shared.record(&shared_steps, &step_results, execution_id);
+    }
+    if let Some(store) = &step_cache {
+        if let Err(e) = store.record(&cached_steps, &step_results, execution_id) {
+            warn!(error = %e, "Failed to save step cache");
+        }
+    }
+
     // 5. Gera o relatório de execução.
-    let summary = ExecutionSummary::from_results(&step_results, duration_ms);
+    let mut summary = ExecutionSummary::from_results(&step_results, duration_ms);
+    summary.failures = triage::group_failures(&step_results);
+    summary.connections = executors::http_connections::summarize(&step_results);
+
+    // 4.1. Com quality gate, o status final é o resultado do gate.
+    if let Some(gate) = &plan.config.quality_gate {
+        let gate_result = quality_gate::evaluate(gate, &step_results);
+        if !silent {
+            for check in &gate_result.checks {
+                let icon = if check.passed { "✅" } else { "❌" };
+                println!(
+                    "{} Quality gate {}: {} (limite {})",
+                    icon, check.name, check.actual, check.limit
+                );
+            }
+        }
+        all_passed = gate_result.passed;
+        summary.quality_gate = Some(gate_result);
+    }
+    // Execução interrompida (ex: limite de memória) nunca passa, nem pelo gate.
+    all_passed &= run_errors.is_empty();
+    if let (Some(feed), Some(task)) = (&feed, feed_task) {
+        feed.finish(task, all_passed).await;
+    }
+    if let Some(events) = &progress_stream {
+        events.finish(all_passed);
+    }
+    if !summary.failures.is_empty() && !silent {
+        eprint!("{}", triage::render(&summary.failures));
+    }
+    if let Some(slo) = &summary.slo {
+        info!(
+            compliance_pct = slo.compliance_pct,
+            violations = slo.violations.len(),
+            "Latency budget (SLO) compliance"
+        );
+    }
+
+    for result in &mut step_results {
+        result.apply_detail(report_detail);
+    }
+
+    run_warnings.extend(warnings::result_warnings(&step_results));
+    if !run_warnings.is_empty() && !silent {
+        info!(
+            count = run_warnings.len(),
+            "Plan quality warnings recorded in report"
+        );
+    }
 
     let report = ExecutionReport {
+        report_version: REPORT_VERSION.to_string(),
+        report_detail,
         execution_id: execution_id.to_string(),
         plan_id: plan.meta.id.clone(),
         plan_name: plan.meta.name.clone(),
@@ -443,15 +1517,48 @@ async fn execute_plan(
         },
         summary,
         steps: step_results,
+        metadata: if run_metadata.is_empty() {
+            None
+        } else {
+            Some(run_metadata)
+        },
+        retry_of,
+        seed,
+        generator: plan.meta.generator.clone(),
+        warnings: run_warnings,
+        region: region.map(|r| r.name),
+        errors: run_errors,
     };
 
     // 5. Salva ou imprime o relatório.
+    let mut signing_failed = false;
     if let Some(path) = output_path {
-        let json = serde_json::to_string_pretty(&report).expect("Failed to serialize report");
-        if let Err(e) = fs::write(path, json) {
+        if let Err(e) = report_file::write_json(path, &report) {
             eprintln!("❌ Failed to write report: {}", e);
-        } else if !silent {
-            println!("📄 Report saved to: {:?}", path);
+            signing_failed = signer.is_some();
+        } else {
+            // Relatório final salvo: o parcial não é mais necessário.
+            if let Some(stream) = &stream {
+                stream.finish();
+            }
+            if !silent {
+                println!("📄 Report saved to: {:?}", path);
+            }
+
+            // 5.1. Assina o arquivo gravado (--sign-report).
+            if let Some(signer) = &signer {
+                match signer.sign_file(path, execution_id, &report.plan_id) {
+                    Ok(sig_path) => {
+                        if !silent {
+                            println!("🔏 Report signature saved to: {:?}", sig_path);
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("❌ Failed to sign report: {}", e);
+                        signing_failed = true;
+                    }
+                }
+            }
         }
     } else if !silent {
         // Imprime no stdout se nenhum arquivo foi especificado.
@@ -459,9 +1566,307 @@ async fn execute_plan(
         println!("\n--- Execution Report ---\n{}", json);
     }
 
-    // Exit code baseado no resultado
-    if !all_passed {
-        std::process::exit(1);
+    // Exit code baseado no resultado (um relatório sem a assinatura pedida também falha).
+    let exit_code = if stop.timed_out() {
+        ExitCode::from(EXIT_DEADLINE_EXCEEDED)
+    } else if all_passed && !signing_failed {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    };
+    (exit_code, Some(report))
+}
+
+/// Executa o plano em cada região de `--regions` e gera o comparativo.
+///
+/// As regiões rodam uma após a outra, para que a carga de uma não
+/// distorça a latência medida na outra.
+///
+/// ## Retorno:
+/// `ExitCode::FAILURE` se alguma região falhou.
+async fn execute_regions(
+    file_path: &PathBuf,
+    output_path: &Option<PathBuf>,
+    execution_id: &str,
+    options: RunOptions,
+    spec: &str,
+) -> ExitCode {
+    let selected = match loader::load_plan_from_file(file_path)
+        .and_then(|plan| regions::select(&plan.config.regions, spec))
+    {
+        Ok(selected) => selected,
+        Err(e) => {
+            error!("{:#}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let silent = options.silent;
+    let mut failed = false;
+    let mut reports = Vec::new();
+    for region in selected {
+        let name = region.name.clone();
+        let output = output_path
+            .as_ref()
+            .map(|path| regions::region_output(path, &name));
+        let mut region_options = options.clone();
+        region_options.region = Some(region);
+
+        let region_id = format!("{}-{}", execution_id, name);
+        let (exit_code, report) =
+            execute_plan(file_path, &output, &region_id, region_options).await;
+        failed |= exit_code != ExitCode::SUCCESS;
+        match report {
+            Some(report) => reports.push((name, report)),
+            None => return ExitCode::FAILURE,
+        }
+    }
+
+    let comparison = regions::compare(&reports);
+    if !silent {
+        print!("{}", regions::render(&comparison));
+    }
+    if let Some(path) = output_path {
+        let path = regions::comparison_output(path);
+        match report_file::write_json(&path, &comparison) {
+            Ok(()) if !silent => println!("📄 Region comparison saved to: {:?}", path),
+            Ok(()) => {}
+            Err(e) => {
+                eprintln!("❌ Failed to write region comparison: {}", e);
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Executa os planos de uma suíte (vários `--file`), um após o outro.
+///
+/// Cada plano tem seu relatório (`report.<plano>.json`) e execution_id
+/// `<id>-<plano>`; steps `shared` executam só no primeiro plano que os tem.
+///
+/// ## Retorno:
+/// `ExitCode::FAILURE` se algum plano falhou.
+async fn execute_suite(
+    files: &[PathBuf],
+    output_path: &Option<PathBuf>,
+    execution_id: &str,
+    mut options: RunOptions,
+) -> ExitCode {
+    options.shared = Some(suite::SharedSteps::default());
+    let silent = options.silent;
+
+    let mut failed = Vec::new();
+    for file in files {
+        let output = output_path
+            .as_ref()
+            .map(|path| suite::plan_output(path, file));
+        let plan_id = format!(
+            "{}-{}",
+            execution_id,
+            file.file_stem().unwrap_or_default().to_string_lossy()
+        );
+        let (exit_code, _) = execute_plan(file, &output, &plan_id, options.clone()).await;
+        if exit_code != ExitCode::SUCCESS {
+            failed.push(file.display().to_string());
+        }
+    }
+
+    if !silent {
+        println!(
+            "📚 Suite: {}/{} plans passed",
+            files.len() - failed.len(),
+            files.len()
+        );
+        for file in &failed {
+            println!("   ❌ {}", file);
+        }
+    }
+    if failed.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+// ============================================================================
+// COMANDO LINT
+// ============================================================================
+
+/// Carrega o plano, valida e aplica as regras do linter.
+///
+/// ## Retorno:
+/// `ExitCode::FAILURE` se o plano não carrega, não é válido ou tem
+/// achados de regras em `--deny`.
+fn lint_plan_file(file_path: &PathBuf, deny: &[String]) -> ExitCode {
+    let mut denied = HashSet::new();
+    for rule in deny {
+        if rule.eq_ignore_ascii_case("all") {
+            denied.extend(LintRule::ALL.iter().copied());
+            continue;
+        }
+        match LintRule::parse(rule) {
+            Some(rule) => {
+                denied.insert(rule);
+            }
+            None => {
+                eprintln!("❌ Unknown lint rule: {}", rule);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let plan = match loader::load_plan_from_file(file_path) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("❌ Failed to load plan: {:#}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut has_errors = false;
+    if let Err(errors) = validation::validate_plan(&plan) {
+        has_errors = true;
+        for err in &errors {
+            println!("error[validation] {}", err);
+        }
+    }
+
+    let findings = lint::lint_plan(&plan, &denied);
+    for finding in &findings {
+        println!("{}", finding);
+    }
+    has_errors |= findings.iter().any(|f| f.severity == Severity::Error);
+
+    let warnings = findings
+        .iter()
+        .filter(|f| f.severity == Severity::Warning)
+        .count();
+    println!(
+        "{} finding(s): {} warning(s), {} error(s)",
+        findings.len(),
+        warnings,
+        findings.len() - warnings
+    );
+
+    if has_errors {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+// ============================================================================
+// COMANDO EXPLAIN
+// ============================================================================
+
+/// Imprime a ficha de um código de erro (ou a lista de todos os códigos).
+fn explain_error_code(code: Option<&str>) -> ExitCode {
+    let Some(code) = code else {
+        for code in ErrorCode::all() {
+            println!("{}  {:<28} {}", code, code.name(), code.description());
+        }
+        return ExitCode::SUCCESS;
+    };
+
+    match ErrorCode::parse(code) {
+        Some(code) => {
+            print!("{}", code.explain());
+            ExitCode::SUCCESS
+        }
+        None => {
+            eprintln!(
+                "❌ Unknown error code: {} (run `runner explain` to list all codes)",
+                code
+            );
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// ============================================================================
+// COMANDO FUZZ
+// ============================================================================
+
+/// Gera o plano de testes negativos de um step (stdout ou `--output`).
+fn fuzz_plan(file: &Path, step_id: &str, output: Option<&Path>) -> ExitCode {
+    let mut plan = match loader::load_plan_from_file(file) {
+        Ok(plan) => plan,
+        Err(e) => {
+            eprintln!("❌ {:#}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let count = match fuzz::expand_plan(&mut plan, step_id) {
+        Ok(count) => count,
+        Err(e) => {
+            eprintln!("❌ {:#}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let json = serde_json::to_string_pretty(&plan).expect("plano serializável");
+    match output {
+        Some(path) => match output::write_file(path, json) {
+            Ok(()) => {
+                eprintln!(
+                    "🧪 {} steps negativos de '{}' em {:?}",
+                    count, step_id, path
+                );
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("❌ {:#}", e);
+                ExitCode::FAILURE
+            }
+        },
+        None => {
+            println!("{}", json);
+            ExitCode::SUCCESS
+        }
+    }
+}
+
+// ============================================================================
+// COMANDO SUGGEST
+// ============================================================================
+
+/// Gera o patch de sugestões de um relatório (stdout ou `--output`).
+fn suggest_from_report(report: &Path, output: Option<&Path>) -> ExitCode {
+    let patch = match suggest::from_report(report) {
+        Ok(patch) => patch,
+        Err(e) => {
+            eprintln!("❌ {:#}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if patch.steps.iter().any(|s| !s.body_recorded) {
+        eprintln!(
+            "⚠️  Alguns steps não têm body no relatório: só o status foi sugerido (use --report-detail full)"
+        );
+    }
+
+    let json = serde_json::to_string_pretty(&patch).expect("patch serializável");
+    match output {
+        Some(path) => match output::write_file(path, json) {
+            Ok(()) => {
+                eprintln!("💡 {} steps com sugestões em {:?}", patch.steps.len(), path);
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("❌ {:#}", e);
+                ExitCode::FAILURE
+            }
+        },
+        None => {
+            println!("{}", json);
+            ExitCode::SUCCESS
+        }
     }
 }
 
@@ -478,6 +1883,11 @@ async fn execute_plan(
 /// - `steps`: Lista de steps a executar
 /// - `executors`: Lista de executores disponíveis
 /// - `context`: Contexto de execução (variáveis)
+/// - `clock`: Relógio usado para o backoff entre retries
+/// - `full_context`: Mantém os snapshots completos (senão, só o delta)
+/// - `sinks`: Destinos de cada resultado (relatório parcial, heartbeat, eventos)
+/// - `stop`: Motivo de parada (memória, abort do Brain, prazo); disparado, os
+///   steps restantes são cancelados (o prazo também interrompe o step atual)
 ///
 /// ## Retorno:
 /// Vetor com os resultados de cada step.
@@ -485,17 +1895,52 @@ async fn execute_sequential(
     steps: Vec<Step>,
     executors: Vec<Box<dyn StepExecutor + Send + Sync>>,
     mut context: Context,
+    clock: SharedClock,
+    full_context: bool,
+    sinks: &ResultSinks,
+    stop: &StopSignal,
 ) -> Vec<protocol::StepResult> {
     let mut step_results = Vec::new();
 
     for step in steps {
+        // Execução parada (memória, abort, prazo): os steps restantes não rodam.
+        if let Some(result) = stop.cancelled_result(&step.id) {
+            sinks.record(&result);
+            step_results.push(result);
+            continue;
+        }
+
         info!(step_id = %step.id, action = %step.action, "Running step");
 
         // Encontra um executor que saiba lidar com esta action.
-        let executor = executors.iter().find(|e| e.can_handle(&step.action));
+        let executor = executors.iter().find(|e| e.can_handle_step(&step));
+        let started_at = Utc::now();
+        if let Some(events) = &sinks.events {
+            events.step_started(&step);
+        }
 
-        let result = match executor {
-            Some(exec) => execute_step_with_retry(&step, exec.as_ref(), &mut context).await,
+        let mut result = match executor {
+            Some(exec) => {
+                let context_before = context.variables.clone();
+                // Prazo vencido no meio do step (ou das tentativas): ele é interrompido.
+                let outcome = stop
+                    .within_deadline(
+                        execute_step_with_retry(
+                            &step,
+                            exec.as_ref(),
+                            &mut context,
+                            clock.as_ref(),
+                            sinks.events.as_deref(),
+                        )
+                        .instrument(step_span(&step)),
+                    )
+                    .await;
+                outcome.unwrap_or_else(|| protocol::StepResult {
+                    context_before: Some(context_before),
+                    context_after: Some(context.variables.clone()),
+                    ..stop.interrupted_result(&step.id)
+                })
+            }
             None => {
                 error!(step_id = %step.id, action = %step.action, "No executor found for action");
                 // Captura contexto para debug
@@ -510,17 +1955,63 @@ async fn execute_sequential(
                     context_after: Some(context_snapshot),
                     extractions: None,
                     http_details: None,
+                    ..Default::default()
                 }
             }
         };
 
+        // Sequencial: um único worker, sem disputa pelo contexto.
+        result.timeline = Some(timeline::stamp(started_at, 0, 0));
+
         info!(step_id = %step.id, status = ?result.status, duration_ms = result.duration_ms, "Step finished");
+        if !full_context {
+            result.compact_context();
+        }
+        sinks.memory.record_context(&context.variables);
+        sinks.record(&result);
         step_results.push(result);
     }
 
     step_results
 }
 
+// ============================================================================
+// COMANDO TIMELINE
+// ============================================================================
+
+/// `runner timeline`: renderiza a linha do tempo do relatório.
+///
+/// Grava em `--output` (criando as pastas pai) ou imprime no stdout.
+fn render_timeline(
+    report: &Path,
+    format: timeline::TimelineFormat,
+    output: Option<&Path>,
+) -> ExitCode {
+    let rendered = match timeline::from_report(report, format) {
+        Ok(rendered) => rendered,
+        Err(e) => {
+            eprintln!("❌ {:#}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    match output {
+        Some(path) => match output::write_file(path, rendered) {
+            Ok(()) => {
+                eprintln!("📊 Linha do tempo salva em {:?}", path);
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("❌ {:#}", e);
+                ExitCode::FAILURE
+            }
+        },
+        None => {
+            print!("{}", rendered);
+            ExitCode::SUCCESS
+        }
+    }
+}
+
 // ============================================================================
 // EXECUÇÃO COM RETRY
 // ============================================================================
@@ -541,10 +2032,16 @@ async fn execute_sequential(
 /// - Tentativa 3: falha → espera backoff_ms × backoff_factor²
 ///
 /// Exemplo: backoff_ms=500, backoff_factor=2.0 → 500ms, 1000ms, 2000ms...
+///
+/// O backoff é aguardado através do `clock`, então `--fast-wait` também
+/// acelera os retries. Cada nova tentativa vira um evento `retry` em
+/// `--progress-stream`.
 async fn execute_step_with_retry(
     step: &Step,
     executor: &dyn StepExecutor,
     context: &mut Context,
+    clock: &dyn Clock,
+    events: Option<&ProgressStream>,
 ) -> protocol::StepResult {
     // Extrai configurações de retry da RecoveryPolicy.
     let max_attempts = step
@@ -552,11 +2049,13 @@ async fn execute_step_with_retry(
         .as_ref()
         .map(|p| p.max_attempts)
         .unwrap_or(1);
+    // A validação aceita a strategy sem diferenciar maiúsculas; o enum
+    // normaliza uma vez para o loop não comparar strings.
     let strategy = step
         .recovery_policy
         .as_ref()
-        .map(|p| p.strategy.as_str())
-        .unwrap_or("fail_fast");
+        .map(|p| retry::RecoveryStrategy::parse(&p.strategy))
+        .unwrap_or(retry::RecoveryStrategy::FailFast);
     let backoff_ms = step
         .recovery_policy
         .as_ref()
@@ -569,6 +2068,7 @@ async fn execute_step_with_retry(
         .unwrap_or(2.0);
 
     let mut attempt = 0u32;
+    let mut backoff_applied = 0u64;
 
     loop {
         attempt += 1;
@@ -576,70 +2076,69 @@ async fn execute_step_with_retry(
         // Snapshot do contexto antes da execução
         let context_before = context.variables.clone();
 
-        match executor.execute(step, context).await {
-            Ok(result) => {
-                if result.status == StepStatus::Passed {
-                    return result;
-                }
-                // Assertion falhou.
-
-                if strategy == "ignore" {
-                    return protocol::StepResult {
-                        step_id: step.id.clone(),
-                        status: StepStatus::Passed, // Ignora falha
-                        duration_ms: result.duration_ms,
-                        attempt,
-                        error: None,
-                        context_before: result.context_before,
-                        context_after: result.context_after,
-                        extractions: result.extractions,
-                        http_details: result.http_details,
-                    };
-                }
-
-                if strategy != "retry" || attempt >= max_attempts {
-                    return result;
-                }
+        // Com retry, cada tentativa vira um span filho do step.
+        let span = if strategy == retry::RecoveryStrategy::Retry {
+            attempt_span(step, attempt, backoff_applied)
+        } else {
+            tracing::Span::none()
+        };
+        let outcome = foreach::execute_step(step, executor, context, clock)
+            .instrument(span.clone())
+            .await;
+        let attempt_error = match &outcome {
+            Ok(result) if !matches!(result.status, StepStatus::Passed | StepStatus::NotRun) => {
+                let error = result.error.as_deref().unwrap_or("failed");
+                span.record("retry.error", error);
+                Some(error.to_string())
             }
             Err(e) => {
-                error!(step_id = %step.id, error = %e, attempt = attempt, "Step execution failed");
-
-                // Captura contexto após erro para debug
-                let context_after = context.variables.clone();
-
-                if strategy == "ignore" {
-                    return protocol::StepResult {
-                        step_id: step.id.clone(),
-                        status: StepStatus::Passed,
-                        duration_ms: 0,
-                        attempt,
-                        error: None,
-                        context_before: Some(context_before),
-                        context_after: Some(context_after),
-                        extractions: None,
-                        http_details: None,
-                    };
-                }
+                let error = e.to_string();
+                span.record("retry.error", error.as_str());
+                Some(error)
+            }
+            Ok(_) => None,
+        };
 
-                if strategy != "retry" || attempt >= max_attempts {
-                    return protocol::StepResult {
-                        step_id: step.id.clone(),
-                        status: StepStatus::Failed,
-                        duration_ms: 0,
-                        attempt,
-                        error: Some(e.to_string()),
-                        context_before: Some(context_before),
-                        context_after: Some(context_after),
-                        extractions: None,
-                        http_details: None,
-                    };
-                }
+        // Erro inesperado vira um resultado `failed` com os snapshots do contexto.
+        let mut result = outcome.unwrap_or_else(|e| {
+            error!(step_id = %step.id, error = %e, attempt = attempt, "Step execution failed");
+            protocol::StepResult {
+                step_id: step.id.clone(),
+                status: StepStatus::Failed,
+                attempt,
+                error: Some(e.to_string()),
+                context_before: Some(context_before),
+                context_after: Some(context.variables.clone()),
+                ..Default::default()
             }
+        });
+        // Condição falsa (`not_run`) não é falha: nada a repetir nem ignorar.
+        if matches!(result.status, StepStatus::Passed | StepStatus::NotRun) {
+            return result;
+        }
+
+        if strategy == retry::RecoveryStrategy::Ignore {
+            // Ignora a falha, mantendo o resto do resultado (timeline, artefatos...).
+            return protocol::StepResult {
+                status: StepStatus::Passed,
+                ignored_error: result.error.take(),
+                error: None,
+                attempt,
+                ..result
+            };
+        }
+
+        if strategy != retry::RecoveryStrategy::Retry || attempt >= max_attempts {
+            return result;
         }
 
         // Calcula backoff exponencial e aguarda.
         let backoff = (backoff_ms as f64 * backoff_factor.powi(attempt as i32 - 1)) as u64;
         info!(step_id = %step.id, attempt = attempt, max_attempts = max_attempts, backoff_ms = backoff, "Retrying after backoff");
-        tokio::time::sleep(std::time::Duration::from_millis(backoff)).await;
+        if let Some(events) = events {
+            events.retry(&step.id, attempt, backoff, attempt_error.as_deref());
+        }
+        clock.sleep(std::time::Duration::from_millis(backoff)).await;
+        backoff_applied = backoff;
     }
 }
@@ -0,0 +1,184 @@
+//! # Módulo de Memória - Limite de Dados Retidos na Execução
+//!
+//! Estima quanto a execução está guardando (resultados, bodies capturados,
+//! contexto) e interrompe o plano de forma ordenada ao passar de
+//! `RUNNER_MAX_MEMORY_MB`.
+//!
+//! ## Para todos entenderem:
+//!
+//! Um plano que baixa um arquivo de 200 MB em 50 iterações não falha com
+//! uma mensagem: o processo é morto pelo sistema (OOM) no meio da execução
+//! e nenhum relatório é gravado. Com o limite:
+//!
+//! ```bash
+//! RUNNER_MAX_MEMORY_MB=512 runner execute --file plan.json --output report.json
+//! ```
+//!
+//! | Situação                              | Comportamento                        |
+//! |---------------------------------------|--------------------------------------|
+//! | Abaixo do limite                      | Nada muda                            |
+//! | Passou do limite após um step         | Steps em andamento terminam          |
+//! | Steps que ainda não começaram         | `cancelled` (motivo `cancelled`)     |
+//! | Relatório                             | Gravado, com o erro E5004 em `errors` |
+//!
+//! ## Detalhe:
+//!
+//! A estimativa é o tamanho em JSON do que fica retido (cada resultado,
+//! com os bodies ainda não podados por `--report-detail`, mais o contexto
+//! atual). Não é a memória do processo, mas cresce junto com ela e é
+//! estável entre execuções. Sem a variável, nada é medido.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::cancel::StopSignal;
+use crate::errors::ErrorCode;
+use crate::protocol::StepResult;
+
+/// Bytes em um MB (para mensagens e `RUNNER_MAX_MEMORY_MB`).
+const MB: u64 = 1024 * 1024;
+
+/// Conta os bytes escritos sem guardá-los.
+struct ByteCounter(u64);
+
+impl io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Tamanho aproximado de um valor retido (seu JSON).
+pub fn estimate<T: Serialize + ?Sized>(value: &T) -> u64 {
+    let mut counter = ByteCounter(0);
+    let _ = serde_json::to_writer(&mut counter, value);
+    counter.0
+}
+
+// ============================================================================
+// GUARDA DE MEMÓRIA
+// ============================================================================
+
+/// Limite de dados retidos, compartilhado entre os steps da execução.
+#[derive(Debug, Default)]
+pub struct MemoryGuard {
+    limit_bytes: Option<u64>,
+    results_bytes: AtomicU64,
+    context_bytes: AtomicU64,
+    tripped: AtomicBool,
+    stop: Arc<StopSignal>,
+}
+
+impl MemoryGuard {
+    /// Guarda com o limite em bytes (`None` = sem limite, nada é medido).
+    ///
+    /// Ao estourar, dispara `stop` com E5004.
+    pub fn new(limit_bytes: Option<u64>, stop: Arc<StopSignal>) -> Arc<Self> {
+        Arc::new(Self {
+            limit_bytes,
+            stop,
+            ..Default::default()
+        })
+    }
+
+    /// Guarda sem limite.
+    pub fn disabled() -> Arc<Self> {
+        Self::new(None, StopSignal::new())
+    }
+
+    /// Soma um resultado retido e verifica o limite.
+    pub fn record_result(&self, result: &StepResult) {
+        if self.limit_bytes.is_some() {
+            self.results_bytes
+                .fetch_add(estimate(result), Ordering::Relaxed);
+            self.check();
+        }
+    }
+
+    /// Atualiza o tamanho do contexto (substitui o anterior) e verifica o limite.
+    pub fn record_context(&self, variables: &HashMap<String, Value>) {
+        if self.limit_bytes.is_some() {
+            self.context_bytes
+                .store(estimate(variables), Ordering::Relaxed);
+            self.check();
+        }
+    }
+
+    /// Estimativa atual dos dados retidos.
+    pub fn retained_bytes(&self) -> u64 {
+        self.results_bytes.load(Ordering::Relaxed) + self.context_bytes.load(Ordering::Relaxed)
+    }
+
+    fn check(&self) {
+        let Some(limit) = self.limit_bytes else {
+            return;
+        };
+        let retained = self.retained_bytes();
+        if retained <= limit {
+            return;
+        }
+        if !self.tripped.swap(true, Ordering::Relaxed) {
+            self.stop.stop(
+                ErrorCode::MEMORY_LIMIT_EXCEEDED,
+                format!(
+                    "Limite de memória excedido: ~{} MB retidos (RUNNER_MAX_MEMORY_MB={}); execução interrompida",
+                    retained.div_ceil(MB),
+                    limit / MB
+                ),
+            );
+        }
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn result_with_body(size: usize) -> StepResult {
+        StepResult {
+            step_id: "download".to_string(),
+            logs: Some(vec!["x".repeat(size)]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_guard_trips_over_limit_and_cancels() {
+        let stop = StopSignal::new();
+        let guard = MemoryGuard::new(Some(MB), Arc::clone(&stop));
+        guard.record_result(&result_with_body(600 * 1024));
+        assert!(stop.cancelled_result("next").is_none());
+
+        let mut context = HashMap::new();
+        context.insert("blob".to_string(), json!("y".repeat(600 * 1024)));
+        guard.record_context(&context);
+
+        let (_, message) = stop.reason().unwrap();
+        assert!(message.contains("~2 MB retidos (RUNNER_MAX_MEMORY_MB=1)"));
+        let cancelled = stop.cancelled_result("next").unwrap();
+        assert_eq!(cancelled.status, crate::protocol::StepStatus::Cancelled);
+        assert!(cancelled.error.unwrap().starts_with("[E5004]"));
+        assert_eq!(stop.report_error().unwrap().code, "E5004");
+    }
+
+    #[test]
+    fn test_disabled_guard_measures_nothing() {
+        let guard = MemoryGuard::disabled();
+        guard.record_result(&result_with_body(4 * 1024 * 1024));
+        assert_eq!(guard.retained_bytes(), 0);
+        assert!(!guard.tripped.load(Ordering::Relaxed));
+    }
+}
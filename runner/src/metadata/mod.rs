@@ -0,0 +1,289 @@
+//! # Módulo de Metadados - Rastreabilidade CI/Git
+//!
+//! Coleta informações sobre **de onde veio** a execução (commit, branch,
+//! pipeline, autor) para incluir no relatório.
+//!
+//! ## Para todos entenderem:
+//!
+//! Um relatório dizendo "3 testes falharam" é pouco útil se não sabemos
+//! qual mudança de código disparou a execução. Este módulo lê as variáveis
+//! de ambiente que os serviços de CI já definem e anota o relatório com
+//! o commit, a branch e o link do pipeline.
+//!
+//! ## Fontes detectadas:
+//!
+//! | Provedor        | Variável de detecção |
+//! |-----------------|----------------------|
+//! | GitHub Actions  | `GITHUB_ACTIONS`     |
+//! | GitLab CI       | `GITLAB_CI`          |
+//! | CircleCI        | `CIRCLECI`           |
+//! | Azure Pipelines | `TF_BUILD`           |
+//! | Jenkins         | `JENKINS_URL`        |
+//! | Genérico        | `CI`                 |
+//!
+//! Fora de um CI, commit e branch são lidos do `git` local (se disponível).
+//!
+//! ## Metadados customizados:
+//!
+//! ```bash
+//! runner execute --file plano.json --meta release=1.4.0 --meta ticket=QA-42
+//! ```
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::process::Command;
+
+// ============================================================================
+// ESTRUTURA DE METADADOS
+// ============================================================================
+
+/// Metadados da execução incluídos no relatório (`metadata`).
+///
+/// Todos os campos são opcionais e omitidos do JSON quando ausentes.
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct RunMetadata {
+    /// Provedor de CI detectado (ex: "github_actions", "gitlab").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ci_provider: Option<String>,
+
+    /// SHA do commit que disparou a execução.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_sha: Option<String>,
+
+    /// Branch (ou ref) do commit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+
+    /// Link para o pipeline/job no CI.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pipeline_url: Option<String>,
+
+    /// Usuário que disparou o pipeline.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+
+    /// Pares chave/valor informados via `--meta key=value`.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom: BTreeMap<String, String>,
+}
+
+impl RunMetadata {
+    /// Coleta metadados do ambiente atual e adiciona os pares customizados.
+    ///
+    /// Se nenhum CI for detectado, tenta o repositório git local.
+    pub fn collect(custom: &[(String, String)]) -> Self {
+        let mut metadata =
+            Self::detect_with(|name| std::env::var(name).ok().filter(|v| !v.trim().is_empty()));
+
+        if metadata.commit_sha.is_none() {
+            metadata.commit_sha = git_output(&["rev-parse", "HEAD"]);
+        }
+        if metadata.branch.is_none() {
+            metadata.branch =
+                git_output(&["rev-parse", "--abbrev-ref", "HEAD"]).filter(|b| b != "HEAD");
+        }
+
+        metadata.custom.extend(custom.iter().cloned());
+        metadata
+    }
+
+    /// Detecta metadados de CI usando a função de leitura informada.
+    ///
+    /// Recebe a função em vez de ler `std::env` diretamente para facilitar testes.
+    pub fn detect_with<F>(get: F) -> Self
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        if get("GITHUB_ACTIONS").is_some() {
+            let pipeline_url = match (
+                get("GITHUB_SERVER_URL"),
+                get("GITHUB_REPOSITORY"),
+                get("GITHUB_RUN_ID"),
+            ) {
+                (Some(server), Some(repo), Some(run)) => {
+                    Some(format!("{}/{}/actions/runs/{}", server, repo, run))
+                }
+                _ => None,
+            };
+            return Self {
+                ci_provider: Some("github_actions".to_string()),
+                commit_sha: get("GITHUB_SHA"),
+                branch: get("GITHUB_HEAD_REF").or_else(|| get("GITHUB_REF_NAME")),
+                pipeline_url,
+                actor: get("GITHUB_ACTOR"),
+                ..Default::default()
+            };
+        }
+
+        if get("GITLAB_CI").is_some() {
+            return Self {
+                ci_provider: Some("gitlab".to_string()),
+                commit_sha: get("CI_COMMIT_SHA"),
+                branch: get("CI_COMMIT_REF_NAME"),
+                pipeline_url: get("CI_PIPELINE_URL"),
+                actor: get("GITLAB_USER_LOGIN"),
+                ..Default::default()
+            };
+        }
+
+        if get("CIRCLECI").is_some() {
+            return Self {
+                ci_provider: Some("circleci".to_string()),
+                commit_sha: get("CIRCLE_SHA1"),
+                branch: get("CIRCLE_BRANCH"),
+                pipeline_url: get("CIRCLE_BUILD_URL"),
+                actor: get("CIRCLE_USERNAME"),
+                ..Default::default()
+            };
+        }
+
+        if get("TF_BUILD").is_some() {
+            let pipeline_url = match (
+                get("SYSTEM_COLLECTIONURI"),
+                get("SYSTEM_TEAMPROJECT"),
+                get("BUILD_BUILDID"),
+            ) {
+                (Some(uri), Some(project), Some(build)) => Some(format!(
+                    "{}{}/_build/results?buildId={}",
+                    uri, project, build
+                )),
+                _ => None,
+            };
+            return Self {
+                ci_provider: Some("azure_pipelines".to_string()),
+                commit_sha: get("BUILD_SOURCEVERSION"),
+                branch: get("BUILD_SOURCEBRANCHNAME"),
+                pipeline_url,
+                actor: get("BUILD_REQUESTEDFOR"),
+                ..Default::default()
+            };
+        }
+
+        if get("JENKINS_URL").is_some() {
+            return Self {
+                ci_provider: Some("jenkins".to_string()),
+                commit_sha: get("GIT_COMMIT"),
+                branch: get("BRANCH_NAME").or_else(|| get("GIT_BRANCH")),
+                pipeline_url: get("BUILD_URL"),
+                actor: get("BUILD_USER_ID"),
+                ..Default::default()
+            };
+        }
+
+        if get("CI").is_some() {
+            return Self {
+                ci_provider: Some("generic".to_string()),
+                commit_sha: get("GIT_COMMIT"),
+                branch: get("GIT_BRANCH"),
+                ..Default::default()
+            };
+        }
+
+        Self::default()
+    }
+
+    /// Retorna `true` se nenhum metadado foi coletado.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+// ============================================================================
+// FUNÇÕES AUXILIARES
+// ============================================================================
+
+/// Executa um comando `git` e retorna a saída (ou `None` em caso de erro).
+fn git_output(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let value = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// Parseia um argumento `key=value` da CLI (`--meta`).
+pub fn parse_key_value(raw: &str) -> Result<(String, String), String> {
+    match raw.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!("formato inválido '{}': use chave=valor", raw)),
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| map.get(name).cloned()
+    }
+
+    #[test]
+    fn test_detect_github_actions() {
+        let metadata = RunMetadata::detect_with(env(&[
+            ("GITHUB_ACTIONS", "true"),
+            ("GITHUB_SHA", "abc123"),
+            ("GITHUB_REF_NAME", "main"),
+            ("GITHUB_SERVER_URL", "https://github.com"),
+            ("GITHUB_REPOSITORY", "org/repo"),
+            ("GITHUB_RUN_ID", "42"),
+            ("GITHUB_ACTOR", "octocat"),
+        ]));
+
+        assert_eq!(metadata.ci_provider.as_deref(), Some("github_actions"));
+        assert_eq!(metadata.commit_sha.as_deref(), Some("abc123"));
+        assert_eq!(metadata.branch.as_deref(), Some("main"));
+        assert_eq!(
+            metadata.pipeline_url.as_deref(),
+            Some("https://github.com/org/repo/actions/runs/42")
+        );
+        assert_eq!(metadata.actor.as_deref(), Some("octocat"));
+    }
+
+    #[test]
+    fn test_detect_gitlab() {
+        let metadata = RunMetadata::detect_with(env(&[
+            ("GITLAB_CI", "true"),
+            ("CI", "true"),
+            ("CI_COMMIT_SHA", "def456"),
+            ("CI_PIPELINE_URL", "https://gitlab.com/p/1"),
+        ]));
+
+        assert_eq!(metadata.ci_provider.as_deref(), Some("gitlab"));
+        assert_eq!(metadata.commit_sha.as_deref(), Some("def456"));
+        assert_eq!(
+            metadata.pipeline_url.as_deref(),
+            Some("https://gitlab.com/p/1")
+        );
+    }
+
+    #[test]
+    fn test_detect_without_ci_is_empty() {
+        assert!(RunMetadata::detect_with(env(&[])).is_empty());
+    }
+
+    #[test]
+    fn test_parse_key_value() {
+        assert_eq!(
+            parse_key_value("release=1.4.0=rc").unwrap(),
+            ("release".to_string(), "1.4.0=rc".to_string())
+        );
+        assert!(parse_key_value("sem_igual").is_err());
+        assert!(parse_key_value("=valor").is_err());
+    }
+}
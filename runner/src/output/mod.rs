@@ -0,0 +1,179 @@
+//! # Módulo de Saída - Gravação Segura de Relatórios e Artefatos
+//!
+//! Centraliza a criação de arquivos do Runner (relatório, parcial,
+//! assinatura, artefatos por step) para que funcione igual em Linux,
+//! macOS e Windows.
+//!
+//! ## Para todos entenderem:
+//!
+//! `fs::write("reports/2024/run.json", ...)` falha com "No such file or
+//! directory" se a pasta `reports/2024` não existe, e a mensagem nem diz
+//! qual caminho. Além disso, um step chamado `get /users?id=1` não pode
+//! virar nome de arquivo no Windows (`/`, `?` são proibidos).
+//!
+//! | Problema                         | O que este módulo faz                        |
+//! |----------------------------------|----------------------------------------------|
+//! | Pasta de destino não existe      | Cria as pastas pai (`create_dir_all`)        |
+//! | Erro sem contexto                | Mensagem com o caminho e a operação          |
+//! | Caracteres proibidos no nome     | Troca `<>:"/\|?*` e controles por `_`        |
+//! | Nomes reservados (`CON`, `NUL`)  | Prefixa com `_`                              |
+//! | Nomes longos                     | Corta e adiciona hash curto (sem colisão)    |
+//! | Caminhos > 260 chars no Windows  | Usa o prefixo `\\?\` em caminhos absolutos   |
+
+use anyhow::{Context as _, Result};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+/// Tamanho máximo (bytes) do nome de arquivo gerado a partir de um step ID.
+const MAX_FILE_STEM_BYTES: usize = 100;
+
+/// Nomes de dispositivo reservados no Windows (sem diferenciar maiúsculas).
+const WINDOWS_RESERVED: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+// ============================================================================
+// CRIAÇÃO DE ARQUIVOS
+// ============================================================================
+
+/// Cria as pastas pai de `path` e devolve o caminho pronto para uso.
+///
+/// No Windows, caminhos absolutos longos recebem o prefixo `\\?\`.
+pub fn prepare_path(path: &Path) -> Result<PathBuf> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(long_path(parent))
+            .with_context(|| format!("Falha ao criar diretório {:?}", parent))?;
+    }
+    Ok(long_path(path))
+}
+
+/// Cria (ou trunca) um arquivo, criando as pastas pai se necessário.
+pub fn create_file(path: &Path) -> Result<File> {
+    let prepared = prepare_path(path)?;
+    File::create(&prepared).with_context(|| format!("Falha ao criar arquivo {:?}", path))
+}
+
+/// Grava `contents` em `path`, criando as pastas pai se necessário.
+pub fn write_file(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    let prepared = prepare_path(path)?;
+    std::fs::write(&prepared, contents).with_context(|| format!("Falha ao gravar {:?}", path))
+}
+
+// ============================================================================
+// NOMES DE ARQUIVO
+// ============================================================================
+
+/// Converte um step ID em um nome de arquivo válido em qualquer sistema.
+///
+/// Se o nome precisou mudar, um hash curto do ID original é adicionado
+/// para que IDs diferentes (`a/b` e `a_b`) não gerem o mesmo arquivo.
+pub fn sanitize_file_name(id: &str) -> String {
+    let mut name: String = id
+        .chars()
+        .map(|c| {
+            if c.is_control() || matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*') {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+
+    // Windows ignora pontos e espaços no final do nome.
+    let trimmed_len = name.trim_end_matches(['.', ' ']).len();
+    name.truncate(trimmed_len);
+    if name.is_empty() {
+        name.push('_');
+    }
+
+    let stem = name.split('.').next().unwrap_or("");
+    if WINDOWS_RESERVED
+        .iter()
+        .any(|r| r.eq_ignore_ascii_case(stem))
+    {
+        name.insert(0, '_');
+    }
+
+    let changed = name != id;
+    if name.len() > MAX_FILE_STEM_BYTES {
+        let mut cut = MAX_FILE_STEM_BYTES;
+        while !name.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        name.truncate(cut);
+    }
+    if changed || name.len() < id.len() {
+        let digest = Sha256::digest(id.as_bytes());
+        name.push_str(&format!(
+            "-{:02x}{:02x}{:02x}{:02x}",
+            digest[0], digest[1], digest[2], digest[3]
+        ));
+    }
+    name
+}
+
+/// Prefixo `\\?\` para caminhos absolutos longos no Windows (no-op nos demais).
+#[cfg(windows)]
+fn long_path(path: &Path) -> PathBuf {
+    const MAX_PATH: usize = 260;
+    let Ok(absolute) = std::path::absolute(path) else {
+        return path.to_path_buf();
+    };
+    let text = absolute.as_os_str().to_string_lossy();
+    if text.len() < MAX_PATH || text.starts_with(r"\\?\") {
+        return path.to_path_buf();
+    }
+    match text.strip_prefix(r"\\") {
+        // Compartilhamento de rede: \\server\share → \\?\UNC\server\share
+        Some(unc) => PathBuf::from(format!(r"\\?\UNC\{}", unc)),
+        None => PathBuf::from(format!(r"\\?\{}", text)),
+    }
+}
+
+#[cfg(not(windows))]
+fn long_path(path: &Path) -> PathBuf {
+    path.to_path_buf()
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_keeps_safe_ids() {
+        assert_eq!(sanitize_file_name("create_user"), "create_user");
+        assert_eq!(sanitize_file_name("login-v2.retry"), "login-v2.retry");
+    }
+
+    #[test]
+    fn test_sanitize_unsafe_ids_without_collisions() {
+        let slash = sanitize_file_name("get /users?id=1");
+        assert!(slash.starts_with("get _users_id=1-"));
+        assert_ne!(sanitize_file_name("a/b"), sanitize_file_name("a_b"));
+
+        assert!(sanitize_file_name("con").starts_with("_con-"));
+        assert!(sanitize_file_name("NUL.json").starts_with("_NUL.json-"));
+        assert!(sanitize_file_name("step. ").starts_with("step-"));
+
+        let long = sanitize_file_name(&"é".repeat(80));
+        assert!(long.len() <= MAX_FILE_STEM_BYTES + 9);
+    }
+
+    #[test]
+    fn test_write_file_creates_parent_directories() {
+        let dir = std::env::temp_dir().join(format!("aqa-output-{}", std::process::id()));
+        let path = dir.join("nested/deeper/report.json");
+
+        write_file(&path, b"{}").unwrap();
+        let content = std::fs::read(&path).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(content, b"{}");
+    }
+}
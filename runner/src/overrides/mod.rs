@@ -0,0 +1,101 @@
+//! # Módulo de Overrides - Variáveis Injetadas na Execução
+//!
+//! Lê `--var chave=valor` (repetível) e `--vars-file vars.json` do comando
+//! `execute` e sobrepõe `config.variables` do plano, sem editar o arquivo.
+//!
+//! ## Para todos entenderem:
+//!
+//! O mesmo plano roda em staging com um usuário e em produção com outro.
+//! Em vez de manter duas cópias do plano:
+//!
+//! ```bash
+//! runner execute --file plan.json --vars-file staging.json --var user_id=42
+//! ```
+//!
+//! ## Precedência (a última vence):
+//!
+//! | Origem              | Exemplo                          |
+//! |---------------------|----------------------------------|
+//! | `config.variables`  | Definidas no plano               |
+//! | Região (`--regions`)| `config.regions[].variables`     |
+//! | `--vars-file`       | `{"env": "staging"}`             |
+//! | `--var`             | `--var env=prod`                 |
+//!
+//! O valor de `--var` é lido como JSON quando possível (`--var limit=10`
+//! vira número, `--var tags='["a","b"]'` vira array); senão, fica como
+//! texto (`--var env=prod` é a string `"prod"`). As variáveis entram no
+//! contexto antes do primeiro step, então `${env}` nos steps e nas
+//! `config.variables` já enxerga o valor sobreposto.
+
+use anyhow::{bail, Context as _, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::metadata::parse_key_value;
+
+/// Parseia um argumento `chave=valor` de `--var` (valor JSON ou texto).
+pub fn parse_var(raw: &str) -> Result<(String, Value), String> {
+    let (key, value) = parse_key_value(raw)?;
+    let value = serde_json::from_str(&value).unwrap_or(Value::String(value));
+    Ok((key, value))
+}
+
+/// Junta `--vars-file` e os `--var` (estes têm prioridade).
+pub fn load(vars_file: Option<&Path>, vars: &[(String, Value)]) -> Result<HashMap<String, Value>> {
+    let mut overrides = HashMap::new();
+    if let Some(path) = vars_file {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Falha ao ler --vars-file {:?}", path))?;
+        let file: Value = serde_json::from_str(&content)
+            .with_context(|| format!("--vars-file {:?} não é um JSON válido", path))?;
+        let Value::Object(entries) = file else {
+            bail!(
+                "--vars-file {:?} deve ser um objeto JSON (nome → valor)",
+                path
+            );
+        };
+        overrides.extend(entries);
+    }
+    overrides.extend(vars.iter().cloned());
+    Ok(overrides)
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_var_reads_json_or_text() {
+        assert_eq!(parse_var("limit=10").unwrap(), ("limit".into(), json!(10)));
+        assert_eq!(parse_var("debug=true").unwrap().1, json!(true));
+        assert_eq!(
+            parse_var("tags=[\"a\",\"b\"]").unwrap().1,
+            json!(["a", "b"])
+        );
+        assert_eq!(parse_var("env=prod").unwrap().1, json!("prod"));
+        assert_eq!(parse_var("query=a=b").unwrap().1, json!("a=b"));
+        assert_eq!(parse_var("empty=").unwrap().1, json!(""));
+        assert!(parse_var("no_equals").is_err());
+    }
+
+    #[test]
+    fn test_cli_vars_override_vars_file() {
+        let path = std::env::temp_dir().join(format!("aqa-vars-{}.json", std::process::id()));
+        std::fs::write(&path, r#"{"env": "staging", "user_id": 7}"#).unwrap();
+
+        let overrides = load(Some(&path), &[("env".into(), json!("prod"))]).unwrap();
+        assert_eq!(overrides["env"], json!("prod"));
+        assert_eq!(overrides["user_id"], json!(7));
+
+        std::fs::write(&path, "[1, 2]").unwrap();
+        let error = load(Some(&path), &[]).unwrap_err().to_string();
+        assert!(error.contains("deve ser um objeto JSON"));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
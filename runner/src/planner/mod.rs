@@ -45,16 +45,29 @@
 //!         [E]         <- Depende de C e D (só roda quando ambos terminam)
 //! ```
 
+use chrono::Utc;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use tokio::sync::{Mutex, RwLock, Semaphore};
+use std::time::Instant;
+use tokio::sync::{mpsc, Mutex, RwLock};
 use tokio::task::JoinSet;
-use tracing::{error, info, instrument};
+use tracing::{error, info, instrument, warn, Instrument};
 
+use crate::cancel::{PauseGate, StopSignal};
+use crate::clock::{system_clock, SharedClock};
+use crate::concurrency::ConcurrencyLimit;
 use crate::context::Context;
 use crate::executors::StepExecutor;
+use crate::foreach;
+use crate::heartbeat::Progress;
 use crate::limits::ExecutionLimits;
-use crate::protocol::{Step, StepResult, StepStatus};
+use crate::memory::MemoryGuard;
+use crate::progress_feed::ProgressFeed;
+use crate::progress_stream::ProgressStream;
+use crate::protocol::{SkipReason, Step, StepResult, StepStatus};
+use crate::streaming::ResultStream;
+use crate::telemetry::step_span;
+use crate::timeline::{self, WorkerSlots};
 
 // ============================================================================
 // ESTRUTURA DO NÓ DE EXECUÇÃO
@@ -113,6 +126,80 @@ pub struct DagPlanner {
     /// Steps que não têm dependências (raízes do DAG).
     /// Estes podem começar a executar imediatamente.
     roots: Vec<String>,
+
+    /// Se `false`, os snapshots de contexto de cada resultado são
+    /// trocados pelo delta (`StepResult::compact_context`).
+    full_context: bool,
+
+    /// Para onde vai cada resultado assim que o step termina.
+    sinks: ResultSinks,
+
+    /// Relógio das esperas entre avaliações de `assertions_retry`.
+    clock: SharedClock,
+
+    /// Se `true`, o paralelismo se adapta ao ambiente (`--max-parallel auto`).
+    adaptive: bool,
+
+    /// Sinal de parada; disparado, os steps que não começaram são cancelados.
+    stop: Arc<StopSignal>,
+
+    /// Pausa; enquanto fechada, nenhum step novo começa.
+    pause: PauseGate,
+}
+
+/// Para onde vai cada resultado assim que o step termina.
+///
+/// Compartilhado pelas execuções paralela (DAG) e sequencial.
+#[derive(Clone)]
+pub struct ResultSinks {
+    /// Arquivo parcial (`--output`).
+    pub stream: Option<Arc<ResultStream>>,
+    /// Contadores lidos pelo heartbeat (`--heartbeat-secs`).
+    pub progress: Option<Arc<Progress>>,
+    /// Eventos por step enviados para `--progress-url`.
+    pub feed: Option<Arc<ProgressFeed>>,
+    /// Eventos NDJSON de `--progress-stream`.
+    pub events: Option<Arc<ProgressStream>>,
+    /// Canal de resultados de um `ExecutionHandle`.
+    pub channel: Option<mpsc::UnboundedSender<StepResult>>,
+    /// Limite de dados retidos (`RUNNER_MAX_MEMORY_MB`); estourado, os
+    /// steps restantes são cancelados.
+    pub memory: Arc<MemoryGuard>,
+}
+
+impl Default for ResultSinks {
+    fn default() -> Self {
+        Self {
+            stream: None,
+            progress: None,
+            feed: None,
+            events: None,
+            channel: None,
+            memory: MemoryGuard::disabled(),
+        }
+    }
+}
+
+impl ResultSinks {
+    pub fn record(&self, result: &StepResult) {
+        if let Some(stream) = &self.stream {
+            stream.append(result);
+        }
+        if let Some(progress) = &self.progress {
+            progress.record(result);
+        }
+        if let Some(feed) = &self.feed {
+            feed.record(result);
+        }
+        if let Some(events) = &self.events {
+            events.record(result);
+        }
+        if let Some(channel) = &self.channel {
+            // Receptor descartado: quem embute não quer mais os resultados.
+            let _ = channel.send(result.clone());
+        }
+        self.memory.record_result(result);
+    }
 }
 
 impl DagPlanner {
@@ -140,6 +227,13 @@ impl DagPlanner {
 
         // Primeira passagem: criar nós e mapear dependentes.
         for step in steps {
+            // A validação rejeita IDs duplicados; se um chegar aqui, mantém o
+            // primeiro em vez de sobrescrevê-lo silenciosamente no HashMap.
+            if nodes.contains_key(&step.id) {
+                warn!(step_id = %step.id, "ID de step duplicado; ocorrência ignorada no DAG");
+                continue;
+            }
+
             // Converte Vec<String> para HashSet<String>.
             // HashSet é mais eficiente para verificar se contém algo.
             let deps: HashSet<String> = step.depends_on.iter().cloned().collect();
@@ -185,7 +279,61 @@ impl DagPlanner {
             "DAG construído"
         );
 
-        Self { nodes, roots }
+        Self {
+            nodes,
+            roots,
+            full_context: true,
+            sinks: ResultSinks::default(),
+            clock: system_clock(),
+            adaptive: false,
+            stop: StopSignal::new(),
+            pause: PauseGate::new(),
+        }
+    }
+
+    /// Define se os resultados mantêm os snapshots completos do contexto.
+    ///
+    /// Com `false`, cada resultado guarda apenas o `context_delta`,
+    /// reduzindo memória e tamanho do relatório em planos grandes.
+    pub fn with_full_context(mut self, full_context: bool) -> Self {
+        self.full_context = full_context;
+        self
+    }
+
+    /// Define o relógio das esperas (`--fast-wait` usa um relógio virtual).
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Destinos de cada resultado (relatório parcial, heartbeat, eventos, memória).
+    pub fn with_sinks(mut self, sinks: ResultSinks) -> Self {
+        self.sinks = sinks;
+        self
+    }
+
+    /// Adapta o paralelismo (até `max_parallel`) a erros e latência observados.
+    pub fn with_adaptive_parallelism(mut self, adaptive: bool) -> Self {
+        self.adaptive = adaptive;
+        self
+    }
+
+    /// Cancela os steps que ainda não começaram quando o sinal disparar.
+    pub fn with_stop_signal(mut self, stop: Arc<StopSignal>) -> Self {
+        self.stop = stop;
+        self
+    }
+
+    /// Segura os steps que ainda não começaram enquanto a pausa estiver fechada.
+    pub fn with_pause_gate(mut self, pause: PauseGate) -> Self {
+        self.pause = pause;
+        self
+    }
+
+    /// Envia uma cópia de cada resultado pelo canal assim que o step termina.
+    pub fn with_result_channel(mut self, channel: mpsc::UnboundedSender<StepResult>) -> Self {
+        self.sinks.channel = Some(channel);
+        self
     }
 
     // ========================================================================
@@ -218,7 +366,8 @@ impl DagPlanner {
     ///
     /// Usamos `Arc` (referência contada) e locks (`Mutex`, `RwLock`)
     /// para garantir acesso seguro aos dados compartilhados.
-    /// Um `Semaphore` controla o número máximo de steps em paralelo.
+    /// Um `ConcurrencyLimit` controla o número máximo de steps em paralelo
+    /// (fixo ou adaptativo).
     #[instrument(skip(self, executors, context, limits))]
     pub async fn execute(
         self,
@@ -226,16 +375,29 @@ impl DagPlanner {
         context: Arc<RwLock<Context>>,
         limits: ExecutionLimits,
     ) -> Vec<StepResult> {
-        // Semáforo para limitar paralelismo.
+        // Limite de paralelismo (no modo adaptativo, o teto).
         // Se max_parallel = 0, usamos número de steps (sem limite efetivo).
         let max_parallel = if limits.max_parallel > 0 {
             limits.max_parallel
         } else {
             self.nodes.len().max(1)
         };
-        let semaphore = Arc::new(Semaphore::new(max_parallel));
+        let concurrency = if self.adaptive {
+            ConcurrencyLimit::adaptive(max_parallel)
+        } else {
+            ConcurrencyLimit::fixed(max_parallel)
+        };
+        let full_context = self.full_context;
+        let sinks = self.sinks;
+        let clock = self.clock;
+        let stop = self.stop;
+        let pause = self.pause;
+        let slots = WorkerSlots::new();
+        // O mesmo limite é o teto do `parallel_foreach` dentro de cada step.
+        context.write().await.max_parallel = limits.max_parallel;
         info!(
             max_parallel = max_parallel,
+            adaptive = self.adaptive,
             "DAG executor initialized with concurrency limit"
         );
 
@@ -293,13 +455,24 @@ impl DagPlanner {
                 let completed_clone = Arc::clone(&completed);
                 let failed_clone = Arc::clone(&failed);
                 let ready_clone = Arc::clone(&ready);
-                let semaphore_clone = Arc::clone(&semaphore);
+                let concurrency_clone = concurrency.clone();
+                let sinks = sinks.clone();
+                let clock = Arc::clone(&clock);
+                let slots = slots.clone();
+                let stop = Arc::clone(&stop);
+                let pause = pause.clone();
 
                 // Spawna uma nova task assíncrona para este step.
+                // `in_current_span` mantém o step como filho do span do plano.
                 join_set.spawn(async move {
-                    // Adquire permit do semáforo para controlar paralelismo.
+                    // Em pausa, o step espera (um cancelamento também libera).
+                    pause.wait(&stop).await;
+                    // Adquire uma vaga para controlar paralelismo.
                     // Isso garante que no máximo max_parallel steps rodem ao mesmo tempo.
-                    let _permit = semaphore_clone.acquire().await.expect("Semaphore closed");
+                    let _permit = concurrency_clone.acquire().await;
+                    // Numera a vaga e marca o início para a linha do tempo.
+                    let slot = slots.acquire();
+                    let started_at = Utc::now();
 
                     // Obtém o step do nó.
                     let step = {
@@ -313,6 +486,16 @@ impl DagPlanner {
                         None => return,
                     };
 
+                    // Execução parada (memória, abort): o step nem começa.
+                    if let Some(mut result) = stop.cancelled_result(&step_id) {
+                        result.timeline = Some(timeline::stamp(started_at, slot.index(), 0));
+                        sinks.record(&result);
+                        results_clone.lock().await.push(result);
+                        failed_clone.write().await.insert(step_id.clone());
+                        release_dependents(&step_id, &nodes_clone, &completed_clone, &failed_clone, &ready_clone).await;
+                        return;
+                    }
+
                     // Verifica se alguma dependência falhou.
                     // Se sim, pula este step.
                     let failed_dep = {
@@ -338,36 +521,65 @@ impl DagPlanner {
                         let ctx = context_clone.read().await;
                         let context_snapshot = ctx.variables.clone();
                         drop(ctx); // Libera o lock explicitamente
-                        let result = StepResult {
+                        let mut result = StepResult {
                             step_id: step_id.clone(),
                             status: StepStatus::Skipped,
                             duration_ms: 0,
                             attempt: 1,
                             error: Some(format!("Dependency '{}' failed", dep)),
+                            skip_reason: Some(SkipReason::DependencyFailed),
                             context_before: Some(context_snapshot.clone()),
                             context_after: Some(context_snapshot),
                             extractions: None,
                             http_details: None,
+                            timeline: Some(timeline::stamp(started_at, slot.index(), 0)),
+                            ..Default::default()
                         };
 
+                        if !full_context {
+                            result.compact_context();
+                        }
+                        sinks.record(&result);
                         results_clone.lock().await.push(result);
                         failed_clone.write().await.insert(step_id.clone());
+                        release_dependents(&step_id, &nodes_clone, &completed_clone, &failed_clone, &ready_clone).await;
                         return;
                     }
 
                     // Encontra executor
                     let executor = executors_clone
                         .iter()
-                        .find(|e| e.can_handle(&step.action));
+                        .find(|e| e.can_handle_step(&step));
+                    if let Some(events) = &sinks.events {
+                        events.step_started(&step);
+                    }
 
-                    let result = match executor {
+                    let mut lock_wait_ms = 0;
+                    let mut result = match executor {
                         Some(exec) => {
+                            // O contexto é exclusivo durante o step: esta espera é
+                            // a serialização que o `runner timeline` destaca.
+                            let waiting = Instant::now();
                             let mut ctx = context_clone.write().await;
+                            lock_wait_ms = waiting.elapsed().as_millis() as u64;
                             // Snapshot do contexto antes da execução
                             let context_before = ctx.variables.clone();
-                            match exec.execute(&step, &mut ctx).await {
-                                Ok(r) => r,
-                                Err(e) => {
+                            // Prazo vencido no meio do step: ele é interrompido.
+                            let outcome = stop
+                                .within_deadline(
+                                    foreach::execute_step(&step, exec.as_ref(), &mut ctx, clock.as_ref())
+                                        .instrument(step_span(&step)),
+                                )
+                                .await;
+                            sinks.memory.record_context(&ctx.variables);
+                            match outcome {
+                                Some(Ok(r)) => r,
+                                None => StepResult {
+                                    context_before: Some(context_before),
+                                    context_after: Some(ctx.variables.clone()),
+                                    ..stop.interrupted_result(&step_id)
+                                },
+                                Some(Err(e)) => {
                                     error!(step_id = %step_id, error = %e, "Step execution failed");
                                     // Captura contexto após erro para debug
                                     let context_after = ctx.variables.clone();
@@ -381,6 +593,7 @@ impl DagPlanner {
                                         context_after: Some(context_after),
                                         extractions: None,
                                         http_details: None,
+                                        ..Default::default()
                                     }
                                 }
                             }
@@ -399,14 +612,24 @@ impl DagPlanner {
                                 context_after: Some(context_snapshot),
                                 extractions: None,
                                 http_details: None,
+                                ..Default::default()
                             }
                         }
                     };
 
-                    let passed = result.status == StepStatus::Passed;
+                    result.timeline = Some(timeline::stamp(started_at, slot.index(), lock_wait_ms));
+                    drop(slot);
+
+                    // Condição falsa (`not_run`) libera os dependentes como um sucesso.
+                    let passed = matches!(result.status, StepStatus::Passed | StepStatus::NotRun);
                     info!(step_id = %step_id, status = ?result.status, "Step completed");
+                    concurrency_clone.record(&result);
 
                     // Registra resultado
+                    if !full_context {
+                        result.compact_context();
+                    }
+                    sinks.record(&result);
                     results_clone.lock().await.push(result);
 
                     if passed {
@@ -418,50 +641,66 @@ impl DagPlanner {
                     // Libera dependentes para processamento
                     // Se passou: dependentes podem executar normalmente
                     // Se falhou: dependentes serão marcados como skipped
-                    //
-                    // NOTA: Adquirimos todos os locks necessários de uma vez para evitar deadlock
-                    let dependents_to_add: Vec<String> = {
-                        let nodes_guard = nodes_clone.read().await;
-                        let completed_guard = completed_clone.read().await;
-                        let failed_guard = failed_clone.read().await;
-
-                        let mut to_add = Vec::new();
-                        if let Some(node) = nodes_guard.get(&step_id) {
-                            for dependent_id in &node.dependents {
-                                if let Some(dependent_node) = nodes_guard.get(dependent_id) {
-                                    // Verifica se todas as deps foram processadas (passou ou falhou)
-                                    let all_deps_processed = dependent_node
-                                        .dependencies
-                                        .iter()
-                                        .all(|d| completed_guard.contains(d) || failed_guard.contains(d));
-
-                                    if all_deps_processed {
-                                        to_add.push(dependent_id.clone());
-                                    }
-                                }
-                            }
-                        }
-                        to_add
-                    };
-
-                    // Agora adiciona à fila ready (lock separado)
-                    if !dependents_to_add.is_empty() {
-                        let mut ready_guard = ready_clone.lock().await;
-                        ready_guard.extend(dependents_to_add);
-                    }
-                });
+                    release_dependents(&step_id, &nodes_clone, &completed_clone, &failed_clone, &ready_clone).await;
+                }.in_current_span());
             }
 
             // Aguarda todas as tasks deste lote
             while join_set.join_next().await.is_some() {}
         }
 
+        if let Some(limit) = concurrency.adaptive_limit() {
+            info!(final_parallelism = limit, "Adaptive parallelism settled");
+        }
+
         // Retorna resultados ordenados pela ordem original (aproximada)
         let final_results = results.lock().await;
         final_results.clone()
     }
 }
 
+/// Põe na fila os dependentes de `step_id` cujas dependências já terminaram
+/// (com sucesso ou não). Chamado em todo caminho em que um step termina,
+/// inclusive pulado ou cancelado, senão a cadeia seguinte nunca é processada.
+///
+/// NOTA: Adquirimos todos os locks necessários de uma vez para evitar deadlock
+async fn release_dependents(
+    step_id: &str,
+    nodes: &RwLock<HashMap<String, ExecutionNode>>,
+    completed: &RwLock<HashSet<String>>,
+    failed: &RwLock<HashSet<String>>,
+    ready: &Mutex<Vec<String>>,
+) {
+    let dependents_to_add: Vec<String> = {
+        let nodes_guard = nodes.read().await;
+        let completed_guard = completed.read().await;
+        let failed_guard = failed.read().await;
+
+        let mut to_add = Vec::new();
+        if let Some(node) = nodes_guard.get(step_id) {
+            for dependent_id in &node.dependents {
+                if let Some(dependent_node) = nodes_guard.get(dependent_id) {
+                    // Verifica se todas as deps foram processadas (passou ou falhou)
+                    let all_deps_processed = dependent_node
+                        .dependencies
+                        .iter()
+                        .all(|d| completed_guard.contains(d) || failed_guard.contains(d));
+
+                    if all_deps_processed {
+                        to_add.push(dependent_id.clone());
+                    }
+                }
+            }
+        }
+        to_add
+    };
+
+    // Agora adiciona à fila ready (lock separado)
+    if !dependents_to_add.is_empty() {
+        ready.lock().await.extend(dependents_to_add);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -477,6 +716,7 @@ mod tests {
             assertions: vec![],
             extract: vec![],
             recovery_policy: None,
+            ..Default::default()
         }
     }
 
@@ -490,6 +730,7 @@ mod tests {
             assertions: vec![],
             extract: vec![],
             recovery_policy: None,
+            ..Default::default()
         }
     }
 
@@ -538,6 +779,21 @@ mod tests {
         assert!(node_a.dependents.contains("C"));
     }
 
+    #[test]
+    fn test_duplicate_step_id_keeps_first() {
+        let steps = vec![
+            create_step("A", vec![]),
+            create_step("B", vec!["A"]),
+            create_step("B", vec![]),
+        ];
+
+        let planner = DagPlanner::new(steps);
+
+        assert_eq!(planner.nodes.len(), 2);
+        assert!(planner.nodes["B"].dependencies.contains("A"));
+        assert_eq!(planner.roots, vec!["A".to_string()]);
+    }
+
     #[tokio::test]
     async fn test_context_snapshots_on_no_executor() {
         // Testa que context_before/after são preenchidos quando não há executor
@@ -588,6 +844,7 @@ mod tests {
             assertions: vec![],
             extract: vec![],
             recovery_policy: None,
+            ..Default::default()
         };
         let step_b = Step {
             id: "step_b".to_string(),
@@ -598,6 +855,7 @@ mod tests {
             assertions: vec![],
             extract: vec![],
             recovery_policy: None,
+            ..Default::default()
         };
 
         let planner = DagPlanner::new(vec![step_a, step_b]);
@@ -617,6 +875,7 @@ mod tests {
         let skipped = results.iter().find(|r| r.step_id == "step_b").unwrap();
 
         assert_eq!(skipped.status, StepStatus::Skipped);
+        assert_eq!(skipped.skip_reason, Some(SkipReason::DependencyFailed));
         assert!(
             skipped.context_before.is_some(),
             "Skipped step should have context_before"
@@ -630,4 +889,105 @@ mod tests {
         let ctx = skipped.context_before.as_ref().unwrap();
         assert_eq!(ctx.get("initial_var").unwrap(), &json!(42));
     }
+
+    #[tokio::test]
+    async fn test_context_delta_replaces_snapshots() {
+        use crate::executors::set_variable::SetVariableExecutor;
+
+        let step = Step {
+            id: "set".to_string(),
+            action: "set_variable".to_string(),
+            params: json!({ "variables": { "new_var": 1, "existing": "changed" } }),
+            ..Default::default()
+        };
+        let planner = DagPlanner::new(vec![step]).with_full_context(false);
+
+        let mut context = Context::new();
+        context.set("existing", json!("original"));
+        context.set("untouched", json!(true));
+
+        let executors: Arc<Vec<Box<dyn StepExecutor + Send + Sync>>> =
+            Arc::new(vec![Box::new(SetVariableExecutor::new())]);
+        let context = Arc::new(RwLock::new(context));
+
+        let results = planner
+            .execute(executors, context, ExecutionLimits::default())
+            .await;
+
+        let result = &results[0];
+        assert_eq!(result.status, StepStatus::Passed, "{:?}", result.error);
+        assert!(result.context_before.is_none());
+        assert!(result.context_after.is_none());
+
+        let delta = result.context_delta.as_ref().unwrap();
+        assert_eq!(delta.added.get("new_var"), Some(&json!(1)));
+        assert_eq!(delta.changed.get("existing"), Some(&json!("changed")));
+        assert!(!delta.changed.contains_key("untouched"));
+        assert!(delta.removed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_memory_limit_cancels_remaining_steps() {
+        use crate::executors::set_variable::SetVariableExecutor;
+
+        let set = |id: &str, depends_on: &[&str]| Step {
+            id: id.to_string(),
+            action: "set_variable".to_string(),
+            params: json!({ "variables": { id: "x".repeat(2048) } }),
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        };
+        let stop = StopSignal::new();
+        let memory = MemoryGuard::new(Some(1024), Arc::clone(&stop));
+        let planner = DagPlanner::new(vec![set("first", &[]), set("second", &["first"])])
+            .with_sinks(ResultSinks {
+                memory,
+                ..Default::default()
+            })
+            .with_stop_signal(Arc::clone(&stop));
+
+        let executors: Arc<Vec<Box<dyn StepExecutor + Send + Sync>>> =
+            Arc::new(vec![Box::new(SetVariableExecutor::new())]);
+        let context = Arc::new(RwLock::new(Context::new()));
+        let results = planner
+            .execute(executors, context, ExecutionLimits::default())
+            .await;
+
+        assert_eq!(results[0].status, StepStatus::Passed);
+        assert_eq!(results[1].status, StepStatus::Cancelled);
+        assert_eq!(results[1].skip_reason, Some(SkipReason::Cancelled));
+        assert!(stop.report_error().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_deadline_interrupts_running_step() {
+        use crate::executors::wait::WaitExecutor;
+
+        let wait = |id: &str, duration_ms: u64, depends_on: &[&str]| Step {
+            id: id.to_string(),
+            action: "wait".to_string(),
+            params: json!({ "duration_ms": duration_ms }),
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        };
+        let stop = StopSignal::new();
+        let _deadline = stop.deadline(std::time::Duration::from_millis(50));
+        let planner = DagPlanner::new(vec![wait("slow", 30_000, &[]), wait("next", 0, &["slow"])])
+            .with_stop_signal(Arc::clone(&stop));
+
+        let executors: Arc<Vec<Box<dyn StepExecutor + Send + Sync>>> =
+            Arc::new(vec![Box::new(WaitExecutor::new())]);
+        let context = Arc::new(RwLock::new(Context::new()));
+        let started = Instant::now();
+        let results = planner
+            .execute(executors, context, ExecutionLimits::default())
+            .await;
+
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+        let slow = results.iter().find(|r| r.step_id == "slow").unwrap();
+        assert_eq!(slow.status, StepStatus::Failed);
+        assert_eq!(slow.skip_reason, Some(SkipReason::TimedOut));
+        assert!(results.iter().all(|r| r.status != StepStatus::Passed));
+        assert!(stop.timed_out());
+    }
 }
@@ -0,0 +1,204 @@
+//! # Módulo de Preflight - Espera pela Prontidão do Ambiente
+//!
+//! Antes de executar o primeiro step, verifica se o ambiente alvo já está
+//! de pé, conforme `config.wait_for`.
+//!
+//! ## Para todos entenderem:
+//!
+//! É como conferir se a loja abriu antes de entrar: o Runner bate na porta
+//! (health check HTTP ou conexão TCP) a cada `interval_ms` e só começa os
+//! testes quando alguém responde. Se ninguém responder até `timeout_ms`,
+//! a execução é abortada com uma mensagem clara, em vez de dezenas de
+//! steps falhando com "connection refused".
+//!
+//! ## Critério de prontidão:
+//!
+//! | Modo | Pronto quando                              |
+//! |------|--------------------------------------------|
+//! | HTTP | `GET <path>` retorna status 2xx            |
+//! | TCP  | A porta `host:porta` aceita uma conexão    |
+
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+
+use crate::protocol::WaitFor;
+
+/// Tempo máximo de cada tentativa individual.
+const MAX_ATTEMPT_TIMEOUT: Duration = Duration::from_secs(5);
+
+// ============================================================================
+// RESULTADO
+// ============================================================================
+
+/// Resultado de um preflight bem-sucedido.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreflightOutcome {
+    /// Número de tentativas até o ambiente responder.
+    pub attempts: u32,
+
+    /// Tempo total de espera em milissegundos.
+    pub elapsed_ms: u64,
+}
+
+// ============================================================================
+// ESPERA
+// ============================================================================
+
+/// Aguarda o ambiente ficar pronto conforme `wait_for`.
+///
+/// ## Parâmetros:
+/// - `wait_for`: Configuração do preflight
+/// - `base_url`: URL base do plano (usada quando `path` é relativo)
+///
+/// ## Retorno:
+/// - `Ok(PreflightOutcome)`: Ambiente respondeu dentro do prazo
+/// - `Err`: Prazo esgotado (com o último erro observado)
+pub async fn wait_until_ready(wait_for: &WaitFor, base_url: &str) -> Result<PreflightOutcome> {
+    let start = Instant::now();
+    let deadline = start + Duration::from_millis(wait_for.timeout_ms);
+    let interval = Duration::from_millis(wait_for.interval_ms);
+    let target = describe_target(wait_for, base_url);
+    let client = Client::new();
+    let mut attempts = 0u32;
+
+    info!(target = %target, timeout_ms = wait_for.timeout_ms, "⏳ Aguardando ambiente (wait_for)");
+
+    loop {
+        attempts += 1;
+        let attempt_timeout = deadline
+            .saturating_duration_since(Instant::now())
+            .min(MAX_ATTEMPT_TIMEOUT)
+            .max(Duration::from_millis(1));
+
+        let check = match &wait_for.tcp {
+            Some(address) => check_tcp(address, attempt_timeout).await,
+            None => check_http(&client, &target, attempt_timeout).await,
+        };
+
+        match check {
+            Ok(()) => {
+                let elapsed_ms = start.elapsed().as_millis() as u64;
+                info!(target = %target, attempts, elapsed_ms, "✅ Ambiente pronto");
+                return Ok(PreflightOutcome {
+                    attempts,
+                    elapsed_ms,
+                });
+            }
+            Err(e) => {
+                debug!(target = %target, attempt = attempts, error = %e, "Ambiente ainda não está pronto");
+                if Instant::now() + interval >= deadline {
+                    return Err(anyhow!(
+                        "wait_for: '{}' não ficou pronto em {}ms ({} tentativas). Último erro: {}",
+                        target,
+                        wait_for.timeout_ms,
+                        attempts,
+                        e
+                    ));
+                }
+            }
+        }
+
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Descreve o alvo verificado (URL completa ou `host:porta`).
+fn describe_target(wait_for: &WaitFor, base_url: &str) -> String {
+    match &wait_for.tcp {
+        Some(address) => address.clone(),
+        None if wait_for.path.starts_with("http") => wait_for.path.clone(),
+        None => format!("{}{}", base_url.trim_end_matches('/'), wait_for.path),
+    }
+}
+
+/// Uma tentativa HTTP: pronto se o status for 2xx.
+async fn check_http(client: &Client, url: &str, timeout: Duration) -> Result<()> {
+    let response = client.get(url).timeout(timeout).send().await?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(anyhow!("status {}", response.status().as_u16()))
+    }
+}
+
+/// Uma tentativa TCP: pronto se a conexão for aceita.
+async fn check_tcp(address: &str, timeout: Duration) -> Result<()> {
+    tokio::time::timeout(timeout, tokio::net::TcpStream::connect(address))
+        .await
+        .map_err(|_| anyhow!("timeout ao conectar"))??;
+    Ok(())
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wait_for(path: &str, tcp: Option<String>, timeout_ms: u64) -> WaitFor {
+        WaitFor {
+            path: path.to_string(),
+            tcp,
+            timeout_ms,
+            interval_ms: 50,
+        }
+    }
+
+    /// Reserva uma porta livre e a libera (ninguém escuta nela depois).
+    async fn closed_port() -> u16 {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        listener.local_addr().unwrap().port()
+    }
+
+    #[tokio::test]
+    async fn test_tcp_ready() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+
+        let outcome = wait_until_ready(&wait_for("/health", Some(address), 1000), "")
+            .await
+            .unwrap();
+        assert_eq!(outcome.attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_http_becomes_ready() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Responde 503 na primeira requisição e 200 na segunda.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            for status in ["503 Service Unavailable", "200 OK"] {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let base_url = format!("http://127.0.0.1:{}/", port);
+        let outcome = wait_until_ready(&wait_for("/health", None, 2000), &base_url)
+            .await
+            .unwrap();
+        assert_eq!(outcome.attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn test_times_out_when_unreachable() {
+        let address = format!("127.0.0.1:{}", closed_port().await);
+
+        let error = wait_until_ready(&wait_for("/health", Some(address.clone()), 200), "")
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains(&address));
+    }
+}
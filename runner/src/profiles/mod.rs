@@ -0,0 +1,241 @@
+//! # Módulo de Perfis de Execução - `--profile`
+//!
+//! Carrega presets nomeados (`smoke`, `regression`, `nightly`, ...) de um
+//! arquivo de perfis e os aplica à execução.
+//!
+//! ## Para todos entenderem:
+//!
+//! Cada pipeline acabava com uma linha de comando enorme, copiada e colada
+//! com pequenas diferenças. O perfil dá nome a esse conjunto de opções:
+//!
+//! ```bash
+//! runner execute --file plan.json --profile smoke
+//! ```
+//!
+//! ## Formato do arquivo (YAML ou JSON):
+//!
+//! ```yaml
+//! # runner.profiles.yaml
+//! profiles:
+//!   smoke:
+//!     tags: [smoke]            # só steps com essas tags (e suas dependências)
+//!     max_attempts: 1          # sem retries
+//!     report_detail: minimal
+//!     limits:
+//!       max_execution_secs: 120
+//!   nightly:
+//!     parallel: true
+//!     max_attempts: 3
+//!     report_detail: full
+//!     quarantine: ./quarantine.yaml
+//!     heartbeat_secs: 60
+//!     heartbeat_url: http://orchestrator/runs/nightly/ping
+//! ```
+//!
+//! | Campo            | Efeito                                                  |
+//! |------------------|---------------------------------------------------------|
+//! | `tags`           | Executa só os steps marcados (e as dependências deles)  |
+//! | `max_attempts`   | Substitui `max_attempts` das `recovery_policy` de retry |
+//! | `limits`         | Sobrescreve os limites (`RUNNER_MAX_*`)                 |
+//! | `parallel`, `report_detail`, `quarantine` | Como as flags de mesmo nome    |
+//! | `heartbeat_secs`, `heartbeat_url` | Notificações de progresso              |
+//!
+//! Flags passadas explicitamente na CLI têm prioridade sobre o perfil.
+
+use anyhow::{anyhow, Context as _, Result};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::limits::ExecutionLimits;
+use crate::protocol::{ReportDetail, Step};
+use crate::selection;
+
+/// Arquivo de perfis procurado quando `--profiles-file` não é informado.
+pub const DEFAULT_PROFILES_FILE: &str = "runner.profiles.yaml";
+
+// ============================================================================
+// ESTRUTURAS
+// ============================================================================
+
+/// Conteúdo do arquivo de perfis.
+#[derive(Debug, Deserialize)]
+struct ProfilesFile {
+    profiles: HashMap<String, Profile>,
+}
+
+/// Um preset de execução.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// Executa só os steps com alguma dessas tags (vazio = todos).
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// Tentativas de cada step com `recovery_policy` de retry.
+    pub max_attempts: Option<u32>,
+
+    /// Limites de execução que sobrescrevem os de `RUNNER_MAX_*`.
+    #[serde(default)]
+    pub limits: ProfileLimits,
+
+    pub parallel: Option<bool>,
+    pub report_detail: Option<ReportDetail>,
+    pub quarantine: Option<PathBuf>,
+    pub heartbeat_secs: Option<u64>,
+    pub heartbeat_url: Option<String>,
+}
+
+/// Limites opcionais de um perfil (mesmos nomes de `RUNNER_MAX_*`).
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileLimits {
+    pub max_steps: Option<usize>,
+    pub max_parallel: Option<usize>,
+    pub max_retries: Option<u32>,
+    pub max_execution_secs: Option<u64>,
+    pub max_step_timeout_secs: Option<u64>,
+}
+
+// ============================================================================
+// CARREGAMENTO
+// ============================================================================
+
+/// Carrega o perfil `name` do arquivo (ou de `runner.profiles.yaml`).
+pub fn load(path: Option<&Path>, name: &str) -> Result<Profile> {
+    let path = path.unwrap_or(Path::new(DEFAULT_PROFILES_FILE));
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Falha ao ler arquivo de perfis {:?}", path))?;
+    let file: ProfilesFile = serde_yaml::from_str(&content)
+        .with_context(|| format!("Arquivo de perfis inválido {:?}", path))?;
+
+    let mut profiles = file.profiles;
+    profiles.remove(name).ok_or_else(|| {
+        let mut available: Vec<_> = profiles.into_keys().collect();
+        available.sort();
+        anyhow!(
+            "Perfil '{}' não existe em {:?}. Disponíveis: {}",
+            name,
+            path,
+            available.join(", ")
+        )
+    })
+}
+
+// ============================================================================
+// APLICAÇÃO
+// ============================================================================
+
+impl Profile {
+    /// IDs dos steps selecionados pelas tags, mais suas dependências.
+    ///
+    /// Retorna `None` se o perfil não filtra por tag.
+    pub fn select_steps(&self, steps: &[Step]) -> Option<HashSet<String>> {
+        if self.tags.is_empty() {
+            return None;
+        }
+        let roots = steps
+            .iter()
+            .filter(|s| s.tags.iter().any(|t| self.tags.contains(t)))
+            .map(|s| s.id.as_str());
+        Some(selection::with_dependencies(steps, roots))
+    }
+
+    /// Substitui as tentativas das `recovery_policy` de retry.
+    pub fn apply_retries(&self, steps: &mut [Step]) {
+        let Some(max_attempts) = self.max_attempts else {
+            return;
+        };
+        for policy in steps.iter_mut().filter_map(|s| s.recovery_policy.as_mut()) {
+            if policy.strategy == "retry" {
+                policy.max_attempts = max_attempts.max(1);
+            }
+        }
+    }
+
+    /// Sobrescreve os limites informados no perfil.
+    pub fn apply_limits(&self, limits: &mut ExecutionLimits) {
+        let overrides = &self.limits;
+        if let Some(n) = overrides.max_steps {
+            limits.max_steps = n;
+        }
+        if let Some(n) = overrides.max_parallel {
+            limits.max_parallel = n;
+        }
+        if let Some(n) = overrides.max_retries {
+            limits.max_retries_total = n;
+        }
+        if let Some(secs) = overrides.max_execution_secs {
+            limits.max_execution_time = Duration::from_secs(secs);
+        }
+        if let Some(secs) = overrides.max_step_timeout_secs {
+            limits.max_step_timeout = Duration::from_secs(secs);
+        }
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn write_profiles(content: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "aqa-profiles-{}-{}.yaml",
+            std::process::id(),
+            content.len()
+        ));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_load_profile_and_unknown_name() {
+        let path = write_profiles(
+            "profiles:\n  smoke:\n    tags: [smoke]\n    report_detail: minimal\n    limits:\n      max_execution_secs: 120\n  nightly:\n    parallel: true\n",
+        );
+
+        let smoke = load(Some(&path), "smoke").unwrap();
+        assert_eq!(smoke.tags, vec!["smoke"]);
+        assert_eq!(smoke.report_detail, Some(ReportDetail::Minimal));
+        let mut limits = ExecutionLimits::default();
+        smoke.apply_limits(&mut limits);
+        assert_eq!(limits.max_execution_time, Duration::from_secs(120));
+
+        let err = load(Some(&path), "weekly").unwrap_err().to_string();
+        assert!(err.contains("Disponíveis: nightly, smoke"), "{}", err);
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_tag_selection_keeps_dependencies_and_caps_retries() {
+        let mut steps: Vec<Step> = serde_json::from_value(json!([
+            { "id": "login", "action": "log", "params": {},
+              "recovery_policy": { "strategy": "retry", "max_attempts": 5, "backoff_ms": 10 } },
+            { "id": "health", "action": "log", "params": {}, "tags": ["smoke"],
+              "depends_on": ["login"] },
+            { "id": "report", "action": "log", "params": {}, "tags": ["slow"] }
+        ]))
+        .unwrap();
+        let profile = Profile {
+            tags: vec!["smoke".to_string()],
+            max_attempts: Some(1),
+            ..Default::default()
+        };
+
+        let selected = profile.select_steps(&steps).unwrap();
+        assert_eq!(
+            selected,
+            HashSet::from(["login".to_string(), "health".to_string()])
+        );
+        assert!(Profile::default().select_steps(&steps).is_none());
+
+        profile.apply_retries(&mut steps);
+        assert_eq!(steps[0].recovery_policy.as_ref().unwrap().max_attempts, 1);
+    }
+}
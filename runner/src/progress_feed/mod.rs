@@ -0,0 +1,304 @@
+//! # Módulo de Progress Feed - Resultados por Step em Tempo Real
+//!
+//! Envia um `POST` JSON para `--progress-url` a cada step que termina, com
+//! status, código de erro e extrações, e um evento final com o resultado
+//! da execução. A resposta pode pedir para abortar o restante do plano.
+//!
+//! ## Para todos entenderem:
+//!
+//! O relatório só fica pronto no fim. Para o Brain reagir no meio do
+//! caminho (ex: o login falhou, não adianta rodar os 200 steps seguintes),
+//! ele recebe cada resultado assim que acontece:
+//!
+//! ```bash
+//! runner execute --file plan.json --progress-url http://brain/runs/42/events
+//! ```
+//!
+//! ```json
+//! {
+//!   "event": "step_finished", "execution_id": "42", "sequence": 3,
+//!   "completed": 3, "total": 10,
+//!   "step_id": "login", "status": "failed", "attempt": 1, "duration_ms": 120,
+//!   "error_code": "E3001", "error": "Assertion failed: status_code ...",
+//!   "extractions": [{ "target": "token", "success": false, "error_code": "E3010", "...": "..." }]
+//! }
+//! ```
+//!
+//! ## Contrato:
+//!
+//! | Evento          | Quando                     | Campos próprios                           |
+//! |-----------------|----------------------------|-------------------------------------------|
+//! | `step_finished` | Cada step termina          | `step_id`, `status`, `attempt`, `duration_ms`, `error_code`, `error`, `extractions` |
+//! | `run_finished`  | Fim da execução (último)   | `success`                                 |
+//!
+//! - Todos os eventos têm `execution_id`, `sequence` (1, 2, 3...), `completed` e `total`.
+//! - Os eventos saem em ordem, um de cada vez, sem atrasar os steps.
+//! - `error_code` só aparece para `failed`/`cancelled` (mesma triagem do resumo).
+//! - Respondendo `{"action": "abort", "reason": "..."}`, os steps que ainda
+//!   não começaram ficam `cancelled` e o relatório traz o erro E4006.
+//! - Falha ao enviar (timeout de 5s, receptor fora do ar) só gera um aviso.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+use crate::cancel::StopSignal;
+use crate::errors::ErrorCode;
+use crate::extractors::ExtractionResult;
+use crate::protocol::{StepResult, StepStatus};
+use crate::triage;
+
+/// Tempo máximo de cada `POST` para `--progress-url`.
+const POST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Tempo máximo esperando os eventos pendentes no fim da execução.
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+// ============================================================================
+// EVENTOS
+// ============================================================================
+
+/// Um evento enviado para `--progress-url`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProgressEvent {
+    pub execution_id: String,
+    /// Ordem do evento na execução (começa em 1).
+    pub sequence: u64,
+    /// Steps terminados até este evento.
+    pub completed: usize,
+    /// Steps do plano.
+    pub total: usize,
+    #[serde(flatten)]
+    pub kind: EventKind,
+}
+
+/// Tipo do evento (campo `event` no JSON).
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum EventKind {
+    /// Um step terminou.
+    StepFinished(StepOutcome),
+    /// A execução terminou (último evento).
+    RunFinished { success: bool },
+}
+
+/// Resultado de um step, resumido para o Brain.
+#[derive(Debug, Clone, Serialize)]
+pub struct StepOutcome {
+    pub step_id: String,
+    pub status: StepStatus,
+    pub attempt: u32,
+    pub duration_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub extractions: Vec<ExtractionResult>,
+}
+
+impl StepOutcome {
+    pub fn from_result(result: &StepResult) -> Self {
+        let error_code = matches!(result.status, StepStatus::Failed | StepStatus::Cancelled)
+            .then(|| triage::classify(result).formatted());
+        Self {
+            step_id: result.step_id.clone(),
+            status: result.status.clone(),
+            attempt: result.attempt,
+            duration_ms: result.duration_ms,
+            error_code,
+            error: result.error.clone(),
+            extractions: result.extractions.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// Resposta opcional do receptor.
+#[derive(Debug, Default, Deserialize)]
+struct FeedReply {
+    #[serde(default)]
+    action: Option<String>,
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+// ============================================================================
+// FEED
+// ============================================================================
+
+/// Fila de eventos para `--progress-url`, compartilhada entre os steps.
+pub struct ProgressFeed {
+    execution_id: String,
+    total: usize,
+    /// Próximo `sequence`; travado durante o envio para manter a ordem.
+    sequence: Mutex<u64>,
+    completed: AtomicUsize,
+    tx: mpsc::UnboundedSender<ProgressEvent>,
+}
+
+impl ProgressFeed {
+    /// Enfileira o resultado de um step (não bloqueia).
+    pub fn record(&self, result: &StepResult) {
+        let completed = self.completed.fetch_add(1, Ordering::Relaxed) + 1;
+        self.send(
+            completed,
+            EventKind::StepFinished(StepOutcome::from_result(result)),
+        );
+    }
+
+    fn send(&self, completed: usize, kind: EventKind) {
+        let mut sequence = self.sequence.lock().expect("progress feed");
+        *sequence += 1;
+        let event = ProgressEvent {
+            execution_id: self.execution_id.clone(),
+            sequence: *sequence,
+            completed,
+            total: self.total,
+            kind,
+        };
+        // Receptor encerrado: o evento é descartado.
+        let _ = self.tx.send(event);
+    }
+
+    /// Envia `run_finished` e espera os eventos pendentes (até 10s).
+    pub async fn finish(&self, task: JoinHandle<()>, success: bool) {
+        let completed = self.completed.load(Ordering::Relaxed);
+        self.send(completed, EventKind::RunFinished { success });
+        if tokio::time::timeout(DRAIN_TIMEOUT, task).await.is_err() {
+            warn!("Progress feed did not drain in time; pending events dropped");
+        }
+    }
+}
+
+/// Inicia o envio em segundo plano. `stop` é disparado se o receptor pedir abort.
+pub fn spawn(
+    url: String,
+    execution_id: &str,
+    total: usize,
+    stop: Arc<StopSignal>,
+) -> (Arc<ProgressFeed>, JoinHandle<()>) {
+    let (tx, mut rx) = mpsc::unbounded_channel::<ProgressEvent>();
+    let feed = Arc::new(ProgressFeed {
+        execution_id: execution_id.to_string(),
+        total,
+        sequence: Mutex::new(0),
+        completed: AtomicUsize::new(0),
+        tx,
+    });
+
+    let task = tokio::spawn(async move {
+        let client = reqwest::Client::builder()
+            .timeout(POST_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+
+        while let Some(event) = rx.recv().await {
+            let last = matches!(event.kind, EventKind::RunFinished { .. });
+            match client.post(&url).json(&event).send().await {
+                Ok(response) => {
+                    let reply: FeedReply = response.json().await.unwrap_or_default();
+                    if reply.action.as_deref() == Some("abort") {
+                        let reason = reply.reason.unwrap_or_else(|| "sem motivo".to_string());
+                        if stop.stop(
+                            ErrorCode::EXECUTION_ABORTED,
+                            format!("Execução abortada via --progress-url: {}", reason),
+                        ) {
+                            warn!(url = %url, reason = %reason, "Execution aborted by progress receiver");
+                        }
+                    }
+                }
+                Err(e) => warn!(url = %url, error = %e, "Progress event delivery failed"),
+            }
+            if last {
+                break;
+            }
+        }
+    });
+
+    (feed, task)
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[test]
+    fn test_outcome_carries_error_code_only_for_failures() {
+        let passed = StepOutcome::from_result(&StepResult {
+            step_id: "health".to_string(),
+            status: StepStatus::Passed,
+            ..Default::default()
+        });
+        assert!(passed.error_code.is_none());
+
+        let failed = StepOutcome::from_result(&StepResult {
+            step_id: "login".to_string(),
+            status: StepStatus::Failed,
+            error: Some("Assertion failed: status_code expected 200, got 500".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(failed.error_code.as_deref(), Some("E3001"));
+
+        let json = serde_json::to_value(ProgressEvent {
+            execution_id: "exec-1".to_string(),
+            sequence: 1,
+            completed: 1,
+            total: 2,
+            kind: EventKind::StepFinished(failed),
+        })
+        .unwrap();
+        assert_eq!(json["event"], "step_finished");
+        assert_eq!(json["step_id"], "login");
+        assert_eq!(json["status"], "failed");
+    }
+
+    #[tokio::test]
+    async fn test_abort_reply_trips_stop_signal() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/events", listener.local_addr().unwrap());
+        let stop = StopSignal::new();
+        let (feed, task) = spawn(url, "exec-7", 3, Arc::clone(&stop));
+
+        feed.record(&StepResult {
+            step_id: "login".to_string(),
+            status: StepStatus::Failed,
+            ..Default::default()
+        });
+
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buffer = vec![0u8; 4096];
+        let mut request = String::new();
+        while !request.contains("\"extractions\"") {
+            let n = socket.read(&mut buffer).await.unwrap();
+            request.push_str(&String::from_utf8_lossy(&buffer[..n]));
+        }
+        let body = r#"{"action":"abort","reason":"login quebrado"}"#;
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        socket.write_all(response.as_bytes()).await.unwrap();
+        drop(socket);
+
+        while stop.reason().is_none() {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        task.abort();
+
+        assert!(request.starts_with("POST /events"));
+        assert!(request.contains("\"event\":\"step_finished\""));
+        assert!(request.contains("\"execution_id\":\"exec-7\""));
+        let (code, message) = stop.reason().unwrap();
+        assert_eq!(code, ErrorCode::EXECUTION_ABORTED);
+        assert!(message.ends_with("login quebrado"));
+    }
+}
@@ -0,0 +1,369 @@
+//! # Módulo de Progress Stream - Eventos NDJSON em Tempo Real
+//!
+//! Escreve um evento JSON por linha (NDJSON) enquanto o plano roda: o step
+//! começou, vai ser repetido, uma extração falhou, o step terminou, o
+//! plano terminou. Destino: stdout ou um arquivo/named pipe.
+//!
+//! ## Para todos entenderem:
+//!
+//! `--progress-url` empurra eventos para um servidor. Uma UI local, ou o
+//! Brain rodando o runner como subprocesso, só precisa ler uma saída:
+//!
+//! ```bash
+//! runner execute --file plan.json --progress-stream - | jq -c .
+//! mkfifo /tmp/run-42 && runner execute --file plan.json --progress-stream /tmp/run-42
+//! ```
+//!
+//! ```text
+//! {"execution_id":"42","sequence":1,"timestamp":"...","event":"step_started","step_id":"login","action":"http_request"}
+//! {"execution_id":"42","sequence":2,"timestamp":"...","event":"retry","step_id":"login","attempt":1,"backoff_ms":500,"error":"..."}
+//! {"execution_id":"42","sequence":3,"timestamp":"...","event":"step_finished","step_id":"login","status":"passed",...}
+//! {"execution_id":"42","sequence":4,"timestamp":"...","event":"plan_finished","success":true}
+//! ```
+//!
+//! ## Contrato:
+//!
+//! | Evento              | Quando                                        | Campos próprios                  |
+//! |---------------------|-----------------------------------------------|----------------------------------|
+//! | `step_started`      | O executor do step vai rodar                  | `step_id`, `action`              |
+//! | `retry`             | A tentativa falhou e haverá outra (`recovery_policy`) | `step_id`, `attempt`, `backoff_ms`, `error` |
+//! | `extraction_failed` | Uma extração do step falhou (antes do `step_finished`) | `step_id`, `extraction`  |
+//! | `step_finished`     | O step terminou (inclusive pulado/cancelado)  | Os mesmos do `--progress-url`    |
+//! | `plan_finished`     | Fim do plano (último evento do plano)         | `success`                        |
+//!
+//! - Todas as linhas têm `execution_id`, `sequence` (1, 2, 3...) e `timestamp`.
+//! - Com `-`, a execução fica silenciosa e os logs vão para o stderr: o
+//!   stdout traz só eventos.
+//! - Cada linha é descarregada (flush) na hora; abrir um named pipe espera
+//!   o leitor conectar.
+//! - A escrita roda numa thread própria: os steps só enfileiram o evento e
+//!   nunca esperam o leitor.
+//! - Se o leitor não acompanhar (fila com `QUEUE_CAPACITY` eventos cheia),
+//!   os eventos excedentes são descartados com um único aviso; o `sequence`
+//!   continua contínuo nas linhas que saem.
+//! - Se a escrita falhar (leitor fechou o pipe), os eventos seguintes são
+//!   descartados com um único aviso; a execução continua.
+//! - No fim, o runner espera até `DRAIN_TIMEOUT` a fila esvaziar; um leitor
+//!   travado não segura o processo.
+
+use anyhow::{Context as _, Result};
+use chrono::Utc;
+use serde::Serialize;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TrySendError};
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::extractors::ExtractionResult;
+use crate::progress_feed::StepOutcome;
+use crate::protocol::{Step, StepResult};
+
+/// Valor de `--progress-stream` que escreve no stdout.
+pub const STDOUT: &str = "-";
+
+/// Eventos aguardando a thread de escrita antes de começar a descartar.
+const QUEUE_CAPACITY: usize = 1024;
+
+/// Quanto o fim da execução espera a fila ser escrita.
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(2);
+
+// ============================================================================
+// EVENTOS
+// ============================================================================
+
+/// Uma linha do stream.
+#[derive(Debug, Serialize)]
+struct StreamLine<'a> {
+    execution_id: &'a str,
+    /// Ordem da linha no stream (começa em 1).
+    sequence: u64,
+    timestamp: String,
+    #[serde(flatten)]
+    event: StreamEvent,
+}
+
+/// Tipo do evento (campo `event`).
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum StreamEvent {
+    StepStarted {
+        step_id: String,
+        action: String,
+    },
+    Retry {
+        step_id: String,
+        /// Tentativa que acabou de falhar.
+        attempt: u32,
+        /// Espera até a próxima tentativa.
+        backoff_ms: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    ExtractionFailed {
+        step_id: String,
+        extraction: ExtractionResult,
+    },
+    StepFinished(StepOutcome),
+    PlanFinished {
+        success: bool,
+    },
+}
+
+// ============================================================================
+// STREAM
+// ============================================================================
+
+/// Destino dos eventos NDJSON, compartilhado entre os steps.
+pub struct ProgressStream {
+    /// `None` depois do `Drop` (fecha a fila para a thread terminar).
+    queue: Option<SyncSender<(String, StreamEvent)>>,
+    /// Avisa quando a thread de escrita terminou de esvaziar a fila.
+    done: Mutex<Receiver<()>>,
+    /// Já avisou que está descartando eventos por leitor lento.
+    lagging: AtomicBool,
+}
+
+impl ProgressStream {
+    /// Abre o destino: `-` (stdout) ou caminho de arquivo/named pipe.
+    pub fn open(target: &str, execution_id: &str) -> Result<Self> {
+        let out: Box<dyn Write + Send> = if target == STDOUT {
+            Box::new(std::io::stdout())
+        } else {
+            let file = OpenOptions::new()
+                .create(true)
+                .write(true)
+                .truncate(true)
+                .open(target)
+                .with_context(|| format!("Falha ao abrir --progress-stream {:?}", target))?;
+            Box::new(file)
+        };
+        Ok(Self::to_writer(out, execution_id))
+    }
+
+    /// Stream sobre qualquer destino (ex: um buffer em testes).
+    pub fn to_writer(out: Box<dyn Write + Send>, execution_id: &str) -> Self {
+        let (queue, events) = mpsc::sync_channel(QUEUE_CAPACITY);
+        let (finished, done) = mpsc::channel();
+        let execution_id = execution_id.to_string();
+        std::thread::spawn(move || {
+            write_events(out, &execution_id, events);
+            let _ = finished.send(());
+        });
+        Self {
+            queue: Some(queue),
+            done: Mutex::new(done),
+            lagging: AtomicBool::new(false),
+        }
+    }
+
+    /// O step vai rodar.
+    pub fn step_started(&self, step: &Step) {
+        self.emit(StreamEvent::StepStarted {
+            step_id: step.id.clone(),
+            action: step.action.clone(),
+        });
+    }
+
+    /// A tentativa `attempt` falhou; a próxima começa após `backoff_ms`.
+    pub fn retry(&self, step_id: &str, attempt: u32, backoff_ms: u64, error: Option<&str>) {
+        self.emit(StreamEvent::Retry {
+            step_id: step_id.to_string(),
+            attempt,
+            backoff_ms,
+            error: error.map(String::from),
+        });
+    }
+
+    /// O step terminou: uma linha por extração que falhou e o `step_finished`.
+    pub fn record(&self, result: &StepResult) {
+        for extraction in result.extractions.iter().flatten() {
+            if !extraction.success {
+                self.emit(StreamEvent::ExtractionFailed {
+                    step_id: result.step_id.clone(),
+                    extraction: extraction.clone(),
+                });
+            }
+        }
+        self.emit(StreamEvent::StepFinished(StepOutcome::from_result(result)));
+    }
+
+    /// Último evento do plano.
+    pub fn finish(&self, success: bool) {
+        self.emit(StreamEvent::PlanFinished { success });
+    }
+
+    /// Enfileira o evento sem bloquear o step.
+    fn emit(&self, event: StreamEvent) {
+        let Some(queue) = &self.queue else {
+            return;
+        };
+        match queue.try_send((Utc::now().to_rfc3339(), event)) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => {
+                if !self.lagging.swap(true, Ordering::Relaxed) {
+                    warn!("Progress stream reader is not keeping up; dropping events");
+                }
+            }
+            // A thread parou após uma falha de escrita (já avisada).
+            Err(TrySendError::Disconnected(_)) => {}
+        }
+    }
+}
+
+impl Drop for ProgressStream {
+    /// Fecha a fila e espera a thread escrever o que restou, até `DRAIN_TIMEOUT`.
+    fn drop(&mut self) {
+        self.queue.take();
+        let done = self.done.lock().expect("progress stream");
+        if let Err(RecvTimeoutError::Timeout) = done.recv_timeout(DRAIN_TIMEOUT) {
+            warn!("Progress stream reader stalled; pending events dropped");
+        }
+    }
+}
+
+/// Laço da thread de escrita: numera e escreve cada evento até a fila fechar.
+fn write_events(
+    mut out: Box<dyn Write + Send>,
+    execution_id: &str,
+    events: Receiver<(String, StreamEvent)>,
+) {
+    for (sequence, (timestamp, event)) in (1..).zip(events) {
+        let line = StreamLine {
+            execution_id,
+            sequence,
+            timestamp,
+            event,
+        };
+        let mut bytes = serde_json::to_vec(&line).expect("evento serializável");
+        bytes.push(b'\n');
+        if let Err(e) = out.write_all(&bytes).and_then(|()| out.flush()) {
+            warn!(error = %e, "Progress stream closed; further events dropped");
+            return;
+        }
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::StepStatus;
+    use serde_json::Value;
+    use std::sync::Arc;
+
+    /// Buffer compartilhado para ler o que foi escrito.
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_events_are_one_json_per_line_in_order() {
+        let buffer = Shared::default();
+        let stream = ProgressStream::to_writer(Box::new(buffer.clone()), "exec-9");
+        let step = Step {
+            id: "login".to_string(),
+            action: "http_request".to_string(),
+            ..Default::default()
+        };
+
+        stream.step_started(&step);
+        stream.retry("login", 1, 500, Some("Assertion failed: status_code"));
+        stream.record(&StepResult {
+            step_id: "login".to_string(),
+            status: StepStatus::Passed,
+            attempt: 2,
+            extractions: Some(vec![ExtractionResult::failure(
+                "token".to_string(),
+                "body".to_string(),
+                "$.token".to_string(),
+                "Path not found".to_string(),
+            )]),
+            ..Default::default()
+        });
+        stream.finish(true);
+        drop(stream);
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let events: Vec<&str> = lines.iter().map(|l| l["event"].as_str().unwrap()).collect();
+        assert_eq!(
+            events,
+            [
+                "step_started",
+                "retry",
+                "extraction_failed",
+                "step_finished",
+                "plan_finished"
+            ]
+        );
+        assert!(lines.iter().all(|l| l["execution_id"] == "exec-9"));
+        assert_eq!(lines[4]["sequence"], 5);
+        assert_eq!(lines[1]["backoff_ms"], 500);
+        assert_eq!(lines[2]["extraction"]["target"], "token");
+        assert_eq!(lines[3]["attempt"], 2);
+        assert_eq!(lines[4]["success"], true);
+    }
+
+    /// Destino que só aceita escrita depois de `gate` ser liberado.
+    struct Stalled {
+        gate: Receiver<()>,
+        buffer: Shared,
+    }
+
+    impl Write for Stalled {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            let _ = self.gate.recv();
+            self.buffer.write(buf)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_stalled_reader_does_not_block_steps() {
+        let (release, gate) = mpsc::channel();
+        let buffer = Shared::default();
+        let out = Stalled {
+            gate,
+            buffer: buffer.clone(),
+        };
+        let stream = ProgressStream::to_writer(Box::new(out), "exec-9");
+
+        // Com o leitor travado, emitir além da capacidade não espera.
+        for attempt in 0..(QUEUE_CAPACITY as u32 * 3) {
+            stream.retry("login", attempt, 0, None);
+        }
+        drop(release);
+        drop(stream);
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let sequences: Vec<u64> = output
+            .lines()
+            .map(|line| {
+                serde_json::from_str::<Value>(line).unwrap()["sequence"]
+                    .as_u64()
+                    .unwrap()
+            })
+            .collect();
+        assert!(sequences.len() <= QUEUE_CAPACITY + 1);
+        assert!(sequences.iter().copied().eq(1..=sequences.len() as u64));
+    }
+}
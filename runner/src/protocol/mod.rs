@@ -47,9 +47,11 @@
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use crate::extractors::ExtractionResult;
+use crate::metadata::RunMetadata;
+use crate::quality_gate::GateResult;
 
 // ============================================================================
 // ESTRUTURA PRINCIPAL: PLAN
@@ -127,6 +129,34 @@ pub struct Meta {
     ///
     /// Ex: "2024-01-15T12:00:00Z"
     pub created_at: String,
+
+    /// Quem gerou o plano (versão do Brain, hash do prompt, modelo).
+    ///
+    /// Repetido no relatório e nos spans OTEL, para correlacionar planos
+    /// ruins com a versão do Brain que os produziu.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generator: Option<PlanGenerator>,
+}
+
+/// Origem de um plano gerado automaticamente (`meta.generator`).
+///
+/// ## Exemplo:
+/// ```json
+/// "generator": { "brain_version": "0.9.2", "prompt_hash": "sha256:4f1c...", "model": "gpt-4o" }
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct PlanGenerator {
+    /// Versão do Brain que gerou o plano.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub brain_version: Option<String>,
+
+    /// Hash do prompt usado na geração.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_hash: Option<String>,
+
+    /// Modelo de linguagem usado.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
 }
 
 // ============================================================================
@@ -162,6 +192,565 @@ pub struct Config {
     /// Ex: { "env": "staging", "admin_email": "admin@test.com" }
     #[serde(default)]
     pub variables: HashMap<String, Value>,
+
+    /// Header de correlação injetado automaticamente em todo step HTTP.
+    ///
+    /// Carrega o `execution_id` da execução; o ID do step vai junto em
+    /// `X-Step-Id`. Facilita achar nos logs do servidor as requisições
+    /// feitas por um run. String vazia desativa a injeção.
+    ///
+    /// Padrão: "X-Execution-Id"
+    #[serde(default = "default_correlation_header")]
+    pub correlation_header: String,
+
+    /// Configurações do cliente HTTP (pool de conexões, HTTP/2, user-agent).
+    ///
+    /// Opcional: quando ausente, usa os padrões do reqwest.
+    #[serde(default)]
+    pub http: HttpClientConfig,
+
+    /// Família de endereços usada na resolução DNS (`v4`, `v6` ou `auto`).
+    ///
+    /// Em hosts dual-stack, fixa se as conexões saem por IPv4 ou IPv6.
+    #[serde(default, skip_serializing_if = "IpPreference::is_auto")]
+    pub ip_preference: IpPreference,
+
+    /// Verificação de prontidão executada antes do primeiro step.
+    ///
+    /// Aguarda o ambiente responder (health check HTTP ou porta TCP),
+    /// substituindo steps de "sleep" no início de planos.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_for: Option<WaitFor>,
+
+    /// Fragmentos de `params` reutilizáveis, referenciados por nome em `step.template`.
+    ///
+    /// Ex: `{ "authed_json": { "headers": { "Authorization": "Bearer ${token}" } } }`
+    /// O template é mesclado por baixo dos params do step antes da interpolação.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub request_templates: HashMap<String, Value>,
+
+    /// Extrações aplicadas a **toda** resposta HTTP, quando o valor existir.
+    ///
+    /// Cada valor vai para `<step_id>.<target>` (ex: `${login.request_id}`),
+    /// e as regras valem também para steps que falharam, justamente onde
+    /// dados como `X-Request-Id` e `$.error.code` ajudam no debug.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub auto_extract: Vec<Extraction>,
+
+    /// Sessões nomeadas (um "usuário" cada), referenciadas por `step.session`.
+    ///
+    /// Steps da mesma sessão compartilham cookies, conexões e o token
+    /// renovado; steps sem sessão continuam isolados.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub sessions: HashMap<String, SessionConfig>,
+
+    /// Atores (ex: `admin`, `customer`), referenciados por `step.actor`.
+    ///
+    /// Cada ator tem variáveis, headers, cookies e token próprios, para
+    /// testar limites de permissão no mesmo plano.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub actors: HashMap<String, ActorConfig>,
+
+    /// Agentes remotos (ex: `eu-west`), referenciados por `step.agent`.
+    ///
+    /// Steps com `agent` são executados pelo `runner agent` daquela região
+    /// e o resultado volta para o relatório local.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub agents: HashMap<String, AgentConfig>,
+
+    /// Assinadores de requisição (ex: `partner`), referenciados por `params.signer`.
+    ///
+    /// A assinatura é calculada depois da interpolação, logo antes do envio
+    /// (ver `executors::http_signer`).
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub signers: HashMap<String, SignerConfig>,
+
+    /// Regiões em que o plano pode rodar (`--regions`), cada uma com sua
+    /// `base_url` (ver `regions`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub regions: Vec<Region>,
+
+    /// Critérios que decidem o status final da execução (ver `quality_gate`).
+    ///
+    /// Sem gate, a execução só passa se todos os steps passarem.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality_gate: Option<QualityGate>,
+
+    /// Autenticação obtida pelo Runner antes dos steps (ex: OIDC).
+    ///
+    /// O token fica em `${auth.access_token}` e é renovado sozinho.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<AuthConfig>,
+
+    /// Listener dos steps `webhook_wait` (endereço local e URL pública).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<WebhookConfig>,
+}
+
+/// Nome padrão do header de correlação.
+pub fn default_correlation_header() -> String {
+    "X-Execution-Id".to_string()
+}
+
+/// Preflight de prontidão (`config.wait_for`).
+///
+/// ## Para todos entenderem:
+///
+/// Ambientes efêmeros (containers, preview deploys) demoram para subir.
+/// Em vez de um step `wait` de 30 segundos "por garantia", o Runner
+/// consulta o ambiente repetidamente e começa assim que ele responder.
+///
+/// ## Exemplos:
+///
+/// ```json
+/// "wait_for": { "path": "/health", "timeout_ms": 60000 }
+/// "wait_for": { "tcp": "localhost:5432", "interval_ms": 500 }
+/// ```
+///
+/// Com `tcp`, basta a porta aceitar conexões. Sem `tcp`, faz GET em
+/// `path` (relativo à `base_url`, ou URL completa) até receber 2xx.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct WaitFor {
+    /// Endpoint de health check (padrão: "/health").
+    #[serde(default = "default_wait_for_path")]
+    pub path: String,
+
+    /// Endereço `host:porta` a verificar via TCP (em vez de HTTP).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tcp: Option<String>,
+
+    /// Tempo máximo de espera em ms (padrão: 60000).
+    #[serde(default = "default_wait_for_timeout_ms")]
+    pub timeout_ms: u64,
+
+    /// Intervalo entre tentativas em ms (padrão: 1000).
+    #[serde(default = "default_wait_for_interval_ms")]
+    pub interval_ms: u64,
+}
+
+fn default_wait_for_path() -> String {
+    "/health".to_string()
+}
+
+fn default_wait_for_timeout_ms() -> u64 {
+    60_000
+}
+
+fn default_wait_for_interval_ms() -> u64 {
+    1_000
+}
+
+/// Configurações do cliente HTTP compartilhado pelos steps `http_request`.
+///
+/// ## Para todos entenderem:
+///
+/// O Runner cria **um** cliente HTTP por execução e reaproveita as
+/// conexões entre os steps (connection pool). Estes campos ajustam esse
+/// cliente para APIs com comportamento específico:
+///
+/// | Campo                   | Efeito                                          |
+/// |-------------------------|-------------------------------------------------|
+/// | `pool_max_idle_per_host`| Máximo de conexões ociosas mantidas por host     |
+/// | `pool_idle_timeout_ms`  | Tempo até fechar uma conexão ociosa             |
+/// | `http2_adaptive_window` | Ajuste dinâmico da janela de fluxo HTTP/2       |
+/// | `user_agent`            | Valor do header `User-Agent` de toda requisição |
+/// | `response_cache`        | GET/HEAD idênticos vão à rede uma vez por execução |
+/// | `header_capture`        | Headers de resposta no relatório (mascarados, com limite) |
+///
+/// ## Exemplo:
+///
+/// ```json
+/// "http": {
+///   "pool_max_idle_per_host": 4,
+///   "pool_idle_timeout_ms": 30000,
+///   "http2_adaptive_window": true,
+///   "user_agent": "aqa-runner/0.1"
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct HttpClientConfig {
+    /// Máximo de conexões ociosas por host no pool (`0` desativa o reuso).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool_max_idle_per_host: Option<usize>,
+
+    /// Tempo (ms) até uma conexão ociosa ser fechada (padrão do reqwest: 90s).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool_idle_timeout_ms: Option<u64>,
+
+    /// Ativa a janela adaptativa de controle de fluxo do HTTP/2.
+    #[serde(default)]
+    pub http2_adaptive_window: bool,
+
+    /// User-Agent enviado em todas as requisições.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+
+    /// Cache de respostas GET/HEAD idênticas durante a execução.
+    #[serde(default)]
+    pub response_cache: bool,
+
+    /// Captura dos headers de resposta em `http_details.response_headers`.
+    #[serde(default)]
+    pub header_capture: HeaderCaptureConfig,
+}
+
+/// Política de captura de headers de resposta (`config.http.header_capture`).
+///
+/// Todo step HTTP guarda os headers recebidos no relatório (`X-Request-Id`,
+/// `Cache-Control`, `Age`...). Credenciais (`Authorization`, `Cookie`,
+/// `Set-Cookie`, `X-Api-Key`...) e os nomes de `redact` aparecem como
+/// `***`; passado `max_bytes`, o resto é descartado e
+/// `response_headers_truncated` fica `true`.
+///
+/// ```json
+/// "http": { "header_capture": { "redact": ["x-internal-token"], "max_bytes": 4096 } }
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct HeaderCaptureConfig {
+    /// Headers extras mascarados (sem diferenciar maiúsculas).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redact: Vec<String>,
+
+    /// Tamanho máximo (nomes + valores) guardado por resposta; `0` desativa.
+    #[serde(default = "default_header_capture_max_bytes")]
+    pub max_bytes: usize,
+}
+
+impl Default for HeaderCaptureConfig {
+    fn default() -> Self {
+        Self {
+            redact: Vec::new(),
+            max_bytes: default_header_capture_max_bytes(),
+        }
+    }
+}
+
+fn default_header_capture_max_bytes() -> usize {
+    8 * 1024
+}
+
+/// Sessão HTTP nomeada (`config.sessions.<nome>`).
+///
+/// ## Para todos entenderem:
+///
+/// Para simular dois usuários no mesmo plano (ex: comprador e vendedor),
+/// cada um precisa dos próprios cookies e do próprio token. Cada sessão
+/// tem um cliente HTTP separado, com cookie jar e pool de conexões só dela:
+///
+/// ```json
+/// "sessions": {
+///   "buyer": {
+///     "refresh": {
+///       "path": "/auth/refresh",
+///       "body": { "refresh_token": "${buyer_refresh}" },
+///       "token_path": "$.access_token"
+///     }
+///   },
+///   "seller": {}
+/// }
+/// ```
+///
+/// E nos steps: `{ "id": "add_to_cart", "session": "buyer", ... }`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct SessionConfig {
+    /// Renovação de token quando a API responde 401 (opcional).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh: Option<TokenRefresh>,
+}
+
+/// Hook de renovação de token de uma sessão.
+///
+/// Quando um step da sessão recebe um status de `on_status`, o Runner faz
+/// esta requisição (com o cliente da sessão), lê o token em `token_path`
+/// e repete o step uma vez. Daí em diante, toda requisição da sessão leva
+/// `<header>: <prefix><token>`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct TokenRefresh {
+    /// Método HTTP (padrão: "POST").
+    #[serde(default = "default_refresh_method")]
+    pub method: String,
+
+    /// Path relativo à `base_url` (ou URL completa), com interpolação.
+    pub path: String,
+
+    /// Headers da requisição de renovação.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+
+    /// Body JSON da requisição de renovação (com interpolação).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+
+    /// JSONPath do token na resposta (ex: "$.access_token").
+    pub token_path: String,
+
+    /// Header que recebe o token (padrão: "Authorization").
+    #[serde(default = "default_refresh_header")]
+    pub header: String,
+
+    /// Prefixo do valor do header (padrão: "Bearer ").
+    #[serde(default = "default_refresh_prefix")]
+    pub prefix: String,
+
+    /// Status que disparam a renovação (padrão: [401]).
+    #[serde(default = "default_refresh_on_status")]
+    pub on_status: Vec<u16>,
+}
+
+fn default_refresh_method() -> String {
+    "POST".to_string()
+}
+
+fn default_refresh_header() -> String {
+    "Authorization".to_string()
+}
+
+fn default_refresh_prefix() -> String {
+    "Bearer ".to_string()
+}
+
+fn default_refresh_on_status() -> Vec<u16> {
+    vec![401]
+}
+
+/// Autenticação gerenciada pelo Runner (`config.auth`).
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct AuthConfig {
+    /// Login OpenID Connect (discovery + password ou device flow).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oidc: Option<OidcConfig>,
+}
+
+/// Login OpenID Connect (`config.auth.oidc`).
+///
+/// ## Para todos entenderem:
+///
+/// Muitos IdPs não liberam `client_credentials` para usuários de teste.
+/// O Runner faz o login de um usuário de verdade e expõe o token:
+///
+/// | Fluxo          | Como o usuário se autentica                        |
+/// |----------------|----------------------------------------------------|
+/// | `password`     | `username`/`password` do plano (resource owner)    |
+/// | `device_code`  | Alguém abre a URL exibida no log e digita o código |
+///
+/// ## Exemplo:
+///
+/// ```json
+/// "auth": { "oidc": {
+///   "issuer": "https://idp.example.com/realms/qa",
+///   "client_id": "aqa-runner",
+///   "flow": "password",
+///   "username": "${qa_user}", "password": "${env:QA_PASSWORD}"
+/// } }
+/// ```
+///
+/// Steps usam `"Authorization": "Bearer ${auth.access_token}"`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct OidcConfig {
+    /// Issuer; o discovery lê `<issuer>/.well-known/openid-configuration`.
+    pub issuer: String,
+
+    /// Client ID registrado no IdP.
+    pub client_id: String,
+
+    /// Client secret (clientes confidenciais; com interpolação).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+
+    /// Fluxo de login (padrão: "password").
+    #[serde(default)]
+    pub flow: OidcFlow,
+
+    /// Usuário do fluxo `password` (com interpolação).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+
+    /// Senha do fluxo `password` (com interpolação).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+
+    /// Escopos (padrão: "openid").
+    #[serde(default = "default_oidc_scope")]
+    pub scope: String,
+
+    /// Envia PKCE (S256) no device flow, para IdPs que o exigem.
+    #[serde(default)]
+    pub pkce: bool,
+
+    /// Renova o token quando faltar menos que isso para expirar (padrão: 30000).
+    #[serde(default = "default_oidc_refresh_margin_ms")]
+    pub refresh_margin_ms: u64,
+}
+
+/// Fluxo de login OIDC.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OidcFlow {
+    /// Resource owner password credentials.
+    #[default]
+    Password,
+    /// Device authorization grant (RFC 8628).
+    DeviceCode,
+}
+
+fn default_oidc_scope() -> String {
+    "openid".to_string()
+}
+
+fn default_oidc_refresh_margin_ms() -> u64 {
+    30_000
+}
+
+/// Ator de um cenário multiusuário (`config.actors.<nome>`).
+///
+/// ## Para todos entenderem:
+///
+/// Testar "cliente não pode apagar produto, admin pode" exige dois usuários
+/// logados ao mesmo tempo. Cada ator é uma sessão HTTP própria (cookies,
+/// conexões, token) com variáveis próprias:
+///
+/// ```json
+/// "actors": {
+///   "admin":    { "variables": { "email": "admin@shop.com", "token": null },
+///                 "headers": { "Authorization": "Bearer ${token}" } },
+///   "customer": { "variables": { "email": "ana@mail.com", "token": null },
+///                 "headers": { "Authorization": "Bearer ${token}" } }
+/// }
+/// ```
+///
+/// Num step com `"actor": "customer"`, `${email}` e `${token}` são os do
+/// cliente, e uma extração para `token` atualiza **só** o token do cliente.
+/// Fora dos steps do ator, os valores ficam em `${customer.token}`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct ActorConfig {
+    /// Variáveis do ator (sobrepõem as globais nos steps do ator).
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub variables: HashMap<String, Value>,
+
+    /// Headers enviados em todo `http_request` do ator (com interpolação).
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+
+    /// Renovação de token, como em `config.sessions` (opcional).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh: Option<TokenRefresh>,
+}
+
+/// Listener de callbacks (`config.webhook`) usado pelos steps `webhook_wait`.
+///
+/// ## Exemplo (túnel/relay na frente do listener):
+///
+/// ```json
+/// "webhook": { "bind": "0.0.0.0:8765", "public_url": "https://abc123.ngrok.app" }
+/// ```
+///
+/// Sem `public_url`, a URL divulgada é a do próprio listener
+/// (`http://127.0.0.1:<porta>`), que só serve para serviços locais.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct WebhookConfig {
+    /// Endereço `host:porta` do listener (padrão: `127.0.0.1:0`, porta livre).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bind: Option<String>,
+
+    /// URL pública que encaminha para o listener (túnel ou relay).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_url: Option<String>,
+}
+
+/// Um agente remoto (`runner agent`) em `config.agents`.
+///
+/// ## Exemplo:
+///
+/// ```json
+/// "agents": {
+///   "eu-west": { "url": "https://runner-eu.internal:7700", "token": "${env:AGENT_TOKEN}" }
+/// }
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct AgentConfig {
+    /// URL base do agente.
+    pub url: String,
+
+    /// Token enviado como `Authorization: Bearer` (com interpolação).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+
+    /// Tempo máximo de cada despacho em ms (padrão: 60000).
+    #[serde(default = "default_agent_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+/// Um assinador de `config.signers`: o tipo e as opções dele.
+///
+/// ```json
+/// "signers": { "partner": { "type": "hmac", "secret": "${env:PARTNER_SECRET}" } }
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct SignerConfig {
+    /// `hmac`, `sigv4`, `script` ou um tipo registrado por plugin.
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// Opções do tipo (com interpolação de variáveis).
+    #[serde(flatten)]
+    pub options: serde_json::Map<String, Value>,
+}
+
+/// Uma região de `config.regions`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct Region {
+    /// Nome usado em `--regions` e no relatório (ex: `eu`).
+    pub name: String,
+
+    /// `base_url` do plano nesta região.
+    pub base_url: String,
+
+    /// Variáveis que sobrepõem `config.variables` nesta região.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub variables: HashMap<String, Value>,
+}
+
+/// `config.quality_gate`: orçamento de falhas e latência da execução.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct QualityGate {
+    /// Máximo de steps com status `failed`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_failed_steps: Option<usize>,
+
+    /// Máximo do percentil 95 da latência HTTP, em ms.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_p95_latency_ms: Option<u64>,
+
+    /// Mínimo de steps executados que passaram, em porcentagem (0-100).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_pass_rate_pct: Option<f64>,
+}
+
+/// `config.ip_preference`: família de endereços das conexões HTTP.
+///
+/// | Valor  | Endereços usados                                      |
+/// |--------|-------------------------------------------------------|
+/// | `auto` | Todos, na ordem do resolver do sistema (padrão)       |
+/// | `v4`   | Só IPv4 (falha se o host não tiver registro A)        |
+/// | `v6`   | Só IPv6 (falha se o host não tiver registro AAAA)     |
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IpPreference {
+    #[default]
+    Auto,
+    V4,
+    V6,
+}
+
+impl IpPreference {
+    pub fn is_auto(&self) -> bool {
+        *self == Self::Auto
+    }
+}
+
+/// Valor padrão de `AgentConfig.timeout_ms`.
+fn default_agent_timeout_ms() -> u64 {
+    60_000
 }
 
 // ============================================================================
@@ -181,10 +770,11 @@ pub struct Config {
 /// ## Campos opcionais:
 /// - `description`: Texto descritivo para logs
 /// - `depends_on`: IDs de steps que devem executar antes
+/// - `condition`: Só executa se a condição sobre o contexto for verdadeira
 /// - `assertions`: Validações a fazer após a execução
 /// - `extract`: Dados a extrair da resposta
 /// - `recovery_policy`: O que fazer em caso de falha
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
 pub struct Step {
     /// Identificador único do step dentro do plano.
     ///
@@ -208,12 +798,32 @@ pub struct Step {
     #[serde(default)]
     pub depends_on: Vec<String>,
 
+    /// Condição sobre o contexto avaliada antes de executar o step.
+    ///
+    /// Falsa, o step não roda e fica `not_run` (`skip_reason: condition_false`),
+    /// sem reprovar a execução nem os dependentes.
+    /// Ex: `{ "path": "feature_flags.checkout_v2", "operator": "eq", "value": true }`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<StepCondition>,
+
     /// Tipo de ação a executar.
     ///
     /// Valores suportados:
     /// - "http_request": Faz uma requisição HTTP
     /// - "wait": Espera um tempo em milissegundos
     /// - "sleep": Alias de "wait"
+    /// - "set_variable": Define variáveis no contexto (com interpolação)
+    /// - "log": Registra uma mensagem interpolada no relatório
+    /// - "assert": Avalia assertions sobre variáveis do contexto
+    /// - "transform": Aplica JSONPath/operação a variáveis e salva o resultado
+    /// - "tcp_send": Envia um payload por TCP e verifica a resposta
+    /// - "udp_send": Envia um datagrama UDP e verifica a resposta
+    /// - "grpc_health": Consulta o health check padrão do gRPC
+    /// - "grpc_call": Faz uma chamada gRPC unária com a mensagem em JSON
+    /// - "shell_command": Roda um comando local isolado (exige `--allow-shell`)
+    /// - "graphql_request": Envia uma query/mutation GraphQL
+    /// - "rate_limit_probe": Dispara uma rajada e verifica quando a API limita (429)
+    /// - "webhook_wait": Espera o callback (webhook) do sistema testado
     pub action: String,
 
     /// Parâmetros específicos da ação.
@@ -234,6 +844,25 @@ pub struct Step {
     /// ```json
     /// { "duration_ms": 1000 }
     /// ```
+    ///
+    /// Para "set_variable":
+    /// ```json
+    /// { "variables": { "full_name": "${first} ${last}" } }
+    /// ```
+    ///
+    /// Para "log":
+    /// ```json
+    /// { "message": "Pedido criado: ${order_id}" }
+    /// ```
+    ///
+    /// Para "transform":
+    /// ```json
+    /// { "from": "cart", "path": "$.items[*].price", "op": "sum", "target": "total" }
+    /// ```
+    ///
+    /// Para "assert" não há parâmetros (pode ser omitido); as verificações
+    /// ficam em `assertions` com `"type": "variable"`.
+    #[serde(default)]
     pub params: Value,
 
     /// Lista de validações a fazer após a execução.
@@ -253,6 +882,146 @@ pub struct Step {
     /// Define se deve fazer retry, ignorar, ou falhar imediatamente.
     #[serde(default)]
     pub recovery_policy: Option<RecoveryPolicy>,
+
+    /// Reavalia requisição + assertions até passarem (consistência eventual).
+    ///
+    /// Independente de `recovery_policy`: só repete falhas de assertion
+    /// (não erros de rede) e não conta em `attempt` nem no limite global
+    /// de retries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assertions_retry: Option<AssertionsRetry>,
+
+    /// Orçamento de latência esperado (SLO), em ms.
+    ///
+    /// Não reprova o step (para isso existe a assertion `latency`): só entra
+    /// no relatório e no percentual de conformidade de `summary.slo`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_budget_ms: Option<u64>,
+
+    /// Step de aquecimento (cold start, JIT, cache frio).
+    ///
+    /// Executa normalmente, mas fica fora das estatísticas de latência:
+    /// assertions `latency` são ignoradas e `latency_budget_ms` não conta no SLO.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub warmup: bool,
+
+    /// Desvio de relógio simulado, em ms (negativo = atrasado).
+    ///
+    /// Desloca `${now}`, `${timestamp}` e afins neste step e, em
+    /// `http_request`, envia um header `Date` com o horário deslocado.
+    /// Testa janelas de assinatura e expiração de token no servidor.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clock_skew_ms: Option<i64>,
+
+    /// Fan-out: executa o step uma vez para cada elemento de um array.
+    ///
+    /// Ex: `{ "items": "user_ids", "as": "user_id", "max_parallel": 5 }`
+    /// roda o step para cada ID extraído, com `${user_id}` disponível.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parallel_foreach: Option<ParallelForeach>,
+
+    /// Nome de um template de `config.request_templates` aplicado aos params.
+    ///
+    /// Os params do step têm prioridade; objetos (ex: `headers`) são mesclados.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+
+    /// Teste negativo: o step passa somente se falhar (assertion ou erro).
+    ///
+    /// Ex: token inválido deve receber 401 — com `expect_failure: true`
+    /// basta escrever a assertion do caminho feliz (`status_code eq 200`).
+    #[serde(default)]
+    pub expect_failure: bool,
+
+    /// Tags do step (ex: `["smoke", "known-broken"]`), usadas por `--tags`, perfis e quarentena.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Sessão de `config.sessions` usada pelo step (apenas `http_request`).
+    ///
+    /// Sem sessão, o step usa o cliente compartilhado sem cookies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+
+    /// Ator de `config.actors` que executa o step.
+    ///
+    /// Aplica as variáveis do ator e, em `http_request`, a sessão HTTP dele
+    /// (tem prioridade sobre `session`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+
+    /// Agente remoto de `config.agents` que executa o step (ver `agents`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+
+    /// No modo suíte, executa uma vez e reaproveita as variáveis nos
+    /// planos seguintes (ver `suite`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub shared: bool,
+
+    /// Reaproveita as variáveis do step entre execuções, dentro do TTL
+    /// (ver `step_cache`). Ex: login que emite um token de 1h.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<StepCache>,
+}
+
+/// Condição de execução de um step (`Step.condition`).
+///
+/// Mesma semântica de uma assertion `variable` do step `assert`: `path`
+/// é o nome da variável (com navegação por ponto) e `value` é interpolado.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct StepCondition {
+    /// Variável do contexto (ex: `"user.role"`).
+    pub path: String,
+
+    /// Operador de comparação (`eq`, `neq`, `exists`, `not_exists`...).
+    pub operator: String,
+
+    /// Valor esperado (ignorado por `exists`/`not_exists`).
+    #[serde(default)]
+    pub value: Value,
+}
+
+/// Configuração de fan-out de um step sobre um array do contexto.
+///
+/// ## Para todos entenderem:
+///
+/// Uma listagem retornou 50 IDs e queremos consultar cada um. Em vez de
+/// escrever 50 steps, marcamos um único step com `parallel_foreach` e o
+/// Runner o executa uma vez por ID, no máximo `max_parallel` por vez.
+///
+/// ## Exemplo:
+/// ```json
+/// {
+///   "id": "get_user",
+///   "action": "http_request",
+///   "params": { "method": "GET", "path": "/users/${user_id}" },
+///   "parallel_foreach": { "items": "user_ids", "as": "user_id", "max_parallel": 5 }
+/// }
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ParallelForeach {
+    /// Variável do contexto com o array (`"user_ids"` ou `"${user_ids}"`).
+    pub items: String,
+
+    /// Nome da variável que recebe o elemento atual.
+    /// O índice fica em `<as>_index`.
+    #[serde(rename = "as", default = "default_foreach_item_var")]
+    pub item_var: String,
+
+    /// Máximo de iterações simultâneas.
+    #[serde(default = "default_foreach_max_parallel")]
+    pub max_parallel: usize,
+}
+
+/// Nome padrão da variável de iteração.
+fn default_foreach_item_var() -> String {
+    "item".to_string()
+}
+
+/// Paralelismo padrão do fan-out (igual ao limite padrão de steps paralelos).
+fn default_foreach_max_parallel() -> usize {
+    10
 }
 
 // ============================================================================
@@ -277,11 +1046,15 @@ pub struct Step {
 /// - `lte`, `gte`: Menor ou igual, maior ou igual
 /// - `contains`: Contém substring
 /// - `exists`, `not_exists`: Campo existe ou não
-#[derive(Debug, Deserialize, Serialize, Clone)]
+/// - `in`, `not_in`: Valor está (ou não) na lista (`status_code`: `[200, 201, 204]`)
+/// - `approx_eq`: Igualdade numérica com `tolerance`
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
 pub struct Assertion {
     /// Tipo de assertion.
     ///
-    /// Valores: "status_code", "json_body", "header", "latency"
+    /// Valores: "status_code", "json_body", "header", "latency",
+    /// "content_type", "content_encoding", "body_size", "body_signature",
+    /// "body_sha256" (respostas binárias), "variable" (apenas na action `assert`)
     #[serde(rename = "type")] // No JSON é "type", mas em Rust "type" é palavra reservada
     pub assertion_type: String,
 
@@ -293,10 +1066,12 @@ pub struct Assertion {
     /// Valor esperado para a comparação.
     ///
     /// O tipo depende da assertion:
-    /// - status_code: número (ex: 200)
+    /// - status_code: número (ex: 200), ou lista com `in`/`not_in`
     /// - json_body: qualquer valor JSON
     /// - header: string
     /// - latency: número em ms
+    /// - body_size: número em bytes; body_signature: formato ou hex;
+    ///   body_sha256: hash em hex; content_type: media type
     pub value: Value,
 
     /// Caminho para o campo (usado em json_body e header).
@@ -305,6 +1080,67 @@ pub struct Assertion {
     /// Para header: nome do header (ex: "Content-Type")
     #[serde(default)]
     pub path: Option<String>,
+
+    /// Como tratar headers repetidos (ex: vários `Set-Cookie`).
+    ///
+    /// - `"any"` (padrão): passa se algum valor atender ao operador
+    /// - `"all"`: todos os valores precisam atender
+    #[serde(default, rename = "match", skip_serializing_if = "Option::is_none")]
+    pub match_mode: Option<String>,
+
+    /// Tolerância do operador `approx_eq` (padrão: absoluta de 1e-9).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tolerance: Option<Tolerance>,
+
+    /// Formato regional do valor atual (ex: `"1.234,56"`, `"01/06/2024"`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<AssertionLocale>,
+}
+
+/// Tolerância numérica de `approx_eq`.
+///
+/// Passa se `|atual - esperado| <= max(abs, rel * max(|atual|, |esperado|))`.
+///
+/// ## Exemplo:
+///
+/// ```json
+/// { "type": "json_body", "path": "$.total", "operator": "approx_eq",
+///   "value": 59.97, "tolerance": { "abs": 0.01 } }
+/// ```
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Tolerance {
+    /// Diferença absoluta aceita (ex: 0.01 para centavos).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub abs: Option<f64>,
+
+    /// Diferença relativa aceita (ex: 0.001 = 0,1%).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rel: Option<f64>,
+}
+
+/// Como ler números e datas formatados para uma região.
+///
+/// Strings do valor atual no formato informado são convertidas antes da
+/// comparação: `"R$ 1.234,56"` vira `1234.56` e `"01/06/2024"` vira
+/// `"2024-06-01"`. O `value` esperado continua no formato do plano.
+///
+/// ## Exemplo:
+///
+/// ```json
+/// { "type": "json_body", "path": "$.total", "operator": "gte",
+///   "value": 1000, "locale": { "decimal": "," } }
+/// ```
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+pub struct AssertionLocale {
+    /// Separador decimal (`","` em pt-BR/de-DE, `"."` em en-US).
+    ///
+    /// O outro separador (`.`, `,`, espaço, `'`) é tratado como milhar.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimal: Option<String>,
+
+    /// Formato `strftime` de datas (ex: `"%d/%m/%Y"`, `"%d/%m/%Y %H:%M"`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_format: Option<String>,
 }
 
 // ============================================================================
@@ -356,6 +1192,24 @@ pub struct Extraction {
     /// Padrão: false (tolerante a falhas).
     #[serde(default)]
     pub critical: bool,
+
+    /// Visibilidade do valor extraído (padrão: `plan`).
+    ///
+    /// Com `step`, `${target}` só existe para os steps que declaram este
+    /// em `depends_on`, evitando que dois steps sobrescrevam a mesma chave.
+    #[serde(default)]
+    pub scope: ExtractionScope,
+}
+
+/// Visibilidade de uma extração (`extract[].scope`).
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtractionScope {
+    /// Visível para todos os steps seguintes (comportamento padrão).
+    #[default]
+    Plan,
+    /// Visível apenas para os dependentes declarados (`depends_on`).
+    Step,
 }
 
 // ============================================================================
@@ -405,9 +1259,73 @@ pub struct RecoveryPolicy {
     pub backoff_factor: f64,
 }
 
-/// Valor padrão para o fator de backoff.
-fn default_backoff_factor() -> f64 {
-    2.0
+/// Valor padrão para o fator de backoff.
+fn default_backoff_factor() -> f64 {
+    2.0
+}
+
+/// Repetição das assertions de um step (`assertions_retry`).
+///
+/// ## Para todos entenderem:
+///
+/// Depois de criar um pedido, a busca pode demorar alguns segundos para
+/// encontrá-lo (índice assíncrono). Não é um erro: basta perguntar de novo.
+///
+/// ```json
+/// "assertions_retry": { "max_attempts": 10, "interval_ms": 500 }
+/// ```
+///
+/// Diferente de `recovery_policy` (erros transitórios, com backoff), aqui
+/// o intervalo é fixo e as tentativas ficam em `assertion_attempts` no
+/// relatório, sem inflar as métricas de retry.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct AssertionsRetry {
+    /// Avaliações no total, incluindo a primeira.
+    pub max_attempts: u32,
+
+    /// Intervalo fixo entre avaliações em ms (padrão: 1000).
+    #[serde(default = "default_assertions_retry_interval_ms")]
+    pub interval_ms: u64,
+}
+
+fn default_assertions_retry_interval_ms() -> u64 {
+    1_000
+}
+
+/// Cache do step entre execuções (`cache`).
+///
+/// ## Para todos entenderem:
+///
+/// O login leva 2s e o token vale 1h. Rodando o plano 30 vezes seguidas,
+/// só a primeira precisa fazer o login de verdade:
+///
+/// ```json
+/// "cache": { "key": "login-admin", "ttl_s": 3000 }
+/// ```
+///
+/// Dentro do TTL (e antes do `exp` de um JWT produzido pelo step), o step
+/// não executa: as variáveis guardadas voltam ao contexto e o resultado
+/// traz `cache_hit`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct StepCache {
+    /// Nome da entrada no cache (compartilhado entre planos).
+    pub key: String,
+
+    /// Validade da entrada, em segundos.
+    pub ttl_s: u64,
+}
+
+/// Entrada de cache usada no lugar da execução do step.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct CacheHit {
+    /// `cache.key` do step.
+    pub key: String,
+
+    /// Quando a entrada foi gravada (RFC 3339).
+    pub stored_at: String,
+
+    /// Quando a entrada expira (RFC 3339).
+    pub expires_at: String,
 }
 
 // ============================================================================
@@ -449,6 +1367,14 @@ pub struct StepResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context_after: Option<HashMap<String, Value>>,
 
+    /// Diferença entre o contexto antes e depois do step.
+    ///
+    /// Substitui `context_before`/`context_after` no relatório (padrão),
+    /// evitando copiar o contexto inteiro a cada step.
+    /// Use `--full-context` para manter os snapshots completos.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_delta: Option<ContextDelta>,
+
     /// Resultados das extrações realizadas neste step.
     /// Inclui sucesso/falha de cada regra de extração.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -458,12 +1384,325 @@ pub struct StepResult {
     /// Inclui método, URL, status code e latência.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub http_details: Option<HttpDetails>,
+
+    /// Detalhes da troca de bytes (apenas para steps `tcp_send`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub socket_details: Option<SocketDetails>,
+
+    /// Detalhes do processo executado (apenas para steps `shell_command`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command_details: Option<CommandDetails>,
+
+    /// Rajada enviada e respostas (apenas para steps `rate_limit_probe`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit_details: Option<RateLimitDetails>,
+
+    /// Mensagens emitidas pelo step (ex: action `log`).
+    /// Já interpoladas, prontas para leitura no relatório.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logs: Option<Vec<String>>,
+
+    /// Resultados de cada iteração de um step com `parallel_foreach`,
+    /// na ordem dos elementos do array (`step_id` = `"<id>[<índice>]"`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iterations: Option<Vec<StepResult>>,
+
+    /// `execution_id` da execução anterior de onde este resultado foi
+    /// reaproveitado (`--retry-failed`). Ausente se o step foi executado.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reused_from: Option<String>,
+
+    /// Falha observada em um step com `expect_failure` (que por isso passou).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_failure: Option<String>,
+
+    /// Falha descartada por `recovery_policy.strategy: ignore` (que por isso passou).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ignored_error: Option<String>,
+
+    /// Por que o step não executou (`skipped`, `cancelled` ou `not_run`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip_reason: Option<SkipReason>,
+
+    /// Avaliações feitas por `assertions_retry` (separadas de `attempt`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assertion_attempts: Option<u32>,
+
+    /// Latência medida contra `latency_budget_ms` do step.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_budget: Option<LatencyBudget>,
+
+    /// Step de aquecimento (fora das estatísticas de latência).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub warmup: bool,
+
+    /// O que foi observado até o timeout do step (ausente sem timeout).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_capture: Option<TimeoutCapture>,
+
+    /// Agente remoto que executou o step (ausente se rodou localmente).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+
+    /// Assertions que falharam, com esperado e observado (`error` traz a primeira).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assertion_failures: Vec<AssertionFailure>,
+
+    /// Quando e em qual slot de worker o step rodou (`runner timeline`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeline: Option<StepTiming>,
+
+    /// Entrada de `cache` usada no lugar da execução (ausente se o step rodou).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_hit: Option<CacheHit>,
+}
+
+/// Posição de um step na linha do tempo da execução.
+///
+/// ## Para todos entenderem:
+///
+/// Com steps em paralelo, a duração sozinha não mostra onde a execução
+/// ficou "em fila". Cada step guarda quando começou, quando terminou, em
+/// qual slot de concorrência rodou e quanto esperou pelo contexto:
+///
+/// ```json
+/// { "started_at": "2026-01-05T10:00:00.120Z", "ended_at": "2026-01-05T10:00:00.480Z",
+///   "worker": 2, "lock_wait_ms": 310 }
+/// ```
+///
+/// `lock_wait_ms` alto em vários steps indica serialização pelo contexto
+/// compartilhado; lacunas entre steps dependentes indicam a cadeia do DAG.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct StepTiming {
+    /// Início (RFC 3339, milissegundos), já com o slot de worker ocupado.
+    pub started_at: String,
+
+    /// Fim do step (RFC 3339, milissegundos).
+    pub ended_at: String,
+
+    /// Slot de concorrência usado (0 em execução sequencial).
+    pub worker: usize,
+
+    /// Espera pelo lock do contexto compartilhado antes de executar.
+    #[serde(default)]
+    pub lock_wait_ms: u64,
+}
+
+/// Uma assertion que falhou, em formato estruturado.
+///
+/// ## Para todos entenderem:
+///
+/// `error` é texto para humanos ("Assertion failed: json_body 'data.id'
+/// eq 42 (got 41)"). Para renderizar um diff ou agrupar falhas em outra
+/// ferramenta, os mesmos dados vêm separados:
+///
+/// ```json
+/// { "type": "json_body", "operator": "eq", "path": "data.id",
+///   "expected": 42, "actual": 41, "message": "Assertion failed: ..." }
+/// ```
+///
+/// `actual` fica ausente quando não há valor observado (path inexistente,
+/// header ausente) ou o tipo de assertion não tem um valor único.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct AssertionFailure {
+    #[serde(rename = "type")]
+    pub assertion_type: String,
+    pub operator: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    pub expected: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actual: Option<Value>,
+    pub message: String,
+}
+
+/// Valores padrão de um StepResult.
+///
+/// Permite que executores preencham apenas os campos relevantes
+/// com `..Default::default()`.
+impl Default for StepResult {
+    fn default() -> Self {
+        Self {
+            step_id: String::new(),
+            status: StepStatus::Passed,
+            duration_ms: 0,
+            attempt: default_attempt(),
+            error: None,
+            context_before: None,
+            context_after: None,
+            context_delta: None,
+            extractions: None,
+            http_details: None,
+            socket_details: None,
+            command_details: None,
+            rate_limit_details: None,
+            logs: None,
+            iterations: None,
+            reused_from: None,
+            expected_failure: None,
+            ignored_error: None,
+            skip_reason: None,
+            assertion_attempts: None,
+            latency_budget: None,
+            warmup: false,
+            timeout_capture: None,
+            agent: None,
+            assertion_failures: Vec::new(),
+            timeline: None,
+            cache_hit: None,
+        }
+    }
+}
+
+/// Resultado parcial de um step interrompido pelo timeout.
+///
+/// Em vez de só "operation timed out", mostra até onde a requisição
+/// chegou: sem conexão, esperando os headers ou no meio do body.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct TimeoutCapture {
+    /// Fase em que o timeout disparou: `connect`, `waiting_for_headers` ou `reading_body`.
+    pub phase: String,
+
+    /// Timeout aplicado à requisição.
+    pub timeout_ms: u64,
+
+    /// Tempo decorrido até a interrupção.
+    pub elapsed_ms: u64,
+
+    /// Bytes do body recebidos antes do timeout.
+    pub bytes_received: u64,
+
+    /// Status recebido (presente se os headers chegaram).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<u16>,
+
+    /// Headers recebidos (valores sensíveis mascarados).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_headers: Option<HashMap<String, String>>,
+
+    /// Ms do envio até o primeiro byte do body (se chegou algum).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_byte_ms: Option<u64>,
+}
+
+/// Resultado de um step contra o seu `latency_budget_ms`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct LatencyBudget {
+    /// Orçamento definido no step.
+    pub budget_ms: u64,
+
+    /// Latência medida (da requisição HTTP, ou a duração do step).
+    pub latency_ms: u64,
+
+    /// True se a latência passou do orçamento.
+    pub exceeded: bool,
+}
+
+impl LatencyBudget {
+    /// Compara o resultado de um step com o orçamento.
+    pub fn evaluate(budget_ms: u64, result: &StepResult) -> Self {
+        let latency_ms = result
+            .http_details
+            .as_ref()
+            .map_or(result.duration_ms, |http| http.latency_ms);
+        Self {
+            budget_ms,
+            latency_ms,
+            exceeded: latency_ms > budget_ms,
+        }
+    }
+}
+
+/// Detalhes de uma troca de bytes por socket (`tcp_send`).
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct SocketDetails {
+    /// Protocolo de transporte (`tcp`, `udp`, `grpc`).
+    pub protocol: String,
+
+    /// Destino (`host:porta`).
+    pub address: String,
+
+    /// Bytes enviados.
+    pub bytes_sent: usize,
+
+    /// Bytes recebidos na resposta.
+    pub bytes_received: usize,
+
+    /// Do envio ao fim da leitura, em ms.
+    pub latency_ms: u64,
+
+    /// Início da resposta em hexadecimal (até 256 bytes).
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub reply_hex: String,
+}
+
+/// Detalhes de um processo executado por `shell_command`.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct CommandDetails {
+    /// Programa e argumentos, já interpolados.
+    pub command: Vec<String>,
+
+    /// Código de saída (ausente se o processo foi morto por sinal ou timeout).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+
+    /// Do spawn ao fim do processo, em ms.
+    pub latency_ms: u64,
+
+    /// Início da saída padrão (só com `--report-detail full`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stdout: Option<String>,
+
+    /// Início da saída de erro (só com `--report-detail full`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stderr: Option<String>,
+}
+
+/// Detalhes da rajada de um `rate_limit_probe`.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct RateLimitDetails {
+    /// URL chamada (já interpolada).
+    pub url: String,
+
+    /// Ritmo de envio pedido.
+    pub rate_per_sec: f64,
+
+    /// Status de cada requisição, na ordem de envio (0 = sem resposta).
+    pub statuses: Vec<u16>,
+
+    /// Posição (1, 2, 3...) da primeira resposta limitada.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit_onset: Option<u32>,
+
+    /// Quantas respostas vieram limitadas.
+    pub throttled: u32,
+
+    /// `Retry-After` da primeira resposta limitada, como veio.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<String>,
+
+    /// Status da requisição feita após a espera (assertion `recovered`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recovery_status: Option<u16>,
+
+    /// Do primeiro envio à última resposta (com a espera), em ms.
+    pub duration_ms: u64,
 }
 
 /// Detalhes de uma requisição HTTP executada.
 ///
-/// Incluído no StepResult para steps do tipo HTTP.
-/// Fornece visibilidade sobre a requisição real feita.
+/// Incluído no StepResult (`http_details`) para steps do tipo HTTP.
+/// Fornece visibilidade sobre a requisição real feita:
+///
+/// | Campo                                  | Conteúdo                                  |
+/// |----------------------------------------|-------------------------------------------|
+/// | `method`, `url`, `final_url`           | O que foi pedido e quem respondeu         |
+/// | `status_code`, `latency_ms`, `timing`  | Resultado e decomposição do tempo         |
+/// | `request_headers`, `response_headers`  | Headers, com credenciais mascaradas       |
+/// | `request_bytes`, `response_bytes`      | Tamanho dos bodies (resposta como veio)   |
+/// | `request_body`, `response_body`        | Bodies, só com `--report-detail full`     |
+///
+/// O formato está em `schemas/runner_report.schema.json` (`HttpDetails`).
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct HttpDetails {
     /// Método HTTP usado (GET, POST, PUT, DELETE, etc.).
@@ -472,32 +1711,239 @@ pub struct HttpDetails {
     /// URL completa da requisição (com base_url e query params).
     pub url: String,
 
+    /// URL que respondeu, quando redirecionamentos levaram a outra URL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub final_url: Option<String>,
+
     /// Status code HTTP retornado (200, 404, 500, etc.).
     pub status_code: u16,
 
     /// Latência da requisição em milissegundos.
     pub latency_ms: u64,
 
-    /// Headers da requisição (opcional, para debug).
+    /// Headers enviados (depois de sessão, interceptores e `signer`), com a
+    /// mesma política de `config.http.header_capture`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub request_headers: Option<HashMap<String, String>>,
 
-    /// Headers da resposta (opcional, para debug).
+    /// Headers da resposta, mascarados conforme `config.http.header_capture`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub response_headers: Option<HashMap<String, String>>,
+
+    /// `response_headers` ficou incompleto por passar de `max_bytes`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub response_headers_truncated: bool,
+
+    /// Decomposição do tempo da requisição por fase.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timing: Option<HttpTiming>,
+
+    /// Body enviado (já interpolado). Apenas com `--report-detail full`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<Value>,
+
+    /// Body bruto da resposta. Apenas com `--report-detail full`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_body: Option<String>,
+
+    /// Tamanho do body enviado, em bytes (ausente sem body).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_bytes: Option<u64>,
+
+    /// Tamanho do body recebido, como veio da rede (antes de descompactar).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_bytes: Option<u64>,
+
+    /// Compressão da resposta (presente se houve `Content-Encoding`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression: Option<HttpCompression>,
+
+    /// Resposta veio do cache da execução (`config.http.response_cache`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cache_hit: bool,
+
+    /// Protocolo usado na resposta (`HTTP/1.1`, `HTTP/2`), negociado ou
+    /// fixado por `params.http_version`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_version: Option<String>,
+
+    /// Endereço (IP:porta) do servidor que respondeu; ausente em cache hits
+    /// e erros de rede. Mostra se a conexão saiu por IPv4 ou IPv6.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peer_addr: Option<String>,
+}
+
+/// Tempos de cada fase de uma requisição HTTP.
+///
+/// Ajuda a distinguir lentidão de rede (DNS, transferência) de lentidão
+/// do servidor (TTFB). Connect e TLS não são expostos pelo cliente HTTP
+/// e ficam embutidos no `ttfb_ms` de conexões novas.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct HttpTiming {
+    /// Resolução DNS em ms (ausente quando a conexão veio do pool).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dns_ms: Option<u64>,
+
+    /// Do envio até receber os headers da resposta (time to first byte).
+    pub ttfb_ms: u64,
+
+    /// Leitura do body da resposta.
+    pub transfer_ms: u64,
+
+    /// Tempo total (envio + leitura do body).
+    pub total_ms: u64,
+
+    /// Do envio até o primeiro chunk do body (ausente se o body veio vazio).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_byte_ms: Option<u64>,
+
+    /// Chunks em que o body foi recebido.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk_count: Option<u32>,
+
+    /// Se a requisição usou uma conexão do pool (`false` = conexão nova).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection_reused: Option<bool>,
+}
+
+/// Tamanhos de uma resposta comprimida.
+///
+/// Permite verificar que a API realmente comprime (e quanto economiza)
+/// para clientes que enviam `Accept-Encoding`.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct HttpCompression {
+    /// Valor do `Content-Encoding` (ex: "gzip", "br").
+    pub encoding: String,
+
+    /// Tamanho do body como recebido (comprimido), em bytes.
+    pub compressed_bytes: u64,
+
+    /// Tamanho após descompactar (ausente com `decompress: false` ou
+    /// encoding não suportado).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decompressed_bytes: Option<u64>,
 }
 
 fn default_attempt() -> u32 {
     1
 }
 
+impl StepResult {
+    /// Remove do resultado o que não pertence ao nível de detalhe informado.
+    ///
+    /// - `minimal`: sem contexto, extrações nem bodies HTTP
+    /// - `standard`: sem bodies HTTP
+    /// - `full`: mantém tudo
+    ///
+    /// Aplica-se também às iterações de `parallel_foreach`.
+    pub fn apply_detail(&mut self, detail: ReportDetail) {
+        if detail == ReportDetail::Full {
+            return;
+        }
+
+        if let Some(http) = &mut self.http_details {
+            http.request_body = None;
+            http.response_body = None;
+        }
+        if let Some(command) = &mut self.command_details {
+            command.stdout = None;
+            command.stderr = None;
+        }
+
+        if detail == ReportDetail::Minimal {
+            self.context_before = None;
+            self.context_after = None;
+            self.context_delta = None;
+            self.extractions = None;
+        }
+
+        if let Some(iterations) = &mut self.iterations {
+            iterations
+                .iter_mut()
+                .for_each(|iteration| iteration.apply_detail(detail));
+        }
+    }
+
+    /// Troca os snapshots completos do contexto pela diferença entre eles.
+    ///
+    /// Aplica-se também às iterações de `parallel_foreach`. Não faz nada
+    /// se o resultado não tiver os dois snapshots.
+    pub fn compact_context(&mut self) {
+        if let (Some(before), Some(after)) = (&self.context_before, &self.context_after) {
+            self.context_delta = Some(ContextDelta::between(before, after));
+            self.context_before = None;
+            self.context_after = None;
+        }
+        if let Some(iterations) = &mut self.iterations {
+            iterations.iter_mut().for_each(StepResult::compact_context);
+        }
+    }
+}
+
+/// Variáveis do contexto alteradas por um step.
+///
+/// ## Para todos entenderem:
+///
+/// Em vez de fotografar o contexto inteiro antes e depois de cada step,
+/// anotamos só o que mudou:
+///
+/// | Campo     | Conteúdo                                   |
+/// |-----------|--------------------------------------------|
+/// | `added`   | Variáveis novas (nome → valor)             |
+/// | `changed` | Variáveis com valor novo (nome → valor novo) |
+/// | `removed` | Nomes das variáveis que deixaram de existir |
+///
+/// Um step que não mexe no contexto gera um delta vazio (`{}`).
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ContextDelta {
+    /// Variáveis criadas pelo step.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub added: BTreeMap<String, Value>,
+
+    /// Variáveis cujo valor mudou (com o valor novo).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub changed: BTreeMap<String, Value>,
+
+    /// Variáveis removidas.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed: Vec<String>,
+}
+
+impl ContextDelta {
+    /// Calcula a diferença entre dois snapshots do contexto.
+    pub fn between(before: &HashMap<String, Value>, after: &HashMap<String, Value>) -> Self {
+        let mut delta = Self::default();
+
+        for (key, value) in after {
+            match before.get(key) {
+                None => {
+                    delta.added.insert(key.clone(), value.clone());
+                }
+                Some(old) if old != value => {
+                    delta.changed.insert(key.clone(), value.clone());
+                }
+                Some(_) => {}
+            }
+        }
+
+        delta.removed = before
+            .keys()
+            .filter(|key| !after.contains_key(*key))
+            .cloned()
+            .collect();
+        delta.removed.sort();
+
+        delta
+    }
+}
+
 // ============================================================================
 // STATUS DE STEP: STEP STATUS
 // ============================================================================
 
 /// Status possíveis de um step após execução.
 #[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
-#[serde(rename_all = "lowercase")] // Serializa como "passed", "failed", "skipped"
+#[serde(rename_all = "snake_case")] // Serializa como "passed", "failed", "not_run", ...
 pub enum StepStatus {
     /// Step executou com sucesso e todas as assertions passaram.
     Passed,
@@ -505,20 +1951,119 @@ pub enum StepStatus {
     /// Step falhou (erro de execução ou assertion falhou).
     Failed,
 
-    /// Step foi pulado (dependência falhou).
+    /// Step foi pulado (motivo em `skip_reason`).
     Skipped,
+
+    /// Execução do step foi interrompida (cancelamento ou prazo global).
+    Cancelled,
+
+    /// Step não rodou porque sua condição (`condition`) era falsa ou
+    /// ficou fora dos filtros `--only`/`--skip`/`--tags` (não é falha).
+    NotRun,
+
+    /// Step em quarentena (`--quarantine`) que não passou: a falha fica
+    /// registrada em `error`, mas não afeta o status geral.
+    Quarantined,
+}
+
+impl StepStatus {
+    /// Retorna true se o status não reprova a execução.
+    ///
+    /// | Status        | Reprova? |
+    /// |---------------|----------|
+    /// | `passed`      | Não      |
+    /// | `not_run`     | Não      |
+    /// | `quarantined` | Não      |
+    /// | `failed`      | Sim      |
+    /// | `skipped`     | Sim      |
+    /// | `cancelled`   | Sim      |
+    pub fn is_success(&self) -> bool {
+        matches!(
+            self,
+            StepStatus::Passed | StepStatus::NotRun | StepStatus::Quarantined
+        )
+    }
+}
+
+/// Motivo legível por máquina de um step que não executou.
+///
+/// Permite a dashboards separar "dependência falhou" de "filtrado" de
+/// "estourou o tempo" sem interpretar a mensagem de `error`.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// Uma dependência (`depends_on`) falhou ou foi pulada.
+    DependencyFailed,
+
+    /// Step excluído por filtro de seleção (ID, tag ou `--retry-failed`).
+    FilteredOut,
+
+    /// Prazo esgotado antes de o step começar ou durante ele (step interrompido).
+    TimedOut,
+
+    /// Condição do step (`condition`) avaliada como falsa.
+    ConditionFalse,
+
+    /// Execução cancelada antes de o step começar.
+    Cancelled,
 }
 
 // ============================================================================
 // RELATÓRIO DE EXECUÇÃO: EXECUTION REPORT
 // ============================================================================
 
+/// Versão do formato do relatório (campo `version` do runner_report.schema.json).
+///
+/// Incrementada a cada mudança no formato, para que consumidores (Brain, CI)
+/// saibam quais campos esperar.
+pub const REPORT_VERSION: &str = "1.2.0";
+
+/// Nível de detalhe do relatório (`--report-detail`).
+///
+/// ## Para todos entenderem:
+///
+/// | Nível      | Inclui                                                  |
+/// |------------|---------------------------------------------------------|
+/// | `minimal`  | Status, duração, erros e dados HTTP básicos             |
+/// | `standard` | + delta do contexto e resultados das extrações (padrão) |
+/// | `full`     | + snapshots completos do contexto e bodies HTTP brutos  |
+///
+/// Use `minimal` em CI (relatórios pequenos e estáveis) e `full` para debug.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportDetail {
+    /// Apenas o essencial para saber o que passou ou falhou.
+    Minimal,
+    /// Padrão: inclui o que mudou no contexto e as extrações.
+    #[default]
+    Standard,
+    /// Tudo o que foi capturado durante a execução.
+    Full,
+}
+
+impl ReportDetail {
+    /// Nome do nível, como usado na CLI e no relatório.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Minimal => "minimal",
+            Self::Standard => "standard",
+            Self::Full => "full",
+        }
+    }
+}
+
 /// Relatório final de execução de um plano.
 ///
 /// Gerado ao final da execução e salvo em arquivo ou impresso no console.
 /// Este formato segue o schema definido em /schemas/runner_report.schema.json.
 #[derive(Debug, Serialize)]
 pub struct ExecutionReport {
+    /// Versão do formato do relatório (`REPORT_VERSION`).
+    pub report_version: String,
+
+    /// Nível de detalhe com que o relatório foi gerado.
+    pub report_detail: ReportDetail,
+
     /// UUID único desta execução.
     /// Permite rastrear esta execução específica em logs/dashboards.
     pub execution_id: String,
@@ -552,6 +2097,80 @@ pub struct ExecutionReport {
 
     /// Resultados de cada step.
     pub steps: Vec<StepResult>,
+
+    /// Metadados de rastreabilidade (commit, branch, pipeline, `--meta`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<RunMetadata>,
+
+    /// `execution_id` da execução reexecutada com `--retry-failed`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_of: Option<String>,
+
+    /// Semente dos valores aleatórios (`--seed`), para reproduzir a execução.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+
+    /// `meta.generator` do plano (quem o gerou).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generator: Option<PlanGenerator>,
+
+    /// Problemas de qualidade do plano que não reprovam a execução
+    /// (achados do linter, sintaxe legada, falhas ignoradas).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<RunWarning>,
+
+    /// Região de `config.regions` em que o plano rodou (`--regions`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+
+    /// Erros da execução como um todo (ex: E5004, limite de memória),
+    /// fora dos erros de cada step.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<ReportError>,
+}
+
+/// Erro estruturado da execução (`errors` no runner_report.schema.json).
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ReportError {
+    /// Código (`E5004`).
+    pub code: String,
+
+    /// Categoria do código (`internal`, `configuration`, ...).
+    pub category: String,
+
+    /// Mensagem descritiva.
+    pub message: String,
+
+    /// Step onde ocorreu (ausente para erros da execução inteira).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step_id: Option<String>,
+}
+
+impl ReportError {
+    pub fn new(code: crate::errors::ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code: code.formatted(),
+            category: code.category().as_str().to_string(),
+            message: message.into(),
+            step_id: None,
+        }
+    }
+}
+
+/// Aviso de qualidade anotado no relatório (veja o módulo `warnings`).
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RunWarning {
+    /// Código estável (`L001`... do linter, `W001`... de execução).
+    pub code: String,
+
+    /// Nome legível (ex: `legacy-env-syntax`).
+    pub name: String,
+
+    /// Step de origem (`None` para `config`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step_id: Option<String>,
+
+    pub message: String,
 }
 
 /// Resumo estatístico da execução.
@@ -571,11 +2190,131 @@ pub struct ExecutionSummary {
     /// Número de steps pulados (dependência falhou).
     pub skipped: usize,
 
+    /// Número de steps cancelados (execução interrompida).
+    pub cancelled: usize,
+
+    /// Número de steps não executados (condição falsa ou fora de `--only`/`--skip`/`--tags`).
+    pub not_run: usize,
+
+    /// Número de steps em quarentena que não passaram.
+    pub quarantined: usize,
+
     /// Número total de retries realizados.
     pub total_retries: u32,
 
     /// Duração total da execução em milissegundos.
     pub duration_ms: u64,
+
+    /// Conformidade com os orçamentos de latência (ausente sem `latency_budget_ms`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slo: Option<SloSummary>,
+
+    /// Steps que falharam agrupados por código de erro (ver `triage`).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub failures: Vec<FailureGroup>,
+
+    /// Conexões HTTP novas e reusadas por host (ver `http_connections`).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub connections: Vec<HostConnections>,
+
+    /// Avaliação de `config.quality_gate` (ausente sem gate).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality_gate: Option<GateResult>,
+}
+
+/// Uso do pool de conexões de um host.
+#[derive(Debug, Clone, Serialize)]
+pub struct HostConnections {
+    /// Host e porta (ex: `api.example.com:443`).
+    pub host: String,
+
+    /// Requisições que abriram uma conexão nova (handshake TCP/TLS).
+    pub new_connections: usize,
+
+    /// Requisições atendidas por uma conexão do pool.
+    pub reused_connections: usize,
+}
+
+/// Grupo de falhas com o mesmo código de erro.
+#[derive(Debug, Clone, Serialize)]
+pub struct FailureGroup {
+    /// Código do erro (ex: `E3001`).
+    pub code: String,
+
+    /// Nome do código (ex: `ASSERTION_STATUS_CODE`).
+    pub name: String,
+
+    /// Categoria do código (`assertion`, `http_execution`...).
+    pub category: String,
+
+    /// Quantidade de steps no grupo.
+    pub count: usize,
+
+    /// IDs dos steps, na ordem do relatório.
+    pub steps: Vec<String>,
+}
+
+/// Resumo de SLO: steps dentro do `latency_budget_ms`.
+///
+/// ## Para todos entenderem:
+///
+/// Uma assertion `latency` reprova o step; o orçamento só mede. Assim o
+/// mesmo plano serve de teste funcional e de checagem leve de SLO:
+/// "95% dos steps responderam dentro do esperado".
+#[derive(Debug, Serialize, PartialEq)]
+pub struct SloSummary {
+    /// Steps executados que têm orçamento.
+    pub steps_with_budget: usize,
+
+    /// Quantos ficaram dentro do orçamento.
+    pub within_budget: usize,
+
+    /// `within_budget / steps_with_budget` em porcentagem (0-100).
+    pub compliance_pct: f64,
+
+    /// Steps que estouraram o orçamento.
+    pub violations: Vec<BudgetViolation>,
+}
+
+/// Step que passou do orçamento de latência.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct BudgetViolation {
+    pub step_id: String,
+    pub budget_ms: u64,
+    pub latency_ms: u64,
+}
+
+impl SloSummary {
+    /// Agrega os orçamentos dos resultados (`None` se nenhum step tem orçamento).
+    pub fn from_results(results: &[StepResult]) -> Option<Self> {
+        let budgets: Vec<(&str, &LatencyBudget)> = results
+            .iter()
+            .filter_map(|r| r.latency_budget.as_ref().map(|b| (r.step_id.as_str(), b)))
+            .collect();
+        if budgets.is_empty() {
+            return None;
+        }
+
+        let violations: Vec<BudgetViolation> = budgets
+            .iter()
+            .filter(|(_, b)| b.exceeded)
+            .map(|(step_id, b)| BudgetViolation {
+                step_id: step_id.to_string(),
+                budget_ms: b.budget_ms,
+                latency_ms: b.latency_ms,
+            })
+            .collect();
+        let within_budget = budgets.len() - violations.len();
+        let compliance_pct =
+            (within_budget as f64 / budgets.len() as f64 * 10_000.0).round() / 100.0;
+
+        Some(Self {
+            steps_with_budget: budgets.len(),
+            within_budget,
+            compliance_pct,
+            violations,
+        })
+    }
 }
 
 impl ExecutionSummary {
@@ -593,6 +2332,10 @@ impl ExecutionSummary {
             .iter()
             .filter(|r| r.status == StepStatus::Skipped)
             .count();
+        let count = |status: StepStatus| results.iter().filter(|r| r.status == status).count();
+        let cancelled = count(StepStatus::Cancelled);
+        let not_run = count(StepStatus::NotRun);
+        let quarantined = count(StepStatus::Quarantined);
 
         // TODO: Contar retries quando StepResult tiver esse campo
         let total_retries = 0;
@@ -602,8 +2345,15 @@ impl ExecutionSummary {
             passed,
             failed,
             skipped,
+            cancelled,
+            not_run,
+            quarantined,
             total_retries,
             duration_ms,
+            slo: SloSummary::from_results(results),
+            failures: Vec::new(),
+            connections: Vec::new(),
+            quality_gate: None,
         }
     }
 }
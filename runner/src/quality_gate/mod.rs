@@ -0,0 +1,198 @@
+//! # Módulo de Quality Gate - Status Final por Orçamento
+//!
+//! Avalia `config.quality_gate` ao fim da execução: com um gate, o status
+//! do relatório (e o exit code) passa a ser "o gate foi respeitado?" em
+//! vez de "todos os steps passaram?".
+//!
+//! ## Para todos entenderem:
+//!
+//! Em um smoke test contra staging, um step instável não deveria travar o
+//! deploy, mas cinco deveriam; e a latência importa tanto quanto o status.
+//! O gate escreve essa política no plano:
+//!
+//! ```json
+//! "config": {
+//!   "quality_gate": {
+//!     "max_failed_steps": 1,
+//!     "max_p95_latency_ms": 800,
+//!     "min_pass_rate_pct": 95
+//!   }
+//! }
+//! ```
+//!
+//! | Critério              | Medido sobre                                          |
+//! |-----------------------|-------------------------------------------------------|
+//! | `max_failed_steps`    | Steps com status `failed`                             |
+//! | `max_p95_latency_ms`  | Latência HTTP p95 (steps e iterações, sem `warmup`)   |
+//! | `min_pass_rate_pct`   | `passed / executados` (steps `not_run` ficam de fora) |
+//!
+//! Critérios ausentes não são avaliados. O resultado de cada um fica em
+//! `summary.quality_gate` no relatório.
+
+use serde::Serialize;
+
+use crate::protocol::{QualityGate, StepResult, StepStatus};
+
+/// Resultado da avaliação do gate.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct GateResult {
+    /// `true` se todos os critérios configurados foram respeitados.
+    pub passed: bool,
+    pub checks: Vec<GateCheck>,
+}
+
+/// Um critério do gate: limite configurado e valor medido.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct GateCheck {
+    pub name: String,
+    pub limit: f64,
+    pub actual: f64,
+    pub passed: bool,
+}
+
+// ============================================================================
+// AVALIAÇÃO
+// ============================================================================
+
+/// Avalia os critérios configurados sobre os resultados da execução.
+pub fn evaluate(gate: &QualityGate, results: &[StepResult]) -> GateResult {
+    let mut checks = Vec::new();
+
+    if let Some(max_failed) = gate.max_failed_steps {
+        let failed = results
+            .iter()
+            .filter(|r| r.status == StepStatus::Failed)
+            .count();
+        checks.push(check_max(
+            "max_failed_steps",
+            max_failed as f64,
+            failed as f64,
+        ));
+    }
+
+    if let Some(max_p95) = gate.max_p95_latency_ms {
+        // Sem nenhuma requisição HTTP, não há latência para reprovar.
+        let p95 = p95_latency_ms(results).unwrap_or(0);
+        checks.push(check_max("max_p95_latency_ms", max_p95 as f64, p95 as f64));
+    }
+
+    if let Some(min_rate) = gate.min_pass_rate_pct {
+        let executed: Vec<&StepResult> = results
+            .iter()
+            .filter(|r| r.status != StepStatus::NotRun)
+            .collect();
+        let passed = executed.iter().filter(|r| r.status.is_success()).count();
+        let rate = if executed.is_empty() {
+            100.0
+        } else {
+            (passed as f64 / executed.len() as f64 * 10_000.0).round() / 100.0
+        };
+        checks.push(GateCheck {
+            name: "min_pass_rate_pct".to_string(),
+            limit: min_rate,
+            actual: rate,
+            passed: rate >= min_rate,
+        });
+    }
+
+    GateResult {
+        passed: checks.iter().all(|c| c.passed),
+        checks,
+    }
+}
+
+fn check_max(name: &str, limit: f64, actual: f64) -> GateCheck {
+    GateCheck {
+        name: name.to_string(),
+        limit,
+        actual,
+        passed: actual <= limit,
+    }
+}
+
+/// Percentil 95 (nearest-rank) das latências HTTP, incluindo iterações.
+///
+/// Steps de `warmup` (e as iterações deles) ficam de fora.
+fn p95_latency_ms(results: &[StepResult]) -> Option<u64> {
+    let measured = results.iter().filter(|r| !r.warmup);
+    let mut latencies: Vec<u64> = measured
+        .clone()
+        .chain(measured.flat_map(|r| r.iterations.iter().flatten()))
+        .filter_map(|r| r.http_details.as_ref())
+        .filter(|http| http.status_code != 0)
+        .map(|http| http.latency_ms)
+        .collect();
+    if latencies.is_empty() {
+        return None;
+    }
+    latencies.sort_unstable();
+    let rank = (latencies.len() as f64 * 0.95).ceil() as usize;
+    Some(latencies[rank.clamp(1, latencies.len()) - 1])
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn results() -> Vec<StepResult> {
+        let step = |id: &str, status: &str, latency: u64| {
+            json!({
+                "step_id": id, "status": status, "duration_ms": latency,
+                "http_details": { "method": "GET", "url": "http://api", "status_code": 200,
+                                  "latency_ms": latency }
+            })
+        };
+        let mut steps: Vec<serde_json::Value> = (0..18)
+            .map(|i| step(&format!("s{}", i), "passed", 100))
+            .collect();
+        steps.push(step("slow", "passed", 900));
+        steps.push(step("broken", "failed", 1200));
+        steps.push(json!({ "step_id": "cond", "status": "not_run", "duration_ms": 0 }));
+        serde_json::from_value(json!(steps)).unwrap()
+    }
+
+    #[test]
+    fn test_gate_tolerates_failures_within_budget() {
+        let gate = QualityGate {
+            max_failed_steps: Some(1),
+            max_p95_latency_ms: Some(1000),
+            min_pass_rate_pct: Some(95.0),
+        };
+        let result = evaluate(&gate, &results());
+
+        assert!(result.passed, "{:?}", result.checks);
+        assert_eq!(result.checks[1].actual, 900.0);
+        assert_eq!(result.checks[2].actual, 95.0);
+
+        // O cold start do warmup não entra no p95.
+        let mut warm = results();
+        warm[18].warmup = true;
+        warm[19].warmup = true;
+        assert_eq!(p95_latency_ms(&warm), Some(100));
+    }
+
+    #[test]
+    fn test_gate_fails_on_any_violated_check() {
+        let gate = QualityGate {
+            max_failed_steps: Some(0),
+            max_p95_latency_ms: None,
+            min_pass_rate_pct: None,
+        };
+        let result = evaluate(&gate, &results());
+        assert!(!result.passed);
+        assert_eq!(result.checks.len(), 1);
+        assert_eq!(result.checks[0].actual, 1.0);
+
+        let empty = QualityGate {
+            max_failed_steps: None,
+            max_p95_latency_ms: Some(10),
+            min_pass_rate_pct: Some(100.0),
+        };
+        assert!(evaluate(&empty, &[]).passed);
+    }
+}
@@ -0,0 +1,143 @@
+//! # Módulo de Quarentena - Steps com Falha Conhecida
+//!
+//! Lê a lista de `--quarantine` e marca os resultados dos steps listados
+//! com o status `quarantined`: o erro continua no relatório, mas não
+//! reprova a execução.
+//!
+//! ## Para todos entenderem:
+//!
+//! Um endpoint está quebrado e o time já sabe (tem ticket aberto). Apagar
+//! o step perde a cobertura; deixá-lo reprovar toda noite treina o time a
+//! ignorar o vermelho. A quarentena é o meio-termo: o step continua
+//! rodando e aparecendo no relatório, só não derruba o status geral nem o
+//! exit code.
+//!
+//! ## Formato do arquivo (YAML ou JSON):
+//!
+//! ```yaml
+//! # quarantine.yaml
+//! steps:
+//!   - get_legacy_report
+//! tags:
+//!   - known-broken
+//! ```
+//!
+//! - `steps`: IDs de steps.
+//! - `tags`: qualquer step com uma dessas tags (`Step.tags`).
+//!
+//! Steps pulados por dependerem de um step em quarentena **não** entram
+//! automaticamente na quarentena: liste-os também (ou use uma tag comum).
+
+use anyhow::{Context as _, Result};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::protocol::{Step, StepResult, StepStatus};
+
+// ============================================================================
+// LISTA DE QUARENTENA
+// ============================================================================
+
+/// Steps (por ID ou tag) cujas falhas não afetam o status geral.
+#[derive(Debug, Default, Deserialize)]
+pub struct Quarantine {
+    /// IDs de steps em quarentena.
+    #[serde(default)]
+    pub steps: HashSet<String>,
+
+    /// Tags de steps em quarentena.
+    #[serde(default)]
+    pub tags: HashSet<String>,
+}
+
+impl Quarantine {
+    /// Carrega a lista de um arquivo YAML (JSON também é aceito).
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Falha ao ler lista de quarentena {:?}", path))?;
+        serde_yaml::from_str(&content)
+            .with_context(|| format!("Lista de quarentena inválida {:?}", path))
+    }
+
+    /// Retorna true se o step está em quarentena (por ID ou por tag).
+    pub fn contains(&self, step: &Step) -> bool {
+        self.steps.contains(&step.id) || step.tags.iter().any(|t| self.tags.contains(t))
+    }
+
+    /// Troca para `quarantined` o status dos resultados que não passaram.
+    ///
+    /// ## Retorno:
+    /// Número de resultados marcados.
+    pub fn apply(&self, steps: &[Step], results: &mut [StepResult]) -> usize {
+        let quarantined: HashSet<&str> = steps
+            .iter()
+            .filter(|s| self.contains(s))
+            .map(|s| s.id.as_str())
+            .collect();
+
+        let mut marked = 0;
+        for result in results {
+            if !result.status.is_success() && quarantined.contains(result.step_id.as_str()) {
+                result.status = StepStatus::Quarantined;
+                marked += 1;
+            }
+        }
+        marked
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(id: &str, tags: &[&str]) -> Step {
+        Step {
+            id: id.to_string(),
+            action: "log".to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn result(id: &str, status: StepStatus) -> StepResult {
+        StepResult {
+            step_id: id.to_string(),
+            status,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_yaml_and_match_by_id_or_tag() {
+        let quarantine: Quarantine =
+            serde_yaml::from_str("steps:\n  - legacy\ntags:\n  - known-broken\n").unwrap();
+
+        assert!(quarantine.contains(&step("legacy", &[])));
+        assert!(quarantine.contains(&step("other", &["api", "known-broken"])));
+        assert!(!quarantine.contains(&step("other", &["api"])));
+    }
+
+    #[test]
+    fn test_apply_marks_only_non_passed_results() {
+        let quarantine = Quarantine {
+            steps: ["a".to_string(), "b".to_string()].into_iter().collect(),
+            tags: HashSet::new(),
+        };
+        let steps = vec![step("a", &[]), step("b", &[]), step("c", &[])];
+        let mut results = vec![
+            result("a", StepStatus::Failed),
+            result("b", StepStatus::Passed),
+            result("c", StepStatus::Failed),
+        ];
+
+        assert_eq!(quarantine.apply(&steps, &mut results), 1);
+        assert_eq!(results[0].status, StepStatus::Quarantined);
+        assert_eq!(results[1].status, StepStatus::Passed);
+        assert_eq!(results[2].status, StepStatus::Failed);
+    }
+}
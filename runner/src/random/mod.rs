@@ -0,0 +1,134 @@
+//! # Módulo de Aleatoriedade Reprodutível - `--seed`
+//!
+//! Com `--seed N`, os valores "aleatórios" da interpolação (`${random_int}`,
+//! `${random_uuid}`) passam a ser derivados da semente, e a mesma execução
+//! gera exatamente os mesmos dados.
+//!
+//! ## Para todos entenderem:
+//!
+//! Um teste falhou porque o nome gerado tinha um caractere inesperado. Sem
+//! semente, a próxima execução gera outro nome e a falha some. Com
+//! `--seed 42` (a semente aparece no relatório), basta repetir a execução
+//! com a mesma semente para obter os mesmos dados.
+//!
+//! ## Por que é seguro em paralelo?
+//!
+//! No modo DAG a ordem em que os steps rodam varia. Se houvesse um único
+//! gerador compartilhado, o step que rodasse primeiro "roubaria" os
+//! números do outro. Aqui cada valor é derivado de:
+//!
+//! ```text
+//!   semente + ID do step (escopo) + nº da chamada dentro do step
+//! ```
+//!
+//! então o valor de um step não depende de quando os outros rodam.
+//!
+//! ## Valores gerados:
+//!
+//! | Placeholder      | Com semente                                    |
+//! |------------------|------------------------------------------------|
+//! | `${random_int}`  | u32 derivado de SHA-256(semente, escopo, n)    |
+//! | `${random_uuid}` | UUID v5 (SHA-1) de `semente:escopo:n`          |
+
+use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicU64, Ordering};
+use uuid::Uuid;
+
+// ============================================================================
+// GERADOR COM SEMENTE
+// ============================================================================
+
+/// Gerador determinístico, com um contador por escopo (step).
+#[derive(Debug)]
+pub struct SeededRandom {
+    /// Semente informada em `--seed`.
+    seed: u64,
+
+    /// Escopo atual (ID do step em execução).
+    scope: String,
+
+    /// Quantos valores já foram gerados neste escopo.
+    counter: AtomicU64,
+}
+
+impl Clone for SeededRandom {
+    fn clone(&self) -> Self {
+        Self {
+            seed: self.seed,
+            scope: self.scope.clone(),
+            counter: AtomicU64::new(self.counter.load(Ordering::SeqCst)),
+        }
+    }
+}
+
+impl SeededRandom {
+    /// Cria um gerador com a semente informada (escopo vazio).
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            scope: String::new(),
+            counter: AtomicU64::new(0),
+        }
+    }
+
+    /// Passa a gerar valores para um novo escopo, reiniciando o contador.
+    pub fn enter_scope(&mut self, scope: &str) {
+        self.scope = scope.to_string();
+        self.counter = AtomicU64::new(0);
+    }
+
+    /// Próximo inteiro de 32 bits do escopo.
+    pub fn next_u32(&self) -> u32 {
+        let digest = Sha256::digest(self.next_name("int").as_bytes());
+        u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]])
+    }
+
+    /// Próximo UUID do escopo (versão 5, derivado por SHA-1).
+    pub fn next_uuid(&self) -> Uuid {
+        Uuid::new_v5(&Uuid::NAMESPACE_OID, self.next_name("uuid").as_bytes())
+    }
+
+    /// Nome único da próxima chamada: `semente:escopo:tipo:n`.
+    fn next_name(&self, kind: &str) -> String {
+        let n = self.counter.fetch_add(1, Ordering::SeqCst);
+        format!("{}:{}:{}:{}", self.seed, self.scope, kind, n)
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scoped(seed: u64, scope: &str) -> SeededRandom {
+        let mut random = SeededRandom::new(seed);
+        random.enter_scope(scope);
+        random
+    }
+
+    #[test]
+    fn test_same_seed_and_scope_repeat_values() {
+        let a = scoped(42, "create_user");
+        let b = scoped(42, "create_user");
+
+        assert_eq!(a.next_u32(), b.next_u32());
+        assert_eq!(a.next_uuid(), b.next_uuid());
+        assert_eq!(a.next_uuid().get_version_num(), 5);
+    }
+
+    #[test]
+    fn test_scopes_are_independent() {
+        let a = scoped(42, "step_a");
+        let b = scoped(42, "step_b");
+        let c = scoped(7, "step_a");
+
+        let first_a = a.next_u32();
+        assert_ne!(first_a, b.next_u32());
+        assert_ne!(first_a, c.next_u32());
+        // Valores seguintes no mesmo escopo mudam.
+        assert_ne!(first_a, a.next_u32());
+    }
+}
@@ -0,0 +1,329 @@
+//! # Módulo de Regiões - O Mesmo Plano em Cada Região
+//!
+//! Implementa `--regions`: executa o plano uma vez por região de
+//! `config.regions` (trocando a `base_url`) e compara os resultados.
+//!
+//! ## Para todos entenderem:
+//!
+//! A API está no ar em `eu` e `us`, com o mesmo código. Rodar o plano em
+//! cada uma e olhar os relatórios lado a lado é trabalhoso; o relatório
+//! comparativo mostra de cara onde algo diverge:
+//!
+//! ```text
+//! Comparação por região:
+//!   eu: passed (12/12) em 1840ms
+//!   us: failed (11/12) em 2310ms
+//!
+//!   step           eu              us              Δ latência
+//!   create_user    passed 120ms    passed 410ms    290ms
+//!   get_user       passed 80ms     failed 95ms     15ms
+//! ```
+//!
+//! ## Configuração:
+//!
+//! ```json
+//! "config": {
+//!   "base_url": "https://eu.api.example.com",
+//!   "regions": [
+//!     { "name": "eu", "base_url": "https://eu.api.example.com" },
+//!     { "name": "us", "base_url": "https://us.api.example.com", "variables": { "currency": "USD" } }
+//!   ]
+//! }
+//! ```
+//!
+//! ```bash
+//! runner execute --file plan.json --regions all --output report.json
+//! # report.eu.json, report.us.json e report.regions.json (comparativo)
+//! ```
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use crate::protocol::{ExecutionReport, Region, StepResult, StepStatus};
+
+// ============================================================================
+// SELEÇÃO
+// ============================================================================
+
+/// Regiões pedidas em `--regions` (`all` ou nomes separados por vírgula).
+pub fn select(regions: &[Region], spec: &str) -> Result<Vec<Region>> {
+    if regions.is_empty() {
+        return Err(anyhow!("--regions requer config.regions no plano"));
+    }
+    if spec.trim().eq_ignore_ascii_case("all") {
+        return Ok(regions.to_vec());
+    }
+    spec.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            regions
+                .iter()
+                .find(|r| r.name == name)
+                .cloned()
+                .ok_or_else(|| {
+                    let available: Vec<&str> = regions.iter().map(|r| r.name.as_str()).collect();
+                    anyhow!(
+                        "Região '{}' não existe em config.regions. Disponíveis: {}",
+                        name,
+                        available.join(", ")
+                    )
+                })
+        })
+        .collect()
+}
+
+/// Caminho do relatório de uma região: `report.json` → `report.eu.json`.
+pub fn region_output(path: &Path, region: &str) -> PathBuf {
+    crate::report_file::with_suffix(path, region)
+}
+
+/// Caminho do comparativo: `report.json` → `report.regions.json`.
+pub fn comparison_output(path: &Path) -> PathBuf {
+    crate::report_file::with_suffix(path, "regions")
+}
+
+// ============================================================================
+// COMPARAÇÃO
+// ============================================================================
+
+/// Relatório comparativo entre regiões.
+#[derive(Debug, Serialize)]
+pub struct RegionComparison {
+    pub plan_id: String,
+    pub status: String,
+    pub regions: Vec<RegionSummary>,
+    pub steps: Vec<StepComparison>,
+}
+
+/// Resultado geral de uma região.
+#[derive(Debug, Serialize)]
+pub struct RegionSummary {
+    pub region: String,
+    pub execution_id: String,
+    pub status: String,
+    pub passed: usize,
+    pub total_steps: usize,
+    pub duration_ms: u64,
+}
+
+/// Um step em cada região.
+#[derive(Debug, Serialize)]
+pub struct StepComparison {
+    pub step_id: String,
+    pub regions: BTreeMap<String, RegionStep>,
+    /// Diferença entre a maior e a menor latência entre as regiões.
+    pub latency_delta_ms: u64,
+    /// `true` se o status não é o mesmo em todas as regiões.
+    pub status_differs: bool,
+}
+
+/// Status e latência de um step em uma região.
+#[derive(Debug, Serialize)]
+pub struct RegionStep {
+    pub status: StepStatus,
+    pub latency_ms: u64,
+}
+
+/// Monta o comparativo a partir dos relatórios de cada região.
+pub fn compare(reports: &[(String, ExecutionReport)]) -> RegionComparison {
+    let mut steps: Vec<StepComparison> = Vec::new();
+    for (region, report) in reports {
+        for result in &report.steps {
+            let entry = match steps.iter_mut().find(|s| s.step_id == result.step_id) {
+                Some(entry) => entry,
+                None => {
+                    steps.push(StepComparison {
+                        step_id: result.step_id.clone(),
+                        regions: BTreeMap::new(),
+                        latency_delta_ms: 0,
+                        status_differs: false,
+                    });
+                    steps.last_mut().expect("just pushed")
+                }
+            };
+            entry.regions.insert(
+                region.clone(),
+                RegionStep {
+                    status: result.status.clone(),
+                    latency_ms: latency_of(result),
+                },
+            );
+        }
+    }
+
+    for step in &mut steps {
+        let latencies = step.regions.values().map(|r| r.latency_ms);
+        step.latency_delta_ms = latencies.clone().max().unwrap_or(0) - latencies.min().unwrap_or(0);
+        let mut statuses = step.regions.values().map(|r| &r.status);
+        let first = statuses.next();
+        step.status_differs =
+            statuses.any(|s| Some(s) != first) || step.regions.len() < reports.len();
+    }
+
+    let all_passed = reports.iter().all(|(_, r)| r.status == "passed");
+    RegionComparison {
+        plan_id: reports
+            .first()
+            .map(|(_, r)| r.plan_id.clone())
+            .unwrap_or_default(),
+        status: if all_passed { "passed" } else { "failed" }.to_string(),
+        regions: reports
+            .iter()
+            .map(|(region, report)| RegionSummary {
+                region: region.clone(),
+                execution_id: report.execution_id.clone(),
+                status: report.status.clone(),
+                passed: report.summary.passed,
+                total_steps: report.summary.total_steps,
+                duration_ms: report.duration_ms,
+            })
+            .collect(),
+        steps,
+    }
+}
+
+/// Tabela do comparativo para o console.
+pub fn render(comparison: &RegionComparison) -> String {
+    let mut out = String::from("Comparação por região:\n");
+    for region in &comparison.regions {
+        let _ = writeln!(
+            out,
+            "  {}: {} ({}/{}) em {}ms",
+            region.region, region.status, region.passed, region.total_steps, region.duration_ms
+        );
+    }
+
+    let _ = write!(out, "\n  {:<24}", "step");
+    for region in &comparison.regions {
+        let _ = write!(out, "{:<16}", region.region);
+    }
+    out.push_str("Δ latência\n");
+    for step in &comparison.steps {
+        let _ = write!(out, "  {:<24}", step.step_id);
+        for region in &comparison.regions {
+            let cell = step
+                .regions
+                .get(&region.region)
+                .map(|r| format!("{} {}ms", status_name(&r.status), r.latency_ms))
+                .unwrap_or_else(|| "-".to_string());
+            let _ = write!(out, "{:<16}", cell);
+        }
+        let marker = if step.status_differs { "  ≠" } else { "" };
+        let _ = writeln!(out, "{}ms{}", step.latency_delta_ms, marker);
+    }
+    out
+}
+
+fn latency_of(result: &StepResult) -> u64 {
+    result
+        .http_details
+        .as_ref()
+        .map_or(result.duration_ms, |http| http.latency_ms)
+}
+
+fn status_name(status: &StepStatus) -> String {
+    serde_json::to_value(status)
+        .ok()
+        .and_then(|s| s.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{ExecutionSummary, ReportDetail};
+    use serde_json::json;
+
+    fn regions() -> Vec<Region> {
+        serde_json::from_value(json!([
+            { "name": "eu", "base_url": "https://eu.api" },
+            { "name": "us", "base_url": "https://us.api" }
+        ]))
+        .unwrap()
+    }
+
+    fn report(region: &str, steps: serde_json::Value) -> ExecutionReport {
+        let steps: Vec<StepResult> = serde_json::from_value(steps).unwrap();
+        let passed = steps.iter().all(|s| s.status == StepStatus::Passed);
+        ExecutionReport {
+            report_version: "1".to_string(),
+            report_detail: ReportDetail::Standard,
+            execution_id: format!("exec-{}", region),
+            plan_id: "plan".to_string(),
+            plan_name: "Plan".to_string(),
+            status: if passed { "passed" } else { "failed" }.to_string(),
+            start_time: String::new(),
+            end_time: String::new(),
+            duration_ms: 100,
+            runner_version: String::new(),
+            execution_mode: "sequential".to_string(),
+            summary: ExecutionSummary::from_results(&steps, 100),
+            steps,
+            metadata: None,
+            retry_of: None,
+            seed: None,
+            generator: None,
+            warnings: Vec::new(),
+            region: Some(region.to_string()),
+            errors: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_select_regions_and_output_paths() {
+        assert_eq!(select(&regions(), "all").unwrap().len(), 2);
+        let us = select(&regions(), "us").unwrap();
+        assert_eq!(us[0].base_url, "https://us.api");
+        let err = select(&regions(), "eu,asia").unwrap_err().to_string();
+        assert!(err.contains("Disponíveis: eu, us"), "{}", err);
+        assert!(select(&[], "all").is_err());
+
+        assert_eq!(
+            region_output(Path::new("out/report.json.gz"), "eu"),
+            PathBuf::from("out/report.eu.json.gz")
+        );
+        assert_eq!(
+            comparison_output(Path::new("report.json")),
+            PathBuf::from("report.regions.json")
+        );
+    }
+
+    #[test]
+    fn test_compare_reports_latency_delta_and_divergence() {
+        let step = |id: &str, status: &str, latency: u64| json!({ "step_id": id, "status": status, "duration_ms": latency });
+        let reports = vec![
+            (
+                "eu".to_string(),
+                report(
+                    "eu",
+                    json!([step("create", "passed", 120), step("get", "passed", 80)]),
+                ),
+            ),
+            (
+                "us".to_string(),
+                report(
+                    "us",
+                    json!([step("create", "passed", 410), step("get", "failed", 95)]),
+                ),
+            ),
+        ];
+
+        let comparison = compare(&reports);
+        assert_eq!(comparison.status, "failed");
+        assert_eq!(comparison.steps[0].latency_delta_ms, 290);
+        assert!(!comparison.steps[0].status_differs);
+        assert!(comparison.steps[1].status_differs);
+
+        let text = render(&comparison);
+        assert!(text.contains("us: failed (1/2) em 100ms"));
+        assert!(text.contains("failed 95ms"));
+    }
+}
@@ -0,0 +1,171 @@
+//! # Módulo de Arquivo de Relatório - Compressão por Extensão
+//!
+//! Grava (e lê) relatórios comprimidos conforme a extensão do arquivo de
+//! `--output`.
+//!
+//! ## Para todos entenderem:
+//!
+//! Com `--report-detail full`, um plano de 100 steps gera relatórios de
+//! dezenas de MB (bodies, snapshots de contexto). JSON comprime muito bem:
+//! basta trocar `--output report.json` por `--output report.json.zst`.
+//!
+//! | Extensão         | Formato          |
+//! |------------------|------------------|
+//! | `.gz`            | gzip             |
+//! | `.zst` / `.zstd` | Zstandard        |
+//! | outras           | JSON sem compressão |
+//!
+//! O JSON é serializado direto no compressor (streaming), sem montar o
+//! relatório inteiro em uma `String` na memória. `--retry-failed` lê os
+//! mesmos formatos.
+
+use anyhow::{Context as _, Result};
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Nível de compressão do zstd (padrão da biblioteca: bom equilíbrio).
+const ZSTD_LEVEL: i32 = 3;
+
+// ============================================================================
+// FORMATO
+// ============================================================================
+
+/// Compressão escolhida pela extensão do arquivo.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportCompression {
+    /// JSON puro.
+    None,
+    /// `.gz`
+    Gzip,
+    /// `.zst` / `.zstd`
+    Zstd,
+}
+
+impl ReportCompression {
+    /// Detecta a compressão pela extensão (sem diferenciar maiúsculas).
+    pub fn from_path(path: &Path) -> Self {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase());
+        match extension.as_deref() {
+            Some("gz") => Self::Gzip,
+            Some("zst") | Some("zstd") => Self::Zstd,
+            _ => Self::None,
+        }
+    }
+}
+
+// ============================================================================
+// ESCRITA E LEITURA
+// ============================================================================
+
+/// Serializa `value` como JSON formatado em `path`, comprimindo conforme a extensão.
+pub fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let file = crate::output::create_file(path)?;
+    let writer = BufWriter::new(file);
+
+    match ReportCompression::from_path(path) {
+        ReportCompression::None => {
+            let mut writer = writer;
+            serde_json::to_writer_pretty(&mut writer, value)?;
+            writer.flush()?;
+        }
+        ReportCompression::Gzip => {
+            let mut encoder = GzEncoder::new(writer, flate2::Compression::default());
+            serde_json::to_writer_pretty(&mut encoder, value)?;
+            encoder.finish()?.flush()?;
+        }
+        ReportCompression::Zstd => {
+            let mut encoder = zstd::Encoder::new(writer, ZSTD_LEVEL)?;
+            serde_json::to_writer_pretty(&mut encoder, value)?;
+            encoder.finish()?.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Lê um relatório (comprimido ou não) como texto.
+pub fn read_to_string(path: &Path) -> Result<String> {
+    let file = File::open(path).with_context(|| format!("Falha ao abrir {:?}", path))?;
+    let reader = BufReader::new(file);
+
+    let mut content = String::new();
+    match ReportCompression::from_path(path) {
+        ReportCompression::None => {
+            let mut reader = reader;
+            reader.read_to_string(&mut content)?;
+        }
+        ReportCompression::Gzip => {
+            MultiGzDecoder::new(reader).read_to_string(&mut content)?;
+        }
+        ReportCompression::Zstd => {
+            zstd::Decoder::new(reader)?.read_to_string(&mut content)?;
+        }
+    }
+    Ok(content)
+}
+
+/// Caminho derivado com um sufixo: `report.json.gz` → `report.eu.json.gz`.
+///
+/// O sufixo entra antes de todas as extensões, preservando a compressão.
+pub fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let name = match name.split_once('.') {
+        Some((stem, extensions)) => format!("{}.{}.{}", stem, suffix, extensions),
+        None => format!("{}.{}", name, suffix),
+    };
+    path.with_file_name(name)
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn test_detects_compression_by_extension() {
+        assert_eq!(
+            ReportCompression::from_path(Path::new("out/report.json.zst")),
+            ReportCompression::Zstd
+        );
+        assert_eq!(
+            ReportCompression::from_path(Path::new("report.json.GZ")),
+            ReportCompression::Gzip
+        );
+        assert_eq!(
+            ReportCompression::from_path(Path::new("report.json")),
+            ReportCompression::None
+        );
+    }
+
+    #[test]
+    fn test_round_trip_all_formats() {
+        let report = json!({ "status": "passed", "steps": [{ "step_id": "a" }] });
+        let dir = std::env::temp_dir();
+
+        for extension in ["json", "json.gz", "json.zst"] {
+            let path = dir.join(format!("aqa-report-{}.{}", std::process::id(), extension));
+            write_json(&path, &report).unwrap();
+
+            let raw = std::fs::read(&path).unwrap();
+            let content = read_to_string(&path).unwrap();
+            let _ = std::fs::remove_file(&path);
+
+            assert_eq!(serde_json::from_str::<Value>(&content).unwrap(), report);
+            // Só o JSON puro começa com '{'.
+            assert_eq!(raw[0] == b'{', extension == "json", "{}", extension);
+        }
+    }
+}
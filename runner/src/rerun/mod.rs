@@ -0,0 +1,215 @@
+//! # Módulo de Re-execução - `--retry-failed`
+//!
+//! Reexecuta apenas a parte de um plano que falhou em uma execução
+//! anterior e mescla os resultados em um novo relatório.
+//!
+//! ## Para todos entenderem:
+//!
+//! Uma suíte noturna de 300 steps teve 4 falhas intermitentes. Em vez de
+//! rodar tudo de novo, `--retry-failed relatorio_anterior.json` roda só os
+//! 4 steps (e o que eles precisam), e o novo relatório mostra o plano
+//! inteiro: os resultados novos e os que já tinham passado.
+//!
+//! ## Quais steps são reexecutados:
+//!
+//! | Situação no relatório anterior         | Reexecuta? |
+//! |----------------------------------------|------------|
+//! | `failed` ou `skipped`                  | Sim        |
+//! | Ausente (step novo no plano)           | Sim        |
+//! | `passed`                               | Não        |
+//! | `passed`, mas é dependência de um acima| Sim        |
+//!
+//! As dependências (`depends_on`, transitivamente) são reexecutadas porque
+//! as variáveis que elas extraem não são restauradas do relatório anterior.
+//!
+//! Resultados reaproveitados levam `reused_from` com o `execution_id` da
+//! execução de onde vieram.
+
+use anyhow::{anyhow, Context as _, Result};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::protocol::{Step, StepResult, StepStatus};
+use crate::selection;
+
+// ============================================================================
+// RELATÓRIO ANTERIOR
+// ============================================================================
+
+/// Campos do relatório anterior necessários para a re-execução.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PreviousReport {
+    /// ID da execução anterior.
+    pub execution_id: String,
+
+    /// ID do plano executado (deve coincidir com o plano atual).
+    pub plan_id: String,
+
+    /// Resultados de cada step.
+    pub steps: Vec<StepResult>,
+}
+
+/// Lê o relatório anterior e confere se ele pertence ao mesmo plano.
+pub fn load_previous_report(path: &Path, plan_id: &str) -> Result<PreviousReport> {
+    let content = crate::report_file::read_to_string(path)
+        .with_context(|| format!("Falha ao ler relatório anterior {:?}", path))?;
+    let report: PreviousReport = serde_json::from_str(&content)
+        .with_context(|| format!("Relatório anterior inválido {:?}", path))?;
+
+    if report.plan_id != plan_id {
+        return Err(anyhow!(
+            "Relatório anterior é do plano '{}', mas o plano atual é '{}'",
+            report.plan_id,
+            plan_id
+        ));
+    }
+    Ok(report)
+}
+
+// ============================================================================
+// SELEÇÃO
+// ============================================================================
+
+/// Retorna os IDs dos steps a reexecutar (falhos, novos e suas dependências).
+pub fn select_steps(steps: &[Step], previous: &PreviousReport) -> HashSet<String> {
+    let passed: HashSet<&str> = previous
+        .steps
+        .iter()
+        .filter(|r| r.status == StepStatus::Passed)
+        .map(|r| r.step_id.as_str())
+        .collect();
+    let roots = steps
+        .iter()
+        .filter(|s| !passed.contains(s.id.as_str()))
+        .map(|s| s.id.as_str());
+    selection::with_dependencies(steps, roots)
+}
+
+// ============================================================================
+// MESCLAGEM
+// ============================================================================
+
+/// Monta os resultados do novo relatório, na ordem dos steps do plano.
+///
+/// Steps reexecutados usam o resultado novo; os demais reaproveitam o
+/// resultado anterior, marcado com `reused_from`.
+pub fn merge_results(
+    steps: &[Step],
+    previous: PreviousReport,
+    new_results: Vec<StepResult>,
+) -> Vec<StepResult> {
+    let mut fresh: HashMap<String, StepResult> = new_results
+        .into_iter()
+        .map(|r| (r.step_id.clone(), r))
+        .collect();
+    let mut old: HashMap<String, StepResult> = previous
+        .steps
+        .into_iter()
+        .map(|r| (r.step_id.clone(), r))
+        .collect();
+
+    steps
+        .iter()
+        .filter_map(|step| {
+            fresh.remove(&step.id).or_else(|| {
+                old.remove(&step.id).map(|mut result| {
+                    result.reused_from = Some(previous.execution_id.clone());
+                    result
+                })
+            })
+        })
+        .collect()
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(id: &str, depends_on: &[&str]) -> Step {
+        Step {
+            id: id.to_string(),
+            action: "log".to_string(),
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn result(id: &str, status: StepStatus) -> StepResult {
+        StepResult {
+            step_id: id.to_string(),
+            status,
+            ..Default::default()
+        }
+    }
+
+    fn previous() -> PreviousReport {
+        PreviousReport {
+            execution_id: "exec-1".to_string(),
+            plan_id: "plan".to_string(),
+            steps: vec![
+                result("login", StepStatus::Passed),
+                result("create", StepStatus::Failed),
+                result("read", StepStatus::Skipped),
+                result("health", StepStatus::Passed),
+            ],
+        }
+    }
+
+    fn plan_steps() -> Vec<Step> {
+        vec![
+            step("login", &[]),
+            step("create", &["login"]),
+            step("read", &["create"]),
+            step("health", &[]),
+            step("new_step", &[]),
+        ]
+    }
+
+    #[test]
+    fn test_select_failed_new_and_dependencies() {
+        let selected = select_steps(&plan_steps(), &previous());
+
+        let expected: HashSet<String> = ["login", "create", "read", "new_step"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(selected, expected);
+    }
+
+    #[test]
+    fn test_merge_keeps_plan_order_and_marks_reused() {
+        let merged = merge_results(
+            &plan_steps(),
+            previous(),
+            vec![
+                result("read", StepStatus::Passed),
+                result("create", StepStatus::Passed),
+            ],
+        );
+
+        let ids: Vec<&str> = merged.iter().map(|r| r.step_id.as_str()).collect();
+        assert_eq!(ids, vec!["login", "create", "read", "health"]);
+        assert_eq!(merged[0].reused_from.as_deref(), Some("exec-1"));
+        assert_eq!(merged[1].status, StepStatus::Passed);
+        assert!(merged[1].reused_from.is_none());
+    }
+
+    #[test]
+    fn test_load_rejects_other_plan() {
+        let path = std::env::temp_dir().join(format!("aqa-rerun-{}.json", std::process::id()));
+        std::fs::write(
+            &path,
+            r#"{"execution_id":"e","plan_id":"outro","steps":[]}"#,
+        )
+        .unwrap();
+
+        let error = load_previous_report(&path, "plan").unwrap_err();
+        let _ = std::fs::remove_file(&path);
+        assert!(error.to_string().contains("outro"));
+    }
+}
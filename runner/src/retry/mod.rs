@@ -89,7 +89,7 @@ impl RecoveryStrategy {
     /// - "fail_fast" ou "failfast" → FailFast
     /// - "ignore" → Ignore
     /// - Qualquer outro valor → FailFast (comportamento conservador)
-    pub fn from_str(s: &str) -> Self {
+    pub fn parse(s: &str) -> Self {
         match s.to_lowercase().as_str() {
             "retry" => Self::Retry,
             "fail_fast" | "failfast" => Self::FailFast,
@@ -133,7 +133,7 @@ pub struct RetryExecutor {
 impl RetryExecutor {
     /// Cria um novo RetryExecutor a partir de uma política.
     pub fn new(policy: RecoveryPolicy) -> Self {
-        let strategy = RecoveryStrategy::from_str(&policy.strategy);
+        let strategy = RecoveryStrategy::parse(&policy.strategy);
         Self { policy, strategy }
     }
 
@@ -395,4 +395,14 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(attempts.load(Ordering::SeqCst), 3); // Todas tentativas
     }
+
+    #[test]
+    fn test_strategy_ignores_case() {
+        assert_eq!(RecoveryStrategy::parse("Retry"), RecoveryStrategy::Retry);
+        assert_eq!(RecoveryStrategy::parse("IGNORE"), RecoveryStrategy::Ignore);
+        assert_eq!(
+            RecoveryStrategy::parse("FailFast"),
+            RecoveryStrategy::FailFast
+        );
+    }
 }
@@ -0,0 +1,166 @@
+//! # Módulo de Escopo de Extrações
+//!
+//! Implementa `extract[].scope: "step"`: o valor extraído fica visível
+//! apenas para os steps que declaram o step de origem em `depends_on`.
+//!
+//! ## Para todos entenderem:
+//!
+//! Dois steps que extraem `id` (um pedido e um produto) sobrescrevem um ao
+//! outro no contexto global; `Context::set` só avisa no log. Com escopo de
+//! step, cada `id` fica guardado junto do step que o extraiu:
+//!
+//! | Momento                                   | `${id}`                     |
+//! |-------------------------------------------|-----------------------------|
+//! | Step com `"depends_on": ["create_order"]` | id extraído por `create_order` |
+//! | Step com `"depends_on": ["create_product"]` | id extraído por `create_product` |
+//! | Step sem essas dependências               | valor global (se houver)    |
+//!
+//! ## Como funciona:
+//!
+//! 1. `enter` (antes do step) expõe as extrações de escopo `step` das
+//!    dependências, guardando os valores globais que elas escondem.
+//! 2. `leave` (depois do step) restaura os globais e move as extrações de
+//!    escopo `step` do próprio step para `Context::step_variables`.
+
+use serde_json::Value;
+
+use crate::context::Context;
+use crate::protocol::{ExtractionScope, Step};
+
+/// Estado salvo por `enter` para ser desfeito em `leave`.
+pub struct ExtractionScopeGuard {
+    /// Valores globais escondidos pelas extrações das dependências.
+    shadowed: Vec<(String, Option<Value>)>,
+    /// Alvos de escopo `step` do próprio step: valor global e valor na entrada.
+    own: Vec<(String, Option<Value>, Option<Value>)>,
+}
+
+/// Expõe as extrações de escopo `step` das dependências declaradas.
+pub fn enter(context: &mut Context, step: &Step) -> ExtractionScopeGuard {
+    let globals: Vec<(String, Option<Value>)> = step
+        .extract
+        .iter()
+        .filter(|e| e.scope == ExtractionScope::Step)
+        .map(|e| (e.target.clone(), context.variables.get(&e.target).cloned()))
+        .collect();
+
+    let mut shadowed: Vec<(String, Option<Value>)> = Vec::new();
+    for dependency in &step.depends_on {
+        let Some(values) = context.step_variables.get(dependency).cloned() else {
+            continue;
+        };
+        for (name, value) in values {
+            let previous = context.variables.insert(name.clone(), value);
+            // Duas dependências com o mesmo alvo: vale a última, mas o
+            // global a restaurar continua sendo o de antes da primeira.
+            if !shadowed.iter().any(|(n, _)| *n == name) {
+                shadowed.push((name, previous));
+            }
+        }
+    }
+
+    let own = globals
+        .into_iter()
+        .map(|(name, global)| {
+            let on_entry = context.variables.get(&name).cloned();
+            (name, global, on_entry)
+        })
+        .collect();
+
+    ExtractionScopeGuard { shadowed, own }
+}
+
+/// Restaura os globais e guarda as extrações de escopo `step` do step.
+pub fn leave(context: &mut Context, step: &Step, guard: ExtractionScopeGuard) {
+    for (name, global, on_entry) in guard.own {
+        let current = context.variables.get(&name).cloned();
+        let Some(value) = current.filter(|v| Some(v) != on_entry.as_ref()) else {
+            continue;
+        };
+        restore(context, name.clone(), global);
+        context
+            .step_variables
+            .entry(step.id.clone())
+            .or_default()
+            .insert(name, value);
+    }
+    for (name, previous) in guard.shadowed {
+        restore(context, name, previous);
+    }
+}
+
+fn restore(context: &mut Context, name: String, previous: Option<Value>) {
+    match previous {
+        Some(value) => {
+            context.variables.insert(name, value);
+        }
+        None => {
+            context.variables.remove(&name);
+        }
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn step(id: &str, depends_on: &[&str], scoped_target: Option<&str>) -> Step {
+        let extract = scoped_target
+            .map(|t| json!([{ "source": "body", "path": "$.id", "target": t, "scope": "step" }]))
+            .unwrap_or(json!([]));
+        serde_json::from_value(json!({
+            "id": id, "action": "log", "params": {},
+            "depends_on": depends_on, "extract": extract
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_step_scoped_extraction_does_not_overwrite_global() {
+        let mut ctx = Context::new();
+        ctx.set("id", json!("global"));
+
+        let order = step("create_order", &[], Some("id"));
+        let guard = enter(&mut ctx, &order);
+        ctx.set("id", json!("order-1"));
+        leave(&mut ctx, &order, guard);
+
+        let product = step("create_product", &[], Some("id"));
+        let guard = enter(&mut ctx, &product);
+        ctx.set("id", json!("product-7"));
+        leave(&mut ctx, &product, guard);
+
+        assert_eq!(ctx.get("id"), Some(&json!("global")));
+        assert_eq!(ctx.step_variables["create_order"]["id"], json!("order-1"));
+        assert_eq!(
+            ctx.step_variables["create_product"]["id"],
+            json!("product-7")
+        );
+    }
+
+    #[test]
+    fn test_only_declared_dependents_see_scoped_values() {
+        let mut ctx = Context::new();
+        let order = step("create_order", &[], Some("order_id"));
+        let guard = enter(&mut ctx, &order);
+        ctx.set("order_id", json!(42));
+        leave(&mut ctx, &order, guard);
+        assert_eq!(ctx.get("order_id"), None);
+
+        let dependent = step("get_order", &["create_order"], None);
+        let guard = enter(&mut ctx, &dependent);
+        assert_eq!(ctx.get("order_id"), Some(&json!(42)));
+        leave(&mut ctx, &dependent, guard);
+        assert_eq!(ctx.get("order_id"), None);
+
+        let unrelated = step("list", &[], None);
+        let guard = enter(&mut ctx, &unrelated);
+        assert_eq!(ctx.get("order_id"), None);
+        leave(&mut ctx, &unrelated, guard);
+    }
+}
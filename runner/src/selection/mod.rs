@@ -0,0 +1,248 @@
+//! # Módulo de Seleção - `--only`, `--skip` e `--tags`
+//!
+//! Decide quais steps do plano rodam quando a CLI pede um subconjunto, sem
+//! quebrar as dependências entre eles.
+//!
+//! ## Para todos entenderem:
+//!
+//! Ao depurar um endpoint, rodar o plano inteiro é lento; ao saber que um
+//! step está quebrado por um motivo conhecido, ele atrapalha o resto:
+//!
+//! ```bash
+//! runner execute --file plan.json --only create_order
+//! runner execute --file plan.json --tags smoke,auth --skip slow_report
+//! ```
+//!
+//! | Filtro    | Efeito                                                     |
+//! |-----------|------------------------------------------------------------|
+//! | `--only`  | Roda os steps citados **e as dependências deles**          |
+//! | `--tags`  | Roda os steps com alguma das tags (e as dependências)      |
+//! | `--skip`  | Não roda o step nem os que dependem dele (direta ou não)   |
+//!
+//! - `--only` e `--tags` juntos somam: entra o step citado ou marcado.
+//! - `--skip` vale por último: vence mesmo uma dependência incluída.
+//! - Steps fora da seleção aparecem no relatório como `not_run` com
+//!   `skip_reason: filtered_out` e o motivo em `error` (não reprovam a
+//!   execução).
+//! - Um ID inexistente em `--only`/`--skip` é erro: um typo não deve
+//!   virar "rodou tudo" ou "rodou nada" em silêncio.
+
+use anyhow::{bail, Result};
+use std::collections::{HashMap, HashSet};
+
+use crate::protocol::{SkipReason, Step, StepResult, StepStatus};
+
+/// Filtros de seleção vindos da CLI.
+#[derive(Debug, Clone, Default)]
+pub struct StepFilter {
+    /// `--only`: IDs a executar.
+    pub only: Vec<String>,
+    /// `--skip`: IDs a não executar.
+    pub skip: Vec<String>,
+    /// `--tags`: tags a executar.
+    pub tags: Vec<String>,
+}
+
+impl StepFilter {
+    pub fn is_empty(&self) -> bool {
+        self.only.is_empty() && self.skip.is_empty() && self.tags.is_empty()
+    }
+
+    /// Recusa IDs de `--only`/`--skip` que não existem em `steps`.
+    ///
+    /// Roda contra o plano original: um step podado por `--retry-failed` ou
+    /// pelo perfil ainda é um ID válido.
+    pub fn check_ids(&self, steps: &[Step]) -> Result<()> {
+        let known: HashSet<&str> = steps.iter().map(|s| s.id.as_str()).collect();
+        for (flag, ids) in [("--only", &self.only), ("--skip", &self.skip)] {
+            if let Some(unknown) = ids.iter().find(|id| !known.contains(id.as_str())) {
+                bail!("{}: step '{}' não existe no plano", flag, unknown);
+            }
+        }
+        Ok(())
+    }
+
+    /// Aplica os filtros: IDs que rodam e um resultado `not_run` por step excluído.
+    pub fn select(&self, steps: &[Step]) -> (HashSet<String>, Vec<StepResult>) {
+        // 1. Raízes (--only/--tags) mais as dependências transitivas.
+        let mut selected = if self.only.is_empty() && self.tags.is_empty() {
+            steps.iter().map(|s| s.id.clone()).collect()
+        } else {
+            with_dependencies(
+                steps,
+                steps
+                    .iter()
+                    .filter(|s| {
+                        self.only.contains(&s.id) || s.tags.iter().any(|t| self.tags.contains(t))
+                    })
+                    .map(|s| s.id.as_str()),
+            )
+        };
+
+        // 2. --skip e, por propagação, quem depende de um step excluído.
+        let mut reasons: HashMap<&str, String> = HashMap::new();
+        for id in &self.skip {
+            reasons.insert(id.as_str(), "Excluído por --skip".to_string());
+        }
+        loop {
+            let blocked: Vec<(&str, String)> = steps
+                .iter()
+                .filter(|s| selected.contains(&s.id) && !reasons.contains_key(s.id.as_str()))
+                .filter_map(|s| {
+                    let dep = s
+                        .depends_on
+                        .iter()
+                        .find(|d| reasons.contains_key(d.as_str()))?;
+                    Some((
+                        s.id.as_str(),
+                        format!("Depende de '{}', excluído por --skip", dep),
+                    ))
+                })
+                .collect();
+            if blocked.is_empty() {
+                break;
+            }
+            reasons.extend(blocked);
+        }
+        selected.retain(|id| !reasons.contains_key(id.as_str()));
+
+        let excluded = steps
+            .iter()
+            .filter(|s| !selected.contains(&s.id))
+            .map(|s| StepResult {
+                step_id: s.id.clone(),
+                status: StepStatus::NotRun,
+                error: Some(
+                    reasons
+                        .remove(s.id.as_str())
+                        .unwrap_or_else(|| "Fora da seleção (--only/--tags)".to_string()),
+                ),
+                skip_reason: Some(SkipReason::FilteredOut),
+                ..Default::default()
+            })
+            .collect();
+        (selected, excluded)
+    }
+}
+
+/// IDs de `roots` mais todas as dependências transitivas (`depends_on`).
+///
+/// Base de `--only`/`--tags`, das tags do perfil e do `--retry-failed`.
+pub fn with_dependencies<'a>(
+    steps: &'a [Step],
+    roots: impl IntoIterator<Item = &'a str>,
+) -> HashSet<String> {
+    let by_id: HashMap<&str, &Step> = steps.iter().map(|s| (s.id.as_str(), s)).collect();
+    let mut pending: Vec<&str> = roots.into_iter().collect();
+    let mut selected = HashSet::new();
+    while let Some(id) = pending.pop() {
+        if !selected.insert(id.to_string()) {
+            continue;
+        }
+        if let Some(step) = by_id.get(id) {
+            pending.extend(step.depends_on.iter().map(String::as_str));
+        }
+    }
+    selected
+}
+
+/// Junta os resultados executados aos excluídos, na ordem `order` do plano.
+///
+/// Um step que já tem resultado (ex: reaproveitado por `--retry-failed`)
+/// mantém o resultado em vez da entrada `not_run`.
+pub fn merge(
+    order: &[String],
+    results: Vec<StepResult>,
+    excluded: Vec<StepResult>,
+) -> Vec<StepResult> {
+    let position = |id: &str| order.iter().position(|o| o == id).unwrap_or(usize::MAX);
+    let ran: HashSet<String> = results.iter().map(|r| r.step_id.clone()).collect();
+    let mut merged: Vec<StepResult> = results
+        .into_iter()
+        .chain(excluded.into_iter().filter(|r| !ran.contains(&r.step_id)))
+        .collect();
+    merged.sort_by_key(|r| position(&r.step_id));
+    merged
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(id: &str, deps: &[&str], tags: &[&str]) -> Step {
+        Step {
+            id: id.to_string(),
+            action: "log".to_string(),
+            depends_on: deps.iter().map(|d| d.to_string()).collect(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn plan() -> Vec<Step> {
+        vec![
+            step("login", &[], &["auth"]),
+            step("create_order", &["login"], &[]),
+            step("pay", &["create_order"], &["smoke"]),
+            step("report", &[], &["slow"]),
+        ]
+    }
+
+    #[test]
+    fn test_only_and_tags_pull_in_dependencies() {
+        let filter = StepFilter {
+            tags: vec!["smoke".to_string()],
+            ..Default::default()
+        };
+        let (selected, excluded) = filter.select(&plan());
+        let mut selected: Vec<_> = selected.into_iter().collect();
+        selected.sort();
+        assert_eq!(selected, ["create_order", "login", "pay"]);
+        assert_eq!(excluded.len(), 1);
+        assert_eq!(excluded[0].step_id, "report");
+        assert_eq!(excluded[0].status, StepStatus::NotRun);
+        assert_eq!(excluded[0].skip_reason, Some(SkipReason::FilteredOut));
+
+        let only = StepFilter {
+            only: vec!["create_order".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(only.select(&plan()).0.len(), 2);
+    }
+
+    #[test]
+    fn test_skip_propagates_to_dependents_and_rejects_unknown_ids() {
+        let filter = StepFilter {
+            skip: vec!["login".to_string()],
+            ..Default::default()
+        };
+        let (selected, excluded) = filter.select(&plan());
+        assert_eq!(selected, HashSet::from(["report".to_string()]));
+        let reasons: Vec<_> = excluded
+            .iter()
+            .map(|r| r.error.as_deref().unwrap())
+            .collect();
+        assert_eq!(
+            reasons,
+            [
+                "Excluído por --skip",
+                "Depende de 'login', excluído por --skip",
+                "Depende de 'create_order', excluído por --skip"
+            ]
+        );
+
+        let typo = StepFilter {
+            only: vec!["pay_typo".to_string()],
+            ..Default::default()
+        };
+        assert!(typo
+            .check_ids(&plan())
+            .unwrap_err()
+            .to_string()
+            .contains("--only: step 'pay_typo'"));
+    }
+}
@@ -0,0 +1,312 @@
+//! # Módulo de Auto-Atualização - `runner self-update`
+//!
+//! Baixa o binário do Runner da última GitHub Release (ou de uma versão
+//! específica) e substitui o executável atual.
+//!
+//! ## Para todos entenderem:
+//!
+//! Em vez de entrar no GitHub, achar o arquivo certo para o seu sistema e
+//! trocar o binário na mão:
+//!
+//! ```bash
+//! runner self-update --check        # só informa se há versão nova
+//! runner self-update                # atualiza para a última versão
+//! runner self-update --version 0.6.0
+//! ```
+//!
+//! ## Como funciona:
+//!
+//! 1. Consulta a API de releases do GitHub (`GITHUB_TOKEN`, se definido,
+//!    evita o limite de requisições anônimas).
+//! 2. Escolhe o asset do sistema atual (mesmos nomes do workflow de release):
+//!
+//! | Sistema         | Asset                          |
+//! |-----------------|--------------------------------|
+//! | Linux x64       | `aqa-runner-linux-x64`         |
+//! | macOS x64       | `aqa-runner-macos-x64`         |
+//! | macOS ARM       | `aqa-runner-macos-arm64`       |
+//! | Windows x64     | `aqa-runner-windows-x64.exe`   |
+//!
+//! 3. Confere o SHA-256 com o asset `<nome>.sha256` da release. Sem esse
+//!    asset, a atualização é recusada (só `--insecure` instala sem checar).
+//! 4. Grava o binário novo ao lado do atual e troca os dois com `rename`
+//!    (o antigo fica como `<exe>.old` até a próxima atualização).
+//!
+//! Só existe quando o Runner é compilado com a feature `self-update`
+//! (padrão).
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Repositório das releases.
+const REPOSITORY: &str = "lipeamarok/autonomous-quality-agent";
+
+/// API do GitHub (sobrescrevível por `RUNNER_UPDATE_API_URL`, ex: mirror interno).
+const DEFAULT_API_URL: &str = "https://api.github.com";
+
+// ============================================================================
+// RELEASES
+// ============================================================================
+
+/// Release do GitHub (só os campos usados).
+#[derive(Debug, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+/// Arquivo anexado a uma release.
+#[derive(Debug, Deserialize)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+impl Release {
+    /// Versão sem o prefixo `v` da tag.
+    pub fn version(&self) -> &str {
+        self.tag_name.trim_start_matches('v')
+    }
+
+    fn asset(&self, name: &str) -> Option<&ReleaseAsset> {
+        self.assets.iter().find(|a| a.name == name)
+    }
+}
+
+/// Nome do asset para o sistema atual (`None` se não há binário publicado).
+pub fn platform_asset_name() -> Option<&'static str> {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("linux", "x86_64") => Some("aqa-runner-linux-x64"),
+        ("macos", "x86_64") => Some("aqa-runner-macos-x64"),
+        ("macos", "aarch64") => Some("aqa-runner-macos-arm64"),
+        ("windows", "x86_64") => Some("aqa-runner-windows-x64.exe"),
+        _ => None,
+    }
+}
+
+/// Compara versões `maior.menor.patch` (sufixos como `-beta.1` são ignorados).
+pub fn is_newer(candidate: &str, current: &str) -> bool {
+    fn parts(version: &str) -> Vec<u64> {
+        version
+            .split(['-', '+'])
+            .next()
+            .unwrap_or("")
+            .split('.')
+            .map(|p| p.parse().unwrap_or(0))
+            .collect()
+    }
+    parts(candidate) > parts(current)
+}
+
+// ============================================================================
+// ATUALIZAÇÃO
+// ============================================================================
+
+/// Opções de `runner self-update`.
+pub struct UpdateOptions {
+    /// Apenas informa se há versão nova.
+    pub check_only: bool,
+    /// Versão específica (sem `v`); `None` = última.
+    pub version: Option<String>,
+    /// Instala mesmo sem o checksum `.sha256` na release.
+    pub insecure: bool,
+}
+
+/// Executa o self-update e devolve a mensagem final para o usuário.
+pub async fn run(options: UpdateOptions) -> Result<String> {
+    let current = env!("CARGO_PKG_VERSION");
+    let client = reqwest::Client::builder()
+        .user_agent(format!("aqa-runner/{}", current))
+        .build()?;
+
+    let release = fetch_release(&client, options.version.as_deref()).await?;
+    let target = release.version().to_string();
+
+    if options.version.is_none() && !is_newer(&target, current) {
+        return Ok(format!("runner {} já é a versão mais recente", current));
+    }
+    if options.check_only {
+        return Ok(format!(
+            "Nova versão disponível: {} (atual: {})",
+            target, current
+        ));
+    }
+
+    let asset_name = platform_asset_name().ok_or_else(|| {
+        anyhow!(
+            "Nenhum binário publicado para {}-{}",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        )
+    })?;
+    let asset = release.asset(asset_name).ok_or_else(|| {
+        anyhow!(
+            "Release {} não tem o asset '{}'",
+            release.tag_name,
+            asset_name
+        )
+    })?;
+
+    let binary = download(&client, &asset.browser_download_url).await?;
+    match release.asset(&format!("{}.sha256", asset_name)) {
+        Some(checksum) => {
+            let expected =
+                String::from_utf8_lossy(&download(&client, &checksum.browser_download_url).await?)
+                    .to_string();
+            verify_checksum(&binary, &expected)?;
+        }
+        None if options.insecure => {
+            tracing::warn!(asset = %asset_name, "Release sem checksum .sha256; binário não verificado (--insecure)")
+        }
+        None => bail!(
+            "Release {} não tem '{}.sha256': binário não verificável (use --insecure para instalar assim mesmo)",
+            release.tag_name,
+            asset_name
+        ),
+    }
+
+    let exe = std::env::current_exe().context("Não foi possível localizar o executável atual")?;
+    replace_executable(&exe, &binary)?;
+    Ok(format!("runner atualizado: {} → {}", current, target))
+}
+
+/// Busca a última release ou a da tag `v<version>`.
+async fn fetch_release(client: &reqwest::Client, version: Option<&str>) -> Result<Release> {
+    let api =
+        std::env::var("RUNNER_UPDATE_API_URL").unwrap_or_else(|_| DEFAULT_API_URL.to_string());
+    let url = match version {
+        Some(v) => format!(
+            "{}/repos/{}/releases/tags/v{}",
+            api,
+            REPOSITORY,
+            v.trim_start_matches('v')
+        ),
+        None => format!("{}/repos/{}/releases/latest", api, REPOSITORY),
+    };
+
+    let mut request = client
+        .get(&url)
+        .header("Accept", "application/vnd.github+json");
+    if let Ok(token) = std::env::var("GITHUB_TOKEN") {
+        request = request.bearer_auth(token);
+    }
+    let response = request
+        .send()
+        .await
+        .with_context(|| format!("Falha ao consultar {}", url))?;
+    if !response.status().is_success() {
+        bail!("GitHub respondeu {} para {}", response.status(), url);
+    }
+    Ok(response.json().await?)
+}
+
+async fn download(client: &reqwest::Client, url: &str) -> Result<Vec<u8>> {
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .with_context(|| format!("Falha ao baixar {}", url))?;
+    if !response.status().is_success() {
+        bail!("Download respondeu {} para {}", response.status(), url);
+    }
+    Ok(response.bytes().await?.to_vec())
+}
+
+/// Confere o SHA-256 (`expected` no formato do `sha256sum`: `<hex>  <arquivo>`).
+pub fn verify_checksum(binary: &[u8], expected: &str) -> Result<()> {
+    let expected = expected
+        .split_whitespace()
+        .next()
+        .unwrap_or("")
+        .to_lowercase();
+    let actual = format!("{:x}", Sha256::digest(binary));
+    if actual != expected {
+        bail!(
+            "Checksum não confere (esperado {}, obtido {})",
+            expected,
+            actual
+        );
+    }
+    Ok(())
+}
+
+/// Troca o executável: grava `<exe>.new`, move o atual para `<exe>.old` e
+/// o novo para o lugar do atual (funciona mesmo com o binário em uso).
+fn replace_executable(exe: &Path, binary: &[u8]) -> Result<()> {
+    let new_path = with_suffix(exe, ".new");
+    let old_path = with_suffix(exe, ".old");
+
+    crate::output::write_file(&new_path, binary)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&new_path, std::fs::Permissions::from_mode(0o755))?;
+    }
+
+    let _ = std::fs::remove_file(&old_path);
+    std::fs::rename(exe, &old_path).with_context(|| format!("Falha ao mover {:?}", exe))?;
+    if let Err(e) = std::fs::rename(&new_path, exe) {
+        // Restaura o binário original.
+        let _ = std::fs::rename(&old_path, exe);
+        return Err(e).with_context(|| format!("Falha ao instalar {:?}", exe));
+    }
+    Ok(())
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_comparison() {
+        assert!(is_newer("0.6.0", "0.5.0"));
+        assert!(is_newer("0.5.10", "0.5.9"));
+        assert!(is_newer("1.0.0-beta.1", "0.9.9"));
+        assert!(!is_newer("0.5.0", "0.5.0"));
+        assert!(!is_newer("0.4.9", "0.5.0"));
+    }
+
+    #[test]
+    fn test_release_parsing_and_checksum() {
+        let release: Release = serde_json::from_str(
+            r#"{ "tag_name": "v0.6.0", "assets": [
+                { "name": "aqa-runner-linux-x64", "browser_download_url": "https://x/bin" }
+            ] }"#,
+        )
+        .unwrap();
+        assert_eq!(release.version(), "0.6.0");
+        assert!(release.asset("aqa-runner-linux-x64").is_some());
+
+        let digest = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        assert!(verify_checksum(b"hello", &format!("{}  aqa-runner-linux-x64\n", digest)).is_ok());
+        assert!(verify_checksum(b"tampered", digest).is_err());
+    }
+
+    #[test]
+    fn test_replace_executable_keeps_old_copy() {
+        let dir = std::env::temp_dir().join(format!("aqa-update-{}", std::process::id()));
+        let exe = dir.join("runner");
+        crate::output::write_file(&exe, b"old").unwrap();
+
+        replace_executable(&exe, b"new").unwrap();
+        let current = std::fs::read(&exe).unwrap();
+        let old = std::fs::read(with_suffix(&exe, ".old")).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(current, b"new");
+        assert_eq!(old, b"old");
+    }
+}
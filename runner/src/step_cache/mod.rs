@@ -0,0 +1,454 @@
+//! # Módulo de Cache de Steps - Tokens Reaproveitados Entre Execuções
+//!
+//! Steps com `cache: {key, ttl_s}` que passaram têm as variáveis que
+//! produziram gravadas em disco; nas execuções seguintes, dentro do TTL,
+//! o step não executa e as variáveis voltam direto para o contexto.
+//!
+//! ## Para todos entenderem:
+//!
+//! Rodando o mesmo plano várias vezes (desenvolvimento local, CI com
+//! vários jobs na mesma máquina), o login é refeito toda vez, mesmo com
+//! o token anterior ainda válido:
+//!
+//! ```json
+//! { "id": "login", "action": "http_request",
+//!   "cache": { "key": "login-admin", "ttl_s": 3000 },
+//!   "params": { "method": "POST", "path": "/oauth/token", ... },
+//!   "extract": [{ "source": "body", "path": "access_token", "target": "access_token" }] }
+//! ```
+//!
+//! ## Regras:
+//!
+//! | Situação                                         | Comportamento                    |
+//! |--------------------------------------------------|----------------------------------|
+//! | Sem entrada (ou expirada) para `key`             | Executa e grava se passar        |
+//! | Entrada válida, mesma `action`, `params` e alvo  | Pula a execução (`cache_hit`)    |
+//! | Mesma `key` com `action`/`params` diferentes     | Executa (é outro step)           |
+//! | Mesmos `params`, outro `base_url` ou `--var`     | Executa (é outro usuário/alvo)   |
+//! | Variável com JWT que expira antes do TTL         | A entrada vale até o `exp`       |
+//! | `--no-cache`                                     | Executa sempre (e regrava)       |
+//!
+//! Os `params` entram na identidade já interpolados com as variáveis do
+//! início da execução (plano, região, `--var`), junto com o `base_url`.
+//!
+//! As entradas ficam em `RUNNER_CACHE_DIR` (padrão: a pasta de cache do
+//! usuário, ex: `~/.cache/aqa-runner`), em `step-cache.json`. O arquivo
+//! guarda tokens em claro: em Unix, a pasta é criada com `0700` e o
+//! arquivo já nasce com `0600` (gravado em um temporário e renomeado).
+
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+use crate::context::Context;
+use crate::executors::StepExecutor;
+use crate::protocol::{CacheHit, Step, StepResult, StepStatus};
+use crate::suite::produced_variables;
+
+/// Variável de ambiente com a pasta do cache.
+pub const CACHE_DIR_ENV: &str = "RUNNER_CACHE_DIR";
+
+/// Arquivo do cache dentro da pasta.
+const CACHE_FILE: &str = "step-cache.json";
+
+// ============================================================================
+// ENTRADAS
+// ============================================================================
+
+/// Variáveis produzidas por um step, com validade.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct CacheEntry {
+    /// Hash de `action` + `params` interpolados + `base_url` do step que gravou a entrada.
+    fingerprint: String,
+    execution_id: String,
+    stored_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    variables: BTreeMap<String, Value>,
+}
+
+/// Cache de steps persistido em disco.
+#[derive(Debug, Clone)]
+pub struct StepCacheStore {
+    path: PathBuf,
+    entries: Arc<Mutex<HashMap<String, CacheEntry>>>,
+    /// Contexto do início da execução (interpola `params` no fingerprint).
+    context: Arc<Context>,
+}
+
+impl StepCacheStore {
+    /// Abre o cache de `RUNNER_CACHE_DIR` (ou da pasta de cache do usuário).
+    pub fn open(context: &Context) -> Self {
+        let dir = std::env::var(CACHE_DIR_ENV)
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| default_dir());
+        Self::at(dir.join(CACHE_FILE), context)
+    }
+
+    /// Abre o cache de um arquivo (ausente ou ilegível = cache vazio).
+    pub fn at(path: PathBuf, context: &Context) -> Self {
+        let entries = read_entries(&path);
+        Self {
+            path,
+            entries: Arc::new(Mutex::new(entries)),
+            context: Arc::new(context.clone()),
+        }
+    }
+
+    /// Entrada válida para o step (mesma key, mesmo step, não expirada).
+    fn lookup(&self, step: &Step) -> Option<CacheEntry> {
+        let cache = step.cache.as_ref()?;
+        let entries = self.entries.lock().expect("step cache poisoned");
+        entries
+            .get(&cache.key)
+            .filter(|e| {
+                e.fingerprint == fingerprint(step, &self.context) && e.expires_at > Utc::now()
+            })
+            .cloned()
+    }
+
+    /// Grava as variáveis dos steps com `cache` que passaram e salva o arquivo.
+    pub fn record(&self, steps: &[Step], results: &[StepResult], execution_id: &str) -> Result<()> {
+        let now = Utc::now();
+        let mut fresh = HashMap::new();
+        for step in steps {
+            let Some(cache) = &step.cache else {
+                continue;
+            };
+            let Some(result) = results.iter().find(|r| r.step_id == step.id) else {
+                continue;
+            };
+            if result.status != StepStatus::Passed
+                || result.cache_hit.is_some()
+                || result.reused_from.is_some()
+            {
+                continue;
+            }
+            let variables = produced_variables(result);
+            let ttl = chrono::Duration::seconds(cache.ttl_s.min(i64::MAX as u64) as i64);
+            let expires_at = variables
+                .values()
+                .filter_map(jwt_expiry)
+                .fold(now + ttl, |earliest, exp| earliest.min(exp));
+            if expires_at <= now {
+                continue;
+            }
+            fresh.insert(
+                cache.key.clone(),
+                CacheEntry {
+                    fingerprint: fingerprint(step, &self.context),
+                    execution_id: execution_id.to_string(),
+                    stored_at: now,
+                    expires_at,
+                    variables,
+                },
+            );
+        }
+        if fresh.is_empty() {
+            return Ok(());
+        }
+
+        // Relê o arquivo: outra execução pode ter gravado no meio tempo.
+        let mut entries = self.entries.lock().expect("step cache poisoned");
+        *entries = read_entries(&self.path);
+        entries.extend(fresh);
+        entries.retain(|_, e| e.expires_at > now);
+        write_private(&self.path, &serde_json::to_vec_pretty(&*entries)?)
+    }
+}
+
+/// Pasta de cache do usuário (`XDG_CACHE_HOME`, `~/.cache` ou `LOCALAPPDATA`).
+///
+/// Sem nenhuma delas, usa uma pasta temporária com o nome do usuário.
+fn default_dir() -> PathBuf {
+    std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .or_else(|| std::env::var_os("LOCALAPPDATA").map(PathBuf::from))
+        .map(|dir| dir.join("aqa-runner"))
+        .unwrap_or_else(|| {
+            let user = std::env::var("USER")
+                .or_else(|_| std::env::var("USERNAME"))
+                .unwrap_or_else(|_| "default".to_string());
+            std::env::temp_dir().join(format!("aqa-runner-cache-{}", user))
+        })
+}
+
+/// Grava o arquivo de forma atômica e visível só para o dono.
+///
+/// A pasta é criada com `0700`; o conteúdo vai para um temporário criado
+/// com `0600` e é renomeado por cima do arquivo (nunca existe uma janela
+/// com o token legível por outros usuários nem um arquivo pela metade).
+fn write_private(path: &Path, contents: &[u8]) -> Result<()> {
+    if let Some(dir) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        let mut builder = std::fs::DirBuilder::new();
+        builder.recursive(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+            builder.mode(0o700);
+            builder
+                .create(dir)
+                .with_context(|| format!("Falha ao criar {:?}", dir))?;
+            std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))
+                .with_context(|| format!("Falha ao restringir {:?}", dir))?;
+        }
+        #[cfg(not(unix))]
+        builder
+            .create(dir)
+            .with_context(|| format!("Falha ao criar {:?}", dir))?;
+    }
+
+    let temp = path.with_extension(format!("json.{}.tmp", std::process::id()));
+    let _ = std::fs::remove_file(&temp);
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let written = options
+        .open(&temp)
+        .and_then(|mut file| file.write_all(contents).and_then(|_| file.sync_all()))
+        .and_then(|_| std::fs::rename(&temp, path));
+    if written.is_err() {
+        let _ = std::fs::remove_file(&temp);
+    }
+    written.with_context(|| format!("Falha ao gravar {:?}", path))
+}
+
+/// Lê as entradas do arquivo (vazio se não existir ou estiver corrompido).
+fn read_entries(path: &Path) -> HashMap<String, CacheEntry> {
+    let Ok(bytes) = std::fs::read(path) else {
+        return HashMap::new();
+    };
+    serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+        warn!(path = ?path, error = %e, "Step cache unreadable; starting empty");
+        HashMap::new()
+    })
+}
+
+/// Identidade do step dentro da key: mesma ação, mesmos params (já
+/// interpolados) e mesmo `base_url`.
+///
+/// Params que dependem de variáveis ainda não extraídas entram crus. Só o
+/// hash vai para o disco (params podem ter senhas).
+fn fingerprint(step: &Step, context: &Context) -> String {
+    let params = context
+        .interpolate_value(&step.params)
+        .unwrap_or_else(|_| step.params.clone());
+    let base_url = context
+        .get("base_url")
+        .and_then(|u| u.as_str())
+        .unwrap_or_default();
+    format!(
+        "{:x}",
+        Sha256::digest(format!("{}\u{0}{}\u{0}{}", step.action, params, base_url).as_bytes())
+    )
+}
+
+/// `exp` de um valor que seja um JWT (`header.payload.assinatura`).
+fn jwt_expiry(value: &Value) -> Option<DateTime<Utc>> {
+    let mut parts = value.as_str()?.split('.');
+    let (Some(_), Some(payload), Some(_), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return None;
+    };
+    let claims: Value =
+        serde_json::from_slice(&URL_SAFE_NO_PAD.decode(payload.trim_end_matches('=')).ok()?)
+            .ok()?;
+    DateTime::from_timestamp(claims.get("exp")?.as_i64()?, 0)
+}
+
+// ============================================================================
+// EXECUTOR
+// ============================================================================
+
+/// Atende os steps com `cache` que têm uma entrada válida.
+pub struct StepCacheExecutor {
+    store: StepCacheStore,
+}
+
+impl StepCacheExecutor {
+    pub fn new(store: StepCacheStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl StepExecutor for StepCacheExecutor {
+    fn can_handle(&self, _action: &str) -> bool {
+        false
+    }
+
+    fn can_handle_step(&self, step: &Step) -> bool {
+        self.store.lookup(step).is_some()
+    }
+
+    async fn execute(&self, step: &Step, context: &mut Context) -> Result<StepResult> {
+        let key = step
+            .cache
+            .as_ref()
+            .map(|c| c.key.clone())
+            .unwrap_or_default();
+        let entry = self
+            .store
+            .lookup(step)
+            .ok_or_else(|| anyhow::anyhow!("Entrada de cache '{}' expirou", key))?;
+        info!(
+            step_id = %step.id,
+            key = %key,
+            expires_at = %entry.expires_at,
+            variables = entry.variables.len(),
+            "Step served from cache"
+        );
+        for (name, value) in entry.variables {
+            context.set(name, value);
+        }
+        Ok(StepResult {
+            step_id: step.id.clone(),
+            status: StepStatus::Passed,
+            reused_from: Some(entry.execution_id),
+            cache_hit: Some(CacheHit {
+                key,
+                stored_at: entry.stored_at.to_rfc3339_opts(SecondsFormat::Millis, true),
+                expires_at: entry
+                    .expires_at
+                    .to_rfc3339_opts(SecondsFormat::Millis, true),
+            }),
+            ..Default::default()
+        })
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn login(ttl_s: u64) -> Step {
+        serde_json::from_value(json!({
+            "id": "login", "action": "http_request",
+            "params": { "method": "POST", "path": "/token" },
+            "cache": { "key": "login-admin", "ttl_s": ttl_s }
+        }))
+        .unwrap()
+    }
+
+    fn passed_login(token: &str) -> StepResult {
+        serde_json::from_value(json!({
+            "step_id": "login", "status": "passed", "duration_ms": 40,
+            "context_delta": { "added": { "access_token": token } }
+        }))
+        .unwrap()
+    }
+
+    fn jwt(exp: i64) -> String {
+        let payload = URL_SAFE_NO_PAD.encode(json!({ "exp": exp }).to_string());
+        format!("eyJhbGciOiJIUzI1NiJ9.{}.c2ln", payload)
+    }
+
+    fn cache_path(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("aqa-cache-{}-{}.json", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[tokio::test]
+    async fn test_cached_step_served_from_disk_on_next_run() {
+        let path = cache_path("hit");
+        let step = login(3600);
+
+        StepCacheStore::at(path.clone(), &Context::new())
+            .record(
+                std::slice::from_ref(&step),
+                &[passed_login("abc")],
+                "exec-1",
+            )
+            .unwrap();
+
+        // Próxima execução: novo processo, mesmo arquivo.
+        let executor = StepCacheExecutor::new(StepCacheStore::at(path.clone(), &Context::new()));
+        assert!(executor.can_handle_step(&step));
+        let mut other = step.clone();
+        other.params = json!({ "method": "POST", "path": "/other" });
+        assert!(!executor.can_handle_step(&other));
+
+        let mut context = Context::new();
+        let result = executor.execute(&step, &mut context).await.unwrap();
+        assert_eq!(result.status, StepStatus::Passed);
+        assert_eq!(result.reused_from.as_deref(), Some("exec-1"));
+        assert_eq!(result.cache_hit.unwrap().key, "login-admin");
+        assert_eq!(context.get("access_token"), Some(&json!("abc")));
+
+        // Mesmo step contra outro alvo: outra identidade.
+        let mut staging = Context::new();
+        staging.set("base_url", json!("https://staging.example.com"));
+        assert!(StepCacheStore::at(path.clone(), &staging)
+            .lookup(&step)
+            .is_none());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_entry_expires_with_jwt_exp_or_ttl() {
+        let path = cache_path("jwt");
+        let store = StepCacheStore::at(path.clone(), &Context::new());
+        let step = login(3600);
+
+        // JWT expira em 2 minutos: a entrada vale até lá, não 1h.
+        let exp = Utc::now().timestamp() + 120;
+        store
+            .record(
+                std::slice::from_ref(&step),
+                &[passed_login(&jwt(exp))],
+                "exec-1",
+            )
+            .unwrap();
+        let entry = store.lookup(&step).unwrap();
+        assert_eq!(entry.expires_at.timestamp(), exp);
+
+        // JWT já expirado: nada é gravado (a entrada anterior continua).
+        let expired = jwt(Utc::now().timestamp() - 10);
+        store
+            .record(
+                std::slice::from_ref(&step),
+                &[passed_login(&expired)],
+                "exec-2",
+            )
+            .unwrap();
+        assert_eq!(store.lookup(&step).unwrap().execution_id, "exec-1");
+
+        // TTL zero: expira na hora.
+        let _ = std::fs::remove_file(&path);
+        let store = StepCacheStore::at(path.clone(), &Context::new());
+        store
+            .record(&[login(0)], &[passed_login("abc")], "exec-3")
+            .unwrap();
+        assert!(store.lookup(&login(0)).is_none());
+        let _ = std::fs::remove_file(path);
+    }
+}
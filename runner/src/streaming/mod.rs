@@ -0,0 +1,206 @@
+//! # Módulo de Streaming - Relatório Parcial Durante a Execução
+//!
+//! Quando `--output` é informado, cada resultado de step é anexado a um
+//! arquivo JSON Lines ao lado do relatório (`<output>.partial.jsonl`) assim
+//! que o step termina.
+//!
+//! ## Para todos entenderem:
+//!
+//! O relatório final só é escrito quando **todos** os steps terminam. Se o
+//! Runner for morto no meio (timeout do CI, OOM, Ctrl+C), nada era salvo.
+//! Com o streaming, é como um diário de bordo: cada step concluído vira uma
+//! linha no arquivo parcial, e o que já aconteceu nunca se perde.
+//!
+//! ## Formato (uma linha JSON por evento):
+//!
+//! ```text
+//! {"event":"start","execution_id":"...","plan_id":"...","plan_name":"...",...}
+//! {"event":"step","step_id":"login","status":"passed","duration_ms":120,...}
+//! {"event":"step","step_id":"get_user","status":"failed",...}
+//! {"event":"heartbeat","completed":2,"failed":1,"total":40,...}
+//! ```
+//!
+//! - As linhas `step` seguem o formato de `StepResult` do relatório final
+//!   (já com o `--report-detail` aplicado), na ordem de término.
+//! - Quando o relatório final é salvo com sucesso, o arquivo parcial é
+//!   removido. Se ele existir, a execução não terminou normalmente.
+
+use anyhow::{Context as _, Result};
+use serde::Serialize;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::warn;
+
+use crate::heartbeat::Heartbeat;
+use crate::protocol::{ReportDetail, StepResult};
+
+// ============================================================================
+// REGISTROS
+// ============================================================================
+
+/// Dados de identificação gravados na primeira linha do arquivo parcial.
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamHeader {
+    /// Versão do formato do relatório.
+    pub report_version: String,
+
+    /// ID da execução.
+    pub execution_id: String,
+
+    /// ID do plano.
+    pub plan_id: String,
+
+    /// Nome do plano.
+    pub plan_name: String,
+
+    /// Início da execução (RFC 3339).
+    pub start_time: String,
+}
+
+/// Uma linha do arquivo parcial.
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum StreamRecord<'a> {
+    Start(&'a StreamHeader),
+    Step(&'a StepResult),
+    Heartbeat(&'a Heartbeat),
+}
+
+// ============================================================================
+// STREAM DE RESULTADOS
+// ============================================================================
+
+/// Arquivo JSON Lines que recebe os resultados conforme os steps terminam.
+///
+/// Compartilhável entre tasks (o DAG grava de várias tasks ao mesmo tempo).
+#[derive(Debug)]
+pub struct ResultStream {
+    path: PathBuf,
+    file: Mutex<File>,
+    detail: ReportDetail,
+}
+
+impl ResultStream {
+    /// Caminho do arquivo parcial para um relatório: `<output>.partial.jsonl`.
+    pub fn partial_path(output: &Path) -> PathBuf {
+        let mut name = output.as_os_str().to_os_string();
+        name.push(".partial.jsonl");
+        PathBuf::from(name)
+    }
+
+    /// Cria (ou trunca) o arquivo parcial e grava a linha de início.
+    pub fn create(output: &Path, header: &StreamHeader, detail: ReportDetail) -> Result<Self> {
+        let path = Self::partial_path(output);
+        let file = crate::output::create_file(&path)
+            .with_context(|| format!("Falha ao criar relatório parcial {:?}", path))?;
+
+        let stream = Self {
+            path,
+            file: Mutex::new(file),
+            detail,
+        };
+        stream.write_record(&StreamRecord::Start(header))?;
+        Ok(stream)
+    }
+
+    /// Anexa o resultado de um step.
+    ///
+    /// Falhas de escrita são apenas registradas no log: o streaming nunca
+    /// interrompe a execução.
+    pub fn append(&self, result: &StepResult) {
+        let mut result = result.clone();
+        result.apply_detail(self.detail);
+
+        if let Err(e) = self.write_record(&StreamRecord::Step(&result)) {
+            warn!(path = ?self.path, error = %e, "Falha ao gravar resultado parcial");
+        }
+    }
+
+    /// Anexa um sinal de vida (ver `heartbeat`).
+    pub fn heartbeat(&self, beat: &Heartbeat) {
+        if let Err(e) = self.write_record(&StreamRecord::Heartbeat(beat)) {
+            warn!(path = ?self.path, error = %e, "Falha ao gravar heartbeat");
+        }
+    }
+
+    /// Remove o arquivo parcial (chamado após salvar o relatório final).
+    pub fn finish(&self) {
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            warn!(path = ?self.path, error = %e, "Falha ao remover relatório parcial");
+        }
+    }
+
+    /// Grava uma linha completa e força o flush para o disco.
+    fn write_record(&self, record: &StreamRecord) -> Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        file.write_all(&line)?;
+        file.flush()?;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::StepStatus;
+    use serde_json::Value;
+
+    fn header() -> StreamHeader {
+        StreamHeader {
+            report_version: "1.2.0".to_string(),
+            execution_id: "exec-1".to_string(),
+            plan_id: "plan-1".to_string(),
+            plan_name: "Plano".to_string(),
+            start_time: "2024-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_partial_path_appends_suffix() {
+        assert_eq!(
+            ResultStream::partial_path(Path::new("reports/out.json")),
+            PathBuf::from("reports/out.json.partial.jsonl")
+        );
+    }
+
+    #[test]
+    fn test_stream_writes_lines_and_finish_removes_file() {
+        let output = std::env::temp_dir().join(format!("aqa-stream-{}.json", std::process::id()));
+        let stream = ResultStream::create(&output, &header(), ReportDetail::Minimal).unwrap();
+
+        stream.append(&StepResult {
+            step_id: "login".to_string(),
+            status: StepStatus::Passed,
+            duration_ms: 12,
+            context_after: Some(Default::default()),
+            ..Default::default()
+        });
+
+        let partial = ResultStream::partial_path(&output);
+        let content = std::fs::read_to_string(&partial).unwrap();
+        let lines: Vec<Value> = content
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["event"], "start");
+        assert_eq!(lines[0]["execution_id"], "exec-1");
+        assert_eq!(lines[1]["event"], "step");
+        assert_eq!(lines[1]["step_id"], "login");
+        // `--report-detail` é aplicado antes de gravar.
+        assert!(lines[1].get("context_after").is_none());
+
+        stream.finish();
+        assert!(!partial.exists());
+    }
+}
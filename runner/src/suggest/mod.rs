@@ -0,0 +1,247 @@
+//! # Módulo de Sugestões - `runner suggest`
+//!
+//! Lê as respostas gravadas em um relatório e propõe assertions e
+//! extrações para cada step HTTP que passou, como um patch de UTDL.
+//!
+//! ## Para todos entenderem:
+//!
+//! Um plano exploratório (gerado às pressas ou escrito à mão) costuma ter
+//! só `status_code eq 200`. As respostas reais já dizem muito mais: quais
+//! campos sempre vêm, de que tipo, e quais IDs os próximos steps vão
+//! precisar. O `suggest` transforma isso em um ponto de partida:
+//!
+//! ```bash
+//! runner execute --file plan.json --report-detail full --output report.json
+//! runner suggest --report report.json --output suggestions.json
+//! ```
+//!
+//! | Observado na resposta                 | Sugestão                                  |
+//! |---------------------------------------|-------------------------------------------|
+//! | Status HTTP                           | `status_code eq <status>`                 |
+//! | Body JSON                             | `json_schema valid` com tipos e campos    |
+//! | Campos `id`, `*_id`, `token` (topo ou `data`) | `json_body exists` + extração `<step>_<campo>` |
+//!
+//! O patch é organizado por `step_id`; revise antes de copiar para o
+//! plano (um campo que veio por acaso vira uma assertion frágil).
+//!
+//! Os bodies só estão no relatório com `--report-detail full`; sem eles,
+//! apenas o status é sugerido. Steps que falharam ficam de fora: a
+//! resposta de um erro (ex: um 500) não é o comportamento a fixar.
+
+use anyhow::{Context as _, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::path::Path;
+
+use crate::protocol::{StepResult, StepStatus};
+
+/// Profundidade máxima do schema inferido (evita schemas gigantes).
+const MAX_SCHEMA_DEPTH: usize = 4;
+
+/// Campos do relatório usados pelo `suggest`.
+#[derive(Debug, Deserialize)]
+struct SavedReport {
+    plan_id: String,
+    steps: Vec<StepResult>,
+}
+
+/// Patch de UTDL com as sugestões de cada step.
+#[derive(Debug, Serialize)]
+pub struct SuggestionPatch {
+    pub plan_id: String,
+    pub steps: Vec<StepSuggestion>,
+}
+
+/// Assertions e extrações sugeridas para um step.
+#[derive(Debug, Serialize)]
+pub struct StepSuggestion {
+    pub step_id: String,
+    pub assertions: Vec<Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extract: Vec<Value>,
+    /// `false` se o relatório não tinha o body (só o status foi sugerido).
+    pub body_recorded: bool,
+}
+
+// ============================================================================
+// FUNÇÕES PRINCIPAIS
+// ============================================================================
+
+/// Lê o relatório e monta o patch de sugestões.
+pub fn from_report(path: &Path) -> Result<SuggestionPatch> {
+    let content = crate::report_file::read_to_string(path)
+        .with_context(|| format!("Falha ao ler relatório {:?}", path))?;
+    let report: SavedReport =
+        serde_json::from_str(&content).with_context(|| format!("Relatório inválido {:?}", path))?;
+    Ok(suggest(report.plan_id, &report.steps))
+}
+
+/// Sugestões para os steps HTTP que passaram e receberam resposta.
+pub fn suggest(plan_id: String, results: &[StepResult]) -> SuggestionPatch {
+    let steps = results
+        .iter()
+        .filter(|result| result.status == StepStatus::Passed)
+        .filter_map(|result| {
+            let http = result
+                .http_details
+                .as_ref()
+                .filter(|h| h.status_code != 0)?;
+            let mut assertions = vec![json!({
+                "type": "status_code", "operator": "eq", "value": http.status_code
+            })];
+            let mut extract = Vec::new();
+
+            let body: Option<Value> = http
+                .response_body
+                .as_deref()
+                .and_then(|b| serde_json::from_str(b).ok());
+            if let Some(body) = &body {
+                assertions.push(json!({
+                    "type": "json_schema", "operator": "valid", "value": infer_schema(body, 0)
+                }));
+                for path in key_fields(body) {
+                    assertions.push(json!({
+                        "type": "json_body", "path": path, "operator": "exists", "value": null
+                    }));
+                    let field = path.rsplit('.').next().unwrap_or(&path);
+                    extract.push(json!({
+                        "source": "body",
+                        "path": path,
+                        "target": format!("{}_{}", result.step_id, field)
+                    }));
+                }
+            }
+
+            Some(StepSuggestion {
+                step_id: result.step_id.clone(),
+                assertions,
+                extract,
+                body_recorded: http.response_body.is_some(),
+            })
+        })
+        .collect();
+
+    SuggestionPatch { plan_id, steps }
+}
+
+// ============================================================================
+// INFERÊNCIA
+// ============================================================================
+
+/// JSON Schema com os tipos observados (objetos exigem as chaves vistas).
+fn infer_schema(value: &Value, depth: usize) -> Value {
+    match value {
+        Value::Null => json!({ "type": "null" }),
+        Value::Bool(_) => json!({ "type": "boolean" }),
+        Value::Number(n) if n.is_i64() || n.is_u64() => json!({ "type": "integer" }),
+        Value::Number(_) => json!({ "type": "number" }),
+        Value::String(_) => json!({ "type": "string" }),
+        Value::Array(items) => match items.first() {
+            Some(first) if depth < MAX_SCHEMA_DEPTH => {
+                json!({ "type": "array", "items": infer_schema(first, depth + 1) })
+            }
+            _ => json!({ "type": "array" }),
+        },
+        Value::Object(fields) if depth < MAX_SCHEMA_DEPTH => {
+            let properties: Map<String, Value> = fields
+                .iter()
+                .map(|(key, value)| (key.clone(), infer_schema(value, depth + 1)))
+                .collect();
+            let mut required: Vec<&String> = fields.keys().collect();
+            required.sort();
+            json!({ "type": "object", "required": required, "properties": properties })
+        }
+        Value::Object(_) => json!({ "type": "object" }),
+    }
+}
+
+/// Caminhos dos campos que os próximos steps costumam precisar.
+fn key_fields(body: &Value) -> Vec<String> {
+    let mut paths = Vec::new();
+    let mut collect = |object: &Map<String, Value>, prefix: &str| {
+        let mut keys: Vec<&String> = object
+            .iter()
+            .filter(|(key, value)| is_key_field(key) && !value.is_object() && !value.is_array())
+            .map(|(key, _)| key)
+            .collect();
+        keys.sort();
+        paths.extend(keys.into_iter().map(|key| format!("{}{}", prefix, key)));
+    };
+
+    if let Some(object) = body.as_object() {
+        collect(object, "");
+        if let Some(data) = object.get("data").and_then(Value::as_object) {
+            collect(data, "data.");
+        }
+    }
+    paths
+}
+
+fn is_key_field(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    lower == "id"
+        || lower.ends_with("_id")
+        || (name.ends_with("Id") && name.len() > 2)
+        || lower == "token"
+        || lower.ends_with("_token")
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(id: &str, status: u16, body: Option<&str>) -> StepResult {
+        serde_json::from_value(json!({
+            "step_id": id, "status": "passed", "duration_ms": 10,
+            "http_details": {
+                "method": "POST", "url": "http://api/users", "status_code": status,
+                "latency_ms": 10, "response_body": body
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_suggests_schema_key_fields_and_extractions() {
+        let body =
+            r#"{"data":{"id":42,"email":"ana@mail.com","orgId":"o-1"},"tags":["a"],"score":1.5}"#;
+        let patch = suggest(
+            "plan".to_string(),
+            &[result("create_user", 201, Some(body))],
+        );
+        let step = &patch.steps[0];
+
+        assert_eq!(step.assertions[0]["value"], 201);
+        let schema = &step.assertions[1]["value"];
+        assert_eq!(schema["properties"]["score"]["type"], "number");
+        assert_eq!(schema["properties"]["tags"]["items"]["type"], "string");
+        assert_eq!(
+            schema["properties"]["data"]["properties"]["id"]["type"],
+            "integer"
+        );
+        assert_eq!(step.assertions[2]["path"], "data.id");
+        assert_eq!(step.assertions[3]["path"], "data.orgId");
+        assert_eq!(step.extract[0]["target"], "create_user_id");
+        assert_eq!(step.extract[1]["target"], "create_user_orgId");
+        assert!(step.body_recorded);
+    }
+
+    #[test]
+    fn test_without_body_only_status_and_no_response_skipped() {
+        let failed = StepResult {
+            status: StepStatus::Failed,
+            ..result("broken", 500, Some(r#"{"error_id":"e-1"}"#))
+        };
+        let patch = suggest(
+            "plan".to_string(),
+            &[result("list", 200, None), result("down", 0, None), failed],
+        );
+        assert_eq!(patch.steps.len(), 1);
+        assert_eq!(patch.steps[0].assertions.len(), 1);
+        assert!(!patch.steps[0].body_recorded);
+    }
+}
@@ -0,0 +1,241 @@
+//! # Módulo de Suíte - Vários Planos e Steps Compartilhados
+//!
+//! Implementa o modo suíte (`--file` repetido): os planos rodam um após o
+//! outro, e steps marcados com `shared: true` executam só uma vez.
+//!
+//! ## Para todos entenderem:
+//!
+//! Dez planos começam com o mesmo `login`. Rodando a suíte, o IdP recebe
+//! dez logins seguidos (e às vezes responde 429). Com `shared: true`, o
+//! primeiro plano faz o login; os seguintes reaproveitam as variáveis que
+//! ele produziu (`${access_token}`) sem enviar a requisição:
+//!
+//! ```json
+//! { "id": "login", "action": "http_request", "shared": true,
+//!   "params": { "method": "POST", "path": "/oauth/token", ... },
+//!   "extract": [{ "source": "body", "path": "access_token", "target": "access_token" }] }
+//! ```
+//!
+//! ```bash
+//! runner execute --file auth_users.json --file auth_orders.json --output report.json
+//! # report.auth_users.json, report.auth_orders.json
+//! ```
+//!
+//! ## Regras:
+//!
+//! | Situação                                      | Comportamento                   |
+//! |-----------------------------------------------|---------------------------------|
+//! | Primeiro plano com o step                     | Executa normalmente             |
+//! | Plano seguinte, mesmo `id`, `action` e `params` | Pula a execução e injeta as variáveis |
+//! | Mesmo `id` com params diferentes              | Executa (é outro step)          |
+//! | Step compartilhado falhou                     | Os planos seguintes tentam de novo |
+//!
+//! O resultado reaproveitado aparece como `passed` com `reused_from`
+//! apontando para a execução que de fato rodou o step. Fora do modo
+//! suíte, `shared` não tem efeito.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing::info;
+
+use crate::context::Context;
+use crate::executors::StepExecutor;
+use crate::protocol::{ContextDelta, Step, StepResult, StepStatus};
+
+// ============================================================================
+// STEPS COMPARTILHADOS
+// ============================================================================
+
+/// Variáveis produzidas por um step compartilhado.
+#[derive(Debug, Clone)]
+struct SharedEntry {
+    execution_id: String,
+    variables: BTreeMap<String, Value>,
+}
+
+/// Steps compartilhados já executados na suíte.
+#[derive(Debug, Clone, Default)]
+pub struct SharedSteps {
+    entries: Arc<Mutex<HashMap<String, SharedEntry>>>,
+}
+
+impl SharedSteps {
+    /// Guarda as variáveis dos steps `shared` que passaram.
+    pub fn record(&self, steps: &[Step], results: &[StepResult], execution_id: &str) {
+        let mut entries = self.entries.lock().expect("shared steps poisoned");
+        for step in steps.iter().filter(|s| s.shared) {
+            let Some(result) = results.iter().find(|r| r.step_id == step.id) else {
+                continue;
+            };
+            if result.status != StepStatus::Passed || result.reused_from.is_some() {
+                continue;
+            }
+            entries.insert(
+                key(step),
+                SharedEntry {
+                    execution_id: execution_id.to_string(),
+                    variables: produced_variables(result),
+                },
+            );
+        }
+    }
+
+    fn get(&self, step: &Step) -> Option<SharedEntry> {
+        let entries = self.entries.lock().expect("shared steps poisoned");
+        entries.get(&key(step)).cloned()
+    }
+}
+
+/// Identidade do step na suíte: mesmo id, mesma ação e mesmos params.
+fn key(step: &Step) -> String {
+    format!("{}\u{0}{}\u{0}{}", step.id, step.action, step.params)
+}
+
+/// Variáveis criadas ou alteradas pelo step (do delta ou dos snapshots).
+pub fn produced_variables(result: &StepResult) -> BTreeMap<String, Value> {
+    let delta = match (&result.context_before, &result.context_after) {
+        (Some(before), Some(after)) => ContextDelta::between(before, after),
+        _ => result.context_delta.clone().unwrap_or_default(),
+    };
+    let mut variables = delta.added;
+    variables.extend(delta.changed);
+    variables
+}
+
+// ============================================================================
+// EXECUTOR
+// ============================================================================
+
+/// Atende os steps `shared` já executados em um plano anterior da suíte.
+pub struct SharedStepExecutor {
+    shared: SharedSteps,
+}
+
+impl SharedStepExecutor {
+    pub fn new(shared: SharedSteps) -> Self {
+        Self { shared }
+    }
+}
+
+#[async_trait]
+impl StepExecutor for SharedStepExecutor {
+    fn can_handle(&self, _action: &str) -> bool {
+        false
+    }
+
+    fn can_handle_step(&self, step: &Step) -> bool {
+        step.shared && self.shared.get(step).is_some()
+    }
+
+    async fn execute(&self, step: &Step, context: &mut Context) -> Result<StepResult> {
+        let entry = self
+            .shared
+            .get(step)
+            .ok_or_else(|| anyhow::anyhow!("Step compartilhado '{}' não executado", step.id))?;
+        info!(
+            step_id = %step.id,
+            reused_from = %entry.execution_id,
+            variables = entry.variables.len(),
+            "Shared step reused from earlier plan in suite"
+        );
+        for (name, value) in entry.variables {
+            context.set(name, value);
+        }
+        Ok(StepResult {
+            step_id: step.id.clone(),
+            status: StepStatus::Passed,
+            reused_from: Some(entry.execution_id),
+            ..Default::default()
+        })
+    }
+}
+
+// ============================================================================
+// RELATÓRIOS
+// ============================================================================
+
+/// Caminho do relatório de um plano: `report.json` + `plans/users.json` → `report.users.json`.
+pub fn plan_output(path: &Path, plan_file: &Path) -> PathBuf {
+    let name = plan_file
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let stem = name.split('.').next().unwrap_or("plan");
+    crate::report_file::with_suffix(path, stem)
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn login(params: Value) -> Step {
+        serde_json::from_value(json!({
+            "id": "login", "action": "http_request", "shared": true, "params": params
+        }))
+        .unwrap()
+    }
+
+    fn passed_login(status: &str) -> StepResult {
+        serde_json::from_value(json!({
+            "step_id": "login", "status": status, "duration_ms": 40,
+            "context_delta": { "added": { "access_token": "abc" } }
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_shared_step_reused_with_its_variables() {
+        let shared = SharedSteps::default();
+        let step = login(json!({ "method": "POST", "path": "/token" }));
+        let executor = SharedStepExecutor::new(shared.clone());
+        assert!(!executor.can_handle_step(&step));
+
+        shared.record(
+            std::slice::from_ref(&step),
+            &[passed_login("passed")],
+            "exec-1",
+        );
+        assert!(executor.can_handle_step(&step));
+        assert!(!executor.can_handle_step(&login(json!({ "path": "/other" }))));
+
+        let mut context = Context::new();
+        let result = executor.execute(&step, &mut context).await.unwrap();
+        assert_eq!(result.status, StepStatus::Passed);
+        assert_eq!(result.reused_from.as_deref(), Some("exec-1"));
+        assert_eq!(context.get("access_token"), Some(&json!("abc")));
+    }
+
+    #[test]
+    fn test_failed_or_unshared_steps_not_recorded() {
+        let shared = SharedSteps::default();
+        let step = login(json!({}));
+        shared.record(
+            std::slice::from_ref(&step),
+            &[passed_login("failed")],
+            "exec-1",
+        );
+        assert!(shared.get(&step).is_none());
+
+        let mut unshared = step.clone();
+        unshared.shared = false;
+        shared.record(&[unshared], &[passed_login("passed")], "exec-1");
+        assert!(shared.get(&step).is_none());
+
+        assert_eq!(
+            plan_output(
+                Path::new("out/report.json"),
+                Path::new("plans/users.utdl.json")
+            ),
+            PathBuf::from("out/report.users.json")
+        );
+    }
+}
@@ -71,18 +71,26 @@
 //! shutdown_telemetry(); // Flush dos dados
 //! ```
 
+use once_cell::sync::OnceCell;
 use opentelemetry::trace::TracerProvider as _;
 use opentelemetry::{global, KeyValue};
 use opentelemetry_otlp::WithExportConfig;
 use opentelemetry_sdk::runtime::Tokio;
 use opentelemetry_sdk::trace::{RandomIdGenerator, Sampler, Tracer, TracerProvider};
 use opentelemetry_sdk::{trace as sdktrace, Resource};
-use tracing::Level;
+use tracing::{Level, Span};
 use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::EnvFilter;
 
+use crate::protocol::{Meta, Step};
+
+/// Provider OTLP ativo, guardado para permitir flush fora do fluxo normal
+/// (ex: no panic hook), já que o provider global não expõe `force_flush`.
+static TRACER_PROVIDER: OnceCell<TracerProvider> = OnceCell::new();
+
 // ============================================================================
 // CONFIGURAÇÃO
 // ============================================================================
@@ -114,6 +122,9 @@ pub struct TelemetryConfig {
 
     /// Nível de log mínimo (INFO, DEBUG, WARN, ERROR).
     pub log_level: Level,
+
+    /// Logs do console no stderr, deixando o stdout livre (`--progress-stream -`).
+    pub console_to_stderr: bool,
 }
 
 /// Implementação de Default para TelemetryConfig.
@@ -127,6 +138,7 @@ impl Default for TelemetryConfig {
             sampling_ratio: 1.0, // 100% por padrão
             enable_console_logging: true,
             log_level: Level::INFO,
+            console_to_stderr: false,
         }
     }
 }
@@ -205,6 +217,11 @@ pub fn init_telemetry(config: TelemetryConfig) -> anyhow::Result<Option<Tracer>>
     // Primeiro tenta ler de RUST_LOG, senão usa o padrão.
     let env_filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new(config.log_level.to_string()));
+    let console_writer = if config.console_to_stderr {
+        BoxMakeWriter::new(std::io::stderr)
+    } else {
+        BoxMakeWriter::new(std::io::stdout)
+    };
 
     // Se temos endpoint OTLP, configuramos o exporter.
     if let Some(endpoint) = &config.otlp_endpoint {
@@ -221,7 +238,11 @@ pub fn init_telemetry(config: TelemetryConfig) -> anyhow::Result<Option<Tracer>>
         // Adiciona console logging se habilitado.
         if config.enable_console_logging {
             subscriber
-                .with(tracing_subscriber::fmt::layer().compact())
+                .with(
+                    tracing_subscriber::fmt::layer()
+                        .compact()
+                        .with_writer(console_writer),
+                )
                 .init();
         } else {
             subscriber.init();
@@ -241,7 +262,11 @@ pub fn init_telemetry(config: TelemetryConfig) -> anyhow::Result<Option<Tracer>>
 
         if config.enable_console_logging {
             subscriber
-                .with(tracing_subscriber::fmt::layer().compact())
+                .with(
+                    tracing_subscriber::fmt::layer()
+                        .compact()
+                        .with_writer(console_writer),
+                )
                 .init();
         } else {
             subscriber.init();
@@ -308,6 +333,9 @@ fn init_otlp_tracer(
     // Obtém o tracer do provider.
     let tracer = tracer_provider.tracer(service_name.to_string());
 
+    // Guarda uma referência para flush em caso de panic.
+    let _ = TRACER_PROVIDER.set(tracer_provider.clone());
+
     // Registra o provider globalmente.
     // Isso permite usar `global::tracer()` em qualquer lugar.
     global::set_tracer_provider(tracer_provider);
@@ -342,6 +370,102 @@ pub fn shutdown_telemetry() {
     tracing::info!("Telemetria OTEL encerrada");
 }
 
+/// Força o envio dos spans acumulados, sem encerrar a telemetria.
+///
+/// Seguro de chamar várias vezes e sem OTLP configurado (não faz nada).
+pub fn flush_telemetry() {
+    if let Some(provider) = TRACER_PROVIDER.get() {
+        for result in provider.force_flush() {
+            if let Err(e) = result {
+                eprintln!("Warning: Failed to flush telemetry: {}", e);
+            }
+        }
+    }
+}
+
+/// Instala um panic hook que registra o panic e faz flush dos traces.
+///
+/// ## Para todos entenderem:
+///
+/// Um panic encerra o programa sem passar pelo `shutdown_telemetry()`,
+/// e justamente os spans que explicariam o problema ficariam na memória.
+/// O hook roda **antes** do encerramento: loga o erro, envia os spans
+/// pendentes e então chama o hook padrão (que imprime a mensagem).
+///
+/// Usa flush em vez de shutdown porque panics em tasks do Tokio são
+/// capturados e a execução pode continuar.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        tracing::error!(panic = %info, "Runner panicked");
+        flush_telemetry();
+        default_hook(info);
+    }));
+}
+
+// ============================================================================
+// SPANS DE PLANO E STEP
+// ============================================================================
+
+/// Cria o span raiz de uma execução, com os metadados do plano.
+///
+/// ## Para todos entenderem:
+///
+/// Todos os spans dos steps ficam "pendurados" neste span, então filtrar
+/// no backend de observabilidade por `plan.tags` (ex: contém "checkout")
+/// ou `plan.name` traz o trace inteiro da execução.
+///
+/// | Atributo       | Origem                            |
+/// |----------------|-----------------------------------|
+/// | `plan.id`      | `meta.id`                         |
+/// | `plan.name`    | `meta.name`                       |
+/// | `plan.tags`    | `meta.tags`, separadas por vírgula |
+/// | `plan.generator.*` | `meta.generator` (versão do Brain, prompt, modelo) |
+/// | `execution.id` | `--execution-id` (ou UUID gerado) |
+///
+/// Os resource attributes do OTEL são definidos antes do plano ser lido,
+/// por isso os metadados do plano vão nos spans.
+pub fn plan_span(meta: &Meta, execution_id: &str) -> Span {
+    let generator = meta.generator.clone().unwrap_or_default();
+    tracing::info_span!(
+        "plan",
+        plan.id = %meta.id,
+        plan.name = %meta.name,
+        plan.tags = %meta.tags.join(","),
+        plan.generator.brain_version = generator.brain_version.as_deref().unwrap_or(""),
+        plan.generator.prompt_hash = generator.prompt_hash.as_deref().unwrap_or(""),
+        plan.generator.model = generator.model.as_deref().unwrap_or(""),
+        execution.id = %execution_id,
+    )
+}
+
+/// Cria o span de um step, com ID, action e descrição.
+///
+/// Os spans dos executores (ex: `http_request`) ficam como filhos deste.
+pub fn step_span(step: &Step) -> Span {
+    tracing::info_span!(
+        "step",
+        step.id = %step.id,
+        step.action = %step.action,
+        step.description = step.description.as_deref().unwrap_or(""),
+    )
+}
+
+/// Cria o span de uma tentativa de um step com `recovery_policy.strategy: retry`.
+///
+/// Cada tentativa vira um filho do span do step, então o Jaeger mostra a
+/// linha do tempo dos retries (tentativa, espera antes dela e erro).
+/// `retry.error` é preenchido com `Span::record` quando a tentativa falha.
+pub fn attempt_span(step: &Step, attempt: u32, backoff_ms: u64) -> Span {
+    tracing::info_span!(
+        "attempt",
+        step.id = %step.id,
+        retry.attempt = attempt,
+        retry.backoff_ms = backoff_ms,
+        retry.error = tracing::field::Empty,
+    )
+}
+
 /// Macros e helpers para instrumentação de spans.
 #[allow(dead_code)]
 pub mod instrumentation {
@@ -420,6 +544,96 @@ pub mod instrumentation {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::protocol::PlanGenerator;
+
+    /// Layer de teste que guarda os campos `nome=valor` de cada span criado.
+    struct FieldRecorder(std::sync::Arc<std::sync::Mutex<Vec<String>>>);
+
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for FieldRecorder {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            _id: &tracing::span::Id,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            struct Visitor<'a>(&'a mut Vec<String>);
+            impl tracing::field::Visit for Visitor<'_> {
+                fn record_debug(
+                    &mut self,
+                    field: &tracing::field::Field,
+                    value: &dyn std::fmt::Debug,
+                ) {
+                    self.0.push(format!("{}={:?}", field.name(), value));
+                }
+                fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+                    self.0.push(format!("{}={}", field.name(), value));
+                }
+            }
+            attrs.record(&mut Visitor(&mut self.0.lock().unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_plan_and_step_spans_carry_metadata() {
+        let fields = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let subscriber = tracing_subscriber::registry().with(FieldRecorder(fields.clone()));
+
+        let meta = Meta {
+            id: "plan-1".to_string(),
+            name: "Checkout".to_string(),
+            description: None,
+            tags: vec!["checkout".to_string(), "smoke".to_string()],
+            created_at: "2024-01-01".to_string(),
+            generator: Some(PlanGenerator {
+                brain_version: Some("0.9.2".to_string()),
+                model: Some("gpt-4o".to_string()),
+                ..Default::default()
+            }),
+        };
+        let step = Step {
+            id: "pay".to_string(),
+            action: "http_request".to_string(),
+            description: Some("Paga o pedido".to_string()),
+            ..Default::default()
+        };
+
+        tracing::subscriber::with_default(subscriber, || {
+            let _plan = plan_span(&meta, "exec-1");
+            let _step = step_span(&step);
+        });
+
+        let fields = fields.lock().unwrap();
+        assert!(fields.contains(&"plan.tags=checkout,smoke".to_string()));
+        assert!(fields.contains(&"plan.name=Checkout".to_string()));
+        assert!(fields.contains(&"plan.generator.brain_version=0.9.2".to_string()));
+        assert!(fields.contains(&"plan.generator.model=gpt-4o".to_string()));
+        assert!(fields.contains(&"step.description=Paga o pedido".to_string()));
+    }
+
+    #[test]
+    fn test_attempt_span_records_retry_fields() {
+        let fields = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let subscriber = tracing_subscriber::registry().with(FieldRecorder(fields.clone()));
+        let step = Step {
+            id: "flaky".to_string(),
+            ..Default::default()
+        };
+
+        tracing::subscriber::with_default(subscriber, || {
+            let _attempt = attempt_span(&step, 2, 500);
+        });
+
+        let fields = fields.lock().unwrap();
+        assert!(fields.contains(&"retry.attempt=2".to_string()));
+        assert!(fields.contains(&"retry.backoff_ms=500".to_string()));
+    }
+
+    #[test]
+    fn test_flush_without_otlp_is_noop() {
+        // Sem provider OTLP registrado, o flush não deve falhar nem travar.
+        flush_telemetry();
+        flush_telemetry();
+    }
 
     #[test]
     fn test_config_default() {
@@ -0,0 +1,149 @@
+//! # Módulo de Templates - Fragmentos de Requisição Reutilizáveis
+//!
+//! Aplica `config.request_templates` aos steps que declaram `template`,
+//! logo após o carregamento do plano (antes da validação e da interpolação).
+//!
+//! ## Para todos entenderem:
+//!
+//! Quase todo step de uma API autenticada repete o mesmo header de
+//! autorização e o mesmo `Content-Type`. Um template guarda esse "molde"
+//! uma vez; cada step diz qual molde usar e preenche só o que muda.
+//!
+//! ## Exemplo:
+//!
+//! ```json
+//! "config": {
+//!   "request_templates": {
+//!     "authed_json": {
+//!       "headers": {
+//!         "Authorization": "Bearer ${token}",
+//!         "Content-Type": "application/json"
+//!       }
+//!     }
+//!   }
+//! },
+//! "steps": [{
+//!   "id": "criar_pedido",
+//!   "action": "http_request",
+//!   "template": "authed_json",
+//!   "params": { "method": "POST", "path": "/orders", "headers": { "X-Trace": "1" } }
+//! }]
+//! ```
+//!
+//! O step acima envia os dois headers do template **e** o `X-Trace`.
+//!
+//! ## Regras de mesclagem:
+//!
+//! - Objetos são mesclados chave a chave (recursivamente).
+//! - Qualquer outro valor do step substitui o do template.
+//! - Placeholders `${...}` são mantidos e interpolados na execução.
+//!
+//! Templates inexistentes não são aplicados aqui; a validação os reporta.
+
+use serde_json::Value;
+
+use crate::protocol::Plan;
+
+// ============================================================================
+// APLICAÇÃO DOS TEMPLATES
+// ============================================================================
+
+/// Mescla o template referenciado por cada step nos seus `params`.
+pub fn apply_request_templates(plan: &mut Plan) {
+    let templates = &plan.config.request_templates;
+    if templates.is_empty() {
+        return;
+    }
+
+    for step in &mut plan.steps {
+        let Some(template) = step.template.as_ref().and_then(|name| templates.get(name)) else {
+            continue;
+        };
+        step.params = merge(template, &step.params);
+    }
+}
+
+/// Mescla `overlay` sobre `base`: objetos chave a chave, demais valores substituídos.
+fn merge(base: &Value, overlay: &Value) -> Value {
+    match (base, overlay) {
+        (Value::Object(base_map), Value::Object(overlay_map)) => {
+            let mut merged = base_map.clone();
+            for (key, value) in overlay_map {
+                let entry = match merged.get(key) {
+                    Some(existing) => merge(existing, value),
+                    None => value.clone(),
+                };
+                merged.insert(key.clone(), entry);
+            }
+            Value::Object(merged)
+        }
+        // `params` ausente (null) herda o template inteiro.
+        (_, Value::Null) => base.clone(),
+        _ => overlay.clone(),
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_merge_keeps_step_priority() {
+        let template = json!({
+            "method": "GET",
+            "headers": { "Authorization": "Bearer ${token}", "Accept": "application/json" }
+        });
+        let step = json!({
+            "method": "POST",
+            "path": "/orders",
+            "headers": { "Accept": "text/plain" }
+        });
+
+        assert_eq!(
+            merge(&template, &step),
+            json!({
+                "method": "POST",
+                "path": "/orders",
+                "headers": { "Authorization": "Bearer ${token}", "Accept": "text/plain" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_apply_only_to_steps_with_known_template() {
+        let mut plan: Plan = serde_json::from_value(json!({
+            "spec_version": "0.1",
+            "meta": { "id": "p", "name": "p", "created_at": "2024-01-01" },
+            "config": {
+                "base_url": "http://localhost",
+                "timeout_ms": 1000,
+                "request_templates": {
+                    "authed": { "headers": { "Authorization": "Bearer ${token}" } }
+                }
+            },
+            "steps": [
+                { "id": "a", "action": "http_request", "template": "authed",
+                  "params": { "method": "GET", "path": "/a" } },
+                { "id": "b", "action": "http_request", "template": "missing",
+                  "params": { "method": "GET", "path": "/b" } },
+                { "id": "c", "action": "http_request",
+                  "params": { "method": "GET", "path": "/c" } }
+            ]
+        }))
+        .unwrap();
+
+        apply_request_templates(&mut plan);
+
+        assert_eq!(
+            plan.steps[0].params["headers"]["Authorization"],
+            json!("Bearer ${token}")
+        );
+        assert!(plan.steps[1].params.get("headers").is_none());
+        assert!(plan.steps[2].params.get("headers").is_none());
+    }
+}
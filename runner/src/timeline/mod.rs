@@ -0,0 +1,392 @@
+//! # Módulo de Linha do Tempo - `runner timeline`
+//!
+//! Registra quando e onde cada step rodou (`StepResult.timeline`) e
+//! desenha a execução como um gráfico de Gantt, em HTML ou Mermaid.
+//!
+//! ## Para todos entenderem:
+//!
+//! Um plano com `max_parallel: 8` que demora o mesmo que rodando em
+//! sequência está sendo serializado em algum lugar. O Gantt mostra onde:
+//!
+//! ```bash
+//! runner execute --file plan.json --output report.json
+//! runner timeline report.json --format html --output timeline.html
+//! runner timeline report.json --format mermaid-gantt
+//! ```
+//!
+//! | No gráfico                              | O que significa                        |
+//! |-----------------------------------------|----------------------------------------|
+//! | Uma linha por worker                    | Slot de concorrência ocupado           |
+//! | Trecho "lock" antes do step             | Espera pelo contexto compartilhado     |
+//! | Barras em escada em workers diferentes  | Serialização pelo lock                 |
+//! | Lacuna até o início do próximo step     | Espera por dependência do DAG          |
+//!
+//! Relatórios antigos (sem `timeline` nos steps) não têm o que desenhar.
+
+use anyhow::{bail, Context as _, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Deserialize;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use crate::protocol::{StepResult, StepStatus, StepTiming};
+
+// ============================================================================
+// REGISTRO
+// ============================================================================
+
+/// Slots de worker numerados, ocupados enquanto um step roda.
+///
+/// O limite de concorrência só conta vagas; os slots dão um número estável
+/// a cada vaga (sempre o menor livre) para agrupar as barras do Gantt.
+#[derive(Debug, Clone, Default)]
+pub struct WorkerSlots {
+    busy: Arc<Mutex<Vec<bool>>>,
+}
+
+impl WorkerSlots {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ocupa o menor slot livre até o retorno ser descartado.
+    pub fn acquire(&self) -> WorkerSlot {
+        let mut busy = self.busy.lock().expect("slots de worker");
+        let index = match busy.iter().position(|b| !b) {
+            Some(index) => index,
+            None => {
+                busy.push(false);
+                busy.len() - 1
+            }
+        };
+        busy[index] = true;
+        WorkerSlot {
+            index,
+            busy: Arc::clone(&self.busy),
+        }
+    }
+}
+
+/// Um slot ocupado; liberado no `drop`.
+#[derive(Debug)]
+pub struct WorkerSlot {
+    index: usize,
+    busy: Arc<Mutex<Vec<bool>>>,
+}
+
+impl WorkerSlot {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl Drop for WorkerSlot {
+    fn drop(&mut self) {
+        if let Ok(mut busy) = self.busy.lock() {
+            busy[self.index] = false;
+        }
+    }
+}
+
+/// Fecha a posição de um step que começou em `started_at` (termina agora).
+pub fn stamp(started_at: DateTime<Utc>, worker: usize, lock_wait_ms: u64) -> StepTiming {
+    StepTiming {
+        started_at: started_at.to_rfc3339_opts(SecondsFormat::Millis, true),
+        ended_at: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+        worker,
+        lock_wait_ms,
+    }
+}
+
+// ============================================================================
+// RENDERIZAÇÃO
+// ============================================================================
+
+/// Formato de saída do `runner timeline`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum TimelineFormat {
+    /// Página HTML autocontida.
+    Html,
+    /// Bloco `gantt` do Mermaid (Markdown, GitHub, GitLab).
+    MermaidGantt,
+}
+
+/// Campos do relatório usados pelo `timeline`.
+#[derive(Debug, Deserialize)]
+struct SavedReport {
+    plan_name: String,
+    steps: Vec<StepResult>,
+}
+
+/// Um step posicionado, em milissegundos desde o início do primeiro.
+#[derive(Debug, Clone, PartialEq)]
+struct Bar {
+    step_id: String,
+    status: StepStatus,
+    worker: usize,
+    start_ms: i64,
+    lock_ms: i64,
+    end_ms: i64,
+}
+
+/// Lê o relatório e renderiza a linha do tempo.
+pub fn from_report(path: &Path, format: TimelineFormat) -> Result<String> {
+    let content = crate::report_file::read_to_string(path)
+        .with_context(|| format!("Falha ao ler relatório {:?}", path))?;
+    let report: SavedReport =
+        serde_json::from_str(&content).with_context(|| format!("Relatório inválido {:?}", path))?;
+    let bars = bars(&report.steps)?;
+    if bars.is_empty() {
+        bail!(
+            "Relatório {:?} não tem linha do tempo (gerado por uma versão sem `timeline`)",
+            path
+        );
+    }
+    Ok(match format {
+        TimelineFormat::Html => render_html(&report.plan_name, &bars),
+        TimelineFormat::MermaidGantt => render_mermaid(&report.plan_name, &bars),
+    })
+}
+
+/// Steps com `timeline`, relativos ao primeiro início, ordenados por início.
+fn bars(steps: &[StepResult]) -> Result<Vec<Bar>> {
+    let mut timed = Vec::new();
+    for step in steps {
+        let Some(timing) = &step.timeline else {
+            continue;
+        };
+        let start = DateTime::parse_from_rfc3339(&timing.started_at)
+            .with_context(|| format!("started_at inválido em '{}'", step.step_id))?;
+        let end = DateTime::parse_from_rfc3339(&timing.ended_at)
+            .with_context(|| format!("ended_at inválido em '{}'", step.step_id))?;
+        timed.push((
+            step,
+            timing,
+            start.timestamp_millis(),
+            end.timestamp_millis(),
+        ));
+    }
+
+    let Some(origin) = timed.iter().map(|(_, _, start, _)| *start).min() else {
+        return Ok(Vec::new());
+    };
+    let mut bars: Vec<Bar> = timed
+        .into_iter()
+        .map(|(step, timing, start, end)| {
+            let start_ms = start - origin;
+            let end_ms = (end - origin).max(start_ms);
+            Bar {
+                step_id: step.step_id.clone(),
+                status: step.status.clone(),
+                worker: timing.worker,
+                start_ms,
+                lock_ms: (timing.lock_wait_ms as i64).min(end_ms - start_ms),
+                end_ms,
+            }
+        })
+        .collect();
+    bars.sort_by_key(|b| (b.worker, b.start_ms));
+    Ok(bars)
+}
+
+/// Nome de task aceito pelo Mermaid (`:` e `#` quebram a sintaxe).
+fn mermaid_label(text: &str) -> String {
+    text.replace([':', '#', ';'], "_")
+}
+
+fn render_mermaid(plan_name: &str, bars: &[Bar]) -> String {
+    let mut out = String::from("gantt\n");
+    out.push_str(&format!("    title {}\n", mermaid_label(plan_name)));
+    out.push_str("    dateFormat x\n    axisFormat %S.%L\n");
+
+    let mut worker = None;
+    for (i, bar) in bars.iter().enumerate() {
+        if worker != Some(bar.worker) {
+            worker = Some(bar.worker);
+            out.push_str(&format!("    section worker {}\n", bar.worker));
+        }
+        let label = mermaid_label(&bar.step_id);
+        let run_start = bar.start_ms + bar.lock_ms;
+        if bar.lock_ms > 0 {
+            out.push_str(&format!(
+                "    {} (lock) :active, l{}, {}, {}\n",
+                label, i, bar.start_ms, run_start
+            ));
+        }
+        let tag = match bar.status {
+            StepStatus::Passed => "done, ",
+            StepStatus::Failed => "crit, ",
+            _ => "",
+        };
+        out.push_str(&format!(
+            "    {} :{}s{}, {}, {}\n",
+            label,
+            tag,
+            i,
+            run_start,
+            bar.end_ms.max(run_start + 1)
+        ));
+    }
+    out
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn render_html(plan_name: &str, bars: &[Bar]) -> String {
+    let total = bars.iter().map(|b| b.end_ms).max().unwrap_or(0).max(1) as f64;
+    let percent = |ms: i64| ms as f64 * 100.0 / total;
+    let workers = bars.iter().map(|b| b.worker).max().unwrap_or(0);
+
+    let mut rows = String::new();
+    for worker in 0..=workers {
+        rows.push_str(&format!(
+            "<div class=\"row\"><span class=\"label\">worker {}</span><div class=\"lane\">",
+            worker
+        ));
+        for bar in bars.iter().filter(|b| b.worker == worker) {
+            let status = serde_json::to_value(&bar.status)
+                .ok()
+                .and_then(|s| s.as_str().map(str::to_string))
+                .unwrap_or_default();
+            let title = html_escape(&format!(
+                "{} ({}) {}ms, lock {}ms",
+                bar.step_id,
+                status,
+                bar.end_ms - bar.start_ms,
+                bar.lock_ms
+            ));
+            if bar.lock_ms > 0 {
+                rows.push_str(&format!(
+                    "<div class=\"bar lock\" style=\"left:{:.3}%;width:{:.3}%\" title=\"{}\"></div>",
+                    percent(bar.start_ms),
+                    percent(bar.lock_ms),
+                    title
+                ));
+            }
+            let run_start = bar.start_ms + bar.lock_ms;
+            rows.push_str(&format!(
+                "<div class=\"bar {}\" style=\"left:{:.3}%;width:{:.3}%\" title=\"{}\">{}</div>",
+                status,
+                percent(run_start),
+                percent(bar.end_ms - run_start),
+                title,
+                html_escape(&bar.step_id)
+            ));
+        }
+        rows.push_str("</div></div>\n");
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="pt-BR">
+<head>
+<meta charset="utf-8">
+<title>Linha do tempo - {title}</title>
+<style>
+body {{ font-family: sans-serif; margin: 24px; }}
+.row {{ display: flex; align-items: center; margin: 4px 0; }}
+.label {{ width: 90px; font-size: 12px; color: #555; }}
+.lane {{ position: relative; flex: 1; height: 24px; background: #f4f4f4; }}
+.bar {{ position: absolute; top: 2px; height: 20px; min-width: 2px; overflow: hidden;
+        font-size: 11px; line-height: 20px; color: #fff; white-space: nowrap; }}
+.passed {{ background: #2e8b57; }}
+.failed {{ background: #c0392b; }}
+.skipped, .cancelled, .not_run {{ background: #999; }}
+.lock {{ background: repeating-linear-gradient(45deg, #e0a800, #e0a800 4px, #f5d76e 4px, #f5d76e 8px); }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+<p>{steps} steps em {total}ms. Trechos listrados: espera pelo lock do contexto.</p>
+{rows}</body>
+</html>
+"#,
+        title = html_escape(plan_name),
+        steps = bars.len(),
+        total = total as i64,
+        rows = rows
+    )
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(id: &str, status: StepStatus, timing: (&str, &str, usize, u64)) -> StepResult {
+        StepResult {
+            step_id: id.to_string(),
+            status,
+            timeline: Some(StepTiming {
+                started_at: timing.0.to_string(),
+                ended_at: timing.1.to_string(),
+                worker: timing.2,
+                lock_wait_ms: timing.3,
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn steps() -> Vec<StepResult> {
+        vec![
+            result(
+                "login",
+                StepStatus::Passed,
+                ("2026-01-05T10:00:00.100Z", "2026-01-05T10:00:00.300Z", 0, 0),
+            ),
+            result(
+                "list",
+                StepStatus::Failed,
+                (
+                    "2026-01-05T10:00:00.100Z",
+                    "2026-01-05T10:00:00.500Z",
+                    1,
+                    200,
+                ),
+            ),
+            StepResult {
+                step_id: "legacy".to_string(),
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_worker_slots_reuse_lowest_free() {
+        let slots = WorkerSlots::new();
+        let first = slots.acquire();
+        let second = slots.acquire();
+        assert_eq!((first.index(), second.index()), (0, 1));
+
+        drop(first);
+        assert_eq!(slots.acquire().index(), 0);
+    }
+
+    #[test]
+    fn test_mermaid_gantt_splits_lock_wait() {
+        let bars = bars(&steps()).unwrap();
+        assert_eq!(bars.len(), 2);
+
+        let gantt = render_mermaid("Plano: API", &bars);
+        assert!(gantt.starts_with("gantt\n    title Plano_ API\n"));
+        assert!(gantt.contains("section worker 0\n    login :done, s0, 0, 200\n"));
+        assert!(gantt.contains("list (lock) :active, l1, 0, 200\n"));
+        assert!(gantt.contains("list :crit, s1, 200, 400\n"));
+    }
+
+    #[test]
+    fn test_html_has_one_lane_per_worker() {
+        let html = render_html("<API>", &bars(&steps()).unwrap());
+        assert!(html.contains("<title>Linha do tempo - &lt;API&gt;</title>"));
+        assert_eq!(html.matches("class=\"lane\"").count(), 2);
+        assert!(html.contains("class=\"bar lock\" style=\"left:0.000%;width:50.000%\""));
+    }
+}
@@ -0,0 +1,255 @@
+//! # Módulo de Triagem - Falhas Agrupadas por Código
+//!
+//! Classifica cada step que falhou em um `ErrorCode` e agrupa os
+//! resultados, para o resumo do console e `summary.failures`.
+//!
+//! ## Para todos entenderem:
+//!
+//! Com 40 steps vermelhos, a primeira pergunta de quem está de plantão é
+//! "é a API caindo ou o teste quebrado?". A triagem responde de cara:
+//!
+//! ```text
+//! Falhas por tipo:
+//!   3 × E3001 ASSERTION_STATUS_CODE (Assertion): create_user, get_user, delete_user
+//!   2 × E2001 HTTP_TIMEOUT (Execução HTTP): list_orders, search
+//!   1 × E3010 EXTRACTION_PATH_NOT_FOUND (Assertion): login
+//! ```
+//!
+//! ## Como cada falha é classificada:
+//!
+//! | Evidência no resultado                       | Código                 |
+//! |----------------------------------------------|------------------------|
+//! | Erro começando com `[E....]` (ex: cancelado) | O código do prefixo    |
+//! | Sem resposta e erro com "timed out"          | E2001 (timeout)        |
+//! | Sem resposta e erro de certificado/TLS       | E2005 (TLS)            |
+//! | Sem resposta (status 0)                      | E2002 (conexão)        |
+//! | `Assertion failed: status_code`/`status_range` | E3001                |
+//! | `Assertion failed: header`                   | E3003                  |
+//! | `Assertion failed: latency`                  | E3004                  |
+//! | `Assertion failed: ...` (demais)             | E3002 (json_body)      |
+//! | Extração com `error_code`                    | O código da extração   |
+//! | Variável de ambiente/contexto ausente        | E4001 / E4002          |
+//! | Sem executor para a action                   | E5002                  |
+//! | Qualquer outro erro                          | E5001                  |
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use crate::errors::ErrorCode;
+use crate::protocol::{FailureGroup, StepResult, StepStatus};
+
+// ============================================================================
+// FUNÇÕES PRINCIPAIS
+// ============================================================================
+
+/// Agrupa os steps com status `failed` por código, do grupo maior ao menor.
+pub fn group_failures(results: &[StepResult]) -> Vec<FailureGroup> {
+    let mut groups: BTreeMap<ErrorCode, Vec<String>> = BTreeMap::new();
+    for result in results.iter().filter(|r| r.status == StepStatus::Failed) {
+        groups
+            .entry(classify(result))
+            .or_default()
+            .push(result.step_id.clone());
+    }
+
+    let mut groups: Vec<FailureGroup> = groups
+        .into_iter()
+        .map(|(code, steps)| FailureGroup {
+            code: code.to_string(),
+            name: code.name().to_string(),
+            category: code.category().as_str().to_string(),
+            count: steps.len(),
+            steps,
+        })
+        .collect();
+    groups.sort_by_key(|g| std::cmp::Reverse(g.count));
+    groups
+}
+
+/// Linhas do resumo do console (vazio se nada falhou).
+pub fn render(groups: &[FailureGroup]) -> String {
+    let mut out = String::new();
+    if groups.is_empty() {
+        return out;
+    }
+    out.push_str("Falhas por tipo:\n");
+    for group in groups {
+        let category = ErrorCode::parse(&group.code)
+            .map(|c| c.category().to_string())
+            .unwrap_or_default();
+        let _ = writeln!(
+            out,
+            "  {} × {} {} ({}): {}",
+            group.count,
+            group.code,
+            group.name,
+            category,
+            group.steps.join(", ")
+        );
+    }
+    out
+}
+
+/// Código de erro mais provável para um step que falhou.
+pub fn classify(result: &StepResult) -> ErrorCode {
+    let error = result.error.as_deref().unwrap_or_default();
+    let lower = error.to_lowercase();
+
+    if let Some(code) = error
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once(']'))
+        .and_then(|(code, _)| ErrorCode::parse(code))
+    {
+        return code;
+    }
+
+    if let Some(code) = result
+        .extractions
+        .iter()
+        .flatten()
+        .filter(|e| !e.success)
+        .find_map(|e| e.error_code.as_deref().and_then(ErrorCode::parse))
+    {
+        return code;
+    }
+
+    if result
+        .http_details
+        .as_ref()
+        .is_some_and(|http| http.status_code == 0)
+    {
+        return if lower.contains("timed out") || lower.contains("timeout") {
+            ErrorCode::HTTP_TIMEOUT
+        } else if lower.contains("certificate") || lower.contains("tls") {
+            ErrorCode::HTTP_TLS_ERROR
+        } else {
+            ErrorCode::HTTP_CONNECTION_ERROR
+        };
+    }
+
+    if let Some(assertion) = error.strip_prefix("Assertion failed: ") {
+        return if assertion.starts_with("status_code") || assertion.starts_with("status_range") {
+            ErrorCode::ASSERTION_STATUS_CODE
+        } else if assertion.starts_with("header") {
+            ErrorCode::ASSERTION_HEADER
+        } else if assertion.starts_with("latency") {
+            ErrorCode::ASSERTION_LATENCY
+        } else {
+            ErrorCode::ASSERTION_JSON_BODY
+        };
+    }
+
+    if lower.contains("timed out") || lower.contains("timeout") {
+        ErrorCode::HTTP_TIMEOUT
+    } else if lower.contains("variável de ambiente") {
+        ErrorCode::ENV_VAR_NOT_FOUND
+    } else if lower.contains("variável") && lower.contains("não encontrada") {
+        ErrorCode::CONTEXT_VAR_NOT_FOUND
+    } else if lower.starts_with("no executor for action") {
+        ErrorCode::NO_EXECUTOR_FOR_ACTION
+    } else {
+        ErrorCode::INTERNAL_ERROR
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn failed(id: &str, error: &str, status_code: Option<u16>) -> StepResult {
+        let mut result: StepResult = serde_json::from_value(json!({
+            "step_id": id, "status": "failed", "duration_ms": 1, "error": error
+        }))
+        .unwrap();
+        if let Some(status_code) = status_code {
+            result.http_details = serde_json::from_value(json!({
+                "method": "GET", "url": "http://api", "status_code": status_code, "latency_ms": 1
+            }))
+            .unwrap();
+        }
+        result
+    }
+
+    #[test]
+    fn test_classify_failures() {
+        let cases = [
+            (
+                failed("a", "error sending request: operation timed out", Some(0)),
+                ErrorCode::HTTP_TIMEOUT,
+            ),
+            (
+                failed("b", "error sending request: connection refused", Some(0)),
+                ErrorCode::HTTP_CONNECTION_ERROR,
+            ),
+            (
+                failed(
+                    "c",
+                    "Assertion failed: status_code eq 201 (got 400)",
+                    Some(400),
+                ),
+                ErrorCode::ASSERTION_STATUS_CODE,
+            ),
+            (
+                failed(
+                    "d",
+                    "Assertion failed: json_body '$.id' eq 1 (got 2)",
+                    Some(200),
+                ),
+                ErrorCode::ASSERTION_JSON_BODY,
+            ),
+            (
+                failed(
+                    "e",
+                    "Variável 'token' não encontrada. Disponíveis: []",
+                    None,
+                ),
+                ErrorCode::CONTEXT_VAR_NOT_FOUND,
+            ),
+            (
+                failed("g", "[E4006] Execução abortada via --progress-url: x", None),
+                ErrorCode::EXECUTION_ABORTED,
+            ),
+            (failed("f", "boom", None), ErrorCode::INTERNAL_ERROR),
+        ];
+        for (result, expected) in cases {
+            assert_eq!(classify(&result), expected, "step {}", result.step_id);
+        }
+    }
+
+    #[test]
+    fn test_group_and_render() {
+        let mut passed = failed("ok", "", None);
+        passed.status = StepStatus::Passed;
+        let results = vec![
+            failed(
+                "create",
+                "Assertion failed: status_code eq 201 (got 400)",
+                Some(400),
+            ),
+            failed("slow", "operation timed out", Some(0)),
+            failed(
+                "get",
+                "Assertion failed: status_range success (got 500)",
+                Some(500),
+            ),
+            passed,
+        ];
+
+        let groups = group_failures(&results);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].code, "E3001");
+        assert_eq!(groups[0].category, "assertion");
+        assert_eq!(groups[0].steps, vec!["create", "get"]);
+        assert_eq!(groups[1].code, "E2001");
+
+        let text = render(&groups);
+        assert!(text.contains("2 × E3001 ASSERTION_STATUS_CODE (Assertion): create, get"));
+        assert!(text.contains("1 × E2001 HTTP_TIMEOUT (Execução HTTP): slow"));
+        assert!(render(&[]).is_empty());
+    }
+}
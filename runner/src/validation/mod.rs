@@ -22,6 +22,7 @@
 //! 2. **Plano não vazio**: Deve ter pelo menos um step
 //! 3. **Actions válidas**: Apenas ações conhecidas são aceitas
 //! 4. **Parâmetros completos**: Campos obrigatórios presentes
+//! 4.1. **Parâmetros válidos**: Tipos e nomes conforme o schema do executor
 //! 5. **Dependências existem**: Não referencia steps inexistentes
 //! 6. **Sem ciclos**: Evita dependências circulares
 //!
@@ -40,8 +41,12 @@
 //! }
 //! ```
 
-use crate::protocol::{Plan, Step};
-use std::collections::HashMap;
+use crate::executors;
+use crate::protocol::{Plan, RecoveryPolicy, Step};
+use jsonschema::error::ValidationErrorKind;
+use jsonschema::JSONSchema;
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
 use thiserror::Error;
 
 // ============================================================================
@@ -66,7 +71,7 @@ pub enum ValidationError {
 
     /// Ação (action) do step não é reconhecida.
     /// Exemplo: "browser_click" quando só temos "http_request", "wait", "sleep"
-    #[error("Step '{step_id}': action '{action}' não é conhecida. Ações válidas: http_request, wait, sleep")]
+    #[error("Step '{step_id}': action '{action}' não é conhecida. Ações válidas: http_request, wait, sleep, set_variable, log, assert, transform, tcp_send, udp_send, grpc_health, shell_command, graphql_request, grpc_call, rate_limit_probe, webhook_wait")]
     UnknownAction { step_id: String, action: String },
 
     /// Parâmetro obrigatório não foi informado.
@@ -95,6 +100,103 @@ pub enum ValidationError {
     /// Método HTTP inválido (não é GET, POST, PUT, etc).
     #[error("Step '{step_id}': método HTTP '{method}' inválido")]
     InvalidHttpMethod { step_id: String, method: String },
+
+    /// Dois ou mais steps com o mesmo ID.
+    /// Sem esta checagem, o DAG manteria só um deles e os `depends_on`
+    /// ficariam ambíguos.
+    #[error("Step '{step_id}': ID duplicado ({count} steps usam este ID)")]
+    DuplicateStepId { step_id: String, count: usize },
+
+    /// Step referencia um template que não existe em `config.request_templates`.
+    #[error("Step '{step_id}': template '{template}' não existe em config.request_templates")]
+    UnknownTemplate { step_id: String, template: String },
+
+    /// Step referencia uma sessão que não existe em `config.sessions`.
+    #[error("Step '{step_id}': sessão '{session}' não existe em config.sessions")]
+    UnknownSession { step_id: String, session: String },
+
+    /// Step referencia um ator que não existe em `config.actors`.
+    #[error("Step '{step_id}': ator '{actor}' não existe em config.actors")]
+    UnknownActor { step_id: String, actor: String },
+
+    /// Step referencia um agente que não existe em `config.agents`.
+    #[error("Step '{step_id}': agente '{agent}' não existe em config.agents")]
+    UnknownAgent { step_id: String, agent: String },
+
+    /// Step referencia um assinador que não existe em `config.signers`.
+    #[error("Step '{step_id}': assinador '{signer}' não existe em config.signers")]
+    UnknownSigner { step_id: String, signer: String },
+
+    /// `recovery_policy` que o runtime trataria como fail_fast em silêncio
+    /// (ou que esperaria tempo absurdo entre tentativas).
+    #[error("Step '{step_id}': recovery_policy inválida (E1014): {reason}")]
+    InvalidRecoveryPolicy { step_id: String, reason: String },
+
+    /// Parâmetro que o executor da action não conhece (ex: `methd`).
+    #[error(
+        "Step '{step_id}': parâmetro '{param}' não é aceito por {action} (aceitos: {accepted})"
+    )]
+    UnknownParam {
+        step_id: String,
+        action: String,
+        param: String,
+        accepted: String,
+    },
+
+    /// Parâmetro com tipo ou valor fora do schema do executor.
+    /// Exemplo: `"duration_ms": "fast"` em um wait
+    #[error("Step '{step_id}': parâmetro '{param}' inválido: {reason}")]
+    InvalidParam {
+        step_id: String,
+        param: String,
+        reason: String,
+    },
+
+    /// `shell_command` sem autorização explícita (`--allow-shell`).
+    /// Planos gerados pelo Brain ou recebidos pela rede não rodam
+    /// programas locais por padrão.
+    #[error("Step '{step_id}': shell_command roda comandos locais e exige --allow-shell")]
+    ShellNotAllowed { step_id: String },
+
+    /// Parâmetros que não podem aparecer juntos (ex: `body` e `body_file`).
+    #[error("Step '{step_id}': use apenas um entre '{first}' e '{second}'")]
+    ConflictingParams {
+        step_id: String,
+        first: String,
+        second: String,
+    },
+}
+
+/// Avisos de dependência (não impedem a execução).
+///
+/// ## Para leigos:
+///
+/// Um step isolado (ninguém depende dele e ele não depende de ninguém)
+/// é normal no modo paralelo: ele só roda ao mesmo tempo que os outros.
+/// Já um step que usa `${user_id}` sem depender de quem extrai `user_id`
+/// é um bug de ordem: no paralelo ele pode rodar antes e ler um valor
+/// vazio (ou antigo).
+#[derive(Debug, Error, PartialEq)]
+pub enum ValidationWarning {
+    /// Step sem dependências e sem dependentes (informativo no modo paralelo).
+    #[error("Step '{step_id}': isolado (não depende de nenhum step e nenhum step depende dele)")]
+    IsolatedStep { step_id: String },
+
+    /// Step usa uma variável antes de o step que a produz ter garantidamente rodado.
+    #[error("Step '{step_id}': usa '${{{variable}}}', produzida por '{producer}', que {reason}")]
+    UseBeforeProduce {
+        step_id: String,
+        variable: String,
+        producer: String,
+        reason: String,
+    },
+}
+
+impl ValidationWarning {
+    /// True para avisos que indicam bug de ordenação (não apenas informativos).
+    pub fn is_ordering_bug(&self) -> bool {
+        matches!(self, ValidationWarning::UseBeforeProduce { .. })
+    }
 }
 
 // ============================================================================
@@ -118,11 +220,45 @@ pub const SUPPORTED_SPEC_VERSION: &str = "0.1";
 /// - `http_request`: Faz requisição HTTP
 /// - `wait`: Pausa a execução
 /// - `sleep`: Alias de wait (mesmo comportamento)
-const KNOWN_ACTIONS: &[&str] = &["http_request", "wait", "sleep"];
+/// - `set_variable`: Define variáveis no contexto
+/// - `log`: Emite mensagem no relatório
+/// - `assert`: Avalia assertions sobre o contexto
+/// - `transform`: Remodela variáveis do contexto
+/// - `tcp_send`: Envia bytes crus por TCP e lê a resposta
+/// - `udp_send`: Envia um datagrama UDP e espera (ou não) a resposta
+/// - `grpc_health`: Consulta o health check padrão de um servidor gRPC
+/// - `shell_command`: Roda um programa local e verifica a saída
+/// - `graphql_request`: Envia uma operação GraphQL (POST via HTTP)
+/// - `grpc_call`: Faz uma chamada gRPC unária (descritores ou reflection)
+/// - `rate_limit_probe`: Dispara uma rajada e verifica quando a API limita
+/// - `webhook_wait`: Espera um callback de entrada no listener da execução
+pub const KNOWN_ACTIONS: &[&str] = &[
+    "http_request",
+    "wait",
+    "sleep",
+    "set_variable",
+    "log",
+    "assert",
+    "transform",
+    "tcp_send",
+    "udp_send",
+    "grpc_health",
+    "shell_command",
+    "graphql_request",
+    "grpc_call",
+    "rate_limit_probe",
+    "webhook_wait",
+];
 
 /// Métodos HTTP válidos conforme RFC 7231 e RFC 5789.
 ///
 /// Requisições com outros métodos serão rejeitadas.
+/// Estratégias aceitas em `recovery_policy.strategy` (sem diferenciar maiúsculas).
+const RECOVERY_STRATEGIES: &[&str] = &["retry", "fail_fast", "failfast", "ignore"];
+
+/// Maior espera (ms) aceita entre duas tentativas de um `recovery_policy`.
+const MAX_RECOVERY_BACKOFF_MS: u64 = 5 * 60 * 1000;
+
 const VALID_HTTP_METHODS: &[&str] = &["GET", "POST", "PUT", "DELETE", "PATCH", "HEAD", "OPTIONS"];
 
 // ============================================================================
@@ -210,9 +346,76 @@ pub fn validate_plan(plan: &Plan) -> ValidationResult {
     // `.collect()` junta tudo em um vetor
     let step_ids: Vec<&str> = plan.steps.iter().map(|s| s.id.as_str()).collect();
 
+    // Detecta IDs repetidos (um erro por ID, na ordem em que aparecem).
+    let mut id_counts: HashMap<&str, usize> = HashMap::new();
+    for id in &step_ids {
+        *id_counts.entry(id).or_default() += 1;
+    }
+    let mut reported = HashSet::new();
+    for id in &step_ids {
+        let count = id_counts[id];
+        if count > 1 && reported.insert(*id) {
+            errors.push(ValidationError::DuplicateStepId {
+                step_id: id.to_string(),
+                count,
+            });
+        }
+    }
+
     // Valida cada step individualmente.
     for step in &plan.steps {
         validate_step(step, &step_ids, &mut errors);
+
+        // Templates são aplicados no carregamento; um nome desconhecido
+        // deixaria o step sem os headers/params esperados.
+        if let Some(template) = &step.template {
+            if !plan.config.request_templates.contains_key(template) {
+                errors.push(ValidationError::UnknownTemplate {
+                    step_id: step.id.clone(),
+                    template: template.clone(),
+                });
+            }
+        }
+
+        // Sem a sessão, o step rodaria no cliente compartilhado, sem os
+        // cookies e o token do "usuário" esperado.
+        if let Some(session) = &step.session {
+            if !plan.config.sessions.contains_key(session) {
+                errors.push(ValidationError::UnknownSession {
+                    step_id: step.id.clone(),
+                    session: session.clone(),
+                });
+            }
+        }
+
+        if let Some(actor) = &step.actor {
+            if !plan.config.actors.contains_key(actor) {
+                errors.push(ValidationError::UnknownActor {
+                    step_id: step.id.clone(),
+                    actor: actor.clone(),
+                });
+            }
+        }
+
+        if let Some(agent) = &step.agent {
+            if !plan.config.agents.contains_key(agent) {
+                errors.push(ValidationError::UnknownAgent {
+                    step_id: step.id.clone(),
+                    agent: agent.clone(),
+                });
+            }
+        }
+
+        // Sem o assinador, a requisição sairia sem assinatura (e o erro do
+        // servidor esconderia a causa).
+        if let Some(signer) = step.params.get("signer").and_then(|s| s.as_str()) {
+            if !plan.config.signers.contains_key(signer) {
+                errors.push(ValidationError::UnknownSigner {
+                    step_id: step.id.clone(),
+                    signer: signer.to_string(),
+                });
+            }
+        }
     }
 
     // Retorna resultado.
@@ -269,8 +472,27 @@ fn validate_step(step: &Step, all_step_ids: &[&str], errors: &mut Vec<Validation
     match step.action.as_str() {
         "http_request" => validate_http_request_params(step, errors),
         "wait" | "sleep" => validate_wait_params(step, errors),
+        "set_variable" => validate_set_variable_params(step, errors),
+        "log" => validate_log_params(step, errors),
+        "assert" => validate_assert_step(step, errors),
+        "transform" => validate_transform_params(step, errors),
+        "tcp_send" | "udp_send" | "grpc_health" => validate_socket_params(step, errors),
+        "shell_command" => validate_shell_params(step, errors),
+        "graphql_request" => validate_graphql_params(step, errors),
+        "grpc_call" => validate_grpc_call_params(step, errors),
+        "rate_limit_probe" => validate_rate_limit_probe_params(step, errors),
         _ => {} // Ações desconhecidas já foram reportadas acima
     }
+    validate_params_schema(step, errors);
+
+    if let Some(policy) = &step.recovery_policy {
+        if let Some(reason) = recovery_policy_problem(policy) {
+            errors.push(ValidationError::InvalidRecoveryPolicy {
+                step_id: step.id.clone(),
+                reason,
+            });
+        }
+    }
 
     // Verifica dependências.
     // Para cada dependência declarada, verifica se existe.
@@ -343,6 +565,15 @@ fn validate_http_request_params(step: &Step, errors: &mut Vec<ValidationError>)
             param: "path".to_string(),
         });
     }
+
+    // Body embutido e body de arquivo são alternativos.
+    if step.params.get("body").is_some() && step.params.get("body_file").is_some() {
+        errors.push(ValidationError::ConflictingParams {
+            step_id: step.id.clone(),
+            first: "body".to_string(),
+            second: "body_file".to_string(),
+        });
+    }
 }
 
 /// Valida parâmetros obrigatórios de wait/sleep.
@@ -370,6 +601,287 @@ fn validate_wait_params(step: &Step, errors: &mut Vec<ValidationError>) {
     }
 }
 
+/// Valida parâmetros obrigatórios de set_variable.
+///
+/// Aceita `variables` (objeto com várias variáveis) ou `name` (uma só).
+fn validate_set_variable_params(step: &Step, errors: &mut Vec<ValidationError>) {
+    let has_variables = step
+        .params
+        .get("variables")
+        .map(|v| v.is_object())
+        .unwrap_or(false);
+    let has_name = step.params.get("name").and_then(|v| v.as_str()).is_some();
+
+    if !has_variables && !has_name {
+        errors.push(ValidationError::MissingParam {
+            step_id: step.id.clone(),
+            param: "variables".to_string(),
+        });
+    }
+}
+
+/// Valida parâmetros obrigatórios de log.
+///
+/// Uma mensagem de log precisa de:
+/// - `message`: Texto (pode conter `${variavel}`)
+fn validate_log_params(step: &Step, errors: &mut Vec<ValidationError>) {
+    if step
+        .params
+        .get("message")
+        .and_then(|v| v.as_str())
+        .is_none()
+    {
+        errors.push(ValidationError::MissingParam {
+            step_id: step.id.clone(),
+            param: "message".to_string(),
+        });
+    }
+}
+
+/// Primeiro problema de uma `recovery_policy`, se houver.
+///
+/// Sem esta checagem, uma strategy com erro de digitação vira fail_fast em
+/// silêncio e um backoff exagerado trava a execução por horas.
+fn recovery_policy_problem(policy: &RecoveryPolicy) -> Option<String> {
+    if !RECOVERY_STRATEGIES.contains(&policy.strategy.to_lowercase().as_str()) {
+        return Some(format!(
+            "strategy '{}' desconhecida (use retry, fail_fast ou ignore)",
+            policy.strategy
+        ));
+    }
+    if policy.max_attempts == 0 {
+        return Some("max_attempts deve ser pelo menos 1".to_string());
+    }
+    if !(policy.backoff_factor.is_finite() && policy.backoff_factor > 0.0) {
+        return Some(format!(
+            "backoff_factor deve ser maior que 0 (recebido {})",
+            policy.backoff_factor
+        ));
+    }
+    // A maior espera é a anterior à última tentativa.
+    let retries = policy.max_attempts.saturating_sub(1);
+    let longest = (1..=retries)
+        .map(|attempt| policy.backoff_ms as f64 * policy.backoff_factor.powi(attempt as i32 - 1))
+        .fold(0.0, f64::max);
+    if longest > MAX_RECOVERY_BACKOFF_MS as f64 {
+        return Some(format!(
+            "backoff chega a {:.0}ms entre tentativas (máximo {}ms)",
+            longest, MAX_RECOVERY_BACKOFF_MS
+        ));
+    }
+    None
+}
+
+/// Valida os parâmetros de um step tcp_send/udp_send/grpc_health (destino obrigatório).
+fn validate_socket_params(step: &Step, errors: &mut Vec<ValidationError>) {
+    if step.params.get("host").and_then(|v| v.as_str()).is_none() {
+        errors.push(ValidationError::MissingParam {
+            step_id: step.id.clone(),
+            param: "host".to_string(),
+        });
+    }
+    if step.params.get("port").and_then(|v| v.as_u64()).is_none() {
+        errors.push(ValidationError::MissingParam {
+            step_id: step.id.clone(),
+            param: "port".to_string(),
+        });
+    }
+}
+
+/// Valida os parâmetros de um step shell_command (`command` obrigatório).
+fn validate_shell_params(step: &Step, errors: &mut Vec<ValidationError>) {
+    if step
+        .params
+        .get("command")
+        .and_then(|v| v.as_str())
+        .is_none()
+    {
+        errors.push(ValidationError::MissingParam {
+            step_id: step.id.clone(),
+            param: "command".to_string(),
+        });
+    }
+}
+
+/// Valida os parâmetros de um step grpc_call (destino e `method` obrigatórios).
+fn validate_grpc_call_params(step: &Step, errors: &mut Vec<ValidationError>) {
+    validate_socket_params(step, errors);
+    if step.params.get("method").and_then(|v| v.as_str()).is_none() {
+        errors.push(ValidationError::MissingParam {
+            step_id: step.id.clone(),
+            param: "method".to_string(),
+        });
+    }
+}
+
+/// Valida os parâmetros de um step graphql_request (`query` obrigatória).
+fn validate_graphql_params(step: &Step, errors: &mut Vec<ValidationError>) {
+    if step.params.get("query").and_then(|v| v.as_str()).is_none() {
+        errors.push(ValidationError::MissingParam {
+            step_id: step.id.clone(),
+            param: "query".to_string(),
+        });
+    }
+}
+
+/// Valida os parâmetros de um step rate_limit_probe (destino, tamanho e ritmo da rajada).
+fn validate_rate_limit_probe_params(step: &Step, errors: &mut Vec<ValidationError>) {
+    for param in ["path", "requests", "rate_per_sec"] {
+        if step.params.get(param).is_none() {
+            errors.push(ValidationError::MissingParam {
+                step_id: step.id.clone(),
+                param: param.to_string(),
+            });
+        }
+    }
+}
+
+/// Schemas dos params de cada action embutida, compilados uma vez por processo.
+fn params_schemas() -> &'static HashMap<&'static str, (serde_json::Value, JSONSchema)> {
+    static COMPILED: OnceLock<HashMap<&'static str, (serde_json::Value, JSONSchema)>> =
+        OnceLock::new();
+    COMPILED.get_or_init(|| {
+        let executors = executors::builtin();
+        KNOWN_ACTIONS
+            .iter()
+            .filter_map(|action| {
+                let schema = executors
+                    .iter()
+                    .find(|executor| executor.can_handle(action))?
+                    .params_schema(action)?;
+                let compiled = JSONSchema::compile(&schema).expect("schema de params inválido");
+                Some((*action, (schema, compiled)))
+            })
+            .collect()
+    })
+}
+
+/// Schema compilado dos params de `action`.
+fn compiled_params_schema(action: &str) -> Option<&'static JSONSchema> {
+    params_schemas().get(action).map(|(_, compiled)| compiled)
+}
+
+/// Confere os params contra o schema registrado pelo executor da action.
+///
+/// Um param presente com o tipo errado (ex: `"port": "5432"`) é reportado
+/// como `InvalidParam` no lugar do `MissingParam` das checagens acima.
+/// Params omitidos (`null`) contam como objeto vazio.
+fn validate_params_schema(step: &Step, errors: &mut Vec<ValidationError>) {
+    let Some(schema) = compiled_params_schema(&step.action) else {
+        return;
+    };
+    let empty = serde_json::json!({});
+    let params = if step.params.is_null() {
+        &empty
+    } else {
+        &step.params
+    };
+    let Err(failures) = schema.validate(params) else {
+        return;
+    };
+    for failure in failures {
+        let location = failure.instance_path.clone().into_vec().join(".");
+        match &failure.kind {
+            ValidationErrorKind::Required { .. } => {}
+            ValidationErrorKind::AdditionalProperties { unexpected } => {
+                let accepted = params_schemas()
+                    .get(step.action.as_str())
+                    .and_then(|(s, _)| params_at(s, &location))
+                    .unwrap_or_default();
+                for name in unexpected {
+                    errors.push(ValidationError::UnknownParam {
+                        step_id: step.id.clone(),
+                        action: step.action.clone(),
+                        param: join_path(&location, name),
+                        accepted: accepted.clone(),
+                    });
+                }
+            }
+            _ => {
+                errors.retain(|e| {
+                    !matches!(e, ValidationError::MissingParam { step_id, param }
+                        if step_id == &step.id && param == &location)
+                });
+                errors.push(ValidationError::InvalidParam {
+                    step_id: step.id.clone(),
+                    param: if location.is_empty() {
+                        "params".to_string()
+                    } else {
+                        location
+                    },
+                    reason: failure.to_string(),
+                });
+            }
+        }
+    }
+}
+
+/// Nomes aceitos pelo schema no objeto em `location` (`auth`, `""` = raiz).
+fn params_at(schema: &serde_json::Value, location: &str) -> Option<String> {
+    let mut node = schema;
+    for part in location.split('.').filter(|p| !p.is_empty()) {
+        node = match node.get("properties").and_then(|p| p.get(part)) {
+            Some(property) => property,
+            // Índice de array: desce para `items`.
+            None => node.get("items")?,
+        };
+    }
+    let mut names: Vec<&str> = node
+        .get("properties")?
+        .as_object()?
+        .keys()
+        .map(String::as_str)
+        .collect();
+    names.sort_unstable();
+    Some(if names.is_empty() {
+        "nenhum".to_string()
+    } else {
+        names.join(", ")
+    })
+}
+
+fn join_path(location: &str, name: &str) -> String {
+    if location.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", location, name)
+    }
+}
+
+/// Valida um step de assert.
+///
+/// Um assert sem assertions não verifica nada, então exigimos ao menos uma.
+fn validate_assert_step(step: &Step, errors: &mut Vec<ValidationError>) {
+    if step.assertions.is_empty() {
+        errors.push(ValidationError::MissingParam {
+            step_id: step.id.clone(),
+            param: "assertions".to_string(),
+        });
+    }
+}
+
+/// Valida parâmetros obrigatórios de transform.
+///
+/// Cada transform (direto nos params ou em `transforms`) precisa de
+/// `from` (origem) e `target` (destino).
+fn validate_transform_params(step: &Step, errors: &mut Vec<ValidationError>) {
+    let specs: Vec<&serde_json::Value> = match step.params.get("transforms") {
+        Some(serde_json::Value::Array(items)) => items.iter().collect(),
+        _ => vec![&step.params],
+    };
+
+    for spec in specs {
+        for param in ["from", "target"] {
+            if spec.get(param).is_none() {
+                errors.push(ValidationError::MissingParam {
+                    step_id: step.id.clone(),
+                    param: param.to_string(),
+                });
+            }
+        }
+    }
+}
+
 // ============================================================================
 // VALIDAÇÃO DE DAG (DETECÇÃO DE CICLOS)
 // ============================================================================
@@ -449,7 +961,7 @@ fn detect_cycle_dfs<'a>(
     // Visita todas as dependências
     if let Some(deps) = graph.get(node) {
         for dep in deps {
-            match color.get(dep) {
+            match color.get(dep).copied() {
                 Some(1) => {
                     // Encontrou nó cinza = ciclo!
                     errors.push(ValidationError::CircularDependency {
@@ -457,11 +969,9 @@ fn detect_cycle_dfs<'a>(
                     });
                     return true;
                 }
-                Some(0) => {
-                    // Nó branco, continua DFS
-                    if detect_cycle_dfs(dep, graph, color, errors) {
-                        return true;
-                    }
+                // Nó branco, continua DFS
+                Some(0) if detect_cycle_dfs(dep, graph, color, errors) => {
+                    return true;
                 }
                 _ => {
                     // Nó preto (já processado), ignora
@@ -475,6 +985,166 @@ fn detect_cycle_dfs<'a>(
     false
 }
 
+/// Steps `shell_command` de um plano executado sem `--allow-shell`.
+pub fn shell_not_allowed(plan: &Plan) -> Vec<ValidationError> {
+    plan.steps
+        .iter()
+        .filter(|step| step.action == "shell_command")
+        .map(|step| ValidationError::ShellNotAllowed {
+            step_id: step.id.clone(),
+        })
+        .collect()
+}
+
+// ============================================================================
+// ANÁLISE DE FLUXO DE DADOS (AVISOS)
+// ============================================================================
+
+/// Analisa dependências e o uso de variáveis entre steps.
+///
+/// Para cada variável produzida por um step (`extract`, `set_variable`,
+/// `transform`), verifica se os steps que a usam rodam garantidamente
+/// depois do produtor:
+///
+/// - **Sequencial**: o produtor vem antes na lista.
+/// - **Paralelo**: o produtor é dependência (direta ou transitiva) em `depends_on`.
+///
+/// No modo paralelo também lista os steps isolados (informativo).
+pub fn dependency_warnings(plan: &Plan, parallel: bool) -> Vec<ValidationWarning> {
+    let steps = &plan.steps;
+    let mut warnings = Vec::new();
+
+    if parallel && steps.len() > 1 {
+        let depended: HashSet<&str> = steps
+            .iter()
+            .flat_map(|s| s.depends_on.iter().map(String::as_str))
+            .collect();
+        for step in steps {
+            if step.depends_on.is_empty() && !depended.contains(step.id.as_str()) {
+                warnings.push(ValidationWarning::IsolatedStep {
+                    step_id: step.id.clone(),
+                });
+            }
+        }
+    }
+
+    // variável → steps que a produzem (em ordem).
+    let mut producers: Vec<(String, Vec<usize>)> = Vec::new();
+    for (index, step) in steps.iter().enumerate() {
+        for variable in produced_variables(step) {
+            match producers.iter_mut().find(|(name, _)| *name == variable) {
+                Some((_, list)) => list.push(index),
+                None => producers.push((variable, vec![index])),
+            }
+        }
+    }
+
+    let ancestors = if parallel {
+        Some(ancestor_sets(steps))
+    } else {
+        None
+    };
+
+    for (index, step) in steps.iter().enumerate() {
+        for (variable, sources) in &producers {
+            if sources.contains(&index) || !step_uses_variable(step, variable) {
+                continue;
+            }
+            let guaranteed = sources.iter().any(|&source| match &ancestors {
+                Some(ancestors) => ancestors[index].contains(steps[source].id.as_str()),
+                None => source < index,
+            });
+            if guaranteed {
+                continue;
+            }
+
+            let producer = &steps[sources[0]];
+            let reason = if parallel {
+                "não está em depends_on (direto ou transitivo)".to_string()
+            } else {
+                "vem depois na lista".to_string()
+            };
+            warnings.push(ValidationWarning::UseBeforeProduce {
+                step_id: step.id.clone(),
+                variable: variable.clone(),
+                producer: producer.id.clone(),
+                reason,
+            });
+        }
+    }
+
+    warnings
+}
+
+/// Verifica se o step usa a variável `name` (fora do próprio `extract`).
+///
+/// Conta `${name}`, `${name.campo}`, `${name[0]}` e strings iguais ao nome
+/// (ex: `"from": "name"` em `transform`, `"items": "name"` em `parallel_foreach`).
+pub fn step_uses_variable(step: &Step, name: &str) -> bool {
+    let mut value = serde_json::to_value(step).unwrap_or(serde_json::Value::Null);
+    if let Some(obj) = value.as_object_mut() {
+        obj.remove("extract");
+    }
+    uses_variable(&value, name)
+}
+
+fn uses_variable(value: &serde_json::Value, name: &str) -> bool {
+    use serde_json::Value;
+    match value {
+        Value::String(text) => {
+            text == name
+                || ["}", ".", "["]
+                    .iter()
+                    .any(|end| text.contains(&format!("${{{}{}", name, end)))
+        }
+        Value::Array(items) => items.iter().any(|v| uses_variable(v, name)),
+        Value::Object(map) => map.values().any(|v| uses_variable(v, name)),
+        _ => false,
+    }
+}
+
+/// Variáveis que o step grava no contexto.
+fn produced_variables(step: &Step) -> Vec<String> {
+    let mut variables: Vec<String> = step.extract.iter().map(|e| e.target.clone()).collect();
+    match step.action.as_str() {
+        "set_variable" => {
+            if let Some(map) = step.params.get("variables").and_then(|v| v.as_object()) {
+                variables.extend(map.keys().cloned());
+            }
+            if let Some(name) = step.params.get("name").and_then(|v| v.as_str()) {
+                variables.push(name.to_string());
+            }
+        }
+        "transform" => {
+            if let Some(target) = step.params.get("target").and_then(|v| v.as_str()) {
+                variables.push(target.to_string());
+            }
+        }
+        _ => {}
+    }
+    variables
+}
+
+/// Para cada step (mesma ordem), o conjunto de dependências transitivas.
+fn ancestor_sets(steps: &[Step]) -> Vec<HashSet<&str>> {
+    let by_id: HashMap<&str, &Step> = steps.iter().map(|s| (s.id.as_str(), s)).collect();
+    steps
+        .iter()
+        .map(|step| {
+            let mut seen = HashSet::new();
+            let mut stack: Vec<&str> = step.depends_on.iter().map(String::as_str).collect();
+            while let Some(id) = stack.pop() {
+                if seen.insert(id) {
+                    if let Some(dep) = by_id.get(id) {
+                        stack.extend(dep.depends_on.iter().map(String::as_str));
+                    }
+                }
+            }
+            seen
+        })
+        .collect()
+}
+
 // ============================================================================
 // TESTES
 // ============================================================================
@@ -482,7 +1152,7 @@ fn detect_cycle_dfs<'a>(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::protocol::{Config, Meta, Step};
+    use crate::protocol::{default_correlation_header, Config, Meta, Step};
     use serde_json::json;
     use std::collections::HashMap;
 
@@ -496,12 +1166,27 @@ mod tests {
                 description: None,
                 tags: vec![],
                 created_at: "2024-01-01".to_string(),
+                generator: None,
             },
             config: Config {
                 base_url: "https://api.test.com".to_string(),
                 timeout_ms: 5000,
                 global_headers: HashMap::new(),
                 variables: HashMap::new(),
+                correlation_header: default_correlation_header(),
+                http: Default::default(),
+                ip_preference: Default::default(),
+                wait_for: None,
+                request_templates: HashMap::new(),
+                auto_extract: vec![],
+                sessions: Default::default(),
+                actors: Default::default(),
+                agents: Default::default(),
+                signers: Default::default(),
+                regions: Vec::new(),
+                quality_gate: None,
+                auth: None,
+                webhook: None,
             },
             steps,
         }
@@ -517,6 +1202,7 @@ mod tests {
             assertions: vec![],
             extract: vec![],
             recovery_policy: None,
+            ..Default::default()
         }
     }
 
@@ -546,6 +1232,7 @@ mod tests {
             assertions: vec![],
             extract: vec![],
             recovery_policy: None,
+            ..Default::default()
         }]);
 
         let result = validate_plan(&plan);
@@ -556,6 +1243,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_shell_command_requires_opt_in() {
+        let shell = Step {
+            id: "seed".to_string(),
+            action: "shell_command".to_string(),
+            params: json!({ "command": "./seed.sh" }),
+            ..Default::default()
+        };
+        let plan = create_test_plan(vec![create_http_step("step1", "GET", "/api"), shell]);
+
+        // Estruturalmente válido; só a execução sem --allow-shell recusa.
+        assert!(validate_plan(&plan).is_ok());
+        let errors = shell_not_allowed(&plan);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].to_string().contains("'seed'"));
+        assert!(errors[0].to_string().contains("--allow-shell"));
+    }
+
     #[test]
     fn test_missing_http_params() {
         let plan = create_test_plan(vec![Step {
@@ -567,6 +1272,7 @@ mod tests {
             assertions: vec![],
             extract: vec![],
             recovery_policy: None,
+            ..Default::default()
         }]);
 
         let result = validate_plan(&plan);
@@ -586,6 +1292,7 @@ mod tests {
             assertions: vec![],
             extract: vec![],
             recovery_policy: None,
+            ..Default::default()
         }]);
 
         let result = validate_plan(&plan);
@@ -607,6 +1314,7 @@ mod tests {
             assertions: vec![],
             extract: vec![],
             recovery_policy: None,
+            ..Default::default()
         }]);
 
         let result = validate_plan(&plan);
@@ -630,6 +1338,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_body_and_body_file_conflict() {
+        let mut step = create_http_step("upload", "POST", "/files");
+        step.params["body"] = json!({});
+        step.params["body_file"] = json!("./payloads/big.json");
+
+        let errors = validate_plan(&create_test_plan(vec![step])).unwrap_err();
+        assert!(matches!(
+            &errors[0],
+            ValidationError::ConflictingParams { second, .. } if second == "body_file"
+        ));
+    }
+
     #[test]
     fn test_wait_missing_duration() {
         let plan = create_test_plan(vec![Step {
@@ -641,6 +1362,7 @@ mod tests {
             assertions: vec![],
             extract: vec![],
             recovery_policy: None,
+            ..Default::default()
         }]);
 
         let result = validate_plan(&plan);
@@ -661,12 +1383,27 @@ mod tests {
                 description: None,
                 tags: vec![],
                 created_at: "2024-01-01T00:00:00Z".to_string(),
+                generator: None,
             },
             config: Config {
                 base_url: "https://api.example.com".to_string(),
                 timeout_ms: 5000,
                 global_headers: HashMap::new(),
                 variables: HashMap::new(),
+                correlation_header: default_correlation_header(),
+                http: Default::default(),
+                ip_preference: Default::default(),
+                wait_for: None,
+                request_templates: HashMap::new(),
+                auto_extract: vec![],
+                sessions: Default::default(),
+                actors: Default::default(),
+                agents: Default::default(),
+                signers: Default::default(),
+                regions: Vec::new(),
+                quality_gate: None,
+                auth: None,
+                webhook: None,
             },
             steps: vec![create_http_step("step1", "GET", "/test")],
         };
@@ -692,12 +1429,295 @@ mod tests {
             assertions: vec![],
             extract: vec![],
             recovery_policy: None,
+            ..Default::default()
         }]);
 
         let result = validate_plan(&plan);
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_utility_actions_are_valid() {
+        let plan = create_test_plan(vec![
+            Step {
+                id: "vars".to_string(),
+                description: None,
+                depends_on: vec![],
+                action: "set_variable".to_string(),
+                params: json!({ "variables": { "env": "staging" } }),
+                assertions: vec![],
+                extract: vec![],
+                recovery_policy: None,
+                ..Default::default()
+            },
+            Step {
+                id: "note".to_string(),
+                description: None,
+                depends_on: vec!["vars".to_string()],
+                action: "log".to_string(),
+                params: json!({ "message": "Ambiente: ${env}" }),
+                assertions: vec![],
+                extract: vec![],
+                recovery_policy: None,
+                ..Default::default()
+            },
+        ]);
+
+        assert!(validate_plan(&plan).is_ok());
+    }
+
+    #[test]
+    fn test_log_missing_message() {
+        let plan = create_test_plan(vec![Step {
+            id: "note".to_string(),
+            description: None,
+            depends_on: vec![],
+            action: "log".to_string(),
+            params: json!({}),
+            assertions: vec![],
+            extract: vec![],
+            recovery_policy: None,
+            ..Default::default()
+        }]);
+
+        let errors = validate_plan(&plan).unwrap_err();
+        assert!(
+            matches!(&errors[0], ValidationError::MissingParam { param, .. } if param == "message")
+        );
+    }
+
+    #[test]
+    fn test_assert_requires_assertions() {
+        let plan = create_test_plan(vec![Step {
+            id: "check".to_string(),
+            description: None,
+            depends_on: vec![],
+            action: "assert".to_string(),
+            params: json!({}),
+            assertions: vec![],
+            extract: vec![],
+            recovery_policy: None,
+            ..Default::default()
+        }]);
+
+        let errors = validate_plan(&plan).unwrap_err();
+        assert!(
+            matches!(&errors[0], ValidationError::MissingParam { param, .. } if param == "assertions")
+        );
+    }
+
+    #[test]
+    fn test_duplicate_step_id() {
+        let plan = create_test_plan(vec![
+            create_http_step("get", "GET", "/a"),
+            create_http_step("other", "GET", "/b"),
+            create_http_step("get", "GET", "/c"),
+        ]);
+
+        let errors = validate_plan(&plan).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            ValidationError::DuplicateStepId { step_id, count: 2 } if step_id == "get"
+        ));
+    }
+
+    #[test]
+    fn test_dependency_warnings_detect_use_before_produce() {
+        let mut login = create_http_step("login", "POST", "/login");
+        login.extract = vec![serde_json::from_value(
+            json!({ "source": "body", "path": "$.token", "target": "token" }),
+        )
+        .unwrap()];
+        let mut me = create_http_step("me", "GET", "/me");
+        me.params["headers"] = json!({ "Authorization": "Bearer ${token}" });
+        let health = create_http_step("health", "GET", "/health");
+
+        // Sequencial: `login` vem antes de `me`, sem aviso de ordem.
+        let plan = create_test_plan(vec![login.clone(), me.clone(), health.clone()]);
+        assert!(dependency_warnings(&plan, false).is_empty());
+
+        // Paralelo: `me` não depende de `login`.
+        let warnings = dependency_warnings(&plan, true);
+        let bugs: Vec<_> = warnings.iter().filter(|w| w.is_ordering_bug()).collect();
+        assert_eq!(bugs.len(), 1);
+        assert!(matches!(
+            bugs[0],
+            ValidationWarning::UseBeforeProduce { step_id, producer, .. }
+                if step_id == "me" && producer == "login"
+        ));
+        assert_eq!(warnings.len(), 4); // 3 steps isolados + o bug de ordem
+
+        // Com depends_on, o aviso de ordem some; `health` continua isolado.
+        me.depends_on = vec!["login".to_string()];
+        let plan = create_test_plan(vec![me, login, health]);
+        assert_eq!(
+            dependency_warnings(&plan, true),
+            vec![ValidationWarning::IsolatedStep {
+                step_id: "health".to_string()
+            }]
+        );
+        // No sequencial, `login` agora vem depois de `me` na lista.
+        assert_eq!(dependency_warnings(&plan, false).len(), 1);
+    }
+
+    #[test]
+    fn test_unknown_template() {
+        let mut step = create_http_step("get", "GET", "/users");
+        step.template = Some("authed_json".to_string());
+        let plan = create_test_plan(vec![step]);
+
+        let errors = validate_plan(&plan).unwrap_err();
+        assert!(matches!(
+            &errors[0],
+            ValidationError::UnknownTemplate { template, .. } if template == "authed_json"
+        ));
+    }
+
+    #[test]
+    fn test_unknown_session() {
+        let mut step = create_http_step("get", "GET", "/users");
+        step.session = Some("buyer".to_string());
+        let mut plan = create_test_plan(vec![step.clone()]);
+
+        let errors = validate_plan(&plan).unwrap_err();
+        assert!(matches!(
+            &errors[0],
+            ValidationError::UnknownSession { session, .. } if session == "buyer"
+        ));
+
+        plan.config
+            .sessions
+            .insert("buyer".to_string(), Default::default());
+        assert!(validate_plan(&plan).is_ok());
+    }
+
+    #[test]
+    fn test_unknown_signer() {
+        let mut step = create_http_step("create", "POST", "/orders");
+        step.params["signer"] = serde_json::json!("partner");
+        let mut plan = create_test_plan(vec![step]);
+
+        let errors = validate_plan(&plan).unwrap_err();
+        assert!(matches!(
+            &errors[0],
+            ValidationError::UnknownSigner { signer, .. } if signer == "partner"
+        ));
+
+        plan.config.signers = serde_json::from_value(serde_json::json!({
+            "partner": { "type": "hmac", "secret": "s" }
+        }))
+        .unwrap();
+        assert!(validate_plan(&plan).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_recovery_policy() {
+        let policy = |strategy: &str, max_attempts: u32, backoff_ms: u64, factor: f64| {
+            let mut step = create_http_step("flaky", "GET", "/health");
+            step.recovery_policy = Some(crate::protocol::RecoveryPolicy {
+                strategy: strategy.to_string(),
+                max_attempts,
+                backoff_ms,
+                backoff_factor: factor,
+            });
+            validate_plan(&create_test_plan(vec![step]))
+        };
+        let reason = |result: ValidationResult| match result.unwrap_err().remove(0) {
+            ValidationError::InvalidRecoveryPolicy { reason, .. } => reason,
+            other => panic!("erro inesperado: {}", other),
+        };
+
+        assert!(reason(policy("retyr", 3, 100, 2.0)).contains("strategy 'retyr'"));
+        assert!(reason(policy("retry", 0, 100, 2.0)).contains("max_attempts"));
+        assert!(reason(policy("retry", 3, 100, 0.0)).contains("backoff_factor"));
+        // 1s × 10^3 = 1000s antes da 5ª tentativa.
+        assert!(reason(policy("retry", 5, 1000, 10.0)).contains("máximo 300000ms"));
+
+        assert!(policy("RETRY", 3, 500, 2.0).is_ok());
+        assert!(policy("ignore", 1, 0, 2.0).is_ok());
+    }
+
+    #[test]
+    fn test_unknown_actor() {
+        let mut step = create_http_step("delete", "DELETE", "/products/1");
+        step.actor = Some("customer".to_string());
+        let plan = create_test_plan(vec![step]);
+
+        let errors = validate_plan(&plan).unwrap_err();
+        assert!(matches!(
+            &errors[0],
+            ValidationError::UnknownActor { actor, .. } if actor == "customer"
+        ));
+    }
+
+    #[test]
+    fn test_params_checked_against_executor_schema() {
+        let mut typo = create_http_step("login", "POST", "/login");
+        typo.params["methd"] = json!("POST");
+        typo.params["auth"] =
+            json!({ "type": "basic", "username": "u", "password": "p", "realm": "x" });
+        let wait = Step {
+            id: "pause".to_string(),
+            action: "wait".to_string(),
+            params: json!({ "duration_ms": "fast" }),
+            ..Default::default()
+        };
+        let errors = validate_plan(&create_test_plan(vec![typo, wait])).unwrap_err();
+
+        let unknown: Vec<(&str, &str)> = errors
+            .iter()
+            .filter_map(|e| match e {
+                ValidationError::UnknownParam {
+                    param, accepted, ..
+                } => Some((param.as_str(), accepted.as_str())),
+                _ => None,
+            })
+            .collect();
+        assert!(unknown
+            .iter()
+            .any(|(p, accepted)| *p == "methd" && accepted.contains("method")));
+        assert!(unknown.contains(&("auth.realm", "password, type, username")));
+
+        let invalid = errors
+            .iter()
+            .map(ToString::to_string)
+            .find(|m| m.contains("'duration_ms'"))
+            .unwrap();
+        assert!(invalid.starts_with("Step 'pause': parâmetro 'duration_ms' inválido"));
+        assert!(invalid.contains("\"fast\""));
+        assert_eq!(errors.len(), 3);
+    }
+
+    #[test]
+    fn test_every_known_action_has_a_params_schema() {
+        for action in KNOWN_ACTIONS {
+            assert!(compiled_params_schema(action).is_some(), "{}", action);
+        }
+
+        // Presente com o tipo errado: InvalidParam no lugar de MissingParam.
+        let mut step = create_http_step("probe", "GET", "/");
+        step.action = "tcp_send".to_string();
+        step.params = json!({ "host": "db", "port": "5432" });
+        let errors = validate_plan(&create_test_plan(vec![step])).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(
+            matches!(&errors[0], ValidationError::InvalidParam { param, .. } if param == "port")
+        );
+
+        // `assert` sem params (null) é válido.
+        let assert_step = Step {
+            id: "check".to_string(),
+            action: "assert".to_string(),
+            assertions: vec![serde_json::from_value(
+                json!({ "type": "variable", "path": "x", "operator": "exists", "value": null }),
+            )
+            .unwrap()],
+            ..Default::default()
+        };
+        assert!(validate_plan(&create_test_plan(vec![assert_step])).is_ok());
+    }
+
     // ========================================================================
     // TESTES DE CICLOS NO DAG
     // ========================================================================
@@ -715,6 +1735,7 @@ mod tests {
                 assertions: vec![],
                 extract: vec![],
                 recovery_policy: None,
+                ..Default::default()
             },
             Step {
                 id: "B".to_string(),
@@ -725,6 +1746,7 @@ mod tests {
                 assertions: vec![],
                 extract: vec![],
                 recovery_policy: None,
+                ..Default::default()
             },
         ]);
 
@@ -749,6 +1771,7 @@ mod tests {
                 assertions: vec![],
                 extract: vec![],
                 recovery_policy: None,
+                ..Default::default()
             },
             Step {
                 id: "B".to_string(),
@@ -759,6 +1782,7 @@ mod tests {
                 assertions: vec![],
                 extract: vec![],
                 recovery_policy: None,
+                ..Default::default()
             },
             Step {
                 id: "C".to_string(),
@@ -769,6 +1793,7 @@ mod tests {
                 assertions: vec![],
                 extract: vec![],
                 recovery_policy: None,
+                ..Default::default()
             },
         ]);
 
@@ -798,6 +1823,7 @@ mod tests {
                 assertions: vec![],
                 extract: vec![],
                 recovery_policy: None,
+                ..Default::default()
             },
             Step {
                 id: "B".to_string(),
@@ -808,6 +1834,7 @@ mod tests {
                 assertions: vec![],
                 extract: vec![],
                 recovery_policy: None,
+                ..Default::default()
             },
             Step {
                 id: "C".to_string(),
@@ -818,6 +1845,7 @@ mod tests {
                 assertions: vec![],
                 extract: vec![],
                 recovery_policy: None,
+                ..Default::default()
             },
             Step {
                 id: "D".to_string(),
@@ -828,6 +1856,7 @@ mod tests {
                 assertions: vec![],
                 extract: vec![],
                 recovery_policy: None,
+                ..Default::default()
             },
         ]);
 
@@ -850,6 +1879,7 @@ mod tests {
                 assertions: vec![],
                 extract: vec![],
                 recovery_policy: None,
+                ..Default::default()
             },
             Step {
                 id: "B".to_string(),
@@ -860,6 +1890,7 @@ mod tests {
                 assertions: vec![],
                 extract: vec![],
                 recovery_policy: None,
+                ..Default::default()
             },
             Step {
                 id: "C".to_string(),
@@ -870,6 +1901,7 @@ mod tests {
                 assertions: vec![],
                 extract: vec![],
                 recovery_policy: None,
+                ..Default::default()
             },
             Step {
                 id: "D".to_string(),
@@ -880,6 +1912,7 @@ mod tests {
                 assertions: vec![],
                 extract: vec![],
                 recovery_policy: None,
+                ..Default::default()
             },
         ]);
 
@@ -890,4 +1923,53 @@ mod tests {
             .iter()
             .any(|e| matches!(e, ValidationError::CircularDependency { .. })));
     }
+
+    /// O schema sem anotações (`description`, `default`, `examples`) e com
+    /// números inteiros na mesma forma (`10000.0` == `10000`).
+    fn without_annotations(schema: &serde_json::Value) -> serde_json::Value {
+        match schema {
+            serde_json::Value::Number(n) if n.as_f64().is_some_and(|f| f.fract() == 0.0) => {
+                json!(n.as_f64().unwrap() as i64)
+            }
+            serde_json::Value::Object(fields) => fields
+                .iter()
+                .filter(|(key, _)| !matches!(key.as_str(), "description" | "default" | "examples"))
+                .map(|(key, value)| (key.clone(), without_annotations(value)))
+                .collect(),
+            serde_json::Value::Array(items) => items.iter().map(without_annotations).collect(),
+            other => other.clone(),
+        }
+    }
+
+    #[test]
+    fn test_utdl_schema_matches_executor_params() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../schemas/utdl.schema.json");
+        let utdl: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        let mut checked = 0;
+        for rule in utdl["definitions"]["Step"]["allOf"].as_array().unwrap() {
+            let Some(reference) = rule["then"]["properties"]["params"]["$ref"].as_str() else {
+                continue;
+            };
+            let name = reference.trim_start_matches("#/definitions/");
+            let documented = without_annotations(&utdl["definitions"][name]);
+            let action = &rule["if"]["properties"]["action"];
+            let actions = match action.get("const") {
+                Some(single) => vec![single.clone()],
+                None => action["enum"].as_array().unwrap().clone(),
+            };
+            for action in actions {
+                let action = action.as_str().unwrap();
+                let (schema, _) = &params_schemas()[action];
+                assert_eq!(
+                    without_annotations(schema),
+                    documented,
+                    "{}: schemas/utdl.schema.json diverge do executor",
+                    action
+                );
+                checked += 1;
+            }
+        }
+        assert!(checked >= 10);
+    }
 }
@@ -0,0 +1,116 @@
+//! # Módulo de Versão - `runner version`
+//!
+//! Informa a versão do Runner e o que ele suporta, para que o Brain
+//! confira a compatibilidade antes de despachar um plano.
+//!
+//! ## Para todos entenderem:
+//!
+//! O Brain gera planos UTDL; se ele usar uma action que este Runner não
+//! conhece, o plano falha na validação. Com `runner version --json`, o Brain
+//! pergunta antes: "qual `spec_version` você aceita? quais actions?".
+//!
+//! ## Saída (`--json`):
+//!
+//! ```json
+//! {
+//!   "version": "0.5.0",
+//!   "git_sha": "40b0ee1",
+//!   "spec_version": "0.1",
+//!   "report_version": "1.2.0",
+//!   "target": "x86_64-unknown-linux-gnu",
+//!   "features": ["self-update"],
+//!   "actions": ["http_request", "wait", "sleep", "..."]
+//! }
+//! ```
+//!
+//! `features` lista as features de compilação ativas (um binário compilado
+//! com `--no-default-features` não tem `self-update`).
+
+use serde::Serialize;
+
+use crate::protocol::REPORT_VERSION;
+use crate::validation::{KNOWN_ACTIONS, SUPPORTED_SPEC_VERSION};
+
+/// Versão e capacidades deste binário.
+#[derive(Debug, Serialize)]
+pub struct VersionInfo {
+    /// Versão do crate (`Cargo.toml`).
+    pub version: &'static str,
+
+    /// Commit do build (`unknown` fora de um repositório git).
+    pub git_sha: &'static str,
+
+    /// `spec_version` de UTDL aceita.
+    pub spec_version: &'static str,
+
+    /// Versão do formato do relatório gerado.
+    pub report_version: &'static str,
+
+    /// Target triple do binário.
+    pub target: &'static str,
+
+    /// Features de compilação ativas.
+    pub features: Vec<&'static str>,
+
+    /// Actions que o Runner executa.
+    pub actions: Vec<&'static str>,
+}
+
+impl VersionInfo {
+    /// Informações do binário em execução.
+    pub fn current() -> Self {
+        let mut features = Vec::new();
+        if cfg!(feature = "self-update") {
+            features.push("self-update");
+        }
+
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            git_sha: env!("RUNNER_GIT_SHA"),
+            spec_version: SUPPORTED_SPEC_VERSION,
+            report_version: REPORT_VERSION,
+            target: env!("RUNNER_TARGET"),
+            features,
+            actions: KNOWN_ACTIONS.to_vec(),
+        }
+    }
+
+    /// Texto legível de `runner version` (sem `--json`).
+    pub fn human(&self) -> String {
+        format!(
+            "runner {} ({})\nspec_version: {}\nreport_version: {}\ntarget: {}\nfeatures: {}",
+            self.version,
+            self.git_sha,
+            self.spec_version,
+            self.report_version,
+            self.target,
+            if self.features.is_empty() {
+                "-".to_string()
+            } else {
+                self.features.join(", ")
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_info_json() {
+        let info = VersionInfo::current();
+        let json = serde_json::to_value(&info).unwrap();
+
+        assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(json["spec_version"], SUPPORTED_SPEC_VERSION);
+        assert!(json["actions"]
+            .as_array()
+            .unwrap()
+            .contains(&"http_request".into()));
+        assert!(!info.git_sha.is_empty());
+        assert!(info
+            .human()
+            .starts_with(&format!("runner {}", info.version)));
+    }
+}
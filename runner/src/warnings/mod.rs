@@ -0,0 +1,309 @@
+//! # Módulo de Avisos - Regressões de Qualidade sem Reprovar a Execução
+//!
+//! Reúne os problemas de qualidade do plano observados durante um
+//! `execute` e os publica em `warnings` no relatório.
+//!
+//! ## Para todos entenderem:
+//!
+//! Um plano pode passar e ainda assim estar piorando: usa sintaxe antiga,
+//! engole falhas com `strategy: ignore` ou tem achados do linter que
+//! ninguém roda. Em vez de reprovar a execução, o Runner anota esses
+//! problemas no relatório, onde dashboards e revisores conseguem vê-los.
+//!
+//! ## Origens:
+//!
+//! | Código | Nome                | Quando aparece                                        |
+//! |--------|---------------------|-------------------------------------------------------|
+//! | L0xx   | (regra do linter)   | Achado de `runner lint` (sempre como aviso)           |
+//! | W001   | `legacy-env-syntax` | Plano usa `${ENV_NOME}` em vez de `${env:NOME}`       |
+//! | W002   | `deprecated-field`  | Plano usa um nome mantido só por compatibilidade      |
+//! | W003   | `ignored-failure`   | Step falhou e a falha foi descartada por `ignore`     |
+//! | W004   | `legacy-interpolation` | Plano usa `{{var}}` em vez de `${var}`             |
+//!
+//! ## Exemplo no relatório:
+//!
+//! ```json
+//! "warnings": [
+//!   { "code": "W001", "name": "legacy-env-syntax", "step_id": "login",
+//!     "message": "sintaxe legada de ambiente: ${ENV_API_KEY} → ${env:API_KEY}" }
+//! ]
+//! ```
+
+use serde_json::Value;
+use std::collections::{BTreeSet, HashSet};
+
+use crate::context::LEGACY_PLACEHOLDER_RE;
+use crate::lint;
+use crate::protocol::{Plan, RunWarning, Step, StepResult};
+
+/// Actions mantidas apenas por compatibilidade → nome atual.
+const DEPRECATED_ACTIONS: &[(&str, &str)] = &[("sleep", "wait")];
+
+// ============================================================================
+// FUNÇÕES PRINCIPAIS
+// ============================================================================
+
+/// Avisos detectáveis antes de executar: linter, sintaxe legada e campos
+/// obsoletos.
+pub fn plan_warnings(plan: &Plan) -> Vec<RunWarning> {
+    let mut warnings: Vec<RunWarning> = lint::lint_plan(plan, &HashSet::new())
+        .into_iter()
+        .map(|finding| RunWarning {
+            code: finding.rule.code().to_string(),
+            name: finding.rule.name().to_string(),
+            step_id: finding.step_id,
+            message: finding.message,
+        })
+        .collect();
+
+    let config = serde_json::to_value(&plan.config).unwrap_or(Value::Null);
+    if let Some(warning) = legacy_env_warning(None, &config) {
+        warnings.push(warning);
+    }
+    if let Some(warning) = legacy_interpolation_warning(None, &config) {
+        warnings.push(warning);
+    }
+    for step in &plan.steps {
+        warnings.extend(step_warnings(step));
+    }
+
+    warnings
+}
+
+/// Avisos produzidos pela execução: falhas descartadas por `ignore`.
+pub fn result_warnings(results: &[StepResult]) -> Vec<RunWarning> {
+    results
+        .iter()
+        .filter_map(|result| {
+            let error = result.ignored_error.as_ref()?;
+            Some(RunWarning {
+                code: "W003".to_string(),
+                name: "ignored-failure".to_string(),
+                step_id: Some(result.step_id.clone()),
+                message: format!("falha ignorada por recovery_policy: {}", error),
+            })
+        })
+        .collect()
+}
+
+// ============================================================================
+// AUXILIARES
+// ============================================================================
+
+fn step_warnings(step: &Step) -> Vec<RunWarning> {
+    let mut warnings = Vec::new();
+
+    if let Some((_, current)) = DEPRECATED_ACTIONS
+        .iter()
+        .find(|(legacy, _)| *legacy == step.action)
+    {
+        warnings.push(RunWarning {
+            code: "W002".to_string(),
+            name: "deprecated-field".to_string(),
+            step_id: Some(step.id.clone()),
+            message: format!(
+                "action '{}' é mantida por compatibilidade; use '{}'",
+                step.action, current
+            ),
+        });
+    }
+
+    let step_value = serde_json::to_value(step).unwrap_or(Value::Null);
+    if let Some(warning) = legacy_env_warning(Some(&step.id), &step_value) {
+        warnings.push(warning);
+    }
+    if let Some(warning) = legacy_interpolation_warning(Some(&step.id), &step_value) {
+        warnings.push(warning);
+    }
+
+    warnings
+}
+
+/// Um aviso W001 listando cada `${ENV_*}` encontrado em `value`.
+fn legacy_env_warning(step_id: Option<&str>, value: &Value) -> Option<RunWarning> {
+    let mut names = BTreeSet::new();
+    collect_legacy_env(value, &mut names);
+    if names.is_empty() {
+        return None;
+    }
+
+    let message = names
+        .iter()
+        .map(|name| format!("${{ENV_{0}}} → ${{env:{0}}}", name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some(RunWarning {
+        code: "W001".to_string(),
+        name: "legacy-env-syntax".to_string(),
+        step_id: step_id.map(str::to_string),
+        message: format!("sintaxe legada de ambiente: {}", message),
+    })
+}
+
+/// Um aviso W004 com a troca sugerida para cada `{{nome}}` em `value`.
+///
+/// O runtime já resolve `{{nome}}` quando a variável existe; o aviso é o
+/// auto-fix para o plano (ex: gerado com o delimitador errado).
+fn legacy_interpolation_warning(step_id: Option<&str>, value: &Value) -> Option<RunWarning> {
+    let mut names = BTreeSet::new();
+    collect_strings(value, &mut |text| {
+        for capture in LEGACY_PLACEHOLDER_RE.captures_iter(text) {
+            names.insert(capture[1].to_string());
+        }
+    });
+    if names.is_empty() {
+        return None;
+    }
+
+    let message = names
+        .iter()
+        .map(|name| format!("{{{{{0}}}}} → ${{{0}}}", name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some(RunWarning {
+        code: "W004".to_string(),
+        name: "legacy-interpolation".to_string(),
+        step_id: step_id.map(str::to_string),
+        message: format!("sintaxe legada de interpolação: {}", message),
+    })
+}
+
+/// Chama `visit` para cada string dentro de `value`.
+fn collect_strings(value: &Value, visit: &mut impl FnMut(&str)) {
+    match value {
+        Value::String(text) => visit(text),
+        Value::Array(items) => items.iter().for_each(|v| collect_strings(v, visit)),
+        Value::Object(map) => map.values().for_each(|v| collect_strings(v, visit)),
+        _ => {}
+    }
+}
+
+/// Busca recursiva por placeholders `${ENV_NOME}` em strings.
+fn collect_legacy_env(value: &Value, names: &mut BTreeSet<String>) {
+    match value {
+        Value::String(text) => {
+            let mut rest = text.as_str();
+            while let Some(start) = rest.find("${ENV_") {
+                rest = &rest[start + "${ENV_".len()..];
+                if let Some(end) = rest.find('}') {
+                    names.insert(rest[..end].to_string());
+                    rest = &rest[end..];
+                }
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|v| collect_legacy_env(v, names)),
+        Value::Object(map) => map.values().for_each(|v| collect_legacy_env(v, names)),
+        _ => {}
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn plan(config: Value, steps: Value) -> Plan {
+        serde_json::from_value(json!({
+            "spec_version": "0.1",
+            "meta": { "id": "p", "name": "p", "created_at": "2024-01-01" },
+            "config": config,
+            "steps": steps
+        }))
+        .unwrap()
+    }
+
+    fn codes(warnings: &[RunWarning]) -> Vec<(&str, Option<&str>)> {
+        warnings
+            .iter()
+            .map(|w| (w.code.as_str(), w.step_id.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn test_legacy_env_and_deprecated_action() {
+        let plan = plan(
+            json!({ "base_url": "http://api", "timeout_ms": 5000,
+                    "variables": { "key": "${ENV_API_KEY}" } }),
+            json!([
+                { "id": "pause", "action": "sleep", "params": { "duration_ms": 1 } },
+                { "id": "get", "action": "http_request",
+                  "params": { "method": "GET", "path": "/${ENV_TENANT}/${env:REGION}",
+                              "headers": { "X-Token": "${ENV_TOKEN}" } } }
+            ]),
+        );
+
+        let warnings = plan_warnings(&plan);
+        assert_eq!(
+            codes(&warnings),
+            vec![
+                ("W001", None),
+                ("W002", Some("pause")),
+                ("W001", Some("get"))
+            ]
+        );
+        assert!(warnings[0]
+            .message
+            .contains("${ENV_API_KEY} → ${env:API_KEY}"));
+        assert!(warnings[2].message.contains("ENV_TENANT"));
+        assert!(warnings[2].message.contains("ENV_TOKEN"));
+        assert!(!warnings[2].message.contains("REGION"));
+    }
+
+    #[test]
+    fn test_legacy_interpolation_suggests_fix() {
+        let plan = plan(
+            json!({ "base_url": "http://api", "timeout_ms": 5000 }),
+            json!([
+                { "id": "get", "action": "http_request",
+                  "params": { "method": "GET", "path": "/users/{{ user_id }}",
+                              "headers": { "X-Token": "{{token}}" } },
+                  "assertions": [{ "type": "status_code", "operator": "eq", "value": 200 }] }
+            ]),
+        );
+
+        let warnings = plan_warnings(&plan);
+        assert_eq!(codes(&warnings), vec![("W004", Some("get"))]);
+        assert_eq!(
+            warnings[0].message,
+            "sintaxe legada de interpolação: {{token}} → ${token}, {{user_id}} → ${user_id}"
+        );
+    }
+
+    #[test]
+    fn test_lint_findings_become_warnings() {
+        let plan = plan(
+            json!({ "base_url": "http://api", "timeout_ms": 5000 }),
+            json!([
+                { "id": "create", "action": "http_request",
+                  "params": { "method": "POST", "path": "/users" } }
+            ]),
+        );
+
+        let warnings = plan_warnings(&plan);
+        assert_eq!(codes(&warnings), vec![("L003", Some("create"))]);
+        assert_eq!(warnings[0].name, "missing-assertions");
+    }
+
+    #[test]
+    fn test_ignored_failures() {
+        let results = vec![
+            StepResult {
+                step_id: "flaky".to_string(),
+                ignored_error: Some("status_code esperado 200, recebido 503".to_string()),
+                ..Default::default()
+            },
+            StepResult {
+                step_id: "ok".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let warnings = result_warnings(&results);
+        assert_eq!(codes(&warnings), vec![("W003", Some("flaky"))]);
+        assert!(warnings[0].message.contains("503"));
+    }
+}
@@ -77,6 +77,16 @@ function StepResultItem({ step }: { step: StepResult }) {
         {step.error && (
           <p className="text-xs text-red-500 mt-1">{step.error}</p>
         )}
+        {step.assertion_failures?.map((failure, i) => (
+          <p key={i} className="text-xs text-red-500 mt-1">
+            <code>
+              {failure.type}
+              {failure.path ? ` ${failure.path}` : ""} {failure.operator}{" "}
+              {JSON.stringify(failure.expected)}
+            </code>
+            {" "}→ actual: {failure.actual === undefined ? "<missing>" : JSON.stringify(failure.actual)}
+          </p>
+        ))}
       </div>
       <div className="text-xs text-muted-foreground flex items-center gap-1">
         <Clock className="h-3 w-3" />
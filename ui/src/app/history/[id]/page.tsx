@@ -156,6 +156,27 @@ function StepResultCard({ step, index }: { step: StepResult; index: number }) {
             </div>
           )}
 
+          {step.assertion_failures && step.assertion_failures.length > 0 && (
+            <div className="space-y-2">
+              <h4 className="text-sm font-medium text-red-600">Failed assertions</h4>
+              <div className="space-y-1">
+                {step.assertion_failures.map((failure, i) => (
+                  <div key={i} className="flex items-center gap-2 text-sm">
+                    <XCircle className="h-3.5 w-3.5 text-red-500" />
+                    <code className="text-xs bg-muted px-1.5 py-0.5 rounded">
+                      {failure.type}
+                      {failure.path ? ` ${failure.path}` : ""} {failure.operator}{" "}
+                      {JSON.stringify(failure.expected)}
+                    </code>
+                    <span className="text-xs text-red-500">
+                      actual: {failure.actual === undefined ? "<missing>" : JSON.stringify(failure.actual)}
+                    </span>
+                  </div>
+                ))}
+              </div>
+            </div>
+          )}
+
           {step.extractions && Object.keys(step.extractions).length > 0 && (
             <div className="space-y-2">
               <h4 className="text-sm font-medium">Extractions</h4>
@@ -200,6 +200,7 @@ export interface StepResult {
   error?: string | null
   http_details?: HttpDetails | null
   assertions_results?: AssertionResult[]
+  assertion_failures?: AssertionFailure[]
   extractions?: Record<string, unknown>
 }
 
@@ -219,6 +220,15 @@ export interface AssertionResult {
   path?: string
 }
 
+export interface AssertionFailure {
+  type: string
+  operator: string
+  path?: string | null
+  expected: unknown
+  actual?: unknown
+  message: string
+}
+
 // History
 export interface HistoryRecord {
   execution_id: string